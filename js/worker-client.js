@@ -0,0 +1,57 @@
+// Main-thread side of the spawn-wasm-ecdsa worker protocol (see src/worker.rs).
+//
+//   const verifier = new EcdsaWorker(new Worker(new URL('./worker.js', import.meta.url), { type: 'module' }));
+//   const valid = await verifier.verify(message, signature, publicKey);
+//
+// Binary arguments are copied by default. List their buffers in `transfer` to move them to
+// the worker instead; they are then detached (empty) on this side.
+
+export class EcdsaWorker {
+  constructor(worker) {
+    this.worker = worker;
+    this.nextId = 0;
+    this.pending = new Map();
+    worker.onmessage = (event) => {
+      const { id, ok, result, error } = event.data;
+      const request = this.pending.get(id);
+      if (!request) return;
+      this.pending.delete(id);
+      if (ok) request.resolve(result);
+      else request.reject(new Error(error));
+    };
+    worker.onerror = (event) => {
+      for (const request of this.pending.values()) request.reject(event);
+      this.pending.clear();
+    };
+  }
+
+  request(op, params, transfer = []) {
+    const id = this.nextId++;
+    return new Promise((resolve, reject) => {
+      this.pending.set(id, { resolve, reject });
+      this.worker.postMessage({ id, op, ...params }, transfer);
+    });
+  }
+
+  // Resolves to a boolean. `message` is a string or bytes; `signature` and `publicKey` are
+  // hex strings or bytes.
+  verify(message, signature, publicKey, hashAlgorithm, transfer) {
+    return this.request('verify', { message, signature, publicKey, hashAlgorithm }, transfer);
+  }
+
+  // Resolves to an array of { valid, error } objects, as ECDSAVerifier.verify_batch does.
+  verifyBatch(items, hashAlgorithm) {
+    return this.request('verifyBatch', { items, hashAlgorithm });
+  }
+
+  // Resolves to the digest as a Uint8Array.
+  hash(data, hashAlgorithm, transfer) {
+    return this.request('hash', { data, hashAlgorithm }, transfer);
+  }
+
+  terminate() {
+    this.worker.terminate();
+    for (const request of this.pending.values()) request.reject(new Error('Worker terminated'));
+    this.pending.clear();
+  }
+}
@@ -0,0 +1,17 @@
+// Web Worker entry point for spawn-wasm-ecdsa.
+//
+// Start it from the main thread with
+//   new Worker(new URL('./worker.js', import.meta.url), { type: 'module' })
+// and drive it with EcdsaWorker from worker-client.js. The import below assumes the
+// wasm-pack output (`wasm-pack build --target web`) sits in ../pkg.
+import init, { handle_worker_message } from '../pkg/spawn_wasm_ecdsa.js';
+
+const ready = init();
+
+self.onmessage = async (event) => {
+  await ready;
+  const response = handle_worker_message(event.data);
+  // Digests are fresh copies, so their buffers can be handed over instead of cloned
+  const transfer = response.result instanceof Uint8Array ? [response.result.buffer] : [];
+  self.postMessage(response, transfer);
+};
@@ -0,0 +1,398 @@
+//! Schnorr and ECDSA adaptor signatures over secp256k1, for atomic swaps and DLCs.
+//!
+//! A pre-signature is encrypted to an adaptor point `T = t*G`: anyone can check it against
+//! `T`, whoever knows `t` can complete it into an ordinary signature, and the completed
+//! signature together with the pre-signature reveals `t`.
+//!
+//! Schnorr pre-signatures are 65 bytes: the compressed nonce `R = k*G + T` and
+//! `s' = k + e*x`, completed to the BIP-340 signature (R.x, s' + t), negated as BIP-340
+//! requires when `R` has an odd y. ECDSA pre-signatures are 162 bytes, laid out as in the DLC
+//! specification: `R = k*T`, `R' = k*G`, `s' = k^-1 * (z + r*x)` with `r = R.x`, and a DLEQ
+//! proof `(e, s)` that `R` and `R'` share the discrete log `k`.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::elliptic_curve::{AffineXCoordinate, IsHigh};
+use k256::{FieldBytes, ProjectivePoint, Scalar, U256};
+
+use crate::schnorr::{decode_point, has_even_y, lift_x, parse_x_only, tagged_hash};
+use crate::{digest_to_scalar, keys, ECDSAError};
+
+const POINT_LENGTH: usize = 33;
+const SCHNORR_PRE_SIGNATURE_LENGTH: usize = POINT_LENGTH + 32;
+const ECDSA_PRE_SIGNATURE_LENGTH: usize = 2 * POINT_LENGTH + 3 * 32;
+
+fn parse_scalar(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes)))
+}
+
+fn parse_hex(value: &str, error: ECDSAError) -> Result<Vec<u8>, ECDSAError> {
+    hex::decode(value).map_err(|_| error)
+}
+
+fn parse_adaptor_point(adaptor_point: &str) -> Result<ProjectivePoint, ECDSAError> {
+    decode_point(&parse_hex(adaptor_point, ECDSAError::InvalidPublicKey)?).ok_or(ECDSAError::InvalidPublicKey)
+}
+
+fn parse_secret(secret: &str) -> Result<Scalar, ECDSAError> {
+    let secret = parse_hex(secret, ECDSAError::InvalidPrivateKey)?;
+    parse_scalar(&secret).filter(|secret| !bool::from(secret.is_zero())).ok_or(ECDSAError::InvalidPrivateKey)
+}
+
+fn x_coordinate(point: &ProjectivePoint) -> FieldBytes {
+    point.to_affine().x()
+}
+
+/// The `1` or `-1` that gives a nonce point an even y.
+fn nonce_sign(point: &ProjectivePoint) -> Scalar {
+    if has_even_y(&point.to_affine()) { Scalar::ONE } else { -Scalar::ONE }
+}
+
+/// A parsed Schnorr pre-signature.
+struct SchnorrPreSignature {
+    nonce: ProjectivePoint,
+    s: Scalar,
+}
+
+impl SchnorrPreSignature {
+    fn parse(pre_signature: &str) -> Result<SchnorrPreSignature, ECDSAError> {
+        let bytes = parse_hex(pre_signature, ECDSAError::InvalidAdaptorSignature)?;
+        if bytes.len() != SCHNORR_PRE_SIGNATURE_LENGTH {
+            return Err(ECDSAError::InvalidAdaptorSignature);
+        }
+        let (nonce, s) = bytes.split_at(POINT_LENGTH);
+        Ok(SchnorrPreSignature {
+            nonce: decode_point(nonce).ok_or(ECDSAError::InvalidAdaptorSignature)?,
+            s: parse_scalar(s).ok_or(ECDSAError::InvalidAdaptorSignature)?,
+        })
+    }
+}
+
+/// Verifies a Schnorr pre-signature, which completes to a BIP-340 signature of `message`
+/// once the adaptor secret is known.
+///
+/// # Arguments:
+/// * `pre_signature` - The 65-byte pre-signature, provided as a hex string.
+/// * `message` - The message, provided as a hex string and signed as is, as in BIP-340.
+/// * `public_key` - The signer's 32-byte x-only public key, provided as a hex string.
+/// * `adaptor_point` - The 33-byte compressed adaptor point, provided as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the pre-signature is valid.
+///
+/// # Errors:
+/// * Returns an error if an input is malformed.
+#[wasm_bindgen]
+pub fn verify_schnorr_adaptor(pre_signature: &str, message: &str, public_key: &str, adaptor_point: &str) -> Result<bool, ECDSAError> {
+    let pre_signature = SchnorrPreSignature::parse(pre_signature)?;
+    let message = parse_hex(message, ECDSAError::InvalidDigestLength)?;
+    let public_key = parse_x_only(&parse_hex(public_key, ECDSAError::InvalidPublicKey)?)?;
+    let adaptor_point = parse_adaptor_point(adaptor_point)?;
+
+    let nonce = &pre_signature.nonce;
+    let e = tagged_hash("BIP0340/challenge", &[&x_coordinate(nonce), &public_key, &message]);
+    let e = <Scalar as Reduce<U256>>::from_be_bytes_reduced(e.into());
+    // s'*G = g*(R - T) + e*P, where g makes the final nonce's y even
+    let expected = (*nonce - adaptor_point) * nonce_sign(nonce) + ProjectivePoint::from(lift_x(&public_key)?) * e;
+    Ok(ProjectivePoint::GENERATOR * pre_signature.s == expected)
+}
+
+/// Completes a Schnorr pre-signature with the adaptor secret.
+///
+/// # Arguments:
+/// * `pre_signature` - The 65-byte pre-signature, provided as a hex string.
+/// * `secret` - The 32-byte adaptor secret `t`, provided as a hex string.
+///
+/// # Returns:
+/// * The 64-byte BIP-340 signature, as a hex string.
+#[wasm_bindgen]
+pub fn complete_schnorr_adaptor(pre_signature: &str, secret: &str) -> Result<String, ECDSAError> {
+    let pre_signature = SchnorrPreSignature::parse(pre_signature)?;
+    let s = pre_signature.s + nonce_sign(&pre_signature.nonce) * parse_secret(secret)?;
+    Ok(hex::encode([x_coordinate(&pre_signature.nonce).as_slice(), &s.to_bytes()].concat()))
+}
+
+/// Recovers the adaptor secret from a pre-signature and the signature completed from it.
+///
+/// # Arguments:
+/// * `pre_signature` - The 65-byte pre-signature, provided as a hex string.
+/// * `signature` - The 64-byte BIP-340 signature, provided as a hex string.
+/// * `adaptor_point` - The 33-byte compressed adaptor point, provided as a hex string.
+///
+/// # Returns:
+/// * The 32-byte adaptor secret, as a hex string.
+///
+/// # Errors:
+/// * `AdaptorSecretMismatch` if the signature was not completed from the pre-signature.
+#[wasm_bindgen]
+pub fn extract_schnorr_adaptor_secret(pre_signature: &str, signature: &str, adaptor_point: &str) -> Result<String, ECDSAError> {
+    let pre_signature = SchnorrPreSignature::parse(pre_signature)?;
+    let signature = parse_hex(signature, ECDSAError::InvalidSignatureFormat)?;
+    if signature.len() != 64 {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    let adaptor_point = parse_adaptor_point(adaptor_point)?;
+    let (r, s) = signature.split_at(32);
+    let s = parse_scalar(s).ok_or(ECDSAError::InvalidSignatureFormat)?;
+    if r != x_coordinate(&pre_signature.nonce).as_slice() {
+        return Err(ECDSAError::AdaptorSecretMismatch);
+    }
+    let secret = (s - pre_signature.s) * nonce_sign(&pre_signature.nonce);
+    if ProjectivePoint::GENERATOR * secret != adaptor_point {
+        return Err(ECDSAError::AdaptorSecretMismatch);
+    }
+    Ok(hex::encode(secret.to_bytes()))
+}
+
+/// A parsed ECDSA pre-signature.
+struct EcdsaPreSignature {
+    /// `k*T`, whose x-coordinate is the final signature's `r`.
+    nonce: ProjectivePoint,
+    /// `k*G`.
+    base_nonce: ProjectivePoint,
+    s: Scalar,
+    proof: (Scalar, Scalar),
+}
+
+impl EcdsaPreSignature {
+    fn parse(pre_signature: &str) -> Result<EcdsaPreSignature, ECDSAError> {
+        let bytes = parse_hex(pre_signature, ECDSAError::InvalidAdaptorSignature)?;
+        if bytes.len() != ECDSA_PRE_SIGNATURE_LENGTH {
+            return Err(ECDSAError::InvalidAdaptorSignature);
+        }
+        let (points, scalars) = bytes.split_at(2 * POINT_LENGTH);
+        let scalar = |i: usize| parse_scalar(&scalars[32 * i..32 * (i + 1)]);
+        (|| {
+            Some(EcdsaPreSignature {
+                nonce: decode_point(&points[..POINT_LENGTH])?,
+                base_nonce: decode_point(&points[POINT_LENGTH..])?,
+                s: scalar(0).filter(|s| !bool::from(s.is_zero()))?,
+                proof: (scalar(1)?, scalar(2)?),
+            })
+        })()
+        .ok_or(ECDSAError::InvalidAdaptorSignature)
+    }
+
+    /// `r`: the nonce's x-coordinate reduced modulo the group order.
+    fn r(&self) -> Scalar {
+        <Scalar as Reduce<U256>>::from_be_bytes_reduced(x_coordinate(&self.nonce))
+    }
+}
+
+/// The DLEQ challenge binding `X = k*G`, `Y`, `Z = k*Y` and the prover's commitments.
+fn dleq_challenge(points: [&ProjectivePoint; 5]) -> Scalar {
+    let encoded = points.map(|point| point.to_affine().to_bytes());
+    let parts: Vec<&[u8]> = encoded.iter().map(|point| point.as_slice()).collect();
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(tagged_hash("DLEQ", &parts).into())
+}
+
+/// Checks a proof `(e, s)` that `log_G(X) == log_Y(Z)`.
+fn verify_dleq(proof: &(Scalar, Scalar), x: &ProjectivePoint, y: &ProjectivePoint, z: &ProjectivePoint) -> bool {
+    let (e, s) = proof;
+    let a1 = ProjectivePoint::GENERATOR * s - *x * e;
+    let a2 = *y * s - *z * e;
+    dleq_challenge([x, y, z, &a1, &a2]) == *e
+}
+
+/// Verifies an ECDSA pre-signature, which completes to a signature of `digest` once the
+/// adaptor secret is known.
+///
+/// # Arguments:
+/// * `pre_signature` - The 162-byte pre-signature, provided as a hex string.
+/// * `digest` - The 32-byte message digest, provided as a hex string.
+/// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+/// * `adaptor_point` - The 33-byte compressed adaptor point, provided as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the pre-signature and its DLEQ proof are valid.
+///
+/// # Errors:
+/// * Returns an error if an input is malformed.
+#[wasm_bindgen]
+pub fn verify_ecdsa_adaptor(pre_signature: &str, digest: &str, public_key: &str, adaptor_point: &str) -> Result<bool, ECDSAError> {
+    let pre_signature = EcdsaPreSignature::parse(pre_signature)?;
+    let digest = parse_hex(digest, ECDSAError::InvalidDigestLength)?;
+    if digest.len() != 32 {
+        return Err(ECDSAError::InvalidDigestLength);
+    }
+    let public_key = k256::PublicKey::from(&keys::parse_public_key_hex(public_key)?).to_projective();
+    let adaptor_point = parse_adaptor_point(adaptor_point)?;
+
+    if !verify_dleq(&pre_signature.proof, &pre_signature.base_nonce, &adaptor_point, &pre_signature.nonce) {
+        return Ok(false);
+    }
+    // R' = s'^-1 * (z*G + r*P)
+    let s_inverse = pre_signature.s.invert().unwrap();
+    let expected = (ProjectivePoint::GENERATOR * digest_to_scalar(&digest) + public_key * pre_signature.r()) * s_inverse;
+    Ok(expected == pre_signature.base_nonce)
+}
+
+/// Completes an ECDSA pre-signature with the adaptor secret.
+///
+/// # Arguments:
+/// * `pre_signature` - The 162-byte pre-signature, provided as a hex string.
+/// * `secret` - The 32-byte adaptor secret `t`, provided as a hex string.
+///
+/// # Returns:
+/// * The 64-byte compact signature (r || s) with a low `s`, as a hex string.
+#[wasm_bindgen]
+pub fn complete_ecdsa_adaptor(pre_signature: &str, secret: &str) -> Result<String, ECDSAError> {
+    let pre_signature = EcdsaPreSignature::parse(pre_signature)?;
+    let s = pre_signature.s * parse_secret(secret)?.invert().unwrap();
+    let s = if bool::from(s.is_high()) { -s } else { s };
+    Ok(hex::encode([pre_signature.r().to_bytes(), s.to_bytes()].concat()))
+}
+
+/// Recovers the adaptor secret from a pre-signature and the signature completed from it.
+///
+/// # Arguments:
+/// * `pre_signature` - The 162-byte pre-signature, provided as a hex string.
+/// * `signature` - The 64-byte compact signature (r || s), provided as a hex string.
+/// * `adaptor_point` - The 33-byte compressed adaptor point, provided as a hex string.
+///
+/// # Returns:
+/// * The 32-byte adaptor secret, as a hex string.
+///
+/// # Errors:
+/// * `AdaptorSecretMismatch` if the signature was not completed from the pre-signature.
+#[wasm_bindgen]
+pub fn extract_ecdsa_adaptor_secret(pre_signature: &str, signature: &str, adaptor_point: &str) -> Result<String, ECDSAError> {
+    let pre_signature = EcdsaPreSignature::parse(pre_signature)?;
+    let signature = parse_hex(signature, ECDSAError::InvalidSignatureFormat)?;
+    if signature.len() != 64 {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    let adaptor_point = parse_adaptor_point(adaptor_point)?;
+    let (r, s) = signature.split_at(32);
+    let s = parse_scalar(s).filter(|s| !bool::from(s.is_zero())).ok_or(ECDSAError::InvalidSignatureFormat)?;
+    if parse_scalar(r) != Some(pre_signature.r()) {
+        return Err(ECDSAError::AdaptorSecretMismatch);
+    }
+    // t = s' / s, up to the sign lost by low-s normalization
+    let secret = pre_signature.s * s.invert().unwrap();
+    [secret, -secret]
+        .into_iter()
+        .find(|secret| ProjectivePoint::GENERATOR * secret == adaptor_point)
+        .map(|secret| hex::encode(secret.to_bytes()))
+        .ok_or(ECDSAError::AdaptorSecretMismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::Signature;
+    use sha2::{Digest, Sha256};
+
+    use crate::schnorr::verify_schnorr;
+    use crate::verify_digest;
+
+    fn scalar(seed: &str) -> Scalar {
+        <Scalar as Reduce<U256>>::from_be_bytes_reduced(Sha256::digest(seed.as_bytes()))
+    }
+
+    fn point_hex(point: ProjectivePoint) -> String {
+        hex::encode(point.to_affine().to_bytes())
+    }
+
+    fn adaptor() -> (Scalar, String) {
+        let secret = scalar("adaptor secret");
+        (secret, point_hex(ProjectivePoint::GENERATOR * secret))
+    }
+
+    /// Pre-signs a BIP-340 message: `R = k*G + T`, `s' = k + e*x`, with signs adjusted so
+    /// the final nonce and the key have even y.
+    fn schnorr_pre_sign(secret_key: Scalar, message: &[u8], adaptor_point: &ProjectivePoint, seed: &str) -> (String, [u8; 32]) {
+        let key_point = ProjectivePoint::GENERATOR * secret_key;
+        let secret_key = secret_key * nonce_sign(&key_point);
+        let public_key: [u8; 32] = x_coordinate(&key_point).into();
+        let k = scalar(seed);
+        let nonce = ProjectivePoint::GENERATOR * k + adaptor_point;
+        let k = k * nonce_sign(&nonce);
+        let e = tagged_hash("BIP0340/challenge", &[&x_coordinate(&nonce), &public_key, message]);
+        let s = k + <Scalar as Reduce<U256>>::from_be_bytes_reduced(e.into()) * secret_key;
+        (point_hex(nonce) + &hex::encode(s.to_bytes()), public_key)
+    }
+
+    /// Pre-signs an ECDSA digest with a DLEQ proof.
+    fn ecdsa_pre_sign(secret_key: Scalar, digest: &[u8], adaptor_point: &ProjectivePoint) -> String {
+        let k = scalar("ecdsa nonce");
+        let (nonce, base_nonce) = (*adaptor_point * k, ProjectivePoint::GENERATOR * k);
+        let r = <Scalar as Reduce<U256>>::from_be_bytes_reduced(x_coordinate(&nonce));
+        let s = k.invert().unwrap() * (digest_to_scalar(digest) + r * secret_key);
+
+        let a = scalar("dleq nonce");
+        let (a1, a2) = (ProjectivePoint::GENERATOR * a, *adaptor_point * a);
+        let e = dleq_challenge([&base_nonce, adaptor_point, &nonce, &a1, &a2]);
+        let proof_s = a + e * k;
+        [point_hex(nonce), point_hex(base_nonce), hex::encode(s.to_bytes()), hex::encode(e.to_bytes()), hex::encode(proof_s.to_bytes())].concat()
+    }
+
+    #[test]
+    fn test_schnorr_adaptor_round_trip() {
+        let (secret, adaptor_point) = adaptor();
+        let message = Sha256::digest(b"swap");
+        // Different nonces give both parities of R
+        for seed in ["nonce 1", "nonce 2", "nonce 3", "nonce 4"] {
+            let (pre_signature, public_key) = schnorr_pre_sign(scalar("alice"), &message, &parse_adaptor_point(&adaptor_point).unwrap(), seed);
+            let public_key_hex = hex::encode(public_key);
+            assert!(verify_schnorr_adaptor(&pre_signature, &hex::encode(message), &public_key_hex, &adaptor_point).unwrap());
+            assert!(!verify_schnorr_adaptor(&pre_signature, "00", &public_key_hex, &adaptor_point).unwrap());
+
+            let signature = complete_schnorr_adaptor(&pre_signature, &hex::encode(secret.to_bytes())).unwrap();
+            assert!(verify_schnorr(&public_key, &message, &hex::decode(&signature).unwrap()).unwrap(), "{seed}");
+            let extracted = extract_schnorr_adaptor_secret(&pre_signature, &signature, &adaptor_point).unwrap();
+            assert_eq!(extracted, hex::encode(secret.to_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_adaptor_round_trip() {
+        let (secret, adaptor_point) = adaptor();
+        let secret_key = scalar("bob");
+        let public_key = point_hex(ProjectivePoint::GENERATOR * secret_key);
+        let digest = Sha256::digest(b"swap");
+        let pre_signature = ecdsa_pre_sign(secret_key, &digest, &parse_adaptor_point(&adaptor_point).unwrap());
+        assert!(verify_ecdsa_adaptor(&pre_signature, &hex::encode(digest), &public_key, &adaptor_point).unwrap());
+
+        let signature = complete_ecdsa_adaptor(&pre_signature, &hex::encode(secret.to_bytes())).unwrap();
+        let parsed = Signature::try_from(hex::decode(&signature).unwrap().as_slice()).unwrap();
+        assert!(verify_digest(&keys::parse_public_key_hex(&public_key).unwrap(), &digest, &parsed));
+        let extracted = extract_ecdsa_adaptor_secret(&pre_signature, &signature, &adaptor_point).unwrap();
+        assert_eq!(extracted, hex::encode(secret.to_bytes()));
+    }
+
+    #[test]
+    fn test_rejects_wrong_adaptor_point() {
+        let (_, adaptor_point) = adaptor();
+        let other_point = point_hex(ProjectivePoint::GENERATOR * scalar("other"));
+        let secret_key = scalar("bob");
+        let public_key = point_hex(ProjectivePoint::GENERATOR * secret_key);
+        let digest = Sha256::digest(b"swap");
+
+        let pre_signature = ecdsa_pre_sign(secret_key, &digest, &parse_adaptor_point(&adaptor_point).unwrap());
+        // The DLEQ proof ties the pre-signature to its adaptor point
+        assert!(!verify_ecdsa_adaptor(&pre_signature, &hex::encode(digest), &public_key, &other_point).unwrap());
+        assert!(!verify_ecdsa_adaptor(&pre_signature, &"11".repeat(32), &public_key, &adaptor_point).unwrap());
+        let (schnorr_pre_signature, x_only) = schnorr_pre_sign(secret_key, &digest, &parse_adaptor_point(&adaptor_point).unwrap(), "nonce");
+        assert!(!verify_schnorr_adaptor(&schnorr_pre_signature, &hex::encode(digest), &hex::encode(x_only), &other_point).unwrap());
+
+        // Completing with the wrong secret yields a signature from which nothing is extracted
+        let wrong = hex::encode(scalar("other").to_bytes());
+        let signature = complete_ecdsa_adaptor(&pre_signature, &wrong).unwrap();
+        assert!(matches!(extract_ecdsa_adaptor_secret(&pre_signature, &signature, &adaptor_point), Err(ECDSAError::AdaptorSecretMismatch)));
+        let signature = complete_schnorr_adaptor(&schnorr_pre_signature, &wrong).unwrap();
+        assert!(matches!(extract_schnorr_adaptor_secret(&schnorr_pre_signature, &signature, &adaptor_point), Err(ECDSAError::AdaptorSecretMismatch)));
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let (_, adaptor_point) = adaptor();
+        assert!(matches!(verify_schnorr_adaptor("00", "", &"11".repeat(32), &adaptor_point), Err(ECDSAError::InvalidAdaptorSignature)));
+        assert!(matches!(complete_ecdsa_adaptor(&"00".repeat(ECDSA_PRE_SIGNATURE_LENGTH), "01"), Err(ECDSAError::InvalidAdaptorSignature)));
+        assert!(matches!(parse_secret(&"00".repeat(32)), Err(ECDSAError::InvalidPrivateKey)));
+        assert!(matches!(parse_adaptor_point(&"00".repeat(33)), Err(ECDSAError::InvalidPublicKey)));
+    }
+}
@@ -0,0 +1,204 @@
+//! AES-256-GCM (NIST SP 800-38D) and AES-128-CTR, using the RustCrypto `aes`, `aes-gcm` and
+//! `ctr` crates, whose software AES is bitsliced and so free of table lookups on secret data.
+
+use wasm_bindgen::prelude::*;
+use aes::{Aes128, Aes256};
+use aes_gcm::aead::consts::{U12, U16};
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::AesGcm;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+
+use crate::ECDSAError;
+
+/// Length of a GCM authentication tag.
+pub(crate) const TAG_LENGTH: usize = 16;
+/// Key length of AES-256.
+const AES_256_KEY_LENGTH: usize = 32;
+
+/// Encrypts or decrypts `data` with AES-128 in CTR mode, incrementing the whole 128-bit
+/// counter block.
+pub(crate) fn aes_128_ctr(key: &[u8; 16], iv: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut output = data.to_vec();
+    ctr::Ctr128BE::<Aes128>::new(key.into(), iv.into()).apply_keystream(&mut output);
+    output
+}
+
+/// Checks the key length and returns it as a cipher key.
+fn aes_256_key(key: &[u8]) -> Result<&GenericArray<u8, aes_gcm::aead::consts::U32>, ECDSAError> {
+    if key.len() != AES_256_KEY_LENGTH {
+        return Err(ECDSAError::InvalidSymmetricKey);
+    }
+    Ok(GenericArray::from_slice(key))
+}
+
+/// Encrypts `plaintext` with AES-256-GCM, returning the ciphertext and the tag separately.
+/// Nonces may be 12 bytes (the usual size) or 16 bytes (as eciesjs uses).
+pub(crate) fn aes_256_gcm_encrypt(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, [u8; TAG_LENGTH]), ECDSAError> {
+    let key = aes_256_key(key)?;
+    let mut buffer = plaintext.to_vec();
+    let tag = match nonce.len() {
+        12 => AesGcm::<Aes256, U12>::new(key).encrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer),
+        16 => AesGcm::<Aes256, U16>::new(key).encrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer),
+        _ => return Err(ECDSAError::InvalidNonce),
+    }
+    .map_err(|_| ECDSAError::EncryptionFailed)?;
+    Ok((buffer, tag.into()))
+}
+
+/// Authenticates and decrypts AES-256-GCM `ciphertext` against a separate tag.
+pub(crate) fn aes_256_gcm_decrypt(key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let key = aes_256_key(key)?;
+    if tag.len() != TAG_LENGTH {
+        return Err(ECDSAError::DecryptionFailed);
+    }
+    let tag = GenericArray::from_slice(tag);
+    let mut buffer = ciphertext.to_vec();
+    match nonce.len() {
+        12 => AesGcm::<Aes256, U12>::new(key).decrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer, tag),
+        16 => AesGcm::<Aes256, U16>::new(key).decrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer, tag),
+        _ => return Err(ECDSAError::InvalidNonce),
+    }
+    .map_err(|_| ECDSAError::DecryptionFailed)?;
+    Ok(buffer)
+}
+
+/// Encrypts and authenticates data with AES-256-GCM.
+///
+/// # Arguments:
+/// * `key` - The 32-byte key.
+/// * `nonce` - The 12 or 16-byte nonce; it must never repeat under the same key.
+/// * `aad` - Additional data that is authenticated but not encrypted; pass an empty array for none.
+/// * `data` - The plaintext.
+///
+/// # Returns:
+/// * The ciphertext followed by the 16-byte tag, the layout WebCrypto uses (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidSymmetricKey` if the key is not 32 bytes.
+/// * `InvalidNonce` if the nonce is not 12 or 16 bytes.
+/// * `EncryptionFailed` if the plaintext exceeds the 64 GiB limit.
+#[wasm_bindgen]
+pub fn aes_gcm_encrypt(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let (mut ciphertext, tag) = aes_256_gcm_encrypt(key, nonce, aad, data)?;
+    ciphertext.extend_from_slice(&tag);
+    Ok(ciphertext)
+}
+
+/// Authenticates and decrypts data encrypted with AES-256-GCM.
+///
+/// # Arguments:
+/// * `key` - The 32-byte key.
+/// * `nonce` - The nonce used for encryption.
+/// * `aad` - The additional data used for encryption.
+/// * `data` - The ciphertext followed by the 16-byte tag.
+///
+/// # Returns:
+/// * The plaintext (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidSymmetricKey` if the key is not 32 bytes.
+/// * `InvalidNonce` if the nonce is not 12 or 16 bytes.
+/// * `DecryptionFailed` if the data is too short for a tag or fails authentication.
+#[wasm_bindgen]
+pub fn aes_gcm_decrypt(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    aes_256_key(key)?;
+    let body_length = data.len().checked_sub(TAG_LENGTH).ok_or(ECDSAError::DecryptionFailed)?;
+    let (ciphertext, tag) = data.split_at(body_length);
+    aes_256_gcm_decrypt(key, nonce, aad, ciphertext, tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ctr_vector() {
+        // NIST SP 800-38A F.5.1 (CTR-AES128.Encrypt), first two blocks
+        let key: [u8; 16] = hex::decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap().try_into().unwrap();
+        let iv: [u8; 16] = hex::decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap().try_into().unwrap();
+        let plaintext = hex::decode("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51").unwrap();
+        let ciphertext = aes_128_ctr(&key, &iv, &plaintext);
+        assert_eq!(
+            hex::encode(&ciphertext),
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff"
+        );
+        assert_eq!(aes_128_ctr(&key, &iv, &ciphertext), plaintext);
+    }
+
+    #[test]
+    fn test_gcm_vector() {
+        // GCM specification test case 16 (AES-256, 96-bit IV, with AAD)
+        let key = hex::decode("feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308").unwrap();
+        let nonce = hex::decode("cafebabefacedbaddecaf888").unwrap();
+        let aad = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+        let plaintext = hex::decode(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        )
+        .unwrap();
+
+        let (ciphertext, tag) = aes_256_gcm_encrypt(&key, &nonce, &aad, &plaintext).unwrap();
+        assert_eq!(
+            hex::encode(&ciphertext),
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa\
+             8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662"
+        );
+        assert_eq!(hex::encode(tag), "76fc6ece0f4e1768cddf8853bb2d551b");
+        assert_eq!(aes_256_gcm_decrypt(&key, &nonce, &aad, &ciphertext, &tag).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_gcm_rejects_tampering() {
+        let key = [7u8; 32];
+        let nonce = [1u8; 16];
+        let (mut ciphertext, tag) = aes_256_gcm_encrypt(&key, &nonce, b"", b"attack at dawn").unwrap();
+        // 128-bit nonces are hashed into the pre-counter block
+        assert_eq!(hex::encode(&ciphertext), "fcd4b0268e3b67bc9ef4182ec6dc");
+        assert_eq!(hex::encode(tag), "11ec1979beba726086df7f1fd98fbba1");
+        assert_eq!(aes_256_gcm_decrypt(&key, &nonce, b"", &ciphertext, &tag).unwrap(), b"attack at dawn");
+
+        ciphertext[0] ^= 1;
+        assert!(matches!(aes_256_gcm_decrypt(&key, &nonce, b"", &ciphertext, &tag), Err(ECDSAError::DecryptionFailed)));
+        assert!(matches!(aes_256_gcm_encrypt(&key, &[1u8; 8], b"", b""), Err(ECDSAError::InvalidNonce)));
+    }
+
+    #[test]
+    fn test_aes_256_gcm_round_trip() {
+        // GCM specification test case 16, as ciphertext || tag
+        let key = hex::decode("feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308").unwrap();
+        let nonce = hex::decode("cafebabefacedbaddecaf888").unwrap();
+        let aad = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+        let plaintext = hex::decode("d9313225f88406e5a55909c5aff5269a").unwrap();
+        let sealed = aes_gcm_encrypt(&key, &nonce, &aad, &plaintext).unwrap();
+        assert_eq!(sealed.len(), plaintext.len() + TAG_LENGTH);
+        assert_eq!(hex::encode(&sealed[..16]), "522dc1f099567d07f47f37a32a84427d");
+        assert_eq!(aes_gcm_decrypt(&key, &nonce, &aad, &sealed).unwrap(), plaintext);
+
+        // An empty plaintext still carries a tag
+        let empty = aes_gcm_encrypt(&key, &nonce, b"", b"").unwrap();
+        assert_eq!(empty.len(), TAG_LENGTH);
+        assert!(aes_gcm_decrypt(&key, &nonce, b"", &empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_aes_256_gcm_rejects_bad_input() {
+        let key = [7u8; 32];
+        let nonce = [1u8; 12];
+        let sealed = aes_gcm_encrypt(&key, &nonce, b"header", b"payload").unwrap();
+        assert!(matches!(aes_gcm_decrypt(&key, &nonce, b"other header", &sealed), Err(ECDSAError::DecryptionFailed)));
+        assert!(matches!(aes_gcm_decrypt(&[8u8; 32], &nonce, b"header", &sealed), Err(ECDSAError::DecryptionFailed)));
+        assert!(matches!(aes_gcm_decrypt(&key, &nonce, b"header", &sealed[..15]), Err(ECDSAError::DecryptionFailed)));
+        // AES-128 keys are valid for the cipher but not for this API
+        assert!(matches!(aes_gcm_encrypt(&[7u8; 16], &nonce, b"", b""), Err(ECDSAError::InvalidSymmetricKey)));
+        assert!(matches!(aes_gcm_encrypt(&key, &[], b"", b""), Err(ECDSAError::InvalidNonce)));
+        assert!(matches!(aes_gcm_encrypt(&key, &[1u8; 8], b"", b""), Err(ECDSAError::InvalidNonce)));
+        assert!(matches!(aes_gcm_decrypt(&key, &[1u8; 8], b"header", &sealed), Err(ECDSAError::InvalidNonce)));
+
+        // 16-byte nonces are accepted too, and are not interchangeable with 12-byte ones
+        let long_nonce = [1u8; 16];
+        let sealed = aes_gcm_encrypt(&key, &long_nonce, b"", b"payload").unwrap();
+        assert_eq!(aes_gcm_decrypt(&key, &long_nonce, b"", &sealed).unwrap(), b"payload");
+        assert!(aes_gcm_decrypt(&key, &nonce, b"", &sealed).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+//! Verifying many signatures in a single call across the JS/wasm boundary.
+//!
+//! With the `parallel` feature, batches are spread across a rayon thread pool. In the browser
+//! that pool is made of Web Workers sharing the wasm memory, started by awaiting
+//! `initThreadPool(navigator.hardwareConcurrency)` once after loading the module; until then,
+//! batches are verified on the calling thread.
+
+use std::future::Future;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+
+use k256::ecdsa::{Signature, VerifyingKey};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{keys, ECDSAError, ECDSAVerifier, HashAlgorithm, SignatureFormat};
+
+/// Items verified between yields to the event loop by `verify_batch_async`.
+const DEFAULT_YIELD_EVERY: usize = 16;
+
+/// One `{ message, signature, publicKey }` entry of a batch.
+pub(crate) struct BatchItem {
+    pub(crate) message: String,
+    pub(crate) signature: String,
+    pub(crate) public_key: String,
+}
+
+impl BatchItem {
+    /// Reads a batch entry from a plain JS object.
+    fn from_js(value: &JsValue) -> Result<BatchItem, ECDSAError> {
+        let field = |name: &str| {
+            Reflect::get(value, &JsValue::from_str(name))
+                .ok()
+                .and_then(|v| v.as_string())
+                .ok_or(ECDSAError::InvalidBatchInput)
+        };
+        Ok(BatchItem {
+            message: field("message")?,
+            signature: field("signature")?,
+            public_key: field("publicKey")?,
+        })
+    }
+}
+
+/// Converts a per-item result into a `{ valid, error }` JS object.
+pub(crate) fn result_to_js(result: &Result<bool, ECDSAError>) -> JsValue {
+    let object = Object::new();
+    let (valid, error) = match result {
+        Ok(valid) => (*valid, JsValue::UNDEFINED),
+        Err(err) => (false, JsValue::from_str(&err.to_string())),
+    };
+    // Setting properties on a fresh plain object cannot fail
+    let _ = Reflect::set(&object, &JsValue::from_str("valid"), &JsValue::from_bool(valid));
+    let _ = Reflect::set(&object, &JsValue::from_str("error"), &error);
+    object.into()
+}
+
+/// Reads an array of batch entries.
+pub(crate) fn items_from_js(items: &JsValue) -> Result<Vec<BatchItem>, ECDSAError> {
+    if !Array::is_array(items) {
+        return Err(ECDSAError::InvalidBatchInput);
+    }
+    Array::from(items).iter().map(|item| BatchItem::from_js(&item)).collect()
+}
+
+/// Resolves on the next macrotask (`setTimeout(0)`), giving the browser a chance to render
+/// and handle input. Where there is no `setTimeout`, it resolves on the next microtask.
+fn next_tick() -> JsFuture {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        let scheduled = Reflect::get(&js_sys::global(), &JsValue::from_str("setTimeout"))
+            .ok()
+            .and_then(|set_timeout| set_timeout.dyn_into::<Function>().ok())
+            .is_some_and(|set_timeout| set_timeout.call2(&JsValue::UNDEFINED, &resolve, &JsValue::from(0)).is_ok());
+        if !scheduled {
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    });
+    JsFuture::from(promise)
+}
+
+impl ECDSAVerifier {
+    /// Verifies every item independently; a malformed item does not affect the others.
+    pub(crate) fn verify_items(&self, items: &[BatchItem]) -> Vec<Result<bool, ECDSAError>> {
+        let verify = |item: &BatchItem| self.verify_inner(&item.message, &item.signature, &item.public_key, SignatureFormat::Auto);
+        #[cfg(feature = "parallel")]
+        return items.par_iter().map(verify).collect();
+        #[cfg(not(feature = "parallel"))]
+        items.iter().map(verify).collect()
+    }
+
+    /// Like `verify_items`, but awaits `pause` after every `yield_every` items.
+    async fn verify_items_yielding<F: Future>(
+        &self,
+        items: &[BatchItem],
+        yield_every: usize,
+        mut pause: impl FnMut() -> F,
+    ) -> Vec<Result<bool, ECDSAError>> {
+        let mut results = Vec::with_capacity(items.len());
+        for (index, slice) in items.chunks(yield_every.max(1)).enumerate() {
+            if index > 0 {
+                pause().await;
+            }
+            results.extend(self.verify_items(slice));
+        }
+        results
+    }
+}
+
+#[wasm_bindgen]
+impl ECDSAVerifier {
+    /// Verifies a batch of signatures in one call.
+    ///
+    /// # Arguments:
+    /// * `items` - An array of `{ message, signature, publicKey }` objects, with the same
+    ///   encodings as `verify_signature`.
+    ///
+    /// # Returns:
+    /// * An array of `{ valid: boolean, error?: string }` objects, in input order.
+    ///
+    /// # Errors:
+    /// * Returns an error if `items` is not an array or an entry is missing a field.
+    pub fn verify_batch(&self, items: JsValue) -> Result<JsValue, ECDSAError> {
+        let items = items_from_js(&items)?;
+        let results: Array = self.verify_items(&items).iter().map(result_to_js).collect();
+        Ok(results.into())
+    }
+
+    /// Verifies a signature without blocking the caller: the work runs on a later turn of
+    /// the event loop.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The DER or compact signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A promise of whether the signature is valid, rejected with the error message if
+    ///   any input is malformed.
+    pub fn verify_signature_async(&self, message: String, signature: String, public_key: String) -> Promise {
+        let verifier = *self;
+        future_to_promise(async move {
+            next_tick().await.ok();
+            Ok(JsValue::from_bool(verifier.verify_signature_with_format(&message, &signature, &public_key, SignatureFormat::Auto)?))
+        })
+    }
+
+    /// Verifies a batch like `verify_batch`, but yields to the event loop every few items so
+    /// that a large batch does not freeze the page.
+    ///
+    /// # Arguments:
+    /// * `items` - An array of `{ message, signature, publicKey }` objects.
+    /// * `yield_every` - Items to verify between yields; defaults to 16.
+    ///
+    /// # Returns:
+    /// * A promise of an array of `{ valid: boolean, error?: string }` objects, in input order.
+    ///
+    /// # Errors:
+    /// * Returns an error if `items` is not an array or an entry is missing a field.
+    pub fn verify_batch_async(&self, items: JsValue, yield_every: Option<usize>) -> Result<Promise, ECDSAError> {
+        // Entries are read now, so later changes to the caller's array have no effect
+        let items = items_from_js(&items)?;
+        let verifier = *self;
+        Ok(future_to_promise(async move {
+            let yield_every = yield_every.unwrap_or(DEFAULT_YIELD_EVERY);
+            let results = verifier.verify_items_yielding(&items, yield_every, next_tick).await;
+            Ok(results.iter().map(result_to_js).collect::<Array>().into())
+        }))
+    }
+}
+
+/// A parsed entry held by `BatchVerifier`, ready to be checked.
+struct PreparedItem {
+    digest: Vec<u8>,
+    signature: Signature,
+    public_key: VerifyingKey,
+}
+
+/// Accumulates signatures, e.g. from a streaming source, and verifies them in a single pass.
+/// Each entry is hashed and parsed when it is added, so `verify_all` only runs the curve arithmetic.
+/// Entries are hashed, parsed and checked with the settings of the `ECDSAVerifier` it was
+/// created from.
+#[wasm_bindgen]
+pub struct BatchVerifier {
+    verifier: ECDSAVerifier,
+    items: Vec<PreparedItem>,
+}
+
+impl Default for BatchVerifier {
+    fn default() -> Self {
+        BatchVerifier::new(None)
+    }
+}
+
+impl BatchVerifier {
+    /// Verification result of every entry, in insertion order.
+    fn results(&self) -> Vec<bool> {
+        let verify = |item: &PreparedItem| self.verifier.verify_with_policy(&item.public_key, &item.digest, &item.signature);
+        #[cfg(feature = "parallel")]
+        return self.items.par_iter().map(verify).collect();
+        #[cfg(not(feature = "parallel"))]
+        self.items.iter().map(verify).collect()
+    }
+}
+
+#[wasm_bindgen]
+impl BatchVerifier {
+    /// Creates an empty batch whose messages are hashed with `hash_algorithm` (SHA-256 if omitted).
+    #[wasm_bindgen(constructor)]
+    pub fn new(hash_algorithm: Option<HashAlgorithm>) -> BatchVerifier {
+        BatchVerifier::from_verifier(&ECDSAVerifier::new(hash_algorithm))
+    }
+
+    /// Creates an empty batch with a verifier's hash algorithm, high-S policy and DER parsing mode.
+    pub fn from_verifier(verifier: &ECDSAVerifier) -> BatchVerifier {
+        BatchVerifier {
+            verifier: *verifier,
+            items: Vec::new(),
+        }
+    }
+
+    /// Adds an entry to the batch.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The DER or compact signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or public key is malformed; the entry is not added.
+    pub fn add(&mut self, message: &str, signature: &str, public_key: &str) -> Result<(), ECDSAError> {
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let signature = self.verifier.parse_signature(&signature_bytes, SignatureFormat::Auto)?;
+        let public_key = keys::parse_public_key_hex(public_key)?;
+
+        self.items.push(PreparedItem {
+            digest: self.verifier.hash_message(message),
+            signature,
+            public_key,
+        });
+        Ok(())
+    }
+
+    /// The number of entries in the batch.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the batch has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Removes all entries, keeping the verifier settings.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Verifies every entry.
+    ///
+    /// # Returns:
+    /// * An array of booleans, one per entry in insertion order.
+    pub fn verify_all(&self) -> Array {
+        self.results().into_iter().map(JsValue::from_bool).collect()
+    }
+
+    /// Verifies every entry, failing if any of them is invalid.
+    ///
+    /// # Errors:
+    /// * Returns an error if at least one signature in the batch does not verify.
+    pub fn verify_all_or_fail(&self) -> Result<(), ECDSAError> {
+        if self.results().into_iter().all(|valid| valid) {
+            Ok(())
+        } else {
+            Err(ECDSAError::BatchVerificationFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DerParsing, HighSPolicy};
+
+    const SIGNATURE: &str = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+    const PUBLIC_KEY: &str = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+    fn item(message: &str, signature: &str) -> BatchItem {
+        BatchItem {
+            message: message.to_string(),
+            signature: signature.to_string(),
+            public_key: PUBLIC_KEY.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_items() {
+        let verifier = ECDSAVerifier::default();
+        let results = verifier.verify_items(&[
+            item("Test message", SIGNATURE),
+            item("Other message", SIGNATURE),
+            item("Test message", "zz"),
+        ]);
+
+        assert!(matches!(results[0], Ok(true)));
+        assert!(matches!(results[1], Ok(false)));
+        assert!(matches!(results[2], Err(ECDSAError::InvalidSignatureFormat)));
+    }
+
+    #[test]
+    fn test_batch_verifier() {
+        let mut batch = BatchVerifier::default();
+        batch.add("Test message", SIGNATURE, PUBLIC_KEY).unwrap();
+        assert!(batch.verify_all_or_fail().is_ok());
+
+        batch.add("Other message", SIGNATURE, PUBLIC_KEY).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch.results(), vec![true, false]);
+        assert!(matches!(batch.verify_all_or_fail(), Err(ECDSAError::BatchVerificationFailed)));
+
+        batch.clear();
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn test_batch_verifier_uses_verifier_settings() {
+        let high_s = "bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269682cd616936451dda44a073dfa6c1bb000b5b442ee5e650a2857a8efd082c6d89";
+        let padded = "304602220000bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+
+        let mut batch = BatchVerifier::default();
+        batch.add("Test message", high_s, PUBLIC_KEY).unwrap();
+        assert_eq!(batch.results(), vec![false]);
+        assert!(matches!(batch.add("Test message", padded, PUBLIC_KEY), Err(ECDSAError::InvalidSignatureFormat)));
+
+        let mut verifier = ECDSAVerifier::default();
+        verifier.set_high_s_policy(HighSPolicy::Normalize);
+        verifier.set_der_parsing(DerParsing::Lax);
+        let mut batch = BatchVerifier::from_verifier(&verifier);
+        batch.add("Test message", high_s, PUBLIC_KEY).unwrap();
+        batch.add("Test message", padded, PUBLIC_KEY).unwrap();
+        assert_eq!(batch.results(), vec![true, true]);
+    }
+
+    #[test]
+    fn test_batch_verifier_rejects_malformed_entry() {
+        let mut batch = BatchVerifier::default();
+        assert!(batch.add("Test message", SIGNATURE, "02abcd").is_err());
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn test_verify_items_yielding() {
+        let verifier = ECDSAVerifier::default();
+        let items: Vec<BatchItem> = (0..5).map(|i| item(if i % 2 == 0 { "Test message" } else { "Other" }, SIGNATURE)).collect();
+        for (yield_every, expected_pauses) in [(2, 2), (5, 0), (0, 4)] {
+            let mut pauses = 0;
+            let results = {
+                let future = verifier.verify_items_yielding(&items, yield_every, || {
+                    pauses += 1;
+                    std::future::ready(())
+                });
+                let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+                match std::pin::pin!(future).poll(&mut context) {
+                    std::task::Poll::Ready(results) => results,
+                    std::task::Poll::Pending => panic!("ready pauses never suspend"),
+                }
+            };
+            let valid: Vec<bool> = results.into_iter().map(Result::unwrap).collect();
+            assert_eq!(valid, [true, false, true, false, true]);
+            assert_eq!(pauses, expected_pauses, "yield_every = {yield_every}");
+        }
+    }
+
+    #[test]
+    fn test_large_batch_keeps_order() {
+        let items: Vec<BatchItem> = (0..64).map(|i| item(if i % 3 == 0 { "Test message" } else { "Other" }, SIGNATURE)).collect();
+        let results = ECDSAVerifier::default().verify_items(&items);
+        assert!(results.iter().enumerate().all(|(i, result)| matches!(result, Ok(valid) if *valid == (i % 3 == 0))));
+
+        let mut batch = BatchVerifier::default();
+        for i in 0..64 {
+            batch.add(if i % 3 == 0 { "Test message" } else { "Other" }, SIGNATURE, PUBLIC_KEY).unwrap();
+        }
+        assert!(batch.results().iter().enumerate().all(|(i, valid)| *valid == (i % 3 == 0)));
+    }
+
+    #[test]
+    fn test_verify_items_empty() {
+        assert!(ECDSAVerifier::default().verify_items(&[]).is_empty());
+    }
+}
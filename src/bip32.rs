@@ -0,0 +1,536 @@
+//! BIP-32 hierarchical deterministic keys over secp256k1.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use k256::elliptic_curve::ff::PrimeField;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{FieldBytes, ProjectivePoint, PublicKey, Scalar};
+
+use crate::bitcoin::hash160;
+use crate::encoding::{from_base58check, to_base58check};
+use crate::kdf::hmac_sha512;
+use crate::{ECDSAError, ECDSASigner};
+
+/// Child indices at or above this value use hardened derivation.
+pub(crate) const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// The script type an extended key is serialized for (SLIP-0132 version bytes).
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExtendedKeyFormat {
+    /// xprv/xpub (tprv/tpub on testnet): legacy P2PKH and generic BIP-32 keys.
+    #[default]
+    Legacy,
+    /// yprv/ypub (uprv/upub): BIP-49 P2WPKH nested in P2SH.
+    NestedSegwit,
+    /// zprv/zpub (vprv/vpub): BIP-84 native P2WPKH.
+    NativeSegwit,
+}
+
+/// Serialization version bytes: format, testnet and (private, public).
+const VERSIONS: [(ExtendedKeyFormat, bool, (u32, u32)); 6] = [
+    (ExtendedKeyFormat::Legacy, false, (0x0488ade4, 0x0488b21e)),
+    (ExtendedKeyFormat::Legacy, true, (0x04358394, 0x043587cf)),
+    (ExtendedKeyFormat::NestedSegwit, false, (0x049d7878, 0x049d7cb2)),
+    (ExtendedKeyFormat::NestedSegwit, true, (0x044a4e28, 0x044a5262)),
+    (ExtendedKeyFormat::NativeSegwit, false, (0x04b2430c, 0x04b24746)),
+    (ExtendedKeyFormat::NativeSegwit, true, (0x045f18bc, 0x045f1cf6)),
+];
+
+fn versions_for(format: ExtendedKeyFormat, testnet: bool) -> (u32, u32) {
+    VERSIONS
+        .iter()
+        .find(|&&(f, t, _)| f == format && t == testnet)
+        .map(|&(_, _, versions)| versions)
+        .expect("every format has mainnet and testnet versions")
+}
+
+const SERIALIZED_LENGTH: usize = 78;
+
+#[derive(Clone)]
+enum Key {
+    Private(SigningKey),
+    Public(VerifyingKey),
+}
+
+impl Key {
+    fn verifying_key(&self) -> VerifyingKey {
+        match self {
+            Key::Private(signing_key) => signing_key.verifying_key(),
+            Key::Public(verifying_key) => *verifying_key,
+        }
+    }
+}
+
+/// Parses a big-endian 32-byte value as a scalar, rejecting values not below the curve order.
+fn parse_scalar(bytes: &[u8]) -> Option<Scalar> {
+    Scalar::from_repr(FieldBytes::clone_from_slice(bytes)).into()
+}
+
+/// Parses a derivation path such as `m/44'/0'/0'/0/0` into child indices.
+/// Hardened components are marked with `'`, `h` or `H`. Returns whether the path
+/// starts at the master key (`m`) along with the indices.
+pub(crate) fn parse_path(path: &str) -> Result<(bool, Vec<u32>), ECDSAError> {
+    let mut components = path.trim().split('/').peekable();
+    let absolute = components.next_if_eq(&"m").is_some();
+
+    let indices = components
+        .map(|component| {
+            let (number, hardened) = match component.strip_suffix(['\'', 'h', 'H']) {
+                Some(number) => (number, true),
+                None => (component, false),
+            };
+            if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ECDSAError::InvalidDerivationPath);
+            }
+            let index: u32 = number.parse().map_err(|_| ECDSAError::InvalidDerivationPath)?;
+            if index >= HARDENED_OFFSET {
+                return Err(ECDSAError::InvalidDerivationPath);
+            }
+            Ok(if hardened { index + HARDENED_OFFSET } else { index })
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    Ok((absolute, indices))
+}
+
+/// A BIP-32 extended private or public key (xprv/xpub and the SLIP-0132 variants).
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct ExtendedKey {
+    key: Key,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    format: ExtendedKeyFormat,
+    testnet: bool,
+}
+
+impl ExtendedKey {
+    /// Derives the master key from a seed (`I = HMAC-SHA512("Bitcoin seed", seed)`).
+    pub(crate) fn master(seed: &[u8], format: ExtendedKeyFormat, testnet: bool) -> Result<ExtendedKey, ECDSAError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(ECDSAError::InvalidExtendedKey);
+        }
+        let i = hmac_sha512(b"Bitcoin seed", &[seed]);
+        let signing_key = SigningKey::from_bytes(&i[..32]).map_err(|_| ECDSAError::InvalidChildKey)?;
+        Ok(ExtendedKey {
+            key: Key::Private(signing_key),
+            chain_code: i[32..].try_into().unwrap(),
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            format,
+            testnet,
+        })
+    }
+
+    /// CKDpriv or CKDpub, depending on which half of the key pair is held.
+    pub(crate) fn child(&self, index: u32) -> Result<ExtendedKey, ECDSAError> {
+        let depth = self.depth.checked_add(1).ok_or(ECDSAError::InvalidDerivationPath)?;
+        let public_key = self.key.verifying_key().to_bytes();
+
+        let i = match (&self.key, index >= HARDENED_OFFSET) {
+            (Key::Private(signing_key), true) => hmac_sha512(&self.chain_code, &[&[0], &signing_key.to_bytes(), &index.to_be_bytes()]),
+            (Key::Public(_), true) => return Err(ECDSAError::PrivateKeyRequired),
+            (_, false) => hmac_sha512(&self.chain_code, &[&public_key, &index.to_be_bytes()]),
+        };
+        let tweak = parse_scalar(&i[..32]).ok_or(ECDSAError::InvalidChildKey)?;
+
+        let key = match &self.key {
+            Key::Private(signing_key) => {
+                let parent = parse_scalar(&signing_key.to_bytes()).ok_or(ECDSAError::InvalidPrivateKey)?;
+                let child = (tweak + parent).to_bytes();
+                Key::Private(SigningKey::from_bytes(&child).map_err(|_| ECDSAError::InvalidChildKey)?)
+            }
+            Key::Public(verifying_key) => {
+                let parent = PublicKey::from(verifying_key).to_projective();
+                let child = (ProjectivePoint::GENERATOR * tweak + parent).to_affine();
+                if child == k256::AffinePoint::IDENTITY {
+                    return Err(ECDSAError::InvalidChildKey);
+                }
+                let encoded = child.to_encoded_point(true);
+                Key::Public(VerifyingKey::from_sec1_bytes(encoded.as_bytes()).map_err(|_| ECDSAError::InvalidChildKey)?)
+            }
+        };
+
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&hash160(&public_key)[..4]);
+        Ok(ExtendedKey {
+            key,
+            chain_code: i[32..].try_into().unwrap(),
+            depth,
+            parent_fingerprint,
+            child_number: index,
+            format: self.format,
+            testnet: self.testnet,
+        })
+    }
+
+    /// The 78-byte serialization of either the private or the public key.
+    fn serialize(&self, private: bool) -> Result<Vec<u8>, ECDSAError> {
+        let versions = versions_for(self.format, self.testnet);
+        let (version, key_data) = match (&self.key, private) {
+            (Key::Private(signing_key), true) => {
+                let mut key_data = vec![0];
+                key_data.extend_from_slice(&signing_key.to_bytes());
+                (versions.0, key_data)
+            }
+            (Key::Public(_), true) => return Err(ECDSAError::PrivateKeyRequired),
+            (key, false) => (versions.1, key.verifying_key().to_bytes().to_vec()),
+        };
+
+        let mut data = Vec::with_capacity(SERIALIZED_LENGTH);
+        data.extend_from_slice(&version.to_be_bytes());
+        data.push(self.depth);
+        data.extend_from_slice(&self.parent_fingerprint);
+        data.extend_from_slice(&self.child_number.to_be_bytes());
+        data.extend_from_slice(&self.chain_code);
+        data.extend_from_slice(&key_data);
+        Ok(data)
+    }
+}
+
+#[wasm_bindgen]
+impl ExtendedKey {
+    /// Derives the master key from a seed, e.g. `Mnemonic.to_seed()`.
+    ///
+    /// # Arguments:
+    /// * `seed` - The 16 to 64 byte seed.
+    /// * `testnet` - Serialize as tprv/tpub instead of xprv/xpub. Defaults to false.
+    ///
+    /// # Errors:
+    /// * Returns an error if the seed length is out of range or yields an invalid key.
+    pub fn from_seed(seed: &[u8], testnet: Option<bool>) -> Result<ExtendedKey, ECDSAError> {
+        ExtendedKey::master(seed, ExtendedKeyFormat::Legacy, testnet.unwrap_or(false))
+    }
+
+    /// Parses a Base58Check extended key: xprv/xpub, yprv/ypub or zprv/zpub, or their
+    /// testnet counterparts.
+    ///
+    /// # Errors:
+    /// * Returns an error if the checksum, version, key data or depth fields are invalid.
+    pub fn parse(encoded: &str) -> Result<ExtendedKey, ECDSAError> {
+        let data = from_base58check(encoded).ok_or(ECDSAError::InvalidExtendedKey)?;
+        if data.len() != SERIALIZED_LENGTH {
+            return Err(ECDSAError::InvalidExtendedKey);
+        }
+
+        let version = u32::from_be_bytes(data[..4].try_into().unwrap());
+        let (format, testnet, versions) = VERSIONS
+            .into_iter()
+            .find(|&(_, _, (private, public))| version == private || version == public)
+            .ok_or(ECDSAError::InvalidExtendedKey)?;
+
+        let depth = data[4];
+        let parent_fingerprint: [u8; 4] = data[5..9].try_into().unwrap();
+        let child_number = u32::from_be_bytes(data[9..13].try_into().unwrap());
+        if depth == 0 && (parent_fingerprint != [0; 4] || child_number != 0) {
+            return Err(ECDSAError::InvalidExtendedKey);
+        }
+
+        let key_data = &data[45..];
+        let key = if version == versions.0 {
+            if key_data[0] != 0 {
+                return Err(ECDSAError::InvalidExtendedKey);
+            }
+            Key::Private(SigningKey::from_bytes(&key_data[1..]).map_err(|_| ECDSAError::InvalidExtendedKey)?)
+        } else {
+            if !matches!(key_data[0], 0x02 | 0x03) {
+                return Err(ECDSAError::InvalidExtendedKey);
+            }
+            Key::Public(VerifyingKey::from_sec1_bytes(key_data).map_err(|_| ECDSAError::InvalidExtendedKey)?)
+        };
+
+        Ok(ExtendedKey {
+            key,
+            chain_code: data[13..45].try_into().unwrap(),
+            depth,
+            parent_fingerprint,
+            child_number,
+            format,
+            testnet,
+        })
+    }
+
+    /// Derives a direct child.
+    ///
+    /// # Arguments:
+    /// * `index` - The child index; indices from 2^31 upwards are hardened.
+    ///
+    /// # Errors:
+    /// * Returns an error for hardened derivation from a public key, or in the
+    ///   vanishingly unlikely case that the child key is invalid (use the next index).
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedKey, ECDSAError> {
+        self.child(index)
+    }
+
+    /// Derives a descendant along a path such as `m/44'/0'/0'/0/0`.
+    ///
+    /// # Arguments:
+    /// * `path` - Slash-separated indices, hardened ones suffixed with `'` or `h`. Paths
+    ///   starting with `m` are only accepted on a master key; others are relative to this key.
+    ///
+    /// # Errors:
+    /// * Returns an error if the path is malformed or a step cannot be derived.
+    pub fn derive_path(&self, path: &str) -> Result<ExtendedKey, ECDSAError> {
+        let (absolute, indices) = parse_path(path)?;
+        if absolute && self.depth != 0 {
+            return Err(ECDSAError::InvalidDerivationPath);
+        }
+        indices.into_iter().try_fold(self.clone(), |key, index| key.child(index))
+    }
+
+    /// Derives the public key at a non-hardened path below this key. Works on public-only
+    /// keys, so a watch-only service holding an account xpub can find the key expected to
+    /// have signed for, say, receive address `0/N`.
+    ///
+    /// # Arguments:
+    /// * `path` - The relative path, e.g. `0/5`.
+    ///
+    /// # Returns:
+    /// * The SEC1 compressed public key, as a hex string.
+    ///
+    /// # Errors:
+    /// * Returns an error if the path is malformed or hardened on a public-only key.
+    pub fn derive_public_key(&self, path: &str) -> Result<String, ECDSAError> {
+        Ok(self.derive_path(path)?.public_key())
+    }
+
+    /// Returns the same key re-labelled for another script type, e.g. to turn a zpub into
+    /// the xpub expected by generic BIP-32 tooling.
+    pub fn with_format(&self, format: ExtendedKeyFormat) -> ExtendedKey {
+        ExtendedKey { format, ..self.clone() }
+    }
+
+    /// Returns the public-only counterpart of this key.
+    pub fn neuter(&self) -> ExtendedKey {
+        ExtendedKey { key: Key::Public(self.key.verifying_key()), ..self.clone() }
+    }
+
+    /// Serializes the private key as xprv/tprv, or yprv/zprv per `format`.
+    ///
+    /// # Errors:
+    /// * Returns an error if this is a public-only key.
+    pub fn to_xprv(&self) -> Result<String, ECDSAError> {
+        Ok(to_base58check(&self.serialize(true)?))
+    }
+
+    /// Serializes the public key as xpub/tpub, or ypub/zpub per `format`.
+    pub fn to_xpub(&self) -> String {
+        to_base58check(&self.serialize(false).expect("public serialization cannot fail"))
+    }
+
+    /// The script type this key is serialized for.
+    #[wasm_bindgen(getter)]
+    pub fn format(&self) -> ExtendedKeyFormat {
+        self.format
+    }
+
+    /// Whether this key uses testnet version bytes.
+    #[wasm_bindgen(getter)]
+    pub fn testnet(&self) -> bool {
+        self.testnet
+    }
+
+    /// Whether this key holds private key material.
+    #[wasm_bindgen(getter)]
+    pub fn is_private(&self) -> bool {
+        matches!(self.key, Key::Private(_))
+    }
+
+    /// The number of derivation steps from the master key.
+    #[wasm_bindgen(getter)]
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The index this key was derived with; hardened indices include the 2^31 offset.
+    #[wasm_bindgen(getter)]
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// The first four bytes of the parent's key identifier, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn parent_fingerprint(&self) -> String {
+        hex::encode(self.parent_fingerprint)
+    }
+
+    /// The first four bytes of this key's identifier (HASH160 of its public key), as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn fingerprint(&self) -> String {
+        hex::encode(&hash160(&self.key.verifying_key().to_bytes())[..4])
+    }
+
+    /// The 32-byte chain code, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn chain_code(&self) -> String {
+        hex::encode(self.chain_code)
+    }
+
+    /// Returns the SEC1 compressed public key, as a hex string.
+    pub fn public_key(&self) -> String {
+        hex::encode(self.key.verifying_key().to_bytes())
+    }
+
+    /// Returns the 32-byte private key, as a hex string.
+    ///
+    /// # Errors:
+    /// * Returns an error if this is a public-only key.
+    pub fn private_key(&self) -> Result<String, ECDSAError> {
+        match &self.key {
+            Key::Private(signing_key) => Ok(hex::encode(signing_key.to_bytes())),
+            Key::Public(_) => Err(ECDSAError::PrivateKeyRequired),
+        }
+    }
+
+    /// Creates an `ECDSASigner` for this key.
+    ///
+    /// # Errors:
+    /// * Returns an error if this is a public-only key.
+    pub fn signer(&self) -> Result<ECDSASigner, ECDSAError> {
+        match &self.key {
+            Key::Private(signing_key) => Ok(ECDSASigner::from_signing_key(signing_key.clone())),
+            Key::Public(_) => Err(ECDSAError::PrivateKeyRequired),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mnemonic;
+
+    // BIP-32 test vector 1
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn test_vector_1() {
+        let master = ExtendedKey::from_seed(&hex::decode(SEED).unwrap(), None).unwrap();
+        assert_eq!(
+            master.to_xprv().unwrap(),
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"
+        );
+        assert_eq!(
+            master.to_xpub(),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        );
+
+        let child = master.derive_path("m/0'").unwrap();
+        assert_eq!(
+            child.to_xprv().unwrap(),
+            "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"
+        );
+        assert_eq!(child.parent_fingerprint(), master.fingerprint());
+        assert_eq!(child.child_number(), HARDENED_OFFSET);
+
+        let leaf = master.derive_path("m/0h/1/2'/2/1000000000").unwrap();
+        assert_eq!(leaf.depth(), 5);
+        assert_eq!(
+            leaf.to_xprv().unwrap(),
+            "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"
+        );
+        assert_eq!(
+            leaf.to_xpub(),
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"
+        );
+    }
+
+    #[test]
+    fn test_public_derivation_matches_private() {
+        let account = ExtendedKey::from_seed(&hex::decode(SEED).unwrap(), None)
+            .unwrap()
+            .derive_path("m/44'/0'/0'")
+            .unwrap();
+        let watch_only = ExtendedKey::parse(&account.to_xpub()).unwrap();
+        assert!(!watch_only.is_private());
+
+        let expected = account.derive_path("0/7").unwrap();
+        let derived = watch_only.derive_path("0/7").unwrap();
+        assert_eq!(derived.public_key(), expected.public_key());
+        assert_eq!(derived.to_xpub(), expected.to_xpub());
+
+        assert!(matches!(watch_only.derive_child(HARDENED_OFFSET), Err(ECDSAError::PrivateKeyRequired)));
+        assert!(matches!(watch_only.to_xprv(), Err(ECDSAError::PrivateKeyRequired)));
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let xprv = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7";
+        let key = ExtendedKey::parse(xprv).unwrap();
+        assert_eq!(key.to_xprv().unwrap(), xprv);
+        assert_eq!(key.neuter().to_xpub(), key.to_xpub());
+
+        let testnet = ExtendedKey::from_seed(&hex::decode(SEED).unwrap(), Some(true)).unwrap();
+        assert!(testnet.to_xprv().unwrap().starts_with("tprv"));
+        assert!(ExtendedKey::parse(&testnet.to_xpub()).unwrap().to_xpub().starts_with("tpub"));
+
+        // Corrupted checksum
+        let corrupted = xprv.replace("kJ7", "kJ8");
+        assert!(matches!(ExtendedKey::parse(&corrupted), Err(ECDSAError::InvalidExtendedKey)));
+    }
+
+    #[test]
+    fn test_zpub_watch_derivation() {
+        // BIP-84 test vector: account 0 of the all-"abandon" mnemonic
+        let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+        let account = ExtendedKey::parse(zpub).unwrap();
+        assert_eq!(account.format(), ExtendedKeyFormat::NativeSegwit);
+        assert!(!account.testnet());
+        assert_eq!(account.to_xpub(), zpub);
+        assert_eq!(
+            account.derive_public_key("0/0").unwrap(),
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c"
+        );
+        assert!(account.derive_public_key("0'/0").is_err());
+
+        let mnemonic = Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        let private = ExtendedKey::from_seed(&mnemonic.to_seed(None), None)
+            .unwrap()
+            .derive_path("m/84'/0'/0'")
+            .unwrap()
+            .with_format(ExtendedKeyFormat::NativeSegwit);
+        assert_eq!(private.to_xpub(), zpub);
+        assert!(private.to_xprv().unwrap().starts_with("zprv"));
+
+        let xpub = account.with_format(ExtendedKeyFormat::Legacy).to_xpub();
+        assert!(xpub.starts_with("xpub"));
+        assert_eq!(
+            ExtendedKey::parse(&xpub).unwrap().derive_public_key("0/0").unwrap(),
+            account.derive_public_key("0/0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("m").unwrap(), (true, vec![]));
+        assert_eq!(
+            parse_path("m/44'/0h/0H/0/5").unwrap(),
+            (true, vec![44 + HARDENED_OFFSET, HARDENED_OFFSET, HARDENED_OFFSET, 0, 5])
+        );
+        assert_eq!(parse_path("0/1").unwrap(), (false, vec![0, 1]));
+        for path in ["m/", "m//0", "m/-1", "m/2147483648", "m/0'/x", "m/0''", "n/0"] {
+            assert!(parse_path(path).is_err(), "{path}");
+        }
+
+        let child = ExtendedKey::from_seed(&[7; 32], None).unwrap().derive_child(1).unwrap();
+        assert!(matches!(child.derive_path("m/0"), Err(ECDSAError::InvalidDerivationPath)));
+    }
+
+    #[test]
+    fn test_mnemonic_restore() {
+        let mnemonic = Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        let master = ExtendedKey::from_seed(&mnemonic.to_seed(None), None).unwrap();
+        // The well-known first BIP-44 Bitcoin receive key of the all-"abandon" wallet
+        let leaf = master.derive_path("m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(leaf.public_key(), "03aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5e");
+        assert!(leaf.signer().is_ok());
+    }
+}
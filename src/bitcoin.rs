@@ -0,0 +1,409 @@
+//! Bitcoin address derivation from secp256k1 public keys, signed-message verification, and
+//! SPV Merkle proofs of transaction inclusion.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::VerifyingKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::encoding::{encode_segwit, from_base64, to_base58check};
+use crate::keys::parse_public_key_hex;
+use crate::signature::parse_compact;
+use crate::taproot::tweak_public_key;
+use crate::{recover_from_digest, ECDSAError};
+
+/// Bitcoin's HASH160: `RIPEMD160(SHA256(data))`.
+pub(crate) fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// The output script type an address pays to.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressKind {
+    /// Legacy pay-to-public-key-hash (`1...`).
+    P2pkh,
+    /// Segwit v0 P2WPKH nested in pay-to-script-hash (`3...`).
+    P2shP2wpkh,
+    /// Native segwit v0 pay-to-witness-public-key-hash (`bc1q...`).
+    P2wpkh,
+    /// BIP-86 Taproot key-path output (`bc1p...`).
+    P2tr,
+}
+
+/// The Bitcoin network an address belongs to.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Network {
+    /// Bitcoin mainnet (the default).
+    #[default]
+    Mainnet,
+    /// Testnet and signet, which share address prefixes.
+    Testnet,
+    /// Local regression test networks.
+    Regtest,
+}
+
+impl Network {
+    pub(crate) fn p2pkh_version(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x00,
+            Network::Testnet | Network::Regtest => 0x6f,
+        }
+    }
+
+    pub(crate) fn p2sh_version(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x05,
+            Network::Testnet | Network::Regtest => 0xc4,
+        }
+    }
+
+    pub(crate) fn bech32_hrp(self) -> &'static str {
+        match self {
+            Network::Mainnet => "bc",
+            Network::Testnet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+}
+
+/// Derives the address of `pubkey`. `uncompressed` selects the 65-byte encoding for P2PKH;
+/// segwit outputs always commit to the compressed or x-only key.
+pub(crate) fn address(pubkey: &VerifyingKey, uncompressed: bool, kind: AddressKind, network: Network) -> Result<String, ECDSAError> {
+    match kind {
+        AddressKind::P2pkh => {
+            let encoded = pubkey.to_encoded_point(!uncompressed);
+            let mut payload = vec![network.p2pkh_version()];
+            payload.extend_from_slice(&hash160(encoded.as_bytes()));
+            Ok(to_base58check(&payload))
+        }
+        AddressKind::P2shP2wpkh => {
+            // The redeem script is the v0 witness program: OP_0 PUSH20 <key hash>
+            let mut redeem_script = vec![0x00, 0x14];
+            redeem_script.extend_from_slice(&hash160(&pubkey.to_bytes()));
+            let mut payload = vec![network.p2sh_version()];
+            payload.extend_from_slice(&hash160(&redeem_script));
+            Ok(to_base58check(&payload))
+        }
+        AddressKind::P2wpkh => encode_segwit(network.bech32_hrp(), 0, &hash160(&pubkey.to_bytes())),
+        AddressKind::P2tr => {
+            let mut x_only = [0u8; 32];
+            x_only.copy_from_slice(&pubkey.to_bytes()[1..]);
+            let (output_key, _) = tweak_public_key(&x_only, &[])?;
+            encode_segwit(network.bech32_hrp(), 1, &output_key)
+        }
+    }
+}
+
+/// Derives a Bitcoin address from a public key.
+///
+/// # Arguments:
+/// * `pubkey` - The SEC1 public key, provided as a hex string. P2PKH hashes the key in the
+///   encoding given (compressed or uncompressed); P2WPKH and P2TR always use its compressed
+///   form, as segwit requires.
+/// * `kind` - The output type.
+/// * `network` - The network; defaults to mainnet.
+///
+/// # Returns:
+/// * The address text.
+///
+/// # Errors:
+/// * Returns an error if the public key is invalid.
+#[wasm_bindgen]
+pub fn address_from_pubkey(pubkey: &str, kind: AddressKind, network: Option<Network>) -> Result<String, ECDSAError> {
+    let key = parse_public_key_hex(pubkey)?;
+    // 130 hex digits: a 65-byte uncompressed or hybrid key
+    address(&key, pubkey.len() == 130, kind, network.unwrap_or_default())
+}
+
+/// Computes the digest signed by Bitcoin Core's `signmessage`: double SHA-256 over
+/// `"\x18Bitcoin Signed Message:\n" || varint(len) || message`.
+pub(crate) fn signed_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"\x18Bitcoin Signed Message:\n");
+    hasher.update(compact_size(message.len() as u64));
+    hasher.update(message);
+    Sha256::digest(hasher.finalize()).into()
+}
+
+/// Encodes a length as a Bitcoin CompactSize varint.
+pub(crate) fn compact_size(value: u64) -> Vec<u8> {
+    match value {
+        0..=0xfc => vec![value as u8],
+        0xfd..=0xffff => [&[0xfd], &(value as u16).to_le_bytes()[..]].concat(),
+        0x10000..=0xffff_ffff => [&[0xfe], &(value as u32).to_le_bytes()[..]].concat(),
+        _ => [&[0xff], &value.to_le_bytes()[..]].concat(),
+    }
+}
+
+/// Verifies a Bitcoin signed message (the `signmessage`/`verifymessage` format, with the
+/// BIP-137 header bytes for segwit addresses).
+///
+/// # Arguments:
+/// * `address` - The claimed signer's P2PKH, P2SH-P2WPKH or P2WPKH address, on any network.
+/// * `signature_b64` - The 65-byte recoverable signature, Base64 encoded. Its header byte
+///   selects the key encoding and address type; a compressed P2PKH header (31-34) is also
+///   accepted for segwit addresses, as Electrum and several hardware wallets produce it.
+/// * `message` - The signed message.
+///
+/// # Returns:
+/// * A boolean indicating whether the key recovered from the signature owns `address`.
+///
+/// # Errors:
+/// * Returns an error if the signature is not valid Base64, is not 65 bytes or has an
+///   unknown header byte.
+#[wasm_bindgen]
+pub fn verify_bitcoin_message(address: &str, signature_b64: &str, message: &str) -> Result<bool, ECDSAError> {
+    let signature = from_base64(signature_b64.trim()).ok_or(ECDSAError::InvalidSignatureFormat)?;
+    if signature.len() != 65 {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+
+    let header = signature[0];
+    let (uncompressed, kinds): (bool, &[AddressKind]) = match header {
+        27..=30 => (true, &[AddressKind::P2pkh]),
+        31..=34 => (false, &[AddressKind::P2pkh, AddressKind::P2shP2wpkh, AddressKind::P2wpkh]),
+        35..=38 => (false, &[AddressKind::P2shP2wpkh]),
+        39..=42 => (false, &[AddressKind::P2wpkh]),
+        _ => return Err(ECDSAError::InvalidSignatureRecovery),
+    };
+    let recovery_id = (header - 27) % 4;
+    let sig = parse_compact(&signature[1..])?;
+
+    let digest = signed_message_hash(message.as_bytes());
+    let pubkey = match recover_from_digest(&digest, &sig, recovery_id) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Ok(false),
+    };
+
+    for &kind in kinds {
+        for network in [Network::Mainnet, Network::Testnet, Network::Regtest] {
+            if self::address(&pubkey, uncompressed, kind, network)? == address {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Parses a hash in the byte-reversed hex that block explorers and RPCs display, returning it
+/// in internal byte order.
+fn parse_display_hash(hex_hash: &str) -> Result<[u8; 32], ECDSAError> {
+    let mut hash: [u8; 32] = hex::decode(hex_hash.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ECDSAError::InvalidMerkleProof)?;
+    hash.reverse();
+    Ok(hash)
+}
+
+/// Computes a block's Merkle root from a transaction hash and its branch, in internal byte
+/// order. `index` is the transaction's position in the block, whose bits select at each level
+/// whether the running hash is the left or right child. Returns `None` if `index` has bits
+/// beyond the branch's depth.
+fn merkle_root_from_branch(leaf: [u8; 32], branch: &[[u8; 32]], index: u32) -> Option<[u8; 32]> {
+    if index.checked_shr(branch.len() as u32).unwrap_or(0) != 0 {
+        return None;
+    }
+    let root = branch.iter().enumerate().fold(leaf, |node, (level, sibling)| {
+        let (left, right) = if index.checked_shr(level as u32).unwrap_or(0) & 1 == 0 { (&node, sibling) } else { (sibling, &node) };
+        Sha256::digest(Sha256::new().chain_update(left).chain_update(right).finalize()).into()
+    });
+    Some(root)
+}
+
+/// Verifies that a transaction is included in a block, from the Merkle branch that Electrum's
+/// `blockchain.transaction.get_merkle` or Bitcoin Core's `gettxoutproof` supply.
+///
+/// The tree hashes pairs of nodes with double SHA-256, duplicating the last node of odd-sized
+/// levels. A 64-byte transaction could pass for an inner node, so callers should also check
+/// that `txid` is the hash of a transaction they hold or that the branch has the block's depth.
+///
+/// # Arguments:
+/// * `txid` - The transaction ID, as hex in the usual byte-reversed display order.
+/// * `merkle_branch` - The sibling hashes from the leaf up, in the same display order.
+/// * `block_merkle_root` - The root from the block header, in display order.
+/// * `index` - The transaction's position in the block, which orders each pair.
+///
+/// # Returns:
+/// * A boolean indicating whether the branch leads from `txid` to `block_merkle_root`.
+///
+/// # Errors:
+/// * `InvalidMerkleProof` if a hash is not 32 bytes of hex, or `index` does not fit a tree of
+///   the branch's depth.
+#[wasm_bindgen]
+pub fn verify_merkle_proof(txid: &str, merkle_branch: Vec<String>, block_merkle_root: &str, index: u32) -> Result<bool, ECDSAError> {
+    let leaf = parse_display_hash(txid)?;
+    let branch = merkle_branch.iter().map(|hash| parse_display_hash(hash)).collect::<Result<Vec<_>, _>>()?;
+    let root = merkle_root_from_branch(leaf, &branch, index).ok_or(ECDSAError::InvalidMerkleProof)?;
+    Ok(root == parse_display_hash(block_merkle_root)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_base64;
+    use crate::signer::sign_digest;
+    use crate::{ExtendedKey, Mnemonic};
+    use k256::ecdsa::SigningKey;
+
+    // The public key of private key 1
+    const COMPRESSED: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const UNCOMPRESSED: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                                483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn test_hash160() {
+        assert_eq!(hex::encode(hash160(&hex::decode(COMPRESSED).unwrap())), "751e76e8199196d454941c45d1b3a323f1433bd6");
+    }
+
+    #[test]
+    fn test_p2pkh() {
+        assert_eq!(address_from_pubkey(COMPRESSED, AddressKind::P2pkh, None).unwrap(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(address_from_pubkey(UNCOMPRESSED, AddressKind::P2pkh, None).unwrap(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+        assert!(address_from_pubkey(COMPRESSED, AddressKind::P2pkh, Some(Network::Testnet)).unwrap().starts_with(['m', 'n']));
+    }
+
+    #[test]
+    fn test_p2wpkh() {
+        assert_eq!(
+            address_from_pubkey(COMPRESSED, AddressKind::P2wpkh, None).unwrap(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        // Segwit commits to the compressed key whatever encoding is passed in
+        assert_eq!(
+            address_from_pubkey(UNCOMPRESSED, AddressKind::P2wpkh, Some(Network::Regtest)).unwrap(),
+            address_from_pubkey(COMPRESSED, AddressKind::P2wpkh, Some(Network::Regtest)).unwrap()
+        );
+        assert!(address_from_pubkey(&COMPRESSED[2..], AddressKind::P2wpkh, None).is_err());
+    }
+
+    #[test]
+    fn test_p2tr_bip86_vector() {
+        let mnemonic = Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        let key = ExtendedKey::from_seed(&mnemonic.to_seed(None), None)
+            .unwrap()
+            .derive_path("m/86'/0'/0'/0/0")
+            .unwrap();
+        assert_eq!(
+            address_from_pubkey(&key.public_key(), AddressKind::P2tr, None).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+    }
+
+    #[test]
+    fn test_p2sh_p2wpkh() {
+        // BIP-49 test vector: m/49'/1'/0'/0/0 of the all-"abandon" wallet
+        let mnemonic = Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        let key = ExtendedKey::from_seed(&mnemonic.to_seed(None), None)
+            .unwrap()
+            .derive_path("m/49'/1'/0'/0/0")
+            .unwrap();
+        assert_eq!(
+            address_from_pubkey(&key.public_key(), AddressKind::P2shP2wpkh, Some(Network::Testnet)).unwrap(),
+            "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2"
+        );
+    }
+
+    /// Signs `message` in the signed-message format with header base `header`.
+    fn sign_message(key: &SigningKey, message: &str, header: u8) -> String {
+        let (sig, recovery_id) = sign_digest(key, &signed_message_hash(message.as_bytes()), &[]).unwrap();
+        let mut signature = vec![header + recovery_id];
+        signature.extend_from_slice(sig.as_ref());
+        to_base64(&signature)
+    }
+
+    #[test]
+    fn test_verify_bitcoin_message() {
+        let key = SigningKey::from_bytes(&[0x42; 32]).unwrap();
+        let pubkey = hex::encode(key.verifying_key().to_bytes());
+        let p2pkh = address_from_pubkey(&pubkey, AddressKind::P2pkh, None).unwrap();
+        let p2wpkh = address_from_pubkey(&pubkey, AddressKind::P2wpkh, Some(Network::Testnet)).unwrap();
+
+        let signature = sign_message(&key, "hello", 31);
+        assert!(verify_bitcoin_message(&p2pkh, &signature, "hello").unwrap());
+        assert!(verify_bitcoin_message(&p2wpkh, &signature, "hello").unwrap());
+        assert!(!verify_bitcoin_message(&p2pkh, &signature, "hullo").unwrap());
+
+        // BIP-137 segwit header only matches the segwit address
+        let signature = sign_message(&key, "hello", 39);
+        assert!(verify_bitcoin_message(&p2wpkh, &signature, "hello").unwrap());
+        assert!(!verify_bitcoin_message(&p2pkh, &signature, "hello").unwrap());
+
+        // Uncompressed header commits to the uncompressed-key address
+        let signature = sign_message(&key, "hello", 27);
+        assert!(!verify_bitcoin_message(&p2pkh, &signature, "hello").unwrap());
+        let uncompressed = hex::encode(key.verifying_key().to_encoded_point(false).as_bytes());
+        let legacy = address_from_pubkey(&uncompressed, AddressKind::P2pkh, None).unwrap();
+        assert!(verify_bitcoin_message(&legacy, &signature, "hello").unwrap());
+
+        assert!(verify_bitcoin_message(&p2pkh, "not base64", "hello").is_err());
+        assert!(verify_bitcoin_message(&p2pkh, &to_base64(&[26; 65]), "hello").is_err());
+    }
+
+    #[test]
+    fn test_signed_message_hash_length_prefix() {
+        assert_eq!(compact_size(0xfc), [0xfc]);
+        assert_eq!(compact_size(0xfd), [0xfd, 0xfd, 0x00]);
+        assert_eq!(compact_size(0x10000), [0xfe, 0x00, 0x00, 0x01, 0x00]);
+        assert_ne!(signed_message_hash(b""), signed_message_hash(b"\0"));
+    }
+
+    // The transactions of block 100000 and its Merkle root
+    const BLOCK_100000: [&str; 4] = [
+        "8c14f0db3df150123e6f3dbbf30f8b955a8249b62ac1d1ff16284aefa3d06d87",
+        "fff2525b8931402dd09222c50775608f75787bd2b87e56995a7bdd30f79702c4",
+        "6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4",
+        "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d",
+    ];
+    const BLOCK_100000_ROOT: &str = "f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766";
+
+    fn display(hash: [u8; 32]) -> String {
+        hex::encode(hash.iter().rev().copied().collect::<Vec<_>>())
+    }
+
+    fn parent(left: &str, right: &str) -> String {
+        let (left, right) = (parse_display_hash(left).unwrap(), parse_display_hash(right).unwrap());
+        display(Sha256::digest(Sha256::new().chain_update(left).chain_update(right).finalize()).into())
+    }
+
+    #[test]
+    fn test_verify_merkle_proof() {
+        let pairs = [parent(BLOCK_100000[0], BLOCK_100000[1]), parent(BLOCK_100000[2], BLOCK_100000[3])];
+        for (index, txid) in BLOCK_100000.iter().enumerate() {
+            let branch = vec![BLOCK_100000[index ^ 1].to_string(), pairs[1 - index / 2].clone()];
+            assert!(verify_merkle_proof(txid, branch.clone(), BLOCK_100000_ROOT, index as u32).unwrap(), "{index}");
+            // The position orders each pair, so another index does not verify
+            assert!(!verify_merkle_proof(txid, branch, BLOCK_100000_ROOT, (index ^ 1) as u32).unwrap());
+        }
+
+        // The genesis block's only transaction is its own root
+        let genesis = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+        assert!(verify_merkle_proof(genesis, vec![], genesis, 0).unwrap());
+    }
+
+    #[test]
+    fn test_merkle_proof_odd_levels() {
+        // With three transactions the last is paired with itself
+        let root = parent(&parent(BLOCK_100000[0], BLOCK_100000[1]), &parent(BLOCK_100000[2], BLOCK_100000[2]));
+        let branch = vec![BLOCK_100000[2].to_string(), parent(BLOCK_100000[0], BLOCK_100000[1])];
+        assert!(verify_merkle_proof(BLOCK_100000[2], branch, &root, 2).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed_merkle_proofs() {
+        let branch = vec![BLOCK_100000[1].to_string()];
+        assert!(matches!(verify_merkle_proof(BLOCK_100000[0], branch.clone(), BLOCK_100000_ROOT, 2), Err(ECDSAError::InvalidMerkleProof)));
+        assert!(matches!(verify_merkle_proof(&BLOCK_100000[0][2..], branch.clone(), BLOCK_100000_ROOT, 0), Err(ECDSAError::InvalidMerkleProof)));
+        assert!(matches!(verify_merkle_proof(BLOCK_100000[0], vec!["zz".to_string()], BLOCK_100000_ROOT, 0), Err(ECDSAError::InvalidMerkleProof)));
+        assert!(!verify_merkle_proof(BLOCK_100000[0], branch, BLOCK_100000_ROOT, 0).unwrap());
+    }
+}
@@ -0,0 +1,246 @@
+//! Blind Schnorr signatures over secp256k1 whose unblinded results are ordinary BIP-340
+//! signatures, for privacy-preserving token issuance.
+//!
+//! 1. The signer commits to a nonce `R = k*G` with `BlindSigner::commit`.
+//! 2. The user blinds it into `R' = R + alpha*G + beta*P` with a `BlindingSession` and sends
+//!    the blinded challenge `c = H(R' || P || m) + beta`.
+//! 3. The signer answers `s = k + c*x` with `BlindSigner::sign`.
+//! 4. The user checks `s` and unblinds it into the signature `(R'.x, s + alpha)`, which the
+//!    signer cannot link to the session.
+//!
+//! Plain blind Schnorr signatures are forgeable when a signer runs many sessions
+//! concurrently (the ROS attack), so a `BlindSigner` keeps a single pending nonce and
+//! answers one challenge per commitment.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::elliptic_curve::AffineXCoordinate;
+use k256::{FieldBytes, ProjectivePoint, Scalar, U256};
+
+use crate::random::random_scalar;
+use crate::schnorr::{decode_point, has_even_y, lift_x, parse_x_only, tagged_hash};
+use crate::ECDSAError;
+
+fn parse_scalar(scalar: &str) -> Option<Scalar> {
+    let bytes: [u8; 32] = hex::decode(scalar).ok()?.try_into().ok()?;
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes)))
+}
+
+fn x_only(point: &ProjectivePoint) -> [u8; 32] {
+    point.to_affine().x().into()
+}
+
+/// The BIP-340 challenge `H(R.x || P.x || m)`.
+fn challenge(nonce: &ProjectivePoint, public_key: &[u8; 32], message: &[u8]) -> Scalar {
+    let e = tagged_hash("BIP0340/challenge", &[&x_only(nonce), public_key, message]);
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(e.into())
+}
+
+/// The signing side of the protocol.
+#[wasm_bindgen]
+pub struct BlindSigner {
+    /// The secret key, negated if needed so that its public key has an even y.
+    secret_key: Scalar,
+    public_key: [u8; 32],
+    pending_nonce: Option<Scalar>,
+}
+
+#[wasm_bindgen]
+impl BlindSigner {
+    /// Creates a signer from a secret key.
+    ///
+    /// # Arguments:
+    /// * `secret_key` - The 32-byte secret key, provided as a hex string.
+    ///
+    /// # Errors:
+    /// * `InvalidPrivateKey` if the key is zero or not below the group order.
+    #[wasm_bindgen(constructor)]
+    pub fn new(secret_key: &str) -> Result<BlindSigner, ECDSAError> {
+        let secret_key = parse_scalar(secret_key).filter(|key| !bool::from(key.is_zero())).ok_or(ECDSAError::InvalidPrivateKey)?;
+        let point = ProjectivePoint::GENERATOR * secret_key;
+        let secret_key = if has_even_y(&point.to_affine()) { secret_key } else { -secret_key };
+        Ok(BlindSigner { secret_key, public_key: x_only(&point), pending_nonce: None })
+    }
+
+    /// The 32-byte x-only public key that unblinded signatures verify under, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> String {
+        hex::encode(self.public_key)
+    }
+
+    /// Starts a session by committing to a fresh nonce, replacing any unanswered one.
+    ///
+    /// # Returns:
+    /// * The 33-byte compressed nonce point `R`, as a hex string, for the user.
+    ///
+    /// # Errors:
+    /// * `RandomnessUnavailable` if no randomness is available.
+    pub fn commit(&mut self) -> Result<String, ECDSAError> {
+        let nonce = *random_scalar()?;
+        self.pending_nonce = Some(nonce);
+        Ok(hex::encode((ProjectivePoint::GENERATOR * nonce).to_affine().to_bytes()))
+    }
+
+    /// Answers the user's blinded challenge, consuming the pending nonce.
+    ///
+    /// # Arguments:
+    /// * `blinded_challenge` - The 32-byte challenge from `BlindingSession`, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * The 32-byte blind signature `s`, as a hex string.
+    ///
+    /// # Errors:
+    /// * `NoPendingNonce` if `commit` has not been called since the last signature.
+    /// * `InvalidBlindSignature` if the challenge is not a scalar.
+    pub fn sign(&mut self, blinded_challenge: &str) -> Result<String, ECDSAError> {
+        let challenge = parse_scalar(blinded_challenge).ok_or(ECDSAError::InvalidBlindSignature)?;
+        let nonce = self.pending_nonce.take().ok_or(ECDSAError::NoPendingNonce)?;
+        Ok(hex::encode((nonce + challenge * self.secret_key).to_bytes()))
+    }
+}
+
+/// The user side of the protocol: blinds the signer's nonce and unblinds its answer.
+#[wasm_bindgen]
+pub struct BlindingSession {
+    public_key: [u8; 32],
+    signer_nonce: ProjectivePoint,
+    blinded_nonce: ProjectivePoint,
+    alpha: Scalar,
+    challenge: Scalar,
+}
+
+impl BlindingSession {
+    /// Blinds with factors from `next_factors` until the blinded nonce has the even y that
+    /// BIP-340 requires.
+    fn with_factors(
+        public_key: &str,
+        signer_nonce: &str,
+        message: &str,
+        mut next_factors: impl FnMut() -> Result<(Scalar, Scalar), ECDSAError>,
+    ) -> Result<BlindingSession, ECDSAError> {
+        let public_key = parse_x_only(&hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?)?;
+        let key_point = ProjectivePoint::from(lift_x(&public_key)?);
+        let signer_nonce = hex::decode(signer_nonce).ok().and_then(|bytes| decode_point(&bytes)).ok_or(ECDSAError::InvalidBlindSignature)?;
+        let message = hex::decode(message).map_err(|_| ECDSAError::InvalidDigestLength)?;
+
+        loop {
+            let (alpha, beta) = next_factors()?;
+            let blinded_nonce = signer_nonce + ProjectivePoint::GENERATOR * alpha + key_point * beta;
+            if has_even_y(&blinded_nonce.to_affine()) {
+                let challenge = challenge(&blinded_nonce, &public_key, &message) + beta;
+                return Ok(BlindingSession { public_key, signer_nonce, blinded_nonce, alpha, challenge });
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl BlindingSession {
+    /// Blinds the signer's nonce for a message.
+    ///
+    /// # Arguments:
+    /// * `public_key` - The signer's 32-byte x-only public key, provided as a hex string.
+    /// * `signer_nonce` - The 33-byte nonce point from `BlindSigner::commit`, provided as a hex string.
+    /// * `message` - The message to be signed, provided as a hex string and signed as is.
+    ///
+    /// # Errors:
+    /// * Returns an error if the key, nonce or message is malformed or no randomness is available.
+    #[wasm_bindgen(constructor)]
+    pub fn new(public_key: &str, signer_nonce: &str, message: &str) -> Result<BlindingSession, ECDSAError> {
+        BlindingSession::with_factors(public_key, signer_nonce, message, || Ok((*random_scalar()?, *random_scalar()?)))
+    }
+
+    /// The 32-byte blinded challenge to send to the signer, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn blinded_challenge(&self) -> String {
+        hex::encode(self.challenge.to_bytes())
+    }
+
+    /// Checks the signer's blind signature and unblinds it.
+    ///
+    /// # Arguments:
+    /// * `blind_signature` - The 32-byte answer from `BlindSigner::sign`, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * The 64-byte BIP-340 signature of the message, as a hex string.
+    ///
+    /// # Errors:
+    /// * `InvalidBlindSignature` if the answer is malformed or does not match the challenge.
+    pub fn unblind(&self, blind_signature: &str) -> Result<String, ECDSAError> {
+        let s = parse_scalar(blind_signature).ok_or(ECDSAError::InvalidBlindSignature)?;
+        let key_point = ProjectivePoint::from(lift_x(&self.public_key)?);
+        if ProjectivePoint::GENERATOR * s != self.signer_nonce + key_point * self.challenge {
+            return Err(ECDSAError::InvalidBlindSignature);
+        }
+        let s = s + self.alpha;
+        Ok(hex::encode([x_only(&self.blinded_nonce).as_slice(), &s.to_bytes()].concat()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schnorr::verify_schnorr;
+
+    const SECRET_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000003";
+
+    fn issue(signer: &mut BlindSigner, message: &str) -> (BlindingSession, String) {
+        let nonce = signer.commit().unwrap();
+        let session = BlindingSession::new(&signer.public_key(), &nonce, message).unwrap();
+        let blind_signature = signer.sign(&session.blinded_challenge()).unwrap();
+        (session, blind_signature)
+    }
+
+    #[test]
+    fn test_blind_signature_round_trip() {
+        let mut signer = BlindSigner::new(SECRET_KEY).unwrap();
+        // BIP-340 test vector 0 uses the same key
+        assert_eq!(signer.public_key(), "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9");
+        let message = hex::encode(b"token serial 42");
+        for _ in 0..4 {
+            let (session, blind_signature) = issue(&mut signer, &message);
+            let signature = hex::decode(session.unblind(&blind_signature).unwrap()).unwrap();
+            let public_key = parse_x_only(&hex::decode(signer.public_key()).unwrap()).unwrap();
+            assert!(verify_schnorr(&public_key, &hex::decode(&message).unwrap(), &signature).unwrap());
+            // The signer saw neither the final nonce nor the final s
+            assert_ne!(hex::encode(&signature[32..]), blind_signature);
+        }
+    }
+
+    #[test]
+    fn test_odd_key_and_retried_blinding() {
+        // The key for secret 2 has an odd y, so the signer negates its secret
+        let mut signer = BlindSigner::new(&format!("{:064x}", 2)).unwrap();
+        let nonce = signer.commit().unwrap();
+        // The first factors give an odd blinded nonce about half the time; count the attempts
+        let mut attempts = 0;
+        let session = BlindingSession::with_factors(&signer.public_key(), &nonce, "", || {
+            attempts += 1;
+            Ok((Scalar::from(attempts as u64), Scalar::from(attempts as u64 * 7)))
+        })
+        .unwrap();
+        assert!(attempts >= 1);
+        let signature = session.unblind(&signer.sign(&session.blinded_challenge()).unwrap()).unwrap();
+        let public_key = parse_x_only(&hex::decode(signer.public_key()).unwrap()).unwrap();
+        assert!(verify_schnorr(&public_key, &[], &hex::decode(signature).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_signer_answers_once_per_commitment() {
+        let mut signer = BlindSigner::new(SECRET_KEY).unwrap();
+        let (session, _) = issue(&mut signer, "");
+        assert!(matches!(signer.sign(&session.blinded_challenge()), Err(ECDSAError::NoPendingNonce)));
+        assert!(matches!(BlindSigner::new(&"00".repeat(32)), Err(ECDSAError::InvalidPrivateKey)));
+    }
+
+    #[test]
+    fn test_rejects_wrong_blind_signature() {
+        let mut signer = BlindSigner::new(SECRET_KEY).unwrap();
+        let (first, _) = issue(&mut signer, "01");
+        let (_, second_signature) = issue(&mut signer, "02");
+        assert!(matches!(first.unblind(&second_signature), Err(ECDSAError::InvalidBlindSignature)));
+        assert!(matches!(first.unblind("ff"), Err(ECDSAError::InvalidBlindSignature)));
+    }
+}
@@ -0,0 +1,250 @@
+//! BLS signatures on BLS12-381 in the minimal-public-key-size variant used by Ethereum
+//! consensus: 48-byte compressed G1 public keys, 96-byte compressed G2 signatures, and
+//! messages hashed to G2 with the proof-of-possession ciphersuite
+//! `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`. The curve arithmetic and pairing come from
+//! the `bls12_381` crate.
+//!
+//! A signature verifies when `e(P, H(m)) == e(G1, S)`, checked as a product of pairings that
+//! shares one final exponentiation. Aggregation sums signatures, and for a common message
+//! also public keys, so aggregates need the signers' proofs of possession to have been
+//! checked beforehand, as the beacon chain does at deposit time.
+
+use wasm_bindgen::prelude::*;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt};
+
+use crate::ECDSAError;
+
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+fn hash_to_g2(message: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<ExpandMsgXmd<sha2_09::Sha256>>>::hash_to_curve(message, DST)
+}
+
+/// Parses a public key: a compressed G1 point in the subgroup, other than the point at infinity.
+fn parse_public_key(public_key: &str) -> Result<G1Affine, ECDSAError> {
+    hex::decode(public_key)
+        .ok()
+        .and_then(|bytes| <[u8; 48]>::try_from(bytes).ok())
+        .and_then(|bytes| Option::from(G1Affine::from_compressed(&bytes)))
+        .filter(|point: &G1Affine| !bool::from(point.is_identity()))
+        .ok_or(ECDSAError::InvalidPublicKey)
+}
+
+fn parse_public_keys(public_keys: &[String]) -> Result<Vec<G1Affine>, ECDSAError> {
+    if public_keys.is_empty() {
+        return Err(ECDSAError::InvalidBlsAggregate);
+    }
+    public_keys.iter().map(|public_key| parse_public_key(public_key)).collect()
+}
+
+/// Parses a signature: a compressed G2 point in the subgroup.
+fn parse_signature(signature: &str) -> Result<G2Affine, ECDSAError> {
+    hex::decode(signature)
+        .ok()
+        .and_then(|bytes| <[u8; 96]>::try_from(bytes).ok())
+        .and_then(|bytes| Option::from(G2Affine::from_compressed(&bytes)))
+        .ok_or(ECDSAError::InvalidBlsSignature)
+}
+
+fn parse_message(message: &str) -> Result<Vec<u8>, ECDSAError> {
+    hex::decode(message).map_err(|_| ECDSAError::InvalidDigestLength)
+}
+
+/// Checks `e(P_1, H(m_1)) * ... * e(P_n, H(m_n)) == e(G1, signature)`.
+fn verify_pairs(signed: &[(G1Affine, &[u8])], signature: &G2Affine) -> bool {
+    let mut pairs: Vec<(G1Affine, G2Prepared)> = signed
+        .iter()
+        .map(|(public_key, message)| (*public_key, G2Prepared::from(G2Affine::from(hash_to_g2(message)))))
+        .collect();
+    pairs.push((-G1Affine::generator(), G2Prepared::from(*signature)));
+    let terms: Vec<(&G1Affine, &G2Prepared)> = pairs.iter().map(|(p, q)| (p, q)).collect();
+    multi_miller_loop(&terms).final_exponentiation() == Gt::identity()
+}
+
+fn sum_public_keys(public_keys: &[G1Affine]) -> G1Affine {
+    public_keys.iter().fold(G1Projective::identity(), |sum, public_key| sum + public_key).into()
+}
+
+/// Verifies a BLS signature.
+///
+/// # Arguments:
+/// * `public_key` - The signer's 48-byte compressed public key, provided as a hex string.
+/// * `message` - The signed message, provided as a hex string (e.g. a 32-byte signing root).
+/// * `signature` - The 96-byte compressed signature, provided as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the signature is valid.
+///
+/// # Errors:
+/// * `InvalidPublicKey` if the key is malformed, outside the subgroup or the point at infinity.
+/// * `InvalidBlsSignature` if the signature is malformed or outside the subgroup.
+#[wasm_bindgen]
+pub fn verify_bls_signature(public_key: &str, message: &str, signature: &str) -> Result<bool, ECDSAError> {
+    let public_key = parse_public_key(public_key)?;
+    let message = parse_message(message)?;
+    let signature = parse_signature(signature)?;
+    Ok(verify_pairs(&[(public_key, &message)], &signature))
+}
+
+/// Verifies an aggregate signature by several signers over the same message, as for
+/// beacon chain attestations and sync committee contributions (`FastAggregateVerify`).
+///
+/// # Arguments:
+/// * `public_keys` - The signers' 48-byte compressed public keys, provided as hex strings.
+///   Each key's proof of possession must already have been verified.
+/// * `message` - The common message, provided as a hex string.
+/// * `signature` - The 96-byte compressed aggregate signature, provided as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the aggregate signature is valid.
+///
+/// # Errors:
+/// * `InvalidBlsAggregate` if there are no public keys.
+/// * `InvalidPublicKey` or `InvalidBlsSignature` if a key or the signature is invalid.
+#[wasm_bindgen]
+pub fn verify_bls_fast_aggregate(public_keys: Vec<String>, message: &str, signature: &str) -> Result<bool, ECDSAError> {
+    let public_key = sum_public_keys(&parse_public_keys(&public_keys)?);
+    let message = parse_message(message)?;
+    let signature = parse_signature(signature)?;
+    Ok(verify_pairs(&[(public_key, &message)], &signature))
+}
+
+/// Verifies an aggregate signature in which each signer signed its own message
+/// (`AggregateVerify`).
+///
+/// # Arguments:
+/// * `public_keys` - The signers' 48-byte compressed public keys, provided as hex strings.
+/// * `messages` - The message each signer signed, in the same order, provided as hex strings.
+/// * `signature` - The 96-byte compressed aggregate signature, provided as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the aggregate signature is valid.
+///
+/// # Errors:
+/// * `InvalidBlsAggregate` if there are no public keys or the counts differ.
+/// * `InvalidPublicKey` or `InvalidBlsSignature` if a key or the signature is invalid.
+#[wasm_bindgen]
+pub fn verify_bls_aggregate(public_keys: Vec<String>, messages: Vec<String>, signature: &str) -> Result<bool, ECDSAError> {
+    let public_keys = parse_public_keys(&public_keys)?;
+    if messages.len() != public_keys.len() {
+        return Err(ECDSAError::InvalidBlsAggregate);
+    }
+    let messages = messages.iter().map(|message| parse_message(message)).collect::<Result<Vec<_>, _>>()?;
+    let signature = parse_signature(signature)?;
+    let signed: Vec<(G1Affine, &[u8])> = public_keys.into_iter().zip(messages.iter().map(Vec::as_slice)).collect();
+    Ok(verify_pairs(&signed, &signature))
+}
+
+/// Aggregates signatures into one.
+///
+/// # Arguments:
+/// * `signatures` - The 96-byte compressed signatures, provided as hex strings.
+///
+/// # Returns:
+/// * The 96-byte compressed aggregate signature, as a hex string.
+///
+/// # Errors:
+/// * `InvalidBlsAggregate` if there are no signatures.
+/// * `InvalidBlsSignature` if a signature is malformed or outside the subgroup.
+#[wasm_bindgen]
+pub fn aggregate_bls_signatures(signatures: Vec<String>) -> Result<String, ECDSAError> {
+    if signatures.is_empty() {
+        return Err(ECDSAError::InvalidBlsAggregate);
+    }
+    let mut aggregate = G2Projective::identity();
+    for signature in &signatures {
+        aggregate += parse_signature(signature)?;
+    }
+    Ok(hex::encode(G2Affine::from(aggregate).to_compressed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The Ethereum consensus test keys and messages, with signatures from the consensus-spec
+    // BLS test vectors
+    const PUBLIC_KEYS: [&str; 3] = [
+        "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+        "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+        "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+    ];
+    const SIGNATURE: &str = "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9";
+    const FAST_AGGREGATE_SIGNATURE: &str = "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930";
+
+    fn keys() -> Vec<String> {
+        PUBLIC_KEYS.iter().map(|key| key.to_string()).collect()
+    }
+
+    fn messages() -> Vec<String> {
+        ["00", "56", "ab"].iter().map(|byte| byte.repeat(32)).collect()
+    }
+
+    /// Signs a message with the test key at `index`.
+    fn sign(index: usize, message: &str) -> String {
+        let secret_keys = [
+            "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+            "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+            "328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216",
+        ];
+        // Scalars are encoded little-endian
+        let mut secret_key: [u8; 32] = hex::decode(secret_keys[index]).unwrap().try_into().unwrap();
+        secret_key.reverse();
+        let point = hash_to_g2(&hex::decode(message).unwrap()) * bls12_381::Scalar::from_bytes(&secret_key).unwrap();
+        hex::encode(G2Affine::from(point).to_compressed())
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let message = "ab".repeat(32);
+        assert_eq!(sign(2, &message), SIGNATURE);
+        assert!(verify_bls_signature(PUBLIC_KEYS[2], &message, SIGNATURE).unwrap());
+        assert!(!verify_bls_signature(PUBLIC_KEYS[1], &message, SIGNATURE).unwrap());
+        assert!(!verify_bls_signature(PUBLIC_KEYS[2], &"56".repeat(32), SIGNATURE).unwrap());
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify() {
+        let message = "ab".repeat(32);
+        let signatures = (0..3).map(|index| sign(index, &message)).collect();
+        assert_eq!(aggregate_bls_signatures(signatures).unwrap(), FAST_AGGREGATE_SIGNATURE);
+        assert!(verify_bls_fast_aggregate(keys(), &message, FAST_AGGREGATE_SIGNATURE).unwrap());
+        // A missing signer
+        assert!(!verify_bls_fast_aggregate(keys()[..2].to_vec(), &message, FAST_AGGREGATE_SIGNATURE).unwrap());
+        // A single signer is plain verification
+        assert!(verify_bls_fast_aggregate(vec![PUBLIC_KEYS[2].into()], &message, SIGNATURE).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let messages = messages();
+        let signatures = messages.iter().enumerate().map(|(index, message)| sign(index, message)).collect();
+        let aggregate = aggregate_bls_signatures(signatures).unwrap();
+        assert!(verify_bls_aggregate(keys(), messages.clone(), &aggregate).unwrap());
+        let mut swapped = messages.clone();
+        swapped.swap(0, 1);
+        assert!(!verify_bls_aggregate(keys(), swapped, &aggregate).unwrap());
+        assert!(matches!(verify_bls_aggregate(keys(), messages[..2].to_vec(), &aggregate), Err(ECDSAError::InvalidBlsAggregate)));
+        assert!(matches!(verify_bls_aggregate(vec![], vec![], &aggregate), Err(ECDSAError::InvalidBlsAggregate)));
+    }
+
+    #[test]
+    fn test_rejects_invalid_points() {
+        let message = "ab".repeat(32);
+        // The point at infinity is never a valid public key
+        let infinity_key = format!("c0{}", "00".repeat(47));
+        assert!(matches!(verify_bls_signature(&infinity_key, &message, SIGNATURE), Err(ECDSAError::InvalidPublicKey)));
+        // x = 0 is a valid G1 curve point outside the prime-order subgroup
+        let low_order_key = format!("80{}", "00".repeat(47));
+        assert!(matches!(verify_bls_signature(&low_order_key, &message, SIGNATURE), Err(ECDSAError::InvalidPublicKey)));
+
+        let mut tampered = hex::decode(SIGNATURE).unwrap();
+        tampered[95] ^= 1;
+        assert!(matches!(verify_bls_signature(PUBLIC_KEYS[2], &message, &hex::encode(tampered)), Err(ECDSAError::InvalidBlsSignature)));
+        assert!(matches!(verify_bls_signature(PUBLIC_KEYS[2], &message, &SIGNATURE[2..]), Err(ECDSAError::InvalidBlsSignature)));
+        // The infinity signature decodes but verifies nothing
+        let infinity_signature = format!("c0{}", "00".repeat(95));
+        assert!(!verify_bls_signature(PUBLIC_KEYS[2], &message, &infinity_signature).unwrap());
+    }
+}
@@ -0,0 +1,358 @@
+//! Minimal CBOR (RFC 8949) reader and writer used by the COSE and WebAuthn features.
+//! Only definite-length items are accepted, which covers the deterministic encodings that
+//! COSE and CTAP2 require. Map entry order is preserved.
+
+use std::collections::BTreeSet;
+
+use crate::ECDSAError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    /// Major types 0 and 1, covering -2^64 to 2^64 - 1
+    Integer(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(u64, Box<Value>),
+    Bool(bool),
+    Null,
+    Undefined,
+    Float(f64),
+}
+
+impl Value {
+    /// Looks up a map entry by integer label. Returns `None` for other value kinds.
+    pub(crate) fn get(&self, label: i64) -> Option<&Value> {
+        self.get_entry(|key| *key == Value::Integer(label as i128))
+    }
+
+    /// Looks up a map entry by text key. Returns `None` for other value kinds.
+    pub(crate) fn get_text(&self, name: &str) -> Option<&Value> {
+        self.get_entry(|key| key.as_text() == Some(name))
+    }
+
+    fn get_entry(&self, matches: impl Fn(&Value) -> bool) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries.iter().find(|(key, _)| matches(key)).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Reads an integer that fits in an `i64`.
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Integer(value) => (*value).try_into().ok(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_map(&self) -> Option<&[(Value, Value)]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes a complete CBOR data item.
+pub(crate) fn decode(input: &[u8]) -> Result<Value, ECDSAError> {
+    let (value, length) = decode_prefix(input)?;
+    if length != input.len() {
+        return Err(ECDSAError::InvalidCbor);
+    }
+    Ok(value)
+}
+
+/// Decodes the data item at the start of `input`, returning it with its encoded length.
+pub(crate) fn decode_prefix(input: &[u8]) -> Result<(Value, usize), ECDSAError> {
+    let mut decoder = Decoder { bytes: input, pos: 0 };
+    let value = decoder.value(0)?;
+    Ok((value, decoder.pos))
+}
+
+/// Nesting limit, to keep hostile input from exhausting the wasm stack.
+const MAX_DEPTH: usize = 128;
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, count: usize) -> Result<&[u8], ECDSAError> {
+        let end = self.pos.checked_add(count).ok_or(ECDSAError::InvalidCbor)?;
+        let bytes = self.bytes.get(self.pos..end).ok_or(ECDSAError::InvalidCbor)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Reads the argument that follows an initial byte with additional information `info`.
+    fn argument(&mut self, info: u8) -> Result<u64, ECDSAError> {
+        let width = match info {
+            0..=23 => return Ok(info as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            // 28-30 are reserved and 31 marks indefinite lengths
+            _ => return Err(ECDSAError::InvalidCbor),
+        };
+        Ok(self.take(width)?.iter().fold(0u64, |value, &b| (value << 8) | b as u64))
+    }
+
+    /// Reads a length, which must fit in the remaining input to bound allocations.
+    fn length(&mut self, info: u8) -> Result<usize, ECDSAError> {
+        let length = self.argument(info)?;
+        if length > (self.bytes.len() - self.pos) as u64 {
+            return Err(ECDSAError::InvalidCbor);
+        }
+        Ok(length as usize)
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ECDSAError> {
+        if depth > MAX_DEPTH {
+            return Err(ECDSAError::InvalidCbor);
+        }
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        match major {
+            0 => Ok(Value::Integer(self.argument(info)? as i128)),
+            1 => Ok(Value::Integer(-1 - self.argument(info)? as i128)),
+            2 => {
+                let length = self.length(info)?;
+                Ok(Value::Bytes(self.take(length)?.to_vec()))
+            }
+            3 => {
+                let length = self.length(info)?;
+                let text = std::str::from_utf8(self.take(length)?).map_err(|_| ECDSAError::InvalidCbor)?;
+                Ok(Value::Text(text.to_string()))
+            }
+            4 => {
+                let length = self.length(info)?;
+                (0..length).map(|_| self.value(depth + 1)).collect::<Result<_, _>>().map(Value::Array)
+            }
+            5 => {
+                let length = self.length(info)?;
+                let mut entries = Vec::with_capacity(length);
+                // Keys are compared in their minimal encoding, so a duplicate can't hide
+                // behind a wider argument
+                let mut keys = BTreeSet::new();
+                for _ in 0..length {
+                    let key = self.value(depth + 1)?;
+                    if !keys.insert(encode(&key)) {
+                        return Err(ECDSAError::InvalidCbor);
+                    }
+                    entries.push((key, self.value(depth + 1)?));
+                }
+                Ok(Value::Map(entries))
+            }
+            6 => {
+                let tag = self.argument(info)?;
+                Ok(Value::Tag(tag, Box::new(self.value(depth + 1)?)))
+            }
+            _ => match info {
+                20 => Ok(Value::Bool(false)),
+                21 => Ok(Value::Bool(true)),
+                22 => Ok(Value::Null),
+                23 => Ok(Value::Undefined),
+                25 => {
+                    let bits = self.argument(info)? as u16;
+                    Ok(Value::Float(half_to_f64(bits)))
+                }
+                26 => Ok(Value::Float(f32::from_bits(self.argument(info)? as u32) as f64)),
+                27 => Ok(Value::Float(f64::from_bits(self.argument(info)?))),
+                _ => Err(ECDSAError::InvalidCbor),
+            },
+        }
+    }
+}
+
+/// Widens an IEEE 754 half-precision float.
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let fraction = (bits & 0x3ff) as f64;
+    sign * match exponent {
+        0 => fraction * 2f64.powi(-24),
+        31 if fraction == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + fraction / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+/// Writes an initial byte and argument in the shortest form.
+fn write_head(output: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => output.push(major | argument as u8),
+        24..=0xff => output.extend([major | 24, argument as u8]),
+        0x100..=0xffff => {
+            output.push(major | 25);
+            output.extend((argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            output.push(major | 26);
+            output.extend((argument as u32).to_be_bytes());
+        }
+        _ => {
+            output.push(major | 27);
+            output.extend(argument.to_be_bytes());
+        }
+    }
+}
+
+/// Encodes a value with definite lengths and the shortest argument forms. Map entries are
+/// written in their stored order, so callers building deterministic encodings sort them first.
+pub(crate) fn encode(value: &Value) -> Vec<u8> {
+    let mut output = Vec::new();
+    encode_into(&mut output, value);
+    output
+}
+
+fn encode_into(output: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Integer(value) if *value >= 0 => write_head(output, 0, *value as u64),
+        Value::Integer(value) => write_head(output, 1, (-1 - *value) as u64),
+        Value::Bytes(bytes) => {
+            write_head(output, 2, bytes.len() as u64);
+            output.extend_from_slice(bytes);
+        }
+        Value::Text(text) => {
+            write_head(output, 3, text.len() as u64);
+            output.extend_from_slice(text.as_bytes());
+        }
+        Value::Array(items) => {
+            write_head(output, 4, items.len() as u64);
+            items.iter().for_each(|item| encode_into(output, item));
+        }
+        Value::Map(entries) => {
+            write_head(output, 5, entries.len() as u64);
+            for (key, value) in entries {
+                encode_into(output, key);
+                encode_into(output, value);
+            }
+        }
+        Value::Tag(tag, value) => {
+            write_head(output, 6, *tag);
+            encode_into(output, value);
+        }
+        Value::Bool(value) => output.push(if *value { 0xf5 } else { 0xf4 }),
+        Value::Null => output.push(0xf6),
+        Value::Undefined => output.push(0xf7),
+        Value::Float(value) => {
+            output.push(0xfb);
+            output.extend(value.to_bits().to_be_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_items() {
+        // Examples from RFC 8949 appendix A
+        assert_eq!(decode(&hex::decode("1bffffffffffffffff").unwrap()).unwrap(), Value::Integer(u64::MAX as i128));
+        assert_eq!(decode(&hex::decode("3bffffffffffffffff").unwrap()).unwrap(), Value::Integer(-(1 << 64)));
+        assert_eq!(decode(&hex::decode("f93c00").unwrap()).unwrap(), Value::Float(1.0));
+        assert_eq!(decode(&hex::decode("f90001").unwrap()).unwrap(), Value::Float(5.960464477539063e-8));
+        assert_eq!(decode(&hex::decode("fa47c35000").unwrap()).unwrap(), Value::Float(100000.0));
+
+        let value = decode(&hex::decode("a201613102820203").unwrap()).unwrap();
+        assert_eq!(value.get(1).unwrap(), &Value::Text("1".to_string()));
+        assert_eq!(value.get(2).and_then(Value::as_array).map(<[Value]>::len), Some(2));
+        assert_eq!(value.get(3), None);
+        let value = decode(&hex::decode("a26161016162820203").unwrap()).unwrap();
+        assert_eq!(value.get_text("a").and_then(Value::as_i64), Some(1));
+        assert_eq!(value.get_text("b").and_then(Value::as_array).map(<[Value]>::len), Some(2));
+        assert_eq!(value.get(1), None);
+
+        let value = decode(&hex::decode("c074323031332d30332d32315432303a30343a30305a").unwrap()).unwrap();
+        assert_eq!(value, Value::Tag(0, Box::new(Value::Text("2013-03-21T20:04:00Z".to_string()))));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        for encoding in [
+            "00",
+            "17",
+            "1818",
+            "190100",
+            "1a000f4240",
+            "1b000000e8d4a51000",
+            "20",
+            "3903e7",
+            "4401020304",
+            "6449455446",
+            "83010203",
+            "a201020304",
+            "d8184100",
+            "f4f5",
+        ] {
+            let bytes = hex::decode(encoding).unwrap();
+            let (value, length) = decode_prefix(&bytes).unwrap();
+            assert_eq!(encode(&value), bytes[..length], "{encoding}");
+        }
+    }
+
+    #[test]
+    fn test_reject_malformed() {
+        for encoding in [
+            "",
+            // Truncated argument, string and array
+            "19",
+            "44010203",
+            "8201",
+            // Indefinite lengths and reserved values
+            "5f44aabbccddff",
+            "9fff",
+            "1c",
+            "f8",
+            // Invalid UTF-8 and duplicate map keys, the second in a wider encoding
+            "62c328",
+            "a201020103",
+            "a20001180002",
+            // Trailing data
+            "0000",
+        ] {
+            assert!(matches!(decode(&hex::decode(encoding).unwrap()), Err(ECDSAError::InvalidCbor)), "{encoding}");
+        }
+        // A huge declared length is rejected before allocating
+        assert!(decode(&hex::decode("9b00000000ffffffff").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_large_map() {
+        let entries: Vec<(Value, Value)> = (0..100_000).map(|i| (Value::Integer(i), Value::Null)).collect();
+        let mut encoded = encode(&Value::Map(entries.clone()));
+        assert_eq!(decode(&encoded).unwrap(), Value::Map(entries));
+
+        // Replace the last entry (key 99999) with key 0 in a four-byte argument
+        let last = encoded.len() - 6;
+        encoded[last..].copy_from_slice(&hex::decode("1a00000000f6").unwrap());
+        assert!(matches!(decode(&encoded), Err(ECDSAError::InvalidCbor)));
+    }
+}
@@ -0,0 +1,219 @@
+//! ChaCha20-Poly1305 authenticated encryption (RFC 8439) and its extended-nonce variant
+//! XChaCha20-Poly1305 (draft-irtf-cfrg-xchacha), using the RustCrypto `chacha20poly1305` crate.
+//!
+//! ChaCha20 is built from 32-bit additions, rotations and XORs and Poly1305 from fixed-width
+//! multiplications, so neither needs table lookups or AES hardware to run in constant time.
+//! XChaCha20's 24-byte nonces are long enough to be drawn at random without risk of reuse.
+
+use wasm_bindgen::prelude::*;
+
+use chacha20poly1305::aead::consts::U16;
+use chacha20poly1305::aead::generic_array::GenericArray;
+use chacha20poly1305::aead::{AeadCore, AeadInPlace, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+
+use crate::ECDSAError;
+
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+pub(crate) const XNONCE_LENGTH: usize = 24;
+pub(crate) const TAG_LENGTH: usize = 16;
+
+/// Encrypts with either construction, returning the ciphertext and the tag.
+fn seal<C>(cipher: C, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, [u8; TAG_LENGTH]), ECDSAError>
+where
+    C: AeadInPlace + AeadCore<TagSize = U16>,
+{
+    let mut buffer = plaintext.to_vec();
+    let tag = cipher
+        .encrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer)
+        .map_err(|_| ECDSAError::EncryptionFailed)?;
+    Ok((buffer, tag.into()))
+}
+
+/// Authenticates and decrypts with either construction.
+fn open<C>(cipher: C, nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, ECDSAError>
+where
+    C: AeadInPlace + AeadCore<TagSize = U16>,
+{
+    if tag.len() != TAG_LENGTH {
+        return Err(ECDSAError::DecryptionFailed);
+    }
+    let mut buffer = ciphertext.to_vec();
+    cipher
+        .decrypt_in_place_detached(GenericArray::from_slice(nonce), aad, &mut buffer, GenericArray::from_slice(tag))
+        .map_err(|_| ECDSAError::DecryptionFailed)?;
+    Ok(buffer)
+}
+
+/// Encrypts with XChaCha20-Poly1305, returning the ciphertext and the tag.
+pub(crate) fn xchacha20_poly1305_seal(
+    key: &[u8; KEY_LENGTH],
+    nonce: &[u8; XNONCE_LENGTH],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<(Vec<u8>, [u8; TAG_LENGTH]), ECDSAError> {
+    seal(XChaCha20Poly1305::new(key.into()), nonce, aad, plaintext)
+}
+
+/// Authenticates and decrypts with XChaCha20-Poly1305.
+pub(crate) fn xchacha20_poly1305_open(
+    key: &[u8; KEY_LENGTH],
+    nonce: &[u8; XNONCE_LENGTH],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8],
+) -> Result<Vec<u8>, ECDSAError> {
+    open(XChaCha20Poly1305::new(key.into()), nonce, aad, ciphertext, tag)
+}
+
+fn parse_key(key: &[u8]) -> Result<&[u8; KEY_LENGTH], ECDSAError> {
+    key.try_into().map_err(|_| ECDSAError::InvalidSymmetricKey)
+}
+
+fn parse_nonce<const N: usize>(nonce: &[u8]) -> Result<&[u8; N], ECDSAError> {
+    nonce.try_into().map_err(|_| ECDSAError::InvalidNonce)
+}
+
+/// Splits `ciphertext || tag`.
+fn split_tag(data: &[u8]) -> Result<(&[u8], &[u8]), ECDSAError> {
+    let body_length = data.len().checked_sub(TAG_LENGTH).ok_or(ECDSAError::DecryptionFailed)?;
+    Ok(data.split_at(body_length))
+}
+
+/// Encrypts and authenticates data with ChaCha20-Poly1305.
+///
+/// # Arguments:
+/// * `key` - The 32-byte key.
+/// * `nonce` - The 12-byte nonce; it must never repeat under the same key.
+/// * `aad` - Additional data that is authenticated but not encrypted; pass an empty array for none.
+/// * `data` - The plaintext.
+///
+/// # Returns:
+/// * The ciphertext followed by the 16-byte tag (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidSymmetricKey` or `InvalidNonce` if the key or nonce has the wrong length.
+/// * `EncryptionFailed` if the plaintext exceeds the 256 GiB limit.
+#[wasm_bindgen]
+pub fn chacha20_poly1305_encrypt(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let nonce: &[u8; NONCE_LENGTH] = parse_nonce(nonce)?;
+    let (mut ciphertext, tag) = seal(ChaCha20Poly1305::new(parse_key(key)?.into()), nonce, aad, data)?;
+    ciphertext.extend_from_slice(&tag);
+    Ok(ciphertext)
+}
+
+/// Authenticates and decrypts data encrypted with ChaCha20-Poly1305.
+///
+/// # Arguments:
+/// * `key` - The 32-byte key.
+/// * `nonce` - The 12-byte nonce used for encryption.
+/// * `aad` - The additional data used for encryption.
+/// * `data` - The ciphertext followed by the 16-byte tag.
+///
+/// # Returns:
+/// * The plaintext (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidSymmetricKey` or `InvalidNonce` if the key or nonce has the wrong length.
+/// * `DecryptionFailed` if the data is too short for a tag or fails authentication.
+#[wasm_bindgen]
+pub fn chacha20_poly1305_decrypt(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let (key, nonce): (_, &[u8; NONCE_LENGTH]) = (parse_key(key)?, parse_nonce(nonce)?);
+    let (ciphertext, tag) = split_tag(data)?;
+    open(ChaCha20Poly1305::new(key.into()), nonce, aad, ciphertext, tag)
+}
+
+/// Encrypts and authenticates data with XChaCha20-Poly1305.
+///
+/// # Arguments:
+/// * `key` - The 32-byte key.
+/// * `nonce` - The 24-byte nonce, which can safely be random.
+/// * `aad` - Additional data that is authenticated but not encrypted; pass an empty array for none.
+/// * `data` - The plaintext.
+///
+/// # Returns:
+/// * The ciphertext followed by the 16-byte tag (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidSymmetricKey` or `InvalidNonce` if the key or nonce has the wrong length.
+/// * `EncryptionFailed` if the plaintext exceeds the 256 GiB limit.
+#[wasm_bindgen]
+pub fn xchacha20_poly1305_encrypt(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let (mut ciphertext, tag) = xchacha20_poly1305_seal(parse_key(key)?, parse_nonce(nonce)?, aad, data)?;
+    ciphertext.extend_from_slice(&tag);
+    Ok(ciphertext)
+}
+
+/// Authenticates and decrypts data encrypted with XChaCha20-Poly1305.
+///
+/// # Arguments:
+/// * `key` - The 32-byte key.
+/// * `nonce` - The 24-byte nonce used for encryption.
+/// * `aad` - The additional data used for encryption.
+/// * `data` - The ciphertext followed by the 16-byte tag.
+///
+/// # Returns:
+/// * The plaintext (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidSymmetricKey` or `InvalidNonce` if the key or nonce has the wrong length.
+/// * `DecryptionFailed` if the data is too short for a tag or fails authentication.
+#[wasm_bindgen]
+pub fn xchacha20_poly1305_decrypt(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let (key, nonce) = (parse_key(key)?, parse_nonce(nonce)?);
+    let (ciphertext, tag) = split_tag(data)?;
+    xchacha20_poly1305_open(key, nonce, aad, ciphertext, tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    fn sequential_key(start: u8) -> [u8; 32] {
+        std::array::from_fn(|i| start + i as u8)
+    }
+
+    #[test]
+    fn test_chacha20_poly1305_vector() {
+        // RFC 8439 section 2.8.2
+        let key = sequential_key(0x80);
+        let nonce = hex::decode("070000004041424344454647").unwrap();
+        let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+        let sealed = chacha20_poly1305_encrypt(&key, &nonce, &aad, SUNSCREEN).unwrap();
+        assert_eq!(hex::encode(&sealed[..16]), "d31a8d34648e60db7b86afbc53ef7ec2");
+        assert_eq!(hex::encode(&sealed[sealed.len() - TAG_LENGTH..]), "1ae10b594f09e26a7e902ecbd0600691");
+        assert_eq!(chacha20_poly1305_decrypt(&key, &nonce, &aad, &sealed).unwrap(), SUNSCREEN);
+    }
+
+    #[test]
+    fn test_xchacha20_poly1305_vector() {
+        // draft-irtf-cfrg-xchacha appendix A.3.1
+        let key = sequential_key(0x80);
+        let nonce = hex::decode("404142434445464748494a4b4c4d4e4f5051525354555657").unwrap();
+        let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+        let sealed = xchacha20_poly1305_encrypt(&key, &nonce, &aad, SUNSCREEN).unwrap();
+        assert_eq!(hex::encode(&sealed[..16]), "bd6d179d3e83d43b9576579493c0e939");
+        assert_eq!(hex::encode(&sealed[sealed.len() - TAG_LENGTH..]), "c0875924c1c7987947deafd8780acf49");
+        assert_eq!(xchacha20_poly1305_decrypt(&key, &nonce, &aad, &sealed).unwrap(), SUNSCREEN);
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let key = [7u8; 32];
+        let nonce = [1u8; 12];
+        let sealed = chacha20_poly1305_encrypt(&key, &nonce, b"", b"").unwrap();
+        assert_eq!(hex::encode(&sealed), "082634da64051b0b8e00808b542198cf");
+
+        let mut tampered = chacha20_poly1305_encrypt(&key, &nonce, b"header", b"payload").unwrap();
+        tampered[0] ^= 1;
+        assert!(matches!(chacha20_poly1305_decrypt(&key, &nonce, b"header", &tampered), Err(ECDSAError::DecryptionFailed)));
+        assert!(matches!(chacha20_poly1305_decrypt(&key, &nonce, b"", &sealed[..15]), Err(ECDSAError::DecryptionFailed)));
+        assert!(matches!(chacha20_poly1305_encrypt(&key[..16], &nonce, b"", b""), Err(ECDSAError::InvalidSymmetricKey)));
+        // Each construction takes only its own nonce length
+        assert!(matches!(chacha20_poly1305_encrypt(&key, &[1u8; 24], b"", b""), Err(ECDSAError::InvalidNonce)));
+        assert!(matches!(xchacha20_poly1305_encrypt(&key, &nonce, b"", b""), Err(ECDSAError::InvalidNonce)));
+    }
+}
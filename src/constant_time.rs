@@ -0,0 +1,43 @@
+//! Constant-time byte comparison, for MACs, digests and anything else derived from a secret.
+//!
+//! An ordinary `==` returns at the first differing byte, so its running time tells an
+//! attacker how much of a forged tag or guessed value was right.
+
+use wasm_bindgen::prelude::*;
+use subtle::ConstantTimeEq;
+
+/// Compares two byte strings in time that depends only on their lengths, not their contents.
+///
+/// JavaScript's `===` and most `Buffer` comparisons stop at the first difference; use this
+/// instead when either side is a secret or a MAC.
+///
+/// # Arguments:
+/// * `a` - The first byte string (`Uint8Array` in JS).
+/// * `b` - The second byte string.
+///
+/// # Returns:
+/// * `true` if the two are equal. Strings of different lengths are unequal; lengths are
+///   not treated as secret.
+#[wasm_bindgen]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"secret tag", b"secret tag"));
+        assert!(!constant_time_eq(b"secret tag", b"secret tab"));
+        assert!(!constant_time_eq(b"Secret tag", b"secret tag"));
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        assert!(!constant_time_eq(b"secret", b"secret tag"));
+        assert!(!constant_time_eq(b"", &[0]));
+    }
+}
@@ -0,0 +1,588 @@
+//! COSE (RFC 9052) single-signer messages.
+//!
+//! A COSE_Sign1 is the CBOR array `[protected, unprotected, payload, signature]`, optionally
+//! behind tag 18. The signature covers the Sig_structure
+//! `["Signature1", protected, external_aad, payload]`, and ECDSA signatures are fixed-size
+//! `r || s` as in JOSE (RFC 9053 section 2.1).
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::cbor::{self, Value};
+use crate::keys::asn1::{Der, KeyAlgorithm, TAG_INTEGER};
+use crate::keys::{parse_public_key, PublicKey};
+use crate::pem::ImportedPublicKey;
+use crate::signature::parse_compact;
+use crate::{verify_digest, ECDSAError};
+
+const TAG_COSE_SIGN1: u64 = 18;
+const HEADER_ALG: i64 = 1;
+const HEADER_CRIT: i64 = 2;
+
+/// COSE_Key labels (RFC 9052 section 7 and RFC 9053 section 7.1)
+const KEY_KTY: i64 = 1;
+const KEY_ALG: i64 = 3;
+const KEY_CRV: i64 = -1;
+const KEY_X: i64 = -2;
+const KEY_Y: i64 = -3;
+const KTY_OKP: i64 = 1;
+const KTY_EC2: i64 = 2;
+
+/// The key type, COSE curve identifier, curve and coordinate length of each supported curve
+const CURVES: [(i64, i64, KeyAlgorithm, usize); 5] = [
+    (KTY_EC2, 1, KeyAlgorithm::P256, 32),
+    (KTY_EC2, 2, KeyAlgorithm::P384, 48),
+    (KTY_EC2, 3, KeyAlgorithm::P521, 66),
+    (KTY_EC2, 8, KeyAlgorithm::Secp256k1, 32),
+    (KTY_OKP, 6, KeyAlgorithm::Ed25519, 32),
+];
+
+/// The signature algorithms a key may be restricted to: ES256, ES384, ES512, ES256K and EdDSA
+const KEY_ALGORITHMS: [(i64, KeyAlgorithm); 5] = [
+    (-7, KeyAlgorithm::P256),
+    (-35, KeyAlgorithm::P384),
+    (-36, KeyAlgorithm::P521),
+    (-47, KeyAlgorithm::Secp256k1),
+    (-8, KeyAlgorithm::Ed25519),
+];
+
+/// The COSE signature algorithms this module verifies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CoseAlgorithm {
+    /// ECDSA over secp256k1 with SHA-256 (RFC 8812)
+    Es256k,
+    /// ECDSA over P-256 with SHA-256
+    #[cfg(feature = "p256")]
+    Es256,
+}
+
+impl CoseAlgorithm {
+    /// Looks up an algorithm by its IANA COSE identifier.
+    pub(crate) fn from_id(id: i64) -> Result<CoseAlgorithm, ECDSAError> {
+        match id {
+            -47 => Ok(CoseAlgorithm::Es256k),
+            #[cfg(feature = "p256")]
+            -7 => Ok(CoseAlgorithm::Es256),
+            _ => Err(ECDSAError::UnsupportedCoseAlgorithm),
+        }
+    }
+
+    /// Verifies a fixed-size `r || s` signature over `message` with a SEC1 public key.
+    /// High-S signatures are accepted, as COSE does not require low-S.
+    pub(crate) fn verify(self, public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+        if signature.len() != 64 {
+            return Ok(false);
+        }
+        let digest = Sha256::digest(message);
+        match self {
+            CoseAlgorithm::Es256k => {
+                let key = parse_public_key(public_key)?;
+                let Ok(sig) = parse_compact(signature) else {
+                    return Ok(false);
+                };
+                Ok(verify_digest(&key, &digest, &sig.normalize_s().unwrap_or(sig)))
+            }
+            #[cfg(feature = "p256")]
+            CoseAlgorithm::Es256 => {
+                let key = crate::p256::parse_public_key(public_key)?;
+                let Ok(signature) = crate::p256::parse_signature(signature) else {
+                    return Ok(false);
+                };
+                Ok(crate::p256::verify_prehashed(&key, &digest, &signature))
+            }
+        }
+    }
+
+    /// Verifies a DER encoded signature, the form WebAuthn uses for ECDSA.
+    pub(crate) fn verify_der(self, public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+        let mut fixed = [0u8; 64];
+        let mut outer = Der::new(signature);
+        let parsed = outer.sequence().and_then(|mut integers| {
+            for half in fixed.chunks_exact_mut(32) {
+                let integer = integers.read(TAG_INTEGER)?;
+                let integer = &integer[integer.iter().position(|&b| b != 0).unwrap_or(integer.len())..];
+                if integer.len() > 32 {
+                    return Err(ECDSAError::InvalidSignatureFormat);
+                }
+                half[32 - integer.len()..].copy_from_slice(integer);
+            }
+            integers.finish()?;
+            outer.finish()
+        });
+        match parsed {
+            Ok(()) => self.verify(public_key, message, &fixed),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// The key type the algorithm signs with, for matching certificate keys.
+    pub(crate) fn key_algorithm(self) -> KeyAlgorithm {
+        match self {
+            CoseAlgorithm::Es256k => KeyAlgorithm::Secp256k1,
+            #[cfg(feature = "p256")]
+            CoseAlgorithm::Es256 => KeyAlgorithm::P256,
+        }
+    }
+}
+
+/// A public COSE_Key (RFC 9052 section 7): an EC2 key on P-256, P-384, P-521 or secp256k1,
+/// or an OKP Ed25519 key, as WebAuthn stores credential public keys.
+///
+/// The point is validated on parsing. EC2 keys with a compressed `y` (a sign bit) keep their
+/// compressed form, so encoding a parsed key reproduces it.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct CoseKey {
+    algorithm: KeyAlgorithm,
+    /// The `alg` restriction, if the key carries one
+    alg: Option<i64>,
+    /// A SEC1 point, or the 32 raw bytes of an Ed25519 key
+    public_key: Vec<u8>,
+}
+
+impl CoseKey {
+    pub(crate) fn parse(bytes: &[u8]) -> Result<CoseKey, ECDSAError> {
+        let key = cbor::decode(bytes).map_err(|_| ECDSAError::InvalidCoseKey)?;
+        let label = |label| key.get(label).and_then(Value::as_i64).ok_or(ECDSAError::InvalidCoseKey);
+        let (kty, crv) = (label(KEY_KTY)?, label(KEY_CRV)?);
+        let &(_, _, algorithm, length) =
+            CURVES.iter().find(|&&(t, c, ..)| (t, c) == (kty, crv)).ok_or(ECDSAError::InvalidCoseKey)?;
+        let alg = match key.get(KEY_ALG) {
+            None => None,
+            Some(alg) => Some(alg.as_i64().ok_or(ECDSAError::InvalidCoseKey)?),
+        };
+        if let Some(alg) = alg {
+            match KEY_ALGORITHMS.iter().find(|&&(id, _)| id == alg) {
+                Some(&(_, curve)) if curve == algorithm => {}
+                Some(_) => return Err(ECDSAError::InvalidCoseKey),
+                None => return Err(ECDSAError::UnsupportedCoseAlgorithm),
+            }
+        }
+
+        let x = key.get(KEY_X).and_then(Value::as_bytes).filter(|x| x.len() == length).ok_or(ECDSAError::InvalidCoseKey)?;
+        let public_key = match (kty, key.get(KEY_Y)) {
+            (KTY_OKP, None) => x.to_vec(),
+            (KTY_EC2, Some(Value::Bytes(y))) if y.len() == length => [&[0x04][..], x, y].concat(),
+            (KTY_EC2, Some(&Value::Bool(odd))) => [&[0x02 | odd as u8][..], x].concat(),
+            _ => return Err(ECDSAError::InvalidCoseKey),
+        };
+        algorithm.check_public_key(&public_key).map_err(|error| match error {
+            ECDSAError::UnsupportedCurve => error,
+            _ => ECDSAError::InvalidCoseKey,
+        })?;
+        Ok(CoseKey { algorithm, alg, public_key })
+    }
+
+    /// Encodes the key with its labels in CTAP2 canonical order: `kty`, `alg`, `crv`, `x`, `y`.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let &(kty, crv, _, length) = CURVES.iter().find(|&&(.., curve, _)| curve == self.algorithm).unwrap();
+        let mut entries = vec![(Value::Integer(KEY_KTY as i128), Value::Integer(kty as i128))];
+        if let Some(alg) = self.alg {
+            entries.push((Value::Integer(KEY_ALG as i128), Value::Integer(alg as i128)));
+        }
+        entries.push((Value::Integer(KEY_CRV as i128), Value::Integer(crv as i128)));
+        let (x, y) = match self.public_key[0] {
+            _ if kty == KTY_OKP => (&self.public_key[..], None),
+            0x04 => (&self.public_key[1..1 + length], Some(Value::Bytes(self.public_key[1 + length..].to_vec()))),
+            prefix => (&self.public_key[1..], Some(Value::Bool(prefix == 0x03))),
+        };
+        entries.push((Value::Integer(KEY_X as i128), Value::Bytes(x.to_vec())));
+        if let Some(y) = y {
+            entries.push((Value::Integer(KEY_Y as i128), y));
+        }
+        cbor::encode(&Value::Map(entries))
+    }
+}
+
+#[wasm_bindgen]
+impl CoseKey {
+    /// Parses a CBOR encoded COSE_Key (`Uint8Array` in JS).
+    ///
+    /// # Errors:
+    /// * `InvalidCoseKey` if the key is malformed, of another type or curve, its `alg` does not
+    ///   match its curve, or its point is invalid.
+    /// * `UnsupportedCoseAlgorithm` for an unknown `alg`, and `UnsupportedCurve` for curves
+    ///   whose feature is disabled.
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: &[u8]) -> Result<CoseKey, ECDSAError> {
+        CoseKey::parse(bytes)
+    }
+
+    /// Builds a COSE_Key from a public key.
+    ///
+    /// # Arguments:
+    /// * `curve` - `secp256k1`, `P-256`, `P-384`, `P-521` or `Ed25519`.
+    /// * `public_key` - A SEC1 point (compressed or uncompressed) or a raw Ed25519 key, as hex.
+    /// * `alg` - An optional COSE algorithm to restrict the key to, e.g. -7 for ES256.
+    ///
+    /// # Errors:
+    /// * `UnsupportedCurve`, `InvalidPublicKey` or `InvalidCoseKey` if the curve, key or `alg`
+    ///   cannot be used.
+    pub fn from_public_key(curve: &str, public_key: &str, alg: Option<i32>) -> Result<CoseKey, ECDSAError> {
+        let algorithm = KeyAlgorithm::from_name(curve)?;
+        let public_key = hex::decode(public_key.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)?;
+        algorithm.check_public_key(&public_key)?;
+        let alg = alg.map(i64::from);
+        if alg.is_some_and(|alg| !KEY_ALGORITHMS.contains(&(alg, algorithm))) {
+            return Err(ECDSAError::InvalidCoseKey);
+        }
+        // Hybrid (0x06/0x07) points are stored uncompressed
+        let public_key = match public_key[0] {
+            0x06 | 0x07 if algorithm != KeyAlgorithm::Ed25519 => [&[0x04][..], &public_key[1..]].concat(),
+            _ => public_key,
+        };
+        Ok(CoseKey { algorithm, alg, public_key })
+    }
+
+    /// The key type: `EC2` or `OKP`.
+    #[wasm_bindgen(getter)]
+    pub fn key_type(&self) -> String {
+        match self.algorithm {
+            KeyAlgorithm::Ed25519 => "OKP",
+            _ => "EC2",
+        }
+        .to_string()
+    }
+
+    /// The key's curve: `secp256k1`, `P-256`, `P-384`, `P-521` or `Ed25519`.
+    #[wasm_bindgen(getter)]
+    pub fn curve(&self) -> String {
+        self.algorithm.name().to_string()
+    }
+
+    /// The COSE algorithm the key is restricted to, if any.
+    #[wasm_bindgen(getter)]
+    pub fn alg(&self) -> Option<i32> {
+        self.alg.map(|alg| alg as i32)
+    }
+
+    /// The public key as a hex string, ready for the verifier of its curve.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> String {
+        hex::encode(&self.public_key)
+    }
+
+    /// The key as a CBOR encoded COSE_Key (`Uint8Array` in JS).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+
+    /// Converts a secp256k1 key into a `PublicKey`.
+    ///
+    /// # Errors:
+    /// * `CurveMismatch` if the key is on another curve.
+    pub fn to_public_key(&self) -> Result<PublicKey, ECDSAError> {
+        if self.algorithm != KeyAlgorithm::Secp256k1 {
+            return Err(ECDSAError::CurveMismatch);
+        }
+        PublicKey::from_bytes(&self.public_key)
+    }
+
+    /// Converts the key into an `ImportedPublicKey`, as SPKI and PEM imports produce.
+    pub fn to_imported_public_key(&self) -> ImportedPublicKey {
+        ImportedPublicKey::new(self.algorithm, self.public_key.clone())
+    }
+}
+
+/// Parses a COSE_Key that names one of the algorithms `CoseAlgorithm` verifies, such as a
+/// WebAuthn credential public key, into the algorithm and SEC1 point.
+pub(crate) fn parse_cose_key(bytes: &[u8]) -> Result<(CoseAlgorithm, Vec<u8>), ECDSAError> {
+    let key = CoseKey::parse(bytes)?;
+    let algorithm = CoseAlgorithm::from_id(key.alg.ok_or(ECDSAError::InvalidCoseKey)?)?;
+    if algorithm.key_algorithm() != key.algorithm {
+        return Err(ECDSAError::InvalidCoseKey);
+    }
+    Ok((algorithm, key.public_key))
+}
+
+/// A decoded COSE_Sign1 message.
+pub(crate) struct CoseSign1 {
+    /// The serialized protected header map, exactly as signed
+    protected: Vec<u8>,
+    pub(crate) algorithm: CoseAlgorithm,
+    /// The payload, or `None` if it is detached
+    pub(crate) payload: Option<Vec<u8>>,
+    signature: Vec<u8>,
+}
+
+/// Decodes a tagged or untagged COSE_Sign1 message.
+pub(crate) fn parse_cose_sign1(bytes: &[u8]) -> Result<CoseSign1, ECDSAError> {
+    let message = match cbor::decode(bytes).map_err(|_| ECDSAError::InvalidCose)? {
+        Value::Tag(TAG_COSE_SIGN1, message) => *message,
+        Value::Tag(..) => return Err(ECDSAError::InvalidCose),
+        message => message,
+    };
+    let [protected, unprotected, payload, signature] = message.as_array().ok_or(ECDSAError::InvalidCose)? else {
+        return Err(ECDSAError::InvalidCose);
+    };
+
+    let protected = protected.as_bytes().ok_or(ECDSAError::InvalidCose)?.to_vec();
+    // An empty protected header may be sent as a zero-length string
+    let protected_headers = match protected.is_empty() {
+        true => Value::Map(Vec::new()),
+        false => cbor::decode(&protected).map_err(|_| ECDSAError::InvalidCose)?,
+    };
+    if protected_headers.as_map().is_none() || unprotected.as_map().is_none() {
+        return Err(ECDSAError::InvalidCose);
+    }
+    // Extensions the verifier must understand are not supported
+    if protected_headers.get(HEADER_CRIT).is_some() {
+        return Err(ECDSAError::InvalidCose);
+    }
+    // A header label may appear in only one of the two buckets
+    let algorithm = match (protected_headers.get(HEADER_ALG), unprotected.get(HEADER_ALG)) {
+        (Some(algorithm), None) | (None, Some(algorithm)) => algorithm,
+        _ => return Err(ECDSAError::InvalidCose),
+    };
+    let algorithm = CoseAlgorithm::from_id(algorithm.as_i64().ok_or(ECDSAError::UnsupportedCoseAlgorithm)?)?;
+
+    let payload = match payload {
+        Value::Bytes(payload) => Some(payload.clone()),
+        Value::Null => None,
+        _ => return Err(ECDSAError::InvalidCose),
+    };
+    let signature = signature.as_bytes().ok_or(ECDSAError::InvalidCose)?.to_vec();
+    Ok(CoseSign1 { protected, algorithm, payload, signature })
+}
+
+impl CoseSign1 {
+    /// Verifies the signature, returning the payload on success.
+    pub(crate) fn verify(
+        &self,
+        public_key: &[u8],
+        external_aad: &[u8],
+        detached_payload: Option<&[u8]>,
+    ) -> Result<Vec<u8>, ECDSAError> {
+        let payload = match (&self.payload, detached_payload) {
+            (Some(payload), None) => payload.as_slice(),
+            (None, Some(payload)) => payload,
+            _ => return Err(ECDSAError::InvalidCose),
+        };
+        let sig_structure = cbor::encode(&Value::Array(vec![
+            Value::Text("Signature1".to_string()),
+            Value::Bytes(self.protected.clone()),
+            Value::Bytes(external_aad.to_vec()),
+            Value::Bytes(payload.to_vec()),
+        ]));
+        if !self.algorithm.verify(public_key, &sig_structure, &self.signature)? {
+            return Err(ECDSAError::CoseSignatureMismatch);
+        }
+        Ok(payload.to_vec())
+    }
+}
+
+/// Verifies an ES256K or ES256 signed COSE_Sign1 message, as used by WebAuthn, ISO mDL
+/// and CWT-based IoT payloads.
+///
+/// The algorithm comes from the message's `alg` header (-47 for ES256K, -7 for ES256 with
+/// the `p256` feature). Messages with critical headers are rejected.
+///
+/// # Arguments:
+/// * `message` - The CBOR encoded COSE_Sign1, tagged (18) or untagged (`Uint8Array` in JS).
+/// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+/// * `external_aad` - Optional externally supplied data bound into the signature.
+/// * `detached_payload` - The payload, required when the message carries `nil` in its place.
+///
+/// # Returns:
+/// * The verified payload (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * Returns an error if the message is malformed, uses another algorithm, has an invalid
+///   signature, or the key is malformed.
+#[wasm_bindgen]
+pub fn verify_cose_sign1(
+    message: &[u8],
+    public_key: &str,
+    external_aad: Option<Vec<u8>>,
+    detached_payload: Option<Vec<u8>>,
+) -> Result<Vec<u8>, ECDSAError> {
+    let public_key = hex::decode(public_key.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    let message = parse_cose_sign1(message)?;
+    message.verify(&public_key, external_aad.as_deref().unwrap_or_default(), detached_payload.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[0x47; 32]).unwrap()
+    }
+
+    fn public_key() -> String {
+        hex::encode(key().verifying_key().to_encoded_point(true).as_bytes())
+    }
+
+    /// Builds an ES256K COSE_Sign1 with the given headers.
+    fn sign(protected: Value, unprotected: Value, payload: &[u8], detached: bool, external_aad: &[u8]) -> Vec<u8> {
+        let protected = cbor::encode(&protected);
+        let sig_structure = cbor::encode(&Value::Array(vec![
+            Value::Text("Signature1".to_string()),
+            Value::Bytes(protected.clone()),
+            Value::Bytes(external_aad.to_vec()),
+            Value::Bytes(payload.to_vec()),
+        ]));
+        let (sig, _) = sign_digest(&key(), &Sha256::digest(&sig_structure), &[]).unwrap();
+        let payload = if detached { Value::Null } else { Value::Bytes(payload.to_vec()) };
+        let message = Value::Array(vec![Value::Bytes(protected), unprotected, payload, Value::Bytes(sig.as_ref().to_vec())]);
+        cbor::encode(&Value::Tag(TAG_COSE_SIGN1, Box::new(message)))
+    }
+
+    fn es256k() -> Value {
+        Value::Map(vec![(Value::Integer(1), Value::Integer(-47))])
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_rfc9052_example() {
+        // RFC 9052 appendix C.2.1, signed with the P-256 key "11"
+        let message = hex::decode(
+            "d28443a10126a10442313154546869732069732074686520636f6e74656e742e58408eb33e4ca31d1c465ab05aac34cc6b\
+             23d58fef5c083106c4d25a91aef0b0117e2af9a291aa32e14ab834dc56ed2a223444547e01f11d3b0916e5a4c345cacb36",
+        )
+        .unwrap();
+        let public_key = "04bac5b11cad8f99f9c72b05cf4b9e26d244dc189f745228255a219a86d6a09eff\
+                          20138bf82dc1b6d562be0fa54ab7804a3a64b6d72ccfed6b6fb6ed28bbfc117e";
+        assert_eq!(verify_cose_sign1(&message, public_key, None, None).unwrap(), b"This is the content.");
+        assert!(matches!(
+            verify_cose_sign1(&message, public_key, Some(b"aad".to_vec()), None),
+            Err(ECDSAError::CoseSignatureMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_verify_es256k() {
+        let message = sign(es256k(), Value::Map(vec![]), b"payload", false, b"");
+        assert_eq!(verify_cose_sign1(&message, &public_key(), None, None).unwrap(), b"payload");
+        let other = hex::encode(SigningKey::from_bytes(&[0x48; 32]).unwrap().verifying_key().to_encoded_point(true).as_bytes());
+        assert!(matches!(verify_cose_sign1(&message, &other, None, None), Err(ECDSAError::CoseSignatureMismatch)));
+
+        // External AAD and a detached payload
+        let message = sign(es256k(), Value::Map(vec![]), b"detached", true, b"context");
+        let verify = |aad: &[u8], payload: &[u8]| verify_cose_sign1(&message, &public_key(), Some(aad.to_vec()), Some(payload.to_vec()));
+        assert_eq!(verify(b"context", b"detached").unwrap(), b"detached");
+        assert!(matches!(verify(b"", b"detached"), Err(ECDSAError::CoseSignatureMismatch)));
+        assert!(matches!(verify(b"context", b"other"), Err(ECDSAError::CoseSignatureMismatch)));
+        assert!(matches!(verify_cose_sign1(&message, &public_key(), None, None), Err(ECDSAError::InvalidCose)));
+
+        // The algorithm may instead sit in the unprotected bucket, with an empty protected map
+        let message = sign(Value::Map(vec![]), es256k(), b"payload", false, b"");
+        assert!(verify_cose_sign1(&message, &public_key(), None, None).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_bad_headers() {
+        let check = |protected: Value, unprotected: Value| {
+            verify_cose_sign1(&sign(protected, unprotected, b"payload", false, b""), &public_key(), None, None)
+        };
+        assert!(matches!(check(es256k(), es256k()), Err(ECDSAError::InvalidCose)));
+        assert!(matches!(check(Value::Map(vec![]), Value::Map(vec![])), Err(ECDSAError::InvalidCose)));
+        // ES384 (-35) and EdDSA (-8) are not supported
+        for id in [-35, -8] {
+            let protected = Value::Map(vec![(Value::Integer(1), Value::Integer(id))]);
+            assert!(matches!(check(protected, Value::Map(vec![])), Err(ECDSAError::UnsupportedCoseAlgorithm)));
+        }
+        let critical = Value::Map(vec![
+            (Value::Integer(1), Value::Integer(-47)),
+            (Value::Integer(2), Value::Array(vec![Value::Integer(99)])),
+        ]);
+        assert!(matches!(check(critical, Value::Map(vec![])), Err(ECDSAError::InvalidCose)));
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let message = sign(es256k(), Value::Map(vec![]), b"payload", false, b"");
+        // Untagged messages are accepted, other tags are not
+        assert!(verify_cose_sign1(&message[1..], &public_key(), None, None).is_ok());
+        let mut wrong_tag = message.clone();
+        wrong_tag[0] = 0xd1;
+        assert!(matches!(verify_cose_sign1(&wrong_tag, &public_key(), None, None), Err(ECDSAError::InvalidCose)));
+
+        assert!(matches!(verify_cose_sign1(&message[..message.len() - 1], &public_key(), None, None), Err(ECDSAError::InvalidCose)));
+        assert!(matches!(verify_cose_sign1(&[0x83, 0x40, 0xa0, 0x40], &public_key(), None, None), Err(ECDSAError::InvalidCose)));
+        assert!(verify_cose_sign1(&message, "zz", None, None).is_err());
+    }
+
+    #[test]
+    fn test_cose_key_round_trip() {
+        let uncompressed = hex::encode(key().verifying_key().to_encoded_point(false).as_bytes());
+        let cose_key = CoseKey::from_public_key("secp256k1", &uncompressed, Some(-47)).unwrap();
+        let bytes = cose_key.to_bytes();
+        assert_eq!(&bytes[..10], hex::decode("a5010203382e20082158").unwrap());
+        let parsed = CoseKey::new(&bytes).unwrap();
+        assert_eq!((parsed.key_type().as_str(), parsed.curve().as_str(), parsed.alg()), ("EC2", "secp256k1", Some(-47)));
+        assert_eq!(parsed.public_key(), uncompressed);
+        assert_eq!(parsed.to_public_key().unwrap().uncompressed(), uncompressed);
+        assert_eq!(parse_cose_key(&bytes).unwrap(), (CoseAlgorithm::Es256k, hex::decode(&uncompressed).unwrap()));
+
+        // A compressed key encodes its y sign as a boolean, and needs an alg to verify with
+        let cose_key = CoseKey::from_public_key("secp256k1", &public_key(), None).unwrap();
+        let bytes = cose_key.to_bytes();
+        assert_eq!(bytes[bytes.len() - 1], 0xf4 | (public_key().as_bytes()[1] - b'2'));
+        let parsed = CoseKey::new(&bytes).unwrap();
+        assert_eq!((parsed.public_key(), parsed.alg()), (public_key(), None));
+        assert_eq!(parsed.to_public_key().unwrap().uncompressed(), uncompressed);
+        assert!(matches!(parse_cose_key(&bytes), Err(ECDSAError::InvalidCoseKey)));
+    }
+
+    #[test]
+    fn test_okp_and_p256_cose_keys() {
+        // An EdDSA credential key, as in the WebAuthn specification's examples
+        let okp = hex::decode("a4010103272006215820d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a").unwrap();
+        let key = CoseKey::new(&okp).unwrap();
+        assert_eq!((key.key_type().as_str(), key.curve().as_str(), key.alg()), ("OKP", "Ed25519", Some(-8)));
+        assert_eq!(key.public_key(), "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        assert_eq!(key.to_bytes(), okp);
+        assert_eq!(key.to_imported_public_key().curve(), "Ed25519");
+        assert!(matches!(key.to_public_key(), Err(ECDSAError::CurveMismatch)));
+        assert!(matches!(parse_cose_key(&okp), Err(ECDSAError::UnsupportedCoseAlgorithm)));
+
+        #[cfg(feature = "p256")]
+        {
+            let ec2 = hex::decode(
+                "a501020326200121582060fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+                 2258207903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299",
+            )
+            .unwrap();
+            let key = CoseKey::new(&ec2).unwrap();
+            assert_eq!((key.curve().as_str(), key.alg()), ("P-256", Some(-7)));
+            assert_eq!(key.to_bytes(), ec2);
+            let imported = key.to_imported_public_key();
+            assert_eq!((imported.curve(), imported.public_key()), (key.curve(), key.public_key()));
+            let rebuilt = CoseKey::from_public_key("P-256", &key.public_key(), Some(-7)).unwrap();
+            assert_eq!(rebuilt.to_bytes(), ec2);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_cose_keys() {
+        let point = key().verifying_key().to_encoded_point(false);
+        let cose_key = |entries: Vec<(i128, Value)>| {
+            cbor::encode(&Value::Map(entries.into_iter().map(|(label, value)| (Value::Integer(label), value)).collect()))
+        };
+        let (x, y) = (Value::Bytes(point.x().unwrap().to_vec()), Value::Bytes(point.y().unwrap().to_vec()));
+        let mut off_curve = point.y().unwrap().to_vec();
+        off_curve[31] ^= 1;
+
+        for (entries, expected) in [
+            // An ES256 restriction on a secp256k1 key, and an X25519 key
+            (vec![(1, Value::Integer(2)), (3, Value::Integer(-7)), (-1, Value::Integer(8)), (-2, x.clone()), (-3, y.clone())], "InvalidCoseKey"),
+            (vec![(1, Value::Integer(1)), (-1, Value::Integer(4)), (-2, Value::Bytes(vec![9; 32]))], "InvalidCoseKey"),
+            // Off the curve, a short coordinate and a missing y
+            (vec![(1, Value::Integer(2)), (-1, Value::Integer(8)), (-2, x.clone()), (-3, Value::Bytes(off_curve))], "InvalidCoseKey"),
+            (vec![(1, Value::Integer(2)), (-1, Value::Integer(8)), (-2, Value::Bytes(vec![1; 31])), (-3, y.clone())], "InvalidCoseKey"),
+            (vec![(1, Value::Integer(2)), (-1, Value::Integer(8)), (-2, x.clone())], "InvalidCoseKey"),
+            // RS256
+            (vec![(1, Value::Integer(2)), (3, Value::Integer(-257)), (-1, Value::Integer(8)), (-2, x), (-3, y)], "UnsupportedCoseAlgorithm"),
+        ] {
+            let error = CoseKey::new(&cose_key(entries)).unwrap_err();
+            assert_eq!(format!("{error:?}"), expected);
+        }
+        assert!(CoseKey::new(&[0xa0]).is_err());
+        assert!(CoseKey::from_public_key("secp256k1", &public_key(), Some(-8)).is_err());
+        assert!(CoseKey::from_public_key("X25519", &public_key(), None).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Cosmos ADR-36 arbitrary message signatures, as produced by Keplr's `signArbitrary`.
+//!
+//! The data is wrapped in an amino JSON sign doc holding a single `sign/MsgSignData`
+//! message with an empty chain id, zero account number and sequence, and no fee. The doc
+//! is serialized with sorted keys and no whitespace, hashed with SHA-256 and signed as a
+//! 64-byte `r || s` secp256k1 signature.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::bitcoin::hash160;
+use crate::encoding::{convert_bits, from_base64, from_bech32, to_base64, Bech32Variant};
+use crate::keys::parse_public_key;
+use crate::signature::parse_compact;
+use crate::{verify_digest, ECDSAError};
+
+/// Builds the canonical ADR-36 amino sign doc for `data` signed by `signer`.
+fn sign_doc(signer: &str, data: &[u8]) -> String {
+    format!(
+        concat!(
+            r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","#,
+            r#""msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#
+        ),
+        to_base64(data),
+        signer
+    )
+}
+
+/// Verifies an ADR-36 arbitrary message signature from a Cosmos SDK account.
+///
+/// # Arguments:
+/// * `chain_prefix` - The chain's Bech32 account prefix, e.g. `cosmos` or `osmo`.
+/// * `signer` - The signer's Bech32 account address, exactly as it was signed.
+/// * `data` - The signed data (`Uint8Array` in JS); Keplr signs strings as their UTF-8 bytes.
+/// * `signature` - The 64-byte `r || s` signature, Base64 encoded.
+/// * `pubkey` - The signer's compressed secp256k1 public key, Base64 encoded, as in the
+///   `pub_key.value` of the wallet's response.
+///
+/// # Returns:
+/// * A boolean indicating whether `pubkey` owns `signer` and signed the data.
+///
+/// # Errors:
+/// * Returns an error if the address is not a Bech32 account address for `chain_prefix`,
+///   the public key is not a compressed secp256k1 key, or the signature is not 64 bytes of Base64.
+#[wasm_bindgen]
+pub fn verify_cosmos_arbitrary(
+    chain_prefix: &str,
+    signer: &str,
+    data: &[u8],
+    signature: &str,
+    pubkey: &str,
+) -> Result<bool, ECDSAError> {
+    let (hrp, values, variant) = from_bech32(signer).ok_or(ECDSAError::InvalidAddress)?;
+    if variant != Bech32Variant::Bech32 || hrp != chain_prefix || signer.bytes().any(|c| c.is_ascii_uppercase()) {
+        return Err(ECDSAError::InvalidAddress);
+    }
+    let account = convert_bits(&values, 5, 8, false).ok_or(ECDSAError::InvalidAddress)?;
+    if account.len() != 20 {
+        return Err(ECDSAError::InvalidAddress);
+    }
+
+    let key = from_base64(pubkey.trim()).ok_or(ECDSAError::InvalidPublicKey)?;
+    if key.len() != 33 {
+        return Err(ECDSAError::InvalidPublicKey);
+    }
+    let verifying_key = parse_public_key(&key)?;
+    let signature = from_base64(signature.trim()).ok_or(ECDSAError::InvalidSignatureFormat)?;
+    if signature.len() != 64 {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    let sig = parse_compact(&signature)?;
+
+    if hash160(&key) != account[..] {
+        return Ok(false);
+    }
+    let digest = Sha256::digest(sign_doc(signer, data).as_bytes());
+    Ok(verify_digest(&verifying_key, &digest, &sig))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_bech32;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+
+    fn account(prefix: &str, key: &SigningKey) -> String {
+        let values = convert_bits(&hash160(&key.verifying_key().to_bytes()), 8, 5, true).unwrap();
+        to_bech32(prefix, &values, Bech32Variant::Bech32).unwrap()
+    }
+
+    fn sign(key: &SigningKey, signer: &str, data: &[u8]) -> String {
+        let digest = Sha256::digest(sign_doc(signer, data).as_bytes());
+        let (sig, _) = sign_digest(key, &digest, &[]).unwrap();
+        to_base64(sig.as_ref())
+    }
+
+    #[test]
+    fn test_sign_doc() {
+        assert_eq!(
+            sign_doc("cosmos1abc", b"hi"),
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGk=","signer":"cosmos1abc"}}],"sequence":"0"}"#
+        );
+    }
+
+    #[test]
+    fn test_verify_cosmos_arbitrary() {
+        let key = SigningKey::from_bytes(&[0x24; 32]).unwrap();
+        let pubkey = to_base64(&key.verifying_key().to_bytes());
+        let signer = account("cosmos", &key);
+        let signature = sign(&key, &signer, b"Login nonce 1234");
+
+        assert!(verify_cosmos_arbitrary("cosmos", &signer, b"Login nonce 1234", &signature, &pubkey).unwrap());
+        assert!(!verify_cosmos_arbitrary("cosmos", &signer, b"Login nonce 1235", &signature, &pubkey).unwrap());
+
+        // The same key under another chain's prefix signs a different doc
+        let osmo = account("osmo", &key);
+        assert!(!verify_cosmos_arbitrary("osmo", &osmo, b"Login nonce 1234", &signature, &pubkey).unwrap());
+
+        // A key that does not own the address
+        let other = to_base64(&SigningKey::from_bytes(&[0x25; 32]).unwrap().verifying_key().to_bytes());
+        assert!(!verify_cosmos_arbitrary("cosmos", &signer, b"Login nonce 1234", &signature, &other).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let key = SigningKey::from_bytes(&[0x24; 32]).unwrap();
+        let pubkey = to_base64(&key.verifying_key().to_bytes());
+        let signer = account("cosmos", &key);
+        let signature = sign(&key, &signer, b"");
+
+        assert!(matches!(verify_cosmos_arbitrary("osmo", &signer, b"", &signature, &pubkey), Err(ECDSAError::InvalidAddress)));
+        assert!(matches!(verify_cosmos_arbitrary("cosmos", "cosmos1", b"", &signature, &pubkey), Err(ECDSAError::InvalidAddress)));
+        assert!(matches!(verify_cosmos_arbitrary("cosmos", &signer, b"", &signature, "AAAA"), Err(ECDSAError::InvalidPublicKey)));
+        assert!(matches!(verify_cosmos_arbitrary("cosmos", &signer, b"", "AAAA", &pubkey), Err(ECDSAError::InvalidSignatureLength)));
+    }
+}
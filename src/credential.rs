@@ -0,0 +1,306 @@
+//! Verifiable Credential Data Integrity proofs (W3C VC Data Integrity) with the JCS
+//! cryptosuites: `ecdsa-jcs-2019` and `eddsa-jcs-2022`.
+//!
+//! The signed data is `H(JCS(proof options)) || H(JCS(credential without proof))`, where the
+//! proof options are the proof without `proofValue`, carrying the credential's `@context`.
+//! `proofValue` is the base58btc multibase encoding of the raw signature (`r || s` for ECDSA).
+//!
+//! Suites that canonicalize with RDF Dataset Canonicalization (`ecdsa-rdfc-2019`,
+//! `eddsa-rdfc-2022`, and the `EcdsaSecp256k1Signature2019` linked data signature) need a
+//! JSON-LD processor to expand the credential against its contexts, and are not supported.
+
+use wasm_bindgen::prelude::*;
+
+use crate::did::parse_did_key;
+use crate::encoding::from_base58;
+use crate::json::{self, canonicalize, Value};
+use crate::keys::asn1::KeyAlgorithm;
+use crate::ECDSAError;
+
+const PROOF_TYPE: &str = "DataIntegrityProof";
+
+/// The supported cryptosuites.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cryptosuite {
+    EcdsaJcs2019,
+    EddsaJcs2022,
+}
+
+impl Cryptosuite {
+    fn from_name(name: &str) -> Result<Cryptosuite, ECDSAError> {
+        match name {
+            "ecdsa-jcs-2019" => Ok(Cryptosuite::EcdsaJcs2019),
+            "eddsa-jcs-2022" => Ok(Cryptosuite::EddsaJcs2022),
+            _ => Err(ECDSAError::UnsupportedProofType),
+        }
+    }
+
+    /// Picks the curve of a raw public key from its length.
+    fn algorithm_for(self, public_key: &[u8]) -> Result<KeyAlgorithm, ECDSAError> {
+        match (self, public_key.len()) {
+            (Cryptosuite::EcdsaJcs2019, 33 | 65) => Ok(KeyAlgorithm::P256),
+            (Cryptosuite::EcdsaJcs2019, 49 | 97) => Ok(KeyAlgorithm::P384),
+            (Cryptosuite::EddsaJcs2022, 32) => Ok(KeyAlgorithm::Ed25519),
+            _ => Err(ECDSAError::InvalidPublicKey),
+        }
+    }
+
+    /// Checks that the suite signs with keys of `algorithm`. `ecdsa-jcs-2019` registers P-256
+    /// and P-384; secp256k1 keys are also accepted and hash with SHA-256.
+    fn check_algorithm(self, algorithm: KeyAlgorithm) -> Result<(), ECDSAError> {
+        let allowed = match self {
+            Cryptosuite::EcdsaJcs2019 => {
+                matches!(algorithm, KeyAlgorithm::Secp256k1 | KeyAlgorithm::P256 | KeyAlgorithm::P384)
+            }
+            Cryptosuite::EddsaJcs2022 => algorithm == KeyAlgorithm::Ed25519,
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(ECDSAError::CurveMismatch)
+        }
+    }
+}
+
+/// Reads `@context` as a list of entries.
+fn contexts(value: &Value) -> Option<&[Value]> {
+    match value.get("@context")? {
+        Value::Array(items) => Some(items),
+        context => Some(std::slice::from_ref(context)),
+    }
+}
+
+/// A secured credential split for verification.
+struct ProofData {
+    suite: Cryptosuite,
+    /// The canonical proof options, `@context` included
+    options: Vec<u8>,
+    /// The canonical credential without its proof
+    document: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+/// Splits a secured credential into its canonical proof options and document.
+fn prepare(credential: &Value) -> Result<ProofData, ECDSAError> {
+    let members = credential.as_object().ok_or(ECDSAError::InvalidCredential)?;
+    let proof = credential.get("proof").ok_or(ECDSAError::InvalidCredential)?;
+    let proof_members = proof.as_object().ok_or(ECDSAError::InvalidCredential)?;
+    let member = |name: &str| proof.get(name).and_then(Value::as_str).ok_or(ECDSAError::InvalidCredential);
+    if member("type")? != PROOF_TYPE {
+        return Err(ECDSAError::UnsupportedProofType);
+    }
+    let suite = Cryptosuite::from_name(member("cryptosuite")?)?;
+    let signature = member("proofValue")?
+        .strip_prefix('z')
+        .and_then(from_base58)
+        .ok_or(ECDSAError::InvalidCredential)?;
+
+    let document = Value::Object(members.iter().filter(|(name, _)| name != "proof").cloned().collect());
+    let mut options = proof_members.iter().filter(|(name, _)| name != "proofValue").cloned().collect::<Vec<_>>();
+    if let Some(proof_contexts) = contexts(proof) {
+        // The proof's contexts must be a prefix of the credential's
+        if !contexts(credential).is_some_and(|contexts| contexts.starts_with(proof_contexts)) {
+            return Err(ECDSAError::InvalidCredential);
+        }
+    } else if let Some(context) = credential.get("@context") {
+        options.push(("@context".to_string(), context.clone()));
+    }
+
+    let canonical = |value: &Value| canonicalize(value).map(String::into_bytes).map_err(|_| ECDSAError::InvalidCredential);
+    Ok(ProofData { suite, options: canonical(&Value::Object(options))?, document: canonical(&document)?, signature })
+}
+
+/// Verifies the Data Integrity proof of a Verifiable Credential or Presentation.
+///
+/// # Arguments:
+/// * `vc_json` - The secured credential as JSON, with a single `proof` object whose `type` is
+///   `DataIntegrityProof` and whose `cryptosuite` is `ecdsa-jcs-2019` or `eddsa-jcs-2022`.
+/// * `pubkey_or_did` - The issuer's key: a `did:key` identifier (or verification method ID),
+///   or a hex public key, a P-256 or P-384 SEC1 point for `ecdsa-jcs-2019` or 32 Ed25519
+///   bytes for `eddsa-jcs-2022`. secp256k1 keys are accepted as `did:key`s.
+///
+/// # Returns:
+/// * A boolean indicating whether the proof is valid. The proof's `verificationMethod` is
+///   not resolved: callers decide whether the key speaks for the `issuer`.
+///
+/// # Errors:
+/// * `InvalidCredential` if the JSON is malformed or lacks a proof, or the proof's contexts
+///   are not a prefix of the credential's.
+/// * `UnsupportedProofType` for other proof types and cryptosuites, including the RDF ones.
+/// * `CurveMismatch` if the key's type is not one the cryptosuite uses.
+/// * `InvalidDidKey`, `InvalidPublicKey` or `UnsupportedCurve` if the key cannot be used.
+#[wasm_bindgen]
+pub fn verify_credential(vc_json: &str, pubkey_or_did: &str) -> Result<bool, ECDSAError> {
+    let credential = json::parse(vc_json).map_err(|_| ECDSAError::InvalidCredential)?;
+    let proof = prepare(&credential)?;
+
+    let (algorithm, public_key) = if pubkey_or_did.starts_with("did:") {
+        parse_did_key(pubkey_or_did)?
+    } else {
+        let public_key = hex::decode(pubkey_or_did.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)?;
+        (proof.suite.algorithm_for(&public_key)?, public_key)
+    };
+    proof.suite.check_algorithm(algorithm)?;
+    algorithm.check_public_key(&public_key)?;
+
+    let signed = [algorithm.hash(&proof.options), algorithm.hash(&proof.document)].concat();
+    algorithm.verify_raw(&public_key, &signed, &proof.signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An ecdsa-jcs-2019 credential issued by a P-256 did:key
+    #[cfg(feature = "p256")]
+    const P256_CREDENTIAL: &str = r#"{
+  "@context": [
+    "https://www.w3.org/ns/credentials/v2",
+    "https://www.w3.org/ns/credentials/examples/v2"
+  ],
+  "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+  "type": [
+    "VerifiableCredential",
+    "AlumniCredential"
+  ],
+  "name": "Alumni Credential",
+  "issuer": "did:key:zDnaeef69fHnQ6nSBvfYdsXwjQ76qwaT9oZnHqVQPCG9ZwRDT",
+  "validFrom": "2026-01-01T00:00:00Z",
+  "credentialSubject": {
+    "id": "did:example:abcdefgh",
+    "alumniOf": "The School of Examples",
+    "graduated": 2025.5
+  },
+  "proof": {
+    "type": "DataIntegrityProof",
+    "cryptosuite": "ecdsa-jcs-2019",
+    "created": "2026-01-01T00:00:00Z",
+    "verificationMethod": "did:key:zDnaeef69fHnQ6nSBvfYdsXwjQ76qwaT9oZnHqVQPCG9ZwRDT#zDnaeef69fHnQ6nSBvfYdsXwjQ76qwaT9oZnHqVQPCG9ZwRDT",
+    "proofPurpose": "assertionMethod",
+    "proofValue": "z4Su6CDhk9SSNfdLptDDF1kXL5wB7Gcj21hRVXQxPEaZRwQhq1N5Wcmq7GJcXmVkKs7iCia5tkoeLv4jC1r45tv5k"
+  }
+}"#;
+    #[cfg(feature = "p256")]
+    const P256_KEY: &str = "02d360332fad9bc83afaff4a740de8a516bf1b8fb3fde360ff1d03979c1f943ee2";
+    // The same credential issued by a secp256k1 did:key
+    const SECP256K1_CREDENTIAL: &str = r#"{
+  "@context": [
+    "https://www.w3.org/ns/credentials/v2",
+    "https://www.w3.org/ns/credentials/examples/v2"
+  ],
+  "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+  "type": [
+    "VerifiableCredential",
+    "AlumniCredential"
+  ],
+  "name": "Alumni Credential",
+  "issuer": "did:key:zQ3shhVTPBqCUcWgn5d71GXri7h8MwFJT54YsnwjrXcebcgNp",
+  "validFrom": "2026-01-01T00:00:00Z",
+  "credentialSubject": {
+    "id": "did:example:abcdefgh",
+    "alumniOf": "The School of Examples",
+    "graduated": 2025.5
+  },
+  "proof": {
+    "type": "DataIntegrityProof",
+    "cryptosuite": "ecdsa-jcs-2019",
+    "created": "2026-01-01T00:00:00Z",
+    "verificationMethod": "did:key:zQ3shhVTPBqCUcWgn5d71GXri7h8MwFJT54YsnwjrXcebcgNp#zQ3shhVTPBqCUcWgn5d71GXri7h8MwFJT54YsnwjrXcebcgNp",
+    "proofPurpose": "assertionMethod",
+    "proofValue": "z48bgDZ4boB8WiBNSvM8Fn7SacfLQsxaKJ4roffREUtHuzMFZDD1YAbiNddB8c3yBvRgfsKdpTJdrekG36aKAbRb6"
+  }
+}"#;
+    const SECP256K1_KEY: &str = "032a5bbcb0eede528e6abe5f2ec50ad7887eb5677af383a460b05ee23bf892dfe5";
+    // An eddsa-jcs-2022 credential issued by an Ed25519 did:key
+    #[cfg(feature = "ed25519")]
+    const ED25519_CREDENTIAL: &str = r#"{
+  "@context": [
+    "https://www.w3.org/ns/credentials/v2",
+    "https://www.w3.org/ns/credentials/examples/v2"
+  ],
+  "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+  "type": [
+    "VerifiableCredential",
+    "AlumniCredential"
+  ],
+  "name": "Alumni Credential",
+  "issuer": "did:key:z6MkehRgf7yJbgaGfYsdoAsKdBPE3dj2CYhowQdcjqSJgvVd",
+  "validFrom": "2026-01-01T00:00:00Z",
+  "credentialSubject": {
+    "id": "did:example:abcdefgh",
+    "alumniOf": "The School of Examples",
+    "graduated": 2025.5
+  },
+  "proof": {
+    "type": "DataIntegrityProof",
+    "cryptosuite": "eddsa-jcs-2022",
+    "created": "2026-01-01T00:00:00Z",
+    "verificationMethod": "did:key:z6MkehRgf7yJbgaGfYsdoAsKdBPE3dj2CYhowQdcjqSJgvVd#z6MkehRgf7yJbgaGfYsdoAsKdBPE3dj2CYhowQdcjqSJgvVd",
+    "proofPurpose": "assertionMethod",
+    "proofValue": "zT16WVR2MEkwq7BmkKjxHm8zqEbE8EiCgCUWyqTdR35RLSeUjNyP5HU4Atw7uJjp77doMY8znLw7tdS9DNNBUZB7"
+  }
+}"#;
+
+    fn issuer(credential: &str) -> String {
+        json::parse(credential).unwrap().get("issuer").and_then(Value::as_str).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_verify_ecdsa_jcs() {
+        let did = issuer(SECP256K1_CREDENTIAL);
+        assert!(verify_credential(SECP256K1_CREDENTIAL, &did).unwrap());
+        // Member order and whitespace are not signed, but values are
+        let name = r#""name": "Alumni Credential","#;
+        let reordered = SECP256K1_CREDENTIAL.replace('\n', "").replace(name, "").replacen('{', &format!("{{{name}"), 1);
+        assert!(verify_credential(&reordered, &did).unwrap());
+        assert!(!verify_credential(&SECP256K1_CREDENTIAL.replace("2025.5", "2025.6"), &did).unwrap());
+        assert!(verify_credential(&SECP256K1_CREDENTIAL.replace("2025.5", "2025.50"), &did).unwrap());
+        assert!(!verify_credential(&SECP256K1_CREDENTIAL.replace("assertionMethod", "authentication"), &did).unwrap());
+        // Hex secp256k1 keys would be taken for P-256 keys
+        assert!(verify_credential(SECP256K1_CREDENTIAL, SECP256K1_KEY).is_err());
+
+        #[cfg(feature = "p256")]
+        {
+            assert!(verify_credential(P256_CREDENTIAL, &issuer(P256_CREDENTIAL)).unwrap());
+            assert!(verify_credential(P256_CREDENTIAL, P256_KEY).unwrap());
+            assert!(!verify_credential(P256_CREDENTIAL, &did).unwrap());
+        }
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_verify_eddsa_jcs() {
+        let did = issuer(ED25519_CREDENTIAL);
+        assert!(verify_credential(ED25519_CREDENTIAL, &did).unwrap());
+        assert!(verify_credential(ED25519_CREDENTIAL, "03a107bff3ce10be1d70dd18e74bc09967e4d6309ba50d5f1ddc8664125531b8").unwrap());
+        assert!(!verify_credential(&ED25519_CREDENTIAL.replace("Examples", "Samples"), &did).unwrap());
+        // The key type must match the cryptosuite
+        assert!(matches!(
+            verify_credential(ED25519_CREDENTIAL, &issuer(SECP256K1_CREDENTIAL)),
+            Err(ECDSAError::CurveMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unsupported_proofs() {
+        let did = issuer(SECP256K1_CREDENTIAL);
+        for (from, to) in [("ecdsa-jcs-2019", "ecdsa-rdfc-2019"), ("DataIntegrityProof", "EcdsaSecp256k1Signature2019")] {
+            let credential = SECP256K1_CREDENTIAL.replace(from, to);
+            assert!(matches!(verify_credential(&credential, &did), Err(ECDSAError::UnsupportedProofType)), "{to}");
+        }
+        let start = SECP256K1_CREDENTIAL.find(",\n  \"proof\"").unwrap();
+        let unsigned = format!("{}\n}}", &SECP256K1_CREDENTIAL[..start]);
+        for credential in ["[]", "{}", &unsigned, &SECP256K1_CREDENTIAL.replace(r#""proofValue": "z"#, r#""proofValue": "u"#)] {
+            assert!(matches!(verify_credential(credential, &did), Err(ECDSAError::InvalidCredential)), "{credential}");
+        }
+
+        // A proof may name its contexts, which must start the credential's
+        let proof_type = r#""type": "DataIntegrityProof""#;
+        let with_context = |context: &str| SECP256K1_CREDENTIAL.replace(proof_type, &format!(r#""@context": "{context}", {proof_type}"#));
+        assert!(!verify_credential(&with_context("https://www.w3.org/ns/credentials/v2"), &did).unwrap());
+        assert!(matches!(
+            verify_credential(&with_context("https://example.com"), &did),
+            Err(ECDSAError::InvalidCredential)
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+//! `did:key` decentralized identifiers, which embed the public key itself.
+//!
+//! The method-specific identifier is the base58btc multibase encoding (prefix `z`) of a
+//! multicodec varint naming the key type followed by the key: a compressed SEC1 point for the
+//! ECDSA curves, or the 32 raw bytes of an Ed25519 key.
+
+use wasm_bindgen::prelude::*;
+
+use crate::keys::asn1::KeyAlgorithm;
+use crate::multiformats::{decode_multibase, decode_multicodec_key, encode_multibase, encode_multicodec_key};
+use crate::pem::ImportedPublicKey;
+use crate::ECDSAError;
+
+const DID_KEY_PREFIX: &str = "did:key:";
+
+/// Parses a `did:key` identifier, or a DID URL such as a verification method ID, into the key
+/// type and public key.
+pub(crate) fn parse_did_key(did: &str) -> Result<(KeyAlgorithm, Vec<u8>), ECDSAError> {
+    let identifier = did.split('#').next().unwrap_or_default();
+    let encoded = match identifier.strip_prefix(DID_KEY_PREFIX).map(decode_multibase) {
+        Some(Ok(('z', encoded))) => encoded,
+        _ => return Err(ECDSAError::InvalidDidKey),
+    };
+    // The method requires the compressed form, so other encodings of the key are rejected
+    decode_multicodec_key(&encoded).map_err(|error| match error {
+        ECDSAError::InvalidKeyEncoding => ECDSAError::InvalidDidKey,
+        error => error,
+    })
+}
+
+/// Encodes a public key as a `did:key` identifier.
+pub(crate) fn encode_did_key(algorithm: KeyAlgorithm, public_key: &[u8]) -> Result<String, ECDSAError> {
+    Ok(format!("{DID_KEY_PREFIX}{}", encode_multibase('z', &encode_multicodec_key(algorithm, public_key)?)?))
+}
+
+/// Resolves a `did:key` identifier to its public key.
+///
+/// # Arguments:
+/// * `did` - The identifier, e.g. `did:key:z6Mk...`, or a DID URL with a fragment such as
+///   `did:key:z6Mk...#z6Mk...`.
+///
+/// # Returns:
+/// * The key's curve and public key. ECDSA keys are compressed SEC1 points.
+///
+/// # Errors:
+/// * `InvalidDidKey` if the identifier is not a base58btc `did:key` or the key is not compressed.
+/// * `UnsupportedCurve` if the multicodec is not secp256k1, P-256, P-384, P-521 or Ed25519,
+///   or the curve is not enabled.
+/// * `InvalidPublicKey` if the key is not a valid point.
+#[wasm_bindgen]
+pub fn did_key_to_public_key(did: &str) -> Result<ImportedPublicKey, ECDSAError> {
+    let (algorithm, public_key) = parse_did_key(did)?;
+    Ok(ImportedPublicKey::new(algorithm, public_key))
+}
+
+/// Encodes a public key as a `did:key` identifier.
+///
+/// # Arguments:
+/// * `public_key` - The public key as a hex string: a SEC1 point (compressed or uncompressed),
+///   or 32 bytes for Ed25519.
+/// * `curve` - `secp256k1`, `P-256`, `P-384`, `P-521` or `Ed25519`.
+///
+/// # Returns:
+/// * The identifier, e.g. `did:key:zQ3s...` for a secp256k1 key.
+///
+/// # Errors:
+/// * `UnsupportedCurve` if the curve is unknown or not enabled.
+/// * `InvalidPublicKey` if the key is not a valid point on the curve.
+#[wasm_bindgen]
+pub fn public_key_to_did_key(public_key: &str, curve: &str) -> Result<String, ECDSAError> {
+    let algorithm = KeyAlgorithm::from_name(curve)?;
+    let bytes = hex::decode(public_key.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    encode_did_key(algorithm, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_base58;
+
+    // Test vectors from the did:key method specification
+    const SECP256K1: &str = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+    const SECP256K1_KEY: &str = "03874c15c7fda20e539c6e5ba573c139884c351188799f5458b4b41f7924f235cd";
+    #[cfg(feature = "p256")]
+    const P256: &str = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+    const ED25519: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+
+    #[test]
+    fn test_secp256k1_round_trip() {
+        let key = did_key_to_public_key(SECP256K1).unwrap();
+        assert_eq!(key.curve(), "secp256k1");
+        assert_eq!(key.public_key(), SECP256K1_KEY);
+        assert_eq!(public_key_to_did_key(SECP256K1_KEY, "secp256k1").unwrap(), SECP256K1);
+
+        // Uncompressed keys are compressed, and verification method IDs resolve too
+        let uncompressed = hex::encode(KeyAlgorithm::Secp256k1.uncompressed(&hex::decode(SECP256K1_KEY).unwrap()).unwrap());
+        assert_eq!(public_key_to_did_key(&uncompressed, "secp256k1").unwrap(), SECP256K1);
+        let method = format!("{SECP256K1}#{}", &SECP256K1[8..]);
+        assert_eq!(did_key_to_public_key(&method).unwrap().public_key(), SECP256K1_KEY);
+    }
+
+    #[test]
+    fn test_other_key_types() {
+        let key = did_key_to_public_key(ED25519).unwrap();
+        assert_eq!(key.curve(), "Ed25519");
+        assert_eq!(key.public_key(), "2e6fcce36701dc791488e0d0b1745cc1e33a4c1c9fcc41c63bd343dbbe0970e6");
+        assert_eq!(public_key_to_did_key(&key.public_key(), "Ed25519").unwrap(), ED25519);
+
+        #[cfg(feature = "p256")]
+        {
+            let key = did_key_to_public_key(P256).unwrap();
+            assert_eq!(key.curve(), "P-256");
+            assert_eq!(key.public_key(), "037f235830dd3defa722ef1aa249d6a0ddbba4f990b0817538933f573640653542");
+            assert_eq!(public_key_to_did_key(&key.public_key(), "P-256").unwrap(), P256);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_identifiers() {
+        for did in ["", "did:key:", "did:web:example.com", &SECP256K1.replace(":z", ":m"), &SECP256K1.replace("Q3s", "Q0s")] {
+            assert!(matches!(did_key_to_public_key(did), Err(ECDSAError::InvalidDidKey)), "{did}");
+        }
+
+        // An uncompressed point under the secp256k1 codec
+        let uncompressed = KeyAlgorithm::Secp256k1.uncompressed(&hex::decode(SECP256K1_KEY).unwrap()).unwrap();
+        let did = format!("did:key:z{}", to_base58(&[&[0xe7, 0x01], &uncompressed[..]].concat()));
+        assert!(matches!(did_key_to_public_key(&did), Err(ECDSAError::InvalidDidKey)));
+
+        // An unknown codec (x25519-pub) and an x coordinate beyond the field
+        let did = format!("did:key:z{}", to_base58(&[&[0xec, 0x01], &[9; 32][..]].concat()));
+        assert!(matches!(did_key_to_public_key(&did), Err(ECDSAError::UnsupportedCurve)));
+        let did = format!("did:key:z{}", to_base58(&[&[0xe7, 0x01, 0x02], &[0xff; 32][..]].concat()));
+        assert!(matches!(did_key_to_public_key(&did), Err(ECDSAError::InvalidPublicKey)));
+        assert!(matches!(public_key_to_did_key("00", "secp256k1"), Err(ECDSAError::InvalidPublicKey)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Elliptic-curve Diffie-Hellman over secp256k1.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::VerifyingKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{AffinePoint, NonZeroScalar, SecretKey};
+use sha2::{Digest, Sha256};
+
+use crate::keys::parse_public_key_hex;
+use crate::ECDSAError;
+
+/// How the ECDH shared point is turned into the returned shared secret.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SharedSecretFormat {
+    /// The raw 32-byte x-coordinate (the default), as used by SEC1 and Nostr NIP-04.
+    #[default]
+    XOnly,
+    /// The raw 33-byte SEC1 compressed shared point.
+    Compressed,
+    /// SHA-256 of the compressed shared point, as computed by libsecp256k1's `secp256k1_ecdh`.
+    Sha256,
+}
+
+/// Parses a 32-byte secp256k1 private key into a non-zero scalar.
+pub(crate) fn parse_private_key(bytes: &[u8]) -> Result<NonZeroScalar, ECDSAError> {
+    if bytes.len() != 32 {
+        return Err(ECDSAError::InvalidPrivateKey);
+    }
+    let secret = SecretKey::from_be_bytes(bytes).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+    Ok(secret.to_nonzero_scalar())
+}
+
+/// Computes the shared point `secret * peer`.
+///
+/// The peer key is already validated as a curve point and the scalar is non-zero, so the
+/// result is never the identity.
+pub(crate) fn shared_point(secret: &NonZeroScalar, peer: &VerifyingKey) -> AffinePoint {
+    (k256::PublicKey::from(peer).to_projective() * **secret).to_affine()
+}
+
+/// Encodes a shared point in the requested format.
+pub(crate) fn encode_shared_secret(point: &AffinePoint, format: SharedSecretFormat) -> Vec<u8> {
+    let compressed = point.to_encoded_point(true);
+    match format {
+        SharedSecretFormat::XOnly => compressed.as_bytes()[1..].to_vec(),
+        SharedSecretFormat::Compressed => compressed.as_bytes().to_vec(),
+        SharedSecretFormat::Sha256 => Sha256::digest(compressed.as_bytes()).to_vec(),
+    }
+}
+
+/// Derives an ECDH shared secret between our private key and a peer's public key.
+///
+/// # Arguments:
+/// * `private_key` - Our 32-byte private key, provided as a hex string.
+/// * `peer_public_key` - The peer's SEC1 encoded public key, provided as a hex string.
+/// * `format` - The shared secret encoding; defaults to `SharedSecretFormat::XOnly`.
+///   Raw outputs should be passed through a KDF before use as a symmetric key.
+///
+/// # Returns:
+/// * The shared secret, as a hex string.
+///
+/// # Errors:
+/// * Returns an error if either key is malformed.
+#[wasm_bindgen]
+pub fn ecdh(private_key: &str, peer_public_key: &str, format: Option<SharedSecretFormat>) -> Result<String, ECDSAError> {
+    let private_key = hex::decode(private_key).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+    let secret = parse_private_key(&private_key)?;
+    let peer = parse_public_key_hex(peer_public_key)?;
+
+    let point = shared_point(&secret, &peer);
+    Ok(hex::encode(encode_shared_secret(&point, format.unwrap_or_default())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE_SECRET: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const ALICE_PUBLIC: &str = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+    const BOB_SECRET: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+    const BOB_PUBLIC: &str = "03edd2671cfe9473a83a48eee5b77a399a9fbfde0644d31cffc04d23242fabdde0";
+    const SHARED_X: &str = "1cf9fc8b4e4773da56dea9cceec10675990d5c97a27dbcc28b2997a83273baab";
+
+    #[test]
+    fn test_shared_secret_is_symmetric() {
+        assert_eq!(ecdh(ALICE_SECRET, BOB_PUBLIC, None).unwrap(), SHARED_X);
+        assert_eq!(ecdh(BOB_SECRET, ALICE_PUBLIC, None).unwrap(), SHARED_X);
+    }
+
+    #[test]
+    fn test_shared_secret_formats() {
+        assert_eq!(
+            ecdh(ALICE_SECRET, BOB_PUBLIC, Some(SharedSecretFormat::Compressed)).unwrap(),
+            format!("03{}", SHARED_X)
+        );
+        assert_eq!(
+            ecdh(ALICE_SECRET, BOB_PUBLIC, Some(SharedSecretFormat::Sha256)).unwrap(),
+            "3d1431caf32e6bd85e5ddf825afa15a9490e242a033c114e2287f50df3d76224"
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_keys() {
+        assert!(matches!(ecdh(&"00".repeat(32), BOB_PUBLIC, None), Err(ECDSAError::InvalidPrivateKey)));
+        assert!(matches!(ecdh(ALICE_SECRET, &BOB_PUBLIC[2..], None), Err(ECDSAError::InvalidPublicKey)));
+    }
+}
@@ -0,0 +1,193 @@
+//! ECIES public-key encryption over secp256k1.
+//!
+//! The wire format matches the `eciesjs` / `eciespy` defaults, so payloads interoperate
+//! with those libraries:
+//!
+//! `ephemeral public key (65 bytes, uncompressed) || nonce (16) || tag (16) || ciphertext`
+//!
+//! The AES-256-GCM key is HKDF-SHA256 (no salt, no info) over the uncompressed ephemeral
+//! public key concatenated with the uncompressed ECDH shared point. With XChaCha20-Poly1305
+//! (eciesjs' `xchacha20` symmetric algorithm) the layout is the same but the nonce is 24 bytes.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{AffinePoint, NonZeroScalar};
+
+use crate::aes::{aes_256_gcm_decrypt, aes_256_gcm_encrypt, TAG_LENGTH};
+use crate::chacha20::{xchacha20_poly1305_open, xchacha20_poly1305_seal, XNONCE_LENGTH};
+use crate::ecdh::{parse_private_key, shared_point};
+use crate::kdf::hkdf_extract_expand;
+use crate::keys::{parse_public_key, parse_public_key_hex};
+use crate::random::{fill_random, random_scalar};
+use crate::ECDSAError;
+
+const EPHEMERAL_KEY_LENGTH: usize = 65;
+const NONCE_LENGTH: usize = 16;
+
+/// Symmetric ciphers for `ecies_encrypt` and `ecies_decrypt`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EciesCipher {
+    /// AES-256-GCM with a 16-byte nonce (the default).
+    #[default]
+    Aes256Gcm,
+    /// XChaCha20-Poly1305 with a 24-byte nonce; faster on platforms without AES hardware.
+    XChaCha20Poly1305,
+}
+
+impl EciesCipher {
+    fn nonce_length(self) -> usize {
+        match self {
+            EciesCipher::Aes256Gcm => NONCE_LENGTH,
+            EciesCipher::XChaCha20Poly1305 => XNONCE_LENGTH,
+        }
+    }
+}
+
+/// Derives the symmetric key from the ephemeral public key and the shared point.
+fn encapsulation_key(ephemeral: &AffinePoint, shared: &AffinePoint) -> Result<Vec<u8>, ECDSAError> {
+    let mut ikm = ephemeral.to_encoded_point(false).as_bytes().to_vec();
+    ikm.extend_from_slice(shared.to_encoded_point(false).as_bytes());
+    hkdf_extract_expand(&[], &ikm, &[], 32)
+}
+
+/// Encrypts with a caller-supplied ephemeral key and nonce.
+fn encrypt_with(
+    public_key: &k256::ecdsa::VerifyingKey,
+    ephemeral: &NonZeroScalar,
+    cipher: EciesCipher,
+    nonce: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, ECDSAError> {
+    let ephemeral_point = (k256::ProjectivePoint::GENERATOR * **ephemeral).to_affine();
+    let key = encapsulation_key(&ephemeral_point, &shared_point(ephemeral, public_key))?;
+    let (ciphertext, tag) = match cipher {
+        EciesCipher::Aes256Gcm => aes_256_gcm_encrypt(&key, nonce, &[], plaintext)?,
+        EciesCipher::XChaCha20Poly1305 => {
+            let nonce = nonce.try_into().map_err(|_| ECDSAError::InvalidNonce)?;
+            xchacha20_poly1305_seal(key.as_slice().try_into().unwrap(), nonce, &[], plaintext)?
+        }
+    };
+
+    let mut output = ephemeral_point.to_encoded_point(false).as_bytes().to_vec();
+    output.extend_from_slice(nonce);
+    output.extend_from_slice(&tag);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Encrypts a payload to a secp256k1 public key.
+///
+/// # Arguments:
+/// * `public_key` - The recipient's SEC1 encoded public key, provided as a hex string.
+/// * `plaintext` - The bytes to encrypt (`Uint8Array` in JS).
+/// * `cipher` - Optional symmetric cipher; defaults to AES-256-GCM.
+///
+/// # Returns:
+/// * The ECIES payload (`ephemeral key || nonce || tag || ciphertext`).
+///
+/// # Errors:
+/// * Returns an error if the public key is malformed or no randomness is available.
+#[wasm_bindgen]
+pub fn ecies_encrypt(public_key: &str, plaintext: &[u8], cipher: Option<EciesCipher>) -> Result<Vec<u8>, ECDSAError> {
+    let public_key = parse_public_key_hex(public_key)?;
+    let cipher = cipher.unwrap_or_default();
+    let ephemeral = random_scalar()?;
+    let mut nonce = vec![0u8; cipher.nonce_length()];
+    fill_random(&mut nonce)?;
+
+    encrypt_with(&public_key, &ephemeral, cipher, &nonce, plaintext)
+}
+
+/// Decrypts an ECIES payload produced by `ecies_encrypt` (or `eciesjs`).
+///
+/// # Arguments:
+/// * `private_key` - The recipient's 32-byte private key, provided as a hex string.
+/// * `ciphertext` - The ECIES payload (`Uint8Array` in JS).
+/// * `cipher` - Optional symmetric cipher the payload was encrypted with; defaults to AES-256-GCM.
+///
+/// # Returns:
+/// * The decrypted plaintext bytes.
+///
+/// # Errors:
+/// * Returns an error if the key or payload is malformed, or authentication fails.
+#[wasm_bindgen]
+pub fn ecies_decrypt(private_key: &str, ciphertext: &[u8], cipher: Option<EciesCipher>) -> Result<Vec<u8>, ECDSAError> {
+    let private_key = hex::decode(private_key).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+    let secret = parse_private_key(&private_key)?;
+    let cipher = cipher.unwrap_or_default();
+
+    if ciphertext.len() < EPHEMERAL_KEY_LENGTH + cipher.nonce_length() + TAG_LENGTH {
+        return Err(ECDSAError::DecryptionFailed);
+    }
+    let (ephemeral, rest) = ciphertext.split_at(EPHEMERAL_KEY_LENGTH);
+    let (nonce, rest) = rest.split_at(cipher.nonce_length());
+    let (tag, body) = rest.split_at(TAG_LENGTH);
+
+    let ephemeral = parse_public_key(ephemeral).map_err(|_| ECDSAError::DecryptionFailed)?;
+    let shared = shared_point(&secret, &ephemeral);
+    let key = encapsulation_key(k256::PublicKey::from(&ephemeral).as_affine(), &shared)?;
+    match cipher {
+        EciesCipher::Aes256Gcm => aes_256_gcm_decrypt(&key, nonce, &[], body, tag),
+        EciesCipher::XChaCha20Poly1305 => {
+            xchacha20_poly1305_open(key.as_slice().try_into().unwrap(), nonce.try_into().unwrap(), &[], body, tag)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const PUBLIC_KEY: &str = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+    // Produced with the eciespy key schedule, ephemeral key 5dab08...e0eb and nonce 00..0f
+    const PAYLOAD: &str = "04edd2671cfe9473a83a48eee5b77a399a9fbfde0644d31cffc04d23242fabdde0\
+                           d00821e72fd59b3c68f5098ef8736cb467b15e295653f237eb0a771baa804f4d\
+                           000102030405060708090a0b0c0d0e0f21a71f3f90b63126ca2fa3e185b91b49\
+                           28def49938b8962e17eb65";
+
+    #[test]
+    fn test_known_payload() {
+        let ephemeral = parse_private_key(
+            &hex::decode("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb").unwrap(),
+        )
+        .unwrap();
+        let nonce: [u8; 16] = std::array::from_fn(|i| i as u8);
+        let public_key = parse_public_key_hex(PUBLIC_KEY).unwrap();
+
+        let payload = encrypt_with(&public_key, &ephemeral, EciesCipher::Aes256Gcm, &nonce, b"hello ecies").unwrap();
+        assert_eq!(hex::encode(&payload), PAYLOAD);
+        assert_eq!(ecies_decrypt(PRIVATE_KEY, &payload, None).unwrap(), b"hello ecies");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let payload = ecies_encrypt(PUBLIC_KEY, b"end-to-end", None).unwrap();
+        assert_eq!(payload.len(), 65 + 16 + 16 + 10);
+        assert_eq!(ecies_decrypt(PRIVATE_KEY, &payload, None).unwrap(), b"end-to-end");
+        assert_ne!(ecies_encrypt(PUBLIC_KEY, b"end-to-end", None).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_xchacha20_round_trip() {
+        let cipher = Some(EciesCipher::XChaCha20Poly1305);
+        let payload = ecies_encrypt(PUBLIC_KEY, b"end-to-end", cipher).unwrap();
+        assert_eq!(payload.len(), 65 + 24 + 16 + 10);
+        assert_eq!(ecies_decrypt(PRIVATE_KEY, &payload, cipher).unwrap(), b"end-to-end");
+        // The cipher is not recorded in the payload, so decrypting as AES fails
+        assert!(matches!(ecies_decrypt(PRIVATE_KEY, &payload, None), Err(ECDSAError::DecryptionFailed)));
+    }
+
+    #[test]
+    fn test_rejects_tampered_payload() {
+        let mut payload = hex::decode(PAYLOAD).unwrap();
+        *payload.last_mut().unwrap() ^= 1;
+        assert!(matches!(ecies_decrypt(PRIVATE_KEY, &payload, None), Err(ECDSAError::DecryptionFailed)));
+
+        let other_key = "0000000000000000000000000000000000000000000000000000000000000001";
+        assert!(ecies_decrypt(other_key, &hex::decode(PAYLOAD).unwrap(), None).is_err());
+        assert!(ecies_decrypt(PRIVATE_KEY, &[0x04; 40], None).is_err());
+    }
+}
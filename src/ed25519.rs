@@ -0,0 +1,151 @@
+//! Ed25519 (RFC 8032) signature verification and public key derivation, using ed25519-dalek.
+
+use wasm_bindgen::prelude::*;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+
+use crate::ECDSAError;
+
+/// Length of an Ed25519 public key.
+pub(crate) const PUBLIC_KEY_LENGTH: usize = ed25519_dalek::PUBLIC_KEY_LENGTH;
+/// Length of an Ed25519 signature (R || S).
+pub(crate) const SIGNATURE_LENGTH: usize = ed25519_dalek::SIGNATURE_LENGTH;
+
+/// The group order L, little-endian.
+const ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// Derives the public key for a 32-byte private key seed (RFC 8032 section 5.1.5).
+pub(crate) fn public_key_from_seed(seed: &[u8; 32]) -> [u8; PUBLIC_KEY_LENGTH] {
+    SigningKey::from_bytes(seed).verifying_key().to_bytes()
+}
+
+/// Verifies an Ed25519 signature with ed25519-dalek's `verify_strict`, which also rejects
+/// small-order public keys and `R` values.
+pub(crate) fn verify_ed25519(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+    let public_key: &[u8; PUBLIC_KEY_LENGTH] = public_key.try_into().map_err(|_| ECDSAError::InvalidPublicKey)?;
+    let signature: &[u8; SIGNATURE_LENGTH] = signature.try_into().map_err(|_| ECDSAError::InvalidSignatureLength)?;
+    let public_key = VerifyingKey::from_bytes(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+
+    // S >= L is a malformed signature rather than merely an invalid one
+    let s = &signature[32..];
+    if s.iter().rev().cmp(ORDER.iter().rev()).is_ge() {
+        return Err(ECDSAError::InvalidSignatureFormat);
+    }
+    Ok(public_key.verify_strict(message, &Signature::from_bytes(signature)).is_ok())
+}
+
+/// Signs `message` with a 32-byte seed (RFC 8032 section 5.1.6); only used to build test vectors.
+#[cfg(test)]
+pub(crate) fn sign_ed25519(seed: &[u8; 32], message: &[u8]) -> [u8; SIGNATURE_LENGTH] {
+    use ed25519_dalek::Signer;
+    SigningKey::from_bytes(seed).sign(message).to_bytes()
+}
+
+/// Struct for Ed25519 signature verification.
+#[wasm_bindgen]
+pub struct Ed25519Verifier;
+
+impl Default for Ed25519Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl Ed25519Verifier {
+    /// Initializes a new instance of Ed25519Verifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Ed25519Verifier {
+        Ed25519Verifier
+    }
+
+    /// Verifies an Ed25519 signature over a UTF-8 message.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed. Ed25519 hashes it internally.
+    /// * `signature` - The 64-byte signature, provided as a hex string.
+    /// * `public_key` - The 32-byte public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the public key is not a valid point or the signature is malformed.
+    pub fn verify_signature(&self, message: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let signature = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let public_key = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+        verify_ed25519(&public_key, message.as_bytes(), &signature)
+    }
+
+    /// Verifies an Ed25519 signature over binary data (`Uint8Array` in JS).
+    pub fn verify_signature_bytes(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+        verify_ed25519(public_key, message, signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 8032 section 7.1, tests 1 and 2
+    const PUBLIC_KEY_1: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const SIGNATURE_1: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                               5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+    const PUBLIC_KEY_2: &str = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+    const SIGNATURE_2: &str = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                               085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+    #[test]
+    fn test_rfc8032_vectors() {
+        let verifier = Ed25519Verifier::new();
+        assert!(verifier.verify_signature("", SIGNATURE_1, PUBLIC_KEY_1).unwrap());
+
+        let signature = hex::decode(SIGNATURE_2).unwrap();
+        let public_key = hex::decode(PUBLIC_KEY_2).unwrap();
+        assert!(verifier.verify_signature_bytes(&[0x72], &signature, &public_key).unwrap());
+        assert!(!verifier.verify_signature_bytes(&[0x73], &signature, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_signing_helper_matches_rfc8032() {
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap();
+        assert_eq!(hex::encode(sign_ed25519(&seed.try_into().unwrap(), b"")), SIGNATURE_1);
+    }
+
+    #[test]
+    fn test_public_key_from_seed() {
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60").unwrap();
+        assert_eq!(hex::encode(public_key_from_seed(&seed.try_into().unwrap())), PUBLIC_KEY_1);
+    }
+
+    #[test]
+    fn test_utf8_message() {
+        let public_key = "03a107bff3ce10be1d70dd18e74bc09967e4d6309ba50d5f1ddc8664125531b8";
+        let signature = "a26c21bcc7bdfe26640efd44625b24a8ae9b8ed25e0f859f45c674d8b0b2c236\
+                         d6799ccb6cc03f5c19ffb78adf008b364c1aaac0bb01e2177c711aba19029c0b";
+        let verifier = Ed25519Verifier::new();
+        assert!(verifier.verify_signature("Hello, Ed25519 ✓", signature, public_key).unwrap());
+        assert!(!verifier.verify_signature("Hello, Ed25519 ✓", signature, PUBLIC_KEY_1).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let verifier = Ed25519Verifier::new();
+        assert!(verifier.verify_signature("", &SIGNATURE_1[..126], PUBLIC_KEY_1).is_err());
+
+        // S >= L is rejected rather than reduced (signature malleability)
+        let mut high_s = SIGNATURE_1[..64].to_string();
+        high_s.push_str("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010");
+        assert!(matches!(
+            verifier.verify_signature("", &high_s, PUBLIC_KEY_1),
+            Err(ECDSAError::InvalidSignatureFormat)
+        ));
+
+        // y = 2 is not the y-coordinate of any curve point
+        let mut off_curve = "02".to_string();
+        off_curve.push_str(&"00".repeat(31));
+        assert!(verifier.verify_signature("", SIGNATURE_1, &off_curve).is_err());
+    }
+}
@@ -0,0 +1,443 @@
+//! EIP-712 typed structured data hashing.
+//!
+//! Accepts the JSON document passed to `eth_signTypedData_v4`:
+//! `{ "types": {...}, "primaryType": "...", "domain": {...}, "message": {...} }`.
+
+use wasm_bindgen::prelude::*;
+use sha3::{Digest, Keccak256};
+
+use crate::constant_time::constant_time_eq;
+use crate::ethereum::{address_bytes, keccak256, parse_address, recover_address};
+use crate::signature::split_signature;
+use crate::json::{self, Value};
+use crate::{recover_from_digest, ECDSAError};
+
+/// Field order used when the document does not declare an `EIP712Domain` type.
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+/// A parsed typed-data document.
+struct TypedData {
+    types: Vec<(String, Vec<(String, String)>)>,
+    primary_type: String,
+    domain: Value,
+    message: Value,
+}
+
+impl TypedData {
+    fn parse(input: &str) -> Result<TypedData, ECDSAError> {
+        let document = json::parse(input).map_err(|_| ECDSAError::InvalidTypedData)?;
+        let types_value = document.get("types").and_then(Value::as_object).ok_or(ECDSAError::InvalidTypedData)?;
+
+        let mut types = Vec::new();
+        for (name, fields) in types_value {
+            let fields = fields.as_array().ok_or(ECDSAError::InvalidTypedData)?;
+            let mut members = Vec::new();
+            for field in fields {
+                let field_name = field.get("name").and_then(Value::as_str).ok_or(ECDSAError::InvalidTypedData)?;
+                let field_type = field.get("type").and_then(Value::as_str).ok_or(ECDSAError::InvalidTypedData)?;
+                members.push((field_name.to_string(), field_type.to_string()));
+            }
+            types.push((name.clone(), members));
+        }
+
+        let domain = document.get("domain").cloned().ok_or(ECDSAError::InvalidTypedData)?;
+        if !types.iter().any(|(name, _)| name == "EIP712Domain") {
+            let members = DOMAIN_FIELDS
+                .iter()
+                .filter(|(name, _)| domain.get(name).is_some())
+                .map(|(name, ty)| (name.to_string(), ty.to_string()))
+                .collect();
+            types.push(("EIP712Domain".to_string(), members));
+        }
+
+        let primary_type = document
+            .get("primaryType")
+            .and_then(Value::as_str)
+            .ok_or(ECDSAError::InvalidTypedData)?
+            .to_string();
+        let message = document.get("message").cloned().ok_or(ECDSAError::InvalidTypedData)?;
+
+        Ok(TypedData { types, primary_type, domain, message })
+    }
+
+    fn fields(&self, type_name: &str) -> Option<&[(String, String)]> {
+        self.types.iter().find(|(name, _)| name == type_name).map(|(_, fields)| fields.as_slice())
+    }
+
+    /// Collects `type_name` and every struct type it references, transitively.
+    fn dependencies(&self, type_name: &str, found: &mut Vec<String>) {
+        let base = base_type(type_name);
+        if found.iter().any(|name| name == base) {
+            return;
+        }
+        if let Some(fields) = self.fields(base) {
+            found.push(base.to_string());
+            for (_, field_type) in fields {
+                self.dependencies(field_type, found);
+            }
+        }
+    }
+
+    /// `encodeType`: the primary type followed by its dependencies sorted by name.
+    fn encode_type(&self, type_name: &str) -> Result<String, ECDSAError> {
+        let mut deps = Vec::new();
+        self.dependencies(type_name, &mut deps);
+        if deps.is_empty() {
+            return Err(ECDSAError::InvalidTypedData);
+        }
+        let primary = deps.remove(0);
+        deps.sort();
+
+        let mut encoded = String::new();
+        for name in std::iter::once(&primary).chain(deps.iter()) {
+            let fields = self.fields(name).ok_or(ECDSAError::InvalidTypedData)?;
+            let members: Vec<String> = fields.iter().map(|(n, t)| format!("{t} {n}")).collect();
+            encoded.push_str(&format!("{name}({})", members.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn type_hash(&self, type_name: &str) -> Result<[u8; 32], ECDSAError> {
+        Ok(keccak256(self.encode_type(type_name)?.as_bytes()))
+    }
+
+    /// `hashStruct(s) = keccak256(typeHash || encodeData(s))`.
+    fn hash_struct(&self, type_name: &str, value: &Value) -> Result<[u8; 32], ECDSAError> {
+        let fields = self.fields(type_name).ok_or(ECDSAError::InvalidTypedData)?;
+        let mut hasher = Keccak256::new();
+        hasher.update(self.type_hash(type_name)?);
+        for (name, field_type) in fields {
+            let member = value.get(name).unwrap_or(&Value::Null);
+            hasher.update(self.encode_value(field_type, member)?);
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// Encodes a single member value into its 32-byte slot.
+    fn encode_value(&self, field_type: &str, value: &Value) -> Result<[u8; 32], ECDSAError> {
+        if let Some(element_type) = field_type.strip_suffix(']') {
+            let (element_type, length) = element_type.rsplit_once('[').ok_or(ECDSAError::InvalidTypedData)?;
+            let items = value.as_array().ok_or(ECDSAError::InvalidTypedData)?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(items.len()) {
+                return Err(ECDSAError::InvalidTypedData);
+            }
+            let mut hasher = Keccak256::new();
+            for item in items {
+                hasher.update(self.encode_value(element_type, item)?);
+            }
+            return Ok(hasher.finalize().into());
+        }
+
+        // As in eth_signTypedData_v4, a missing or null struct or atomic member is a zero word
+        let is_struct = self.fields(field_type).is_some();
+        if *value == Value::Null && (is_struct || !matches!(field_type, "string" | "bytes")) {
+            return Ok([0u8; 32]);
+        }
+        if is_struct {
+            return self.hash_struct(field_type, value);
+        }
+
+        encode_atomic(field_type, value)
+    }
+
+    fn domain_separator(&self) -> Result<[u8; 32], ECDSAError> {
+        self.hash_struct("EIP712Domain", &self.domain)
+    }
+
+    /// The digest to sign: `keccak256(0x19 0x01 || domainSeparator || hashStruct(message))`.
+    fn signing_hash(&self) -> Result<[u8; 32], ECDSAError> {
+        let mut hasher = Keccak256::new();
+        hasher.update([0x19, 0x01]);
+        hasher.update(self.domain_separator()?);
+        hasher.update(self.hash_struct(&self.primary_type, &self.message)?);
+        Ok(hasher.finalize().into())
+    }
+}
+
+/// Strips any array suffixes from a type name (`Person[][2]` -> `Person`).
+fn base_type(type_name: &str) -> &str {
+    type_name.split('[').next().unwrap_or(type_name)
+}
+
+fn decode_hex(value: &Value) -> Result<Vec<u8>, ECDSAError> {
+    let text = value.as_str().ok_or(ECDSAError::InvalidTypedData)?;
+    let digits = text.strip_prefix("0x").ok_or(ECDSAError::InvalidTypedData)?;
+    hex::decode(digits).map_err(|_| ECDSAError::InvalidTypedData)
+}
+
+/// Encodes the atomic and dynamic (`bytes`, `string`) Solidity types.
+fn encode_atomic(field_type: &str, value: &Value) -> Result<[u8; 32], ECDSAError> {
+    let mut slot = [0u8; 32];
+    match field_type {
+        "string" => {
+            let text = value.as_str().ok_or(ECDSAError::InvalidTypedData)?;
+            return Ok(keccak256(text.as_bytes()));
+        }
+        "bytes" => return Ok(keccak256(&decode_hex(value)?)),
+        "bool" => match value {
+            Value::Bool(flag) => slot[31] = *flag as u8,
+            _ => return Err(ECDSAError::InvalidTypedData),
+        },
+        "address" => {
+            let text = value.as_str().ok_or(ECDSAError::InvalidTypedData)?;
+            let address = parse_address(text).map_err(|_| ECDSAError::InvalidTypedData)?;
+            slot[12..].copy_from_slice(&address);
+        }
+        _ => {
+            if let Some(size) = field_type.strip_prefix("bytes") {
+                let size: usize = size.parse().map_err(|_| ECDSAError::InvalidTypedData)?;
+                let bytes = decode_hex(value)?;
+                if !(1..=32).contains(&size) || bytes.len() != size {
+                    return Err(ECDSAError::InvalidTypedData);
+                }
+                slot[..size].copy_from_slice(&bytes);
+            } else if let Some(bits) = field_type.strip_prefix("uint") {
+                slot = parse_integer(value, integer_bits(bits)?, false)?;
+            } else if let Some(bits) = field_type.strip_prefix("int") {
+                slot = parse_integer(value, integer_bits(bits)?, true)?;
+            } else {
+                return Err(ECDSAError::InvalidTypedData);
+            }
+        }
+    }
+    Ok(slot)
+}
+
+fn integer_bits(suffix: &str) -> Result<usize, ECDSAError> {
+    let bits = if suffix.is_empty() { 256 } else { suffix.parse().map_err(|_| ECDSAError::InvalidTypedData)? };
+    if bits == 0 || bits > 256 || bits % 8 != 0 {
+        return Err(ECDSAError::InvalidTypedData);
+    }
+    Ok(bits)
+}
+
+/// Parses a JSON number or a decimal/0x-hex string into a 32-byte big-endian word,
+/// enforcing the range of a `bits`-wide (signed or unsigned) integer.
+fn parse_integer(value: &Value, bits: usize, signed: bool) -> Result<[u8; 32], ECDSAError> {
+    let text = match value {
+        Value::Number(text) | Value::String(text) => text.as_str(),
+        _ => return Err(ECDSAError::InvalidTypedData),
+    };
+    let (negative, magnitude_text) = match text.strip_prefix('-') {
+        Some(rest) if signed => (true, rest),
+        Some(_) => return Err(ECDSAError::InvalidTypedData),
+        None => (false, text),
+    };
+
+    let mut magnitude = [0u8; 32];
+    let (digits, radix) = match magnitude_text.strip_prefix("0x") {
+        Some(hex_digits) => (hex_digits, 16u32),
+        None => (magnitude_text, 10u32),
+    };
+    if digits.is_empty() {
+        return Err(ECDSAError::InvalidTypedData);
+    }
+    for c in digits.chars() {
+        let digit = c.to_digit(radix).ok_or(ECDSAError::InvalidTypedData)?;
+        // magnitude = magnitude * radix + digit, failing on overflow
+        let mut carry = digit;
+        for byte in magnitude.iter_mut().rev() {
+            let acc = *byte as u32 * radix + carry;
+            *byte = acc as u8;
+            carry = acc >> 8;
+        }
+        if carry != 0 {
+            return Err(ECDSAError::InvalidTypedData);
+        }
+    }
+
+    // Range check against 2^bits (unsigned) or 2^(bits - 1) (signed)
+    let limit_bits = if signed { bits - 1 } else { bits };
+    let mut limit = [0u8; 32];
+    if limit_bits < 256 {
+        limit[31 - limit_bits / 8] = 1 << (limit_bits % 8);
+        let exceeds = if negative { magnitude > limit } else { magnitude >= limit };
+        if exceeds {
+            return Err(ECDSAError::InvalidTypedData);
+        }
+    }
+
+    if negative {
+        // Two's complement: invert and add one
+        let mut carry = 1u16;
+        for byte in magnitude.iter_mut().rev() {
+            let acc = (!*byte) as u16 + carry;
+            *byte = acc as u8;
+            carry = acc >> 8;
+        }
+    }
+    Ok(magnitude)
+}
+
+/// Struct for EIP-712 typed data hashing and signature verification.
+#[wasm_bindgen]
+pub struct TypedDataVerifier;
+
+impl Default for TypedDataVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl TypedDataVerifier {
+    /// Initializes a new instance of TypedDataVerifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> TypedDataVerifier {
+        TypedDataVerifier
+    }
+
+    /// Computes the EIP-712 domain separator of a typed data document, as a 0x-prefixed hex string.
+    pub fn domain_separator(&self, typed_data: &str) -> Result<String, ECDSAError> {
+        let data = TypedData::parse(typed_data)?;
+        Ok(format!("0x{}", hex::encode(data.domain_separator()?)))
+    }
+
+    /// Computes the digest a wallet signs for a typed data document, as a 0x-prefixed hex string.
+    pub fn hash_typed_data(&self, typed_data: &str) -> Result<String, ECDSAError> {
+        let data = TypedData::parse(typed_data)?;
+        Ok(format!("0x{}", hex::encode(data.signing_hash()?)))
+    }
+
+    /// Recovers the address that signed a typed data document.
+    ///
+    /// # Arguments:
+    /// * `typed_data` - The typed data document as JSON.
+    /// * `signature` - The 65-byte (r || s || v) or 64-byte EIP-2098 signature, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * The signer's 0x-prefixed, EIP-55 checksummed address.
+    pub fn recover_signer(&self, typed_data: &str, signature: &str) -> Result<String, ECDSAError> {
+        let digest = TypedData::parse(typed_data)?.signing_hash()?;
+        let signature_bytes = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        recover_address(&digest, &signature_bytes)
+    }
+
+    /// Verifies that a typed data document was signed by `expected_address`.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature was produced by `expected_address`.
+    ///
+    /// # Errors:
+    /// * Returns an error if the document, signature, or address is malformed.
+    pub fn verify(&self, typed_data: &str, signature: &str, expected_address: &str) -> Result<bool, ECDSAError> {
+        let expected = parse_address(expected_address)?;
+        let digest = TypedData::parse(typed_data)?.signing_hash()?;
+        let signature_bytes = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let (sig, recovery_id) = split_signature(&signature_bytes)?;
+
+        match recover_from_digest(&digest, &sig, recovery_id) {
+            Ok(pubkey) => Ok(constant_time_eq(&address_bytes(&pubkey), &expected)),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from the EIP-712 specification.
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Person": [
+                {"name": "name", "type": "string"},
+                {"name": "wallet", "type": "address"}
+            ],
+            "Mail": [
+                {"name": "from", "type": "Person"},
+                {"name": "to", "type": "Person"},
+                {"name": "contents", "type": "string"}
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+            "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    const SIGNATURE: &str = "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c";
+
+    #[test]
+    fn test_encode_type() {
+        let data = TypedData::parse(MAIL).unwrap();
+        assert_eq!(
+            data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+    }
+
+    #[test]
+    fn test_hashes_match_specification() {
+        let verifier = TypedDataVerifier::new();
+        assert_eq!(
+            verifier.domain_separator(MAIL).unwrap(),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            verifier.hash_typed_data(MAIL).unwrap(),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn test_recover_and_verify() {
+        let verifier = TypedDataVerifier::new();
+        assert_eq!(
+            verifier.recover_signer(MAIL, SIGNATURE).unwrap(),
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+        );
+        assert!(verifier.verify(MAIL, SIGNATURE, "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826").unwrap());
+        assert!(!verifier.verify(MAIL, SIGNATURE, "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB").unwrap());
+    }
+
+    #[test]
+    fn test_missing_members_encode_as_zero() {
+        // `to` is null and `from.wallet` omitted: both hash as a zero word
+        let mail = MAIL
+            .replace(r#""wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826""#, r#""age": 7"#)
+            .replace(r#"{"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"}"#, "null");
+        assert_eq!(
+            TypedDataVerifier::new().hash_typed_data(&mail).unwrap(),
+            "0xa5aab798a01b876d1b9e82fc5ac60c897284a3ff53590be3da80b01e0759f67b"
+        );
+
+        // Dynamic members have no zero encoding
+        let mail = MAIL.replace(r#""contents": "Hello, Bob!""#, r#""contents": null"#);
+        assert!(TypedDataVerifier::new().hash_typed_data(&mail).is_err());
+    }
+
+    #[test]
+    fn test_integer_encoding() {
+        let word = parse_integer(&Value::Number("-1".into()), 256, true).unwrap();
+        assert_eq!(word, [0xff; 32]);
+        let word = parse_integer(&Value::String("0x0100".into()), 16, false).unwrap();
+        assert_eq!(&word[30..], &[0x01, 0x00]);
+        assert!(parse_integer(&Value::Number("256".into()), 8, false).is_err());
+        assert!(parse_integer(&Value::Number("-129".into()), 8, true).is_err());
+        assert!(parse_integer(&Value::Number("-128".into()), 8, true).is_ok());
+        assert!(parse_integer(&Value::Number("1.5".into()), 256, false).is_err());
+    }
+}
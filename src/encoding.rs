@@ -0,0 +1,676 @@
+//! Base58 and Base58Check, the text encodings of Bitcoin keys and legacy addresses, and
+//! Bech32/Bech32m (BIP-173, BIP-350) for segwit addresses, plus the standard Base64 used
+//! by signed-message formats and the Base32 and Base36 of content identifiers.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::ECDSAError;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const BASE36_ALPHABET: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Encodes bytes as a big-endian number in the base of `alphabet`; each leading zero byte
+/// becomes a leading zero digit.
+fn to_radix(data: &[u8], alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u32;
+    let zeros = data.iter().take_while(|&&byte| byte == 0).count();
+
+    // Little-endian digits of the big-endian input
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 2);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % base) as u8;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push((carry % base) as u8);
+            carry /= base;
+        }
+    }
+
+    let mut text = (alphabet[0] as char).to_string().repeat(zeros);
+    text.extend(digits.iter().rev().map(|&digit| alphabet[digit as usize] as char));
+    text
+}
+
+/// Decodes text written by `to_radix`, or returns `None` if it contains a character outside
+/// the alphabet.
+fn from_radix(text: &str, alphabet: &[u8]) -> Option<Vec<u8>> {
+    let base = alphabet.len() as u32;
+    let zeros = text.bytes().take_while(|&c| c == alphabet[0]).count();
+
+    // Little-endian base-256 bytes of the big-endian input
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len());
+    for c in text.bytes().skip(zeros) {
+        let mut carry = alphabet.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * base;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut data = vec![0u8; zeros];
+    data.extend(bytes.iter().rev());
+    Some(data)
+}
+
+/// Encodes bytes as Base58; each leading zero byte becomes a leading `1`.
+pub(crate) fn to_base58(data: &[u8]) -> String {
+    to_radix(data, BASE58_ALPHABET)
+}
+
+/// Decodes Base58 text, or returns `None` if it contains a character outside the alphabet.
+pub(crate) fn from_base58(text: &str) -> Option<Vec<u8>> {
+    from_radix(text, BASE58_ALPHABET)
+}
+
+/// Encodes bytes as lowercase Base36, the multibase `k` form of IPNS names.
+pub(crate) fn to_base36(data: &[u8]) -> String {
+    to_radix(data, BASE36_ALPHABET)
+}
+
+/// Decodes lowercase Base36 text.
+pub(crate) fn from_base36(text: &str) -> Option<Vec<u8>> {
+    from_radix(text, BASE36_ALPHABET)
+}
+
+/// The first four bytes of SHA-256(SHA-256(data)).
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Encodes `data` followed by its 4-byte double-SHA-256 checksum as Base58.
+pub(crate) fn to_base58check(data: &[u8]) -> String {
+    let mut payload = data.to_vec();
+    payload.extend_from_slice(&checksum(data));
+    to_base58(&payload)
+}
+
+/// Decodes Base58Check text, returning the data without its checksum.
+/// Returns `None` if the text is not Base58 or the checksum does not match.
+pub(crate) fn from_base58check(text: &str) -> Option<Vec<u8>> {
+    let mut payload = from_base58(text)?;
+    if payload.len() < 4 {
+        return None;
+    }
+    let expected = payload.split_off(payload.len() - 4);
+    (checksum(&payload)[..] == expected[..]).then_some(payload)
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as padded standard Base64 (RFC 4648 section 4).
+pub(crate) fn to_base64(data: &[u8]) -> String {
+    let mut text = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| group | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64_ALPHABET[(group >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// Decodes padded standard Base64, rejecting bad characters, missing padding and
+/// non-zero trailing bits.
+pub(crate) fn from_base64(text: &str) -> Option<Vec<u8>> {
+    let bytes = text.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let mut data = Vec::with_capacity(bytes.len() / 4 * 3);
+    for (index, chunk) in bytes.chunks(4).enumerate() {
+        let is_last = index == bytes.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && !is_last) {
+            return None;
+        }
+
+        let mut group = 0u32;
+        for (i, &c) in chunk[..4 - padding].iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+            group |= value << (18 - 6 * i);
+        }
+        let length = 3 - padding;
+        if group & ((1 << (8 * (3 - length))) - 1) != 0 {
+            return None;
+        }
+        data.extend((0..length).map(|i| (group >> (16 - 8 * i)) as u8));
+    }
+    Some(data)
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Encodes bytes as unpadded lowercase Base32 (RFC 4648 section 6), the multibase `b` form
+/// CIDs use.
+pub(crate) fn to_base32(data: &[u8]) -> String {
+    let mut text = String::with_capacity(data.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(BASE32_ALPHABET[(buffer >> bits) as usize & 31] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        text.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    text
+}
+
+/// Decodes unpadded lowercase Base32. Leftover bits must be zero, so each text has one decoding.
+pub(crate) fn from_base32(text: &str) -> Option<Vec<u8>> {
+    let mut data = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u16;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            data.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // At most four bits of padding, all zero
+    (bits < 5 && buffer == 0).then_some(data)
+}
+
+/// Encodes bytes as unpadded Base64url (RFC 4648 section 5), as used by JOSE.
+pub(crate) fn to_base64url(data: &[u8]) -> String {
+    to_base64(data).trim_end_matches('=').replace('+', "-").replace('/', "_")
+}
+
+/// Decodes unpadded Base64url, rejecting padding and characters of the standard alphabet.
+pub(crate) fn from_base64url(text: &str) -> Option<Vec<u8>> {
+    if text.bytes().any(|c| matches!(c, b'+' | b'/' | b'=')) {
+        return None;
+    }
+    let mut padded = text.replace('-', "+").replace('_', "/");
+    padded.extend(std::iter::repeat_n('=', (4 - text.len() % 4) % 4));
+    from_base64(&padded)
+}
+
+/// A decoded Base58Check string: the leading version byte and the data after it.
+#[wasm_bindgen]
+pub struct Base58CheckData {
+    version: u8,
+    payload: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Base58CheckData {
+    /// The version byte, e.g. 0x00 for a P2PKH address or 0x80 for a WIF key.
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the data following the version byte (`Uint8Array` in JS).
+    pub fn payload(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
+}
+
+/// Encodes bytes as Base58 (Bitcoin alphabet), without a checksum.
+#[wasm_bindgen]
+pub fn base58_encode(data: &[u8]) -> String {
+    to_base58(data)
+}
+
+/// Decodes Base58 (Bitcoin alphabet) text.
+///
+/// # Errors:
+/// * Returns an error if the text contains a character outside the alphabet.
+#[wasm_bindgen]
+pub fn base58_decode(text: &str) -> Result<Vec<u8>, ECDSAError> {
+    from_base58(text).ok_or(ECDSAError::InvalidEncoding)
+}
+
+/// Encodes a version byte and payload as Base58Check.
+///
+/// # Arguments:
+/// * `version` - The leading version byte.
+/// * `payload` - The data to encode (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * The Base58 text of `version || payload || checksum`.
+#[wasm_bindgen]
+pub fn base58check_encode(version: u8, payload: &[u8]) -> String {
+    let mut data = vec![version];
+    data.extend_from_slice(payload);
+    to_base58check(&data)
+}
+
+/// Decodes Base58Check text and validates its checksum.
+///
+/// # Returns:
+/// * The version byte and payload.
+///
+/// # Errors:
+/// * Returns an error if the text is not Base58, is too short, or the checksum does not match.
+#[wasm_bindgen]
+pub fn base58check_decode(text: &str) -> Result<Base58CheckData, ECDSAError> {
+    let data = from_base58check(text).ok_or(ECDSAError::InvalidEncoding)?;
+    let (&version, payload) = data.split_first().ok_or(ECDSAError::InvalidEncoding)?;
+    Ok(Base58CheckData { version, payload: payload.to_vec() })
+}
+
+const BECH32_ALPHABET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_MAX_LENGTH: usize = 90;
+
+/// The checksum constant distinguishing the two Bech32 flavours.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bech32Variant {
+    /// BIP-173 Bech32, used by segwit v0 addresses.
+    Bech32,
+    /// BIP-350 Bech32m, used by segwit v1+ (Taproot) addresses.
+    Bech32m,
+}
+
+impl Bech32Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let mut checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+        checksum
+    })
+}
+
+/// The HRP's high bits, a zero separator, then its low bits, as fed into the checksum.
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes().map(|c| c >> 5).chain([0]).chain(hrp.bytes().map(|c| c & 31))
+}
+
+/// Regroups bits, e.g. 8-bit bytes into 5-bit Bech32 values. When `pad` is false the input
+/// must end on a group boundary, apart from at most 4 zero bits.
+pub(crate) fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let (mut accumulator, mut bits) = (0u32, 0u32);
+    let max = (1u32 << to) - 1;
+    let mut output = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    for &value in data {
+        if (value as u32) >> from != 0 {
+            return None;
+        }
+        accumulator = (accumulator << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            output.push(((accumulator >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            output.push(((accumulator << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (accumulator << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(output)
+}
+
+/// Encodes a human-readable part and 5-bit values with a Bech32 or Bech32m checksum.
+pub(crate) fn to_bech32(hrp: &str, values: &[u8], variant: Bech32Variant) -> Result<String, ECDSAError> {
+    if hrp.is_empty() || hrp.bytes().any(|c| !(33..=126).contains(&c) || c.is_ascii_uppercase()) {
+        return Err(ECDSAError::InvalidEncoding);
+    }
+    if hrp.len() + 1 + values.len() + 6 > BECH32_MAX_LENGTH {
+        return Err(ECDSAError::InvalidEncoding);
+    }
+
+    let checksum_input = hrp_expand(hrp).chain(values.iter().copied()).chain([0; 6]);
+    let checksum = polymod(checksum_input) ^ variant.constant();
+
+    let mut text = format!("{hrp}1");
+    text.extend(values.iter().map(|&value| BECH32_ALPHABET[value as usize] as char));
+    text.extend((0..6).map(|i| BECH32_ALPHABET[((checksum >> (5 * (5 - i))) & 31) as usize] as char));
+    Ok(text)
+}
+
+/// Decodes Bech32 or Bech32m text into its lowercase HRP, 5-bit values and variant.
+pub(crate) fn from_bech32(text: &str) -> Option<(String, Vec<u8>, Bech32Variant)> {
+    from_bech32_with_limit(text, BECH32_MAX_LENGTH)
+}
+
+/// Like `from_bech32`, for formats such as BOLT11 invoices that lift the 90 character limit.
+pub(crate) fn from_bech32_with_limit(text: &str, max_length: usize) -> Option<(String, Vec<u8>, Bech32Variant)> {
+    if text.len() > max_length || text.bytes().any(|c| !(33..=126).contains(&c)) {
+        return None;
+    }
+    // Either case is allowed, but not both
+    let lower = text.to_ascii_lowercase();
+    if lower != text && text.to_ascii_uppercase() != text {
+        return None;
+    }
+
+    let separator = lower.rfind('1')?;
+    let (hrp, data) = (&lower[..separator], &lower[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 {
+        return None;
+    }
+    let values = data
+        .bytes()
+        .map(|c| BECH32_ALPHABET.iter().position(|&a| a == c).map(|value| value as u8))
+        .collect::<Option<Vec<u8>>>()?;
+
+    let residue = polymod(hrp_expand(hrp).chain(values.iter().copied()));
+    let variant = [Bech32Variant::Bech32, Bech32Variant::Bech32m]
+        .into_iter()
+        .find(|variant| variant.constant() == residue)?;
+    Some((hrp.to_string(), values[..values.len() - 6].to_vec(), variant))
+}
+
+/// Encodes a segwit output program as an address, choosing Bech32 for v0 and Bech32m above.
+pub(crate) fn encode_segwit(hrp: &str, version: u8, program: &[u8]) -> Result<String, ECDSAError> {
+    check_witness_program(version, program)?;
+    let variant = if version == 0 { Bech32Variant::Bech32 } else { Bech32Variant::Bech32m };
+    let mut values = vec![version];
+    values.extend(convert_bits(program, 8, 5, true).expect("bytes fit in 8 bits"));
+    to_bech32(hrp, &values, variant).map_err(|_| ECDSAError::InvalidAddress)
+}
+
+/// Decodes a segwit address for the expected HRP into its witness version and program.
+pub(crate) fn decode_segwit(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), ECDSAError> {
+    let (decoded_hrp, values, variant) = from_bech32(address).ok_or(ECDSAError::InvalidAddress)?;
+    if decoded_hrp != hrp {
+        return Err(ECDSAError::InvalidAddress);
+    }
+    let (&version, data) = values.split_first().ok_or(ECDSAError::InvalidAddress)?;
+    let expected = if version == 0 { Bech32Variant::Bech32 } else { Bech32Variant::Bech32m };
+    if variant != expected {
+        return Err(ECDSAError::InvalidAddress);
+    }
+    let program = convert_bits(data, 5, 8, false).ok_or(ECDSAError::InvalidAddress)?;
+    check_witness_program(version, &program)?;
+    Ok((version, program))
+}
+
+fn check_witness_program(version: u8, program: &[u8]) -> Result<(), ECDSAError> {
+    let valid = match version {
+        0 => matches!(program.len(), 20 | 32),
+        1..=16 => (2..=40).contains(&program.len()),
+        _ => false,
+    };
+    valid.then_some(()).ok_or(ECDSAError::InvalidAddress)
+}
+
+/// A decoded Bech32 or Bech32m string.
+#[wasm_bindgen]
+pub struct Bech32Data {
+    hrp: String,
+    data: Vec<u8>,
+    variant: Bech32Variant,
+}
+
+#[wasm_bindgen]
+impl Bech32Data {
+    /// The human-readable part, in lowercase.
+    #[wasm_bindgen(getter)]
+    pub fn hrp(&self) -> String {
+        self.hrp.clone()
+    }
+
+    /// Which checksum the string carries.
+    #[wasm_bindgen(getter)]
+    pub fn variant(&self) -> Bech32Variant {
+        self.variant
+    }
+
+    /// Returns the data part regrouped into bytes (`Uint8Array` in JS).
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+/// A decoded segwit address.
+#[wasm_bindgen]
+pub struct SegwitProgram {
+    version: u8,
+    program: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl SegwitProgram {
+    /// The witness version: 0 for P2WPKH/P2WSH, 1 for Taproot.
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the witness program, e.g. a 20-byte key hash (`Uint8Array` in JS).
+    pub fn program(&self) -> Vec<u8> {
+        self.program.clone()
+    }
+}
+
+/// Encodes bytes as Bech32 or Bech32m.
+///
+/// # Arguments:
+/// * `hrp` - The lowercase human-readable part, e.g. `bc`.
+/// * `data` - The bytes to encode; they are regrouped into 5-bit values.
+/// * `variant` - The checksum flavour.
+///
+/// # Errors:
+/// * Returns an error if the HRP is invalid or the result would exceed 90 characters.
+#[wasm_bindgen]
+pub fn bech32_encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> Result<String, ECDSAError> {
+    to_bech32(hrp, &convert_bits(data, 8, 5, true).expect("bytes fit in 8 bits"), variant)
+}
+
+/// Decodes Bech32 or Bech32m text, detecting the variant from the checksum.
+///
+/// # Errors:
+/// * Returns an error for mixed case, bad characters, a bad checksum, or a data part
+///   that does not regroup into whole bytes.
+#[wasm_bindgen]
+pub fn bech32_decode(text: &str) -> Result<Bech32Data, ECDSAError> {
+    let (hrp, values, variant) = from_bech32(text).ok_or(ECDSAError::InvalidEncoding)?;
+    let data = convert_bits(&values, 5, 8, false).ok_or(ECDSAError::InvalidEncoding)?;
+    Ok(Bech32Data { hrp, data, variant })
+}
+
+/// Encodes a segwit address (BIP-173 for v0, BIP-350 for v1+).
+///
+/// # Arguments:
+/// * `hrp` - `bc` for mainnet, `tb` for testnet, `bcrt` for regtest.
+/// * `version` - The witness version, 0 to 16.
+/// * `program` - The witness program (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * Returns an error if the version or program length is invalid, or the HRP is malformed.
+#[wasm_bindgen]
+pub fn segwit_address_encode(hrp: &str, version: u8, program: &[u8]) -> Result<String, ECDSAError> {
+    encode_segwit(hrp, version, program)
+}
+
+/// Decodes and validates a segwit address.
+///
+/// # Arguments:
+/// * `hrp` - The expected human-readable part, e.g. `bc`.
+/// * `address` - The address text.
+///
+/// # Errors:
+/// * Returns an error if the address is malformed, uses the wrong checksum variant for its
+///   version, or belongs to a different network.
+#[wasm_bindgen]
+pub fn segwit_address_decode(hrp: &str, address: &str) -> Result<SegwitProgram, ECDSAError> {
+    let (version, program) = decode_segwit(hrp, address)?;
+    Ok(SegwitProgram { version, program })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58_vectors() {
+        // From Bitcoin Core's base58_encode_decode.json
+        let cases = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            ("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+            ("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+            ("00000000000000000000", "1111111111"),
+        ];
+        for (data, text) in cases {
+            assert_eq!(to_base58(&hex::decode(data).unwrap()), text);
+            assert_eq!(hex::encode(from_base58(text).unwrap()), data);
+        }
+        assert!(from_base58("0OIl").is_none());
+    }
+
+    #[test]
+    fn test_base58check() {
+        // Version byte 0x00 and the HASH160 of the generator point: a P2PKH address
+        let data = hex::decode("00751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        let text = to_base58check(&data);
+        assert_eq!(text, "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(from_base58check(&text).unwrap(), data);
+        assert!(from_base58check("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ").is_none());
+        assert!(from_base58check("1").is_none());
+    }
+
+    #[test]
+    fn test_versioned_api() {
+        let hash = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        let address = base58check_encode(0x00, &hash);
+        assert_eq!(address, "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+        let decoded = base58check_decode(&address).unwrap();
+        assert_eq!(decoded.version(), 0x00);
+        assert_eq!(decoded.payload(), hash);
+
+        assert!(matches!(base58check_decode("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ"), Err(ECDSAError::InvalidEncoding)));
+        // A valid checksum over no data at all has no version byte
+        assert!(base58check_decode(&to_base58check(&[])).is_err());
+        assert!(matches!(base58_decode("0x"), Err(ECDSAError::InvalidEncoding)));
+        assert_eq!(base58_decode(&base58_encode(b"hello")).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_bech32_checksums() {
+        // BIP-173 and BIP-350 valid strings
+        for (text, variant) in [
+            ("A12UEL5L", Bech32Variant::Bech32),
+            ("a12uel5l", Bech32Variant::Bech32),
+            ("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", Bech32Variant::Bech32),
+            ("A1LQFN3A", Bech32Variant::Bech32m),
+            ("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Bech32Variant::Bech32m),
+        ] {
+            let (_, _, detected) = from_bech32(text).unwrap();
+            assert_eq!(detected, variant, "{text}");
+        }
+        // Mixed case, bad checksum and empty HRP
+        for text in ["A12uEL5L", "a12uel5m", "1qzzfhee"] {
+            assert!(from_bech32(text).is_none(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_bech32_round_trip() {
+        let encoded = bech32_encode("test", b"hello", Bech32Variant::Bech32m).unwrap();
+        let decoded = bech32_decode(&encoded).unwrap();
+        assert_eq!(decoded.hrp(), "test");
+        assert_eq!(decoded.variant(), Bech32Variant::Bech32m);
+        assert_eq!(decoded.data(), b"hello");
+        assert_eq!(bech32_decode(&encoded.to_uppercase()).unwrap().data(), b"hello");
+
+        assert!(bech32_encode("BC", b"", Bech32Variant::Bech32).is_err());
+        assert!(bech32_encode("bc", &[0; 60], Bech32Variant::Bech32).is_err());
+    }
+
+    #[test]
+    fn test_segwit_addresses() {
+        let p2wpkh = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let key_hash = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+        let decoded = segwit_address_decode("bc", &p2wpkh.to_uppercase()).unwrap();
+        assert_eq!((decoded.version(), decoded.program()), (0, key_hash.clone()));
+        assert_eq!(segwit_address_encode("bc", 0, &key_hash).unwrap(), p2wpkh);
+
+        let p2tr = "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0";
+        let x_only = hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert_eq!(segwit_address_encode("bc", 1, &x_only).unwrap(), p2tr);
+        assert_eq!(segwit_address_decode("bc", p2tr).unwrap().program(), x_only);
+
+        // Wrong network, and v0 with a Bech32m checksum
+        assert!(segwit_address_decode("tb", p2wpkh).is_err());
+        let mut values = vec![0];
+        values.extend(convert_bits(&key_hash, 8, 5, true).unwrap());
+        let wrong_variant = to_bech32("bc", &values, Bech32Variant::Bech32m).unwrap();
+        assert!(matches!(segwit_address_decode("bc", &wrong_variant), Err(ECDSAError::InvalidAddress)));
+
+        assert!(segwit_address_encode("bc", 0, &[0; 21]).is_err());
+        assert!(segwit_address_encode("bc", 17, &[0; 32]).is_err());
+    }
+
+    #[test]
+    fn test_base64_vectors() {
+        // RFC 4648 section 10
+        for (data, text) in [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foobar", "Zm9vYmFy")] {
+            assert_eq!(to_base64(data.as_bytes()), text);
+            assert_eq!(from_base64(text).unwrap(), data.as_bytes());
+        }
+        for text in ["Zg", "Zg=a", "Zh==", "Zg==Zg==", "Z===", "Zm9v!A=="] {
+            assert!(from_base64(text).is_none(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_base32_vectors() {
+        // RFC 4648 section 10, lowercase and without padding
+        for (data, text) in [("", ""), ("f", "my"), ("fo", "mzxq"), ("foo", "mzxw6"), ("foob", "mzxw6yq"), ("fooba", "mzxw6ytb"), ("foobar", "mzxw6ytboi")] {
+            assert_eq!(to_base32(data.as_bytes()), text);
+            assert_eq!(from_base32(text).unwrap(), data.as_bytes());
+        }
+        // Uppercase, a length no encoding has, and nonzero padding bits
+        for text in ["MY", "m", "mz"] {
+            assert!(from_base32(text).is_none(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_base64url() {
+        assert_eq!(to_base64url(&[0xfb, 0xff]), "-_8");
+        assert_eq!(from_base64url("-_8").unwrap(), [0xfb, 0xff]);
+        assert_eq!(from_base64url("").unwrap(), b"");
+        for text in ["+/8", "-_8=", "Z", "-_9"] {
+            assert!(from_base64url(text).is_none(), "{text}");
+        }
+    }
+}
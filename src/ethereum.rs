@@ -0,0 +1,243 @@
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::VerifyingKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use sha3::{Digest, Keccak256};
+
+use crate::constant_time::constant_time_eq;
+use crate::keys::parse_public_key_hex;
+use crate::signature::split_signature;
+use crate::{recover_from_digest, ECDSAError, ECDSAVerifier};
+
+/// Computes the Keccak-256 digest of `data`.
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Derives the 20-byte Ethereum address of a public key:
+/// the last 20 bytes of Keccak-256 over the uncompressed point without its 0x04 prefix.
+pub(crate) fn address_bytes(pubkey: &VerifyingKey) -> [u8; 20] {
+    let point = pubkey.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Formats a 20-byte address as a 0x-prefixed EIP-55 mixed-case checksummed string.
+pub(crate) fn to_checksum_address(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        if nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+    checksummed
+}
+
+/// Parses a 0x-prefixed, 40 hex digit Ethereum address (checksum casing is not enforced).
+pub(crate) fn parse_address(address: &str) -> Result<[u8; 20], ECDSAError> {
+    let digits = address.strip_prefix("0x").ok_or(ECDSAError::InvalidAddress)?;
+    let bytes = hex::decode(digits).map_err(|_| ECDSAError::InvalidAddress)?;
+    bytes.try_into().map_err(|_| ECDSAError::InvalidAddress)
+}
+
+/// Computes Keccak-256 over `prefix + len(message) + message`, the length in decimal.
+pub(crate) fn prefixed_message_hash(prefix: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(prefix);
+    hasher.update(message.len().to_string().as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// Computes the EIP-191 (version 0x45) digest signed by `personal_sign`:
+/// Keccak-256 over `"\x19Ethereum Signed Message:\n" + len(message) + message`.
+pub(crate) fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    prefixed_message_hash(b"\x19Ethereum Signed Message:\n", message)
+}
+
+/// Recovers the checksummed address that signed `digest` with the 65-byte `signature`.
+pub(crate) fn recover_address(digest: &[u8], signature: &[u8]) -> Result<String, ECDSAError> {
+    let (sig, recovery_id) = split_signature(signature)?;
+    let pubkey = recover_from_digest(digest, &sig, recovery_id)?;
+    Ok(to_checksum_address(&address_bytes(&pubkey)))
+}
+
+/// Derives the Ethereum address of a secp256k1 public key.
+///
+/// # Arguments:
+/// * `pubkey` - The SEC1 public key (compressed, uncompressed or hybrid), provided as a hex string.
+///
+/// # Returns:
+/// * The 0x-prefixed, EIP-55 checksummed address.
+///
+/// # Errors:
+/// * Returns an error if the public key is invalid.
+#[wasm_bindgen]
+pub fn ethereum_address_from_pubkey(pubkey: &str) -> Result<String, ECDSAError> {
+    let key = parse_public_key_hex(pubkey.trim_start_matches("0x"))?;
+    Ok(to_checksum_address(&address_bytes(&key)))
+}
+
+/// Checks that an address carries a correct EIP-55 checksum.
+///
+/// Addresses in all-lowercase or all-uppercase carry no checksum and are rejected, as is
+/// anything that is not a 0x-prefixed 20-byte address.
+#[wasm_bindgen]
+pub fn validate_eip55(address: &str) -> bool {
+    parse_address(address).is_ok_and(|bytes| to_checksum_address(&bytes) == address)
+}
+
+#[wasm_bindgen]
+impl ECDSAVerifier {
+    /// Recovers the Ethereum address that signed a message, like Solidity's `ecrecover`.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed; it is hashed with Keccak-256.
+    /// * `signature` - The 65-byte signature (r || s || v), provided as a hex string.
+    ///   `v` may be 0/1 or 27/28. A 64-byte EIP-2098 compact signature is also accepted.
+    ///
+    /// # Returns:
+    /// * The signer's 0x-prefixed, EIP-55 checksummed address.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature is malformed or no valid key can be recovered.
+    pub fn recover_ethereum_address(&self, message: &str, signature: &str) -> Result<String, ECDSAError> {
+        let digest = keccak256(message.as_bytes());
+        let signature_bytes = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        recover_address(&digest, &signature_bytes)
+    }
+
+    /// Verifies an EIP-191 `personal_sign` signature, as produced by wallets such as MetaMask.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed, before the Ethereum prefix is applied.
+    /// * `signature` - The 65-byte signature (r || s || v), provided as a hex string.
+    ///   `v` may be 0/1 or 27/28. A 64-byte EIP-2098 compact signature is also accepted.
+    /// * `expected_address` - The 0x-prefixed address of the claimed signer.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature was produced by `expected_address`.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or the address is malformed.
+    pub fn verify_personal_sign(
+        &self,
+        message: &str,
+        signature: &str,
+        expected_address: &str,
+    ) -> Result<bool, ECDSAError> {
+        let expected = parse_address(expected_address)?;
+        let signature_bytes = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let (sig, recovery_id) = split_signature(&signature_bytes)?;
+
+        let digest = personal_message_hash(message.as_bytes());
+        match recover_from_digest(&digest, &sig, recovery_id) {
+            Ok(pubkey) => Ok(constant_time_eq(&address_bytes(&pubkey), &expected)),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::sign_digest;
+    use crate::ECDSASignature;
+    use k256::ecdsa::SigningKey;
+
+    const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+    fn sign_keccak(message: &str, v_offset: u8) -> String {
+        let key = SigningKey::from_bytes(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+        let (sig, recovery_id) = sign_digest(&key, &keccak256(message.as_bytes()), &[]).unwrap();
+        let mut bytes = sig.as_ref().to_vec();
+        bytes.push(recovery_id + v_offset);
+        hex::encode(bytes)
+    }
+
+    #[test]
+    fn test_checksum_address_vectors() {
+        for expected in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&hex::decode(expected[2..].to_lowercase()).unwrap());
+            assert_eq!(to_checksum_address(&address), expected);
+        }
+    }
+
+    #[test]
+    fn test_address_from_pubkey() {
+        let key = SigningKey::from_bytes(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+        let compressed = hex::encode(key.verifying_key().to_bytes());
+        let uncompressed = hex::encode(key.verifying_key().to_encoded_point(false).as_bytes());
+
+        assert_eq!(ethereum_address_from_pubkey(&compressed).unwrap(), ADDRESS);
+        assert_eq!(ethereum_address_from_pubkey(&uncompressed).unwrap(), ADDRESS);
+        assert!(ethereum_address_from_pubkey(&compressed[2..]).is_err());
+    }
+
+    #[test]
+    fn test_validate_eip55() {
+        assert!(validate_eip55(ADDRESS));
+        assert!(validate_eip55("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+        // One letter with the wrong case
+        assert!(!validate_eip55("0xfb6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+        assert!(!validate_eip55(&ADDRESS.to_lowercase()));
+        assert!(!validate_eip55("0x1234"));
+        assert!(!validate_eip55(&ADDRESS[2..]));
+    }
+
+    #[test]
+    fn test_recover_ethereum_address() {
+        let verifier = ECDSAVerifier::default();
+        for v_offset in [0, 27] {
+            let signature = sign_keccak("Some data", v_offset);
+            let address = verifier.recover_ethereum_address("Some data", &signature).unwrap();
+            assert_eq!(address, ADDRESS);
+        }
+
+        let compact = ECDSASignature::from_hex(&sign_keccak("Some data", 27)).unwrap().eip2098().unwrap();
+        assert_eq!(verifier.recover_ethereum_address("Some data", &compact).unwrap(), ADDRESS);
+    }
+
+    #[test]
+    fn test_verify_personal_sign() {
+        // Vector from the web3.js `eth.accounts.sign` documentation
+        let signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+        assert_eq!(
+            hex::encode(personal_message_hash(b"Some data")),
+            "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655"
+        );
+
+        let verifier = ECDSAVerifier::default();
+        assert!(verifier.verify_personal_sign("Some data", signature, ADDRESS).unwrap());
+        assert!(verifier.verify_personal_sign("Some data", signature, &ADDRESS.to_lowercase()).unwrap());
+        assert!(!verifier.verify_personal_sign("Other data", signature, ADDRESS).unwrap());
+        assert!(verifier.verify_personal_sign("Some data", signature, "0x1234").is_err());
+    }
+
+    #[test]
+    fn test_recover_ethereum_address_invalid() {
+        let verifier = ECDSAVerifier::default();
+        let mut signature = sign_keccak("Some data", 27);
+        signature.replace_range(128.., "1d");
+        assert!(verifier.recover_ethereum_address("Some data", &signature).is_err());
+        assert!(verifier.recover_ethereum_address("Some data", &signature[..126]).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Ethereum transaction signature verification: rebuilding the signing hash of a raw
+//! signed transaction and recovering its sender.
+//!
+//! Legacy transactions are a bare RLP list. Typed transactions (EIP-2718) are a type byte
+//! followed by an RLP list whose last three fields are `y_parity, r, s`; they sign
+//! `keccak256(type || rlp(fields without the signature))`.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::Signature;
+
+use crate::ethereum::{address_bytes, to_checksum_address};
+use crate::ethereum::keccak256;
+use crate::rlp::{self, Rlp};
+use crate::signature::parse_compact;
+use crate::{recover_from_digest, ECDSAError};
+
+/// Number of fields in a legacy transaction before the signature.
+const LEGACY_FIELDS: usize = 6;
+
+/// EIP-2930 access list transactions.
+const ACCESS_LIST_TX_TYPE: u8 = 0x01;
+/// EIP-1559 dynamic fee transactions.
+const DYNAMIC_FEE_TX_TYPE: u8 = 0x02;
+
+/// Builds a signature from the RLP `r` and `s` scalars.
+fn signature_from_rlp(r: &Rlp, s: &Rlp) -> Result<Signature, ECDSAError> {
+    let mut compact = [0u8; 64];
+    for (scalar, out) in [r, s].into_iter().zip(compact.chunks_exact_mut(32)) {
+        let bytes = scalar.as_uint()?;
+        if bytes.len() > 32 {
+            return Err(ECDSAError::InvalidSignatureFormat);
+        }
+        out[32 - bytes.len()..].copy_from_slice(bytes);
+    }
+    parse_compact(&compact)
+}
+
+/// Computes the signing hash and recovery id of a legacy transaction, following
+/// EIP-155 when `v` encodes a chain id (`v = chain_id * 2 + 35 + y_parity`).
+fn legacy_signing_hash(fields: &[Rlp]) -> Result<([u8; 32], u8), ECDSAError> {
+    if fields.len() != LEGACY_FIELDS + 3 {
+        return Err(ECDSAError::InvalidTransaction);
+    }
+    let mut unsigned: Vec<Vec<u8>> = fields[..LEGACY_FIELDS].iter().map(Rlp::encode).collect();
+    let v = fields[LEGACY_FIELDS].as_u64()?;
+    let recovery_id = match v {
+        27 | 28 => (v - 27) as u8,
+        35.. => {
+            let chain_id = (v - 35) / 2;
+            unsigned.extend([rlp::encode_u64(chain_id), rlp::encode_bytes(&[]), rlp::encode_bytes(&[])]);
+            ((v - 35) % 2) as u8
+        }
+        _ => return Err(ECDSAError::InvalidSignatureRecovery),
+    };
+    Ok((keccak256(&rlp::encode_list(&unsigned)), recovery_id))
+}
+
+/// Checks an EIP-2930 access list: a list of `[address, [storage_key, ...]]` entries.
+fn check_access_list(access_list: &Rlp) -> Result<(), ECDSAError> {
+    for entry in access_list.as_list()? {
+        match entry.as_list()? {
+            [address, storage_keys] if address.as_bytes()?.len() == 20 => {
+                for key in storage_keys.as_list()? {
+                    if key.as_bytes()?.len() != 32 {
+                        return Err(ECDSAError::InvalidTransaction);
+                    }
+                }
+            }
+            _ => return Err(ECDSAError::InvalidTransaction),
+        }
+    }
+    Ok(())
+}
+
+/// Computes the signing hash and recovery id of a typed transaction with `field_count`
+/// fields before the signature, the last of which is its access list.
+fn typed_signing_hash(tx_type: u8, fields: &[Rlp], field_count: usize) -> Result<([u8; 32], u8), ECDSAError> {
+    if fields.len() != field_count + 3 {
+        return Err(ECDSAError::InvalidTransaction);
+    }
+    check_access_list(&fields[field_count - 1]).map_err(|_| ECDSAError::InvalidTransaction)?;
+    let recovery_id = match fields[field_count].as_u64()? {
+        y_parity @ (0 | 1) => y_parity as u8,
+        _ => return Err(ECDSAError::InvalidSignatureRecovery),
+    };
+
+    let unsigned: Vec<Vec<u8>> = fields[..field_count].iter().map(Rlp::encode).collect();
+    let mut payload = vec![tx_type];
+    payload.extend(rlp::encode_list(&unsigned));
+    Ok((keccak256(&payload), recovery_id))
+}
+
+/// Recovers the sender of a raw signed transaction, legacy or typed.
+pub(crate) fn recover_sender(raw_tx: &[u8]) -> Result<[u8; 20], ECDSAError> {
+    let tx_type = *raw_tx.first().ok_or(ECDSAError::InvalidTransaction)?;
+    let (body, typed_fields) = match tx_type {
+        0xc0.. => (raw_tx, None),
+        // chain_id, nonce, gas_price, gas_limit, to, value, data, access_list
+        ACCESS_LIST_TX_TYPE => (&raw_tx[1..], Some(8)),
+        // chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to, value, data, access_list
+        DYNAMIC_FEE_TX_TYPE => (&raw_tx[1..], Some(9)),
+        _ => return Err(ECDSAError::InvalidTransaction),
+    };
+    let fields = rlp::decode(body)?;
+    let fields = fields.as_list().map_err(|_| ECDSAError::InvalidTransaction)?;
+
+    let (digest, recovery_id) = match typed_fields {
+        Some(field_count) => typed_signing_hash(tx_type, fields, field_count)?,
+        None => legacy_signing_hash(fields)?,
+    };
+    let sig = signature_from_rlp(&fields[fields.len() - 2], &fields[fields.len() - 1])?;
+    Ok(address_bytes(&recover_from_digest(&digest, &sig, recovery_id)?))
+}
+
+/// Recovers the sender of a signed Ethereum transaction, as `eth_sendRawTransaction` would.
+///
+/// # Arguments:
+/// * `raw_tx_hex` - The signed transaction as broadcast, provided as a hex string
+///   (optionally 0x-prefixed). Legacy transactions, with or without EIP-155 replay
+///   protection, and EIP-2930 (type 1) and EIP-1559 (type 2) envelopes are accepted.
+///
+/// # Returns:
+/// * The sender's 0x-prefixed, EIP-55 checksummed address.
+///
+/// # Errors:
+/// * Returns an error if the encoding is malformed, `v` is invalid, or no key can be
+///   recovered from the signature (including high-S signatures, which EIP-2 forbids).
+#[wasm_bindgen]
+pub fn verify_ethereum_tx(raw_tx_hex: &str) -> Result<String, ECDSAError> {
+    let raw_tx = hex::decode(raw_tx_hex.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidTransaction)?;
+    Ok(to_checksum_address(&recover_sender(&raw_tx)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+
+    #[test]
+    fn test_eip155_example() {
+        // The worked example from EIP-155, signed with the private key 0x4646...46 on chain 1
+        let raw = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+        assert_eq!(verify_ethereum_tx(raw).unwrap(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+        let bytes = hex::decode(&raw[2..]).unwrap();
+        let fields = rlp::decode(&bytes).unwrap();
+        let (digest, _) = legacy_signing_hash(fields.as_list().unwrap()).unwrap();
+        assert_eq!(hex::encode(digest), "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+    }
+
+    #[test]
+    fn test_pre_eip155_transaction() {
+        let key = SigningKey::from_bytes(&[0x46; 32]).unwrap();
+        let mut fields = vec![rlp::encode_u64(9), rlp::encode_u64(20_000_000_000), rlp::encode_u64(21000)];
+        fields.extend([rlp::encode_bytes(&[0x35; 20]), rlp::encode_u64(1), rlp::encode_bytes(&[])]);
+        let (sig, recovery_id) = sign_digest(&key, &keccak256(&rlp::encode_list(&fields)), &[]).unwrap();
+
+        let (r, s) = sig.as_ref().split_at(32);
+        fields.extend([rlp::encode_u64(27 + recovery_id as u64), rlp::encode_bytes(r), rlp::encode_bytes(s)]);
+        let raw = hex::encode(rlp::encode_list(&fields));
+        assert_eq!(verify_ethereum_tx(&raw).unwrap(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    }
+
+    /// Signs a typed transaction with the 0x4646...46 key, returning the raw envelope.
+    fn sign_typed(tx_type: u8, mut fields: Vec<Vec<u8>>) -> String {
+        let key = SigningKey::from_bytes(&[0x46; 32]).unwrap();
+        let mut payload = vec![tx_type];
+        payload.extend(rlp::encode_list(&fields));
+        let (sig, recovery_id) = sign_digest(&key, &keccak256(&payload), &[]).unwrap();
+
+        let (r, s) = sig.as_ref().split_at(32);
+        fields.extend([rlp::encode_u64(recovery_id as u64), rlp::encode_bytes(r), rlp::encode_bytes(s)]);
+        let mut raw = vec![tx_type];
+        raw.extend(rlp::encode_list(&fields));
+        hex::encode(raw)
+    }
+
+    fn access_list() -> Vec<u8> {
+        let entry = rlp::encode_list(&[rlp::encode_bytes(&[0xde; 20]), rlp::encode_list(&[rlp::encode_bytes(&[0; 32])])]);
+        rlp::encode_list(&[entry])
+    }
+
+    #[test]
+    fn test_typed_transactions() {
+        let common = [rlp::encode_u64(21000), rlp::encode_bytes(&[0x35; 20]), rlp::encode_u64(1), rlp::encode_bytes(&[])];
+
+        // EIP-2930: chain_id, nonce, gas_price, then the common fields and the access list
+        let mut fields = vec![rlp::encode_u64(1), rlp::encode_u64(0), rlp::encode_u64(20_000_000_000)];
+        fields.extend(common.clone());
+        fields.push(access_list());
+        let raw = sign_typed(ACCESS_LIST_TX_TYPE, fields.clone());
+        assert_eq!(verify_ethereum_tx(&raw).unwrap(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        // The type byte is part of the signed payload
+        assert_ne!(verify_ethereum_tx(&format!("02{}", &raw[2..])).ok().as_deref(), Some("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"));
+
+        // EIP-1559: chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, ...
+        let mut fields = vec![rlp::encode_u64(1), rlp::encode_u64(0), rlp::encode_u64(1_000_000_000), rlp::encode_u64(30_000_000_000)];
+        fields.extend(common);
+        fields.push(rlp::encode_list(&[]));
+        let raw = sign_typed(DYNAMIC_FEE_TX_TYPE, fields);
+        assert_eq!(verify_ethereum_tx(&format!("0x{raw}")).unwrap(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    }
+
+    #[test]
+    fn test_rejects_malformed_typed_transaction() {
+        let mut fields = vec![rlp::encode_u64(1), rlp::encode_u64(0), rlp::encode_u64(1), rlp::encode_u64(21000)];
+        fields.extend([rlp::encode_bytes(&[0x35; 20]), rlp::encode_u64(1), rlp::encode_bytes(&[])]);
+        // Access list entry with a short address
+        fields.push(rlp::encode_list(&[rlp::encode_list(&[rlp::encode_bytes(&[0xde; 19]), rlp::encode_list(&[])])]));
+        assert!(matches!(verify_ethereum_tx(&sign_typed(ACCESS_LIST_TX_TYPE, fields)), Err(ECDSAError::InvalidTransaction)));
+        // Unknown envelope type
+        assert!(matches!(verify_ethereum_tx("03c0"), Err(ECDSAError::InvalidTransaction)));
+        assert!(matches!(verify_ethereum_tx(""), Err(ECDSAError::InvalidTransaction)));
+    }
+
+    #[test]
+    fn test_rejects_malformed_transaction() {
+        assert!(matches!(verify_ethereum_tx("0xzz"), Err(ECDSAError::InvalidTransaction)));
+        // A list with too few fields
+        assert!(matches!(verify_ethereum_tx("c3010203"), Err(ECDSAError::InvalidTransaction)));
+        // A bare string instead of a list
+        assert!(matches!(verify_ethereum_tx("83646f67"), Err(ECDSAError::InvalidTransaction)));
+    }
+
+    #[test]
+    fn test_rejects_deeply_nested_transaction() {
+        // 20,000 nested empty lists, built from the inside out as prefixes: decoding them
+        // recursively would overflow the stack
+        let mut prefixes = Vec::new();
+        let mut length = 1;
+        for _ in 0..20_000 {
+            let prefix = if length < 56 {
+                vec![0xc0 + length as u8]
+            } else {
+                let significant: Vec<u8> = (length as u32).to_be_bytes().into_iter().skip_while(|&b| b == 0).collect();
+                [vec![0xf7 + significant.len() as u8], significant].concat()
+            };
+            length += prefix.len();
+            prefixes.push(prefix);
+        }
+        let raw: Vec<u8> = prefixes.into_iter().rev().flatten().chain([0xc0]).collect();
+        assert!(matches!(verify_ethereum_tx(&hex::encode(raw)), Err(ECDSAError::InvalidRlp)));
+    }
+}
@@ -0,0 +1,357 @@
+//! RFC 9591 FROST(secp256k1, SHA-256) threshold signatures: verification of each signer's
+//! round-two share, aggregation of the shares, and verification of the final signature.
+//!
+//! Scalars and identifiers are 32 bytes big-endian and group elements are 33-byte compressed
+//! points, so signatures are 65 bytes (R || z). They are not BIP-340 signatures.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::bigint::U512;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::{CompressedPoint, FieldBytes, ProjectivePoint, Scalar};
+use sha2::{Digest, Sha256};
+
+use crate::hash::expand_message_xmd;
+use crate::schnorr::decode_point;
+use crate::ECDSAError;
+
+const CONTEXT_STRING: &[u8] = b"FROST-secp256k1-SHA256-v1";
+const SCALAR_LENGTH: usize = 32;
+const ELEMENT_LENGTH: usize = 33;
+/// An encoded commitment: identifier, hiding nonce commitment and binding nonce commitment.
+const COMMITMENT_LENGTH: usize = SCALAR_LENGTH + 2 * ELEMENT_LENGTH;
+
+/// The ciphersuite's hash-to-scalar functions `H1`, `H2` and `H3`: 48 bytes of
+/// `expand_message_xmd` reduced modulo the group order.
+fn hash_to_scalar(tag: &str, message: &[&[u8]]) -> Scalar {
+    let dst = [CONTEXT_STRING, tag.as_bytes()].concat();
+    let mut wide = [0u8; 64];
+    wide[16..].copy_from_slice(&expand_message_xmd(message, &dst, 48));
+    <Scalar as Reduce<U512>>::from_uint_reduced(U512::from_be_slice(&wide))
+}
+
+/// The ciphersuite's hash functions `H4` and `H5`.
+fn hash(tag: &str, message: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update(CONTEXT_STRING).chain_update(tag).chain_update(message).finalize().into()
+}
+
+fn parse_scalar(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; SCALAR_LENGTH] = bytes.try_into().ok()?;
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes)))
+}
+
+fn serialize_element(point: &ProjectivePoint) -> CompressedPoint {
+    point.to_affine().to_bytes()
+}
+
+fn parse_hex_element(element: &str) -> Result<ProjectivePoint, ECDSAError> {
+    hex::decode(element).ok().and_then(|bytes| decode_point(&bytes)).ok_or(ECDSAError::InvalidPublicKey)
+}
+
+/// Parses a nonzero participant identifier, provided as a hex scalar.
+fn parse_identifier(identifier: &str) -> Result<Scalar, ECDSAError> {
+    hex::decode(identifier)
+        .ok()
+        .and_then(|bytes| parse_scalar(&bytes))
+        .filter(|identifier| !bool::from(identifier.is_zero()))
+        .ok_or(ECDSAError::InvalidFrostCommitment)
+}
+
+/// The challenge `H2(R || PK || msg)`.
+fn challenge(group_commitment: &ProjectivePoint, group_public_key: &ProjectivePoint, message: &[u8]) -> Scalar {
+    hash_to_scalar("chal", &[&serialize_element(group_commitment), &serialize_element(group_public_key), message])
+}
+
+/// One signer's round-one commitment.
+struct Commitment {
+    identifier: Scalar,
+    hiding: ProjectivePoint,
+    binding: ProjectivePoint,
+    /// The binding factor `rho` for this signer, which depends on the whole signing package.
+    binding_factor: Scalar,
+}
+
+/// The commitments and message of a signing operation, from which the coordinator checks
+/// and combines the signers' round-two shares.
+#[wasm_bindgen]
+pub struct FrostSigningPackage {
+    group_public_key: ProjectivePoint,
+    commitments: Vec<Commitment>,
+    message: Vec<u8>,
+    group_commitment: ProjectivePoint,
+    challenge: Scalar,
+}
+
+impl FrostSigningPackage {
+    /// The Lagrange coefficient of a signer over the participating identifiers.
+    fn interpolating_value(&self, identifier: &Scalar) -> Scalar {
+        let (numerator, denominator) = self
+            .commitments
+            .iter()
+            .filter(|commitment| commitment.identifier != *identifier)
+            .fold((Scalar::ONE, Scalar::ONE), |(numerator, denominator), commitment| {
+                (numerator * commitment.identifier, denominator * (commitment.identifier - identifier))
+            });
+        // The identifiers are distinct, so the denominator is invertible
+        numerator * denominator.invert().unwrap()
+    }
+}
+
+#[wasm_bindgen]
+impl FrostSigningPackage {
+    /// Assembles a signing package from the signers' commitments.
+    ///
+    /// # Arguments:
+    /// * `group_public_key` - The 33-byte compressed group public key, provided as a hex string.
+    /// * `commitments` - One 98-byte commitment per participating signer, each its 32-byte
+    ///   identifier followed by its hiding and binding nonce commitments, provided as hex
+    ///   strings in any order.
+    /// * `message` - The message being signed, provided as a hex string.
+    ///
+    /// # Errors:
+    /// * `InvalidPublicKey` if the group public key is malformed.
+    /// * `InvalidFrostCommitment` if there are no commitments, one is malformed, or two share
+    ///   an identifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new(group_public_key: &str, commitments: Vec<String>, message: &str) -> Result<FrostSigningPackage, ECDSAError> {
+        let group_public_key = parse_hex_element(group_public_key)?;
+        let message = hex::decode(message).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        let mut encoded = commitments
+            .iter()
+            .map(|commitment| hex::decode(commitment).ok().filter(|bytes| bytes.len() == COMMITMENT_LENGTH))
+            .collect::<Option<Vec<_>>>()
+            .filter(|encoded| !encoded.is_empty())
+            .ok_or(ECDSAError::InvalidFrostCommitment)?;
+        // The commitment list is ordered by identifier, and identifiers sort as their encodings
+        encoded.sort();
+        if encoded.windows(2).any(|pair| pair[0][..SCALAR_LENGTH] == pair[1][..SCALAR_LENGTH]) {
+            return Err(ECDSAError::InvalidFrostCommitment);
+        }
+
+        let rho_prefix = [
+            serialize_element(&group_public_key).as_slice(),
+            &hash("msg", &message),
+            &hash("com", &encoded.concat()),
+        ]
+        .concat();
+        let commitments = encoded
+            .iter()
+            .map(|bytes| {
+                let (identifier, points) = bytes.split_at(SCALAR_LENGTH);
+                Some(Commitment {
+                    identifier: parse_scalar(identifier).filter(|identifier| !bool::from(identifier.is_zero()))?,
+                    hiding: decode_point(&points[..ELEMENT_LENGTH])?,
+                    binding: decode_point(&points[ELEMENT_LENGTH..])?,
+                    binding_factor: hash_to_scalar("rho", &[&rho_prefix, identifier]),
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ECDSAError::InvalidFrostCommitment)?;
+
+        let group_commitment = commitments
+            .iter()
+            .fold(ProjectivePoint::IDENTITY, |sum, commitment| sum + commitment.hiding + commitment.binding * commitment.binding_factor);
+        let challenge = challenge(&group_commitment, &group_public_key, &message);
+        Ok(FrostSigningPackage { group_public_key, commitments, message, group_commitment, challenge })
+    }
+
+    /// The group commitment `R`, the first half of the final signature, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn group_commitment(&self) -> String {
+        hex::encode(serialize_element(&self.group_commitment))
+    }
+
+    /// Verifies one signer's round-two signature share.
+    ///
+    /// # Arguments:
+    /// * `identifier` - The signer's 32-byte identifier, provided as a hex string.
+    /// * `signature_share` - The signer's 32-byte share, provided as a hex string.
+    /// * `verifying_share` - The signer's 33-byte compressed public verifying share from key
+    ///   generation, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the share is valid.
+    ///
+    /// # Errors:
+    /// * `InvalidFrostCommitment` if the signer has no commitment in the package.
+    /// * `InvalidFrostShare` if the share is not a scalar.
+    /// * `InvalidPublicKey` if the verifying share is malformed.
+    pub fn verify_signature_share(&self, identifier: &str, signature_share: &str, verifying_share: &str) -> Result<bool, ECDSAError> {
+        let identifier = parse_identifier(identifier)?;
+        let commitment = self
+            .commitments
+            .iter()
+            .find(|commitment| commitment.identifier == identifier)
+            .ok_or(ECDSAError::InvalidFrostCommitment)?;
+        let share = hex::decode(signature_share).ok().and_then(|bytes| parse_scalar(&bytes)).ok_or(ECDSAError::InvalidFrostShare)?;
+        let verifying_share = parse_hex_element(verifying_share)?;
+
+        let commitment_share = commitment.hiding + commitment.binding * commitment.binding_factor;
+        let lambda = self.interpolating_value(&identifier);
+        Ok(ProjectivePoint::GENERATOR * share == commitment_share + verifying_share * (self.challenge * lambda))
+    }
+
+    /// Sums the signature shares into the final signature, which should then be checked with
+    /// `verify_frost_signature`, or the shares checked individually to find a faulty signer.
+    ///
+    /// # Arguments:
+    /// * `signature_shares` - One 32-byte share per signer in the package, provided as hex strings.
+    ///
+    /// # Returns:
+    /// * The 65-byte signature (R || z), as a hex string.
+    ///
+    /// # Errors:
+    /// * `InvalidFrostShare` if a share is malformed or their number differs from the signers'.
+    pub fn aggregate(&self, signature_shares: Vec<String>) -> Result<String, ECDSAError> {
+        if signature_shares.len() != self.commitments.len() {
+            return Err(ECDSAError::InvalidFrostShare);
+        }
+        let z = signature_shares.iter().try_fold(Scalar::ZERO, |sum, share| {
+            let share = hex::decode(share).ok().and_then(|bytes| parse_scalar(&bytes)).ok_or(ECDSAError::InvalidFrostShare)?;
+            Ok::<_, ECDSAError>(sum + share)
+        })?;
+        Ok(hex::encode([serialize_element(&self.group_commitment).as_slice(), &z.to_bytes()].concat()))
+    }
+
+    /// Verifies the final signature over this package's message and group key.
+    pub fn verify(&self, signature: &str) -> Result<bool, ECDSAError> {
+        verify_frost_signature(&hex::encode(serialize_element(&self.group_public_key)), &hex::encode(&self.message), signature)
+    }
+}
+
+/// Verifies a FROST(secp256k1, SHA-256) signature.
+///
+/// # Arguments:
+/// * `group_public_key` - The 33-byte compressed group public key, provided as a hex string.
+/// * `message` - The signed message, provided as a hex string.
+/// * `signature` - The 65-byte signature (R || z), provided as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the signature is valid.
+///
+/// # Errors:
+/// * Returns an error if the key or signature is malformed.
+#[wasm_bindgen]
+pub fn verify_frost_signature(group_public_key: &str, message: &str, signature: &str) -> Result<bool, ECDSAError> {
+    let group_public_key = parse_hex_element(group_public_key)?;
+    let message = hex::decode(message).map_err(|_| ECDSAError::InvalidDigestLength)?;
+    let signature = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    if signature.len() != ELEMENT_LENGTH + SCALAR_LENGTH {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    let (r, z) = signature.split_at(ELEMENT_LENGTH);
+    let r = decode_point(r).ok_or(ECDSAError::InvalidSignatureFormat)?;
+    let z = parse_scalar(z).ok_or(ECDSAError::InvalidSignatureFormat)?;
+    let c = challenge(&r, &group_public_key, &message);
+    Ok(ProjectivePoint::GENERATOR * z == r + group_public_key * c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(seed: &str) -> Scalar {
+        hash_to_scalar("test", &[seed.as_bytes()])
+    }
+
+    fn element_hex(point: ProjectivePoint) -> String {
+        hex::encode(serialize_element(&point))
+    }
+
+    /// A 2-of-3 dealer split of a group key, and the round-one nonces of each signer.
+    struct Signer {
+        identifier: Scalar,
+        secret_share: Scalar,
+        nonces: (Scalar, Scalar),
+    }
+
+    fn dealer() -> (Scalar, Vec<Signer>) {
+        let (secret, coefficient) = (scalar("secret"), scalar("coefficient"));
+        let signers = (1u64..=3)
+            .map(|i| {
+                let identifier = Scalar::from(i);
+                Signer {
+                    identifier,
+                    secret_share: secret + coefficient * identifier,
+                    nonces: (scalar(&format!("{i}/hiding")), scalar(&format!("{i}/binding"))),
+                }
+            })
+            .collect();
+        (secret, signers)
+    }
+
+    impl Signer {
+        fn identifier(&self) -> String {
+            hex::encode(self.identifier.to_bytes())
+        }
+
+        fn verifying_share(&self) -> String {
+            element_hex(ProjectivePoint::GENERATOR * self.secret_share)
+        }
+
+        fn commitment(&self) -> String {
+            let (hiding, binding) = (ProjectivePoint::GENERATOR * self.nonces.0, ProjectivePoint::GENERATOR * self.nonces.1);
+            self.identifier() + &element_hex(hiding) + &element_hex(binding)
+        }
+
+        /// Round two: `z = d + e * rho + lambda * s * c`.
+        fn sign(&self, package: &FrostSigningPackage) -> String {
+            let commitment = package.commitments.iter().find(|c| c.identifier == self.identifier).unwrap();
+            let lambda = package.interpolating_value(&self.identifier);
+            let share = self.nonces.0 + self.nonces.1 * commitment.binding_factor + lambda * self.secret_share * package.challenge;
+            hex::encode(share.to_bytes())
+        }
+    }
+
+    #[test]
+    fn test_threshold_signing() {
+        let (secret, signers) = dealer();
+        let group_public_key = element_hex(ProjectivePoint::GENERATOR * secret);
+        let message = hex::encode(b"FROST test message");
+        // Any two of the three signers can sign, with commitments given in any order
+        for pair in [[2, 0], [1, 2], [0, 1]] {
+            let participants: Vec<&Signer> = pair.iter().map(|&i| &signers[i]).collect();
+            let package = FrostSigningPackage::new(&group_public_key, participants.iter().map(|s| s.commitment()).collect(), &message).unwrap();
+            let shares: Vec<String> = participants.iter().map(|signer| signer.sign(&package)).collect();
+            for (signer, share) in participants.iter().zip(&shares) {
+                assert!(package.verify_signature_share(&signer.identifier(), share, &signer.verifying_share()).unwrap());
+            }
+            let signature = package.aggregate(shares).unwrap();
+            assert!(signature.starts_with(&package.group_commitment()));
+            assert!(package.verify(&signature).unwrap());
+            assert!(verify_frost_signature(&group_public_key, &message, &signature).unwrap());
+            assert!(!verify_frost_signature(&group_public_key, "00", &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_rejects_faulty_shares() {
+        let (secret, signers) = dealer();
+        let group_public_key = element_hex(ProjectivePoint::GENERATOR * secret);
+        let package = FrostSigningPackage::new(&group_public_key, vec![signers[0].commitment(), signers[1].commitment()], "").unwrap();
+        let shares = [signers[0].sign(&package), signers[1].sign(&package)];
+
+        // A share checked against another signer's identifier or verifying share
+        assert!(!package.verify_signature_share(&signers[1].identifier(), &shares[0], &signers[0].verifying_share()).unwrap());
+        assert!(!package.verify_signature_share(&signers[0].identifier(), &shares[0], &signers[1].verifying_share()).unwrap());
+        // A faulty share spoils the aggregate
+        let signature = package.aggregate(vec![shares[0].clone(), shares[0].clone()]).unwrap();
+        assert!(!package.verify(&signature).unwrap());
+
+        assert!(matches!(package.verify_signature_share(&signers[2].identifier(), &shares[0], &signers[2].verifying_share()), Err(ECDSAError::InvalidFrostCommitment)));
+        assert!(matches!(package.aggregate(vec![shares[0].clone()]), Err(ECDSAError::InvalidFrostShare)));
+        assert!(matches!(package.verify_signature_share(&signers[0].identifier(), "ff", &signers[0].verifying_share()), Err(ECDSAError::InvalidFrostShare)));
+    }
+
+    #[test]
+    fn test_rejects_malformed_commitments() {
+        let (secret, signers) = dealer();
+        let group_public_key = element_hex(ProjectivePoint::GENERATOR * secret);
+        let commitment = signers[0].commitment();
+        let zero_identifier = "00".repeat(SCALAR_LENGTH) + &commitment[2 * SCALAR_LENGTH..];
+        for commitments in [vec![], vec![commitment.clone(), commitment.clone()], vec![commitment[2..].to_string()], vec![zero_identifier]] {
+            assert!(matches!(FrostSigningPackage::new(&group_public_key, commitments, ""), Err(ECDSAError::InvalidFrostCommitment)));
+        }
+    }
+}
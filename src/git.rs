@@ -0,0 +1,250 @@
+//! SSH signatures on git commits and tags (`gpg.format = ssh`), as `git verify-commit` and
+//! `git verify-tag` check them.
+//!
+//! A commit carries its signature in a `gpgsig` (or `gpgsig-sha256`) header whose
+//! continuation lines start with a space, and the signed payload is the commit without that
+//! header. A tag carries it after the message, and the payload is everything before it.
+//! Either way the signature is an `SSHSIG` in the `git` namespace.
+
+use wasm_bindgen::prelude::*;
+
+use crate::pem::decode_pem;
+use crate::sshsig::{parse_allowed_signers, parse_sshsig, SshPublicKey};
+use crate::time::now_millis;
+use crate::ECDSAError;
+
+const NAMESPACE: &str = "git";
+const SIGNATURE_HEADERS: [&str; 2] = ["gpgsig ", "gpgsig-sha256 "];
+const BEGIN_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----";
+
+/// A signed git object split into the signed payload and its signature.
+pub(crate) struct SignedObject<'a> {
+    pub(crate) kind: &'static str,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) signature: String,
+    /// The `committer` or `tagger` header value, e.g. `Alice <alice@example.com> 1767225600 +0000`
+    pub(crate) signer: Option<&'a str>,
+}
+
+/// Splits a raw commit or tag, as printed by `git cat-file`, into payload and signature.
+pub(crate) fn split_signed_object(object: &[u8]) -> Result<SignedObject<'_>, ECDSAError> {
+    let text = std::str::from_utf8(object).map_err(|_| ECDSAError::InvalidGitObject)?;
+    let header_end = text.find("\n\n").map_or(text.len(), |end| end + 1);
+    let header = |name: &str| {
+        text[..header_end].lines().find_map(|line| line.strip_prefix(name).and_then(|value| value.strip_prefix(' ')))
+    };
+
+    if text.starts_with("tree ") {
+        let mut payload = String::new();
+        let mut signatures = Vec::new();
+        let mut lines = text[..header_end].split_inclusive('\n').peekable();
+        while let Some(line) = lines.next() {
+            let Some(value) = SIGNATURE_HEADERS.iter().find_map(|name| line.strip_prefix(name)) else {
+                payload.push_str(line);
+                continue;
+            };
+            let mut signature = value.to_string();
+            while let Some(continuation) = lines.next_if(|line| line.starts_with(' ')) {
+                signature.push_str(&continuation[1..]);
+            }
+            signatures.push(signature);
+        }
+        payload.push_str(&text[header_end..]);
+        // Objects signed for both hash algorithms are not supported
+        let [signature] = <[String; 1]>::try_from(signatures).map_err(|_| ECDSAError::InvalidGitObject)?;
+        return Ok(SignedObject { kind: "commit", payload: payload.into_bytes(), signature, signer: header("committer") });
+    }
+
+    if text.starts_with("object ") {
+        let start = text[header_end..]
+            .match_indices(BEGIN_SIGNATURE)
+            .map(|(index, _)| header_end + index)
+            .filter(|&index| text[..index].ends_with('\n'))
+            .last()
+            .ok_or(ECDSAError::InvalidGitObject)?;
+        return Ok(SignedObject {
+            kind: "tag",
+            payload: object[..start].to_vec(),
+            signature: text[start..].to_string(),
+            signer: header("tagger"),
+        });
+    }
+    Err(ECDSAError::InvalidGitObject)
+}
+
+/// Reads the Unix timestamp from an identity such as `Alice <alice@example.com> 1767225600 +0000`.
+fn identity_time(identity: &str) -> Option<i64> {
+    let mut fields = identity.rsplit(' ');
+    fields.next()?;
+    fields.next()?.parse().ok()
+}
+
+/// A verified git commit or tag signature.
+#[wasm_bindgen]
+pub struct GitSignature {
+    object_type: &'static str,
+    principals: Option<String>,
+    signer: Option<String>,
+    key: SshPublicKey,
+}
+
+#[wasm_bindgen]
+impl GitSignature {
+    /// `commit` or `tag`.
+    #[wasm_bindgen(getter)]
+    pub fn object_type(&self) -> String {
+        self.object_type.to_string()
+    }
+
+    /// The principals of the allowed signer entry holding the key, e.g. `alice@example.com`;
+    /// the identity `git verify-commit` reports.
+    #[wasm_bindgen(getter)]
+    pub fn principals(&self) -> Option<String> {
+        self.principals.clone()
+    }
+
+    /// The committer or tagger the object names, which the signature does not vouch for on
+    /// its own: compare it with `principals`.
+    #[wasm_bindgen(getter)]
+    pub fn signer(&self) -> Option<String> {
+        self.signer.clone()
+    }
+
+    /// The signing key's `SHA256:` fingerprint.
+    #[wasm_bindgen(getter)]
+    pub fn fingerprint(&self) -> String {
+        self.key.fingerprint()
+    }
+
+    /// The signing key type: `ssh-ed25519` or `ecdsa-sha2-nistp256`.
+    #[wasm_bindgen(getter)]
+    pub fn key_type(&self) -> String {
+        self.key.key_type().to_string()
+    }
+}
+
+/// Verifies the SSH signature of a raw git commit or tag, like `git verify-commit` with
+/// `gpg.ssh.allowedSignersFile`.
+///
+/// # Arguments:
+/// * `object` - The object as `git cat-file commit <rev>` or `git cat-file tag <name>` prints
+///   it, signature included (`Uint8Array` in JS).
+/// * `allowed_signers` - The contents of an `allowed_signers` file. As in git, `valid-after`
+///   and `valid-before` are checked against the committer or tagger timestamp.
+///
+/// # Returns:
+/// * The object type, the principals of the matching allowed signer, and the signing key.
+///
+/// # Errors:
+/// * `InvalidGitObject` if the object is not a commit or tag, or carries no SSH signature.
+/// * `SshNamespaceMismatch` if the signature was not made for the `git` namespace.
+/// * The errors of `verify_sshsig` for malformed signatures, unknown keys or bad signatures.
+#[wasm_bindgen]
+pub fn verify_git_signature(object: &[u8], allowed_signers: &str) -> Result<GitSignature, ECDSAError> {
+    let signed = split_signed_object(object)?;
+    let (_, signature) = decode_pem(&signed.signature, &["SSH SIGNATURE"]).map_err(|_| ECDSAError::InvalidGitObject)?;
+    let signature = parse_sshsig(&signature)?;
+    if signature.namespace != NAMESPACE {
+        return Err(ECDSAError::SshNamespaceMismatch);
+    }
+    let allowed_signers = parse_allowed_signers(allowed_signers)?;
+    let time = signed.signer.and_then(identity_time).unwrap_or_else(|| now_millis().div_euclid(1000));
+    let allowed = signature.verify(&signed.payload, &allowed_signers, time)?;
+    Ok(GitSignature {
+        object_type: signed.kind,
+        principals: allowed.principals.clone(),
+        signer: signed.signer.map(str::to_string),
+        key: signature.key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT: &str = "tree 0d8a474fc67971fb3dd7616e26323d3066442555
+author Alice <alice@example.com> 1767225600 +0000
+committer Alice <alice@example.com> 1767225600 +0000
+gpgsig -----BEGIN SSH SIGNATURE-----
+ U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgC2vhkCJMRKCVkxUwXia9Aha3Oh
+ MiJjWieAMUIrt05u4AAAADZ2l0AAAAAAAAAAZzaGE1MTIAAABTAAAAC3NzaC1lZDI1NTE5
+ AAAAQAC6eZC4eU0y9HWsAFYSZrOXFUzjqc8UiQgAWn0qjwRLvs8qqis/2XbY90y750OL3k
+ rKZJfLdNOG+ckKjXK6iQ8=
+ -----END SSH SIGNATURE-----
+
+Initial commit
+";
+    const TAG: &str = "object a216144fc77c4c70f1a3a6f5b0e8fe798d4c5d63
+type commit
+tag v1.0
+tagger Alice <alice@example.com> 1767225600 +0000
+
+Release 1.0
+-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgC2vhkCJMRKCVkxUwXia9Aha3Oh
+MiJjWieAMUIrt05u4AAAADZ2l0AAAAAAAAAAZzaGE1MTIAAABTAAAAC3NzaC1lZDI1NTE5
+AAAAQIsoe87e9TRRkHaOSG2K87F86MY3DJ9fK0+VCPl1BOTJ3cmmxKNNfljF8VRWdZdA9f
+oi0tZ/H7ZfBNJAiiBJYw4=
+-----END SSH SIGNATURE-----
+";
+    const ALLOWED_SIGNERS: &str = "# Maintainers
+alice@example.com namespaces=\"git\" ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIAtr4ZAiTESglZMVMF4mvQIWtzoTIiY1ongDFCK7dObu
+";
+
+    #[test]
+    fn test_split_signed_objects() {
+        let commit = split_signed_object(COMMIT.as_bytes()).unwrap();
+        assert_eq!(commit.kind, "commit");
+        assert!(commit.signature.starts_with(BEGIN_SIGNATURE) && commit.signature.ends_with("-----END SSH SIGNATURE-----\n"));
+        assert!(!commit.signature.contains("\n "));
+        let payload = String::from_utf8(commit.payload).unwrap();
+        assert!(payload.starts_with("tree ") && payload.ends_with("+0000\n\nInitial commit\n") && !payload.contains("gpgsig"));
+        assert_eq!(commit.signer, Some("Alice <alice@example.com> 1767225600 +0000"));
+        assert_eq!(commit.signer.and_then(identity_time), Some(1767225600));
+
+        let tag = split_signed_object(TAG.as_bytes()).unwrap();
+        assert_eq!(tag.kind, "tag");
+        assert!(tag.payload.ends_with(b"\n\nRelease 1.0\n"));
+        assert_eq!(tag.signer, Some("Alice <alice@example.com> 1767225600 +0000"));
+
+        // Unsigned objects and other object types
+        let unsigned = COMMIT.split("gpgsig").next().unwrap().to_string() + "\nInitial commit\n";
+        for object in [unsigned.as_str(), "object a216\ntype commit\n\nno signature\n", "blob\n"] {
+            assert!(matches!(split_signed_object(object.as_bytes()), Err(ECDSAError::InvalidGitObject)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    fn test_verify_commit_and_tag() {
+        let commit = verify_git_signature(COMMIT.as_bytes(), ALLOWED_SIGNERS).unwrap();
+        assert_eq!(commit.object_type(), "commit");
+        assert_eq!(commit.principals().as_deref(), Some("alice@example.com"));
+        assert_eq!(commit.signer().as_deref(), Some("Alice <alice@example.com> 1767225600 +0000"));
+        assert_eq!(commit.fingerprint(), "SHA256:zLr19mbo2V5SZHPMU7LP4bOtUROS00o1tn03ViPtH0w");
+        assert_eq!(commit.key_type(), "ssh-ed25519");
+        let tag = verify_git_signature(TAG.as_bytes(), ALLOWED_SIGNERS).unwrap();
+        assert_eq!((tag.object_type().as_str(), tag.principals()), ("tag", commit.principals()));
+
+        let tampered = COMMIT.replace("Initial", "Evil");
+        assert!(matches!(verify_git_signature(tampered.as_bytes(), ALLOWED_SIGNERS), Err(ECDSAError::SshSignatureMismatch)));
+        // The key's validity window is checked at the commit time
+        let expired = ALLOWED_SIGNERS.replace("namespaces=\"git\"", "valid-before=20251231");
+        assert!(matches!(verify_git_signature(COMMIT.as_bytes(), &expired), Err(ECDSAError::SshSignerNotAllowed)));
+        assert!(matches!(verify_git_signature(COMMIT.as_bytes(), "# nobody\n"), Err(ECDSAError::SshSignerNotAllowed)));
+    }
+
+    #[test]
+    fn test_rejects_other_namespaces() {
+        // `ssh-keygen -Y sign -n file` over the commit payload
+        let file_signature = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgC2vhkCJMRKCVkxUwXia9Aha3Oh
+MiJjWieAMUIrt05u4AAAAEZmlsZQAAAAAAAAAGc2hhNTEyAAAAUwAAAAtzc2gtZWQyNTUx
+OQAAAEDD8N582fAOHNS6QYNhSCGciiCud5BNod1l3H8X0PCzcw2u5oCPM2dQHtShELY/yi
+1+X6Nqlh2paDx4EStBzWkJ
+-----END SSH SIGNATURE-----
+";
+        let tag = TAG[..TAG.find(BEGIN_SIGNATURE).unwrap()].to_string() + file_signature;
+        assert!(matches!(verify_git_signature(tag.as_bytes(), ALLOWED_SIGNERS), Err(ECDSAError::SshNamespaceMismatch)));
+    }
+}
@@ -0,0 +1,184 @@
+//! Hash algorithm dispatch for the message-hashing step of verification.
+
+use wasm_bindgen::prelude::*;
+use blake2::digest::consts::U32;
+use blake2::Blake2b;
+use sha2::{Digest, Sha256, Sha512};
+use sha3::{Keccak256, Sha3_256};
+
+
+/// BLAKE2b with a 256-bit output.
+pub(crate) type Blake2b256 = Blake2b<U32>;
+
+/// Hash functions that can be applied to a message before ECDSA verification.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// SHA-256 (the default).
+    #[default]
+    Sha256,
+    /// SHA-512; the 64-byte digest is truncated to its leftmost 32 bytes by ECDSA.
+    Sha512,
+    /// FIPS 202 SHA3-256.
+    Sha3_256,
+    /// Keccak-256 as used by Ethereum.
+    Keccak256,
+    /// BLAKE2b with a 256-bit output.
+    Blake2b256,
+}
+
+/// Hashes `data` with the selected algorithm.
+pub(crate) fn digest(algorithm: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+    let mut hasher = Hasher::new(Some(algorithm));
+    hasher.update(data);
+    hasher.finalize()
+}
+
+#[derive(Clone)]
+enum HasherState {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Sha3_256(Sha3_256),
+    Keccak256(Keccak256),
+    Blake2b256(Blake2b256),
+}
+
+/// An incremental hash, for payloads too large to pass to wasm in one piece.
+///
+/// Feed the data in chunks with `update`, then call `finalize` for the digest, which can be
+/// checked with `ECDSAVerifier::verify_prehashed`. `finalize` consumes the hasher (JS code must
+/// not use the object afterwards); `clone` first to keep hashing from the same point.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Hasher {
+    algorithm: HashAlgorithm,
+    state: HasherState,
+}
+
+#[wasm_bindgen]
+impl Hasher {
+    /// Starts a new hash.
+    ///
+    /// # Arguments:
+    /// * `algorithm` - Optional hash function; defaults to SHA-256.
+    #[wasm_bindgen(constructor)]
+    pub fn new(algorithm: Option<HashAlgorithm>) -> Hasher {
+        let algorithm = algorithm.unwrap_or_default();
+        let state = match algorithm {
+            HashAlgorithm::Sha256 => HasherState::Sha256(Sha256::new()),
+            HashAlgorithm::Sha512 => HasherState::Sha512(Sha512::new()),
+            HashAlgorithm::Sha3_256 => HasherState::Sha3_256(Sha3_256::new()),
+            HashAlgorithm::Keccak256 => HasherState::Keccak256(Keccak256::new()),
+            HashAlgorithm::Blake2b256 => HasherState::Blake2b256(Blake2b256::new()),
+        };
+        Hasher { algorithm, state }
+    }
+
+    /// The hash function in use.
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Absorbs the next chunk of data (`Uint8Array` in JS).
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            HasherState::Sha256(hasher) => hasher.update(data),
+            HasherState::Sha512(hasher) => hasher.update(data),
+            HasherState::Sha3_256(hasher) => hasher.update(data),
+            HasherState::Keccak256(hasher) => hasher.update(data),
+            HasherState::Blake2b256(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Returns the digest of everything passed to `update`.
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            HasherState::Sha256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha512(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha3_256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Keccak256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Blake2b256(hasher) => hasher.finalize().to_vec(),
+        }
+    }
+
+    /// Returns an independent copy of the hasher in its current state.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn fork(&self) -> Hasher {
+        self.clone()
+    }
+}
+
+/// RFC 9380 `expand_message_xmd` with SHA-256, for outputs of up to 255 blocks.
+pub(crate) fn expand_message_xmd(message: &[&[u8]], dst: &[u8], length: usize) -> Vec<u8> {
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+    let mut hasher = Sha256::new().chain_update([0u8; 64]);
+    for part in message {
+        hasher.update(part);
+    }
+    let b0 = hasher.chain_update((length as u16).to_be_bytes()).chain_update([0]).chain_update(&dst_prime).finalize();
+
+    let mut output = Vec::with_capacity(length);
+    let mut block = Sha256::new().chain_update(b0).chain_update([1]).chain_update(&dst_prime).finalize();
+    for i in 2..=length.div_ceil(32) + 1 {
+        output.extend_from_slice(&block);
+        let mixed: Vec<u8> = b0.iter().zip(block.iter()).map(|(a, b)| a ^ b).collect();
+        block = Sha256::new().chain_update(mixed).chain_update([i as u8]).chain_update(&dst_prime).finalize();
+    }
+    output.truncate(length);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_dispatch() {
+        let cases = [
+            (HashAlgorithm::Sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            (HashAlgorithm::Sha3_256, "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+            (HashAlgorithm::Keccak256, "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+            (HashAlgorithm::Blake2b256, "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"),
+        ];
+        for (algorithm, expected) in cases {
+            assert_eq!(hex::encode(digest(algorithm, b"abc")), expected, "{algorithm:?}");
+        }
+        assert_eq!(digest(HashAlgorithm::Sha512, b"abc").len(), 64);
+    }
+
+    #[test]
+    fn test_hasher_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Sha512, HashAlgorithm::Sha3_256, HashAlgorithm::Keccak256, HashAlgorithm::Blake2b256] {
+            let mut hasher = Hasher::new(Some(algorithm));
+            // Uneven chunks straddle every algorithm's block boundary
+            for chunk in data.chunks(61) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.algorithm(), algorithm);
+            assert_eq!(hasher.finalize(), digest(algorithm, &data), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn test_hasher_fork() {
+        let mut hasher = Hasher::new(None);
+        hasher.update(b"ab");
+        let mut fork = hasher.fork();
+        hasher.update(b"c");
+        fork.update(b"d");
+        assert_eq!(hex::encode(hasher.finalize()), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(fork.finalize(), digest(HashAlgorithm::Sha256, b"abd"));
+        assert_eq!(Hasher::new(None).finalize(), digest(HashAlgorithm::Sha256, b""));
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380 appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(hex::encode(expand_message_xmd(&[b""], dst, 32)), "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235");
+        assert_eq!(hex::encode(expand_message_xmd(&[b"a", b"bc"], dst, 32)), "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615");
+        assert_eq!(expand_message_xmd(&[b"abc"], dst, 48).len(), 48);
+    }
+}
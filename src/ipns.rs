@@ -0,0 +1,273 @@
+//! IPNS records: the signed, versioned pointers that IPNS names resolve to.
+//!
+//! A record is an `IpnsEntry` protobuf whose `data` field (9) holds the authoritative fields as
+//! a DAG-CBOR map: `Value`, `Validity`, `ValidityType`, `Sequence` and `TTL`. `signatureV2`
+//! (field 8) signs `ipns-signature:` followed by those bytes. The legacy protobuf copies of the
+//! fields, if present, must agree with `data`; the deprecated `signatureV1` is ignored.
+
+use wasm_bindgen::prelude::*;
+
+use crate::cbor;
+use crate::libp2p::{decode_public_key, verify_signature};
+use crate::protobuf::Message;
+use crate::time::{now_millis, parse_rfc3339};
+use crate::ECDSAError;
+
+/// Records are capped at 10 KiB so they fit in a DHT message.
+const MAX_RECORD_LENGTH: usize = 10 * 1024;
+
+const SIGNATURE_PREFIX: &[u8] = b"ipns-signature:";
+
+/// `ValidityType` 0: `Validity` is an RFC 3339 end-of-life time.
+const VALIDITY_EOL: u64 = 0;
+
+const FIELD_VALUE: u64 = 1;
+const FIELD_VALIDITY_TYPE: u64 = 3;
+const FIELD_VALIDITY: u64 = 4;
+const FIELD_SEQUENCE: u64 = 5;
+const FIELD_TTL: u64 = 6;
+const FIELD_PUBLIC_KEY: u64 = 7;
+const FIELD_SIGNATURE_V2: u64 = 8;
+const FIELD_DATA: u64 = 9;
+
+/// A verified IPNS record.
+#[wasm_bindgen]
+pub struct IpnsRecord {
+    value: String,
+    validity: String,
+    sequence: u64,
+    ttl: u64,
+    public_key: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl IpnsRecord {
+    /// The content path the name points to, e.g. `/ipfs/bafy...`.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> String {
+        self.value.clone()
+    }
+
+    /// The RFC 3339 time the record expires at.
+    #[wasm_bindgen(getter)]
+    pub fn validity(&self) -> String {
+        self.validity.clone()
+    }
+
+    /// The sequence number: of two valid records for a name, the higher one wins.
+    #[wasm_bindgen(getter)]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// How long resolvers may cache the record, in nanoseconds.
+    #[wasm_bindgen(getter)]
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    /// The libp2p `PublicKey` protobuf the record was verified with.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+}
+
+/// Reads a non-negative integer from the `data` map.
+fn data_integer(data: &cbor::Value, name: &str) -> Result<u64, ECDSAError> {
+    match data.get_text(name) {
+        Some(&cbor::Value::Integer(value)) => value.try_into().map_err(|_| ECDSAError::InvalidIpnsRecord),
+        _ => Err(ECDSAError::InvalidIpnsRecord),
+    }
+}
+
+/// Reads a byte string from the `data` map.
+fn data_bytes<'a>(data: &'a cbor::Value, name: &str) -> Result<&'a [u8], ECDSAError> {
+    data.get_text(name).and_then(cbor::Value::as_bytes).ok_or(ECDSAError::InvalidIpnsRecord)
+}
+
+/// Verifies a record's signature and validity at `time`, in milliseconds since the Unix epoch.
+fn verify_record(record: &[u8], public_key: Option<&[u8]>, time: i64) -> Result<IpnsRecord, ECDSAError> {
+    if record.len() > MAX_RECORD_LENGTH {
+        return Err(ECDSAError::InvalidIpnsRecord);
+    }
+    let entry = Message::decode(record).map_err(|_| ECDSAError::InvalidIpnsRecord)?;
+    let field = |number| entry.bytes(number).map_err(|_| ECDSAError::InvalidIpnsRecord);
+    let data_bytes_field = field(FIELD_DATA)?.ok_or(ECDSAError::InvalidIpnsRecord)?;
+    let signature = field(FIELD_SIGNATURE_V2)?.ok_or(ECDSAError::InvalidIpnsRecord)?;
+    let data = cbor::decode(data_bytes_field).map_err(|_| ECDSAError::InvalidIpnsRecord)?;
+
+    let value = data_bytes(&data, "Value")?;
+    let validity = data_bytes(&data, "Validity")?;
+    let validity_type = data_integer(&data, "ValidityType")?;
+    let sequence = data_integer(&data, "Sequence")?;
+    let ttl = data_integer(&data, "TTL")?;
+    let varint = |number| entry.varint(number).map_err(|_| ECDSAError::InvalidIpnsRecord);
+    let legacy_matches = field(FIELD_VALUE)?.is_none_or(|legacy| legacy == value)
+        && field(FIELD_VALIDITY)?.is_none_or(|legacy| legacy == validity)
+        && varint(FIELD_VALIDITY_TYPE)?.is_none_or(|legacy| legacy == validity_type)
+        && varint(FIELD_SEQUENCE)?.is_none_or(|legacy| legacy == sequence)
+        && varint(FIELD_TTL)?.is_none_or(|legacy| legacy == ttl);
+    if !legacy_matches {
+        return Err(ECDSAError::InvalidIpnsRecord);
+    }
+
+    // A caller's key must agree with the one embedded in the record
+    let embedded = field(FIELD_PUBLIC_KEY)?;
+    let public_key = match (public_key, embedded) {
+        (Some(expected), Some(embedded)) if expected != embedded => return Err(ECDSAError::IpnsKeyMismatch),
+        (Some(key), _) | (None, Some(key)) => key,
+        (None, None) => return Err(ECDSAError::IpnsKeyMismatch),
+    };
+    let (algorithm, key) = decode_public_key(public_key)?;
+    if !verify_signature(algorithm, &key, &[SIGNATURE_PREFIX, data_bytes_field].concat(), signature)? {
+        return Err(ECDSAError::IpnsSignatureMismatch);
+    }
+
+    let validity = std::str::from_utf8(validity).map_err(|_| ECDSAError::InvalidIpnsRecord)?;
+    let expires = parse_rfc3339(validity).filter(|_| validity_type == VALIDITY_EOL).ok_or(ECDSAError::InvalidIpnsRecord)?;
+    if time >= expires {
+        return Err(ECDSAError::IpnsRecordExpired);
+    }
+    Ok(IpnsRecord {
+        value: String::from_utf8(value.to_vec()).map_err(|_| ECDSAError::InvalidIpnsRecord)?,
+        validity: validity.to_string(),
+        sequence,
+        ttl,
+        public_key: public_key.to_vec(),
+    })
+}
+
+/// Verifies an IPNS record.
+///
+/// The `signatureV2` signature is checked against the record's `data` and the end-of-life
+/// validity against the current time. Deriving the key from the IPNS name is left to the
+/// caller, who passes the key the name commits to.
+///
+/// # Arguments:
+/// * `record` - The marshaled `IpnsEntry` protobuf (`Uint8Array` in JS), e.g. from a gateway's
+///   `application/vnd.ipfs.ipns-record` response.
+/// * `public_key` - The name's libp2p `PublicKey` protobuf. It may be omitted if the record
+///   embeds the key, but must then be checked against the name separately.
+/// * `time` - An optional RFC 3339 timestamp to validate at, defaulting to the current time.
+///
+/// # Returns:
+/// * The record's value, validity, sequence number, TTL and the key that signed it.
+///
+/// # Errors:
+/// * `InvalidIpnsRecord` if the record is malformed or its legacy fields disagree with `data`.
+/// * `IpnsKeyMismatch` if the embedded key differs from `public_key`, or there is no key.
+/// * `InvalidLibp2pKey` or `UnsupportedCurve` if the key cannot be used; RSA keys are not supported.
+/// * `IpnsSignatureMismatch` if the signature is invalid.
+/// * `IpnsRecordExpired` if the record's validity has passed.
+#[wasm_bindgen]
+pub fn verify_ipns_record(record: &[u8], public_key: Option<Vec<u8>>, time: Option<String>) -> Result<IpnsRecord, ECDSAError> {
+    let time = match time {
+        Some(time) => parse_rfc3339(&time).ok_or(ECDSAError::InvalidIpnsRecord)?,
+        None => now_millis(),
+    };
+    verify_record(record, public_key.as_deref(), time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::asn1::KeyAlgorithm;
+    use crate::libp2p::encode_public_key;
+    use crate::protobuf::{write_bytes_field, write_varint_field};
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use sha2::{Digest, Sha256};
+
+    const VALUE: &str = "/ipfs/bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    const VALIDITY: &str = "2027-01-01T00:00:00.000000000Z";
+    const TIME: &str = "2026-06-01T00:00:00Z";
+
+    fn data(sequence: i128) -> Vec<u8> {
+        // DAG-CBOR orders map keys by length, then bytewise
+        let text = |text: &str| cbor::Value::Text(text.to_string());
+        cbor::encode(&cbor::Value::Map(vec![
+            (text("TTL"), cbor::Value::Integer(3_600_000_000_000)),
+            (text("Value"), cbor::Value::Bytes(VALUE.as_bytes().to_vec())),
+            (text("Sequence"), cbor::Value::Integer(sequence)),
+            (text("Validity"), cbor::Value::Bytes(VALIDITY.as_bytes().to_vec())),
+            (text("ValidityType"), cbor::Value::Integer(0)),
+        ]))
+    }
+
+    fn entry(data: &[u8], signature: &[u8], public_key: Option<&[u8]>, legacy_sequence: u64) -> Vec<u8> {
+        let mut entry = Vec::new();
+        write_bytes_field(&mut entry, FIELD_VALUE, VALUE.as_bytes());
+        write_varint_field(&mut entry, FIELD_SEQUENCE, legacy_sequence);
+        if let Some(public_key) = public_key {
+            write_bytes_field(&mut entry, FIELD_PUBLIC_KEY, public_key);
+        }
+        write_bytes_field(&mut entry, FIELD_SIGNATURE_V2, signature);
+        write_bytes_field(&mut entry, FIELD_DATA, data);
+        entry
+    }
+
+    fn secp256k1_record(embed_key: bool) -> (Vec<u8>, Vec<u8>) {
+        let key = SigningKey::from_bytes(&[7; 32]).unwrap();
+        let public_key = encode_public_key(KeyAlgorithm::Secp256k1, &key.verifying_key().to_bytes()).unwrap();
+        let data = data(5);
+        let digest = Sha256::digest([SIGNATURE_PREFIX, &data].concat());
+        let (signature, _) = sign_digest(&key, &digest, &[]).unwrap();
+        let record = entry(&data, signature.to_der().as_bytes(), embed_key.then_some(&public_key[..]), 5);
+        (record, public_key)
+    }
+
+    fn verify(record: &[u8], public_key: Option<&[u8]>) -> Result<IpnsRecord, ECDSAError> {
+        verify_ipns_record(record, public_key.map(<[u8]>::to_vec), Some(TIME.to_string()))
+    }
+
+    #[test]
+    fn test_verify_secp256k1_record() {
+        let (record, public_key) = secp256k1_record(false);
+        let verified = verify(&record, Some(&public_key)).unwrap();
+        assert_eq!(verified.value(), VALUE);
+        assert_eq!(verified.validity(), VALIDITY);
+        assert_eq!((verified.sequence(), verified.ttl()), (5, 3_600_000_000_000));
+        assert_eq!(verified.public_key(), public_key);
+
+        // An embedded key may stand in for the caller's, but must not contradict it
+        let (embedded, _) = secp256k1_record(true);
+        assert!(verify(&embedded, None).is_ok());
+        assert!(verify(&embedded, Some(&public_key)).is_ok());
+        let other = encode_public_key(KeyAlgorithm::Secp256k1, &SigningKey::from_bytes(&[8; 32]).unwrap().verifying_key().to_bytes()).unwrap();
+        assert!(matches!(verify(&embedded, Some(&other)), Err(ECDSAError::IpnsKeyMismatch)));
+        assert!(matches!(verify(&record, None), Err(ECDSAError::IpnsKeyMismatch)));
+        assert!(matches!(verify(&record, Some(&other)), Err(ECDSAError::IpnsSignatureMismatch)));
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_verify_ed25519_record() {
+        let seed = [3; 32];
+        let public_key = encode_public_key(KeyAlgorithm::Ed25519, &crate::ed25519::public_key_from_seed(&seed)).unwrap();
+        let signed = data(1);
+        let signature = crate::ed25519::sign_ed25519(&seed, &[SIGNATURE_PREFIX, &signed].concat());
+        let record = entry(&signed, &signature, Some(&public_key), 1);
+        assert_eq!(verify(&record, None).unwrap().sequence(), 1);
+
+        // The signature covers `data`, whatever the legacy fields say
+        let tampered = entry(&data(2), &signature, Some(&public_key), 2);
+        assert!(matches!(verify(&tampered, None), Err(ECDSAError::IpnsSignatureMismatch)));
+    }
+
+    #[test]
+    fn test_rejects_invalid_records() {
+        let (record, public_key) = secp256k1_record(false);
+        let expired = verify_ipns_record(&record, Some(public_key.clone()), Some("2027-01-01T00:00:00Z".to_string()));
+        assert!(matches!(expired, Err(ECDSAError::IpnsRecordExpired)));
+
+        // Legacy fields that disagree with `data`, and records missing `data` or the signature
+        let mismatched = entry(&data(5), &[0x30], None, 6);
+        assert!(matches!(verify(&mismatched, Some(&public_key)), Err(ECDSAError::InvalidIpnsRecord)));
+        let mut unsigned = Vec::new();
+        write_bytes_field(&mut unsigned, FIELD_DATA, &data(5));
+        assert!(matches!(verify(&unsigned, Some(&public_key)), Err(ECDSAError::InvalidIpnsRecord)));
+        assert!(matches!(verify(&record[..record.len() - 1], Some(&public_key)), Err(ECDSAError::InvalidIpnsRecord)));
+        assert!(matches!(verify(&vec![0; MAX_RECORD_LENGTH + 1], Some(&public_key)), Err(ECDSAError::InvalidIpnsRecord)));
+    }
+}
@@ -0,0 +1,278 @@
+//! JOSE: compact JWS and JWT verification.
+//!
+//! A compact JWS is `base64url(header) "." base64url(payload) "." base64url(signature)`.
+//! ECDSA algorithms sign the ASCII `header.payload` prefix and encode the signature as
+//! fixed-size `r || s` rather than DER (RFC 7518 section 3.4).
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::encoding::from_base64url;
+use crate::json::{self, Value};
+use crate::jwk::{parse_jwk, JwkCurve};
+use crate::keys::parse_public_key;
+use crate::signature::parse_compact;
+use crate::time::now_millis;
+use crate::{verify_digest, ECDSAError};
+
+/// The parts of a compact JWS.
+pub(crate) struct Jws<'a> {
+    pub(crate) header: Value,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) signing_input: &'a str,
+    pub(crate) signature: Vec<u8>,
+}
+
+pub(crate) fn split_jws(token: &str) -> Result<Jws<'_>, ECDSAError> {
+    let token = token.trim();
+    let (signing_input, signature) = token.rsplit_once('.').ok_or(ECDSAError::InvalidJwt)?;
+    let (header, payload) = signing_input.split_once('.').ok_or(ECDSAError::InvalidJwt)?;
+
+    let header = from_base64url(header).ok_or(ECDSAError::InvalidJwt)?;
+    let header = std::str::from_utf8(&header).map_err(|_| ECDSAError::InvalidJwt)?;
+    let header = json::parse(header).map_err(|_| ECDSAError::InvalidJwt)?;
+    if header.as_object().is_none() {
+        return Err(ECDSAError::InvalidJwt);
+    }
+    // Extensions the verifier must understand, such as unencoded payloads, are not supported
+    if header.get("crit").is_some() {
+        return Err(ECDSAError::InvalidJwt);
+    }
+
+    Ok(Jws {
+        header,
+        payload: from_base64url(payload).ok_or(ECDSAError::InvalidJwt)?,
+        signing_input,
+        signature: from_base64url(signature).ok_or(ECDSAError::InvalidJwt)?,
+    })
+}
+
+/// The JWS algorithms this module verifies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Algorithm {
+    /// ECDSA over secp256k1 with SHA-256 (RFC 8812)
+    Es256k,
+    /// ECDSA over P-256 with SHA-256 (RFC 7518)
+    #[cfg(feature = "p256")]
+    Es256,
+}
+
+impl Algorithm {
+    fn from_header(header: &Value) -> Result<Algorithm, ECDSAError> {
+        match header.get("alg").and_then(Value::as_str) {
+            Some("ES256K") => Ok(Algorithm::Es256k),
+            #[cfg(feature = "p256")]
+            Some("ES256") => Ok(Algorithm::Es256),
+            _ => Err(ECDSAError::UnsupportedJwtAlgorithm),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Es256k => "ES256K",
+            #[cfg(feature = "p256")]
+            Algorithm::Es256 => "ES256",
+        }
+    }
+
+    fn curve(self) -> JwkCurve {
+        match self {
+            Algorithm::Es256k => JwkCurve::Secp256k1,
+            #[cfg(feature = "p256")]
+            Algorithm::Es256 => JwkCurve::P256,
+        }
+    }
+}
+
+/// Parses a verification key given either as a JWK or as a SEC1 hex string.
+fn parse_key(key: &str, algorithm: Algorithm) -> Result<Vec<u8>, ECDSAError> {
+    let key = key.trim();
+    if key.starts_with('{') {
+        let jwk = parse_jwk(key)?;
+        if jwk.curve != algorithm.curve() || jwk.alg.is_some_and(|alg| alg != algorithm.name()) {
+            return Err(ECDSAError::UnsupportedJwtAlgorithm);
+        }
+        Ok(jwk.point)
+    } else {
+        hex::decode(key.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)
+    }
+}
+
+/// Checks the signature of a compact JWS and returns its payload.
+fn verify_jws(token: &str, key: &str) -> Result<Vec<u8>, ECDSAError> {
+    let jws = split_jws(token)?;
+    let algorithm = Algorithm::from_header(&jws.header)?;
+    let key = parse_key(key, algorithm)?;
+    if jws.signature.len() != 64 {
+        return Err(ECDSAError::JwtSignatureMismatch);
+    }
+    let digest = Sha256::digest(jws.signing_input.as_bytes());
+
+    let valid = match algorithm {
+        Algorithm::Es256k => {
+            let key = parse_public_key(&key)?;
+            // JOSE does not require low-S signatures
+            let sig = parse_compact(&jws.signature).map_err(|_| ECDSAError::JwtSignatureMismatch)?;
+            verify_digest(&key, &digest, &sig.normalize_s().unwrap_or(sig))
+        }
+        #[cfg(feature = "p256")]
+        Algorithm::Es256 => {
+            let key = crate::p256::parse_public_key(&key)?;
+            let signature = crate::p256::parse_signature(&jws.signature).map_err(|_| ECDSAError::JwtSignatureMismatch)?;
+            crate::p256::verify_prehashed(&key, &digest, &signature)
+        }
+    };
+    if !valid {
+        return Err(ECDSAError::JwtSignatureMismatch);
+    }
+    Ok(jws.payload)
+}
+
+/// Reads an optional NumericDate claim, in seconds since the epoch.
+fn numeric_date(claims: &Value, name: &str) -> Result<Option<f64>, ECDSAError> {
+    match claims.get(name) {
+        None => Ok(None),
+        Some(Value::Number(text)) => text.parse().map(Some).map_err(|_| ECDSAError::InvalidJwt),
+        Some(_) => Err(ECDSAError::InvalidJwt),
+    }
+}
+
+/// Verifies a JWT and checks its `exp` and `nbf` claims, returning the claims as JSON text.
+fn verify_jwt_claims(token: &str, key: &str) -> Result<String, ECDSAError> {
+    let payload = verify_jws(token, key)?;
+    let payload = String::from_utf8(payload).map_err(|_| ECDSAError::InvalidJwt)?;
+    let claims = json::parse(&payload).map_err(|_| ECDSAError::InvalidJwt)?;
+    if claims.as_object().is_none() {
+        return Err(ECDSAError::InvalidJwt);
+    }
+
+    let now = now_millis() as f64 / 1000.0;
+    if numeric_date(&claims, "exp")?.is_some_and(|exp| now >= exp) {
+        return Err(ECDSAError::JwtExpired);
+    }
+    if numeric_date(&claims, "nbf")?.is_some_and(|nbf| now < nbf) {
+        return Err(ECDSAError::JwtNotYetValid);
+    }
+    Ok(payload)
+}
+
+/// Verifies an ES256K or ES256 signed JWT, covering both wallet and DID issued tokens and
+/// mainstream OpenID Connect ID tokens.
+///
+/// The header's `alg` selects the curve: `ES256K` for secp256k1 and `ES256` for P-256
+/// (with the `p256` feature); a JWK's `crv` and, if present, `alg` must agree with it.
+/// If present, `exp` and `nbf` are checked against the current time; the caller remains
+/// responsible for checking `iss`, `aud` and other claims.
+///
+/// # Arguments:
+/// * `token` - The compact JWT, `header.payload.signature`.
+/// * `key` - The issuer's public key, either as a JWK JSON string or as a SEC1 hex string.
+///
+/// # Returns:
+/// * The token's claims, as a JS object.
+///
+/// # Errors:
+/// * Returns an error if the token is malformed, uses another algorithm, has an invalid
+///   signature, has expired or is not yet valid, or the key is malformed.
+#[wasm_bindgen]
+pub fn verify_jwt(token: &str, key: &str) -> Result<JsValue, ECDSAError> {
+    let claims = verify_jwt_claims(token, key)?;
+    js_sys::JSON::parse(&claims).map_err(|_| ECDSAError::InvalidJwt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_base64url;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[0x66; 32]).unwrap()
+    }
+
+    fn jwk(key: &SigningKey) -> String {
+        let point = key.verifying_key().to_encoded_point(false);
+        format!(
+            r#"{{"kty":"EC","crv":"secp256k1","x":"{}","y":"{}"}}"#,
+            to_base64url(point.x().unwrap()),
+            to_base64url(point.y().unwrap())
+        )
+    }
+
+    fn sign_token(key: &SigningKey, header: &str, claims: &str) -> String {
+        let signing_input = format!("{}.{}", to_base64url(header.as_bytes()), to_base64url(claims.as_bytes()));
+        let (sig, _) = sign_digest(key, &Sha256::digest(signing_input.as_bytes()), &[]).unwrap();
+        format!("{signing_input}.{}", to_base64url(sig.as_ref()))
+    }
+
+    #[test]
+    fn test_verify_es256k() {
+        let key = key();
+        let claims = r#"{"iss":"did:example:123","exp":99999999999}"#;
+        let token = sign_token(&key, r#"{"alg":"ES256K","typ":"JWT"}"#, claims);
+
+        assert_eq!(verify_jwt_claims(&token, &jwk(&key)).unwrap(), claims);
+        assert_eq!(verify_jwt_claims(&token, &hex::encode(key.verifying_key().to_bytes())).unwrap(), claims);
+
+        let other = SigningKey::from_bytes(&[0x67; 32]).unwrap();
+        assert!(matches!(verify_jwt_claims(&token, &jwk(&other)), Err(ECDSAError::JwtSignatureMismatch)));
+
+        // Swapping in another payload breaks the signature
+        let (header, rest) = token.split_once('.').unwrap();
+        let forged = format!("{header}.{}.{}", to_base64url(b"{}"), rest.split_once('.').unwrap().1);
+        assert!(matches!(verify_jwt_claims(&forged, &jwk(&key)), Err(ECDSAError::JwtSignatureMismatch)));
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_verify_es256() {
+        // RFC 7515 appendix A.3
+        let p256_jwk = r#"{"kty":"EC","crv":"P-256","x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU","y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"}"#;
+        let token = "eyJhbGciOiJFUzI1NiJ9.eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ.\
+                     DtEhU3ljbEg8L38VWAfUAqOyKAM6-Xx-F4GawxaepmXFCgfTjDxw5djxLa8ISlSApmWQxfKTUJqPP3-Kg6NU1Q";
+        let payload = verify_jws(token, p256_jwk).unwrap();
+        assert!(payload.starts_with(b"{\"iss\":\"joe\""));
+        // The example token expired in 2011
+        assert!(matches!(verify_jwt_claims(token, p256_jwk), Err(ECDSAError::JwtExpired)));
+
+        // A secp256k1 key cannot verify an ES256 token
+        assert!(matches!(verify_jws(token, &jwk(&key())), Err(ECDSAError::UnsupportedJwtAlgorithm)));
+        let tampered = token.replace("DtEh", "DtEi");
+        assert!(matches!(verify_jws(&tampered, p256_jwk), Err(ECDSAError::JwtSignatureMismatch)));
+    }
+
+    #[test]
+    fn test_time_claims() {
+        let key = key();
+        let expired = sign_token(&key, r#"{"alg":"ES256K"}"#, r#"{"exp":1000}"#);
+        assert!(matches!(verify_jwt_claims(&expired, &jwk(&key)), Err(ECDSAError::JwtExpired)));
+        let early = sign_token(&key, r#"{"alg":"ES256K"}"#, r#"{"nbf":99999999999.5}"#);
+        assert!(matches!(verify_jwt_claims(&early, &jwk(&key)), Err(ECDSAError::JwtNotYetValid)));
+        let bad = sign_token(&key, r#"{"alg":"ES256K"}"#, r#"{"exp":"tomorrow"}"#);
+        assert!(matches!(verify_jwt_claims(&bad, &jwk(&key)), Err(ECDSAError::InvalidJwt)));
+    }
+
+    #[test]
+    fn test_rejects_other_algorithms() {
+        let key = key();
+        for header in [r#"{"alg":"none"}"#, r#"{"alg":"HS256"}"#, r#"{"typ":"JWT"}"#] {
+            let token = sign_token(&key, header, "{}");
+            assert!(matches!(verify_jwt_claims(&token, &jwk(&key)), Err(ECDSAError::UnsupportedJwtAlgorithm)), "{header}");
+        }
+        let critical = sign_token(&key, r#"{"alg":"ES256K","crit":["b64"],"b64":false}"#, "{}");
+        assert!(matches!(verify_jwt_claims(&critical, &jwk(&key)), Err(ECDSAError::InvalidJwt)));
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let key = key();
+        let token = sign_token(&key, r#"{"alg":"ES256K"}"#, "{}");
+        assert!(matches!(verify_jwt_claims("abc", &jwk(&key)), Err(ECDSAError::InvalidJwt)));
+        assert!(matches!(verify_jwt_claims(&format!("{token}="), &jwk(&key)), Err(ECDSAError::InvalidJwt)));
+        assert!(matches!(verify_jwt_claims(&token, r#"{"kty":"EC","crv":"P-384","x":"","y":""}"#), Err(ECDSAError::InvalidJwk)));
+        assert!(matches!(verify_jwt_claims(&token, r#"{"kty":"OKP","crv":"secp256k1"}"#), Err(ECDSAError::InvalidJwk)));
+        assert!(matches!(verify_jwt_claims(&token, "02abcd"), Err(ECDSAError::InvalidPublicKey)));
+    }
+}
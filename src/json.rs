@@ -0,0 +1,428 @@
+//! Minimal JSON reader used by the structured-data features (EIP-712, events, tokens).
+//! Object member order is preserved and numbers are kept as their source text,
+//! so large integers survive without loss of precision.
+
+use crate::ECDSAError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Looks up a member of an object. Returns `None` for other value kinds.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Reads a non-negative integer that fits in a `u64`.
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a complete JSON document.
+pub(crate) fn parse(input: &str) -> Result<Value, ECDSAError> {
+    let mut parser = Parser { bytes: input.as_bytes(), pos: 0 };
+    let value = parser.value(0)?;
+    parser.whitespace();
+    if parser.pos != parser.bytes.len() {
+        return Err(ECDSAError::InvalidJson);
+    }
+    Ok(value)
+}
+
+/// Appends `text` as a JSON string with minimal escaping, as RFC 8785 and NIP-01 both
+/// specify: `"`, `\\` and the `\n \r \t \b \f` controls use short escapes, other controls
+/// `\u00XX`, all else verbatim.
+pub(crate) fn write_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Appends a number as ECMAScript's `Number.prototype.toString` formats the nearest double
+/// (RFC 8785 section 3.2.2.3).
+fn write_number(out: &mut String, text: &str) -> Result<(), ECDSAError> {
+    let value: f64 = text.parse().map_err(|_| ECDSAError::InvalidJson)?;
+    if !value.is_finite() {
+        return Err(ECDSAError::InvalidJson);
+    }
+    if value == 0.0 {
+        out.push('0');
+        return Ok(());
+    }
+    if value < 0.0 {
+        out.push('-');
+    }
+    // The shortest digits that round-trip, and the position of the decimal point after them
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').ok_or(ECDSAError::InvalidJson)?;
+    let digits = mantissa.replace('.', "");
+    let length = digits.len() as i32;
+    let point = exponent.parse::<i32>().map_err(|_| ECDSAError::InvalidJson)? + 1;
+    match point {
+        _ if length <= point && point <= 21 => {
+            out.push_str(&digits);
+            out.extend(std::iter::repeat_n('0', (point - length) as usize));
+        }
+        1..=21 => {
+            out.push_str(&digits[..point as usize]);
+            out.push('.');
+            out.push_str(&digits[point as usize..]);
+        }
+        -5..=0 => {
+            out.push_str("0.");
+            out.extend(std::iter::repeat_n('0', -point as usize));
+            out.push_str(&digits);
+        }
+        _ => {
+            out.push_str(&digits[..1]);
+            if length > 1 {
+                out.push('.');
+                out.push_str(&digits[1..]);
+            }
+            out.push_str(&format!("e{}{}", if point > 0 { "+" } else { "-" }, (point - 1).abs()));
+        }
+    }
+    Ok(())
+}
+
+fn write_canonical(out: &mut String, value: &Value) -> Result<(), ECDSAError> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        Value::Number(text) => write_number(out, text)?,
+        Value::String(text) => write_string(out, text),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(out, item)?;
+            }
+            out.push(']');
+        }
+        Value::Object(members) => {
+            let mut members = members.iter().collect::<Vec<_>>();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            if members.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                return Err(ECDSAError::InvalidJson);
+            }
+            out.push('{');
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_canonical(out, value)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Serializes a value with the JSON Canonicalization Scheme (RFC 8785): no whitespace, object
+/// members sorted by the UTF-16 code units of their names, and numbers in ECMAScript form.
+///
+/// # Errors:
+/// * `InvalidJson` for duplicate member names or numbers outside the range of a double.
+pub(crate) fn canonicalize(value: &Value) -> Result<String, ECDSAError> {
+    let mut out = String::new();
+    write_canonical(&mut out, value)?;
+    Ok(out)
+}
+
+/// Nesting limit, to keep hostile input from exhausting the wasm stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &[u8]) -> Result<(), ECDSAError> {
+        if self.bytes[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(ECDSAError::InvalidJson)
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ECDSAError> {
+        if depth > MAX_DEPTH {
+            return Err(ECDSAError::InvalidJson);
+        }
+        self.whitespace();
+        match self.peek().ok_or(ECDSAError::InvalidJson)? {
+            b'n' => self.expect(b"null").map(|_| Value::Null),
+            b't' => self.expect(b"true").map(|_| Value::Bool(true)),
+            b'f' => self.expect(b"false").map(|_| Value::Bool(false)),
+            b'"' => self.string().map(Value::String),
+            b'[' => self.array(depth),
+            b'{' => self.object(depth),
+            _ => self.number(),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, ECDSAError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                _ => return Err(ECDSAError::InvalidJson),
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, ECDSAError> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(ECDSAError::InvalidJson);
+            }
+            let key = self.string()?;
+            self.whitespace();
+            self.expect(b":")?;
+            let value = self.value(depth + 1)?;
+            members.push((key, value));
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                _ => return Err(ECDSAError::InvalidJson),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ECDSAError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(ECDSAError::InvalidJson),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.required_digits()?;
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            self.required_digits()?;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).map_err(|_| ECDSAError::InvalidJson)?;
+        Ok(Value::Number(text.to_string()))
+    }
+
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn required_digits(&mut self) -> Result<(), ECDSAError> {
+        let start = self.pos;
+        self.digits();
+        if self.pos == start {
+            return Err(ECDSAError::InvalidJson);
+        }
+        Ok(())
+    }
+
+    fn hex4(&mut self) -> Result<u32, ECDSAError> {
+        let digits = self.bytes.get(self.pos..self.pos + 4).ok_or(ECDSAError::InvalidJson)?;
+        let text = std::str::from_utf8(digits).map_err(|_| ECDSAError::InvalidJson)?;
+        let code = u32::from_str_radix(text, 16).map_err(|_| ECDSAError::InvalidJson)?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn string(&mut self) -> Result<String, ECDSAError> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        loop {
+            let byte = self.peek().ok_or(ECDSAError::InvalidJson)?;
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = self.peek().ok_or(ECDSAError::InvalidJson)?;
+                    self.pos += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(ECDSAError::InvalidJson),
+                    };
+                    let mut buf = [0u8; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                0x00..=0x1f => return Err(ECDSAError::InvalidJson),
+                _ => out.push(byte),
+            }
+        }
+        String::from_utf8(out).map_err(|_| ECDSAError::InvalidJson)
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, ECDSAError> {
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            self.expect(b"\\u")?;
+            let low = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(ECDSAError::InvalidJson);
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or(ECDSAError::InvalidJson)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document() {
+        let value = parse(r#"{"a": [1, -2.5e3, true, null], "b": {"c": "d\"\u00e9\ud83d\ude00"}}"#).unwrap();
+        let items = value.get("a").unwrap().as_array().unwrap();
+        assert_eq!(items[0], Value::Number("1".into()));
+        assert_eq!(items[1], Value::Number("-2.5e3".into()));
+        assert_eq!(items[2], Value::Bool(true));
+        assert_eq!(items[3], Value::Null);
+        assert_eq!(value.get("b").unwrap().get("c").unwrap().as_str(), Some("d\"é😀"));
+        assert_eq!(items[0].as_u64(), Some(1));
+        assert_eq!(items[1].as_u64(), None);
+    }
+
+    #[test]
+    fn test_large_numbers_are_preserved() {
+        let value = parse("115792089237316195423570985008687907853269984665640564039457584007913129639935").unwrap();
+        assert_eq!(
+            value,
+            Value::Number("115792089237316195423570985008687907853269984665640564039457584007913129639935".into())
+        );
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        // Examples from RFC 8785 sections 3.2.2 and 3.2.3
+        let value = parse(r#"{"numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/", "literals": [null, true, false]}"#)
+        .unwrap();
+        assert_eq!(
+            canonicalize(&value).unwrap(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+        let value = parse(r#"{"\u20ac":1,"\r":2,"\ufb33":3,"1":4,"\ud83d\ude00":5,"\u0080":6,"\u00f6":7}"#).unwrap();
+        assert_eq!(canonicalize(&value).unwrap(), "{\"\\r\":2,\"1\":4,\"\u{80}\":6,\"ö\":7,\"€\":1,\"😀\":5,\"\u{fb33}\":3}");
+
+        for (number, expected) in [("-0", "0"), ("1e21", "1e+21"), ("1e20", "100000000000000000000"), ("-1.5e-7", "-1.5e-7"), ("0.000001", "0.000001")] {
+            assert_eq!(canonicalize(&parse(number).unwrap()).unwrap(), expected, "{number}");
+        }
+        assert!(canonicalize(&parse("1e400").unwrap()).is_err());
+        assert!(canonicalize(&parse(r#"{"a": 1, "a": 2}"#).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_reject_malformed() {
+        for input in ["", "{", "[1,]", "{\"a\" 1}", "01", "1.", "\"\\x\"", "[] []", "\"\\ud800\""] {
+            assert!(parse(input).is_err(), "{input}");
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! JSON Web Keys (RFC 7517) for elliptic curve public keys.
+//!
+//! An EC JWK carries the curve name in `crv` and the affine coordinates as fixed-length
+//! Base64url strings in `x` and `y`: `{"kty":"EC","crv":"secp256k1","x":"...","y":"..."}`.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::VerifyingKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+use crate::encoding::{from_base64url, to_base64url};
+use crate::json::{self, Value};
+use crate::keys::parse_public_key;
+use crate::ECDSAError;
+
+/// The curves whose keys can be exchanged as JWKs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum JwkCurve {
+    Secp256k1,
+    #[cfg(feature = "p256")]
+    P256,
+}
+
+impl JwkCurve {
+    fn from_name(name: &str) -> Result<JwkCurve, ECDSAError> {
+        match name {
+            "secp256k1" => Ok(JwkCurve::Secp256k1),
+            #[cfg(feature = "p256")]
+            "P-256" => Ok(JwkCurve::P256),
+            _ => Err(ECDSAError::InvalidJwk),
+        }
+    }
+
+    /// The `crv` name registered for the curve.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            JwkCurve::Secp256k1 => "secp256k1",
+            #[cfg(feature = "p256")]
+            JwkCurve::P256 => "P-256",
+        }
+    }
+
+    /// Validates a SEC1 public key on this curve and returns its uncompressed encoding.
+    fn uncompressed(self, public_key: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+        match self {
+            JwkCurve::Secp256k1 => Ok(parse_public_key(public_key)?.to_encoded_point(false).as_bytes().to_vec()),
+            #[cfg(feature = "p256")]
+            JwkCurve::P256 => Ok(crate::p256::encode_uncompressed(&crate::p256::parse_public_key(public_key)?)),
+        }
+    }
+}
+
+/// A parsed EC public JWK.
+pub(crate) struct Jwk {
+    pub(crate) curve: JwkCurve,
+    /// The key as an uncompressed SEC1 point, checked to lie on the curve
+    pub(crate) point: Vec<u8>,
+    /// The optional `alg` member restricting the key to one algorithm
+    pub(crate) alg: Option<String>,
+}
+
+/// Parses an EC public JWK. Private members such as `d` are ignored.
+pub(crate) fn parse_jwk(jwk: &str) -> Result<Jwk, ECDSAError> {
+    let jwk = json::parse(jwk).map_err(|_| ECDSAError::InvalidJwk)?;
+    let member = |name: &str| jwk.get(name).and_then(Value::as_str).ok_or(ECDSAError::InvalidJwk);
+    if member("kty")? != "EC" {
+        return Err(ECDSAError::InvalidJwk);
+    }
+    let curve = JwkCurve::from_name(member("crv")?)?;
+    let alg = match jwk.get("alg") {
+        None => None,
+        Some(alg) => Some(alg.as_str().ok_or(ECDSAError::InvalidJwk)?.to_string()),
+    };
+
+    let coordinate = |name: &str| {
+        from_base64url(member(name)?).filter(|bytes| bytes.len() == 32).ok_or(ECDSAError::InvalidJwk)
+    };
+    let mut point = vec![0x04];
+    point.extend(coordinate("x")?);
+    point.extend(coordinate("y")?);
+    curve.uncompressed(&point).map_err(|_| ECDSAError::InvalidJwk)?;
+    Ok(Jwk { curve, point, alg })
+}
+
+/// Serializes an uncompressed SEC1 point as a JWK.
+pub(crate) fn to_jwk(curve: JwkCurve, point: &[u8]) -> String {
+    format!(
+        r#"{{"kty":"EC","crv":"{}","x":"{}","y":"{}"}}"#,
+        curve.name(),
+        to_base64url(&point[1..33]),
+        to_base64url(&point[33..65])
+    )
+}
+
+/// Converts an EC JWK into a SEC1 public key.
+///
+/// # Arguments:
+/// * `jwk` - The key as JSON, with `kty` `EC` and `crv` `secp256k1` or `P-256`.
+///
+/// # Returns:
+/// * The 65-byte uncompressed SEC1 public key, as a hex string.
+///
+/// # Errors:
+/// * Returns an error if the JWK is malformed, uses another curve or is not a point on the curve.
+#[wasm_bindgen]
+pub fn jwk_to_public_key(jwk: &str) -> Result<String, ECDSAError> {
+    parse_jwk(jwk).map(|jwk| hex::encode(jwk.point))
+}
+
+/// Converts a SEC1 public key into an EC JWK.
+///
+/// # Arguments:
+/// * `public_key` - The SEC1 public key (compressed or uncompressed), provided as a hex string.
+/// * `crv` - The curve: `secp256k1` or `P-256`.
+///
+/// # Returns:
+/// * The JWK as compact JSON with `kty`, `crv`, `x` and `y` members.
+///
+/// # Errors:
+/// * Returns an error if the curve is not supported or the key is not a point on it.
+#[wasm_bindgen]
+pub fn public_key_to_jwk(public_key: &str, crv: &str) -> Result<String, ECDSAError> {
+    let curve = JwkCurve::from_name(crv)?;
+    let bytes = hex::decode(public_key.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    Ok(to_jwk(curve, &curve.uncompressed(&bytes)?))
+}
+
+/// Parses a secp256k1 JWK into a verifying key.
+pub(crate) fn secp256k1_jwk(jwk: &str) -> Result<VerifyingKey, ECDSAError> {
+    let jwk = parse_jwk(jwk)?;
+    if jwk.curve != JwkCurve::Secp256k1 {
+        return Err(ECDSAError::InvalidJwk);
+    }
+    parse_public_key(&jwk.point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The public key of private key 1
+    const UNCOMPRESSED: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                                483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    const JWK: &str = r#"{"kty":"EC","crv":"secp256k1","x":"eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g","y":"SDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg"}"#;
+
+    #[test]
+    fn test_secp256k1_round_trip() {
+        let compressed = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        assert_eq!(public_key_to_jwk(compressed, "secp256k1").unwrap(), JWK);
+        assert_eq!(public_key_to_jwk(UNCOMPRESSED, "secp256k1").unwrap(), JWK);
+        assert_eq!(jwk_to_public_key(JWK).unwrap(), UNCOMPRESSED);
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_p256_round_trip() {
+        // RFC 7515 appendix A.3
+        let jwk = r#"{"kty":"EC","crv":"P-256","x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU","y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"}"#;
+        let public_key = jwk_to_public_key(jwk).unwrap();
+        assert_eq!(public_key_to_jwk(&public_key, "P-256").unwrap(), jwk);
+
+        // Compressed input is decompressed on the right curve
+        let mut compressed = hex::decode(&public_key).unwrap()[..33].to_vec();
+        compressed[0] = 0x02 | (hex::decode(&public_key).unwrap()[64] & 1);
+        assert_eq!(public_key_to_jwk(&hex::encode(compressed), "P-256").unwrap(), jwk);
+        assert!(public_key_to_jwk(&public_key, "secp256k1").is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_jwks() {
+        for jwk in [
+            r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#,
+            r#"{"kty":"EC","crv":"P-384","x":"eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g","y":"SDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg"}"#,
+            // Off the curve
+            r#"{"kty":"EC","crv":"secp256k1","x":"eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g","y":"TDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg"}"#,
+            // Coordinates must be exactly 32 bytes
+            r#"{"kty":"EC","crv":"secp256k1","x":"vmZ--dy7rFWgYpXOhwsHApv82y3OKNlZ8oFbFvgXmA","y":"SDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg"}"#,
+            r#"{"kty":"EC","crv":"secp256k1"}"#,
+            "[]",
+        ] {
+            assert!(matches!(jwk_to_public_key(jwk), Err(ECDSAError::InvalidJwk)), "{jwk}");
+        }
+        assert!(public_key_to_jwk(UNCOMPRESSED, "P-521").is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Key derivation: HKDF-SHA256 (RFC 5869) and PBKDF2 (RFC 8018) over HMAC-SHA256 and
+//! HMAC-SHA512, which are also exposed directly for webhook and API request signatures.
+//! Uses the RustCrypto `hmac`, `hkdf` and `pbkdf2` crates.
+
+use wasm_bindgen::prelude::*;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+
+use crate::constant_time::constant_time_eq;
+use crate::ECDSAError;
+
+/// Computes HMAC-SHA256 with a key of any length over the concatenation of `parts`.
+pub(crate) fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    parts.iter().for_each(|part| mac.update(part));
+    mac.finalize().into_bytes().into()
+}
+
+/// Computes HMAC-SHA512 with a key of any length over the concatenation of `parts`.
+pub(crate) fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    parts.iter().for_each(|part| mac.update(part));
+    mac.finalize().into_bytes().into()
+}
+
+/// PBKDF2 with HMAC-SHA256 as the pseudorandom function.
+pub(crate) fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Vec<u8> {
+    let mut output = vec![0u8; length];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations, &mut output);
+    output
+}
+
+/// PBKDF2 with HMAC-SHA512 as the pseudorandom function, as used by BIP-39 seeds.
+pub(crate) fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Vec<u8> {
+    let mut output = vec![0u8; length];
+    pbkdf2::pbkdf2_hmac::<Sha512>(password, salt, iterations, &mut output);
+    output
+}
+
+/// Hash functions for `hmac` and `hmac_verify`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HmacAlgorithm {
+    /// HMAC-SHA256 with a 32-byte tag (the default).
+    #[default]
+    Sha256,
+    /// HMAC-SHA512 with a 64-byte tag.
+    Sha512,
+}
+
+/// Computes an HMAC (RFC 2104).
+///
+/// # Arguments:
+/// * `key` - The secret key, of any length.
+/// * `message` - The authenticated message, e.g. a webhook's raw request body.
+/// * `algorithm` - The hash function, SHA-256 by default.
+///
+/// # Returns:
+/// * The tag (`Uint8Array` in JS).
+#[wasm_bindgen]
+pub fn hmac(key: &[u8], message: &[u8], algorithm: Option<HmacAlgorithm>) -> Vec<u8> {
+    match algorithm.unwrap_or_default() {
+        HmacAlgorithm::Sha256 => hmac_sha256(key, &[message]).to_vec(),
+        HmacAlgorithm::Sha512 => hmac_sha512(key, &[message]).to_vec(),
+    }
+}
+
+/// Checks an HMAC tag, comparing it in constant time so that the time taken does not reveal
+/// how much of a forged tag was right.
+///
+/// # Arguments:
+/// * `key` - The secret key, of any length.
+/// * `message` - The authenticated message.
+/// * `tag` - The received tag; truncated tags are rejected.
+/// * `algorithm` - The hash function, SHA-256 by default.
+///
+/// # Returns:
+/// * A boolean indicating whether the tag is valid.
+#[wasm_bindgen]
+pub fn hmac_verify(key: &[u8], message: &[u8], tag: &[u8], algorithm: Option<HmacAlgorithm>) -> bool {
+    let expected = hmac(key, message, algorithm);
+    constant_time_eq(&expected, tag)
+}
+
+/// Derives key material with HKDF-SHA256.
+///
+/// # Arguments:
+/// * `salt` - Optional non-secret salt; pass an empty array for none.
+/// * `ikm` - The input keying material, e.g. an ECDH shared secret.
+/// * `info` - Context and application specific information binding the output to its use.
+/// * `length` - Number of output bytes, at most 8160.
+///
+/// # Returns:
+/// * The derived key material (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * Returns an error if `length` exceeds 255 * 32 bytes.
+#[wasm_bindgen]
+pub fn hkdf_extract_expand(salt: &[u8], ikm: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>, ECDSAError> {
+    let mut okm = vec![0u8; length];
+    Hkdf::<Sha256>::new(Some(salt), ikm).expand(info, &mut okm).map_err(|_| ECDSAError::InvalidOutputLength)?;
+    Ok(okm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc5869_vectors() {
+        // Test case 1
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        assert_eq!(
+            hex::encode(hkdf_extract_expand(&salt, &[0x0b; 22], &info, 42).unwrap()),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+
+        // Test case 3: empty salt and info
+        assert_eq!(
+            hex::encode(hkdf_extract_expand(&[], &[0x0b; 22], &[], 42).unwrap()),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        );
+    }
+
+    #[test]
+    fn test_output_length() {
+        assert!(hkdf_extract_expand(&[], b"ikm", &[], 0).unwrap().is_empty());
+        assert_eq!(hkdf_extract_expand(&[], b"ikm", &[], 255 * 32).unwrap().len(), 255 * 32);
+        assert!(matches!(
+            hkdf_extract_expand(&[], b"ikm", &[], 255 * 32 + 1),
+            Err(ECDSAError::InvalidOutputLength)
+        ));
+    }
+
+    #[test]
+    fn test_pbkdf2_vectors() {
+        // RFC 7914 section 11
+        assert_eq!(
+            hex::encode(pbkdf2_hmac_sha256(b"passwd", b"salt", 1, 64)),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+             49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+        assert_eq!(
+            hex::encode(pbkdf2_hmac_sha256(b"password", b"salt", 4096, 32)),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha512_vectors() {
+        // RFC 4231 test cases 2 and 6
+        assert_eq!(
+            hex::encode(hmac_sha512(b"Jefe", &[b"what do ya want ", b"for nothing?"])),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+        assert_eq!(
+            hex::encode(hmac_sha512(&[0xaa; 131], &[b"Test Using Larger Than Block-Size Key - Hash Key First"])),
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+             6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+        );
+    }
+
+    #[test]
+    fn test_hmac() {
+        // RFC 4231 test case 2
+        let (key, message) = (b"Jefe", b"what do ya want for nothing?");
+        let tag = hmac(key, message, None);
+        assert_eq!(hex::encode(&tag), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_eq!(hmac(key, message, Some(HmacAlgorithm::Sha256)), tag);
+        assert_eq!(
+            hex::encode(hmac(key, message, Some(HmacAlgorithm::Sha512))),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+
+    #[test]
+    fn test_hmac_verify() {
+        let body = br#"{"event":"paid"}"#;
+        let tag = hex::decode("05aa43479857e94cdd026e8f097cefdf27dbd5e3e60c6d8c49a43238e0acdba2").unwrap();
+        assert!(hmac_verify(b"whsec", body, &tag, None));
+        assert!(!hmac_verify(b"whsec", body, &tag, Some(HmacAlgorithm::Sha512)));
+        assert!(!hmac_verify(b"other", body, &tag, None));
+        assert!(!hmac_verify(b"whsec", br#"{"event":"refunded"}"#, &tag, None));
+
+        // Altered and truncated tags
+        let mut altered = tag.clone();
+        altered[31] ^= 1;
+        assert!(!hmac_verify(b"whsec", body, &altered, None));
+        assert!(!hmac_verify(b"whsec", body, &tag[..16], None));
+        assert!(!hmac_verify(b"whsec", body, &[], None));
+    }
+
+    #[test]
+    fn test_long_hmac_key() {
+        // RFC 4231 test case 6: keys longer than the block size are hashed first
+        assert_eq!(
+            hex::encode(hmac_sha256(&[0xaa; 131], &[b"Test Using Larger Than Block-Size Key - Hash Key First"])),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+use crate::random::random_scalar;
+use crate::{ECDSAError, ECDSASigner, SecretKey};
+
+/// A secp256k1 private/public key pair.
+/// Keys are generated inside wasm from the platform CSPRNG (`crypto.getRandomValues` in browsers),
+/// or the source installed with `set_entropy_source`.
+#[wasm_bindgen]
+pub struct KeyPair {
+    signing_key: SigningKey,
+}
+
+#[wasm_bindgen]
+impl KeyPair {
+    /// Generates a fresh random key pair.
+    ///
+    /// # Errors:
+    /// * `RandomnessUnavailable` if no randomness is available.
+    pub fn generate() -> Result<KeyPair, ECDSAError> {
+        Ok(KeyPair { signing_key: SigningKey::from(random_scalar()?) })
+    }
+
+    /// Returns the 32-byte private key scalar, as a hex string.
+    pub fn private_key(&self) -> String {
+        hex::encode(self.signing_key.to_bytes())
+    }
+
+    /// Returns the private key as a `SecretKey`, which can be wiped with `destroy()`.
+    pub fn secret_key(&self) -> SecretKey {
+        SecretKey::from_signing_key(&self.signing_key)
+    }
+
+    /// Returns the 33-byte SEC1 compressed public key, as a hex string.
+    pub fn public_key_compressed(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    /// Returns the 65-byte SEC1 uncompressed (0x04-prefixed) public key, as a hex string.
+    pub fn public_key_uncompressed(&self) -> String {
+        let point = self.signing_key.verifying_key().to_encoded_point(false);
+        hex::encode(point.as_bytes())
+    }
+
+    /// Creates an `ECDSASigner` backed by this key pair's private key.
+    pub fn signer(&self) -> ECDSASigner {
+        ECDSASigner::from_signing_key(self.signing_key.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ECDSAVerifier;
+
+    #[test]
+    fn test_generate_unique() {
+        let a = KeyPair::generate().unwrap();
+        let b = KeyPair::generate().unwrap();
+        assert_ne!(a.private_key(), b.private_key());
+    }
+
+    #[test]
+    fn test_public_key_encodings() {
+        let pair = KeyPair::generate().unwrap();
+        let compressed = pair.public_key_compressed();
+        let uncompressed = pair.public_key_uncompressed();
+
+        assert_eq!(compressed.len(), 66);
+        assert_eq!(uncompressed.len(), 130);
+        assert!(uncompressed.starts_with("04"));
+        // Both encodings share the same x-coordinate
+        assert_eq!(compressed[2..], uncompressed[2..66]);
+    }
+
+    #[test]
+    fn test_generated_key_signs() {
+        let pair = KeyPair::generate().unwrap();
+        let signature = pair.signer().sign("hello").unwrap();
+
+        let verifier = ECDSAVerifier::default();
+        let result = verifier.verify_signature("hello", &signature.der(), &pair.public_key_compressed());
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_secret_key() {
+        let pair = KeyPair::generate().unwrap();
+        let mut secret = pair.secret_key();
+        assert_eq!(secret.public_key(None).unwrap(), pair.public_key_compressed());
+        secret.destroy();
+        assert_eq!(pair.signer().public_key(), pair.public_key_compressed());
+    }
+}
@@ -0,0 +1,186 @@
+//! Public key decoding shared by the verifier entry points.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::VerifyingKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+pub(crate) mod asn1;
+
+use crate::jwk::{self, JwkCurve};
+use crate::{hash, signature, verify_digest, ECDSAError, ECDSAVerifier, SignatureFormat};
+
+/// Parses a SEC1 encoded secp256k1 public key.
+///
+/// Accepts compressed (33 bytes, 0x02/0x03), uncompressed (65 bytes, 0x04) and
+/// hybrid (65 bytes, 0x06/0x07) encodings. Hybrid keys carry the y parity in their
+/// prefix, which must agree with the y-coordinate.
+pub(crate) fn parse_public_key(bytes: &[u8]) -> Result<VerifyingKey, ECDSAError> {
+    match (bytes.len(), bytes.first()) {
+        (33, Some(0x02 | 0x03)) | (65, Some(0x04)) => {
+            VerifyingKey::from_sec1_bytes(bytes).map_err(|_| ECDSAError::InvalidPublicKey)
+        }
+        (65, Some(&prefix @ (0x06 | 0x07))) => {
+            let y_is_odd = bytes[64] & 1 == 1;
+            if y_is_odd != (prefix == 0x07) {
+                return Err(ECDSAError::InvalidPublicKey);
+            }
+            let mut uncompressed = [0u8; 65];
+            uncompressed.copy_from_slice(bytes);
+            uncompressed[0] = 0x04;
+            VerifyingKey::from_sec1_bytes(&uncompressed).map_err(|_| ECDSAError::InvalidPublicKey)
+        }
+        _ => Err(ECDSAError::InvalidPublicKey),
+    }
+}
+
+/// Parses a hex encoded SEC1 public key (see `parse_public_key`).
+pub(crate) fn parse_public_key_hex(public_key: &str) -> Result<VerifyingKey, ECDSAError> {
+    let bytes = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    parse_public_key(&bytes)
+}
+
+/// A validated secp256k1 public key.
+/// Parsing and curve-point validation happen once, so a single key can verify many messages cheaply.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct PublicKey {
+    inner: VerifyingKey,
+}
+
+#[wasm_bindgen]
+impl PublicKey {
+    /// Parses a hex encoded SEC1 public key (compressed, uncompressed or hybrid).
+    #[wasm_bindgen(constructor)]
+    pub fn new(public_key: &str) -> Result<PublicKey, ECDSAError> {
+        parse_public_key_hex(public_key).map(|inner| PublicKey { inner })
+    }
+
+    /// Parses raw SEC1 public key bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, ECDSAError> {
+        parse_public_key(bytes).map(|inner| PublicKey { inner })
+    }
+
+    /// Parses a secp256k1 JWK (`{"kty":"EC","crv":"secp256k1","x":...,"y":...}`).
+    ///
+    /// # Errors:
+    /// * Returns an error if the JWK is malformed, uses another curve or is not on the curve.
+    pub fn from_jwk(jwk: &str) -> Result<PublicKey, ECDSAError> {
+        jwk::secp256k1_jwk(jwk).map(|inner| PublicKey { inner })
+    }
+
+    /// The key as a JWK JSON string.
+    pub fn to_jwk(&self) -> String {
+        jwk::to_jwk(JwkCurve::Secp256k1, self.inner.to_encoded_point(false).as_bytes())
+    }
+
+    /// The 33-byte SEC1 compressed encoding, as a hex string.
+    pub fn compressed(&self) -> String {
+        hex::encode(self.inner.to_bytes())
+    }
+
+    /// The 65-byte SEC1 uncompressed encoding, as a hex string.
+    pub fn uncompressed(&self) -> String {
+        hex::encode(self.inner.to_encoded_point(false).as_bytes())
+    }
+
+    /// Verifies a DER or compact hex signature over the SHA-256 hash of `message`.
+    pub fn verify(&self, message: &str, signature: &str) -> Result<bool, ECDSAError> {
+        ECDSAVerifier::default().verify_with_key(message, signature, self)
+    }
+
+    /// Verifies a DER or compact hex signature against a 32-byte hex digest.
+    ///
+    /// # Errors:
+    /// * Returns an error if the digest is not 32 bytes or the signature is malformed.
+    pub fn verify_prehashed(&self, digest: &str, signature: &str) -> Result<bool, ECDSAError> {
+        let digest_bytes = hex::decode(digest).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        if digest_bytes.len() != 32 {
+            return Err(ECDSAError::InvalidDigestLength);
+        }
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let sig = signature::parse_signature(&signature_bytes, SignatureFormat::Auto)?;
+        Ok(verify_digest(&self.inner, &digest_bytes, &sig))
+    }
+}
+
+#[wasm_bindgen]
+impl ECDSAVerifier {
+    /// Verifies a signature against a pre-parsed `PublicKey`, hashing the message with
+    /// the verifier's hash algorithm.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The DER or compact signature, provided as a hex string.
+    /// * `public_key` - A `PublicKey` handle, reused across calls.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    pub fn verify_with_key(&self, message: &str, signature: &str, public_key: &PublicKey) -> Result<bool, ECDSAError> {
+        let msg_hash = hash::digest(self.hash_algorithm(), message.as_bytes());
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let sig = self.parse_signature(&signature_bytes, SignatureFormat::Auto)?;
+        Ok(self.verify_with_policy(&public_key.inner, &msg_hash, &sig))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPRESSED: &str = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+    fn uncompressed() -> Vec<u8> {
+        let key = parse_public_key_hex(COMPRESSED).unwrap();
+        key.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_parse_all_encodings() {
+        let expected = parse_public_key_hex(COMPRESSED).unwrap();
+
+        let mut bytes = uncompressed();
+        assert_eq!(parse_public_key(&bytes).unwrap(), expected);
+
+        // The compressed prefix 0x03 means y is odd, so the hybrid prefix is 0x07
+        bytes[0] = 0x07;
+        assert_eq!(parse_public_key(&bytes).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_reject_hybrid_parity_mismatch() {
+        let mut bytes = uncompressed();
+        bytes[0] = 0x06;
+        assert!(parse_public_key(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_public_key_handle() {
+        let signature = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let key = PublicKey::new(COMPRESSED).unwrap();
+        assert_eq!(key.compressed(), COMPRESSED);
+        assert_eq!(PublicKey::from_bytes(&uncompressed()).unwrap().compressed(), COMPRESSED);
+
+        assert!(key.verify("Test message", signature).unwrap());
+        assert!(!key.verify("Other message", signature).unwrap());
+        assert!(ECDSAVerifier::default().verify_with_key("Test message", signature, &key).unwrap());
+        assert!(PublicKey::new("02").is_err());
+    }
+
+    #[test]
+    fn test_jwk_round_trip() {
+        let key = PublicKey::new(COMPRESSED).unwrap();
+        let jwk = key.to_jwk();
+        assert!(jwk.contains(r#""crv":"secp256k1""#));
+        assert_eq!(PublicKey::from_jwk(&jwk).unwrap().compressed(), COMPRESSED);
+        #[cfg(feature = "p256")]
+        assert!(PublicKey::from_jwk(&jwk.replace("secp256k1", "P-256")).is_err());
+    }
+
+    #[test]
+    fn test_reject_bad_lengths() {
+        let bytes = uncompressed();
+        assert!(parse_public_key(&bytes[..64]).is_err());
+        assert!(parse_public_key(&bytes[1..34]).is_err());
+        assert!(parse_public_key(&[]).is_err());
+    }
+}
@@ -0,0 +1,512 @@
+//! DER decoding of the ASN.1 key containers: SubjectPublicKeyInfo (RFC 5280), PKCS#8
+//! (RFC 5208) and SEC1 `ECPrivateKey` (RFC 5915), and encoding of SubjectPublicKeyInfo.
+
+use k256::ecdsa::Signature;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::ECDSAError;
+
+pub(crate) const TAG_INTEGER: u8 = 0x02;
+pub(crate) const TAG_BIT_STRING: u8 = 0x03;
+pub(crate) const TAG_OCTET_STRING: u8 = 0x04;
+pub(crate) const TAG_OID: u8 = 0x06;
+pub(crate) const TAG_SEQUENCE: u8 = 0x30;
+
+/// `id-ecPublicKey` (1.2.840.10045.2.1)
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// `id-Ed25519` (1.3.101.112)
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+const OID_SECP256K1: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+const OID_P256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const OID_P384: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+const OID_P521: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x23];
+
+/// A reader over a sequence of DER elements.
+#[derive(Clone, Copy)]
+pub(crate) struct Der<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Der<'a> {
+        Der { bytes }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub(crate) fn peek_tag(&self) -> Option<u8> {
+        self.bytes.first().copied()
+    }
+
+    /// Reads the next element, returning its tag, its content and its complete encoding.
+    pub(crate) fn read_element(&mut self) -> Result<(u8, &'a [u8], &'a [u8]), ECDSAError> {
+        let (&tag, rest) = self.bytes.split_first().ok_or(ECDSAError::InvalidKeyEncoding)?;
+        // Multi-byte tags never occur in the structures read here
+        if tag & 0x1f == 0x1f {
+            return Err(ECDSAError::InvalidKeyEncoding);
+        }
+        let (&first, rest) = rest.split_first().ok_or(ECDSAError::InvalidKeyEncoding)?;
+        let (length, rest) = match first {
+            0..=0x7f => (first as usize, rest),
+            0x81..=0x84 => {
+                let count = (first & 0x7f) as usize;
+                let bytes = rest.get(..count).ok_or(ECDSAError::InvalidKeyEncoding)?;
+                let length = bytes.iter().fold(0usize, |length, &b| (length << 8) | b as usize);
+                // DER requires the shortest length encoding
+                if bytes[0] == 0 || length < 0x80 {
+                    return Err(ECDSAError::InvalidKeyEncoding);
+                }
+                (length, &rest[count..])
+            }
+            _ => return Err(ECDSAError::InvalidKeyEncoding),
+        };
+        let content = rest.get(..length).ok_or(ECDSAError::InvalidKeyEncoding)?;
+        let header_length = self.bytes.len() - rest.len();
+        let element = &self.bytes[..header_length + length];
+        self.bytes = &rest[length..];
+        Ok((tag, content, element))
+    }
+
+    /// Reads the next element, which must carry `tag`, and returns its content.
+    pub(crate) fn read(&mut self, tag: u8) -> Result<&'a [u8], ECDSAError> {
+        match self.read_element()? {
+            (actual, content, _) if actual == tag => Ok(content),
+            _ => Err(ECDSAError::InvalidKeyEncoding),
+        }
+    }
+
+    /// Reads a SEQUENCE and returns a reader over its members.
+    pub(crate) fn sequence(&mut self) -> Result<Der<'a>, ECDSAError> {
+        self.read(TAG_SEQUENCE).map(Der::new)
+    }
+
+    /// Reads a small non-negative INTEGER, such as a version number.
+    pub(crate) fn small_integer(&mut self) -> Result<u64, ECDSAError> {
+        match self.read(TAG_INTEGER)? {
+            [value] if *value < 0x80 => Ok(*value as u64),
+            _ => Err(ECDSAError::InvalidKeyEncoding),
+        }
+    }
+
+    /// Reads a BIT STRING that holds whole bytes.
+    pub(crate) fn bit_string(&mut self) -> Result<&'a [u8], ECDSAError> {
+        match self.read(TAG_BIT_STRING)?.split_first() {
+            Some((0, bits)) => Ok(bits),
+            _ => Err(ECDSAError::InvalidKeyEncoding),
+        }
+    }
+
+    /// Reads an optional context-specific constructed field `[number]`.
+    pub(crate) fn optional_explicit(&mut self, number: u8) -> Result<Option<Der<'a>>, ECDSAError> {
+        if self.peek_tag() != Some(0xa0 | number) {
+            return Ok(None);
+        }
+        self.read(0xa0 | number).map(|content| Some(Der::new(content)))
+    }
+
+    /// Fails unless every element has been read.
+    pub(crate) fn finish(&self) -> Result<(), ECDSAError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(ECDSAError::InvalidKeyEncoding)
+        }
+    }
+}
+
+/// The key types recognised in SPKI and PKCS#8 algorithm identifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum KeyAlgorithm {
+    Secp256k1,
+    P256,
+    P384,
+    P521,
+    Ed25519,
+}
+
+impl KeyAlgorithm {
+    fn from_curve_oid(oid: &[u8]) -> Result<KeyAlgorithm, ECDSAError> {
+        match oid {
+            OID_SECP256K1 => Ok(KeyAlgorithm::Secp256k1),
+            OID_P256 => Ok(KeyAlgorithm::P256),
+            OID_P384 => Ok(KeyAlgorithm::P384),
+            OID_P521 => Ok(KeyAlgorithm::P521),
+            _ => Err(ECDSAError::UnsupportedCurve),
+        }
+    }
+
+    /// Looks up an algorithm by the name `name()` returns.
+    pub(crate) fn from_name(name: &str) -> Result<KeyAlgorithm, ECDSAError> {
+        [KeyAlgorithm::Secp256k1, KeyAlgorithm::P256, KeyAlgorithm::P384, KeyAlgorithm::P521, KeyAlgorithm::Ed25519]
+            .into_iter()
+            .find(|algorithm| algorithm.name() == name)
+            .ok_or(ECDSAError::UnsupportedCurve)
+    }
+
+    /// The curve's conventional name, as used in JWK `crv` values.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            KeyAlgorithm::Secp256k1 => "secp256k1",
+            KeyAlgorithm::P256 => "P-256",
+            KeyAlgorithm::P384 => "P-384",
+            KeyAlgorithm::P521 => "P-521",
+            KeyAlgorithm::Ed25519 => "Ed25519",
+        }
+    }
+
+    /// The dotted object identifier of the named curve, or of the algorithm for Ed25519.
+    pub(crate) fn oid(self) -> &'static str {
+        match self {
+            KeyAlgorithm::Secp256k1 => "1.3.132.0.10",
+            KeyAlgorithm::P256 => "1.2.840.10045.3.1.7",
+            KeyAlgorithm::P384 => "1.3.132.0.34",
+            KeyAlgorithm::P521 => "1.3.132.0.35",
+            KeyAlgorithm::Ed25519 => "1.3.101.112",
+        }
+    }
+
+    /// The DER content of the named curve OID, or `None` for Ed25519.
+    fn curve_oid(self) -> Option<&'static [u8]> {
+        match self {
+            KeyAlgorithm::Secp256k1 => Some(OID_SECP256K1),
+            KeyAlgorithm::P256 => Some(OID_P256),
+            KeyAlgorithm::P384 => Some(OID_P384),
+            KeyAlgorithm::P521 => Some(OID_P521),
+            KeyAlgorithm::Ed25519 => None,
+        }
+    }
+
+    /// Length of a private scalar or field element.
+    fn scalar_length(self) -> usize {
+        match self {
+            KeyAlgorithm::Secp256k1 | KeyAlgorithm::P256 | KeyAlgorithm::Ed25519 => 32,
+            KeyAlgorithm::P384 => 48,
+            KeyAlgorithm::P521 => 66,
+        }
+    }
+
+    /// Checks that `point` is a valid public key for the algorithm.
+    pub(crate) fn check_public_key(self, point: &[u8]) -> Result<(), ECDSAError> {
+        let valid = match self {
+            KeyAlgorithm::Secp256k1 => crate::keys::parse_public_key(point).is_ok(),
+            #[cfg(feature = "p256")]
+            KeyAlgorithm::P256 => crate::p256::parse_public_key(point).is_ok(),
+            #[cfg(feature = "p384")]
+            KeyAlgorithm::P384 => crate::p384::parse_public_key(point).is_ok(),
+            #[cfg(feature = "p521")]
+            KeyAlgorithm::P521 => crate::p521::parse_public_key(point).is_ok(),
+            KeyAlgorithm::Ed25519 => point.len() == 32,
+            #[allow(unreachable_patterns)]
+            _ => return Err(ECDSAError::UnsupportedCurve),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ECDSAError::InvalidPublicKey)
+        }
+    }
+
+    /// Re-encodes a public key with an uncompressed SEC1 point. Ed25519 keys are unchanged.
+    pub(crate) fn uncompressed(self, point: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+        match self {
+            KeyAlgorithm::Secp256k1 => Ok(crate::keys::parse_public_key(point)?.to_encoded_point(false).as_bytes().to_vec()),
+            #[cfg(feature = "p256")]
+            KeyAlgorithm::P256 => Ok(crate::p256::encode_uncompressed(&crate::p256::parse_public_key(point)?)),
+            #[cfg(feature = "p384")]
+            KeyAlgorithm::P384 => Ok(crate::p384::encode_uncompressed(&crate::p384::parse_public_key(point)?)),
+            #[cfg(feature = "p521")]
+            KeyAlgorithm::P521 => Ok(crate::p521::encode_uncompressed(&crate::p521::parse_public_key(point)?)),
+            KeyAlgorithm::Ed25519 => self.check_public_key(point).map(|()| point.to_vec()),
+            #[allow(unreachable_patterns)]
+            _ => Err(ECDSAError::UnsupportedCurve),
+        }
+    }
+
+    /// Hashes `data` with the digest ECDSA signatures on the curve conventionally use, as in
+    /// JOSE and COSE: SHA-256, SHA-384 for P-384 and SHA-512 for P-521. Ed25519 keys get SHA-256,
+    /// for protocols that pre-hash whatever the key type.
+    pub(crate) fn hash(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            KeyAlgorithm::P384 => Sha384::digest(data).to_vec(),
+            KeyAlgorithm::P521 => Sha512::digest(data).to_vec(),
+            _ => Sha256::digest(data).to_vec(),
+        }
+    }
+
+    /// Verifies a raw signature over `message`: a fixed-size `r || s` over its `hash` for the
+    /// ECDSA curves, which need not be low-S, or a 64-byte Ed25519 signature.
+    ///
+    /// # Errors:
+    /// * `InvalidPublicKey` if the key is not valid, or `UnsupportedCurve` if the curve is not enabled.
+    pub(crate) fn verify_raw(self, public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+        if self != KeyAlgorithm::Ed25519 && signature.len() != 2 * self.scalar_length() {
+            return Ok(false);
+        }
+        let digest = self.hash(message);
+        match self {
+            KeyAlgorithm::Secp256k1 => {
+                let key = crate::keys::parse_public_key(public_key)?;
+                let Ok(sig) = Signature::try_from(signature) else {
+                    return Ok(false);
+                };
+                Ok(crate::verify_digest(&key, &digest, &sig.normalize_s().unwrap_or(sig)))
+            }
+            #[cfg(feature = "p256")]
+            KeyAlgorithm::P256 => crate::p256::verify_bytes(&digest, signature, public_key),
+            #[cfg(feature = "p384")]
+            KeyAlgorithm::P384 => crate::p384::verify_bytes(&digest, signature, public_key),
+            #[cfg(feature = "p521")]
+            KeyAlgorithm::P521 => crate::p521::verify_bytes(&digest, signature, public_key),
+            #[cfg(feature = "ed25519")]
+            KeyAlgorithm::Ed25519 => crate::ed25519::verify_ed25519(public_key, message, signature),
+            #[allow(unreachable_patterns)]
+            _ => Err(ECDSAError::UnsupportedCurve),
+        }
+    }
+
+    /// Converts a DER `ECDSA-Sig-Value` into the fixed-size `r || s` form `verify_raw` takes.
+    /// Returns `None` if it is malformed or an integer is too long for the curve.
+    pub(crate) fn raw_signature(self, der: &[u8]) -> Option<Vec<u8>> {
+        let length = self.scalar_length();
+        let mut raw = vec![0u8; 2 * length];
+        let mut outer = Der::new(der);
+        let mut integers = outer.sequence().ok()?;
+        for half in raw.chunks_exact_mut(length) {
+            let integer = integers.read(TAG_INTEGER).ok()?;
+            let integer = &integer[integer.iter().position(|&b| b != 0).unwrap_or(integer.len())..];
+            half.get_mut(length.checked_sub(integer.len())?..)?.copy_from_slice(integer);
+        }
+        integers.finish().ok()?;
+        outer.finish().ok()?;
+        Some(raw)
+    }
+}
+
+/// Reads an `AlgorithmIdentifier` for an EC or Ed25519 key.
+fn algorithm_identifier(der: &mut Der) -> Result<KeyAlgorithm, ECDSAError> {
+    let mut identifier = der.sequence()?;
+    let algorithm = match identifier.read(TAG_OID)? {
+        // Explicit curve parameters (a SEQUENCE in place of the named curve) are not supported
+        OID_EC_PUBLIC_KEY if identifier.peek_tag() == Some(TAG_SEQUENCE) => return Err(ECDSAError::UnsupportedCurve),
+        OID_EC_PUBLIC_KEY => KeyAlgorithm::from_curve_oid(identifier.read(TAG_OID)?)?,
+        // RFC 8410: the parameters are absent
+        OID_ED25519 => KeyAlgorithm::Ed25519,
+        _ => return Err(ECDSAError::UnsupportedCurve),
+    };
+    identifier.finish()?;
+    Ok(algorithm)
+}
+
+/// A parsed SubjectPublicKeyInfo.
+#[derive(Debug)]
+pub(crate) struct SubjectPublicKeyInfo<'a> {
+    pub(crate) algorithm: KeyAlgorithm,
+    /// The SEC1 point, or the 32 raw bytes of an Ed25519 key, checked for validity
+    pub(crate) public_key: &'a [u8],
+}
+
+/// Parses a DER SubjectPublicKeyInfo into its key algorithm and public key.
+///
+/// Only named curves are supported: explicit parameters and unknown curves fail with
+/// `UnsupportedCurve`, and a point that is not on its curve with `InvalidPublicKey`.
+pub(crate) fn parse_spki(der: &[u8]) -> Result<SubjectPublicKeyInfo<'_>, ECDSAError> {
+    let mut outer = Der::new(der);
+    let mut spki = outer.sequence()?;
+    outer.finish()?;
+    let algorithm = algorithm_identifier(&mut spki)?;
+    let public_key = spki.bit_string()?;
+    spki.finish()?;
+    algorithm.check_public_key(public_key)?;
+    Ok(SubjectPublicKeyInfo { algorithm, public_key })
+}
+
+/// Writes a DER element with a definite length.
+pub(crate) fn write_element(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut element = vec![tag];
+    match content.len() {
+        length @ 0..=0x7f => element.push(length as u8),
+        length => {
+            let bytes = length.to_be_bytes();
+            let bytes = &bytes[bytes.iter().position(|&b| b != 0).unwrap()..];
+            element.push(0x80 | bytes.len() as u8);
+            element.extend_from_slice(bytes);
+        }
+    }
+    element.extend_from_slice(content);
+    element
+}
+
+/// Encodes a SubjectPublicKeyInfo for a public key that `check_public_key` accepts.
+pub(crate) fn encode_spki(algorithm: KeyAlgorithm, public_key: &[u8]) -> Vec<u8> {
+    let identifier = match algorithm.curve_oid() {
+        Some(curve) => [write_element(TAG_OID, OID_EC_PUBLIC_KEY), write_element(TAG_OID, curve)].concat(),
+        None => write_element(TAG_OID, OID_ED25519),
+    };
+    let key = [&[0][..], public_key].concat();
+    write_element(TAG_SEQUENCE, &[write_element(TAG_SEQUENCE, &identifier), write_element(TAG_BIT_STRING, &key)].concat())
+}
+
+/// Parses a DER SubjectPublicKeyInfo that must hold a key for `expected`.
+///
+/// # Errors:
+/// * Returns `CurveMismatch` for a well-formed key on another curve, besides the errors of
+///   `parse_spki`.
+pub(crate) fn parse_spki_for(der: &[u8], expected: KeyAlgorithm) -> Result<&[u8], ECDSAError> {
+    let spki = parse_spki(der)?;
+    if spki.algorithm != expected {
+        return Err(ECDSAError::CurveMismatch);
+    }
+    Ok(spki.public_key)
+}
+
+/// A private key read from PKCS#8 or SEC1.
+pub(crate) struct PrivateKeyInfo<'a> {
+    pub(crate) algorithm: KeyAlgorithm,
+    /// The big-endian scalar, or the 32-byte seed of an Ed25519 key
+    pub(crate) private_key: &'a [u8],
+    /// The public key embedded alongside, if any
+    pub(crate) public_key: Option<&'a [u8]>,
+}
+
+/// Parses the body of a SEC1 `ECPrivateKey`. The curve comes from its parameters or, inside
+/// PKCS#8, from the enclosing algorithm identifier (`known`); if both are present they must agree.
+fn parse_ec_private_key(der: &[u8], known: Option<KeyAlgorithm>) -> Result<PrivateKeyInfo<'_>, ECDSAError> {
+    let mut outer = Der::new(der);
+    let mut key = outer.sequence()?;
+    outer.finish()?;
+    if key.small_integer()? != 1 {
+        return Err(ECDSAError::InvalidKeyEncoding);
+    }
+    let private_key = key.read(TAG_OCTET_STRING)?;
+
+    let parameters = match key.optional_explicit(0)? {
+        Some(mut parameters) => {
+            let algorithm = KeyAlgorithm::from_curve_oid(parameters.read(TAG_OID)?)?;
+            parameters.finish()?;
+            Some(algorithm)
+        }
+        None => None,
+    };
+    let algorithm = match (known, parameters) {
+        (Some(known), Some(parameters)) if known != parameters => return Err(ECDSAError::CurveMismatch),
+        (Some(algorithm), _) | (None, Some(algorithm)) => algorithm,
+        (None, None) => return Err(ECDSAError::InvalidKeyEncoding),
+    };
+    let public_key = match key.optional_explicit(1)? {
+        Some(mut public_key) => {
+            let point = public_key.bit_string()?;
+            public_key.finish()?;
+            Some(point)
+        }
+        None => None,
+    };
+    key.finish()?;
+
+    if private_key.len() != algorithm.scalar_length() {
+        return Err(ECDSAError::InvalidPrivateKey);
+    }
+    Ok(PrivateKeyInfo { algorithm, private_key, public_key })
+}
+
+/// Parses a DER PKCS#8 `PrivateKeyInfo` (or v2 `OneAsymmetricKey`).
+pub(crate) fn parse_pkcs8(der: &[u8]) -> Result<PrivateKeyInfo<'_>, ECDSAError> {
+    let mut outer = Der::new(der);
+    let mut info = outer.sequence()?;
+    outer.finish()?;
+    if info.small_integer()? > 1 {
+        return Err(ECDSAError::InvalidKeyEncoding);
+    }
+    let algorithm = algorithm_identifier(&mut info)?;
+    let private_key = info.read(TAG_OCTET_STRING)?;
+    // Optional attributes [0] and, in v2, the public key [1] may follow; they are not needed
+
+    if algorithm == KeyAlgorithm::Ed25519 {
+        // RFC 8410: the seed is itself wrapped in an OCTET STRING
+        let mut inner = Der::new(private_key);
+        let seed = inner.read(TAG_OCTET_STRING)?;
+        inner.finish()?;
+        if seed.len() != 32 {
+            return Err(ECDSAError::InvalidPrivateKey);
+        }
+        return Ok(PrivateKeyInfo { algorithm, private_key: seed, public_key: None });
+    }
+    parse_ec_private_key(private_key, Some(algorithm))
+}
+
+/// Parses a DER SEC1 `ECPrivateKey`, which must name its curve.
+pub(crate) fn parse_sec1(der: &[u8]) -> Result<PrivateKeyInfo<'_>, ECDSAError> {
+    parse_ec_private_key(der, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_der_lengths() {
+        let mut der = Der::new(&[0x04, 0x81, 0x80]);
+        assert!(der.read(TAG_OCTET_STRING).is_err());
+        // The long form is not allowed for short lengths
+        let mut der = Der::new(&[0x04, 0x81, 0x01, 0xff]);
+        assert!(der.read(TAG_OCTET_STRING).is_err());
+
+        let mut bytes = vec![0x04, 0x81, 0x80];
+        bytes.extend([7; 0x80]);
+        let mut der = Der::new(&bytes);
+        assert_eq!(der.read(TAG_OCTET_STRING).unwrap(), [7; 0x80]);
+        assert!(der.is_empty());
+    }
+
+    #[test]
+    fn test_algorithm_identifiers() {
+        let spki = hex::decode(
+            "3056301006072a8648ce3d020106052b8104000a034200\
+             0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        )
+        .unwrap();
+        let parsed = parse_spki(&spki).unwrap();
+        assert_eq!(parsed.algorithm, KeyAlgorithm::Secp256k1);
+        assert_eq!(parsed.public_key.len(), 65);
+        assert_eq!(parse_spki_for(&spki, KeyAlgorithm::Secp256k1).unwrap(), parsed.public_key);
+        assert!(matches!(parse_spki_for(&spki, KeyAlgorithm::P256), Err(ECDSAError::CurveMismatch)));
+
+        // Trailing data after the SPKI
+        let mut trailing = spki.clone();
+        trailing.push(0);
+        assert!(parse_spki(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unsupported_curves() {
+        // brainpoolP256r1 (1.3.36.3.3.2.8.1.1.7)
+        let brainpool = hex::decode("301a301406072a8648ce3d020106092b2403030208010107030200ff").unwrap();
+        assert!(matches!(parse_spki(&brainpool), Err(ECDSAError::UnsupportedCurve)));
+        // Explicit parameters in place of a named curve
+        let explicit = hex::decode("3014300e06072a8648ce3d02013003020101030200ff").unwrap();
+        assert!(matches!(parse_spki(&explicit), Err(ECDSAError::UnsupportedCurve)));
+        // RSA (1.2.840.113549.1.1.1)
+        let rsa = hex::decode("3012300d06092a864886f70d0101010500030100").unwrap();
+        assert!(matches!(parse_spki(&rsa), Err(ECDSAError::UnsupportedCurve)));
+
+        let names = ["secp256k1", "P-256", "P-384", "P-521", "Ed25519"];
+        assert!(names.iter().all(|name| KeyAlgorithm::from_name(name).unwrap().name() == *name));
+        assert!(KeyAlgorithm::from_name("P-192").is_err());
+    }
+
+    #[test]
+    fn test_raw_signature() {
+        // Leading zeros are stripped or added to fit the scalar length
+        let der = hex::decode("3007020200ff020101").unwrap();
+        let raw = KeyAlgorithm::P256.raw_signature(&der).unwrap();
+        assert_eq!((raw.len(), raw[31], raw[63]), (64, 0xff, 1));
+        assert_eq!(KeyAlgorithm::P384.raw_signature(&der).map(|raw| raw.len()), Some(96));
+
+        let long = [&[0x30, 0x26, 0x02, 0x21][..], &[1; 33], &[0x02, 0x01, 0x01]].concat();
+        assert_eq!(KeyAlgorithm::P256.raw_signature(&long), None);
+        assert_eq!(KeyAlgorithm::P256.raw_signature(&der[..8]), None);
+    }
+}
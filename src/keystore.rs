@@ -0,0 +1,275 @@
+//! Web3 Secret Storage (Ethereum keystore V3) JSON wallets.
+//!
+//! The private key is encrypted with AES-128-CTR under the first half of a scrypt or
+//! PBKDF2-HMAC-SHA256 derived key; the second half authenticates the ciphertext through
+//! `mac = keccak256(derived_key[16..32] || ciphertext)`.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::SigningKey;
+
+use crate::aes::aes_128_ctr;
+use crate::constant_time::constant_time_eq;
+use crate::ethereum::address_bytes;
+use crate::json::{self, Value};
+use crate::kdf::pbkdf2_hmac_sha256;
+use crate::ethereum::keccak256;
+use crate::password::{scrypt, PasswordKdfParams};
+use crate::random::fill_random;
+use crate::{ECDSAError, ECDSASigner};
+
+/// scrypt cost used by geth's standard keystores.
+const DEFAULT_SCRYPT_N: u32 = 1 << 18;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const DERIVED_KEY_LENGTH: usize = 32;
+
+fn hex_field(value: Option<&Value>) -> Result<Vec<u8>, ECDSAError> {
+    let text = value.and_then(Value::as_str).ok_or(ECDSAError::InvalidKeystore)?;
+    hex::decode(text.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidKeystore)
+}
+
+fn u32_field(params: &Value, key: &str) -> Result<u32, ECDSAError> {
+    params
+        .get(key)
+        .and_then(Value::as_u64)
+        .and_then(|value| u32::try_from(value).ok())
+        .ok_or(ECDSAError::InvalidKeystore)
+}
+
+/// Runs the key derivation function described by a keystore's `kdf` and `kdfparams`.
+fn derive_key(crypto: &Value, password: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let params = crypto.get("kdfparams").ok_or(ECDSAError::InvalidKeystore)?;
+    let salt = hex_field(params.get("salt"))?;
+    let length = u32_field(params, "dklen")? as usize;
+    if length < DERIVED_KEY_LENGTH {
+        return Err(ECDSAError::InvalidKeystore);
+    }
+
+    match crypto.get("kdf").and_then(Value::as_str) {
+        Some("scrypt") => {
+            let (n, r, p) = (u32_field(params, "n")?, u32_field(params, "r")?, u32_field(params, "p")?);
+            PasswordKdfParams::scrypt(n, r, p, length).map_err(|_| ECDSAError::InvalidKeystore)?;
+            scrypt(password, &salt, n, r, p, length).map_err(|_| ECDSAError::InvalidKeystore)
+        }
+        Some("pbkdf2") => {
+            if params.get("prf").and_then(Value::as_str) != Some("hmac-sha256") {
+                return Err(ECDSAError::InvalidKeystore);
+            }
+            let iterations = u32_field(params, "c")?;
+            if iterations == 0 {
+                return Err(ECDSAError::InvalidKeystore);
+            }
+            Ok(pbkdf2_hmac_sha256(password, &salt, iterations, length))
+        }
+        _ => Err(ECDSAError::InvalidKeystore),
+    }
+}
+
+/// The AES-128 key: the first half of the derived key.
+fn cipher_key(derived_key: &[u8]) -> Result<&[u8; 16], ECDSAError> {
+    derived_key.get(..16).and_then(|key| key.try_into().ok()).ok_or(ECDSAError::InvalidKeystore)
+}
+
+fn keystore_mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut input = derived_key[16..32].to_vec();
+    input.extend_from_slice(ciphertext);
+    keccak256(&input)
+}
+
+/// Formats 16 random bytes as an RFC 4122 version 4 UUID.
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// Decrypts a keystore document into the raw private key.
+pub(crate) fn decrypt_private_key(keystore: &str, password: &[u8]) -> Result<SigningKey, ECDSAError> {
+    let document = json::parse(keystore).map_err(|_| ECDSAError::InvalidKeystore)?;
+    if document.get("version").and_then(Value::as_u64) != Some(3) {
+        return Err(ECDSAError::InvalidKeystore);
+    }
+    // Some wallets (e.g. MyEtherWallet) capitalize the "crypto" member
+    let crypto = document
+        .get("crypto")
+        .or_else(|| document.get("Crypto"))
+        .ok_or(ECDSAError::InvalidKeystore)?;
+    if crypto.get("cipher").and_then(Value::as_str) != Some("aes-128-ctr") {
+        return Err(ECDSAError::InvalidKeystore);
+    }
+
+    let iv: [u8; 16] = hex_field(crypto.get("cipherparams").and_then(|p| p.get("iv")))?
+        .try_into()
+        .map_err(|_| ECDSAError::InvalidKeystore)?;
+    let ciphertext = hex_field(crypto.get("ciphertext"))?;
+    let mac = hex_field(crypto.get("mac"))?;
+
+    let derived_key = derive_key(crypto, password)?;
+    let expected = keystore_mac(&derived_key, &ciphertext);
+    if !constant_time_eq(&expected, &mac) {
+        return Err(ECDSAError::InvalidPassword);
+    }
+
+    let private_key = aes_128_ctr(cipher_key(&derived_key)?, &iv, &ciphertext);
+    if private_key.len() != 32 {
+        return Err(ECDSAError::InvalidKeystore);
+    }
+    SigningKey::from_bytes(&private_key).map_err(|_| ECDSAError::InvalidPrivateKey)
+}
+
+/// Encrypts a private key into a scrypt keystore with caller-supplied randomness.
+fn encrypt_with(
+    signing_key: &SigningKey,
+    password: &[u8],
+    n: u32,
+    salt: &[u8; 32],
+    iv: &[u8; 16],
+    id: [u8; 16],
+) -> Result<String, ECDSAError> {
+    PasswordKdfParams::scrypt(n, SCRYPT_R, SCRYPT_P, DERIVED_KEY_LENGTH)?;
+    let derived_key = scrypt(password, salt, n, SCRYPT_R, SCRYPT_P, DERIVED_KEY_LENGTH)?;
+    let ciphertext = aes_128_ctr(cipher_key(&derived_key)?, iv, &signing_key.to_bytes());
+    let mac = keystore_mac(&derived_key, &ciphertext);
+    let address = address_bytes(&signing_key.verifying_key());
+
+    Ok(format!(
+        concat!(
+            r#"{{"address":"{}","crypto":{{"cipher":"aes-128-ctr","cipherparams":{{"iv":"{}"}},"#,
+            r#""ciphertext":"{}","kdf":"scrypt","kdfparams":{{"dklen":{},"n":{},"p":{},"r":{},"salt":"{}"}},"#,
+            r#""mac":"{}"}},"id":"{}","version":3}}"#
+        ),
+        hex::encode(address),
+        hex::encode(iv),
+        hex::encode(ciphertext),
+        DERIVED_KEY_LENGTH,
+        n,
+        SCRYPT_P,
+        SCRYPT_R,
+        hex::encode(salt),
+        hex::encode(mac),
+        uuid_v4(id),
+    ))
+}
+
+/// Import and export of Ethereum keystore V3 JSON wallets.
+#[wasm_bindgen]
+pub struct Keystore;
+
+#[wasm_bindgen]
+impl Keystore {
+    /// Decrypts a keystore V3 JSON document.
+    ///
+    /// # Arguments:
+    /// * `keystore` - The keystore JSON text (scrypt or pbkdf2 KDF, aes-128-ctr cipher).
+    /// * `password` - The keystore password. Its UTF-8 bytes are used as-is, without
+    ///   Unicode normalization.
+    ///
+    /// # Returns:
+    /// * An `ECDSASigner` holding the decrypted key.
+    ///
+    /// # Errors:
+    /// * Returns an error if the document is malformed or unsupported, or the password is wrong.
+    pub fn decrypt(keystore: &str, password: &str) -> Result<ECDSASigner, ECDSAError> {
+        let signing_key = decrypt_private_key(keystore, password.as_bytes())?;
+        Ok(ECDSASigner::from_signing_key(signing_key))
+    }
+
+    /// Encrypts a private key into a keystore V3 JSON document using scrypt.
+    ///
+    /// # Arguments:
+    /// * `private_key` - The 32-byte private key, provided as a hex string.
+    /// * `password` - The password to encrypt with.
+    /// * `scrypt_n` - The scrypt cost; defaults to geth's standard 262144. Decryption needs
+    ///   `128 * 8 * n` bytes of memory, so lighter values (e.g. 4096) suit constrained devices.
+    ///
+    /// # Returns:
+    /// * The keystore JSON text.
+    ///
+    /// # Errors:
+    /// * Returns an error if the key or cost is invalid, or no randomness is available.
+    pub fn encrypt(private_key: &str, password: &str, scrypt_n: Option<u32>) -> Result<String, ECDSAError> {
+        let key_bytes = hex::decode(private_key).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+        if key_bytes.len() != 32 {
+            return Err(ECDSAError::InvalidPrivateKey);
+        }
+        let signing_key = SigningKey::from_bytes(&key_bytes).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+
+        let mut random = [0u8; 64];
+        fill_random(&mut random).map_err(|_| ECDSAError::EncryptionFailed)?;
+        let salt: [u8; 32] = random[..32].try_into().unwrap();
+        let iv: [u8; 16] = random[32..48].try_into().unwrap();
+        let id: [u8; 16] = random[48..].try_into().unwrap();
+
+        let n = scrypt_n.unwrap_or(DEFAULT_SCRYPT_N);
+        encrypt_with(&signing_key, password.as_bytes(), n, &salt, &iv, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // PBKDF2 test vector from the Web3 Secret Storage definition
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+    const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn test_decrypt_pbkdf2_vector() {
+        let signing_key = decrypt_private_key(PBKDF2_KEYSTORE, b"testpassword").unwrap();
+        assert_eq!(hex::encode(signing_key.to_bytes()), PRIVATE_KEY);
+    }
+
+    #[test]
+    fn test_wrong_password() {
+        assert!(matches!(
+            decrypt_private_key(PBKDF2_KEYSTORE, b"wrongpassword"),
+            Err(ECDSAError::InvalidPassword)
+        ));
+    }
+
+    #[test]
+    fn test_scrypt_round_trip() {
+        let keystore = Keystore::encrypt(PRIVATE_KEY, "hunter2", Some(1024)).unwrap();
+        let document = json::parse(&keystore).unwrap();
+        assert_eq!(document.get("version").and_then(Value::as_u64), Some(3));
+        assert_eq!(document.get("id").and_then(Value::as_str).unwrap().as_bytes()[14], b'4');
+        assert_eq!(
+            document.get("address").and_then(Value::as_str),
+            Some("008aeeda4d805471df9b2a5b0f38a0c3bcba786b")
+        );
+
+        let signing_key = decrypt_private_key(&keystore, b"hunter2").unwrap();
+        assert_eq!(hex::encode(signing_key.to_bytes()), PRIVATE_KEY);
+        assert!(Keystore::encrypt(PRIVATE_KEY, "hunter2", Some(1000)).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unsupported_documents() {
+        let wrong_version = PBKDF2_KEYSTORE.replace(r#""version": 3"#, r#""version": 1"#);
+        let wrong_cipher = PBKDF2_KEYSTORE.replace("aes-128-ctr", "aes-128-cbc");
+        let wrong_prf = PBKDF2_KEYSTORE.replace("hmac-sha256", "hmac-sha512");
+        for document in [wrong_version, wrong_cipher, wrong_prf, "{}".into()] {
+            assert!(matches!(
+                decrypt_private_key(&document, b"testpassword"),
+                Err(ECDSAError::InvalidKeystore)
+            ));
+        }
+    }
+}
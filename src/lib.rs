@@ -1,10 +1,192 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsValue;
 use serde::{Serialize, Deserialize};
-use sha2::{Sha256, Digest}; // SHA-256 for hashing
-use k256::ecdsa::{Signature, VerifyingKey};
+use k256::ecdsa::{recoverable, Signature, VerifyingKey};
+use k256::elliptic_curve::ops::Reduce;
+use k256::{FieldBytes, Scalar, U256};
+#[cfg(target_arch = "wasm32")]
 use web_sys::console;
-use k256::ecdsa::signature::Verifier;  // `Verifier` trait'ini ekledik
+
+mod adaptor;
+mod aes;
+mod batch;
+mod bip32;
+mod bitcoin;
+mod blind;
+#[cfg(feature = "bls")]
+mod bls;
+mod cbor;
+mod chacha20;
+mod constant_time;
+mod cose;
+mod cosmos;
+mod credential;
+mod did;
+mod ecdh;
+mod ecies;
+mod eip712;
+mod encoding;
+mod ethereum;
+mod ethereum_tx;
+#[cfg(feature = "ed25519")]
+mod ed25519;
+mod frost;
+mod git;
+mod hash;
+mod ipns;
+mod jose;
+mod json;
+mod jwk;
+mod kdf;
+mod keypair;
+mod keys;
+mod keystore;
+mod libp2p;
+mod lightning;
+mod merkle;
+mod mnemonic;
+mod multiformats;
+mod multihash;
+mod musig2;
+mod nostr;
+#[cfg(feature = "p256")]
+mod p256;
+#[cfg(feature = "p384")]
+mod p384;
+#[cfg(feature = "p521")]
+mod p521;
+mod password;
+mod pem;
+mod precompute;
+mod protobuf;
+mod psbt;
+mod random;
+mod rfc6979;
+mod rlp;
+mod schnorr;
+mod secret;
+mod session;
+mod shamir;
+mod signature;
+mod signer;
+#[cfg(feature = "p256")]
+mod sigstore;
+mod simd;
+mod siwe;
+#[cfg(feature = "ed25519")]
+mod slip10;
+#[cfg(feature = "ed25519")]
+mod solana;
+mod ss58;
+mod sshsig;
+mod stream;
+mod taproot;
+mod time;
+mod tron;
+mod ucan;
+mod vrf;
+mod vss;
+mod webauthn;
+mod webcrypto;
+mod wif;
+mod worker;
+#[cfg(feature = "x25519")]
+mod x25519;
+mod x509;
+
+pub use adaptor::{
+    complete_ecdsa_adaptor, complete_schnorr_adaptor, extract_ecdsa_adaptor_secret, extract_schnorr_adaptor_secret, verify_ecdsa_adaptor,
+    verify_schnorr_adaptor,
+};
+pub use aes::{aes_gcm_decrypt, aes_gcm_encrypt};
+pub use batch::BatchVerifier;
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+pub use bip32::{ExtendedKey, ExtendedKeyFormat};
+pub use bitcoin::{address_from_pubkey, verify_bitcoin_message, verify_merkle_proof, AddressKind, Network};
+pub use blind::{BlindSigner, BlindingSession};
+#[cfg(feature = "bls")]
+pub use bls::{aggregate_bls_signatures, verify_bls_aggregate, verify_bls_fast_aggregate, verify_bls_signature};
+pub use chacha20::{chacha20_poly1305_decrypt, chacha20_poly1305_encrypt, xchacha20_poly1305_decrypt, xchacha20_poly1305_encrypt};
+pub use constant_time::constant_time_eq;
+pub use cose::{verify_cose_sign1, CoseKey};
+pub use cosmos::verify_cosmos_arbitrary;
+pub use credential::verify_credential;
+pub use did::{did_key_to_public_key, public_key_to_did_key};
+pub use ecdh::{ecdh, SharedSecretFormat};
+pub use ecies::{ecies_decrypt, ecies_encrypt, EciesCipher};
+#[cfg(feature = "ed25519")]
+pub use ed25519::Ed25519Verifier;
+pub use eip712::TypedDataVerifier;
+pub use encoding::{
+    base58_decode, base58_encode, base58check_decode, base58check_encode, bech32_decode, bech32_encode,
+    segwit_address_decode, segwit_address_encode, Base58CheckData, Bech32Data, Bech32Variant, SegwitProgram,
+};
+pub use ethereum::{ethereum_address_from_pubkey, validate_eip55};
+pub use ethereum_tx::verify_ethereum_tx;
+pub use frost::{verify_frost_signature, FrostSigningPackage};
+pub use git::{verify_git_signature, GitSignature};
+pub use hash::{HashAlgorithm, Hasher};
+pub use ipns::{verify_ipns_record, IpnsRecord};
+pub use jose::verify_jwt;
+pub use jwk::{jwk_to_public_key, public_key_to_jwk};
+pub use kdf::{hkdf_extract_expand, hmac, hmac_verify, HmacAlgorithm};
+pub use keypair::KeyPair;
+pub use keys::PublicKey;
+pub use keystore::Keystore;
+pub use libp2p::{
+    decode_libp2p_public_key, peer_id_from_public_key, public_key_from_peer_id, verify_peer_id, verify_peer_record, verify_signed_envelope,
+    PeerRecord, SignedEnvelope,
+};
+pub use lightning::{verify_bolt11, verify_lnurl_auth};
+pub use merkle::{verify_sorted_merkle_proof, MerkleTree};
+pub use mnemonic::Mnemonic;
+pub use multiformats::{multibase_decode, multibase_encode, multicodec_to_public_key, public_key_to_multicodec};
+pub use multihash::{multihash_decode, multihash_digest, multihash_verify, Multihash};
+pub use musig2::{musig2_aggregate_nonces, musig2_aggregate_public_keys, musig2_sort_public_keys, MuSig2Session};
+pub use nostr::verify_nostr_event;
+pub use password::{derive_key_from_password, PasswordKdfParams};
+pub use pem::{
+    decode_spki, import_private_key_der, import_private_key_pem, import_public_key_der, import_public_key_pem,
+    ImportedPublicKey,
+};
+pub use precompute::warm_up;
+pub use psbt::verify_psbt_signatures;
+pub use random::{random_bytes, set_entropy_source};
+#[cfg(feature = "p256")]
+pub use p256::P256Verifier;
+#[cfg(feature = "p384")]
+pub use p384::P384Verifier;
+#[cfg(feature = "p521")]
+pub use p521::P521Verifier;
+pub use schnorr::SchnorrVerifier;
+pub use secret::SecretKey;
+pub use session::VerificationSession;
+pub use shamir::{combine_shares, split_secret};
+pub use signature::{DerParsing, ECDSASignature, HighSPolicy, SignatureFormat};
+pub use signer::ECDSASigner;
+#[cfg(feature = "p256")]
+pub use sigstore::{verify_sigstore_bundle, SigstoreVerification};
+pub use simd::{simd_enabled, simd_supported};
+pub use siwe::{verify_siwe, SiweMessage};
+#[cfg(feature = "ed25519")]
+pub use slip10::Ed25519ExtendedKey;
+#[cfg(feature = "ed25519")]
+pub use solana::verify_solana_message;
+pub use ss58::{ss58_decode, ss58_encode, Ss58Address};
+pub use sshsig::{verify_sshsig, SshSignature};
+pub use stream::{hash_blob, hash_readable_stream, verify_blob_signature, verify_stream_signature};
+pub use tron::verify_tron_message;
+pub use ucan::{verify_ucan, UcanToken};
+pub use vrf::verify_ecvrf;
+pub use vss::{feldman_verifying_share, verify_feldman_share};
+pub use webauthn::{verify_webauthn_assertion, verify_webauthn_attestation, WebAuthnAssertion, WebAuthnRegistration};
+pub use webcrypto::{crypto_key_to_public_key, public_key_to_crypto_key};
+pub use wif::{wif_decode, wif_encode, WifKey};
+pub use worker::handle_worker_message;
+#[cfg(feature = "x25519")]
+pub use x25519::{x25519, x25519_public_key};
+pub use x509::{verify_certificate, verify_chain};
 
 /// Enum representing possible ECDSA errors
 /// Provides detailed feedback during signature verification failure.
@@ -14,6 +196,127 @@ pub enum ECDSAError {
     InvalidSignatureLength,
     InvalidSignatureRecovery,
     InvalidPublicKey,
+    InvalidPrivateKey,
+    SigningFailed,
+    InvalidAddress,
+    InvalidJson,
+    InvalidTypedData,
+    InvalidDigestLength,
+    InvalidBatchInput,
+    BatchVerificationFailed,
+    InvalidTaprootTweak,
+    InvalidSymmetricKey,
+    InvalidNonce,
+    EncryptionFailed,
+    DecryptionFailed,
+    InvalidOutputLength,
+    InvalidKdfParams,
+    InvalidKeystore,
+    InvalidPassword,
+    InvalidMnemonic,
+    InvalidEntropy,
+    InvalidExtendedKey,
+    InvalidDerivationPath,
+    InvalidChildKey,
+    PrivateKeyRequired,
+    InvalidEncoding,
+    InvalidPsbt,
+    MissingPsbtInputData,
+    InvalidSighashType,
+    InvalidInvoice,
+    InvalidInvoiceSignature,
+    InvalidNostrEvent,
+    InvalidSiweMessage,
+    SiweDomainMismatch,
+    SiweNonceMismatch,
+    SiweExpired,
+    SiweNotYetValid,
+    SiweSignatureMismatch,
+    InvalidRlp,
+    InvalidTransaction,
+    InvalidJwt,
+    UnsupportedJwtAlgorithm,
+    JwtSignatureMismatch,
+    JwtExpired,
+    JwtNotYetValid,
+    InvalidJwk,
+    InvalidKeyEncoding,
+    UnsupportedCurve,
+    CurveMismatch,
+    InvalidCertificate,
+    UnsupportedSignatureAlgorithm,
+    CertificateExpired,
+    CertificateNotYetValid,
+    InvalidCaCertificate,
+    PathLengthExceeded,
+    UnsupportedCriticalExtension,
+    UntrustedCertificate,
+    InvalidCbor,
+    InvalidCose,
+    UnsupportedCoseAlgorithm,
+    CoseSignatureMismatch,
+    InvalidCoseKey,
+    InvalidWebAuthnData,
+    WebAuthnSignatureMismatch,
+    InvalidAttestation,
+    UnsupportedAttestationFormat,
+    WebCryptoUnavailable,
+    InvalidCryptoKey,
+    InvalidSshSignature,
+    UnsupportedSshKeyType,
+    InvalidAllowedSigners,
+    SshSignatureMismatch,
+    SshSignerNotAllowed,
+    InvalidGitObject,
+    SshNamespaceMismatch,
+    InvalidSigstoreBundle,
+    SigstoreSignatureMismatch,
+    RekorEntryMismatch,
+    InclusionProofMismatch,
+    InvalidDidKey,
+    InvalidCredential,
+    UnsupportedProofType,
+    InvalidUcan,
+    UcanProofMissing,
+    InvalidUcanChain,
+    UcanAudienceMismatch,
+    InvalidProtobuf,
+    InvalidLibp2pKey,
+    InvalidIpnsRecord,
+    IpnsKeyMismatch,
+    IpnsSignatureMismatch,
+    IpnsRecordExpired,
+    InvalidPeerId,
+    InvalidSignedEnvelope,
+    EnvelopeSignatureMismatch,
+    InvalidMultibase,
+    InvalidMultihash,
+    UnsupportedMultihash,
+    InvalidMerkleProof,
+    InvalidMerkleTree,
+    InvalidMuSig2Nonce,
+    InvalidMuSig2Session,
+    InvalidPartialSignature,
+    InvalidFrostCommitment,
+    InvalidFrostShare,
+    InvalidAdaptorSignature,
+    AdaptorSecretMismatch,
+    InvalidShamirParameters,
+    InvalidShamirShare,
+    ShamirRecoveryFailed,
+    InvalidVssShare,
+    InvalidVssCommitment,
+    InvalidBlindSignature,
+    NoPendingNonce,
+    InvalidVrfProof,
+    VrfVerificationFailed,
+    InvalidBlsSignature,
+    InvalidBlsAggregate,
+    RandomnessUnavailable,
+    SecretKeyDestroyed,
+    StreamReadFailed,
+    InvalidWorkerRequest,
+    SignatureVerificationFailed,
 }
 
 impl std::fmt::Display for ECDSAError {
@@ -23,20 +326,240 @@ impl std::fmt::Display for ECDSAError {
             ECDSAError::InvalidSignatureLength => write!(f, "Invalid signature length"),
             ECDSAError::InvalidSignatureRecovery => write!(f, "Failed to recover public key"),
             ECDSAError::InvalidPublicKey => write!(f, "Invalid public key"),
+            ECDSAError::InvalidPrivateKey => write!(f, "Invalid private key"),
+            ECDSAError::SigningFailed => write!(f, "Failed to produce signature"),
+            ECDSAError::InvalidAddress => write!(f, "Invalid address"),
+            ECDSAError::InvalidJson => write!(f, "Invalid JSON"),
+            ECDSAError::InvalidTypedData => write!(f, "Invalid EIP-712 typed data"),
+            ECDSAError::InvalidDigestLength => write!(f, "Message digest must be 32 bytes"),
+            ECDSAError::InvalidBatchInput => write!(f, "Batch items must be an array of {{message, signature, publicKey}} objects"),
+            ECDSAError::BatchVerificationFailed => write!(f, "One or more signatures in the batch are invalid"),
+            ECDSAError::InvalidTaprootTweak => write!(f, "Invalid Taproot merkle root or tweak"),
+            ECDSAError::InvalidSymmetricKey => write!(f, "Invalid symmetric key length"),
+            ECDSAError::InvalidNonce => write!(f, "Invalid nonce"),
+            ECDSAError::EncryptionFailed => write!(f, "Encryption failed"),
+            ECDSAError::DecryptionFailed => write!(f, "Decryption failed: malformed or tampered ciphertext"),
+            ECDSAError::InvalidOutputLength => write!(f, "Requested output length is out of range"),
+            ECDSAError::InvalidKdfParams => write!(f, "Invalid key derivation parameters"),
+            ECDSAError::InvalidKeystore => write!(f, "Invalid or unsupported keystore"),
+            ECDSAError::InvalidPassword => write!(f, "Incorrect password or corrupted keystore"),
+            ECDSAError::InvalidMnemonic => write!(f, "Invalid mnemonic phrase"),
+            ECDSAError::InvalidEntropy => write!(f, "Invalid mnemonic entropy or word count"),
+            ECDSAError::InvalidExtendedKey => write!(f, "Invalid extended key or seed"),
+            ECDSAError::InvalidDerivationPath => write!(f, "Invalid derivation path"),
+            ECDSAError::InvalidChildKey => write!(f, "Derived child key is invalid; use the next index"),
+            ECDSAError::PrivateKeyRequired => write!(f, "Operation requires a private key"),
+            ECDSAError::InvalidEncoding => write!(f, "Malformed encoding or checksum mismatch"),
+            ECDSAError::InvalidPsbt => write!(f, "Malformed or unsupported PSBT"),
+            ECDSAError::MissingPsbtInputData => write!(f, "PSBT input is missing the UTXO or script needed for its sighash"),
+            ECDSAError::InvalidSighashType => write!(f, "Signature sighash type does not match the PSBT input"),
+            ECDSAError::InvalidInvoice => write!(f, "Malformed Lightning invoice"),
+            ECDSAError::InvalidInvoiceSignature => write!(f, "Invoice signature does not match its payee node key"),
+            ECDSAError::InvalidNostrEvent => write!(f, "Invalid Nostr event"),
+            ECDSAError::InvalidSiweMessage => write!(f, "Malformed Sign-In with Ethereum message"),
+            ECDSAError::SiweDomainMismatch => write!(f, "Sign-In with Ethereum domain does not match"),
+            ECDSAError::SiweNonceMismatch => write!(f, "Sign-In with Ethereum nonce does not match"),
+            ECDSAError::SiweExpired => write!(f, "Sign-In with Ethereum message has expired"),
+            ECDSAError::SiweNotYetValid => write!(f, "Sign-In with Ethereum message is not yet valid"),
+            ECDSAError::SiweSignatureMismatch => write!(f, "Signature was not produced by the message's address"),
+            ECDSAError::InvalidRlp => write!(f, "Malformed RLP encoding"),
+            ECDSAError::InvalidTransaction => write!(f, "Malformed or unsupported transaction"),
+            ECDSAError::InvalidJwt => write!(f, "Malformed JWT or JWS"),
+            ECDSAError::UnsupportedJwtAlgorithm => write!(f, "Unsupported or mismatched JWS algorithm"),
+            ECDSAError::JwtSignatureMismatch => write!(f, "JWT signature is invalid"),
+            ECDSAError::JwtExpired => write!(f, "JWT has expired"),
+            ECDSAError::JwtNotYetValid => write!(f, "JWT is not yet valid"),
+            ECDSAError::InvalidJwk => write!(f, "Invalid or unsupported JWK"),
+            ECDSAError::InvalidKeyEncoding => write!(f, "Malformed or unsupported PEM/DER key encoding"),
+            ECDSAError::UnsupportedCurve => write!(f, "Unsupported curve or key algorithm"),
+            ECDSAError::CurveMismatch => write!(f, "Key curve does not match the expected curve"),
+            ECDSAError::InvalidCertificate => write!(f, "Invalid X.509 certificate"),
+            ECDSAError::UnsupportedSignatureAlgorithm => write!(f, "Unsupported certificate signature algorithm"),
+            ECDSAError::CertificateExpired => write!(f, "Certificate has expired"),
+            ECDSAError::CertificateNotYetValid => write!(f, "Certificate is not yet valid"),
+            ECDSAError::InvalidCaCertificate => write!(f, "Issuer certificate is not allowed to sign certificates"),
+            ECDSAError::PathLengthExceeded => write!(f, "Certificate path exceeds an issuer's path length constraint"),
+            ECDSAError::UnsupportedCriticalExtension => write!(f, "Certificate has an unsupported critical extension"),
+            ECDSAError::UntrustedCertificate => write!(f, "No certificate path to a trust anchor"),
+            ECDSAError::InvalidCbor => write!(f, "Invalid CBOR"),
+            ECDSAError::InvalidCose => write!(f, "Invalid COSE message"),
+            ECDSAError::UnsupportedCoseAlgorithm => write!(f, "Unsupported COSE algorithm"),
+            ECDSAError::CoseSignatureMismatch => write!(f, "COSE signature verification failed"),
+            ECDSAError::InvalidCoseKey => write!(f, "Invalid COSE key"),
+            ECDSAError::InvalidWebAuthnData => write!(f, "Invalid WebAuthn authenticator or client data"),
+            ECDSAError::WebAuthnSignatureMismatch => write!(f, "WebAuthn signature verification failed"),
+            ECDSAError::InvalidAttestation => write!(f, "Invalid WebAuthn attestation statement"),
+            ECDSAError::UnsupportedAttestationFormat => write!(f, "Unsupported WebAuthn attestation format"),
+            ECDSAError::WebCryptoUnavailable => write!(f, "WebCrypto is not available in this environment"),
+            ECDSAError::InvalidCryptoKey => write!(f, "CryptoKey could not be exported or imported"),
+            ECDSAError::InvalidSshSignature => write!(f, "Invalid SSH signature encoding"),
+            ECDSAError::UnsupportedSshKeyType => write!(f, "Unsupported SSH key type"),
+            ECDSAError::InvalidAllowedSigners => write!(f, "Invalid allowed signers entry"),
+            ECDSAError::SshSignatureMismatch => write!(f, "SSH signature verification failed"),
+            ECDSAError::SshSignerNotAllowed => write!(f, "SSH signing key is not an allowed signer"),
+            ECDSAError::InvalidGitObject => write!(f, "Invalid or unsigned git object"),
+            ECDSAError::SshNamespaceMismatch => write!(f, "SSH signature namespace does not match"),
+            ECDSAError::InvalidSigstoreBundle => write!(f, "Invalid sigstore bundle"),
+            ECDSAError::SigstoreSignatureMismatch => write!(f, "Sigstore bundle signature does not match the artifact"),
+            ECDSAError::RekorEntryMismatch => write!(f, "Rekor entry does not match the bundle"),
+            ECDSAError::InclusionProofMismatch => write!(f, "Transparency log inclusion proof does not verify"),
+            ECDSAError::InvalidDidKey => write!(f, "Invalid did:key identifier"),
+            ECDSAError::InvalidCredential => write!(f, "Invalid verifiable credential"),
+            ECDSAError::UnsupportedProofType => write!(f, "Unsupported credential proof type or cryptosuite"),
+            ECDSAError::InvalidUcan => write!(f, "Malformed UCAN token"),
+            ECDSAError::UcanProofMissing => write!(f, "UCAN proof not provided"),
+            ECDSAError::InvalidUcanChain => write!(f, "UCAN proof chain is invalid"),
+            ECDSAError::UcanAudienceMismatch => write!(f, "UCAN audience does not match"),
+            ECDSAError::InvalidProtobuf => write!(f, "Malformed protobuf message"),
+            ECDSAError::InvalidLibp2pKey => write!(f, "Malformed libp2p public key"),
+            ECDSAError::InvalidIpnsRecord => write!(f, "Malformed IPNS record"),
+            ECDSAError::IpnsKeyMismatch => write!(f, "IPNS record public key does not match"),
+            ECDSAError::IpnsSignatureMismatch => write!(f, "IPNS record signature is invalid"),
+            ECDSAError::IpnsRecordExpired => write!(f, "IPNS record has expired"),
+            ECDSAError::InvalidPeerId => write!(f, "Malformed libp2p peer ID"),
+            ECDSAError::InvalidSignedEnvelope => write!(f, "Malformed libp2p signed envelope"),
+            ECDSAError::EnvelopeSignatureMismatch => write!(f, "libp2p signed envelope signature is invalid"),
+            ECDSAError::InvalidMultibase => write!(f, "Malformed or unsupported multibase string"),
+            ECDSAError::InvalidMultihash => write!(f, "Malformed multihash"),
+            ECDSAError::UnsupportedMultihash => write!(f, "Unsupported multihash function"),
+            ECDSAError::InvalidMerkleProof => write!(f, "Malformed Merkle proof"),
+            ECDSAError::InvalidMerkleTree => write!(f, "Merkle tree needs at least one well-formed leaf"),
+            ECDSAError::InvalidMuSig2Nonce => write!(f, "Malformed MuSig2 nonce"),
+            ECDSAError::InvalidMuSig2Session => write!(f, "Invalid MuSig2 session: bad key, tweak or signer"),
+            ECDSAError::InvalidPartialSignature => write!(f, "Malformed partial signature"),
+            ECDSAError::InvalidFrostCommitment => write!(f, "Malformed or duplicate FROST commitment"),
+            ECDSAError::InvalidFrostShare => write!(f, "Malformed FROST signature share"),
+            ECDSAError::InvalidAdaptorSignature => write!(f, "Malformed adaptor signature"),
+            ECDSAError::AdaptorSecretMismatch => write!(f, "Adaptor secret does not match the adaptor point"),
+            ECDSAError::InvalidShamirParameters => write!(f, "Invalid secret sharing parameters"),
+            ECDSAError::InvalidShamirShare => write!(f, "Malformed or corrupted secret share"),
+            ECDSAError::ShamirRecoveryFailed => write!(f, "Shares do not recover a consistent secret"),
+            ECDSAError::InvalidVssShare => write!(f, "Malformed verifiable secret share"),
+            ECDSAError::InvalidVssCommitment => write!(f, "Malformed polynomial commitment"),
+            ECDSAError::InvalidBlindSignature => write!(f, "Malformed or mismatched blind signature"),
+            ECDSAError::NoPendingNonce => write!(f, "No pending nonce; call commit first"),
+            ECDSAError::InvalidVrfProof => write!(f, "Malformed VRF proof"),
+            ECDSAError::VrfVerificationFailed => write!(f, "VRF proof verification failed"),
+            ECDSAError::InvalidBlsSignature => write!(f, "Malformed BLS signature"),
+            ECDSAError::InvalidBlsAggregate => write!(f, "BLS aggregates need at least one signer and one message per public key"),
+            ECDSAError::RandomnessUnavailable => write!(f, "No secure randomness source is available"),
+            ECDSAError::SecretKeyDestroyed => write!(f, "Secret key has been destroyed"),
+            ECDSAError::StreamReadFailed => write!(f, "Failed to read from the stream"),
+            ECDSAError::InvalidWorkerRequest => write!(f, "Malformed or unsupported worker request"),
+            ECDSAError::SignatureVerificationFailed => write!(f, "Signature verification failed"),
         }
     }
 }
 
+impl std::error::Error for ECDSAError {}
+
+/// Errors cross the wasm boundary as plain JS strings.
+impl From<ECDSAError> for JsValue {
+    fn from(err: ECDSAError) -> JsValue {
+        JsValue::from_str(&err.to_string())
+    }
+}
+
+/// Logs a message to the browser console.
+/// Outside of wasm this is a no-op, so the crate can be unit tested natively.
+pub(crate) fn log(message: &str) {
+    #[cfg(target_arch = "wasm32")]
+    console::log_1(&message.into());
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = message;
+}
+
+/// Converts a message digest into a scalar (the `bits2int` step of ECDSA).
+/// Digests longer than 32 bytes are truncated to their leftmost bytes, shorter ones are left-padded.
+pub(crate) fn digest_to_scalar(digest: &[u8]) -> Scalar {
+    let mut bytes = FieldBytes::default();
+    let len = digest.len().min(32);
+    bytes[32 - len..].copy_from_slice(&digest[..len]);
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(bytes)
+}
+
+/// Verifies `sig` against an already hashed message digest.
+pub(crate) fn verify_digest(pubkey: &VerifyingKey, digest: &[u8], sig: &Signature) -> bool {
+    precompute::verify_prehashed(pubkey, digest_to_scalar(digest), sig)
+}
+
+/// Recovers the public key that produced `sig` over `digest`, given the recovery id.
+/// The recovered key is checked against the signature before being returned.
+pub(crate) fn recover_from_digest(digest: &[u8], sig: &Signature, recovery_id: u8) -> Result<VerifyingKey, ECDSAError> {
+    let id = recoverable::Id::new(recovery_id).map_err(|_| ECDSAError::InvalidSignatureRecovery)?;
+    let recoverable_sig = recoverable::Signature::new(sig, id).map_err(|_| ECDSAError::InvalidSignatureRecovery)?;
+
+    let digest_bytes = digest_to_scalar(digest).to_bytes();
+    let pubkey = recoverable_sig
+        .recover_verify_key_from_digest_bytes(&digest_bytes)
+        .map_err(|_| ECDSAError::InvalidSignatureRecovery)?;
+
+    if !verify_digest(&pubkey, digest, sig) {
+        return Err(ECDSAError::InvalidSignatureRecovery);
+    }
+
+    Ok(pubkey)
+}
+
 /// Struct for ECDSA signature verification using k256 crate.
 #[wasm_bindgen]
-pub struct ECDSAVerifier;
+#[derive(Clone, Copy)]
+pub struct ECDSAVerifier {
+    hash_algorithm: HashAlgorithm,
+    high_s_policy: HighSPolicy,
+    der_parsing: DerParsing,
+}
+
+impl Default for ECDSAVerifier {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
 
 #[wasm_bindgen]
 impl ECDSAVerifier {
     /// Initializes a new instance of ECDSAVerifier.
+    ///
+    /// # Arguments:
+    /// * `hash_algorithm` - The hash applied to messages before verification.
+    ///   Defaults to SHA-256 when omitted.
     #[wasm_bindgen(constructor)]
-    pub fn new() -> ECDSAVerifier {
-        ECDSAVerifier
+    pub fn new(hash_algorithm: Option<HashAlgorithm>) -> ECDSAVerifier {
+        ECDSAVerifier {
+            hash_algorithm: hash_algorithm.unwrap_or_default(),
+            high_s_policy: HighSPolicy::default(),
+            der_parsing: DerParsing::default(),
+        }
+    }
+
+    /// The hash algorithm this verifier applies to messages.
+    #[wasm_bindgen(getter)]
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    /// How this verifier treats high-S signatures. Defaults to `HighSPolicy::Reject`.
+    #[wasm_bindgen(getter)]
+    pub fn high_s_policy(&self) -> HighSPolicy {
+        self.high_s_policy
+    }
+
+    /// Sets how this verifier treats high-S signatures.
+    #[wasm_bindgen(setter)]
+    pub fn set_high_s_policy(&mut self, policy: HighSPolicy) {
+        self.high_s_policy = policy;
+    }
+
+    /// How strictly this verifier validates DER signatures. Defaults to `DerParsing::Strict`.
+    #[wasm_bindgen(getter)]
+    pub fn der_parsing(&self) -> DerParsing {
+        self.der_parsing
+    }
+
+    /// Sets how strictly this verifier validates DER signatures.
+    #[wasm_bindgen(setter)]
+    pub fn set_der_parsing(&mut self, mode: DerParsing) {
+        self.der_parsing = mode;
     }
 
     /// Verifies a signature using a message, signature, and public key.
@@ -44,10 +567,12 @@ impl ECDSAVerifier {
     /// # Arguments:
     /// * `message` - The message that was signed.
     /// * `signature` - The digital signature to verify, provided as a hex string.
-    /// * `public_key` - The signer's public key, provided as a hex string.
+    ///   Both DER and compact (64-byte r || s) encodings are accepted.
+    /// * `public_key` - The signer's SEC1 public key (compressed, uncompressed or hybrid),
+    ///   provided as a hex string.
     /// 
     /// # Returns:
-    /// * A boolean indicating whether the signature is valid or not.
+    /// * `true` if the signature is valid.
     /// 
     /// # Errors:
     /// * Returns a descriptive error if the signature format is invalid, or the signature fails verification.
@@ -56,42 +581,221 @@ impl ECDSAVerifier {
         message: &str,
         signature: &str,
         public_key: &str,
-    ) -> Result<bool, JsValue> {
+    ) -> Result<bool, ECDSAError> {
+        match self.verify_signature_with_format(message, signature, public_key, SignatureFormat::Auto)? {
+            true => Ok(true),
+            false => Err(ECDSAError::SignatureVerificationFailed),
+        }
+    }
+
+    /// Verifies a signature like `verify_signature`, with an explicit signature encoding
+    /// instead of auto-detection.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The digital signature to verify, provided as a hex string.
+    /// * `public_key` - The signer's public key, provided as a hex string.
+    /// * `format` - The encoding of `signature`.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    pub fn verify_signature_with_format(
+        &self,
+        message: &str,
+        signature: &str,
+        public_key: &str,
+        format: SignatureFormat,
+    ) -> Result<bool, ECDSAError> {
         // Log the verification process
-        console::log_1(&"Starting signature verification...".into());
+        log("Starting signature verification...");
 
-        // Hash the input message using SHA-256
-        let msg_hash = self.hash_message(message);
+        let valid = self.verify_inner(message, signature, public_key, format)?;
 
-        // Decode the signature and public key from hex and check for length errors
-        let signature_bytes = hex::decode(signature).map_err(|_| JsValue::from_str("Invalid signature format"))?;
-        if signature_bytes.len() != 64 {
-            return Err(JsValue::from_str("Signature length is invalid"));
-        }
+        log("Verification complete.");
+
+        Ok(valid)
+    }
+
+    /// Verifies a signature over a message hashed with Keccak-256 instead of SHA-256,
+    /// as used throughout the Ethereum ecosystem.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The digital signature to verify, provided as a hex string.
+    /// * `public_key` - The signer's public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    pub fn verify_signature_keccak(
+        &self,
+        message: &str,
+        signature: &str,
+        public_key: &str,
+    ) -> Result<bool, ECDSAError> {
+        let msg_hash = ethereum::keccak256(message.as_bytes());
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let sig = self.parse_signature(&signature_bytes, SignatureFormat::Auto)?;
+        self.verify_hashed(&msg_hash, &sig, public_key)
+    }
 
-        let pubkey_bytes = hex::decode(public_key).map_err(|_| JsValue::from_str("Invalid public key format"))?;
-        if pubkey_bytes.len() != 33 {
-            return Err(JsValue::from_str("Public key length is invalid"));
+    /// Verifies a fixed-size 64-byte (r || s) signature over a hashed message.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The 64-byte compact signature, provided as a hex string.
+    /// * `public_key` - The signer's public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature is not exactly 64 bytes or the public key is invalid.
+    pub fn verify_signature_compact(
+        &self,
+        message: &str,
+        signature: &str,
+        public_key: &str,
+    ) -> Result<bool, ECDSAError> {
+        self.verify_signature_with_format(message, signature, public_key, SignatureFormat::Compact)
+    }
+
+    /// Verifies a signature directly against a caller-supplied 32-byte digest,
+    /// skipping the internal SHA-256 step.
+    ///
+    /// # Arguments:
+    /// * `digest` - The 32-byte message digest, provided as a hex string.
+    /// * `signature` - The DER or compact signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the digest is not 32 bytes or any input is malformed.
+    pub fn verify_prehashed(&self, digest: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let digest_bytes = hex::decode(digest).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        if digest_bytes.len() != 32 {
+            return Err(ECDSAError::InvalidDigestLength);
         }
 
-        // Convert the message hash, signature, and public key into appropriate types
-        let sig = Signature::from_der(&signature_bytes).map_err(|_| JsValue::from_str("Invalid signature"))?;
-        let pubkey = VerifyingKey::from_sec1_bytes(&pubkey_bytes).map_err(|_| JsValue::from_str("Invalid public key"))?;
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let sig = self.parse_signature(&signature_bytes, SignatureFormat::Auto)?;
+        self.verify_hashed(&digest_bytes, &sig, public_key)
+    }
 
-        // Perform the signature verification using k256
-        pubkey.verify(&msg_hash, &sig).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    /// Recovers the signer's public key from a recoverable signature.
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The 64-byte compact signature (r || s), provided as a hex string.
+    /// * `recovery_id` - The recovery id (0 or 1) emitted alongside the signature.
+    ///
+    /// # Returns:
+    /// * The SEC1 compressed public key of the signer, as a hex string.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature is malformed or no valid key can be recovered.
+    pub fn recover_public_key(
+        &self,
+        message: &str,
+        signature: &str,
+        recovery_id: u8,
+    ) -> Result<String, ECDSAError> {
+        let msg_hash = self.hash_message(message);
 
-        console::log_1(&"Verification complete.".into());
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let sig = signature::parse_compact(&signature_bytes)?;
 
-        Ok(true)
+        let pubkey = recover_from_digest(&msg_hash, &sig, recovery_id)?;
+        Ok(hex::encode(pubkey.to_bytes()))
     }
 
-    /// Hashes the message using SHA-256.
+    /// Verifies a signature over binary data, taking raw bytes (`Uint8Array` in JS)
+    /// instead of UTF-8 text and hex strings.
+    ///
+    /// # Arguments:
+    /// * `message` - The signed bytes; they are hashed with the configured algorithm.
+    /// * `signature` - The DER or compact (64-byte r || s) signature bytes.
+    /// * `public_key` - The signer's SEC1 public key bytes.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    pub fn verify_signature_bytes(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, ECDSAError> {
+        let msg_hash = self.hash_message_bytes(message);
+        let sig = self.parse_signature(signature, SignatureFormat::Auto)?;
+        let pubkey = keys::parse_public_key(public_key)?;
+        Ok(self.verify_with_policy(&pubkey, &msg_hash, &sig))
+    }
+
+    /// Hashes the message using the verifier's hash algorithm (SHA-256 by default).
     /// This function is used internally to prepare the message for signature verification.
     pub fn hash_message(&self, message: &str) -> Vec<u8> {
-        let mut hasher = Sha256::new();
-        hasher.update(message.as_bytes());
-        hasher.finalize().to_vec() // Return the resulting hash
+        self.hash_message_bytes(message.as_bytes())
+    }
+
+    /// Hashes binary data using the verifier's hash algorithm.
+    pub fn hash_message_bytes(&self, message: &[u8]) -> Vec<u8> {
+        hash::digest(self.hash_algorithm, message)
+    }
+
+    /// Starts a chunked verification that uses this verifier's hash algorithm and policies.
+    ///
+    /// # Arguments:
+    /// * `signature` - The DER or compact signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A `VerificationSession` to `feed` the signed data to, then `finish`.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or public key is malformed.
+    pub fn start_session(&self, signature: &str, public_key: &str) -> Result<VerificationSession, ECDSAError> {
+        VerificationSession::with_verifier(*self, signature, public_key)
+    }
+}
+
+impl ECDSAVerifier {
+    /// `verify_signature_with_format` without the console logging, for callers that verify
+    /// many signatures per call.
+    pub(crate) fn verify_inner(
+        &self,
+        message: &str,
+        signature: &str,
+        public_key: &str,
+        format: SignatureFormat,
+    ) -> Result<bool, ECDSAError> {
+        // Hash the input message with the configured algorithm
+        let msg_hash = self.hash_message(message);
+
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let sig = self.parse_signature(&signature_bytes, format)?;
+        self.verify_hashed(&msg_hash, &sig, public_key)
+    }
+
+    /// Shared tail of the `verify_*` entry points: decodes the hex public key and
+    /// checks the signature against an already computed digest.
+    fn verify_hashed(&self, msg_hash: &[u8], sig: &Signature, public_key: &str) -> Result<bool, ECDSAError> {
+        // Decode the SEC1 public key (compressed, uncompressed or hybrid) from hex
+        let pubkey = keys::parse_public_key_hex(public_key)?;
+
+        // Perform the signature verification using k256
+        Ok(self.verify_with_policy(&pubkey, msg_hash, sig))
+    }
+
+    /// Parses a signature using the verifier's DER strictness.
+    pub(crate) fn parse_signature(&self, bytes: &[u8], format: SignatureFormat) -> Result<Signature, ECDSAError> {
+        signature::parse_signature_with_mode(bytes, format, self.der_parsing)
+    }
+
+    /// Verifies `sig` over `digest` after applying the verifier's high-S policy.
+    pub(crate) fn verify_with_policy(&self, pubkey: &VerifyingKey, digest: &[u8], sig: &Signature) -> bool {
+        let sig = signature::apply_high_s_policy(sig, self.high_s_policy);
+        verify_digest(pubkey, digest, &sig)
     }
 }
 
@@ -103,19 +807,165 @@ mod tests {
 
     #[test]
     fn test_verify_signature() {
-        let verifier = ECDSAVerifier::new();
+        let verifier = ECDSAVerifier::default();
         let message = "Test message";
-        let signature = "3045022100ebf...";  // Mock signature for testing
-        let public_key = "03a0...";  // Mock public key for testing
+        let signature = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
 
         let result = verifier.verify_signature(message, signature, public_key);
         assert!(result.is_ok());
         assert!(result.unwrap());
     }
 
+    #[test]
+    fn test_verify_signature_wrong_message() {
+        let verifier = ECDSAVerifier::default();
+        let signature = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+        let result = verifier.verify_signature("Other message", signature, public_key);
+        assert!(matches!(result, Err(ECDSAError::SignatureVerificationFailed)));
+    }
+
+    #[test]
+    fn test_verify_signature_keccak() {
+        let key = k256::ecdsa::SigningKey::from_bytes(&[0x11; 32]).unwrap();
+        let digest = ethereum::keccak256(b"Test message");
+        let (sig, _) = signer::sign_digest(&key, &digest, &[]).unwrap();
+        let signature = hex::encode(sig.to_der().as_bytes());
+        let public_key = hex::encode(key.verifying_key().to_bytes());
+
+        let verifier = ECDSAVerifier::default();
+        assert!(verifier.verify_signature_keccak("Test message", &signature, &public_key).unwrap());
+        // The same signature does not verify under SHA-256
+        assert!(verifier.verify_signature("Test message", &signature, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_verify_digest_any_length() {
+        let key = k256::ecdsa::SigningKey::from_bytes(&[0x11; 32]).unwrap();
+        let digest = [0x5a; 64];
+        let (sig, _) = signer::sign_digest(&key, &digest, &[]).unwrap();
+
+        // Long digests are truncated to their leftmost 32 bytes rather than panicking
+        assert!(verify_digest(&key.verifying_key(), &digest, &sig));
+        assert!(verify_digest(&key.verifying_key(), &digest[..32], &sig));
+        assert!(!verify_digest(&key.verifying_key(), &digest[..20], &sig));
+        assert!(!verify_digest(&key.verifying_key(), &[], &sig));
+    }
+
+    #[test]
+    fn test_verify_signature_compact() {
+        let verifier = ECDSAVerifier::default();
+        let signature = "bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb26967d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+        assert!(verifier.verify_signature_compact("Test message", signature, public_key).unwrap());
+        assert!(!verifier.verify_signature_compact("Other message", signature, public_key).unwrap());
+        assert!(verifier.verify_signature_compact("Test message", &signature[..126], public_key).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_detects_format() {
+        let verifier = ECDSAVerifier::default();
+        let der = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let compact = "bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb26967d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+        assert!(verifier.verify_signature("Test message", compact, public_key).unwrap());
+        assert!(verifier.verify_signature("Test message", der, public_key).unwrap());
+        assert!(verifier
+            .verify_signature_with_format("Test message", der, public_key, SignatureFormat::Der)
+            .unwrap());
+        assert!(verifier
+            .verify_signature_with_format("Test message", compact, public_key, SignatureFormat::Der)
+            .is_err());
+        assert!(verifier
+            .verify_signature_with_format("Test message", der, public_key, SignatureFormat::Compact)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_uncompressed_key() {
+        let verifier = ECDSAVerifier::default();
+        let signature = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "042c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae64564b95e4fdb6948c0386e189b006a29f686769b011704275e4459822dc3328085";
+
+        assert!(verifier.verify_signature("Test message", signature, public_key).unwrap());
+    }
+
+    #[test]
+    fn test_verify_signature_bytes() {
+        let verifier = ECDSAVerifier::default();
+        let signer = ECDSASigner::new(&"11".repeat(32)).unwrap();
+        let payload = [0x00, 0xff, 0x80, 0x0a, 0xc3];
+        let signature = hex::decode(signer.sign_bytes(&payload).unwrap().der()).unwrap();
+        let public_key = hex::decode(signer.public_key()).unwrap();
+
+        assert!(verifier.verify_signature_bytes(&payload, &signature, &public_key).unwrap());
+        assert!(!verifier.verify_signature_bytes(&payload[1..], &signature, &public_key).unwrap());
+        assert!(verifier.verify_signature_bytes(&payload, &signature[1..], &public_key).is_err());
+    }
+
+    #[test]
+    fn test_verify_prehashed() {
+        let verifier = ECDSAVerifier::default();
+        let signature = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+        let digest = hex::encode(verifier.hash_message("Test message"));
+
+        assert!(verifier.verify_prehashed(&digest, signature, public_key).unwrap());
+        assert!(!verifier.verify_prehashed(&"00".repeat(32), signature, public_key).unwrap());
+        assert!(verifier.verify_prehashed(&digest[..62], signature, public_key).is_err());
+    }
+
+    #[test]
+    fn test_high_s_policy() {
+        let high_s = "bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269682cd616936451dda44a073dfa6c1bb000b5b442ee5e650a2857a8efd082c6d89";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+        let mut verifier = ECDSAVerifier::default();
+        assert_eq!(verifier.high_s_policy(), HighSPolicy::Reject);
+        assert!(verifier.verify_signature("Test message", high_s, public_key).is_err());
+
+        verifier.set_high_s_policy(HighSPolicy::Normalize);
+        assert!(verifier.verify_signature("Test message", high_s, public_key).unwrap());
+    }
+
+    #[test]
+    fn test_der_parsing_mode() {
+        // The test vector's r with a redundant zero byte of padding
+        let padded = "304602220000bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+        let public_key = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+        let mut verifier = ECDSAVerifier::default();
+        assert!(verifier.verify_signature("Test message", padded, public_key).is_err());
+
+        verifier.set_der_parsing(DerParsing::Lax);
+        assert!(verifier.verify_signature("Test message", padded, public_key).unwrap());
+    }
+
+    #[test]
+    fn test_configurable_hash_algorithm() {
+        let key = k256::ecdsa::SigningKey::from_bytes(&[0x22; 32]).unwrap();
+        let public_key = hex::encode(key.verifying_key().to_bytes());
+
+        for algorithm in [HashAlgorithm::Sha512, HashAlgorithm::Sha3_256, HashAlgorithm::Blake2b256] {
+            let verifier = ECDSAVerifier::new(Some(algorithm));
+            assert_eq!(verifier.hash_algorithm(), algorithm);
+
+            let digest = verifier.hash_message("Test message");
+            let (sig, _) = signer::sign_digest(&key, &digest, &[]).unwrap();
+            let signature = hex::encode(sig.as_ref());
+
+            assert!(verifier.verify_signature("Test message", &signature, &public_key).unwrap());
+            assert!(ECDSAVerifier::default().verify_signature("Test message", &signature, &public_key).is_err());
+        }
+    }
+
     #[test]
     fn test_invalid_signature() {
-        let verifier = ECDSAVerifier::new();
+        let verifier = ECDSAVerifier::default();
         let message = "Test message";
         let invalid_signature = "invalid_sig";
         let public_key = "03a0...";  // Mock public key for testing
@@ -126,7 +976,7 @@ mod tests {
 
     #[test]
     fn test_invalid_public_key() {
-        let verifier = ECDSAVerifier::new();
+        let verifier = ECDSAVerifier::default();
         let message = "Test message";
         let signature = "3045022100ebf...";  // Mock signature for testing
         let invalid_public_key = "invalid_pubkey";
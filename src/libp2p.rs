@@ -0,0 +1,484 @@
+//! libp2p identities: public keys, the peer IDs derived from them, and signed envelopes.
+//!
+//! Keys travel as a `PublicKey` protobuf with the key type in field 1 and the key in field 2.
+//! Ed25519 keys are the 32 raw bytes and sign messages directly. Secp256k1 keys are compressed
+//! SEC1 points and ECDSA keys DER SubjectPublicKeyInfos; both sign the SHA-256 digest of the
+//! message with DER signatures. RSA keys are not supported.
+//!
+//! A peer ID is a multihash of the encoded key: the identity "hash" for keys of at most 42
+//! bytes, so the ID embeds the key, and SHA-256 otherwise. It is written in base58btc, or as a
+//! CIDv1 with the `libp2p-key` codec in any multibase, such as the base36 of IPNS names.
+
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+use crate::encoding::{from_base58, to_base58};
+use crate::hash::HashAlgorithm;
+use crate::keys::asn1::{parse_spki, KeyAlgorithm};
+use crate::multiformats::decode_multibase;
+use crate::multihash::{compute_multihash, decode_multihash, encode_multihash, IDENTITY, SHA2_256};
+use crate::pem::ImportedPublicKey;
+use crate::protobuf::{write_varint, Message};
+#[cfg(test)]
+use crate::protobuf::{write_bytes_field, write_varint_field};
+use crate::ECDSAError;
+
+const KEY_TYPE_ED25519: u64 = 1;
+const KEY_TYPE_SECP256K1: u64 = 2;
+const KEY_TYPE_ECDSA: u64 = 3;
+
+/// Keys up to this length are inlined in the peer ID.
+const MAX_INLINE_KEY_LENGTH: usize = 42;
+/// CIDv1 with the `libp2p-key` multicodec.
+const CID_PREFIX: [u8; 2] = [0x01, 0x72];
+
+/// The signature domain and payload type of routing records (libp2p RFC 0003).
+const PEER_RECORD_DOMAIN: &str = "libp2p-peer-record";
+const PEER_RECORD_PAYLOAD_TYPE: &[u8] = &[0x03, 0x01];
+
+/// Decodes a `PublicKey` protobuf into the key type and public key: the raw Ed25519 key or a
+/// SEC1 point.
+///
+/// # Errors:
+/// * `InvalidLibp2pKey` if the message is malformed, or `InvalidPublicKey` if the key is.
+/// * `UnsupportedCurve` for RSA keys, ECDSA keys on curves other than P-256, and curves that
+///   are not enabled.
+pub(crate) fn decode_public_key(bytes: &[u8]) -> Result<(KeyAlgorithm, Vec<u8>), ECDSAError> {
+    let message = Message::decode(bytes).map_err(|_| ECDSAError::InvalidLibp2pKey)?;
+    let key_type = message.varint(1).ok().flatten().ok_or(ECDSAError::InvalidLibp2pKey)?;
+    let data = message.bytes(2).ok().flatten().ok_or(ECDSAError::InvalidLibp2pKey)?;
+    let (algorithm, public_key) = match key_type {
+        KEY_TYPE_ED25519 => (KeyAlgorithm::Ed25519, data.to_vec()),
+        KEY_TYPE_SECP256K1 if data.len() == 33 => (KeyAlgorithm::Secp256k1, data.to_vec()),
+        KEY_TYPE_SECP256K1 => return Err(ECDSAError::InvalidPublicKey),
+        KEY_TYPE_ECDSA => {
+            let spki = parse_spki(data)?;
+            // libp2p signs with SHA-256 whatever the curve, which only suits P-256
+            if spki.algorithm != KeyAlgorithm::P256 {
+                return Err(ECDSAError::UnsupportedCurve);
+            }
+            (spki.algorithm, spki.public_key.to_vec())
+        }
+        _ => return Err(ECDSAError::UnsupportedCurve),
+    };
+    algorithm.check_public_key(&public_key)?;
+    Ok((algorithm, public_key))
+}
+
+/// Encodes a public key as a `PublicKey` protobuf, compressing secp256k1 points.
+#[cfg(test)]
+pub(crate) fn encode_public_key(algorithm: KeyAlgorithm, public_key: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let (key_type, data) = match algorithm {
+        KeyAlgorithm::Ed25519 => (KEY_TYPE_ED25519, crate::multiformats::compressed(algorithm, public_key)?),
+        KeyAlgorithm::Secp256k1 => (KEY_TYPE_SECP256K1, crate::multiformats::compressed(algorithm, public_key)?),
+        KeyAlgorithm::P256 => {
+            algorithm.check_public_key(public_key)?;
+            (KEY_TYPE_ECDSA, crate::keys::asn1::encode_spki(algorithm, &algorithm.uncompressed(public_key)?))
+        }
+        _ => return Err(ECDSAError::UnsupportedCurve),
+    };
+    let mut encoded = Vec::new();
+    write_varint_field(&mut encoded, 1, key_type);
+    write_bytes_field(&mut encoded, 2, &data);
+    Ok(encoded)
+}
+
+/// Verifies a signature made with a libp2p key over `message`.
+pub(crate) fn verify_signature(
+    algorithm: KeyAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, ECDSAError> {
+    if algorithm == KeyAlgorithm::Ed25519 {
+        return match algorithm.verify_raw(public_key, message, signature) {
+            Err(ECDSAError::InvalidSignatureLength | ECDSAError::InvalidSignatureFormat) => Ok(false),
+            result => result,
+        };
+    }
+    match algorithm.raw_signature(signature) {
+        Some(raw) => algorithm.verify_raw(public_key, message, &raw),
+        None => Ok(false),
+    }
+}
+
+/// Derives the multihash a peer ID encodes from an encoded `PublicKey`.
+pub(crate) fn peer_id_multihash(public_key: &[u8]) -> Vec<u8> {
+    if public_key.len() <= MAX_INLINE_KEY_LENGTH {
+        encode_multihash(IDENTITY, public_key)
+    } else {
+        compute_multihash(HashAlgorithm::Sha256, public_key)
+    }
+}
+
+/// Parses a peer ID in either text form into its multihash.
+pub(crate) fn parse_peer_id(peer_id: &str) -> Result<Vec<u8>, ECDSAError> {
+    let multihash = if peer_id.starts_with('1') || peer_id.starts_with('Q') {
+        from_base58(peer_id)
+    } else {
+        decode_multibase(peer_id).ok().and_then(|(_, cid)| cid.strip_prefix(&CID_PREFIX[..]).map(<[u8]>::to_vec))
+    }
+    .ok_or(ECDSAError::InvalidPeerId)?;
+    match decode_multihash(&multihash) {
+        Ok((IDENTITY, key)) if key.len() <= MAX_INLINE_KEY_LENGTH => Ok(multihash),
+        Ok((SHA2_256, digest)) if digest.len() == 32 => Ok(multihash),
+        _ => Err(ECDSAError::InvalidPeerId),
+    }
+}
+
+/// Derives the peer ID of a libp2p public key.
+///
+/// # Arguments:
+/// * `public_key` - The `PublicKey` protobuf (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * The peer ID in base58btc, e.g. `12D3KooW...` for an Ed25519 key.
+///
+/// # Errors:
+/// * `InvalidLibp2pKey`, `InvalidPublicKey` or `UnsupportedCurve` if the key cannot be used.
+#[wasm_bindgen]
+pub fn peer_id_from_public_key(public_key: &[u8]) -> Result<String, ECDSAError> {
+    decode_public_key(public_key)?;
+    Ok(to_base58(&peer_id_multihash(public_key)))
+}
+
+/// Extracts the public key from a peer ID that embeds it, as Ed25519 and secp256k1 IDs do.
+///
+/// # Arguments:
+/// * `peer_id` - The peer ID in base58btc or as a multibase CID, e.g. `k51...`.
+///
+/// # Returns:
+/// * The key's curve and public key.
+///
+/// # Errors:
+/// * `InvalidPeerId` if the text is not a peer ID, or it hashes the key rather than embedding it.
+/// * `InvalidLibp2pKey`, `InvalidPublicKey` or `UnsupportedCurve` if the key cannot be used.
+#[wasm_bindgen]
+pub fn public_key_from_peer_id(peer_id: &str) -> Result<ImportedPublicKey, ECDSAError> {
+    let multihash = parse_peer_id(peer_id)?;
+    let (IDENTITY, key) = decode_multihash(&multihash)? else {
+        return Err(ECDSAError::InvalidPeerId);
+    };
+    let (algorithm, public_key) = decode_public_key(key)?;
+    Ok(ImportedPublicKey::new(algorithm, public_key))
+}
+
+/// Decodes a libp2p `PublicKey` protobuf.
+///
+/// # Arguments:
+/// * `public_key` - The encoded key (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * The key's curve and public key: a SEC1 point, or the raw key for Ed25519.
+///
+/// # Errors:
+/// * `InvalidLibp2pKey` if the message is malformed, or `InvalidPublicKey` if the key is.
+/// * `UnsupportedCurve` for RSA keys and ECDSA keys on curves other than P-256.
+#[wasm_bindgen]
+pub fn decode_libp2p_public_key(public_key: &[u8]) -> Result<ImportedPublicKey, ECDSAError> {
+    let (algorithm, public_key) = decode_public_key(public_key)?;
+    Ok(ImportedPublicKey::new(algorithm, public_key))
+}
+
+/// Checks that a peer ID belongs to a public key.
+///
+/// # Arguments:
+/// * `peer_id` - The peer ID in base58btc or as a multibase CID, e.g. `k51...`.
+/// * `public_key` - The `PublicKey` protobuf (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * A boolean indicating whether the peer ID was derived from the key.
+///
+/// # Errors:
+/// * `InvalidPeerId` if the text is not a peer ID.
+/// * `InvalidLibp2pKey`, `InvalidPublicKey` or `UnsupportedCurve` if the key cannot be used.
+#[wasm_bindgen]
+pub fn verify_peer_id(peer_id: &str, public_key: &[u8]) -> Result<bool, ECDSAError> {
+    let multihash = parse_peer_id(peer_id)?;
+    decode_public_key(public_key)?;
+    Ok(multihash == peer_id_multihash(public_key))
+}
+
+/// A signed envelope (libp2p RFC 0002) whose signature has been checked.
+#[wasm_bindgen]
+pub struct SignedEnvelope {
+    public_key: Vec<u8>,
+    payload_type: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl SignedEnvelope {
+    /// The signer's `PublicKey` protobuf.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    /// The signer's peer ID in base58btc.
+    #[wasm_bindgen(getter)]
+    pub fn peer_id(&self) -> String {
+        to_base58(&peer_id_multihash(&self.public_key))
+    }
+
+    /// The multicodec identifying the payload's format.
+    #[wasm_bindgen(getter)]
+    pub fn payload_type(&self) -> Vec<u8> {
+        self.payload_type.clone()
+    }
+
+    /// The signed payload.
+    #[wasm_bindgen(getter)]
+    pub fn payload(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
+}
+
+/// The bytes an envelope's signature covers: the domain, payload type and payload, each
+/// prefixed with its varint length.
+fn envelope_signing_input(domain: &str, payload_type: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut input = Vec::new();
+    for part in [domain.as_bytes(), payload_type, payload] {
+        write_varint(&mut input, part.len() as u64);
+        input.extend_from_slice(part);
+    }
+    input
+}
+
+/// Verifies a signed envelope.
+///
+/// # Arguments:
+/// * `envelope` - The `Envelope` protobuf (`Uint8Array` in JS).
+/// * `domain` - The signature domain the payload type calls for, e.g. `libp2p-peer-record`,
+///   which keeps signatures from being reused in another context.
+///
+/// # Returns:
+/// * The signer's key and peer ID, with the payload type and payload.
+///
+/// # Errors:
+/// * `InvalidSignedEnvelope` if the envelope is malformed.
+/// * `InvalidLibp2pKey`, `InvalidPublicKey` or `UnsupportedCurve` if the key cannot be used.
+/// * `EnvelopeSignatureMismatch` if the signature is invalid for `domain`.
+#[wasm_bindgen]
+pub fn verify_signed_envelope(envelope: &[u8], domain: &str) -> Result<SignedEnvelope, ECDSAError> {
+    let message = Message::decode(envelope).map_err(|_| ECDSAError::InvalidSignedEnvelope)?;
+    let field = |number| message.bytes(number).ok().flatten().ok_or(ECDSAError::InvalidSignedEnvelope);
+    let (public_key, payload_type, payload, signature) = (field(1)?, field(2)?, field(3)?, field(5)?);
+    let (algorithm, key) = decode_public_key(public_key)?;
+    if !verify_signature(algorithm, &key, &envelope_signing_input(domain, payload_type, payload), signature)? {
+        return Err(ECDSAError::EnvelopeSignatureMismatch);
+    }
+    Ok(SignedEnvelope { public_key: public_key.to_vec(), payload_type: payload_type.to_vec(), payload: payload.to_vec() })
+}
+
+/// A verified peer record (libp2p RFC 0003): the addresses a peer can be reached at.
+#[wasm_bindgen]
+pub struct PeerRecord {
+    peer_id: String,
+    sequence: u64,
+    addresses: Vec<Vec<u8>>,
+}
+
+#[wasm_bindgen]
+impl PeerRecord {
+    /// The peer ID in base58btc.
+    #[wasm_bindgen(getter)]
+    pub fn peer_id(&self) -> String {
+        self.peer_id.clone()
+    }
+
+    /// The sequence number: of two records from a peer, the higher one is current.
+    #[wasm_bindgen(getter)]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// The peer's addresses as binary multiaddrs.
+    #[wasm_bindgen(getter)]
+    pub fn addresses(&self) -> Vec<Uint8Array> {
+        self.addresses.iter().map(|address| Uint8Array::from(&address[..])).collect()
+    }
+}
+
+/// Verifies a signed peer record, as exchanged by the identify protocol and rendezvous.
+///
+/// # Arguments:
+/// * `envelope` - The signed envelope holding the record (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * The peer ID, sequence number and addresses of the peer that signed the record.
+///
+/// # Errors:
+/// * `InvalidSignedEnvelope` if the envelope or record is malformed, or the payload is not a
+///   peer record.
+/// * `EnvelopeSignatureMismatch` if the signature is invalid.
+/// * `InvalidPeerId` if the record describes a peer other than the signer.
+#[wasm_bindgen]
+pub fn verify_peer_record(envelope: &[u8]) -> Result<PeerRecord, ECDSAError> {
+    let envelope = verify_signed_envelope(envelope, PEER_RECORD_DOMAIN)?;
+    if envelope.payload_type != PEER_RECORD_PAYLOAD_TYPE {
+        return Err(ECDSAError::InvalidSignedEnvelope);
+    }
+    let record = Message::decode(&envelope.payload).map_err(|_| ECDSAError::InvalidSignedEnvelope)?;
+    // The record must describe the peer that signed it
+    if record.bytes(1).ok().flatten() != Some(&peer_id_multihash(&envelope.public_key)[..]) {
+        return Err(ECDSAError::InvalidPeerId);
+    }
+    let infos = record.repeated_bytes(3).map_err(|_| ECDSAError::InvalidSignedEnvelope)?;
+    let addresses = infos
+        .into_iter()
+        .map(|info| {
+            let info = Message::decode(info).map_err(|_| ECDSAError::InvalidSignedEnvelope)?;
+            info.bytes(1).ok().flatten().map(<[u8]>::to_vec).ok_or(ECDSAError::InvalidSignedEnvelope)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(PeerRecord {
+        peer_id: envelope.peer_id(),
+        sequence: record.varint(2).map_err(|_| ECDSAError::InvalidSignedEnvelope)?.unwrap_or_default(),
+        addresses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use sha2::{Digest, Sha256};
+    
+    // Test vectors from the libp2p peer ID specification
+    const ED25519: &str = "080112201ed1e8fae2c4a144b8be8fd4b47bf3d3b34b871c3cacf6010f0e42d474fce27e";
+    const SECP256K1: &str = "08021221037777e994e452c21604f91de093ce415f5432f701dd8cd1a7a6fea0e630bfca99";
+
+    #[test]
+    fn test_decode_public_keys() {
+        let mut vectors = vec![(SECP256K1, KeyAlgorithm::Secp256k1)];
+        if cfg!(feature = "ed25519") {
+            vectors.push((ED25519, KeyAlgorithm::Ed25519));
+        }
+        for (encoded, algorithm) in vectors {
+            let bytes = hex::decode(encoded).unwrap();
+            let (decoded, public_key) = decode_public_key(&bytes).unwrap();
+            assert_eq!(decoded, algorithm);
+            assert_eq!(public_key, bytes[4..]);
+            assert_eq!(encode_public_key(algorithm, &public_key).unwrap(), bytes);
+        }
+
+        #[cfg(feature = "p256")]
+        {
+            // The generator
+            let generator = hex::decode("036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296").unwrap();
+            let point = KeyAlgorithm::P256.uncompressed(&generator).unwrap();
+            let encoded = encode_public_key(KeyAlgorithm::P256, &point).unwrap();
+            assert_eq!(encoded[..3], [0x08, 0x03, 0x12]);
+            assert_eq!(decode_public_key(&encoded).unwrap(), (KeyAlgorithm::P256, point));
+        }
+    }
+
+    #[test]
+    fn test_rejects_unsupported_keys() {
+        // An RSA key, a missing key and an uncompressed secp256k1 key
+        assert!(matches!(decode_public_key(&hex::decode("08001203010203").unwrap()), Err(ECDSAError::UnsupportedCurve)));
+        assert!(matches!(decode_public_key(&hex::decode("0801").unwrap()), Err(ECDSAError::InvalidLibp2pKey)));
+        let key = SigningKey::from_bytes(&[1; 32]).unwrap();
+        let mut encoded = vec![0x08, 0x02, 0x12, 0x41];
+        encoded.extend(KeyAlgorithm::Secp256k1.uncompressed(&key.verifying_key().to_bytes()).unwrap());
+        assert!(matches!(decode_public_key(&encoded), Err(ECDSAError::InvalidPublicKey)));
+        assert!(matches!(decode_public_key(&hex::decode(&ED25519[..20]).unwrap()), Err(ECDSAError::InvalidLibp2pKey)));
+    }
+
+    #[test]
+    fn test_verify_secp256k1_signature() {
+        let key = SigningKey::from_bytes(&[1; 32]).unwrap();
+        let public_key = key.verifying_key().to_bytes().to_vec();
+        let (signature, _) = sign_digest(&key, &Sha256::digest(b"libp2p"), &[]).unwrap();
+        let der = signature.to_der();
+        assert!(verify_signature(KeyAlgorithm::Secp256k1, &public_key, b"libp2p", der.as_bytes()).unwrap());
+        assert!(!verify_signature(KeyAlgorithm::Secp256k1, &public_key, b"libp2p!", der.as_bytes()).unwrap());
+        assert!(!verify_signature(KeyAlgorithm::Secp256k1, &public_key, b"libp2p", signature.as_ref()).unwrap());
+    }
+
+    #[test]
+    fn test_peer_ids() {
+        let secp256k1 = hex::decode(SECP256K1).unwrap();
+        let peer_id = peer_id_from_public_key(&secp256k1).unwrap();
+        assert!(peer_id.starts_with("16Uiu2HA"), "{peer_id}");
+        assert!(verify_peer_id(&peer_id, &secp256k1).unwrap());
+        assert_eq!(public_key_from_peer_id(&peer_id).unwrap().public_key(), hex::encode(&secp256k1[4..]));
+        // The CID form names the same peer
+        let cid = [&CID_PREFIX[..], &peer_id_multihash(&secp256k1)].concat();
+        for prefix in ['b', 'k'] {
+            let cid = crate::multiformats::encode_multibase(prefix, &cid).unwrap();
+            assert!(verify_peer_id(&cid, &secp256k1).unwrap(), "{cid}");
+        }
+
+        #[cfg(feature = "ed25519")]
+        {
+            let ed25519 = hex::decode(ED25519).unwrap();
+            let peer_id = peer_id_from_public_key(&ed25519).unwrap();
+            assert!(peer_id.starts_with("12D3KooW"), "{peer_id}");
+            assert!(!verify_peer_id(&peer_id, &secp256k1).unwrap());
+        }
+
+        // ECDSA keys are too long to inline, so their IDs only commit to the key
+        #[cfg(feature = "p256")]
+        {
+            let generator = hex::decode("036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296").unwrap();
+            let ecdsa = encode_public_key(KeyAlgorithm::P256, &generator).unwrap();
+            let peer_id = peer_id_from_public_key(&ecdsa).unwrap();
+            assert!(peer_id.starts_with("Qm"), "{peer_id}");
+            assert!(verify_peer_id(&peer_id, &ecdsa).unwrap());
+            assert!(matches!(public_key_from_peer_id(&peer_id), Err(ECDSAError::InvalidPeerId)));
+        }
+
+        for peer_id in ["", "12D3", "bafzaa", "QmInvalid0", &peer_id[..peer_id.len() - 1]] {
+            assert!(matches!(public_key_from_peer_id(peer_id), Err(ECDSAError::InvalidPeerId)), "{peer_id}");
+        }
+    }
+
+    fn envelope(key: &SigningKey, domain: &str, payload_type: &[u8], payload: &[u8]) -> Vec<u8> {
+        let public_key = encode_public_key(KeyAlgorithm::Secp256k1, &key.verifying_key().to_bytes()).unwrap();
+        let digest = Sha256::digest(envelope_signing_input(domain, payload_type, payload));
+        let (signature, _) = sign_digest(key, &digest, &[]).unwrap();
+        let mut envelope = Vec::new();
+        write_bytes_field(&mut envelope, 1, &public_key);
+        write_bytes_field(&mut envelope, 2, payload_type);
+        write_bytes_field(&mut envelope, 3, payload);
+        write_bytes_field(&mut envelope, 5, signature.to_der().as_bytes());
+        envelope
+    }
+
+    fn peer_record(key: &SigningKey, addresses: &[&str]) -> Vec<u8> {
+        let public_key = encode_public_key(KeyAlgorithm::Secp256k1, &key.verifying_key().to_bytes()).unwrap();
+        let mut record = Vec::new();
+        write_bytes_field(&mut record, 1, &peer_id_multihash(&public_key));
+        write_varint_field(&mut record, 2, 1_700_000_000_000);
+        for address in addresses {
+            let mut info = Vec::new();
+            write_bytes_field(&mut info, 1, &hex::decode(address).unwrap());
+            write_bytes_field(&mut record, 3, &info);
+        }
+        record
+    }
+
+    #[test]
+    fn test_verify_peer_record() {
+        // /ip4/127.0.0.1/tcp/4001 and /ip4/127.0.0.1/udp/4001/quic-v1
+        let addresses = ["047f000001060fa1", "047f000001910215a10fcc03"];
+        let key = SigningKey::from_bytes(&[2; 32]).unwrap();
+        let signed = envelope(&key, PEER_RECORD_DOMAIN, PEER_RECORD_PAYLOAD_TYPE, &peer_record(&key, &addresses));
+        let record = verify_peer_record(&signed).unwrap();
+        assert_eq!(record.peer_id, verify_signed_envelope(&signed, PEER_RECORD_DOMAIN).unwrap().peer_id());
+        assert_eq!(record.sequence(), 1_700_000_000_000);
+        assert_eq!(record.addresses, addresses.map(|address| hex::decode(address).unwrap()));
+
+        // Signatures are bound to their domain, and records to their signer
+        assert!(matches!(verify_signed_envelope(&signed, "libp2p-routing-state"), Err(ECDSAError::EnvelopeSignatureMismatch)));
+        let other = SigningKey::from_bytes(&[3; 32]).unwrap();
+        let misattributed = envelope(&key, PEER_RECORD_DOMAIN, PEER_RECORD_PAYLOAD_TYPE, &peer_record(&other, &addresses));
+        assert!(matches!(verify_peer_record(&misattributed), Err(ECDSAError::InvalidPeerId)));
+        let retyped = envelope(&key, PEER_RECORD_DOMAIN, b"/json", &peer_record(&key, &addresses));
+        assert!(matches!(verify_peer_record(&retyped), Err(ECDSAError::InvalidSignedEnvelope)));
+        assert!(matches!(verify_peer_record(&signed[..signed.len() - 1]), Err(ECDSAError::InvalidSignedEnvelope)));
+    }
+}
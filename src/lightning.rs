@@ -0,0 +1,152 @@
+//! Lightning Network signatures: BOLT11 invoices and LNURL-auth logins.
+//!
+//! A BOLT11 invoice is Bech32 text without the usual 90 character limit. Its data part is a
+//! 35-bit timestamp, tagged fields and a trailing 65-byte recoverable signature over
+//! `SHA-256(hrp || data)`, with the 5-bit data zero-padded to a whole number of bytes.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::VerifyingKey;
+use sha2::{Digest, Sha256};
+
+use crate::encoding::{convert_bits, from_bech32_with_limit, Bech32Variant};
+use crate::keys::parse_public_key;
+use crate::signature::{parse_compact, parse_der};
+use crate::{recover_from_digest, verify_digest, ECDSAError};
+
+const TIMESTAMP_WORDS: usize = 7;
+const SIGNATURE_WORDS: usize = 104;
+/// Tag of the `n` field, the payee node's 33-byte public key.
+const PAYEE_TAG: u8 = 19;
+const PAYEE_WORDS: usize = 53;
+
+/// Returns the payee public key from the `n` tagged field, if present.
+/// Fields of an unexpected length are skipped, as BOLT11 requires.
+fn payee_field(mut fields: &[u8]) -> Result<Option<VerifyingKey>, ECDSAError> {
+    while !fields.is_empty() {
+        if fields.len() < 3 {
+            return Err(ECDSAError::InvalidInvoice);
+        }
+        let length = fields[1] as usize * 32 + fields[2] as usize;
+        let data = fields.get(3..3 + length).ok_or(ECDSAError::InvalidInvoice)?;
+        if fields[0] == PAYEE_TAG && length == PAYEE_WORDS {
+            let key = convert_bits(data, 5, 8, false).ok_or(ECDSAError::InvalidInvoice)?;
+            return parse_public_key(&key).map(Some);
+        }
+        fields = &fields[3 + length..];
+    }
+    Ok(None)
+}
+
+/// Verifies the signature on a BOLT11 Lightning invoice and returns the payee node key.
+///
+/// When the invoice carries an `n` field, the signature is checked against that key;
+/// otherwise the key is recovered from the signature. Either way the caller should compare
+/// the result with the node it expects to pay.
+///
+/// # Arguments:
+/// * `invoice` - The invoice, e.g. `lnbc2500u1...`, without a `lightning:` prefix.
+///
+/// # Returns:
+/// * The payee node's compressed public key, as a hex string.
+///
+/// # Errors:
+/// * Returns an error if the invoice is malformed, or its signature does not match its `n` field.
+#[wasm_bindgen]
+pub fn verify_bolt11(invoice: &str) -> Result<String, ECDSAError> {
+    let (hrp, values, variant) = from_bech32_with_limit(invoice.trim(), usize::MAX).ok_or(ECDSAError::InvalidInvoice)?;
+    if variant != Bech32Variant::Bech32 || !hrp.starts_with("ln") || values.len() < TIMESTAMP_WORDS + SIGNATURE_WORDS {
+        return Err(ECDSAError::InvalidInvoice);
+    }
+
+    let (data, signature_words) = values.split_at(values.len() - SIGNATURE_WORDS);
+    let signature = convert_bits(signature_words, 5, 8, false).ok_or(ECDSAError::InvalidInvoice)?;
+    let sig = parse_compact(&signature[..64])?;
+    let recovery_id = signature[64];
+
+    let mut signed_data = hrp.into_bytes();
+    signed_data.extend(convert_bits(data, 5, 8, true).ok_or(ECDSAError::InvalidInvoice)?);
+    let digest = Sha256::digest(&signed_data);
+
+    let payee = match payee_field(&data[TIMESTAMP_WORDS..])? {
+        Some(payee) if verify_digest(&payee, &digest, &sig) => payee,
+        Some(_) => return Err(ECDSAError::InvalidInvoiceSignature),
+        None => recover_from_digest(&digest, &sig, recovery_id)?,
+    };
+    Ok(hex::encode(payee.to_bytes()))
+}
+
+/// Verifies an LNURL-auth (LUD-04) login signature.
+///
+/// The wallet signs the server's challenge `k1` directly as a 32-byte digest, without
+/// hashing it again, using the linking key derived for the service's domain.
+///
+/// # Arguments:
+/// * `k1` - The 32-byte challenge from the login URL, as a hex string.
+/// * `signature_der` - The wallet's `sig` parameter: a strict DER signature, as a hex string.
+/// * `linking_key` - The wallet's `key` parameter: a compressed public key, as a hex string.
+///
+/// # Returns:
+/// * A boolean indicating whether the signature is valid or not.
+///
+/// # Errors:
+/// * Returns an error if `k1` is not 32 bytes of hex, the signature is not strict DER,
+///   or the linking key is not a valid compressed public key.
+#[wasm_bindgen]
+pub fn verify_lnurl_auth(k1: &str, signature_der: &str, linking_key: &str) -> Result<bool, ECDSAError> {
+    let k1 = hex::decode(k1).map_err(|_| ECDSAError::InvalidDigestLength)?;
+    if k1.len() != 32 {
+        return Err(ECDSAError::InvalidDigestLength);
+    }
+    let signature = hex::decode(signature_der).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    let sig = parse_der(&signature)?;
+    let key = hex::decode(linking_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    if key.len() != 33 {
+        return Err(ECDSAError::InvalidPublicKey);
+    }
+    Ok(verify_digest(&parse_public_key(&key)?, &k1, &sig))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+    // BOLT11 example: "Please make a donation of any amount using payment_hash 0001020304050607080900010203040506070809000102030405060708090102 to me @03e7156ae33b0a208d0744199163177e909e80176e55d97a2f221ede0f934dd9ad"
+    const DONATION: &str = "lnbc1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdpl2pkx2ctnv5sxxmmwwd5kgetjypeh2ursdae8g6twvus8g6rfwvs8qun0dfjkxaq8rkx3yf5tcsyz3d73gafnh3cax9rn449d9p5uxz9ezhhypd0elx87sjle52x86fux2ypatgddc6k63n7erqz25le42c4u4ecky03ylcqca784w";
+    const NODE: &str = "03e7156ae33b0a208d0744199163177e909e80176e55d97a2f221ede0f934dd9ad";
+
+    #[test]
+    fn test_recovers_payee() {
+        assert_eq!(verify_bolt11(DONATION).unwrap(), NODE);
+        assert_eq!(verify_bolt11(&DONATION.to_uppercase()).unwrap(), NODE);
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        // Flipped checksum character
+        let corrupted = format!("{}x", &DONATION[..DONATION.len() - 1]);
+        assert!(matches!(verify_bolt11(&corrupted), Err(ECDSAError::InvalidInvoice)));
+        assert!(verify_bolt11("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+        assert!(verify_bolt11("").is_err());
+    }
+
+    #[test]
+    fn test_lnurl_auth() {
+        let key = SigningKey::from_bytes(&[0x17; 32]).unwrap();
+        let linking_key = hex::encode(key.verifying_key().to_bytes());
+        let k1 = "e2af6254a8df433264fa23f67eb8188635d15ce883e8fc020989d5f82ae6f11e";
+        let (sig, _) = sign_digest(&key, &hex::decode(k1).unwrap(), &[]).unwrap();
+        let signature = hex::encode(sig.to_der().as_bytes());
+
+        assert!(verify_lnurl_auth(k1, &signature, &linking_key).unwrap());
+        let other_k1 = "00af6254a8df433264fa23f67eb8188635d15ce883e8fc020989d5f82ae6f11e";
+        assert!(!verify_lnurl_auth(other_k1, &signature, &linking_key).unwrap());
+
+        let uncompressed = hex::encode(key.verifying_key().to_encoded_point(false).as_bytes());
+        assert!(matches!(verify_lnurl_auth(k1, &signature, &uncompressed), Err(ECDSAError::InvalidPublicKey)));
+        assert!(matches!(verify_lnurl_auth(&k1[2..], &signature, &linking_key), Err(ECDSAError::InvalidDigestLength)));
+        assert!(verify_lnurl_auth(k1, &hex::encode(sig.as_ref()), &linking_key).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Binary Merkle trees over caller-supplied leaves, with inclusion proofs for airdrop claims
+//! and batches of signed data.
+//!
+//! Trees are built layer by layer, promoting the last node of an odd-sized layer unchanged
+//! rather than pairing it with itself. OpenZeppelin-compatible trees instead use the complete
+//! binary tree layout of `@openzeppelin/merkle-tree`, whose proofs `MerkleProof.sol` accepts.
+
+use wasm_bindgen::prelude::*;
+
+use crate::hash::{digest, HashAlgorithm};
+use crate::ECDSAError;
+
+/// How the nodes of a tree are arranged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Layout {
+    /// Layers of halving size, with odd nodes promoted.
+    Layered,
+    /// OpenZeppelin's array layout: node `i` has children `2i + 1` and `2i + 2`, and the
+    /// leaves fill the end of the array in reverse.
+    Complete,
+}
+
+/// Parses a hex node, with or without a `0x` prefix.
+fn parse_node(node: &str) -> Option<Vec<u8>> {
+    hex::decode(node.trim().trim_start_matches("0x")).ok()
+}
+
+/// Hashes two sibling nodes, ordering them bytewise first if `sort_pairs` is set.
+fn hash_pair(hash_algorithm: HashAlgorithm, sort_pairs: bool, left: &[u8], right: &[u8]) -> Vec<u8> {
+    let (left, right) = if sort_pairs && right < left { (right, left) } else { (left, right) };
+    digest(hash_algorithm, &[left, right].concat())
+}
+
+/// A Merkle tree with its nodes precomputed, so proofs are read off without rehashing.
+#[wasm_bindgen]
+pub struct MerkleTree {
+    hash_algorithm: HashAlgorithm,
+    sort_pairs: bool,
+    layout: Layout,
+    /// The leaves in the order they were given.
+    leaves: Vec<Vec<u8>>,
+    /// For the layered layout, each layer from the leaves up; for the complete layout, a
+    /// single layer holding the whole array.
+    layers: Vec<Vec<Vec<u8>>>,
+    /// The node index of each leaf.
+    positions: Vec<usize>,
+}
+
+impl MerkleTree {
+    fn build(leaves: &[String], hash_algorithm: HashAlgorithm, sort_pairs: bool, layout: Layout) -> Result<MerkleTree, ECDSAError> {
+        let leaves = leaves.iter().map(|leaf| parse_node(leaf)).collect::<Option<Vec<_>>>().ok_or(ECDSAError::InvalidMerkleTree)?;
+        if leaves.is_empty() {
+            return Err(ECDSAError::InvalidMerkleTree);
+        }
+        let hash = |left: &[u8], right: &[u8]| hash_pair(hash_algorithm, sort_pairs, left, right);
+
+        let (layers, positions) = match layout {
+            Layout::Layered => {
+                let mut layers = vec![leaves.clone()];
+                while let Some(layer) = layers.last().filter(|layer| layer.len() > 1) {
+                    let next = layer
+                        .chunks(2)
+                        .map(|pair| match pair {
+                            [left, right] => hash(left, right),
+                            [node] => node.clone(),
+                            _ => unreachable!(),
+                        })
+                        .collect();
+                    layers.push(next);
+                }
+                (layers, (0..leaves.len()).collect())
+            }
+            Layout::Complete => {
+                // The leaves are sorted so that a tree depends only on the set of leaves
+                let mut order: Vec<usize> = (0..leaves.len()).collect();
+                order.sort_by(|&a, &b| leaves[a].cmp(&leaves[b]));
+                let size = 2 * leaves.len() - 1;
+                let mut nodes = vec![Vec::new(); size];
+                let mut positions = vec![0; leaves.len()];
+                for (rank, &leaf) in order.iter().enumerate() {
+                    positions[leaf] = size - 1 - rank;
+                    nodes[size - 1 - rank] = leaves[leaf].clone();
+                }
+                for i in (0..size - leaves.len()).rev() {
+                    nodes[i] = hash(&nodes[2 * i + 1], &nodes[2 * i + 2]);
+                }
+                (vec![nodes], positions)
+            }
+        };
+        Ok(MerkleTree { hash_algorithm, sort_pairs, layout, leaves, layers, positions })
+    }
+
+    fn root_node(&self) -> &[u8] {
+        match self.layout {
+            Layout::Layered => &self.layers[self.layers.len() - 1][0],
+            Layout::Complete => &self.layers[0][0],
+        }
+    }
+
+    /// The sibling nodes from a leaf up to the root.
+    fn proof_nodes(&self, index: usize) -> Option<Vec<&[u8]>> {
+        let mut position = *self.positions.get(index)?;
+        let mut proof = Vec::new();
+        match self.layout {
+            Layout::Layered => {
+                for layer in &self.layers[..self.layers.len() - 1] {
+                    if let Some(sibling) = layer.get(position ^ 1) {
+                        proof.push(sibling.as_slice());
+                    }
+                    position /= 2;
+                }
+            }
+            Layout::Complete => {
+                let nodes = &self.layers[0];
+                while position > 0 {
+                    let sibling = if position % 2 == 1 { position + 1 } else { position - 1 };
+                    proof.push(nodes[sibling].as_slice());
+                    position = (position - 1) / 2;
+                }
+            }
+        }
+        Some(proof)
+    }
+
+    /// Computes the root a proof leads to from the leaf at `index`, following this tree's
+    /// shape, or `None` if the proof has the wrong number of nodes.
+    fn root_from_proof(&self, index: usize, leaf: &[u8], proof: &[Vec<u8>]) -> Option<Vec<u8>> {
+        let mut position = *self.positions.get(index)?;
+        let mut siblings = proof.iter();
+        let mut node = leaf.to_vec();
+        let hash = |left: &[u8], right: &[u8]| hash_pair(self.hash_algorithm, self.sort_pairs, left, right);
+        match self.layout {
+            Layout::Layered => {
+                for layer in &self.layers[..self.layers.len() - 1] {
+                    if position ^ 1 < layer.len() {
+                        let sibling = siblings.next()?;
+                        node = if position % 2 == 0 { hash(&node, sibling) } else { hash(sibling, &node) };
+                    }
+                    position /= 2;
+                }
+            }
+            Layout::Complete => {
+                while position > 0 {
+                    let sibling = siblings.next()?;
+                    node = if position % 2 == 1 { hash(&node, sibling) } else { hash(sibling, &node) };
+                    position = (position - 1) / 2;
+                }
+            }
+        }
+        siblings.next().is_none().then_some(node)
+    }
+}
+
+#[wasm_bindgen]
+impl MerkleTree {
+    /// Builds a tree over `leaves`, which are used as given, so callers hash their records
+    /// into leaves first.
+    ///
+    /// # Arguments:
+    /// * `leaves` - The leaves, provided as hex strings.
+    /// * `hash_algorithm` - The hash function for inner nodes, defaulting to SHA-256.
+    /// * `sort_pairs` - Whether to order each pair of siblings bytewise before hashing, which
+    ///   lets proofs be checked without knowing the leaf's position.
+    ///
+    /// # Errors:
+    /// * `InvalidMerkleTree` if there are no leaves or a leaf is not valid hex.
+    #[wasm_bindgen(constructor)]
+    pub fn new(leaves: Vec<String>, hash_algorithm: Option<HashAlgorithm>, sort_pairs: bool) -> Result<MerkleTree, ECDSAError> {
+        MerkleTree::build(&leaves, hash_algorithm.unwrap_or_default(), sort_pairs, Layout::Layered)
+    }
+
+    /// Builds a tree with the same root and proofs as OpenZeppelin's `SimpleMerkleTree`:
+    /// Keccak-256 over sorted leaves and sorted pairs, in the complete binary tree layout.
+    /// For a `StandardMerkleTree`, pass each leaf as `keccak256(keccak256(abi.encode(values)))`.
+    ///
+    /// # Arguments:
+    /// * `leaves` - The 32-byte leaves, provided as hex strings.
+    ///
+    /// # Errors:
+    /// * `InvalidMerkleTree` if there are no leaves or a leaf is not 32 bytes of hex.
+    pub fn open_zeppelin(leaves: Vec<String>) -> Result<MerkleTree, ECDSAError> {
+        if leaves.iter().any(|leaf| parse_node(leaf).is_none_or(|leaf| leaf.len() != 32)) {
+            return Err(ECDSAError::InvalidMerkleTree);
+        }
+        MerkleTree::build(&leaves, HashAlgorithm::Keccak256, true, Layout::Complete)
+    }
+
+    /// The root of the tree, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn root(&self) -> String {
+        hex::encode(self.root_node())
+    }
+
+    /// The number of leaves.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// The leaves in the order they were given, as hex strings.
+    #[wasm_bindgen(getter)]
+    pub fn leaves(&self) -> Vec<String> {
+        self.leaves.iter().map(hex::encode).collect()
+    }
+
+    /// Generates the inclusion proof of a leaf.
+    ///
+    /// # Arguments:
+    /// * `index` - The position of the leaf in the list the tree was built from.
+    ///
+    /// # Returns:
+    /// * The sibling nodes from the leaf up to the root, as hex strings.
+    ///
+    /// # Errors:
+    /// * `InvalidMerkleProof` if there is no leaf at `index`.
+    pub fn proof(&self, index: usize) -> Result<Vec<String>, ECDSAError> {
+        let proof = self.proof_nodes(index).ok_or(ECDSAError::InvalidMerkleProof)?;
+        Ok(proof.into_iter().map(hex::encode).collect())
+    }
+
+    /// Checks a proof against this tree's root, following the tree's shape.
+    ///
+    /// # Arguments:
+    /// * `index` - The position of the leaf in the list the tree was built from.
+    /// * `leaf` - The claimed leaf, provided as a hex string.
+    /// * `proof` - The sibling nodes from the leaf up, provided as hex strings.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the proof leads from `leaf` to the root.
+    ///
+    /// # Errors:
+    /// * `InvalidMerkleProof` if there is no leaf at `index` or a node is not valid hex.
+    pub fn verify(&self, index: usize, leaf: &str, proof: Vec<String>) -> Result<bool, ECDSAError> {
+        if index >= self.leaves.len() {
+            return Err(ECDSAError::InvalidMerkleProof);
+        }
+        let leaf = parse_node(leaf).ok_or(ECDSAError::InvalidMerkleProof)?;
+        let proof = proof.iter().map(|node| parse_node(node)).collect::<Option<Vec<_>>>().ok_or(ECDSAError::InvalidMerkleProof)?;
+        Ok(self.root_from_proof(index, &leaf, &proof).is_some_and(|root| root == self.root_node()))
+    }
+}
+
+/// Verifies a proof from a tree with sorted pairs, which needs only the root, as
+/// OpenZeppelin's `MerkleProof.verify` does on-chain.
+///
+/// # Arguments:
+/// * `root` - The tree's root, provided as a hex string.
+/// * `leaf` - The claimed leaf, provided as a hex string.
+/// * `proof` - The sibling nodes from the leaf up, provided as hex strings.
+/// * `hash_algorithm` - The tree's hash function, defaulting to SHA-256; OpenZeppelin trees
+///   use Keccak-256.
+///
+/// # Returns:
+/// * A boolean indicating whether the proof leads from `leaf` to `root`.
+///
+/// # Errors:
+/// * `InvalidMerkleProof` if a node is not valid hex.
+#[wasm_bindgen]
+pub fn verify_sorted_merkle_proof(root: &str, leaf: &str, proof: Vec<String>, hash_algorithm: Option<HashAlgorithm>) -> Result<bool, ECDSAError> {
+    let hash_algorithm = hash_algorithm.unwrap_or_default();
+    let root = parse_node(root).ok_or(ECDSAError::InvalidMerkleProof)?;
+    let leaf = parse_node(leaf).ok_or(ECDSAError::InvalidMerkleProof)?;
+    let computed = proof.iter().try_fold(leaf, |node, sibling| {
+        let sibling = parse_node(sibling).ok_or(ECDSAError::InvalidMerkleProof)?;
+        Ok::<_, ECDSAError>(hash_pair(hash_algorithm, true, &node, &sibling))
+    })?;
+    Ok(computed == root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<String> {
+        (0..count).map(|i| hex::encode(digest(HashAlgorithm::Keccak256, &[i]))).collect()
+    }
+
+    fn node(leaf: &str) -> Vec<u8> {
+        hex::decode(leaf).unwrap()
+    }
+
+    #[test]
+    fn test_layered_tree() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(leaves.clone(), None, false).unwrap();
+        let hash = |left: &[u8], right: &[u8]| hash_pair(HashAlgorithm::Sha256, false, left, right);
+        // The fifth leaf is promoted until it has a sibling
+        let expected = hash(&hash(&hash(&node(&leaves[0]), &node(&leaves[1])), &hash(&node(&leaves[2]), &node(&leaves[3]))), &node(&leaves[4]));
+        assert_eq!(tree.root(), hex::encode(expected));
+        assert_eq!(tree.length(), 5);
+        assert_eq!(tree.leaves(), leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.len(), if index == 4 { 1 } else { 3 });
+            assert!(tree.verify(index, leaf, proof.clone()).unwrap(), "{index}");
+            // Unsorted pairs bind the leaf to its position
+            assert!(!tree.verify((index + 1) % 5, leaf, proof).unwrap());
+        }
+        let single = MerkleTree::new(vec![leaves[0].clone()], None, false).unwrap();
+        assert_eq!(single.root(), leaves[0]);
+        assert!(single.proof(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sorted_pairs() {
+        let leaves = leaves(6);
+        let tree = MerkleTree::new(leaves.clone(), Some(HashAlgorithm::Sha3_256), true).unwrap();
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(verify_sorted_merkle_proof(&tree.root(), leaf, proof.clone(), Some(HashAlgorithm::Sha3_256)).unwrap());
+            assert!(!verify_sorted_merkle_proof(&tree.root(), leaf, proof, None).unwrap());
+        }
+        assert!(!verify_sorted_merkle_proof(&tree.root(), &leaves[0], tree.proof(1).unwrap(), Some(HashAlgorithm::Sha3_256)).unwrap());
+    }
+
+    #[test]
+    fn test_open_zeppelin_tree() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::open_zeppelin(leaves.iter().map(|leaf| format!("0x{leaf}")).collect()).unwrap();
+        // The sorted leaves fill nodes 8 down to 4, and nodes 3 and 2 pair them up from the end
+        let mut sorted: Vec<Vec<u8>> = leaves.iter().map(|leaf| node(leaf)).collect();
+        sorted.sort();
+        let hash = |left: &[u8], right: &[u8]| hash_pair(HashAlgorithm::Keccak256, true, left, right);
+        let node1 = hash(&hash(&sorted[1], &sorted[0]), &sorted[4]);
+        let node2 = hash(&sorted[3], &sorted[2]);
+        assert_eq!(tree.root(), hex::encode(hash(&node1, &node2)));
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(tree.verify(index, leaf, proof.clone()).unwrap());
+            assert!(verify_sorted_merkle_proof(&format!("0x{}", tree.root()), leaf, proof, Some(HashAlgorithm::Keccak256)).unwrap());
+        }
+        // The root depends only on the set of leaves
+        let reversed = MerkleTree::open_zeppelin(leaves.iter().rev().cloned().collect()).unwrap();
+        assert_eq!(reversed.root(), tree.root());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        assert!(matches!(MerkleTree::new(vec![], None, false), Err(ECDSAError::InvalidMerkleTree)));
+        assert!(matches!(MerkleTree::new(vec!["zz".into()], None, false), Err(ECDSAError::InvalidMerkleTree)));
+        assert!(matches!(MerkleTree::open_zeppelin(vec!["abcd".into()]), Err(ECDSAError::InvalidMerkleTree)));
+
+        let leaves = leaves(4);
+        let tree = MerkleTree::new(leaves.clone(), None, false).unwrap();
+        assert!(matches!(tree.proof(4), Err(ECDSAError::InvalidMerkleProof)));
+        assert!(matches!(tree.verify(4, &leaves[0], vec![]), Err(ECDSAError::InvalidMerkleProof)));
+        // Proofs with missing or extra nodes
+        let mut proof = tree.proof(0).unwrap();
+        assert!(!tree.verify(0, &leaves[0], proof[..1].to_vec()).unwrap());
+        proof.push(leaves[0].clone());
+        assert!(!tree.verify(0, &leaves[0], proof).unwrap());
+    }
+}
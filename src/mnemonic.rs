@@ -0,0 +1,232 @@
+//! BIP-39 mnemonic phrases with the English wordlist.
+//!
+//! A phrase encodes 128 to 256 bits of entropy plus a SHA-256 checksum of `entropy_bits / 32`
+//! bits, 11 bits per word. The wallet seed is PBKDF2-HMAC-SHA512 over the phrase, salted
+//! with `"mnemonic" || passphrase`, at 2048 iterations.
+
+use std::sync::OnceLock;
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::kdf::pbkdf2_hmac_sha512;
+use crate::random::fill_random;
+use crate::ECDSAError;
+
+const SEED_ITERATIONS: u32 = 2048;
+const SEED_LENGTH: usize = 64;
+
+/// The 2048 words of the BIP-39 English list, in index order.
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| include_str!("wordlist/english.txt").lines().collect())
+}
+
+/// Encodes entropy of 16 to 32 bytes (a multiple of 4) as word indices.
+fn entropy_to_indices(entropy: &[u8]) -> Result<Vec<u16>, ECDSAError> {
+    if !(16..=32).contains(&entropy.len()) || !entropy.len().is_multiple_of(4) {
+        return Err(ECDSAError::InvalidEntropy);
+    }
+
+    let checksum = Sha256::digest(entropy)[0];
+    let mut bits = entropy.to_vec();
+    bits.push(checksum);
+
+    let word_count = entropy.len() * 3 / 4;
+    let indices = (0..word_count)
+        .map(|word| {
+            (0..11).fold(0u16, |index, offset| {
+                let bit = word * 11 + offset;
+                (index << 1) | ((bits[bit / 8] >> (7 - bit % 8)) & 1) as u16
+            })
+        })
+        .collect();
+    Ok(indices)
+}
+
+/// Decodes a phrase back into its entropy, verifying the word count, words and checksum.
+fn phrase_to_entropy(phrase: &str) -> Result<Vec<u8>, ECDSAError> {
+    let words = wordlist();
+    let indices = phrase
+        .split_whitespace()
+        .map(|word| words.binary_search(&word.to_ascii_lowercase().as_str()).map(|index| index as u16))
+        .collect::<Result<Vec<u16>, _>>()
+        .map_err(|_| ECDSAError::InvalidMnemonic)?;
+    if !(12..=24).contains(&indices.len()) || !indices.len().is_multiple_of(3) {
+        return Err(ECDSAError::InvalidMnemonic);
+    }
+
+    let mut bits = vec![0u8; (indices.len() * 11).div_ceil(8)];
+    for (word, index) in indices.iter().enumerate() {
+        for offset in 0..11 {
+            let bit = word * 11 + offset;
+            bits[bit / 8] |= (((index >> (10 - offset)) & 1) as u8) << (7 - bit % 8);
+        }
+    }
+
+    let entropy_length = indices.len() * 4 / 3;
+    let checksum_bits = entropy_length / 4;
+    let entropy = bits[..entropy_length].to_vec();
+    let expected = Sha256::digest(&entropy)[0] >> (8 - checksum_bits);
+    if bits[entropy_length] >> (8 - checksum_bits) != expected {
+        return Err(ECDSAError::InvalidMnemonic);
+    }
+    Ok(entropy)
+}
+
+/// A BIP-39 mnemonic phrase.
+#[wasm_bindgen]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Mnemonic {
+    /// Generates a phrase from fresh platform randomness.
+    ///
+    /// # Arguments:
+    /// * `word_count` - 12, 15, 18, 21 or 24 words; defaults to 12.
+    ///
+    /// # Errors:
+    /// * Returns an error if the word count is unsupported or no randomness is available.
+    pub fn generate(word_count: Option<usize>) -> Result<Mnemonic, ECDSAError> {
+        let word_count = word_count.unwrap_or(12);
+        if !(12..=24).contains(&word_count) || !word_count.is_multiple_of(3) {
+            return Err(ECDSAError::InvalidEntropy);
+        }
+
+        let mut entropy = vec![0u8; word_count * 4 / 3];
+        fill_random(&mut entropy).map_err(|_| ECDSAError::InvalidEntropy)?;
+        Ok(Mnemonic { entropy })
+    }
+
+    /// Encodes caller-supplied entropy as a phrase.
+    ///
+    /// # Arguments:
+    /// * `entropy` - 16, 20, 24, 28 or 32 bytes of entropy.
+    ///
+    /// # Errors:
+    /// * Returns an error if the entropy length is unsupported.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Mnemonic, ECDSAError> {
+        entropy_to_indices(entropy)?;
+        Ok(Mnemonic { entropy: entropy.to_vec() })
+    }
+
+    /// Parses an existing phrase, e.g. during wallet restore.
+    ///
+    /// # Arguments:
+    /// * `phrase` - The words separated by whitespace. Case is ignored.
+    ///
+    /// # Errors:
+    /// * Returns an error if a word is unknown, the word count is wrong or the checksum fails.
+    pub fn from_phrase(phrase: &str) -> Result<Mnemonic, ECDSAError> {
+        Ok(Mnemonic { entropy: phrase_to_entropy(phrase)? })
+    }
+
+    /// Returns whether `phrase` is a well-formed mnemonic with a valid checksum.
+    pub fn validate(phrase: &str) -> bool {
+        phrase_to_entropy(phrase).is_ok()
+    }
+
+    /// The phrase as lowercase words separated by single spaces.
+    #[wasm_bindgen(getter)]
+    pub fn phrase(&self) -> String {
+        let words = wordlist();
+        entropy_to_indices(&self.entropy)
+            .expect("entropy length is validated on construction")
+            .iter()
+            .map(|&index| words[index as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The number of words in the phrase.
+    #[wasm_bindgen(getter)]
+    pub fn word_count(&self) -> usize {
+        self.entropy.len() * 3 / 4
+    }
+
+    /// Returns the entropy encoded by the phrase (`Uint8Array` in JS).
+    pub fn entropy(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+
+    /// Derives the 64-byte wallet seed.
+    ///
+    /// # Arguments:
+    /// * `passphrase` - Optional BIP-39 passphrase; defaults to empty. Its UTF-8 bytes are
+    ///   used as-is, so non-ASCII passphrases must already be NFKD-normalized.
+    ///
+    /// # Returns:
+    /// * The seed (`Uint8Array` in JS), the input to BIP-32 master key generation.
+    pub fn to_seed(&self, passphrase: Option<String>) -> Vec<u8> {
+        let salt = format!("mnemonic{}", passphrase.unwrap_or_default());
+        pbkdf2_hmac_sha512(self.phrase().as_bytes(), salt.as_bytes(), SEED_ITERATIONS, SEED_LENGTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wordlist() {
+        let words = wordlist();
+        assert_eq!(words.len(), 2048);
+        assert_eq!((words[0], words[2047]), ("abandon", "zoo"));
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_bip39_vectors() {
+        // From the BIP-39 reference test vectors, passphrase "TREZOR"
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+                "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+            ),
+        ];
+        for (entropy, phrase, seed) in vectors {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.phrase(), phrase);
+            assert_eq!(hex::encode(mnemonic.to_seed(Some("TREZOR".into()))), seed);
+            assert_eq!(hex::encode(Mnemonic::from_phrase(phrase).unwrap().entropy()), entropy);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Mnemonic::validate("  Abandon abandon abandon abandon abandon abandon\nabandon abandon abandon abandon abandon ABOUT "));
+        // Bad checksum
+        assert!(!Mnemonic::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"));
+        // Unknown word
+        assert!(!Mnemonic::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abou"));
+        // Wrong word count
+        assert!(!Mnemonic::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
+        assert!(matches!(Mnemonic::from_phrase(""), Err(ECDSAError::InvalidMnemonic)));
+    }
+
+    #[test]
+    fn test_generate() {
+        for word_count in [12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(Some(word_count)).unwrap();
+            assert_eq!(mnemonic.word_count(), word_count);
+            assert_eq!(mnemonic.phrase().split(' ').count(), word_count);
+            assert!(Mnemonic::validate(&mnemonic.phrase()));
+        }
+        assert_eq!(Mnemonic::generate(None).unwrap().word_count(), 12);
+        assert!(matches!(Mnemonic::generate(Some(13)), Err(ECDSAError::InvalidEntropy)));
+        assert!(matches!(Mnemonic::from_entropy(&[0; 15]), Err(ECDSAError::InvalidEntropy)));
+    }
+}
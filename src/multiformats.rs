@@ -0,0 +1,273 @@
+//! Multibase text and multicodec-prefixed public keys, the self-describing encodings that
+//! `did:key` identifiers, CIDs and libp2p peer IDs are built from.
+//!
+//! A multibase string is a one-character prefix naming the base followed by the encoded
+//! bytes. A multicodec key is an unsigned varint naming the key type followed by the key: a
+//! compressed SEC1 point for the ECDSA curves, or the 32 raw bytes of an Ed25519 key.
+
+use wasm_bindgen::prelude::*;
+
+use crate::encoding::{
+    from_base32, from_base36, from_base58, from_base64, from_base64url, to_base32, to_base36, to_base58, to_base64,
+    to_base64url,
+};
+use crate::keys::asn1::KeyAlgorithm;
+use crate::pem::ImportedPublicKey;
+use crate::ECDSAError;
+
+/// The supported multibase prefixes and their names in the multibase table.
+const BASES: [(char, &str); 11] = [
+    ('f', "base16"),
+    ('F', "base16upper"),
+    ('b', "base32"),
+    ('B', "base32upper"),
+    ('k', "base36"),
+    ('K', "base36upper"),
+    ('z', "base58btc"),
+    ('m', "base64"),
+    ('M', "base64pad"),
+    ('u', "base64url"),
+    ('U', "base64urlpad"),
+];
+
+/// The multicodec of each key type, as an unsigned varint.
+const KEY_CODECS: [(KeyAlgorithm, &[u8]); 5] = [
+    (KeyAlgorithm::Secp256k1, &[0xe7, 0x01]),
+    (KeyAlgorithm::P256, &[0x80, 0x24]),
+    (KeyAlgorithm::P384, &[0x81, 0x24]),
+    (KeyAlgorithm::P521, &[0x82, 0x24]),
+    (KeyAlgorithm::Ed25519, &[0xed, 0x01]),
+];
+
+/// Appends the `=` padding that makes Base64 text a multiple of four characters.
+fn padded(text: String) -> String {
+    let padding = (4 - text.len() % 4) % 4;
+    text + &"=".repeat(padding)
+}
+
+/// Encodes bytes as a multibase string with the given prefix.
+///
+/// # Errors:
+/// * `InvalidMultibase` if the prefix is not a supported base.
+pub(crate) fn encode_multibase(prefix: char, data: &[u8]) -> Result<String, ECDSAError> {
+    let encoded = match prefix {
+        'f' => hex::encode(data),
+        'F' => hex::encode_upper(data),
+        'b' => to_base32(data),
+        'B' => to_base32(data).to_uppercase(),
+        'k' => to_base36(data),
+        'K' => to_base36(data).to_uppercase(),
+        'z' => to_base58(data),
+        'm' => to_base64(data).trim_end_matches('=').to_string(),
+        'M' => to_base64(data),
+        'u' => to_base64url(data),
+        'U' => padded(to_base64url(data)),
+        _ => return Err(ECDSAError::InvalidMultibase),
+    };
+    Ok(format!("{prefix}{encoded}"))
+}
+
+/// Decodes a multibase string into its prefix and bytes.
+///
+/// Only the canonical encoding is accepted: the letter case the base calls for, padding only
+/// where it is required, and zero trailing bits, so each string has one decoding.
+///
+/// # Errors:
+/// * `InvalidMultibase` if the base is unsupported or the text is not validly encoded.
+pub(crate) fn decode_multibase(text: &str) -> Result<(char, Vec<u8>), ECDSAError> {
+    let prefix = text.chars().next().ok_or(ECDSAError::InvalidMultibase)?;
+    let encoded = &text[prefix.len_utf8()..];
+    let data = match prefix {
+        'f' | 'F' => hex::decode(encoded).ok(),
+        'b' | 'B' => from_base32(&encoded.to_lowercase()),
+        'k' | 'K' => from_base36(&encoded.to_lowercase()),
+        'z' => from_base58(encoded),
+        'm' | 'M' => from_base64(&padded(encoded.to_string())),
+        'u' | 'U' => from_base64url(encoded.trim_end_matches('=')),
+        _ => None,
+    }
+    .ok_or(ECDSAError::InvalidMultibase)?;
+    // Re-encoding catches the wrong case, padding and non-canonical Base36 or Base58 text
+    if encode_multibase(prefix, &data)? != text {
+        return Err(ECDSAError::InvalidMultibase);
+    }
+    Ok((prefix, data))
+}
+
+/// Compresses a SEC1 point after validating it. Ed25519 keys are returned unchanged.
+pub(crate) fn compressed(algorithm: KeyAlgorithm, public_key: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let point = algorithm.uncompressed(public_key)?;
+    if algorithm == KeyAlgorithm::Ed25519 {
+        return Ok(point);
+    }
+    let (x, y) = point[1..].split_at((point.len() - 1) / 2);
+    let mut compressed = vec![0x02 | (y[y.len() - 1] & 1)];
+    compressed.extend_from_slice(x);
+    Ok(compressed)
+}
+
+/// Prefixes a public key, compressed and validated, with its multicodec.
+pub(crate) fn encode_multicodec_key(algorithm: KeyAlgorithm, public_key: &[u8]) -> Result<Vec<u8>, ECDSAError> {
+    let (_, codec) = KEY_CODECS.iter().find(|(candidate, _)| *candidate == algorithm).ok_or(ECDSAError::UnsupportedCurve)?;
+    Ok([codec.to_vec(), compressed(algorithm, public_key)?].concat())
+}
+
+/// Splits a multicodec-prefixed key into its type and public key.
+///
+/// # Errors:
+/// * `UnsupportedCurve` if the multicodec is not a supported key type, or the curve is not enabled.
+/// * `InvalidPublicKey` if the key is not a valid point, and `InvalidKeyEncoding` if it is
+///   not compressed.
+pub(crate) fn decode_multicodec_key(bytes: &[u8]) -> Result<(KeyAlgorithm, Vec<u8>), ECDSAError> {
+    let (algorithm, public_key) = KEY_CODECS
+        .iter()
+        .find_map(|&(algorithm, codec)| bytes.strip_prefix(codec).map(|key| (algorithm, key)))
+        .ok_or(ECDSAError::UnsupportedCurve)?;
+    // Multicodec keys are compressed, so other encodings of the key are rejected
+    if compressed(algorithm, public_key)? != public_key {
+        return Err(ECDSAError::InvalidKeyEncoding);
+    }
+    Ok((algorithm, public_key.to_vec()))
+}
+
+/// Encodes bytes as a multibase string.
+///
+/// # Arguments:
+/// * `data` - The bytes to encode (`Uint8Array` in JS).
+/// * `base` - The base's name in the multibase table: `base16`, `base16upper`, `base32`,
+///   `base32upper`, `base36`, `base36upper`, `base58btc`, `base64`, `base64pad`, `base64url`
+///   or `base64urlpad`.
+///
+/// # Returns:
+/// * The prefixed text, e.g. `z...` for base58btc.
+///
+/// # Errors:
+/// * `InvalidMultibase` if the base is not supported.
+#[wasm_bindgen]
+pub fn multibase_encode(data: &[u8], base: &str) -> Result<String, ECDSAError> {
+    let (prefix, _) = BASES.iter().find(|(_, name)| *name == base).ok_or(ECDSAError::InvalidMultibase)?;
+    encode_multibase(*prefix, data)
+}
+
+/// Decodes a multibase string.
+///
+/// # Arguments:
+/// * `text` - The prefixed text in any of the bases `multibase_encode` supports.
+///
+/// # Returns:
+/// * The decoded bytes.
+///
+/// # Errors:
+/// * `InvalidMultibase` if the base is not supported or the text is not its canonical encoding.
+#[wasm_bindgen]
+pub fn multibase_decode(text: &str) -> Result<Vec<u8>, ECDSAError> {
+    decode_multibase(text).map(|(_, data)| data)
+}
+
+/// Encodes a public key with its multicodec prefix, as `did:key` and Multikey documents do.
+///
+/// # Arguments:
+/// * `public_key` - The public key as a hex string: a SEC1 point (compressed or uncompressed),
+///   or 32 bytes for Ed25519.
+/// * `curve` - `secp256k1`, `P-256`, `P-384`, `P-521` or `Ed25519`.
+///
+/// # Returns:
+/// * The multicodec varint followed by the compressed key.
+///
+/// # Errors:
+/// * `UnsupportedCurve` if the curve is unknown or not enabled.
+/// * `InvalidPublicKey` if the key is not a valid point on the curve.
+#[wasm_bindgen]
+pub fn public_key_to_multicodec(public_key: &str, curve: &str) -> Result<Vec<u8>, ECDSAError> {
+    let algorithm = KeyAlgorithm::from_name(curve)?;
+    let bytes = hex::decode(public_key.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    encode_multicodec_key(algorithm, &bytes)
+}
+
+/// Decodes a multicodec-prefixed public key.
+///
+/// # Arguments:
+/// * `key` - The multicodec varint followed by the key (`Uint8Array` in JS), e.g. a decoded
+///   `publicKeyMultibase` value.
+///
+/// # Returns:
+/// * The key's curve and public key. ECDSA keys are compressed SEC1 points.
+///
+/// # Errors:
+/// * `UnsupportedCurve` if the multicodec is not secp256k1, P-256, P-384, P-521 or Ed25519,
+///   or the curve is not enabled.
+/// * `InvalidPublicKey` if the key is not a valid point, or `InvalidKeyEncoding` if it is not
+///   compressed.
+#[wasm_bindgen]
+pub fn multicodec_to_public_key(key: &[u8]) -> Result<ImportedPublicKey, ECDSAError> {
+    let (algorithm, public_key) = decode_multicodec_key(key)?;
+    Ok(ImportedPublicKey::new(algorithm, public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multibase_vectors() {
+        // From the multibase test suite: "yes mani !", with and without a leading zero byte
+        let vectors = [
+            ("base16", "f796573206d616e692021", "f00796573206d616e692021"),
+            ("base16upper", "F796573206D616E692021", "F00796573206D616E692021"),
+            ("base32", "bpfsxgidnmfxgsibb", "bab4wk4zanvqw42jaee"),
+            ("base32upper", "BPFSXGIDNMFXGSIBB", "BAB4WK4ZANVQW42JAEE"),
+            ("base36", "k2lcpzo5yikidynfl", "k02lcpzo5yikidynfl"),
+            ("base36upper", "K2LCPZO5YIKIDYNFL", "K02LCPZO5YIKIDYNFL"),
+            ("base58btc", "z7paNL19xttacUY", "z17paNL19xttacUY"),
+            ("base64", "meWVzIG1hbmkgIQ", "mAHllcyBtYW5pICE"),
+            ("base64pad", "MeWVzIG1hbmkgIQ==", "MAHllcyBtYW5pICE="),
+            ("base64url", "ueWVzIG1hbmkgIQ", "uAHllcyBtYW5pICE"),
+            ("base64urlpad", "UeWVzIG1hbmkgIQ==", "UAHllcyBtYW5pICE="),
+        ];
+        for (base, text, zero_text) in vectors {
+            assert_eq!(multibase_encode(b"yes mani !", base).unwrap(), text);
+            assert_eq!(multibase_decode(text).unwrap(), b"yes mani !");
+            assert_eq!(multibase_encode(b"\0yes mani !", base).unwrap(), zero_text);
+            assert_eq!(multibase_decode(zero_text).unwrap(), b"\0yes mani !");
+        }
+        assert!(matches!(multibase_encode(b"", "base2"), Err(ECDSAError::InvalidMultibase)));
+    }
+
+    #[test]
+    fn test_rejects_non_canonical_multibase() {
+        for text in [
+            "",
+            // Unknown and unsupported prefixes
+            "x00",
+            "0110",
+            // The wrong case, padding where the base has none, and nonzero trailing bits
+            "bPFSXGIDNMFXGSIBB",
+            "F796573206d616e692021",
+            "meWVzIG1hbmkgIQ==",
+            "UeWVzIG1hbmkgIQ",
+            "bmz",
+            // Characters outside the alphabet
+            "z0OIl",
+            "k2lcpzo5yikidynf!",
+        ] {
+            assert!(matches!(multibase_decode(text), Err(ECDSAError::InvalidMultibase)), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_multicodec_keys() {
+        let key = "03874c15c7fda20e539c6e5ba573c139884c351188799f5458b4b41f7924f235cd";
+        let encoded = public_key_to_multicodec(key, "secp256k1").unwrap();
+        assert_eq!(encoded[..2], [0xe7, 0x01]);
+        let decoded = multicodec_to_public_key(&encoded).unwrap();
+        assert_eq!((decoded.curve(), decoded.public_key()), ("secp256k1".to_string(), key.to_string()));
+
+        // Uncompressed keys are compressed when encoding and rejected when decoding
+        let uncompressed = KeyAlgorithm::Secp256k1.uncompressed(&hex::decode(key).unwrap()).unwrap();
+        assert_eq!(public_key_to_multicodec(&hex::encode(&uncompressed), "secp256k1").unwrap(), encoded);
+        let prefixed = [&[0xe7, 0x01][..], &uncompressed].concat();
+        assert!(matches!(multicodec_to_public_key(&prefixed), Err(ECDSAError::InvalidKeyEncoding)));
+        // x25519-pub is not a signing key
+        assert!(matches!(multicodec_to_public_key(&[&[0xec, 0x01][..], &[9; 32]].concat()), Err(ECDSAError::UnsupportedCurve)));
+    }
+}
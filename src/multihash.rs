@@ -0,0 +1,200 @@
+//! Multihash: a digest prefixed with varints naming the hash function and the digest length,
+//! as CIDs and libp2p peer IDs carry them.
+
+use wasm_bindgen::prelude::*;
+
+use crate::constant_time::constant_time_eq;
+use crate::hash::{digest, HashAlgorithm};
+use crate::protobuf::{read_varint, write_varint};
+use crate::ECDSAError;
+
+/// The identity "hash": the digest is the data itself.
+pub(crate) const IDENTITY: u32 = 0x00;
+pub(crate) const SHA2_256: u32 = 0x12;
+
+const ALGORITHMS: [HashAlgorithm; 5] = [
+    HashAlgorithm::Sha256,
+    HashAlgorithm::Sha512,
+    HashAlgorithm::Sha3_256,
+    HashAlgorithm::Keccak256,
+    HashAlgorithm::Blake2b256,
+];
+
+/// The multicodec of a hash function.
+fn code(algorithm: HashAlgorithm) -> u32 {
+    match algorithm {
+        HashAlgorithm::Sha256 => SHA2_256,
+        HashAlgorithm::Sha512 => 0x13,
+        HashAlgorithm::Sha3_256 => 0x16,
+        HashAlgorithm::Keccak256 => 0x1b,
+        HashAlgorithm::Blake2b256 => 0xb220,
+    }
+}
+
+/// Looks up a hash function by its multicodec.
+fn algorithm(code_point: u32) -> Option<HashAlgorithm> {
+    ALGORITHMS.into_iter().find(|&algorithm| code(algorithm) == code_point)
+}
+
+/// Encodes a digest as a multihash.
+pub(crate) fn encode_multihash(code: u32, digest: &[u8]) -> Vec<u8> {
+    let mut multihash = Vec::with_capacity(digest.len() + 4);
+    write_varint(&mut multihash, code as u64);
+    write_varint(&mut multihash, digest.len() as u64);
+    multihash.extend_from_slice(digest);
+    multihash
+}
+
+/// Splits a multihash into its function code and digest.
+///
+/// # Errors:
+/// * `InvalidMultihash` if the varints are malformed or the length does not match the digest.
+pub(crate) fn decode_multihash(multihash: &[u8]) -> Result<(u32, &[u8]), ECDSAError> {
+    let (code, code_length) = read_varint(multihash).ok_or(ECDSAError::InvalidMultihash)?;
+    let rest = &multihash[code_length..];
+    let (length, length_length) = read_varint(rest).ok_or(ECDSAError::InvalidMultihash)?;
+    let digest = &rest[length_length..];
+    if length != digest.len() as u64 {
+        return Err(ECDSAError::InvalidMultihash);
+    }
+    let code = u32::try_from(code).map_err(|_| ECDSAError::InvalidMultihash)?;
+    Ok((code, digest))
+}
+
+/// Hashes `data` into a multihash with the full-length digest.
+pub(crate) fn compute_multihash(algorithm: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+    encode_multihash(code(algorithm), &digest(algorithm, data))
+}
+
+/// A decoded multihash.
+#[wasm_bindgen]
+pub struct Multihash {
+    code: u32,
+    digest: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Multihash {
+    /// The multicodec of the hash function, e.g. `0x12` for SHA-256.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// The hash function, or `undefined` for the identity function and ones this crate lacks.
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> Option<HashAlgorithm> {
+        algorithm(self.code)
+    }
+
+    /// The digest, which may be truncated.
+    #[wasm_bindgen(getter = digest)]
+    pub fn digest_bytes(&self) -> Vec<u8> {
+        self.digest.clone()
+    }
+}
+
+/// Hashes data into a multihash.
+///
+/// # Arguments:
+/// * `data` - The data to hash (`Uint8Array` in JS).
+/// * `algorithm` - The hash function, defaulting to SHA-256.
+///
+/// # Returns:
+/// * The multihash: the function's multicodec, the digest length and the digest.
+#[wasm_bindgen]
+pub fn multihash_digest(data: &[u8], algorithm: Option<HashAlgorithm>) -> Vec<u8> {
+    compute_multihash(algorithm.unwrap_or_default(), data)
+}
+
+/// Decodes a multihash.
+///
+/// # Arguments:
+/// * `multihash` - The encoded multihash (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * The function code, the function if it is supported, and the digest.
+///
+/// # Errors:
+/// * `InvalidMultihash` if the multihash is malformed.
+#[wasm_bindgen]
+pub fn multihash_decode(multihash: &[u8]) -> Result<Multihash, ECDSAError> {
+    let (code, digest) = decode_multihash(multihash)?;
+    Ok(Multihash { code, digest: digest.to_vec() })
+}
+
+/// Checks that a multihash is a digest of `data`.
+///
+/// Truncated digests are compared with the start of the full digest, so a short multihash
+/// only commits to as many bits as it holds.
+///
+/// # Arguments:
+/// * `multihash` - The expected multihash (`Uint8Array` in JS).
+/// * `data` - The content to hash.
+///
+/// # Returns:
+/// * A boolean indicating whether `data` hashes to the multihash.
+///
+/// # Errors:
+/// * `InvalidMultihash` if the multihash is malformed or longer than the function's digest.
+/// * `UnsupportedMultihash` if the hash function is not SHA-256, SHA-512, SHA3-256,
+///   Keccak-256, BLAKE2b-256 or the identity.
+#[wasm_bindgen]
+pub fn multihash_verify(multihash: &[u8], data: &[u8]) -> Result<bool, ECDSAError> {
+    let (code, expected) = decode_multihash(multihash)?;
+    if code == IDENTITY {
+        return Ok(constant_time_eq(expected, data));
+    }
+    let algorithm = algorithm(code).ok_or(ECDSAError::UnsupportedMultihash)?;
+    let computed = digest(algorithm, data);
+    let computed = computed.get(..expected.len()).ok_or(ECDSAError::InvalidMultihash)?;
+    Ok(constant_time_eq(computed, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multihash_digests() {
+        // Multihashes of "foo"
+        let vectors = [
+            (HashAlgorithm::Sha256, "12202c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"),
+            (
+                HashAlgorithm::Sha512,
+                "1340f7fbba6e0636f890e56fbbf3283e524c6fa3204ae298382d624741d0dc6638326e282c41be5e4254d8820772c5518a2c5a8c0c7f7eda19594a7eb539453e1ed7",
+            ),
+            (HashAlgorithm::Blake2b256, "a0e40220b8fe9f7f6255a6fa08f668ab632a8d081ad87983c77cd274e48ce450f0b349fd"),
+        ];
+        for (algorithm, expected) in vectors {
+            let multihash = multihash_digest(b"foo", Some(algorithm));
+            assert_eq!(hex::encode(&multihash), expected);
+            let decoded = multihash_decode(&multihash).unwrap();
+            assert_eq!(decoded.algorithm(), Some(algorithm));
+            assert_eq!(encode_multihash(decoded.code(), &decoded.digest_bytes()), multihash);
+        }
+        assert_eq!(multihash_digest(b"foo", None), hex::decode(vectors[0].1).unwrap());
+    }
+
+    #[test]
+    fn test_multihash_verify() {
+        let multihash = multihash_digest(b"foo", Some(HashAlgorithm::Sha3_256));
+        assert!(multihash_verify(&multihash, b"foo").unwrap());
+        assert!(!multihash_verify(&multihash, b"bar").unwrap());
+
+        // Truncated digests and the identity function
+        let truncated = encode_multihash(0x12, &hex::decode("2c26b46b68ffc68f").unwrap());
+        assert!(multihash_verify(&truncated, b"foo").unwrap());
+        assert!(multihash_verify(&encode_multihash(IDENTITY, b"foo"), b"foo").unwrap());
+        assert!(matches!(multihash_verify(&encode_multihash(0x12, &[0; 33]), b"foo"), Err(ECDSAError::InvalidMultihash)));
+        // SHA-384 is not supported
+        assert!(matches!(multihash_verify(&encode_multihash(0x20, &[0; 48]), b"foo"), Err(ECDSAError::UnsupportedMultihash)));
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        for encoding in ["", "12", "1220", "122000", "80", "1201aabb", "ffffffffffffffffff0100"] {
+            assert!(matches!(multihash_decode(&hex::decode(encoding).unwrap()), Err(ECDSAError::InvalidMultihash)), "{encoding}");
+        }
+    }
+}
@@ -0,0 +1,455 @@
+//! BIP-327 MuSig2 key aggregation, nonce aggregation and partial signature verification, for a
+//! coordinator that checks each cosigner's contribution before combining them into a BIP-340
+//! signature.
+//!
+//! Individual public keys and nonces are 33-byte compressed points; the aggregate key is the
+//! 32-byte x-only key that the final Schnorr signature verifies under.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::elliptic_curve::AffineXCoordinate;
+use k256::{FieldBytes, ProjectivePoint, Scalar, U256};
+
+use crate::schnorr::{decode_point, has_even_y, tagged_hash};
+use crate::ECDSAError;
+
+/// Length of a compressed point, and so of an individual public key.
+const POINT_LENGTH: usize = 33;
+/// Length of a public nonce or aggregate nonce: two compressed points.
+const NONCE_LENGTH: usize = 2 * POINT_LENGTH;
+
+type PublicKey = [u8; POINT_LENGTH];
+type Nonce = [u8; NONCE_LENGTH];
+
+/// Decodes a compressed point, or 33 zero bytes as the point at infinity.
+fn cpoint_ext(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.iter().all(|&byte| byte == 0) {
+        Some(ProjectivePoint::IDENTITY)
+    } else {
+        decode_point(bytes)
+    }
+}
+
+/// Encodes a point compressed, or the point at infinity as 33 zero bytes.
+fn cbytes_ext(point: &ProjectivePoint) -> [u8; POINT_LENGTH] {
+    let mut bytes = [0u8; POINT_LENGTH];
+    if *point != ProjectivePoint::IDENTITY {
+        bytes.copy_from_slice(&point.to_affine().to_bytes());
+    }
+    bytes
+}
+
+fn x_bytes(point: &ProjectivePoint) -> [u8; 32] {
+    point.to_affine().x().into()
+}
+
+/// Reduces a hash modulo the group order.
+fn hash_to_scalar(hash: [u8; 32]) -> Scalar {
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(hash.into())
+}
+
+/// Parses a 32-byte scalar that must be below the group order.
+fn parse_scalar(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes)))
+}
+
+/// Parses a compressed public key, provided as a hex string.
+fn parse_public_key(public_key: &str) -> Result<PublicKey, ECDSAError> {
+    let bytes = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    let public_key: PublicKey = bytes.try_into().map_err(|_| ECDSAError::InvalidPublicKey)?;
+    decode_point(&public_key).ok_or(ECDSAError::InvalidPublicKey)?;
+    Ok(public_key)
+}
+
+/// Parses a public or aggregate nonce, provided as a hex string; the points are checked when
+/// they are used.
+fn parse_nonce(nonce: &str) -> Result<Nonce, ECDSAError> {
+    hex::decode(nonce).ok().and_then(|bytes| bytes.try_into().ok()).ok_or(ECDSAError::InvalidMuSig2Nonce)
+}
+
+/// The `-1` or `1` that negates a point with an odd y.
+fn negate_if_odd(point: &ProjectivePoint) -> Scalar {
+    if has_even_y(&point.to_affine()) { Scalar::ONE } else { -Scalar::ONE }
+}
+
+/// The aggregate key and its tweaks: `Q`, and the accumulated sign `gacc` and tweak `tacc`.
+struct KeyAggContext {
+    public_keys: Vec<PublicKey>,
+    list_hash: [u8; 32],
+    /// The first key that differs from the first, whose coefficient is 1.
+    second_key: Option<PublicKey>,
+    q: ProjectivePoint,
+    gacc: Scalar,
+    tacc: Scalar,
+}
+
+impl KeyAggContext {
+    fn new(public_keys: Vec<PublicKey>) -> Result<KeyAggContext, ECDSAError> {
+        let first = public_keys.first().ok_or(ECDSAError::InvalidPublicKey)?;
+        let list_hash = tagged_hash("KeyAgg list", &[&public_keys.concat()]);
+        let second_key = public_keys.iter().find(|key| *key != first).copied();
+        let mut context = KeyAggContext {
+            public_keys,
+            list_hash,
+            second_key,
+            q: ProjectivePoint::IDENTITY,
+            gacc: Scalar::ONE,
+            tacc: Scalar::ZERO,
+        };
+        for key in &context.public_keys {
+            context.q += decode_point(key).ok_or(ECDSAError::InvalidPublicKey)? * context.coefficient(key);
+        }
+        if context.q == ProjectivePoint::IDENTITY {
+            return Err(ECDSAError::InvalidMuSig2Session);
+        }
+        Ok(context)
+    }
+
+    /// The coefficient of a key in the aggregate.
+    fn coefficient(&self, public_key: &PublicKey) -> Scalar {
+        if self.second_key.as_ref() == Some(public_key) {
+            Scalar::ONE
+        } else {
+            hash_to_scalar(tagged_hash("KeyAgg coefficient", &[&self.list_hash, public_key]))
+        }
+    }
+
+    /// Adds `tweak * G` to the aggregate key, after negating it to an even y for an x-only
+    /// tweak such as Taproot's.
+    fn apply_tweak(&mut self, tweak: &[u8], x_only: bool) -> Result<(), ECDSAError> {
+        let tweak = parse_scalar(tweak).ok_or(ECDSAError::InvalidMuSig2Session)?;
+        let g = if x_only { negate_if_odd(&self.q) } else { Scalar::ONE };
+        let q = self.q * g + ProjectivePoint::GENERATOR * tweak;
+        if q == ProjectivePoint::IDENTITY {
+            return Err(ECDSAError::InvalidMuSig2Session);
+        }
+        self.q = q;
+        self.gacc *= g;
+        self.tacc = tweak + g * self.tacc;
+        Ok(())
+    }
+}
+
+/// Sums public nonces pointwise into the aggregate nonce.
+fn aggregate_nonces(nonces: &[Nonce]) -> Result<Nonce, ECDSAError> {
+    let mut aggregate = [0u8; NONCE_LENGTH];
+    for j in 0..2 {
+        let range = j * POINT_LENGTH..(j + 1) * POINT_LENGTH;
+        let mut sum = ProjectivePoint::IDENTITY;
+        for nonce in nonces {
+            sum += decode_point(&nonce[range.clone()]).ok_or(ECDSAError::InvalidMuSig2Nonce)?;
+        }
+        aggregate[range].copy_from_slice(&cbytes_ext(&sum));
+    }
+    Ok(aggregate)
+}
+
+/// Sorts public keys lexicographically, as BIP-327 `KeySort` does to make the aggregate key
+/// independent of the signers' order.
+///
+/// # Arguments:
+/// * `public_keys` - The 33-byte compressed public keys, provided as hex strings.
+///
+/// # Returns:
+/// * The same keys in sorted order, as lowercase hex strings.
+#[wasm_bindgen]
+pub fn musig2_sort_public_keys(public_keys: Vec<String>) -> Result<Vec<String>, ECDSAError> {
+    let mut keys = public_keys.iter().map(|key| parse_public_key(key)).collect::<Result<Vec<_>, _>>()?;
+    keys.sort();
+    Ok(keys.iter().map(hex::encode).collect())
+}
+
+/// Aggregates the signers' public keys into the key that MuSig2 signatures verify under.
+/// The order of the keys matters; sort them first with `musig2_sort_public_keys` if the
+/// signers have not agreed on one.
+///
+/// # Arguments:
+/// * `public_keys` - The 33-byte compressed public keys, provided as hex strings.
+///
+/// # Returns:
+/// * The 32-byte x-only aggregate public key, as a hex string.
+///
+/// # Errors:
+/// * `InvalidPublicKey` if there are no keys or one is malformed.
+/// * `InvalidMuSig2Session` if the keys cancel out.
+#[wasm_bindgen]
+pub fn musig2_aggregate_public_keys(public_keys: Vec<String>) -> Result<String, ECDSAError> {
+    let keys = public_keys.iter().map(|key| parse_public_key(key)).collect::<Result<Vec<_>, _>>()?;
+    Ok(hex::encode(x_bytes(&KeyAggContext::new(keys)?.q)))
+}
+
+/// Aggregates the signers' public nonces for a signing session.
+///
+/// # Arguments:
+/// * `public_nonces` - The 66-byte public nonces, provided as hex strings.
+///
+/// # Returns:
+/// * The 66-byte aggregate nonce, as a hex string.
+///
+/// # Errors:
+/// * `InvalidMuSig2Nonce` if a nonce is malformed; the coordinator should then exclude the
+///   signer who sent it.
+#[wasm_bindgen]
+pub fn musig2_aggregate_nonces(public_nonces: Vec<String>) -> Result<String, ECDSAError> {
+    let nonces = public_nonces.iter().map(|nonce| parse_nonce(nonce)).collect::<Result<Vec<_>, _>>()?;
+    Ok(hex::encode(aggregate_nonces(&nonces)?))
+}
+
+/// A MuSig2 signing session as the coordinator sees it: the signers' keys, the aggregate
+/// nonce and the message, against which each partial signature can be checked.
+#[wasm_bindgen]
+pub struct MuSig2Session {
+    key_agg: KeyAggContext,
+    aggregate_nonce: Nonce,
+    message: Vec<u8>,
+}
+
+impl MuSig2Session {
+    /// The nonce coefficient `b`, the final nonce `R` and the challenge `e`, which depend on
+    /// the tweaked aggregate key.
+    fn values(&self) -> Result<(Scalar, ProjectivePoint, Scalar), ECDSAError> {
+        let q = x_bytes(&self.key_agg.q);
+        let b = hash_to_scalar(tagged_hash("MuSig/noncecoef", &[&self.aggregate_nonce, &q, &self.message]));
+        let r1 = cpoint_ext(&self.aggregate_nonce[..POINT_LENGTH]).ok_or(ECDSAError::InvalidMuSig2Nonce)?;
+        let r2 = cpoint_ext(&self.aggregate_nonce[POINT_LENGTH..]).ok_or(ECDSAError::InvalidMuSig2Nonce)?;
+        let r = match r1 + r2 * b {
+            r if r == ProjectivePoint::IDENTITY => ProjectivePoint::GENERATOR,
+            r => r,
+        };
+        let e = hash_to_scalar(tagged_hash("BIP0340/challenge", &[&x_bytes(&r), &q, &self.message]));
+        Ok((b, r, e))
+    }
+}
+
+#[wasm_bindgen]
+impl MuSig2Session {
+    /// Starts a session.
+    ///
+    /// # Arguments:
+    /// * `public_keys` - The signers' 33-byte compressed public keys in aggregation order,
+    ///   provided as hex strings.
+    /// * `aggregate_nonce` - The 66-byte aggregate nonce from `musig2_aggregate_nonces`,
+    ///   provided as a hex string.
+    /// * `message` - The message being signed, of any length, provided as a hex string.
+    ///
+    /// # Errors:
+    /// * Returns an error if a key or the nonce is malformed, or the keys cancel out.
+    #[wasm_bindgen(constructor)]
+    pub fn new(public_keys: Vec<String>, aggregate_nonce: &str, message: &str) -> Result<MuSig2Session, ECDSAError> {
+        let keys = public_keys.iter().map(|key| parse_public_key(key)).collect::<Result<Vec<_>, _>>()?;
+        let aggregate_nonce = parse_nonce(aggregate_nonce)?;
+        let message = hex::decode(message).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        Ok(MuSig2Session { key_agg: KeyAggContext::new(keys)?, aggregate_nonce, message })
+    }
+
+    /// Tweaks the aggregate key, e.g. with a BIP-32 derivation (plain) or a Taproot
+    /// commitment (x-only). Tweaks apply in the order they are added.
+    ///
+    /// # Arguments:
+    /// * `tweak` - The 32-byte tweak, provided as a hex string.
+    /// * `x_only` - Whether the tweak applies to the x-only key.
+    ///
+    /// # Errors:
+    /// * `InvalidMuSig2Session` if the tweak is not a scalar or the tweaked key is infinity.
+    pub fn apply_tweak(&mut self, tweak: &str, x_only: bool) -> Result<(), ECDSAError> {
+        let tweak = hex::decode(tweak).map_err(|_| ECDSAError::InvalidMuSig2Session)?;
+        self.key_agg.apply_tweak(&tweak, x_only)
+    }
+
+    /// The 32-byte x-only aggregate public key, after any tweaks, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn aggregate_public_key(&self) -> String {
+        hex::encode(x_bytes(&self.key_agg.q))
+    }
+
+    /// Verifies one signer's partial signature, so a faulty or malicious cosigner can be
+    /// identified before the final signature fails.
+    ///
+    /// # Arguments:
+    /// * `partial_signature` - The 32-byte partial signature, provided as a hex string.
+    /// * `public_nonce` - The signer's 66-byte public nonce, provided as a hex string.
+    /// * `public_key` - The signer's 33-byte compressed public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the partial signature is valid.
+    ///
+    /// # Errors:
+    /// * `InvalidPartialSignature` if the partial signature is not a scalar.
+    /// * `InvalidMuSig2Nonce` if the public nonce is malformed.
+    /// * `InvalidMuSig2Session` if the key is not one of the session's signers.
+    pub fn verify_partial_signature(&self, partial_signature: &str, public_nonce: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let s = hex::decode(partial_signature).ok().and_then(|bytes| parse_scalar(&bytes)).ok_or(ECDSAError::InvalidPartialSignature)?;
+        let public_nonce = parse_nonce(public_nonce)?;
+        let public_key = parse_public_key(public_key)?;
+        if !self.key_agg.public_keys.contains(&public_key) {
+            return Err(ECDSAError::InvalidMuSig2Session);
+        }
+
+        let (b, r, e) = self.values()?;
+        let r1 = decode_point(&public_nonce[..POINT_LENGTH]).ok_or(ECDSAError::InvalidMuSig2Nonce)?;
+        let r2 = decode_point(&public_nonce[POINT_LENGTH..]).ok_or(ECDSAError::InvalidMuSig2Nonce)?;
+        let effective_nonce = (r1 + r2 * b) * negate_if_odd(&r);
+        let g = negate_if_odd(&self.key_agg.q) * self.key_agg.gacc;
+        let a = self.key_agg.coefficient(&public_key);
+        let point = decode_point(&public_key).ok_or(ECDSAError::InvalidPublicKey)?;
+        Ok(ProjectivePoint::GENERATOR * s == effective_nonce + point * (e * a * g))
+    }
+
+    /// Sums verified partial signatures into the final BIP-340 signature.
+    ///
+    /// # Arguments:
+    /// * `partial_signatures` - One 32-byte partial signature per signer, provided as hex strings.
+    ///
+    /// # Returns:
+    /// * The 64-byte Schnorr signature under the aggregate key, as a hex string.
+    ///
+    /// # Errors:
+    /// * `InvalidPartialSignature` if a partial signature is not a scalar.
+    pub fn aggregate_partial_signatures(&self, partial_signatures: Vec<String>) -> Result<String, ECDSAError> {
+        let (_, r, e) = self.values()?;
+        let s = partial_signatures.iter().try_fold(Scalar::ZERO, |sum, partial| {
+            let partial = hex::decode(partial).ok().and_then(|bytes| parse_scalar(&bytes)).ok_or(ECDSAError::InvalidPartialSignature)?;
+            Ok::<_, ECDSAError>(sum + partial)
+        })?;
+        let s = s + e * negate_if_odd(&self.key_agg.q) * self.key_agg.tacc;
+        Ok(hex::encode([x_bytes(&r).as_slice(), &s.to_bytes()].concat()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    use crate::schnorr::verify_schnorr;
+
+    // Keys from the BIP-327 key aggregation vectors
+    const KEYS: [&str; 3] = [
+        "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        "03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+        "023590a94e768f8e1815c2f24b4d80a8e3149316c3518ce7b7ad338368d038ca66",
+    ];
+
+    fn keys(indices: &[usize]) -> Vec<String> {
+        indices.iter().map(|&i| KEYS[i].to_string()).collect()
+    }
+
+    fn scalar(seed: &str) -> Scalar {
+        hash_to_scalar(Sha256::digest(seed.as_bytes()).into())
+    }
+
+    fn point_hex(point: ProjectivePoint) -> String {
+        hex::encode(cbytes_ext(&point))
+    }
+
+    /// A signer's BIP-327 `Sign`, with nonces derived from a seed for repeatability.
+    struct Signer {
+        secret_key: Scalar,
+        nonces: (Scalar, Scalar),
+    }
+
+    impl Signer {
+        fn new(seed: &str) -> Signer {
+            Signer { secret_key: scalar(seed), nonces: (scalar(&format!("{seed}/k1")), scalar(&format!("{seed}/k2"))) }
+        }
+
+        fn public_key(&self) -> String {
+            point_hex(ProjectivePoint::GENERATOR * self.secret_key)
+        }
+
+        fn public_nonce(&self) -> String {
+            point_hex(ProjectivePoint::GENERATOR * self.nonces.0) + &point_hex(ProjectivePoint::GENERATOR * self.nonces.1)
+        }
+
+        fn sign(&self, session: &MuSig2Session) -> String {
+            let (b, r, e) = session.values().unwrap();
+            let k = negate_if_odd(&r);
+            let public_key = parse_public_key(&self.public_key()).unwrap();
+            let a = session.key_agg.coefficient(&public_key);
+            let d = negate_if_odd(&session.key_agg.q) * session.key_agg.gacc * self.secret_key;
+            hex::encode((self.nonces.0 * k + b * self.nonces.1 * k + e * a * d).to_bytes())
+        }
+    }
+
+    /// Runs a session between signers and returns its aggregate key and signature.
+    fn sign(signers: &[Signer], message: &str, tweaks: &[(&str, bool)]) -> (MuSig2Session, Vec<String>) {
+        let nonce = musig2_aggregate_nonces(signers.iter().map(Signer::public_nonce).collect()).unwrap();
+        let mut session = MuSig2Session::new(signers.iter().map(Signer::public_key).collect(), &nonce, message).unwrap();
+        for (tweak, x_only) in tweaks {
+            session.apply_tweak(tweak, *x_only).unwrap();
+        }
+        let partials = signers.iter().map(|signer| signer.sign(&session)).collect();
+        (session, partials)
+    }
+
+    #[test]
+    fn test_key_aggregation_vectors() {
+        let vectors = [
+            (&[0, 1, 2][..], "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c"),
+            (&[2, 1, 0], "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b"),
+            (&[0, 0, 0], "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935"),
+            (&[0, 0, 1, 1], "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e"),
+        ];
+        for (indices, expected) in vectors {
+            assert_eq!(musig2_aggregate_public_keys(keys(indices)).unwrap(), expected, "{indices:?}");
+        }
+        assert_eq!(musig2_sort_public_keys(keys(&[0, 1, 2])).unwrap(), keys(&[2, 0, 1]));
+        assert!(matches!(musig2_aggregate_public_keys(vec![]), Err(ECDSAError::InvalidPublicKey)));
+    }
+
+    #[test]
+    fn test_partial_signatures_combine() {
+        let signers = [Signer::new("alice"), Signer::new("bob"), Signer::new("carol")];
+        let message = hex::encode(b"MuSig2 test message");
+        // Untweaked, with a BIP-32 style plain tweak, and with a Taproot style x-only tweak
+        let tweak = hex::encode(scalar("tweak").to_bytes());
+        for tweaks in [vec![], vec![(tweak.as_str(), false)], vec![(tweak.as_str(), false), (tweak.as_str(), true)]] {
+            let (session, partials) = sign(&signers, &message, &tweaks);
+            for (signer, partial) in signers.iter().zip(&partials) {
+                assert!(session.verify_partial_signature(partial, &signer.public_nonce(), &signer.public_key()).unwrap());
+            }
+            let signature = hex::decode(session.aggregate_partial_signatures(partials).unwrap()).unwrap();
+            let key: [u8; 32] = hex::decode(session.aggregate_public_key()).unwrap().try_into().unwrap();
+            assert!(verify_schnorr(&key, &hex::decode(&message).unwrap(), &signature).unwrap(), "{tweaks:?}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_faulty_partial_signatures() {
+        let signers = [Signer::new("alice"), Signer::new("bob")];
+        let (session, partials) = sign(&signers, "", &[]);
+        let (alice, bob) = (&signers[0], &signers[1]);
+        // Another signer's share, nonce or key
+        assert!(!session.verify_partial_signature(&partials[1], &alice.public_nonce(), &alice.public_key()).unwrap());
+        assert!(!session.verify_partial_signature(&partials[0], &bob.public_nonce(), &alice.public_key()).unwrap());
+        assert!(!session.verify_partial_signature(&partials[0], &alice.public_nonce(), &bob.public_key()).unwrap());
+
+        let outsider = Signer::new("mallory");
+        assert!(matches!(
+            session.verify_partial_signature(&partials[0], &alice.public_nonce(), &outsider.public_key()),
+            Err(ECDSAError::InvalidMuSig2Session)
+        ));
+        let order = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        assert!(matches!(session.verify_partial_signature(order, &alice.public_nonce(), &alice.public_key()), Err(ECDSAError::InvalidPartialSignature)));
+    }
+
+    #[test]
+    fn test_nonce_aggregation() {
+        let signers = [Signer::new("alice"), Signer::new("bob")];
+        let nonces: Vec<String> = signers.iter().map(Signer::public_nonce).collect();
+        // Nonces that cancel out aggregate to the encoding of infinity
+        let negated = |nonce: &str| {
+            let bytes = hex::decode(nonce).unwrap();
+            bytes.chunks(POINT_LENGTH).map(|point| point_hex(-decode_point(point).unwrap())).collect::<String>()
+        };
+        let aggregate = musig2_aggregate_nonces(vec![nonces[0].clone(), negated(&nonces[0])]).unwrap();
+        assert_eq!(aggregate, "00".repeat(NONCE_LENGTH));
+        assert!(MuSig2Session::new(keys(&[0, 1]), &aggregate, "").unwrap().values().is_ok());
+
+        assert!(matches!(musig2_aggregate_nonces(vec![nonces[0][2..].to_string()]), Err(ECDSAError::InvalidMuSig2Nonce)));
+        assert!(matches!(musig2_aggregate_nonces(vec!["00".repeat(NONCE_LENGTH)]), Err(ECDSAError::InvalidMuSig2Nonce)));
+    }
+}
@@ -0,0 +1,167 @@
+//! Nostr (NIP-01) event verification.
+//!
+//! An event's id is the SHA-256 of the compact JSON array
+//! `[0, pubkey, created_at, kind, tags, content]`, and its `sig` is a BIP-340 Schnorr
+//! signature over that id by the x-only `pubkey`.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::json::{self, write_string, Value};
+use crate::schnorr::{parse_x_only, verify_schnorr};
+use crate::ECDSAError;
+
+/// Reads a fixed-length lowercase hex member, as NIP-01 requires for ids, keys and signatures.
+fn hex_member<const N: usize>(event: &Value, name: &str) -> Result<[u8; N], ECDSAError> {
+    let text = event.get(name).and_then(Value::as_str).ok_or(ECDSAError::InvalidNostrEvent)?;
+    if text.bytes().any(|c| c.is_ascii_uppercase()) {
+        return Err(ECDSAError::InvalidNostrEvent);
+    }
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(text, &mut bytes).map_err(|_| ECDSAError::InvalidNostrEvent)?;
+    Ok(bytes)
+}
+
+/// Computes the NIP-01 event id from the event's signed fields.
+fn event_id(event: &Value) -> Result<[u8; 32], ECDSAError> {
+    let pubkey = event.get("pubkey").and_then(Value::as_str).ok_or(ECDSAError::InvalidNostrEvent)?;
+    let created_at = event.get("created_at").and_then(Value::as_u64).ok_or(ECDSAError::InvalidNostrEvent)?;
+    let kind = event.get("kind").and_then(Value::as_u64).ok_or(ECDSAError::InvalidNostrEvent)?;
+    let tags = event.get("tags").and_then(Value::as_array).ok_or(ECDSAError::InvalidNostrEvent)?;
+    let content = event.get("content").and_then(Value::as_str).ok_or(ECDSAError::InvalidNostrEvent)?;
+
+    let mut serialized = String::from("[0,");
+    write_string(&mut serialized, pubkey);
+    serialized.push_str(&format!(",{created_at},{kind},["));
+    for (i, tag) in tags.iter().enumerate() {
+        let items = tag.as_array().ok_or(ECDSAError::InvalidNostrEvent)?;
+        serialized.push_str(if i == 0 { "[" } else { ",[" });
+        for (j, item) in items.iter().enumerate() {
+            if j > 0 {
+                serialized.push(',');
+            }
+            write_string(&mut serialized, item.as_str().ok_or(ECDSAError::InvalidNostrEvent)?);
+        }
+        serialized.push(']');
+    }
+    serialized.push_str("],");
+    write_string(&mut serialized, content);
+    serialized.push(']');
+    Ok(Sha256::digest(serialized.as_bytes()).into())
+}
+
+/// Verifies a Nostr event: its `id` must match the recomputed NIP-01 hash and its `sig`
+/// must be a valid BIP-340 signature over that id by `pubkey`.
+///
+/// # Arguments:
+/// * `event_json` - The event as a JSON object with `id`, `pubkey`, `created_at`, `kind`,
+///   `tags`, `content` and `sig` members.
+///
+/// # Returns:
+/// * A boolean indicating whether the event is authentic. A mismatched id returns false.
+///
+/// # Errors:
+/// * Returns an error if the JSON is malformed, a member is missing or has the wrong type,
+///   or the public key is not a valid x-only key.
+#[wasm_bindgen]
+pub fn verify_nostr_event(event_json: &str) -> Result<bool, ECDSAError> {
+    let event = json::parse(event_json).map_err(|_| ECDSAError::InvalidNostrEvent)?;
+    let id: [u8; 32] = hex_member(&event, "id")?;
+    let pubkey: [u8; 32] = hex_member(&event, "pubkey")?;
+    let sig: [u8; 64] = hex_member(&event, "sig")?;
+
+    if event_id(&event)? != id {
+        return Ok(false);
+    }
+    verify_schnorr(&parse_x_only(&pubkey)?, &id, &sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schnorr::tagged_hash;
+    use k256::elliptic_curve::ff::PrimeField;
+    use k256::elliptic_curve::ops::Reduce;
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+    use k256::{ProjectivePoint, Scalar, U256};
+
+    /// Produces a BIP-340 signature with zero auxiliary randomness.
+    fn sign(secret: [u8; 32], message: &[u8; 32]) -> ([u8; 32], [u8; 64]) {
+        let mut d = Scalar::from_repr(secret.into()).unwrap();
+        let point = (ProjectivePoint::GENERATOR * d).to_affine().to_encoded_point(true);
+        if point.as_bytes()[0] == 0x03 {
+            d = -d;
+        }
+        let pubkey: [u8; 32] = point.x().unwrap().as_slice().try_into().unwrap();
+
+        let t: Vec<u8> = d.to_bytes().iter().zip(tagged_hash("BIP0340/aux", &[&[0; 32]])).map(|(a, b)| a ^ b).collect();
+        let mut k = <Scalar as Reduce<U256>>::from_be_bytes_reduced(tagged_hash("BIP0340/nonce", &[&t, &pubkey, message]).into());
+        let r = (ProjectivePoint::GENERATOR * k).to_affine().to_encoded_point(true);
+        if r.as_bytes()[0] == 0x03 {
+            k = -k;
+        }
+        let r_x = r.x().unwrap().as_slice().to_vec();
+        let e = <Scalar as Reduce<U256>>::from_be_bytes_reduced(tagged_hash("BIP0340/challenge", &[&r_x, &pubkey, message]).into());
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&r_x);
+        sig[32..].copy_from_slice(&(k + e * d).to_bytes());
+        (pubkey, sig)
+    }
+
+    #[test]
+    fn test_signing_helper_matches_bip340() {
+        // BIP-340 test vector 0
+        let mut secret = [0u8; 32];
+        secret[31] = 3;
+        let (pubkey, sig) = sign(secret, &[0; 32]);
+        assert_eq!(hex::encode(pubkey), "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9");
+        assert_eq!(hex::encode(sig), "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0");
+    }
+
+    fn signed_event(content: &str, tags: &str) -> String {
+        let pubkey = sign([0x11; 32], &[0; 32]).0;
+        let unsigned = format!(
+            r#"{{"pubkey":"{}","created_at":1700000000,"kind":1,"tags":{tags},"content":{content}}}"#,
+            hex::encode(pubkey)
+        );
+        let id = event_id(&json::parse(&unsigned).unwrap()).unwrap();
+        let (_, sig) = sign([0x11; 32], &id);
+        format!(
+            r#"{{"id":"{}","pubkey":"{}","created_at":1700000000,"kind":1,"tags":{tags},"content":{content},"sig":"{}"}}"#,
+            hex::encode(id),
+            hex::encode(pubkey),
+            hex::encode(sig)
+        )
+    }
+
+    #[test]
+    fn test_event_id_serialization() {
+        let event = json::parse(
+            r#"{"pubkey":"ab","created_at":1,"kind":7,"tags":[["e","x"],["p"]],"content":"a\"b\\c\n\u0001é"}"#,
+        )
+        .unwrap();
+        let expected = "[0,\"ab\",1,7,[[\"e\",\"x\"],[\"p\"]],\"a\\\"b\\\\c\\n\\u0001é\"]";
+        assert_eq!(event_id(&event).unwrap(), <[u8; 32]>::from(Sha256::digest(expected.as_bytes())));
+    }
+
+    #[test]
+    fn test_verify_nostr_event() {
+        let event = signed_event(r#""hello\nnostr""#, r#"[["t","wasm"]]"#);
+        assert!(verify_nostr_event(&event).unwrap());
+
+        // Editing a signed field changes the id
+        assert!(!verify_nostr_event(&event.replace("wasm", "rust")).unwrap());
+        assert!(!verify_nostr_event(&event.replace("1700000000", "1700000001")).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed_event() {
+        let event = signed_event(r#""""#, "[]");
+        assert!(verify_nostr_event(&event).unwrap());
+        assert!(matches!(verify_nostr_event(&event.replace("\"kind\":1", "\"kind\":\"1\"")), Err(ECDSAError::InvalidNostrEvent)));
+        assert!(matches!(verify_nostr_event(&event.replace("\"tags\":[]", "\"tags\":[1]")), Err(ECDSAError::InvalidNostrEvent)));
+        assert!(verify_nostr_event(&event.to_uppercase()).is_err());
+        assert!(verify_nostr_event("{}").is_err());
+    }
+}
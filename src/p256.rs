@@ -0,0 +1,157 @@
+//! NIST P-256 (secp256r1) ECDSA verification, as used by ES256, WebAuthn and most X.509 PKI.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+use ::p256::ecdsa::{Signature, VerifyingKey};
+use ::p256::elliptic_curve::ops::Reduce;
+use ::p256::{FieldBytes, Scalar, U256};
+use ecdsa::hazmat::VerifyPrimitive;
+
+use crate::ECDSAError;
+
+/// Decodes a SEC1 compressed or uncompressed public key and checks it lies on the curve.
+pub(crate) fn parse_public_key(bytes: &[u8]) -> Result<VerifyingKey, ECDSAError> {
+    VerifyingKey::from_sec1_bytes(bytes).map_err(|_| ECDSAError::InvalidPublicKey)
+}
+
+/// Encodes a public key as an uncompressed SEC1 point.
+pub(crate) fn encode_uncompressed(key: &VerifyingKey) -> Vec<u8> {
+    key.to_encoded_point(false).as_bytes().to_vec()
+}
+
+/// Parses a strict DER or fixed-size (r || s) signature, checking both values are in `[1, n)`.
+pub(crate) fn parse_signature(bytes: &[u8]) -> Result<Signature, ECDSAError> {
+    let signature = if bytes.len() == 64 { Signature::try_from(bytes) } else { Signature::from_der(bytes) };
+    signature.map_err(|_| ECDSAError::InvalidSignatureFormat)
+}
+
+/// Verifies a parsed signature against a message digest. Digests longer than 32 bytes are
+/// truncated to their leftmost 256 bits; high-S signatures are valid on this curve.
+pub(crate) fn verify_prehashed(key: &VerifyingKey, digest: &[u8], signature: &Signature) -> bool {
+    let mut bytes = FieldBytes::default();
+    let len = digest.len().min(32);
+    bytes[32 - len..].copy_from_slice(&digest[..len]);
+    let z = <Scalar as Reduce<U256>>::from_be_bytes_reduced(bytes);
+    ::p256::PublicKey::from(key).as_affine().verify_prehashed(z, signature).is_ok()
+}
+
+/// Verifies raw signature and SEC1 public key bytes against a message digest.
+pub(crate) fn verify_bytes(digest: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+    let signature = parse_signature(signature)?;
+    let public_key = parse_public_key(public_key)?;
+    Ok(verify_prehashed(&public_key, digest, &signature))
+}
+
+/// Verifies hex encoded signature and public key against a message digest.
+fn verify_hex(digest: &[u8], signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+    let signature = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    let public_key = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    verify_bytes(digest, &signature, &public_key)
+}
+
+/// Struct for ECDSA signature verification over NIST P-256.
+#[wasm_bindgen]
+pub struct P256Verifier;
+
+impl Default for P256Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl P256Verifier {
+    /// Initializes a new instance of P256Verifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> P256Verifier {
+        P256Verifier
+    }
+
+    /// Verifies a P-256 signature over the SHA-256 hash of a message (ES256).
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The DER or compact (64-byte r || s) signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key (compressed or uncompressed), provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or public key is malformed.
+    pub fn verify_signature(&self, message: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        verify_hex(&Sha256::digest(message.as_bytes()), signature, public_key)
+    }
+
+    /// Verifies a P-256 signature against a caller-supplied message digest, provided as a hex string.
+    /// Digests longer than 32 bytes are truncated to their leftmost 256 bits.
+    pub fn verify_prehashed(&self, digest: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let digest = hex::decode(digest).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        if digest.is_empty() {
+            return Err(ECDSAError::InvalidDigestLength);
+        }
+        verify_hex(&digest, signature, public_key)
+    }
+
+    /// Verifies a P-256 signature over binary data, hashing it with SHA-256.
+    pub fn verify_signature_bytes(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+        verify_bytes(&Sha256::digest(message), signature, public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6979 appendix A.2.5 key and SHA-256 signatures
+    const PUBLIC_KEY: &str = "0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6";
+    const UNCOMPRESSED: &str = "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+                                7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+    const SAMPLE_R: &str = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716";
+    const SAMPLE_S: &str = "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+    const TEST_DER: &str = "3045022100f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367\
+                            0220019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083";
+
+    #[test]
+    fn test_rfc6979_vectors() {
+        let verifier = P256Verifier::new();
+        let compact = format!("{}{}", SAMPLE_R, SAMPLE_S);
+        assert!(verifier.verify_signature("sample", &compact, PUBLIC_KEY).unwrap());
+        assert!(verifier.verify_signature("sample", &compact, UNCOMPRESSED).unwrap());
+        assert!(verifier.verify_signature("test", TEST_DER, PUBLIC_KEY).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_wrong_message_and_key() {
+        let verifier = P256Verifier::new();
+        assert!(!verifier.verify_signature("sample", TEST_DER, PUBLIC_KEY).unwrap());
+
+        // Same x-coordinate, opposite y
+        let flipped = format!("02{}", &PUBLIC_KEY[2..]);
+        assert!(!verifier.verify_signature("test", TEST_DER, &flipped).unwrap());
+
+        let mut off_curve = UNCOMPRESSED.to_string();
+        off_curve.replace_range(128.., "00");
+        assert!(verifier.verify_signature("test", TEST_DER, &off_curve).is_err());
+    }
+
+    #[test]
+    fn test_prehashed_and_bytes() {
+        let verifier = P256Verifier::new();
+        let digest = hex::encode(Sha256::digest(b"test"));
+        assert!(verifier.verify_prehashed(&digest, TEST_DER, PUBLIC_KEY).unwrap());
+        assert!(verifier.verify_prehashed("", TEST_DER, PUBLIC_KEY).is_err());
+
+        let signature = hex::decode(TEST_DER).unwrap();
+        let public_key = hex::decode(PUBLIC_KEY).unwrap();
+        assert!(verifier.verify_signature_bytes(b"test", &signature, &public_key).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_scalars() {
+        let n = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+        let verifier = P256Verifier::new();
+        assert!(verifier.verify_signature("sample", &format!("{}{}", n, SAMPLE_S), PUBLIC_KEY).is_err());
+        assert!(verifier.verify_signature("sample", &format!("{}{}", SAMPLE_R, "00".repeat(32)), PUBLIC_KEY).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! NIST P-384 (secp384r1) ECDSA verification, as used by ES384 tokens and high-assurance X.509 PKI.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha384};
+use ::p384_13::ecdsa::signature::hazmat::PrehashVerifier;
+use ::p384_13::ecdsa::{Signature, VerifyingKey};
+use ::p384_13::FieldBytes;
+
+use crate::ECDSAError;
+
+/// Decodes a SEC1 compressed or uncompressed public key and checks it lies on the curve.
+pub(crate) fn parse_public_key(bytes: &[u8]) -> Result<VerifyingKey, ECDSAError> {
+    VerifyingKey::from_sec1_bytes(bytes).map_err(|_| ECDSAError::InvalidPublicKey)
+}
+
+/// Encodes a public key as an uncompressed SEC1 point.
+pub(crate) fn encode_uncompressed(key: &VerifyingKey) -> Vec<u8> {
+    key.to_encoded_point(false).as_bytes().to_vec()
+}
+
+/// Parses a strict DER or fixed-size (r || s) signature, checking both values are in `[1, n)`.
+pub(crate) fn parse_signature(bytes: &[u8]) -> Result<Signature, ECDSAError> {
+    let signature = if bytes.len() == 96 { Signature::from_slice(bytes) } else { Signature::from_der(bytes) };
+    signature.map_err(|_| ECDSAError::InvalidSignatureFormat)
+}
+
+/// Verifies a parsed signature against a message digest. Digests longer than 48 bytes are
+/// truncated to their leftmost 48 bytes; high-S signatures are valid on this curve.
+pub(crate) fn verify_prehashed(key: &VerifyingKey, digest: &[u8], signature: &Signature) -> bool {
+    let mut bytes = FieldBytes::default();
+    let len = digest.len().min(48);
+    bytes[48 - len..].copy_from_slice(&digest[..len]);
+    key.verify_prehash(&bytes, signature).is_ok()
+}
+
+/// Verifies raw signature and SEC1 public key bytes against a message digest.
+pub(crate) fn verify_bytes(digest: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+    let signature = parse_signature(signature)?;
+    let public_key = parse_public_key(public_key)?;
+    Ok(verify_prehashed(&public_key, digest, &signature))
+}
+
+/// Verifies hex encoded signature and public key against a message digest.
+fn verify_hex(digest: &[u8], signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+    let signature = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    let public_key = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    verify_bytes(digest, &signature, &public_key)
+}
+
+/// Struct for ECDSA signature verification over NIST P-384.
+#[wasm_bindgen]
+pub struct P384Verifier;
+
+impl Default for P384Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl P384Verifier {
+    /// Initializes a new instance of P384Verifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> P384Verifier {
+        P384Verifier
+    }
+
+    /// Verifies a P-384 signature over the SHA-384 hash of a message (ES384).
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The DER or compact (96-byte r || s) signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key (compressed or uncompressed), provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or public key is malformed.
+    pub fn verify_signature(&self, message: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        verify_hex(&Sha384::digest(message.as_bytes()), signature, public_key)
+    }
+
+    /// Verifies a P-384 signature against a caller-supplied message digest, provided as a hex string.
+    pub fn verify_prehashed(&self, digest: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let digest = hex::decode(digest).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        if digest.is_empty() {
+            return Err(ECDSAError::InvalidDigestLength);
+        }
+        verify_hex(&digest, signature, public_key)
+    }
+
+    /// Verifies a P-384 signature over binary data, hashing it with SHA-384.
+    pub fn verify_signature_bytes(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+        verify_bytes(&Sha384::digest(message), signature, public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "02ec3a4e415b4e19a4568618029f427fa5da9a8bc4ae92e02e06aae5286b300c64def8f0ea9055866064a254515480bc13";
+    const UNCOMPRESSED: &str = "04ec3a4e415b4e19a4568618029f427fa5da9a8bc4ae92e02e06aae5286b300c64def8f0ea9055866064a254515480bc13\
+                                8015d9b72d7d57244ea8ef9ac0c621896708a59367f9dfb9f54ca84b3f1c9db1288b231c3ae0d4fe7344fd2533264720";
+    const SAMPLE_DER: &str = "306502305a627081682c939771d09b1e3cfbcd2f8d37cc8d8b3a492eabc9b43341ef21e77f9926ef4556410198aa937ec46737c1\
+                              0231009eb05711432ad9eff117bbfeabb60040acafcc09a0649ed8b878cde58428490f3a4dd3635302ac7bcde3981076a83638";
+    const TEST_COMPACT: &str = "2f91aa473e2e47a0890d15713c3d1d61445f5832430a3fc9bdc170964805272ba74f535c14d8db09491abe52ef213c3f\
+                                0d5be69cc7e2d257a1752826ad1b4fc017a89a62b4f0a388a32dcac515250f0f1f114ee2220da7fa93984562318c2959";
+
+    #[test]
+    fn test_verify_signature() {
+        let verifier = P384Verifier::new();
+        assert!(verifier.verify_signature("sample", SAMPLE_DER, PUBLIC_KEY).unwrap());
+        assert!(verifier.verify_signature("sample", SAMPLE_DER, UNCOMPRESSED).unwrap());
+        assert!(verifier.verify_signature("test", TEST_COMPACT, PUBLIC_KEY).unwrap());
+        assert!(!verifier.verify_signature("test", SAMPLE_DER, PUBLIC_KEY).unwrap());
+    }
+
+    #[test]
+    fn test_prehashed() {
+        let verifier = P384Verifier::new();
+        let digest = hex::encode(Sha384::digest(b"test"));
+        assert!(verifier.verify_prehashed(&digest, TEST_COMPACT, PUBLIC_KEY).unwrap());
+        assert!(verifier.verify_prehashed(&digest, TEST_COMPACT, &PUBLIC_KEY[..96]).is_err());
+    }
+}
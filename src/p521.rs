@@ -0,0 +1,128 @@
+//! NIST P-521 (secp521r1) ECDSA verification, as used by ES512 tokens and high-assurance X.509 PKI.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha512};
+use ::p521_13::ecdsa::signature::hazmat::PrehashVerifier;
+use ::p521_13::ecdsa::{Signature, VerifyingKey};
+use ::p521_13::FieldBytes;
+
+use crate::ECDSAError;
+
+/// Decodes a SEC1 compressed or uncompressed public key and checks it lies on the curve.
+pub(crate) fn parse_public_key(bytes: &[u8]) -> Result<VerifyingKey, ECDSAError> {
+    VerifyingKey::from_sec1_bytes(bytes).map_err(|_| ECDSAError::InvalidPublicKey)
+}
+
+/// Encodes a public key as an uncompressed SEC1 point.
+pub(crate) fn encode_uncompressed(key: &VerifyingKey) -> Vec<u8> {
+    key.to_encoded_point(false).as_bytes().to_vec()
+}
+
+/// Parses a strict DER or fixed-size (r || s) signature, checking both values are in `[1, n)`.
+pub(crate) fn parse_signature(bytes: &[u8]) -> Result<Signature, ECDSAError> {
+    let signature = if bytes.len() == 132 { Signature::from_slice(bytes) } else { Signature::from_der(bytes) };
+    signature.map_err(|_| ECDSAError::InvalidSignatureFormat)
+}
+
+/// Verifies a parsed signature against a message digest. Digests longer than 66 bytes are
+/// truncated to their leftmost 66 bytes; high-S signatures are valid on this curve.
+pub(crate) fn verify_prehashed(key: &VerifyingKey, digest: &[u8], signature: &Signature) -> bool {
+    let mut bytes = FieldBytes::default();
+    let len = digest.len().min(66);
+    bytes[66 - len..].copy_from_slice(&digest[..len]);
+    key.verify_prehash(&bytes, signature).is_ok()
+}
+
+/// Verifies raw signature and SEC1 public key bytes against a message digest.
+pub(crate) fn verify_bytes(digest: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+    let signature = parse_signature(signature)?;
+    let public_key = parse_public_key(public_key)?;
+    Ok(verify_prehashed(&public_key, digest, &signature))
+}
+
+/// Verifies hex encoded signature and public key against a message digest.
+fn verify_hex(digest: &[u8], signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+    let signature = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    let public_key = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    verify_bytes(digest, &signature, &public_key)
+}
+
+/// Struct for ECDSA signature verification over NIST P-521.
+#[wasm_bindgen]
+pub struct P521Verifier;
+
+impl Default for P521Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl P521Verifier {
+    /// Initializes a new instance of P521Verifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> P521Verifier {
+        P521Verifier
+    }
+
+    /// Verifies a P-521 signature over the SHA-512 hash of a message (ES512).
+    ///
+    /// # Arguments:
+    /// * `message` - The message that was signed.
+    /// * `signature` - The DER or compact (132-byte r || s) signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key (compressed or uncompressed), provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or public key is malformed.
+    pub fn verify_signature(&self, message: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        verify_hex(&Sha512::digest(message.as_bytes()), signature, public_key)
+    }
+
+    /// Verifies a P-521 signature against a caller-supplied message digest, provided as a hex string.
+    pub fn verify_prehashed(&self, digest: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let digest = hex::decode(digest).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        if digest.is_empty() {
+            return Err(ECDSAError::InvalidDigestLength);
+        }
+        verify_hex(&digest, signature, public_key)
+    }
+
+    /// Verifies a P-521 signature over binary data, hashing it with SHA-512.
+    pub fn verify_signature_bytes(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+        verify_bytes(&Sha512::digest(message), signature, public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "0301894550d0785932e00eaa23b694f213f8c3121f86dc97a04e5a7167db4e5bcd371123d46e45db6b5d5370a7f20fb633155d38ffa16d2bd761dcac474b9a2f5023a4";
+    const UNCOMPRESSED: &str = "0401894550d0785932e00eaa23b694f213f8c3121f86dc97a04e5a7167db4e5bcd371123d46e45db6b5d5370a7f20fb633155d38ffa16d2bd761dcac474b9a2f5023a4\
+                                00493101c962cd4d2fddf782285e64584139c2f91b47f87ff82354d6630f746a28a0db25741b5b34a828008b22acc23f924faafbd4d33f81ea66956dfeaa2bfdfcf5";
+    // Long enough to need the 0x81 DER length form
+    const SAMPLE_DER: &str = "3081880242014a8a910868b8250da4690a0c12e823f513030ab8a67428c762350627ee7cc8136d0689b02e5cf1bcebf4d25ac7dfb8bb511e7c7ece7e069de25726edeba303c0db\
+                              024201a68052feb8134eebccd353c4d9c2bbeff826136a98a541838b09934ae460189f3f817a150c2948e4419d10f3701298bdaf40531e421b46657ece942f7d95e8b348";
+    const TEST_COMPACT: &str = "018f2e1540c0eda0cc113db4c372cf5a46eedc36c2a9c1218af1850cf641433952355d95ce4cccabe231a8d84c61dd94327a02883362f765cea0f04033c52061dcf4\
+                                0073c6115c969ca9fd5bef7066755b72da0f7821f0929eb6a4f6c17a53da44fd2b122029a9c0115e1dfe20420280af352a861268a40fb4e9c18465b8b1f2ee5eb44c";
+
+    #[test]
+    fn test_verify_signature() {
+        let verifier = P521Verifier::new();
+        assert!(verifier.verify_signature("sample", SAMPLE_DER, PUBLIC_KEY).unwrap());
+        assert!(verifier.verify_signature("sample", SAMPLE_DER, UNCOMPRESSED).unwrap());
+        assert!(verifier.verify_signature("test", TEST_COMPACT, PUBLIC_KEY).unwrap());
+        assert!(!verifier.verify_signature("test", SAMPLE_DER, PUBLIC_KEY).unwrap());
+    }
+
+    #[test]
+    fn test_prehashed() {
+        let verifier = P521Verifier::new();
+        let digest = hex::encode(Sha512::digest(b"test"));
+        assert!(verifier.verify_prehashed(&digest, TEST_COMPACT, PUBLIC_KEY).unwrap());
+        assert!(verifier.verify_prehashed("", TEST_COMPACT, PUBLIC_KEY).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Password-based key derivation: scrypt (RFC 7914) and Argon2id (RFC 9106), using the
+//! RustCrypto `scrypt` and `argon2` crates.
+
+use wasm_bindgen::prelude::*;
+use argon2::{Argon2, AssociatedData, ParamsBuilder, Version};
+
+use crate::ECDSAError;
+
+/// Largest memory budget accepted for either algorithm (1 GiB), well below the 4 GiB
+/// wasm32 address space.
+const MAX_MEMORY_BYTES: u64 = 1 << 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Algorithm {
+    Scrypt { n: u32, r: u32, p: u32 },
+    Argon2id { memory_kib: u32, iterations: u32, parallelism: u32 },
+}
+
+/// Cost parameters for `derive_key_from_password`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PasswordKdfParams {
+    algorithm: Algorithm,
+    length: usize,
+}
+
+#[wasm_bindgen]
+impl PasswordKdfParams {
+    /// scrypt parameters.
+    ///
+    /// # Arguments:
+    /// * `n` - CPU/memory cost; a power of two greater than 1 (e.g. 262144 for keystores).
+    /// * `r` - Block size (usually 8).
+    /// * `p` - Parallelization (usually 1).
+    /// * `length` - Derived key length in bytes.
+    ///
+    /// # Errors:
+    /// * Returns an error if the parameters are out of range or need more than 1 GiB.
+    pub fn scrypt(n: u32, r: u32, p: u32, length: usize) -> Result<PasswordKdfParams, ECDSAError> {
+        let memory = 128 * r as u64 * n as u64;
+        if n < 2 || !n.is_power_of_two() || r == 0 || p == 0 || r as u64 * p as u64 >= 1 << 30 {
+            return Err(ECDSAError::InvalidKdfParams);
+        }
+        // RFC 7914 requires n < 2^(128 * r / 8)
+        if n.trailing_zeros() as u64 >= 16 * r as u64 {
+            return Err(ECDSAError::InvalidKdfParams);
+        }
+        if memory > MAX_MEMORY_BYTES || length == 0 {
+            return Err(ECDSAError::InvalidKdfParams);
+        }
+        Ok(PasswordKdfParams { algorithm: Algorithm::Scrypt { n, r, p }, length })
+    }
+
+    /// Argon2id (version 0x13) parameters.
+    ///
+    /// # Arguments:
+    /// * `memory_kib` - Memory size in KiB; at least 8 * `parallelism`.
+    /// * `iterations` - Number of passes over memory (at least 1).
+    /// * `parallelism` - Number of lanes (at least 1). Lanes are computed sequentially.
+    /// * `length` - Derived key length in bytes (at least 4).
+    ///
+    /// # Errors:
+    /// * Returns an error if the parameters are out of range or need more than 1 GiB.
+    pub fn argon2id(memory_kib: u32, iterations: u32, parallelism: u32, length: usize) -> Result<PasswordKdfParams, ECDSAError> {
+        if parallelism == 0 || parallelism >= 1 << 24 || iterations == 0 || length < 4 {
+            return Err(ECDSAError::InvalidKdfParams);
+        }
+        if (memory_kib as u64) < 8 * parallelism as u64 || memory_kib as u64 * 1024 > MAX_MEMORY_BYTES {
+            return Err(ECDSAError::InvalidKdfParams);
+        }
+        let algorithm = Algorithm::Argon2id { memory_kib, iterations, parallelism };
+        Ok(PasswordKdfParams { algorithm, length })
+    }
+
+    /// The derived key length in bytes.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.length
+    }
+}
+
+/// Derives a key from a password with scrypt or Argon2id.
+///
+/// # Arguments:
+/// * `password` - The password bytes (UTF-8 encode strings first).
+/// * `salt` - A unique, random salt.
+/// * `params` - The algorithm and its cost parameters.
+///
+/// # Returns:
+/// * The derived key (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidKdfParams` if an Argon2id salt is shorter than 8 bytes.
+#[wasm_bindgen]
+pub fn derive_key_from_password(password: &[u8], salt: &[u8], params: &PasswordKdfParams) -> Result<Vec<u8>, ECDSAError> {
+    match params.algorithm {
+        Algorithm::Scrypt { n, r, p } => scrypt(password, salt, n, r, p, params.length),
+        Algorithm::Argon2id { memory_kib, iterations, parallelism } => {
+            argon2id(password, salt, &[], &[], memory_kib, iterations, parallelism, params.length)
+        }
+    }
+}
+
+/// scrypt (RFC 7914).
+pub(crate) fn scrypt(password: &[u8], salt: &[u8], n: u32, r: u32, p: u32, length: usize) -> Result<Vec<u8>, ECDSAError> {
+    if !n.is_power_of_two() {
+        return Err(ECDSAError::InvalidKdfParams);
+    }
+    // The length in `Params` only applies to PHC strings; the output buffer sets it here
+    let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, scrypt::Params::RECOMMENDED_LEN)
+        .map_err(|_| ECDSAError::InvalidKdfParams)?;
+    let mut output = vec![0u8; length];
+    scrypt::scrypt(password, salt, &params, &mut output).map_err(|_| ECDSAError::InvalidKdfParams)?;
+    Ok(output)
+}
+
+/// Argon2id (RFC 9106) with an optional secret key and associated data.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    length: usize,
+) -> Result<Vec<u8>, ECDSAError> {
+    let associated_data = AssociatedData::new(associated_data).map_err(|_| ECDSAError::InvalidKdfParams)?;
+    let params = ParamsBuilder::new()
+        .m_cost(memory_kib)
+        .t_cost(iterations)
+        .p_cost(parallelism)
+        .output_len(length)
+        .data(associated_data)
+        .build()
+        .map_err(|_| ECDSAError::InvalidKdfParams)?;
+    let argon2 = Argon2::new_with_secret(secret, argon2::Algorithm::Argon2id, Version::V0x13, params)
+        .map_err(|_| ECDSAError::InvalidKdfParams)?;
+    let mut output = vec![0u8; length];
+    argon2.hash_password_into(password, salt, &mut output).map_err(|_| ECDSAError::InvalidKdfParams)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrypt_vectors() {
+        // RFC 7914 section 12
+        let expected = "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+                        fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906";
+        let params = PasswordKdfParams::scrypt(16, 1, 1, 64).unwrap();
+        assert_eq!(hex::encode(derive_key_from_password(b"", b"", &params).unwrap()), expected);
+
+        let expected = "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+                        2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640";
+        let params = PasswordKdfParams::scrypt(1024, 8, 16, 64).unwrap();
+        assert_eq!(hex::encode(derive_key_from_password(b"password", b"NaCl", &params).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_argon2id_rfc9106_vector() {
+        let tag = argon2id(&[0x01; 32], &[0x02; 16], &[0x03; 8], &[0x04; 12], 32, 3, 4, 32).unwrap();
+        assert_eq!(hex::encode(tag), "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659");
+    }
+
+    #[test]
+    fn test_argon2id_params() {
+        let params = PasswordKdfParams::argon2id(64, 2, 1, 32).unwrap();
+        assert_eq!(
+            hex::encode(derive_key_from_password(b"password", b"somesalt", &params).unwrap()),
+            "16a1a498734609dd01456da406de9f3d9da93e6c86c300a12fc1465214ce4922"
+        );
+
+        // Memory that is not a multiple of 4 * lanes, and an output longer than 64 bytes
+        let params = PasswordKdfParams::argon2id(300, 1, 2, 80).unwrap();
+        assert_eq!(
+            hex::encode(derive_key_from_password(b"password", b"somesalt", &params).unwrap()),
+            "40e3b32db068c67b899f2b720eb277897d67874fad18a8873775b94ae01c99b7d5bbdae1e2e160500fbd3d89631ed17d\
+             7648d909fdf4e30712fe85f30919d20e4932bc808acf6064792839674f653df5"
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_params() {
+        assert!(PasswordKdfParams::scrypt(1000, 8, 1, 32).is_err());
+        assert!(PasswordKdfParams::scrypt(1 << 20, 8, 1, 32).is_ok());
+        assert!(PasswordKdfParams::scrypt(1 << 21, 8, 1, 32).is_err());
+        assert!(PasswordKdfParams::argon2id(8, 1, 2, 32).is_err());
+        assert!(PasswordKdfParams::argon2id(64, 0, 1, 32).is_err());
+
+        let params = PasswordKdfParams::argon2id(64, 1, 1, 32).unwrap();
+        assert!(matches!(derive_key_from_password(b"password", b"salt", &params), Err(ECDSAError::InvalidKdfParams)));
+    }
+}
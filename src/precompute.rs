@@ -0,0 +1,200 @@
+//! Precomputed multiplication tables for secp256k1 verification.
+//!
+//! Verification computes `u1 * G + u2 * Q`. k256 does this in constant time, rebuilding its
+//! lookup tables for both points on every call. Verification only handles public values, so
+//! it can instead use variable-time fixed-base tables holding `j * 16^i * P` for each 4-bit
+//! window `i` and digit `j`, which turn a multiplication into at most 64 additions.
+//!
+//! The table for `G` is built once and shared. A public key gets a table the second time in a
+//! row it is verified against, so high-volume verification against one key is fast while
+//! one-off keys do not pay for a table they will not reuse. `warm_up` builds either ahead of
+//! time, e.g. at page load.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::{Signature, VerifyingKey};
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::AffineXCoordinate;
+use k256::{ProjectivePoint, PublicKey, Scalar, U256};
+
+use crate::{keys, ECDSAError};
+
+/// Four-bit windows in a 256-bit scalar.
+const WINDOWS: usize = 64;
+
+/// The nonzero multiples `j * 16^i * P` (`j` in 1..=15) of a point, for each window `i`.
+struct FixedBaseTable(Vec<[ProjectivePoint; 15]>);
+
+impl FixedBaseTable {
+    fn new(point: &ProjectivePoint) -> FixedBaseTable {
+        let mut windows = Vec::with_capacity(WINDOWS);
+        let mut base = *point;
+        for _ in 0..WINDOWS {
+            let mut multiples = [ProjectivePoint::IDENTITY; 15];
+            let mut sum = ProjectivePoint::IDENTITY;
+            for multiple in multiples.iter_mut() {
+                sum += &base;
+                *multiple = sum;
+            }
+            windows.push(multiples);
+            base = sum + base;
+        }
+        FixedBaseTable(windows)
+    }
+
+    /// Multiplies the table's point by `scalar`, in variable time.
+    fn mul(&self, scalar: &Scalar) -> ProjectivePoint {
+        let mut result = ProjectivePoint::IDENTITY;
+        for (i, byte) in scalar.to_bytes().iter().rev().enumerate() {
+            for (window, digit) in [(2 * i, byte & 0x0f), (2 * i + 1, byte >> 4)] {
+                if digit != 0 {
+                    result += &self.0[window][digit as usize - 1];
+                }
+            }
+        }
+        result
+    }
+}
+
+fn generator_table() -> &'static FixedBaseTable {
+    static TABLE: OnceLock<FixedBaseTable> = OnceLock::new();
+    TABLE.get_or_init(|| FixedBaseTable::new(&ProjectivePoint::GENERATOR))
+}
+
+/// The most recently used public key, with its table once it has been used twice in a row.
+struct CachedKey {
+    key: VerifyingKey,
+    table: Option<Rc<FixedBaseTable>>,
+}
+
+thread_local! {
+    static KEY_CACHE: RefCell<Option<CachedKey>> = const { RefCell::new(None) };
+}
+
+fn key_point(key: &VerifyingKey) -> ProjectivePoint {
+    PublicKey::from(key).to_projective()
+}
+
+/// The table for `key` if it was also the previous key, building it on this second use.
+fn key_table(key: &VerifyingKey) -> Option<Rc<FixedBaseTable>> {
+    KEY_CACHE.with(|cell| {
+        let mut cache = cell.borrow_mut();
+        match cache.as_mut() {
+            Some(cached) if cached.key == *key => {
+                Some(cached.table.get_or_insert_with(|| Rc::new(FixedBaseTable::new(&key_point(key)))).clone())
+            }
+            _ => {
+                *cache = Some(CachedKey { key: *key, table: None });
+                None
+            }
+        }
+    })
+}
+
+/// Verifies a low-S signature over the digest scalar `z`, with the same result as k256's
+/// `verify_prehashed`.
+pub(crate) fn verify_prehashed(key: &VerifyingKey, z: Scalar, sig: &Signature) -> bool {
+    // High-S signatures are the ones `normalize_s` would change
+    if sig.normalize_s().is_some() {
+        return false;
+    }
+    let (r, s) = (sig.r(), sig.s());
+
+    let s_inv = s.invert().unwrap();
+    let u1 = z * s_inv;
+    let u2 = *r * s_inv;
+
+    let key_part = match key_table(key) {
+        Some(table) => table.mul(&u2),
+        None => key_point(key) * u2,
+    };
+    let x = (generator_table().mul(&u1) + key_part).to_affine().x();
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(x) == *r
+}
+
+/// Builds verification's precomputed tables now, so that the first verifications are not
+/// slowed down by it. Call it at page load or worker start-up.
+///
+/// # Arguments:
+/// * `public_key` - Optionally, a SEC1 public key that will be verified against repeatedly,
+///   provided as a hex string; its table is built and cached for the calling thread.
+///
+/// # Errors:
+/// * `InvalidPublicKey` if the public key is malformed.
+#[wasm_bindgen]
+pub fn warm_up(public_key: Option<String>) -> Result<(), ECDSAError> {
+    generator_table();
+    if let Some(public_key) = public_key {
+        let key = keys::parse_public_key_hex(&public_key)?;
+        let table = Rc::new(FixedBaseTable::new(&key_point(&key)));
+        KEY_CACHE.with(|cell| *cell.borrow_mut() = Some(CachedKey { key, table: Some(table) }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{digest_to_scalar, signer};
+    use ecdsa::hazmat::VerifyPrimitive;
+    use k256::ecdsa::SigningKey;
+
+    fn cached_table() -> Option<bool> {
+        KEY_CACHE.with(|cell| cell.borrow().as_ref().map(|cached| cached.table.is_some()))
+    }
+
+    #[test]
+    fn test_table_multiplication() {
+        let table = FixedBaseTable::new(&ProjectivePoint::GENERATOR);
+        let scalars = [Scalar::ZERO, Scalar::ONE, -Scalar::ONE, digest_to_scalar(&[0xa5; 32]), digest_to_scalar(&[0x10])];
+        for scalar in scalars {
+            assert_eq!(table.mul(&scalar), ProjectivePoint::GENERATOR * scalar);
+        }
+    }
+
+    #[test]
+    fn test_matches_k256() {
+        let key = SigningKey::from_bytes(&[0x11; 32]).unwrap();
+        let other = SigningKey::from_bytes(&[0x22; 32]).unwrap().verifying_key();
+        let digest = [0x42; 32];
+        let (sig, _) = signer::sign_digest(&key, &digest, &[]).unwrap();
+        let z = digest_to_scalar(&digest);
+
+        // The first call takes the untabled path, the later ones use the key's table
+        for _ in 0..3 {
+            assert!(verify_prehashed(&key.verifying_key(), z, &sig));
+            assert!(!verify_prehashed(&key.verifying_key(), digest_to_scalar(&[0x43; 32]), &sig));
+        }
+        assert_eq!(cached_table(), Some(true));
+        assert!(!verify_prehashed(&other, z, &sig));
+        assert_eq!(cached_table(), Some(false));
+
+        let affine = PublicKey::from(&key.verifying_key());
+        assert!(affine.as_affine().verify_prehashed(z, &sig).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_high_s() {
+        let key = SigningKey::from_bytes(&[0x11; 32]).unwrap();
+        let digest = [0x42; 32];
+        let (sig, _) = signer::sign_digest(&key, &digest, &[]).unwrap();
+        let high = Signature::from_scalars(*sig.r(), -*sig.s()).unwrap();
+        assert!(!verify_prehashed(&key.verifying_key(), digest_to_scalar(&digest), &high));
+    }
+
+    #[test]
+    fn test_warm_up() {
+        let key = SigningKey::from_bytes(&[0x33; 32]).unwrap();
+        warm_up(None).unwrap();
+        warm_up(Some(hex::encode(key.verifying_key().to_bytes()))).unwrap();
+        assert_eq!(cached_table(), Some(true));
+
+        let digest = [0x07; 32];
+        let (sig, _) = signer::sign_digest(&key, &digest, &[]).unwrap();
+        assert!(verify_prehashed(&key.verifying_key(), digest_to_scalar(&digest), &sig));
+        assert!(matches!(warm_up(Some("02abcd".to_string())), Err(ECDSAError::InvalidPublicKey)));
+    }
+}
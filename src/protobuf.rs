@@ -0,0 +1,207 @@
+//! Minimal protocol buffers reader and writer used by the IPNS and libp2p features.
+//! Messages are read without a schema into their fields in wire order; groups (wire types 3
+//! and 4) are rejected, as no format read here uses them.
+
+use crate::ECDSAError;
+
+const WIRE_VARINT: u8 = 0;
+const WIRE_FIXED64: u8 = 1;
+const WIRE_BYTES: u8 = 2;
+const WIRE_FIXED32: u8 = 5;
+
+/// A field value as encoded on the wire.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Field<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    /// Length-delimited: bytes, strings and embedded messages
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+/// A decoded message.
+#[derive(Debug)]
+pub(crate) struct Message<'a> {
+    fields: Vec<(u64, Field<'a>)>,
+}
+
+/// Reads an unsigned LEB128 varint at the start of `bytes`, returning it with its length.
+pub(crate) fn read_varint(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(10) {
+        // The tenth byte holds the top bit of a u64 only
+        if i == 9 && byte > 1 {
+            return None;
+        }
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// Appends an unsigned LEB128 varint.
+pub(crate) fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+/// Appends a varint field.
+#[cfg(test)]
+pub(crate) fn write_varint_field(output: &mut Vec<u8>, number: u64, value: u64) {
+    write_varint(output, number << 3 | WIRE_VARINT as u64);
+    write_varint(output, value);
+}
+
+/// Appends a length-delimited field.
+#[cfg(test)]
+pub(crate) fn write_bytes_field(output: &mut Vec<u8>, number: u64, value: &[u8]) {
+    write_varint(output, number << 3 | WIRE_BYTES as u64);
+    write_varint(output, value.len() as u64);
+    output.extend_from_slice(value);
+}
+
+impl<'a> Message<'a> {
+    /// Decodes a complete message.
+    pub(crate) fn decode(mut bytes: &'a [u8]) -> Result<Message<'a>, ECDSAError> {
+        let varint = |bytes: &mut &'a [u8]| {
+            let (value, length) = read_varint(bytes).ok_or(ECDSAError::InvalidProtobuf)?;
+            *bytes = &bytes[length..];
+            Ok::<_, ECDSAError>(value)
+        };
+        let take = |bytes: &mut &'a [u8], length: u64| {
+            let length = usize::try_from(length).ok().filter(|&length| length <= bytes.len()).ok_or(ECDSAError::InvalidProtobuf)?;
+            let (taken, rest) = bytes.split_at(length);
+            *bytes = rest;
+            Ok::<_, ECDSAError>(taken)
+        };
+
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let key = varint(&mut bytes)?;
+            let number = key >> 3;
+            if number == 0 {
+                return Err(ECDSAError::InvalidProtobuf);
+            }
+            let field = match (key & 7) as u8 {
+                WIRE_VARINT => Field::Varint(varint(&mut bytes)?),
+                WIRE_FIXED64 => Field::Fixed64(u64::from_le_bytes(take(&mut bytes, 8)?.try_into().unwrap())),
+                WIRE_BYTES => {
+                    let length = varint(&mut bytes)?;
+                    Field::Bytes(take(&mut bytes, length)?)
+                }
+                WIRE_FIXED32 => Field::Fixed32(u32::from_le_bytes(take(&mut bytes, 4)?.try_into().unwrap())),
+                _ => return Err(ECDSAError::InvalidProtobuf),
+            };
+            fields.push((number, field));
+        }
+        Ok(Message { fields })
+    }
+
+    /// The last value of a field, which takes precedence over earlier ones as in protobuf
+    /// parsers, or `None` if it is absent.
+    pub(crate) fn field(&self, number: u64) -> Option<Field<'a>> {
+        self.fields.iter().rev().find(|(n, _)| *n == number).map(|&(_, field)| field)
+    }
+
+    /// Reads every value of a repeated length-delimited field, in order.
+    ///
+    /// # Errors:
+    /// * `InvalidProtobuf` if a value has another wire type.
+    pub(crate) fn repeated_bytes(&self, number: u64) -> Result<Vec<&'a [u8]>, ECDSAError> {
+        self.fields
+            .iter()
+            .filter(|(n, _)| *n == number)
+            .map(|(_, field)| match field {
+                Field::Bytes(bytes) => Ok(*bytes),
+                _ => Err(ECDSAError::InvalidProtobuf),
+            })
+            .collect()
+    }
+
+    /// Reads a length-delimited field.
+    ///
+    /// # Errors:
+    /// * `InvalidProtobuf` if the field has another wire type.
+    pub(crate) fn bytes(&self, number: u64) -> Result<Option<&'a [u8]>, ECDSAError> {
+        match self.field(number) {
+            None => Ok(None),
+            Some(Field::Bytes(bytes)) => Ok(Some(bytes)),
+            Some(_) => Err(ECDSAError::InvalidProtobuf),
+        }
+    }
+
+    /// Reads a varint field: an integer, boolean or enum.
+    ///
+    /// # Errors:
+    /// * `InvalidProtobuf` if the field has another wire type.
+    pub(crate) fn varint(&self, number: u64) -> Result<Option<u64>, ECDSAError> {
+        match self.field(number) {
+            None => Ok(None),
+            Some(Field::Varint(value)) => Ok(Some(value)),
+            Some(_) => Err(ECDSAError::InvalidProtobuf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varints() {
+        for (value, encoding) in [(0, "00"), (1, "01"), (150, "9601"), (300, "ac02"), (u64::MAX, "ffffffffffffffffff01")] {
+            let bytes = hex::decode(encoding).unwrap();
+            assert_eq!(read_varint(&bytes), Some((value, bytes.len())), "{encoding}");
+            let mut output = Vec::new();
+            write_varint(&mut output, value);
+            assert_eq!(output, bytes);
+        }
+        // Truncated, and beyond 64 bits
+        assert_eq!(read_varint(&[0x80]), None);
+        assert_eq!(read_varint(&hex::decode("ffffffffffffffffff02").unwrap()), None);
+    }
+
+    #[test]
+    fn test_decode_message() {
+        // Examples from the protobuf encoding guide, plus fixed-width fields
+        let bytes = hex::decode("089601120774657374696e67190100000000000000250200000008ac02").unwrap();
+        let message = Message::decode(&bytes).unwrap();
+        assert_eq!(message.bytes(2).unwrap(), Some(&b"testing"[..]));
+        assert_eq!(message.field(3), Some(Field::Fixed64(1)));
+        assert_eq!(message.field(4), Some(Field::Fixed32(2)));
+        // The last occurrence wins
+        assert_eq!(message.varint(1).unwrap(), Some(300));
+        assert_eq!(message.varint(5).unwrap(), None);
+        assert!(matches!(message.varint(2), Err(ECDSAError::InvalidProtobuf)));
+        assert_eq!(message.repeated_bytes(2).unwrap(), [b"testing"]);
+        assert!(message.repeated_bytes(6).unwrap().is_empty());
+        assert!(message.repeated_bytes(1).is_err());
+
+        let mut encoded = Vec::new();
+        write_varint_field(&mut encoded, 1, 150);
+        write_bytes_field(&mut encoded, 2, b"testing");
+        assert_eq!(encoded, bytes[..12]);
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        for encoding in [
+            // Field number 0, a truncated varint and truncated lengths
+            "0801000a",
+            "08",
+            "1203aabb",
+            "1901",
+            // A group
+            "0b0c",
+        ] {
+            assert!(matches!(Message::decode(&hex::decode(encoding).unwrap()), Err(ECDSAError::InvalidProtobuf)), "{encoding}");
+        }
+        // A huge declared length is rejected before slicing
+        assert!(Message::decode(&hex::decode("12ffffffffffffffff7f").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,627 @@
+//! Partially Signed Bitcoin Transactions (BIP-174): checking the partial signatures a PSBT
+//! carries against the sighash of the input they sign.
+//!
+//! Only ECDSA partial signatures (`PSBT_IN_PARTIAL_SIG`) are covered, over legacy and segwit
+//! v0 inputs (P2PKH, P2SH, P2WPKH, P2WSH and their P2SH-wrapped forms). Version 2 PSBTs
+//! (BIP-370) and Taproot inputs are not supported.
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Array, Object, Reflect};
+use sha2::{Digest, Sha256};
+
+use crate::bitcoin::{compact_size, hash160};
+use crate::encoding::from_base64;
+use crate::keys::parse_public_key;
+use crate::signature::parse_der;
+use crate::{verify_digest, ECDSAError};
+
+const MAGIC: &[u8] = b"psbt\xff";
+
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_WITNESS_SCRIPT: u8 = 0x05;
+
+const SIGHASH_NONE: u32 = 0x02;
+const SIGHASH_SINGLE: u32 = 0x03;
+const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+fn double_sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// A raw `(key, value)` entry of a PSBT map.
+type KeyValue<'a> = (&'a [u8], &'a [u8]);
+
+/// A cursor over serialized PSBT and transaction data.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ECDSAError> {
+        let end = self.pos.checked_add(len).ok_or(ECDSAError::InvalidPsbt)?;
+        let slice = self.bytes.get(self.pos..end).ok_or(ECDSAError::InvalidPsbt)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, ECDSAError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, ECDSAError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, ECDSAError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Reads a CompactSize integer, rejecting non-minimal encodings.
+    fn compact_size(&mut self) -> Result<usize, ECDSAError> {
+        let (value, min) = match self.byte()? {
+            0xfd => (u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as u64, 0xfd),
+            0xfe => (self.u32()? as u64, 0x10000),
+            0xff => (self.u64()?, 0x1_0000_0000),
+            byte => (byte as u64, 0),
+        };
+        if value < min {
+            return Err(ECDSAError::InvalidPsbt);
+        }
+        usize::try_from(value).map_err(|_| ECDSAError::InvalidPsbt)
+    }
+
+    fn var_bytes(&mut self) -> Result<&'a [u8], ECDSAError> {
+        let len = self.compact_size()?;
+        self.take(len)
+    }
+
+    /// Reads one key-value map up to its 0x00 separator, as `(key, value)` pairs.
+    fn map(&mut self) -> Result<Vec<KeyValue<'a>>, ECDSAError> {
+        let mut pairs: Vec<KeyValue> = Vec::new();
+        loop {
+            let key = self.var_bytes()?;
+            if key.is_empty() {
+                return Ok(pairs);
+            }
+            if pairs.iter().any(|(existing, _)| *existing == key) {
+                return Err(ECDSAError::InvalidPsbt);
+            }
+            pairs.push((key, self.var_bytes()?));
+        }
+    }
+}
+
+struct TxIn {
+    prevout: [u8; 36],
+    script_sig: Vec<u8>,
+    sequence: u32,
+}
+
+#[derive(Clone)]
+struct TxOut {
+    value: u64,
+    script_pubkey: Vec<u8>,
+}
+
+impl TxOut {
+    fn parse(reader: &mut Reader) -> Result<TxOut, ECDSAError> {
+        let value = reader.u64()?;
+        let script_pubkey = reader.var_bytes()?.to_vec();
+        Ok(TxOut { value, script_pubkey })
+    }
+
+    fn serialize(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.value.to_le_bytes());
+        write_var_bytes(out, &self.script_pubkey);
+    }
+}
+
+struct Transaction {
+    version: u32,
+    inputs: Vec<TxIn>,
+    outputs: Vec<TxOut>,
+    lock_time: u32,
+}
+
+fn write_var_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend(compact_size(bytes.len() as u64));
+    out.extend_from_slice(bytes);
+}
+
+impl Transaction {
+    /// Parses a transaction in either the legacy or the segwit (BIP-144) serialization.
+    fn parse(bytes: &[u8]) -> Result<Transaction, ECDSAError> {
+        let mut reader = Reader::new(bytes);
+        let version = reader.u32()?;
+        let mut input_count = reader.compact_size()?;
+        let segwit = input_count == 0;
+        if segwit {
+            if reader.byte()? != 0x01 {
+                return Err(ECDSAError::InvalidPsbt);
+            }
+            input_count = reader.compact_size()?;
+        }
+
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            inputs.push(TxIn {
+                prevout: reader.take(36)?.try_into().unwrap(),
+                script_sig: reader.var_bytes()?.to_vec(),
+                sequence: reader.u32()?,
+            });
+        }
+        let output_count = reader.compact_size()?;
+        let outputs = (0..output_count)
+            .map(|_| TxOut::parse(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        if segwit {
+            for _ in 0..input_count {
+                for _ in 0..reader.compact_size()? {
+                    reader.var_bytes()?;
+                }
+            }
+        }
+        let lock_time = reader.u32()?;
+        if !reader.is_empty() {
+            return Err(ECDSAError::InvalidPsbt);
+        }
+        Ok(Transaction { version, inputs, outputs, lock_time })
+    }
+
+    /// Serializes without witness data, as hashed for the txid and legacy sighashes.
+    fn serialize(&self) -> Vec<u8> {
+        let mut out = self.version.to_le_bytes().to_vec();
+        out.extend(compact_size(self.inputs.len() as u64));
+        for input in &self.inputs {
+            out.extend_from_slice(&input.prevout);
+            write_var_bytes(&mut out, &input.script_sig);
+            out.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+        out.extend(compact_size(self.outputs.len() as u64));
+        for output in &self.outputs {
+            output.serialize(&mut out);
+        }
+        out.extend_from_slice(&self.lock_time.to_le_bytes());
+        out
+    }
+
+    fn txid(&self) -> [u8; 32] {
+        double_sha256(&self.serialize())
+    }
+
+    /// The original (pre-segwit) signature hash algorithm.
+    fn legacy_sighash(&self, index: usize, script_code: &[u8], sighash_type: u32) -> [u8; 32] {
+        let base_type = sighash_type & 0x1f;
+        if base_type == SIGHASH_SINGLE && index >= self.outputs.len() {
+            // Bitcoin Core signs the constant 1 when there is no matching output
+            let mut one = [0u8; 32];
+            one[0] = 1;
+            return one;
+        }
+
+        let mut inputs: Vec<TxIn> = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| TxIn {
+                prevout: input.prevout,
+                script_sig: if i == index { script_code.to_vec() } else { Vec::new() },
+                sequence: if i != index && (base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE) {
+                    0
+                } else {
+                    input.sequence
+                },
+            })
+            .collect();
+        if sighash_type & SIGHASH_ANYONECANPAY != 0 {
+            inputs = vec![inputs.swap_remove(index)];
+        }
+
+        let outputs = match base_type {
+            SIGHASH_NONE => Vec::new(),
+            SIGHASH_SINGLE => {
+                let blank = TxOut { value: u64::MAX, script_pubkey: Vec::new() };
+                let mut outputs = vec![blank; index];
+                outputs.push(self.outputs[index].clone());
+                outputs
+            }
+            _ => self.outputs.clone(),
+        };
+
+        let tx = Transaction { version: self.version, inputs, outputs, lock_time: self.lock_time };
+        let mut preimage = tx.serialize();
+        preimage.extend_from_slice(&sighash_type.to_le_bytes());
+        double_sha256(&preimage)
+    }
+
+    /// The BIP-143 signature hash used by segwit v0 inputs.
+    fn segwit_v0_sighash(&self, index: usize, script_code: &[u8], amount: u64, sighash_type: u32) -> [u8; 32] {
+        let base_type = sighash_type & 0x1f;
+        let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+
+        let hash_prevouts = if anyone_can_pay {
+            [0u8; 32]
+        } else {
+            double_sha256(&self.inputs.iter().flat_map(|input| input.prevout).collect::<Vec<_>>())
+        };
+        let hash_sequence = if anyone_can_pay || base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE {
+            [0u8; 32]
+        } else {
+            double_sha256(&self.inputs.iter().flat_map(|input| input.sequence.to_le_bytes()).collect::<Vec<_>>())
+        };
+        let hash_outputs = if base_type != SIGHASH_NONE && base_type != SIGHASH_SINGLE {
+            let mut outputs = Vec::new();
+            self.outputs.iter().for_each(|output| output.serialize(&mut outputs));
+            double_sha256(&outputs)
+        } else if base_type == SIGHASH_SINGLE && index < self.outputs.len() {
+            let mut output = Vec::new();
+            self.outputs[index].serialize(&mut output);
+            double_sha256(&output)
+        } else {
+            [0u8; 32]
+        };
+
+        let input = &self.inputs[index];
+        let mut preimage = self.version.to_le_bytes().to_vec();
+        preimage.extend_from_slice(&hash_prevouts);
+        preimage.extend_from_slice(&hash_sequence);
+        preimage.extend_from_slice(&input.prevout);
+        write_var_bytes(&mut preimage, script_code);
+        preimage.extend_from_slice(&amount.to_le_bytes());
+        preimage.extend_from_slice(&input.sequence.to_le_bytes());
+        preimage.extend_from_slice(&hash_outputs);
+        preimage.extend_from_slice(&self.lock_time.to_le_bytes());
+        preimage.extend_from_slice(&sighash_type.to_le_bytes());
+        double_sha256(&preimage)
+    }
+}
+
+/// The fields of a PSBT input map needed to check its partial signatures.
+#[derive(Default)]
+struct PsbtInput {
+    non_witness_utxo: Option<Transaction>,
+    witness_utxo: Option<TxOut>,
+    partial_sigs: Vec<(Vec<u8>, Vec<u8>)>,
+    sighash_type: Option<u32>,
+    redeem_script: Option<Vec<u8>>,
+    witness_script: Option<Vec<u8>>,
+}
+
+impl PsbtInput {
+    fn parse(pairs: &[KeyValue]) -> Result<PsbtInput, ECDSAError> {
+        let mut input = PsbtInput::default();
+        for &(key, value) in pairs {
+            let key_data = &key[1..];
+            match key[0] {
+                PSBT_IN_NON_WITNESS_UTXO if key_data.is_empty() => {
+                    input.non_witness_utxo = Some(Transaction::parse(value)?);
+                }
+                PSBT_IN_WITNESS_UTXO if key_data.is_empty() => {
+                    let mut reader = Reader::new(value);
+                    input.witness_utxo = Some(TxOut::parse(&mut reader)?);
+                    if !reader.is_empty() {
+                        return Err(ECDSAError::InvalidPsbt);
+                    }
+                }
+                PSBT_IN_PARTIAL_SIG => input.partial_sigs.push((key_data.to_vec(), value.to_vec())),
+                PSBT_IN_SIGHASH_TYPE if key_data.is_empty() => {
+                    let bytes = value.try_into().map_err(|_| ECDSAError::InvalidPsbt)?;
+                    input.sighash_type = Some(u32::from_le_bytes(bytes));
+                }
+                PSBT_IN_REDEEM_SCRIPT if key_data.is_empty() => input.redeem_script = Some(value.to_vec()),
+                PSBT_IN_WITNESS_SCRIPT if key_data.is_empty() => input.witness_script = Some(value.to_vec()),
+                PSBT_IN_NON_WITNESS_UTXO..=PSBT_IN_WITNESS_SCRIPT => return Err(ECDSAError::InvalidPsbt),
+                _ => {}
+            }
+        }
+        Ok(input)
+    }
+}
+
+/// A parsed PSBT: the unsigned transaction and one input map per transaction input.
+struct Psbt {
+    tx: Transaction,
+    inputs: Vec<PsbtInput>,
+}
+
+impl Psbt {
+    fn parse(bytes: &[u8]) -> Result<Psbt, ECDSAError> {
+        let mut reader = Reader::new(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ECDSAError::InvalidPsbt);
+        }
+
+        let globals = reader.map()?;
+        let unsigned_tx = globals
+            .iter()
+            .find(|(key, _)| *key == [PSBT_GLOBAL_UNSIGNED_TX])
+            .ok_or(ECDSAError::InvalidPsbt)?
+            .1;
+        let tx = Transaction::parse(unsigned_tx)?;
+        if tx.inputs.iter().any(|input| !input.script_sig.is_empty()) {
+            return Err(ECDSAError::InvalidPsbt);
+        }
+
+        let inputs = (0..tx.inputs.len())
+            .map(|_| PsbtInput::parse(&reader.map()?))
+            .collect::<Result<Vec<_>, _>>()?;
+        for _ in 0..tx.outputs.len() {
+            reader.map()?;
+        }
+        if !reader.is_empty() {
+            return Err(ECDSAError::InvalidPsbt);
+        }
+        Ok(Psbt { tx, inputs })
+    }
+
+    /// The output spent by input `index`, taken from its witness or non-witness UTXO.
+    fn spent_output(&self, index: usize) -> Result<TxOut, ECDSAError> {
+        let input = &self.inputs[index];
+        if let Some(prev_tx) = &input.non_witness_utxo {
+            let prevout = &self.tx.inputs[index].prevout;
+            if prev_tx.txid()[..] != prevout[..32] {
+                return Err(ECDSAError::InvalidPsbt);
+            }
+            let vout = u32::from_le_bytes(prevout[32..].try_into().unwrap()) as usize;
+            return prev_tx.outputs.get(vout).cloned().ok_or(ECDSAError::InvalidPsbt);
+        }
+        input.witness_utxo.clone().ok_or(ECDSAError::MissingPsbtInputData)
+    }
+
+    /// Computes the digest a signature with `sighash_type` on input `index` must sign.
+    fn sighash(&self, index: usize, sighash_type: u32) -> Result<[u8; 32], ECDSAError> {
+        let input = &self.inputs[index];
+        let spent = self.spent_output(index)?;
+
+        let mut script = spent.script_pubkey.clone();
+        if is_p2sh(&script) {
+            let redeem_script = input.redeem_script.as_ref().ok_or(ECDSAError::MissingPsbtInputData)?;
+            if hash160(redeem_script)[..] != script[2..22] {
+                return Err(ECDSAError::InvalidPsbt);
+            }
+            script = redeem_script.clone();
+        }
+
+        match script.as_slice() {
+            [0x00, 0x14, key_hash @ ..] if key_hash.len() == 20 => {
+                let script_code = [&[0x76, 0xa9, 0x14], key_hash, &[0x88, 0xac]].concat();
+                Ok(self.tx.segwit_v0_sighash(index, &script_code, spent.value, sighash_type))
+            }
+            [0x00, 0x20, script_hash @ ..] if script_hash.len() == 32 => {
+                let witness_script = input.witness_script.as_ref().ok_or(ECDSAError::MissingPsbtInputData)?;
+                if Sha256::digest(witness_script)[..] != *script_hash {
+                    return Err(ECDSAError::InvalidPsbt);
+                }
+                Ok(self.tx.segwit_v0_sighash(index, witness_script, spent.value, sighash_type))
+            }
+            [0x51..=0x60, ..] if (4..=42).contains(&script.len()) && script[1] as usize == script.len() - 2 => {
+                // Taproot and future witness versions sign with Schnorr, not partial sigs
+                Err(ECDSAError::InvalidPsbt)
+            }
+            _ => {
+                if input.non_witness_utxo.is_none() {
+                    return Err(ECDSAError::MissingPsbtInputData);
+                }
+                Ok(self.tx.legacy_sighash(index, &script, sighash_type))
+            }
+        }
+    }
+
+    /// Checks one partial signature of input `index`.
+    fn check_partial_sig(&self, index: usize, public_key: &[u8], signature: &[u8]) -> PartialSigCheck {
+        let (&sighash_byte, der) = match signature.split_last() {
+            Some(split) => split,
+            None => {
+                return PartialSigCheck {
+                    public_key: public_key.to_vec(),
+                    sighash_type: 0,
+                    sighash: None,
+                    result: Err(ECDSAError::InvalidSignatureLength),
+                }
+            }
+        };
+        let sighash_type = sighash_byte as u32;
+        let sighash = match self.inputs[index].sighash_type {
+            Some(declared) if declared != sighash_type => Err(ECDSAError::InvalidSighashType),
+            _ => self.sighash(index, sighash_type),
+        };
+
+        let (sighash, result) = match sighash {
+            Ok(sighash) => (Some(sighash), verify_partial_sig(&sighash, public_key, der)),
+            Err(err) => (None, Err(err)),
+        };
+        PartialSigCheck {
+            public_key: public_key.to_vec(),
+            sighash_type,
+            sighash,
+            result,
+        }
+    }
+
+    /// Checks every partial signature, grouped by input.
+    fn check(&self) -> Vec<Vec<PartialSigCheck>> {
+        self.inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                input
+                    .partial_sigs
+                    .iter()
+                    .map(|(public_key, signature)| self.check_partial_sig(index, public_key, signature))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn verify_partial_sig(sighash: &[u8; 32], public_key: &[u8], der: &[u8]) -> Result<bool, ECDSAError> {
+    let pubkey = parse_public_key(public_key)?;
+    let sig = parse_der(der)?;
+    Ok(verify_digest(&pubkey, sighash, &sig))
+}
+
+fn is_p2sh(script: &[u8]) -> bool {
+    script.len() == 23 && script[0] == 0xa9 && script[1] == 0x14 && script[22] == 0x87
+}
+
+/// The outcome of checking one partial signature.
+struct PartialSigCheck {
+    public_key: Vec<u8>,
+    sighash_type: u32,
+    sighash: Option<[u8; 32]>,
+    result: Result<bool, ECDSAError>,
+}
+
+impl PartialSigCheck {
+    /// Converts the check into a `{ publicKey, sighashType, sighash, valid, error }` JS object.
+    fn to_js(&self) -> JsValue {
+        let object = Object::new();
+        let (valid, error) = match &self.result {
+            Ok(valid) => (*valid, JsValue::UNDEFINED),
+            Err(err) => (false, JsValue::from_str(&err.to_string())),
+        };
+        let sighash = self.sighash.map_or(JsValue::UNDEFINED, |sighash| JsValue::from_str(&hex::encode(sighash)));
+        // Setting properties on a fresh plain object cannot fail
+        let _ = Reflect::set(&object, &JsValue::from_str("publicKey"), &JsValue::from_str(&hex::encode(&self.public_key)));
+        let _ = Reflect::set(&object, &JsValue::from_str("sighashType"), &JsValue::from(self.sighash_type));
+        let _ = Reflect::set(&object, &JsValue::from_str("sighash"), &sighash);
+        let _ = Reflect::set(&object, &JsValue::from_str("valid"), &JsValue::from_bool(valid));
+        let _ = Reflect::set(&object, &JsValue::from_str("error"), &error);
+        object.into()
+    }
+}
+
+/// Decodes a PSBT given as Base64 (the usual interchange format) or hex.
+fn decode_psbt(psbt: &str) -> Result<Vec<u8>, ECDSAError> {
+    let psbt = psbt.trim();
+    match hex::decode(psbt) {
+        Ok(bytes) if bytes.starts_with(MAGIC) => Ok(bytes),
+        _ => from_base64(psbt).ok_or(ECDSAError::InvalidPsbt),
+    }
+}
+
+/// Verifies the partial signatures in a PSBT against the sighash of the input they sign.
+///
+/// # Arguments:
+/// * `psbt` - A version 0 PSBT, Base64 or hex encoded. Inputs need their witness or
+///   non-witness UTXO, plus the redeem and witness scripts for P2SH and P2WSH outputs.
+///
+/// # Returns:
+/// * An array with one entry per input, in order, each an array of
+///   `{ publicKey, sighashType, sighash?, valid, error? }` objects for its partial signatures.
+///
+/// # Errors:
+/// * Returns an error if the PSBT cannot be decoded or parsed. Problems confined to one
+///   input, such as a missing UTXO, are reported in that input's entries instead.
+#[wasm_bindgen]
+pub fn verify_psbt_signatures(psbt: &str) -> Result<JsValue, ECDSAError> {
+    let psbt = Psbt::parse(&decode_psbt(psbt)?)?;
+    let report: Array = psbt
+        .check()
+        .iter()
+        .map(|checks| JsValue::from(checks.iter().map(PartialSigCheck::to_js).collect::<Array>()))
+        .collect();
+    Ok(report.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-143 native P2WPKH example: input 1 spends 6 BTC from a P2WPKH output
+    const UNSIGNED_TX: &str = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000";
+    const PUBLIC_KEY: &str = "025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357";
+    const SIGNATURE: &str = "304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee01";
+    const SIGHASH: &str = "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670";
+
+    fn write_pair(out: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+        write_var_bytes(out, key);
+        write_var_bytes(out, value);
+    }
+
+    /// Builds a PSBT for `UNSIGNED_TX` whose second input carries `input` key-value pairs.
+    fn build_psbt(input: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        let mut psbt = MAGIC.to_vec();
+        write_pair(&mut psbt, &[PSBT_GLOBAL_UNSIGNED_TX], &hex::decode(UNSIGNED_TX).unwrap());
+        psbt.push(0x00);
+        psbt.push(0x00);
+        for (key, value) in input {
+            write_pair(&mut psbt, key, value);
+        }
+        psbt.extend_from_slice(&[0x00, 0x00, 0x00]);
+        psbt
+    }
+
+    fn p2wpkh_input(signature: &str) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let witness_utxo = hex::decode("0046c323000000001600141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap();
+        vec![
+            (vec![PSBT_IN_WITNESS_UTXO], witness_utxo),
+            ([&[PSBT_IN_PARTIAL_SIG][..], &hex::decode(PUBLIC_KEY).unwrap()].concat(), hex::decode(signature).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn test_bip143_p2wpkh_partial_sig() {
+        let psbt = Psbt::parse(&build_psbt(&p2wpkh_input(SIGNATURE))).unwrap();
+        let report = psbt.check();
+        assert!(report[0].is_empty());
+        assert_eq!(report[1].len(), 1);
+        assert_eq!(hex::encode(report[1][0].sighash.unwrap()), SIGHASH);
+        assert!(matches!(report[1][0].result, Ok(true)));
+
+        // Same signature under a different sighash type no longer verifies
+        let wrong_type = format!("{}03", &SIGNATURE[..SIGNATURE.len() - 2]);
+        let report = Psbt::parse(&build_psbt(&p2wpkh_input(&wrong_type))).unwrap().check();
+        assert!(matches!(report[1][0].result, Ok(false)));
+    }
+
+    #[test]
+    fn test_declared_sighash_type_mismatch() {
+        let mut input = p2wpkh_input(SIGNATURE);
+        input.push((vec![PSBT_IN_SIGHASH_TYPE], 2u32.to_le_bytes().to_vec()));
+        let report = Psbt::parse(&build_psbt(&input)).unwrap().check();
+        assert!(matches!(report[1][0].result, Err(ECDSAError::InvalidSighashType)));
+    }
+
+    #[test]
+    fn test_missing_utxo_is_reported_per_input() {
+        let input = p2wpkh_input(SIGNATURE)[1..].to_vec();
+        let report = Psbt::parse(&build_psbt(&input)).unwrap().check();
+        assert!(report[1][0].sighash.is_none());
+        assert!(matches!(report[1][0].result, Err(ECDSAError::MissingPsbtInputData)));
+    }
+
+    #[test]
+    fn test_decode_base64_and_hex() {
+        let bytes = build_psbt(&p2wpkh_input(SIGNATURE));
+        assert_eq!(decode_psbt(&hex::encode(&bytes)).unwrap(), bytes);
+        assert_eq!(decode_psbt(&crate::encoding::to_base64(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let bytes = build_psbt(&p2wpkh_input(SIGNATURE));
+        assert!(Psbt::parse(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Psbt::parse(&[&bytes[..], &[0x00]].concat()).is_err());
+        assert!(Psbt::parse(&bytes[1..]).is_err());
+        // Duplicate key in the input map
+        let mut input = p2wpkh_input(SIGNATURE);
+        input.push(input[1].clone());
+        assert!(Psbt::parse(&build_psbt(&input)).is_err());
+        assert!(decode_psbt("not a psbt").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! The crate's single source of randomness.
+//!
+//! Everything that needs random bytes (key generation, nonces, salts, IVs) goes through
+//! `fill_random`. By default it reads the platform CSPRNG through `getrandom`, which is
+//! `crypto.getRandomValues` in browsers and Node. An application can install its own entropy
+//! source, e.g. a hardware RNG or a deterministic source in tests, with `set_entropy_source`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Function, Uint8Array};
+use k256::{FieldBytes, NonZeroScalar};
+use zeroize::Zeroizing;
+
+use crate::ECDSAError;
+
+/// The most `crypto.getRandomValues` fills in one call; injected sources get the same limit.
+const MAX_CHUNK: usize = 65536;
+
+type EntropySource = Rc<dyn Fn(&mut [u8]) -> Result<(), ECDSAError>>;
+
+thread_local! {
+    static SOURCE: RefCell<Option<EntropySource>> = const { RefCell::new(None) };
+}
+
+/// Replaces the entropy source for the current thread; `None` restores the platform CSPRNG.
+pub(crate) fn install_entropy_source(source: Option<EntropySource>) {
+    SOURCE.with(|cell| *cell.borrow_mut() = source);
+}
+
+/// Fills `buffer` with random bytes from the installed source, or the platform CSPRNG.
+pub(crate) fn fill_random(buffer: &mut [u8]) -> Result<(), ECDSAError> {
+    // Cloned out so that a source may itself replace the source without a double borrow
+    let source = SOURCE.with(|cell| cell.borrow().clone());
+    match source {
+        Some(source) => buffer.chunks_mut(MAX_CHUNK).try_for_each(|chunk| source(chunk)),
+        None => getrandom::getrandom(buffer).map_err(|_| ECDSAError::RandomnessUnavailable),
+    }
+}
+
+/// A uniformly random nonzero secp256k1 scalar, e.g. for nonces and ephemeral keys.
+pub(crate) fn random_scalar() -> Result<NonZeroScalar, ECDSAError> {
+    let mut bytes = Zeroizing::new([0u8; 32]);
+    // Out-of-range candidates have probability about 2^-128
+    loop {
+        fill_random(&mut bytes[..])?;
+        if let Some(scalar) = Option::from(NonZeroScalar::from_repr(*FieldBytes::from_slice(&bytes[..]))) {
+            return Ok(scalar);
+        }
+    }
+}
+
+/// Returns cryptographically secure random bytes.
+///
+/// # Arguments:
+/// * `length` - The number of bytes to return.
+///
+/// # Returns:
+/// * The random bytes (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `RandomnessUnavailable` if the platform has no CSPRNG or the injected source fails.
+#[wasm_bindgen]
+pub fn random_bytes(length: usize) -> Result<Vec<u8>, ECDSAError> {
+    let mut bytes = vec![0u8; length];
+    fill_random(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Routes all of the library's randomness through a JavaScript function.
+///
+/// The function is called like `crypto.getRandomValues`: with a `Uint8Array` of at most
+/// 65536 bytes, which it must fill in place. `crypto.getRandomValues.bind(crypto)` is a valid
+/// source. The source applies to the calling thread (or worker) only.
+///
+/// # Arguments:
+/// * `source` - The entropy function, or `undefined` to restore the platform CSPRNG.
+#[wasm_bindgen]
+pub fn set_entropy_source(source: Option<Function>) {
+    install_entropy_source(source.map(|source| -> EntropySource {
+        Rc::new(move |buffer: &mut [u8]| {
+            let array = Uint8Array::new_with_length(buffer.len() as u32);
+            source.call1(&JsValue::NULL, &array).map_err(|_| ECDSAError::RandomnessUnavailable)?;
+            array.copy_to(buffer);
+            Ok(())
+        })
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeyPair;
+
+    fn counting_source() -> EntropySource {
+        let counter = std::cell::Cell::new(0u8);
+        Rc::new(move |buffer: &mut [u8]| {
+            for byte in buffer {
+                *byte = counter.get();
+                counter.set(counter.get().wrapping_add(1));
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_platform_source() {
+        let a = random_bytes(32).unwrap();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, random_bytes(32).unwrap());
+        assert!(random_bytes(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_injected_source() {
+        install_entropy_source(Some(counting_source()));
+        assert_eq!(random_bytes(4).unwrap(), [0, 1, 2, 3]);
+        // Large requests are split into getRandomValues-sized chunks
+        assert_eq!(random_bytes(MAX_CHUNK + 2).unwrap()[MAX_CHUNK..], [4, 5]);
+        install_entropy_source(None);
+        assert_ne!(random_bytes(4).unwrap(), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_key_generation_uses_source() {
+        install_entropy_source(Some(counting_source()));
+        let first = KeyPair::generate().unwrap().private_key();
+        install_entropy_source(Some(counting_source()));
+        assert_eq!(KeyPair::generate().unwrap().private_key(), first);
+        install_entropy_source(None);
+        assert_ne!(KeyPair::generate().unwrap().private_key(), first);
+    }
+
+    #[test]
+    fn test_failing_source() {
+        install_entropy_source(Some(Rc::new(|_: &mut [u8]| Err(ECDSAError::RandomnessUnavailable))));
+        assert!(matches!(random_bytes(16), Err(ECDSAError::RandomnessUnavailable)));
+        assert!(matches!(random_scalar(), Err(ECDSAError::RandomnessUnavailable)));
+        assert!(matches!(KeyPair::generate(), Err(ECDSAError::RandomnessUnavailable)));
+        install_entropy_source(None);
+    }
+}
@@ -0,0 +1,83 @@
+//! Deterministic nonce generation for secp256k1 as specified by RFC 6979,
+//! instantiated with HMAC-SHA256.
+
+use k256::elliptic_curve::ff::PrimeField;
+use k256::{FieldBytes, NonZeroScalar, Scalar};
+
+use crate::kdf::hmac_sha256;
+
+/// Derives the nonce `k` from the secret scalar `x` and the reduced message digest `z`.
+///
+/// `extra` is the optional additional data `k'` of RFC 6979 section 3.6; pass an empty
+/// slice for plain deterministic signing.
+pub(crate) fn generate_k(x: &Scalar, z: &Scalar, extra: &[u8]) -> NonZeroScalar {
+    let x = x.to_repr();
+    let z = z.to_repr();
+
+    let mut v = [1u8; 32];
+    let mut k = [0u8; 32];
+
+    k = hmac_sha256(&k, &[&v, &[0x00], &x, &z, extra]);
+    v = hmac_sha256(&k, &[&v]);
+    k = hmac_sha256(&k, &[&v, &[0x01], &x, &z, extra]);
+    v = hmac_sha256(&k, &[&v]);
+
+    loop {
+        v = hmac_sha256(&k, &[&v]);
+
+        let candidate = NonZeroScalar::from_repr(FieldBytes::clone_from_slice(&v));
+        if let Some(nonce) = Option::<NonZeroScalar>::from(candidate) {
+            return nonce;
+        }
+
+        k = hmac_sha256(&k, &[&v, &[0x00]]);
+        v = hmac_sha256(&k, &[&v]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digest_to_scalar;
+    use sha2::{Digest, Sha256};
+
+    /// Published secp256k1 / HMAC-SHA256 nonce vectors (RFC 6979 itself only covers NIST curves).
+    const VECTORS: [(&str, &str, &str); 4] = [
+        (
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "Satoshi Nakamoto",
+            "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "All those moments will be lost in time, like tears in rain. Time to die...",
+            "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+        ),
+        (
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+            "Satoshi Nakamoto",
+            "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+        ),
+        (
+            "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+            "Alan Turing",
+            "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+        ),
+    ];
+
+    #[test]
+    fn test_nonce_vectors() {
+        for (secret, message, expected) in VECTORS {
+            let x = Scalar::from_repr(FieldBytes::clone_from_slice(&hex::decode(secret).unwrap())).unwrap();
+            let z = digest_to_scalar(&Sha256::digest(message.as_bytes()));
+            assert_eq!(hex::encode(generate_k(&x, &z, &[]).to_repr()), expected, "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_extra_entropy_changes_nonce() {
+        let x = Scalar::from(1u64);
+        let z = digest_to_scalar(&Sha256::digest(b"Satoshi Nakamoto"));
+        assert_ne!(generate_k(&x, &z, &[]).to_repr(), generate_k(&x, &z, &[0x01; 32]).to_repr());
+    }
+}
@@ -0,0 +1,201 @@
+//! Recursive Length Prefix encoding, Ethereum's serialization for transactions.
+//!
+//! The decoder only accepts canonical encodings: single bytes below 0x80 must be
+//! encoded as themselves and lengths must use the shortest form.
+
+use crate::ECDSAError;
+
+/// A decoded RLP item, borrowing its byte strings from the input.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    List(Vec<Rlp<'a>>),
+}
+
+impl<'a> Rlp<'a> {
+    pub(crate) fn as_bytes(&self) -> Result<&'a [u8], ECDSAError> {
+        match self {
+            Rlp::Bytes(bytes) => Ok(bytes),
+            Rlp::List(_) => Err(ECDSAError::InvalidRlp),
+        }
+    }
+
+    pub(crate) fn as_list(&self) -> Result<&[Rlp<'a>], ECDSAError> {
+        match self {
+            Rlp::List(items) => Ok(items),
+            Rlp::Bytes(_) => Err(ECDSAError::InvalidRlp),
+        }
+    }
+
+    /// Reads a scalar: a big-endian integer without leading zero bytes.
+    pub(crate) fn as_uint(&self) -> Result<&'a [u8], ECDSAError> {
+        let bytes = self.as_bytes()?;
+        if bytes.first() == Some(&0) {
+            return Err(ECDSAError::InvalidRlp);
+        }
+        Ok(bytes)
+    }
+
+    /// Reads a scalar that fits in a `u64`.
+    pub(crate) fn as_u64(&self) -> Result<u64, ECDSAError> {
+        let bytes = self.as_uint()?;
+        if bytes.len() > 8 {
+            return Err(ECDSAError::InvalidRlp);
+        }
+        Ok(bytes.iter().fold(0, |value, &b| (value << 8) | b as u64))
+    }
+
+    /// Re-encodes the item.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        match self {
+            Rlp::Bytes(bytes) => encode_bytes(bytes),
+            Rlp::List(items) => encode_list(&items.iter().map(Rlp::encode).collect::<Vec<_>>()),
+        }
+    }
+}
+
+/// Reads a length prefix's payload bounds, returning `(payload_start, payload_length)`.
+fn header(input: &[u8], short_base: u8, long_base: u8) -> Result<(usize, usize), ECDSAError> {
+    let prefix = input[0];
+    if prefix < long_base {
+        return Ok((1, (prefix - short_base) as usize));
+    }
+    let length_of_length = (prefix - long_base) as usize + 1;
+    let length_bytes = input.get(1..1 + length_of_length).ok_or(ECDSAError::InvalidRlp)?;
+    if length_bytes[0] == 0 || length_of_length > std::mem::size_of::<usize>() {
+        return Err(ECDSAError::InvalidRlp);
+    }
+    let length = length_bytes.iter().fold(0usize, |length, &b| (length << 8) | b as usize);
+    if length < 56 {
+        return Err(ECDSAError::InvalidRlp);
+    }
+    Ok((1 + length_of_length, length))
+}
+
+/// Nesting limit, to keep hostile input from exhausting the wasm stack.
+const MAX_DEPTH: usize = 128;
+
+/// Decodes one item from the front of `input`, returning it and the remaining bytes.
+fn decode_item(input: &[u8], depth: usize) -> Result<(Rlp<'_>, &[u8]), ECDSAError> {
+    if depth > MAX_DEPTH {
+        return Err(ECDSAError::InvalidRlp);
+    }
+    let prefix = *input.first().ok_or(ECDSAError::InvalidRlp)?;
+    if prefix < 0x80 {
+        return Ok((Rlp::Bytes(&input[..1]), &input[1..]));
+    }
+
+    let is_list = prefix >= 0xc0;
+    let (start, length) = if is_list { header(input, 0xc0, 0xf8)? } else { header(input, 0x80, 0xb8)? };
+    let end = start.checked_add(length).ok_or(ECDSAError::InvalidRlp)?;
+    let payload = input.get(start..end).ok_or(ECDSAError::InvalidRlp)?;
+    let rest = &input[end..];
+
+    if !is_list {
+        if length == 1 && payload[0] < 0x80 {
+            return Err(ECDSAError::InvalidRlp);
+        }
+        return Ok((Rlp::Bytes(payload), rest));
+    }
+    let mut items = Vec::new();
+    let mut remaining = payload;
+    while !remaining.is_empty() {
+        let (item, next) = decode_item(remaining, depth + 1)?;
+        items.push(item);
+        remaining = next;
+    }
+    Ok((Rlp::List(items), rest))
+}
+
+/// Decodes an input consisting of exactly one RLP item.
+pub(crate) fn decode(input: &[u8]) -> Result<Rlp<'_>, ECDSAError> {
+    let (item, rest) = decode_item(input, 0)?;
+    if !rest.is_empty() {
+        return Err(ECDSAError::InvalidRlp);
+    }
+    Ok(item)
+}
+
+fn length_prefix(length: usize, short_base: u8) -> Vec<u8> {
+    if length < 56 {
+        return vec![short_base + length as u8];
+    }
+    let length_bytes = length.to_be_bytes();
+    let significant = &length_bytes[length_bytes.iter().position(|&b| b != 0).unwrap_or(0)..];
+    let mut prefix = vec![short_base + 55 + significant.len() as u8];
+    prefix.extend_from_slice(significant);
+    prefix
+}
+
+/// Encodes a byte string.
+pub(crate) fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if let [byte] = bytes {
+        if *byte < 0x80 {
+            return vec![*byte];
+        }
+    }
+    let mut out = length_prefix(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// Encodes a scalar as a minimal big-endian byte string (zero is the empty string).
+pub(crate) fn encode_u64(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    encode_bytes(&bytes[bytes.iter().position(|&b| b != 0).unwrap_or(8)..])
+}
+
+/// Encodes a list from its already-encoded items.
+pub(crate) fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = length_prefix(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        // Examples from the Ethereum wiki: "dog", ["cat", "dog"], 1024 and the set-theoretic three
+        for encoded in ["83646f67", "c88363617483646f67", "820400", "c7c0c1c0c3c0c1c0", "80", "c0", "0f"] {
+            let bytes = hex::decode(encoded).unwrap();
+            assert_eq!(hex::encode(decode(&bytes).unwrap().encode()), encoded);
+        }
+        let lorem = "Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        let encoded = encode_bytes(lorem.as_bytes());
+        assert_eq!(encoded[..2], [0xb8, 0x38]);
+        assert_eq!(decode(&encoded).unwrap(), Rlp::Bytes(lorem.as_bytes()));
+        assert_eq!(encode_u64(0), [0x80]);
+        assert_eq!(encode_u64(1024), [0x82, 0x04, 0x00]);
+    }
+
+    #[test]
+    fn test_rejects_non_canonical() {
+        for encoded in [
+            "8100",       // single byte below 0x80 with a prefix
+            "b800",       // long form for a short string
+            "83646f",     // truncated
+            "83646f6767", // trailing data
+            "c3836361",   // list payload shorter than its item
+        ] {
+            assert!(decode(&hex::decode(encoded).unwrap()).is_err(), "{encoded}");
+        }
+        assert!(decode(&[0x82, 0x00, 0x01]).unwrap().as_uint().is_err());
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| {
+            let mut encoded = vec![0xc0];
+            for _ in 0..depth {
+                encoded = encode_list(&[encoded]);
+            }
+            encoded
+        };
+        assert!(decode(&nested(MAX_DEPTH)).is_ok());
+        assert!(matches!(decode(&nested(MAX_DEPTH + 1)), Err(ECDSAError::InvalidRlp)));
+    }
+}
@@ -0,0 +1,171 @@
+//! BIP-340 Schnorr signatures over secp256k1, as used by Taproot and Nostr.
+//!
+//! Public keys are 32-byte x-only coordinates of the point with an even y; signatures
+//! are 64 bytes (R.x || s).
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::elliptic_curve::{AffineXCoordinate, DecompressPoint};
+use k256::{AffinePoint, CompressedPoint, FieldBytes, ProjectivePoint, Scalar, U256};
+use sha2::{Digest, Sha256};
+
+use crate::ECDSAError;
+
+/// Length of a BIP-340 signature.
+pub(crate) const SCHNORR_SIGNATURE_LENGTH: usize = 64;
+
+/// Computes the BIP-340 tagged hash `SHA256(SHA256(tag) || SHA256(tag) || parts...)`.
+pub(crate) fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Whether a point has an even y-coordinate (the identity counts as odd, so it is never accepted).
+pub(crate) fn has_even_y(point: &AffinePoint) -> bool {
+    point.to_bytes()[0] == 0x02
+}
+
+/// Returns the curve point with x-coordinate `x` and an even y, if it exists.
+pub(crate) fn lift_x(x: &[u8; 32]) -> Result<AffinePoint, ECDSAError> {
+    let point = AffinePoint::decompress(FieldBytes::from_slice(x), 0.into());
+    Option::<AffinePoint>::from(point).ok_or(ECDSAError::InvalidPublicKey)
+}
+
+/// Decodes a 33-byte compressed point, rejecting the point at infinity, which `from_bytes`
+/// would accept as zero bytes.
+pub(crate) fn decode_point(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.len() != 33 {
+        return None;
+    }
+    let point = Option::<AffinePoint>::from(AffinePoint::from_bytes(CompressedPoint::from_slice(bytes)))?;
+    (point != AffinePoint::IDENTITY).then(|| point.into())
+}
+
+/// Parses a 32-byte x-only public key.
+pub(crate) fn parse_x_only(bytes: &[u8]) -> Result<[u8; 32], ECDSAError> {
+    bytes.try_into().map_err(|_| ECDSAError::InvalidPublicKey)
+}
+
+/// Verifies a BIP-340 signature over `message` (of any length) against an x-only key.
+pub(crate) fn verify_schnorr(public_key: &[u8; 32], message: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+    if signature.len() != SCHNORR_SIGNATURE_LENGTH {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    let point = lift_x(public_key)?;
+    let (r, s) = signature.split_at(32);
+    let s = Option::<Scalar>::from(Scalar::from_repr(FieldBytes::clone_from_slice(s)))
+        .ok_or(ECDSAError::InvalidSignatureFormat)?;
+
+    let e = tagged_hash("BIP0340/challenge", &[r, public_key, message]);
+    let e = <Scalar as Reduce<U256>>::from_be_bytes_reduced(e.into());
+
+    // R = s*G - e*P must have an even y and the committed x-coordinate
+    let big_r = (ProjectivePoint::GENERATOR * s - ProjectivePoint::from(point) * e).to_affine();
+    Ok(has_even_y(&big_r) && big_r.x().as_slice() == r)
+}
+
+/// Struct for BIP-340 Schnorr signature verification.
+#[wasm_bindgen]
+pub struct SchnorrVerifier;
+
+impl Default for SchnorrVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl SchnorrVerifier {
+    /// Initializes a new instance of SchnorrVerifier.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SchnorrVerifier {
+        SchnorrVerifier
+    }
+
+    /// Verifies a BIP-340 Schnorr signature.
+    ///
+    /// # Arguments:
+    /// * `message` - The signed message bytes (usually a 32-byte hash), provided as a hex string.
+    ///   BIP-340 signs the message directly; it is not hashed again.
+    /// * `signature` - The 64-byte signature, provided as a hex string.
+    /// * `public_key` - The 32-byte x-only public key, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    ///
+    /// # Errors:
+    /// * Returns an error if the key is not on the curve or the signature is malformed.
+    pub fn verify(&self, message: &str, signature: &str, public_key: &str) -> Result<bool, ECDSAError> {
+        let message = hex::decode(message).map_err(|_| ECDSAError::InvalidDigestLength)?;
+        let signature = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let public_key = hex::decode(public_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+        self.verify_bytes(&message, &signature, &public_key)
+    }
+
+    /// Verifies a BIP-340 Schnorr signature given raw bytes (`Uint8Array` in JS).
+    pub fn verify_bytes(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, ECDSAError> {
+        verify_schnorr(&parse_x_only(public_key)?, message, signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors 0, 1 and 5 from the BIP-340 test-vectors.csv
+    const PUBLIC_KEY_0: &str = "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9";
+    const SIGNATURE_0: &str = "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+                               25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0";
+    const PUBLIC_KEY_1: &str = "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659";
+    const MESSAGE_1: &str = "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89";
+    const SIGNATURE_1: &str = "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+                               8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a";
+
+    #[test]
+    fn test_bip340_vectors() {
+        let verifier = SchnorrVerifier::new();
+        assert!(verifier.verify(&"00".repeat(32), SIGNATURE_0, PUBLIC_KEY_0).unwrap());
+        assert!(verifier.verify(MESSAGE_1, SIGNATURE_1, PUBLIC_KEY_1).unwrap());
+
+        // Public key not on the curve
+        let off_curve = "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34";
+        assert!(matches!(
+            verifier.verify(MESSAGE_1, SIGNATURE_1, off_curve),
+            Err(ECDSAError::InvalidPublicKey)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_tampered_signature() {
+        let verifier = SchnorrVerifier::new();
+        assert!(!verifier.verify(MESSAGE_1, SIGNATURE_1, PUBLIC_KEY_0).unwrap());
+        assert!(!verifier.verify(&"00".repeat(32), SIGNATURE_1, PUBLIC_KEY_1).unwrap());
+        assert!(verifier.verify(MESSAGE_1, &SIGNATURE_1[..126], PUBLIC_KEY_1).is_err());
+    }
+
+    #[test]
+    fn test_variable_length_message() {
+        let signature = "975f737369771bb8cc6e27d5448fe12a59a67db19d9378dc84775af680d197be\
+                         33ee4a466ab1bbe8b6302585b251434dc32f98f54e6560819999ac7121dbc6d4";
+        let public_key = "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517";
+        assert!(SchnorrVerifier::new().verify(&hex::encode("hello nostr"), signature, public_key).unwrap());
+    }
+
+    #[test]
+    fn test_tagged_hash() {
+        // The BIP-340 challenge tag prefix, SHA256("BIP0340/challenge")
+        assert_eq!(
+            hex::encode(Sha256::digest(b"BIP0340/challenge")),
+            "7bb52d7a9fef58323eb1bf7a407db382d2f3f2d81bb1224f49fe518f6d48d37c"
+        );
+        assert_eq!(tagged_hash("tag", &[b"ab", b"c"]), tagged_hash("tag", &[b"abc"]));
+    }
+}
@@ -0,0 +1,185 @@
+//! A secp256k1 private key held in memory that is wiped when it is no longer needed.
+//!
+//! JavaScript cannot free wasm memory deterministically, and a garbage-collected wrapper may
+//! linger long after its last use, so `SecretKey` can be wiped explicitly with `destroy()`. It
+//! is also wiped on drop (`free()` in JS), and neither `Debug` nor serde will reveal it.
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::random::random_scalar;
+use crate::{ECDSAError, ECDSASigner};
+
+const KEY_LENGTH: usize = 32;
+
+/// A secp256k1 private key in a buffer that is zeroed on drop or `destroy()`.
+#[wasm_bindgen]
+pub struct SecretKey {
+    bytes: Option<Zeroizing<[u8; KEY_LENGTH]>>,
+}
+
+impl SecretKey {
+    /// Takes ownership of key bytes after checking that they are a valid scalar.
+    fn from_array(bytes: Zeroizing<[u8; KEY_LENGTH]>) -> Result<SecretKey, ECDSAError> {
+        SigningKey::from_bytes(&bytes[..]).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+        Ok(SecretKey { bytes: Some(bytes) })
+    }
+
+    /// Copies a k256 signing key, which is already known to be valid.
+    pub(crate) fn from_signing_key(signing_key: &SigningKey) -> SecretKey {
+        SecretKey { bytes: Some(Zeroizing::new(signing_key.to_bytes().into())) }
+    }
+
+    /// The k256 signing key for this secret, which also zeroes itself on drop.
+    pub(crate) fn signing_key(&self) -> Result<SigningKey, ECDSAError> {
+        let bytes = self.bytes.as_ref().ok_or(ECDSAError::SecretKeyDestroyed)?;
+        SigningKey::from_bytes(&bytes[..]).map_err(|_| ECDSAError::InvalidPrivateKey)
+    }
+}
+
+#[wasm_bindgen]
+impl SecretKey {
+    /// Generates a fresh random key.
+    ///
+    /// # Errors:
+    /// * `RandomnessUnavailable` if no randomness is available.
+    pub fn generate() -> Result<SecretKey, ECDSAError> {
+        let mut bytes = Zeroizing::new([0u8; KEY_LENGTH]);
+        bytes.copy_from_slice(&random_scalar()?.to_bytes());
+        Ok(SecretKey { bytes: Some(bytes) })
+    }
+
+    /// Creates a key from raw bytes, wiping the caller's array once it has been copied.
+    ///
+    /// # Arguments:
+    /// * `bytes` - The 32-byte private key scalar (`Uint8Array` in JS); zeroed on return.
+    ///
+    /// # Errors:
+    /// * `InvalidPrivateKey` if the bytes are not a valid secp256k1 scalar.
+    pub fn from_bytes(bytes: &mut [u8]) -> Result<SecretKey, ECDSAError> {
+        let key = <[u8; KEY_LENGTH]>::try_from(&*bytes).map(Zeroizing::new);
+        bytes.zeroize();
+        SecretKey::from_array(key.map_err(|_| ECDSAError::InvalidPrivateKey)?)
+    }
+
+    /// Creates a key from a hex string. JS strings cannot be wiped, so prefer `from_bytes`
+    /// where the key is not already held as a string.
+    ///
+    /// # Arguments:
+    /// * `private_key` - The 32-byte private key scalar, provided as a hex string.
+    ///
+    /// # Errors:
+    /// * `InvalidPrivateKey` if the string is not valid hex or not a valid secp256k1 scalar.
+    pub fn from_hex(private_key: &str) -> Result<SecretKey, ECDSAError> {
+        let mut bytes = Zeroizing::new([0u8; KEY_LENGTH]);
+        hex::decode_to_slice(private_key, &mut bytes[..]).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+        SecretKey::from_array(bytes)
+    }
+
+    /// Returns the SEC1 encoded public key, as a hex string.
+    ///
+    /// # Arguments:
+    /// * `compressed` - Whether to use the 33-byte compressed encoding; defaults to true.
+    ///
+    /// # Errors:
+    /// * `SecretKeyDestroyed` if the key has been destroyed.
+    pub fn public_key(&self, compressed: Option<bool>) -> Result<String, ECDSAError> {
+        let point = self.signing_key()?.verifying_key().to_encoded_point(compressed.unwrap_or(true));
+        Ok(hex::encode(point.as_bytes()))
+    }
+
+    /// Creates an `ECDSASigner` for this key. The signer holds its own copy, which is wiped
+    /// when the signer is freed; destroying this key does not affect it.
+    ///
+    /// # Errors:
+    /// * `SecretKeyDestroyed` if the key has been destroyed.
+    pub fn signer(&self) -> Result<ECDSASigner, ECDSAError> {
+        Ok(ECDSASigner::from_signing_key(self.signing_key()?))
+    }
+
+    /// Zeroes the key now rather than when the wrapper is freed. Any later use fails with
+    /// `SecretKeyDestroyed`; destroying twice is harmless.
+    pub fn destroy(&mut self) {
+        // Dropping the `Zeroizing` buffer wipes it
+        self.bytes = None;
+    }
+
+    /// Whether `destroy()` has been called.
+    #[wasm_bindgen(getter)]
+    pub fn destroyed(&self) -> bool {
+        self.bytes.is_none()
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = if self.destroyed() { "destroyed" } else { "redacted" };
+        write!(f, "SecretKey(<{}>)", state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const PUBLIC_KEY: &str = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+    #[test]
+    fn test_from_bytes_wipes_input() {
+        let mut bytes = hex::decode(PRIVATE_KEY).unwrap();
+        let key = SecretKey::from_bytes(&mut bytes).unwrap();
+        assert_eq!(bytes, [0u8; 32]);
+        assert_eq!(key.public_key(None).unwrap(), PUBLIC_KEY);
+        assert_eq!(key.public_key(Some(false)).unwrap().len(), 130);
+        assert_eq!(SecretKey::from_hex(PRIVATE_KEY).unwrap().public_key(None).unwrap(), PUBLIC_KEY);
+    }
+
+    #[test]
+    fn test_destroy() {
+        let mut key = SecretKey::from_hex(PRIVATE_KEY).unwrap();
+        let signer = key.signer().unwrap();
+        key.destroy();
+        assert!(key.destroyed());
+        assert!(matches!(key.public_key(None), Err(ECDSAError::SecretKeyDestroyed)));
+        assert!(matches!(key.signer(), Err(ECDSAError::SecretKeyDestroyed)));
+        key.destroy();
+        // Signers made earlier keep working
+        assert_eq!(signer.public_key(), PUBLIC_KEY);
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let mut key = SecretKey::from_hex(PRIVATE_KEY).unwrap();
+        let debug = format!("{:?}", key);
+        assert_eq!(debug, "SecretKey(<redacted>)");
+        assert!(!debug.contains("c9af"));
+        key.destroy();
+        assert_eq!(format!("{:?}", key), "SecretKey(<destroyed>)");
+    }
+
+    #[test]
+    fn test_rejects_invalid_keys() {
+        assert!(matches!(SecretKey::from_hex(&"00".repeat(32)), Err(ECDSAError::InvalidPrivateKey)));
+        assert!(SecretKey::from_hex(&PRIVATE_KEY[..62]).is_err());
+        assert!(SecretKey::from_hex("zz").is_err());
+        // Above the group order
+        let mut bytes = [0xff; 32];
+        assert!(SecretKey::from_bytes(&mut bytes).is_err());
+        assert_eq!(bytes, [0; 32]);
+        assert!(SecretKey::from_bytes(&mut [1u8; 31]).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let a = SecretKey::generate().unwrap();
+        let b = SecretKey::generate().unwrap();
+        assert_ne!(a.public_key(None).unwrap(), b.public_key(None).unwrap());
+        let signature = a.signer().unwrap().sign("message").unwrap();
+        assert_eq!(signature.compact().len(), 128);
+    }
+}
@@ -0,0 +1,116 @@
+//! Verification of a signature over data that arrives in pieces.
+//!
+//! The signature and public key are parsed when the session starts, so malformed input is
+//! rejected before any data has been received; the data itself is only ever hashed, never
+//! buffered.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::{Signature, VerifyingKey};
+
+use crate::hash::{HashAlgorithm, Hasher};
+use crate::keys::parse_public_key_hex;
+use crate::signature::SignatureFormat;
+use crate::{ECDSAError, ECDSAVerifier};
+
+/// An in-progress secp256k1 ECDSA verification: `start`, `feed` each chunk, then `finish`.
+#[wasm_bindgen]
+pub struct VerificationSession {
+    verifier: ECDSAVerifier,
+    signature: Signature,
+    public_key: VerifyingKey,
+    hasher: Hasher,
+}
+
+impl VerificationSession {
+    pub(crate) fn with_verifier(verifier: ECDSAVerifier, signature: &str, public_key: &str) -> Result<VerificationSession, ECDSAError> {
+        let signature_bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        Ok(VerificationSession {
+            signature: verifier.parse_signature(&signature_bytes, SignatureFormat::Auto)?,
+            public_key: parse_public_key_hex(public_key)?,
+            hasher: Hasher::new(Some(verifier.hash_algorithm())),
+            verifier,
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl VerificationSession {
+    /// Starts verifying a signature over data that will be fed in later.
+    ///
+    /// # Arguments:
+    /// * `signature` - The DER or compact signature, provided as a hex string.
+    /// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+    /// * `hash_algorithm` - Optional hash applied to the data; defaults to SHA-256.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature or public key is malformed.
+    pub fn start(signature: &str, public_key: &str, hash_algorithm: Option<HashAlgorithm>) -> Result<VerificationSession, ECDSAError> {
+        VerificationSession::with_verifier(ECDSAVerifier::new(hash_algorithm), signature, public_key)
+    }
+
+    /// Hashes the next chunk of the signed data (`Uint8Array` in JS).
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Ends the session and checks the signature over everything fed to it. The session is
+    /// consumed, so JS code must not use it afterwards.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the signature is valid or not.
+    pub fn finish(self) -> bool {
+        let digest = self.hasher.finalize();
+        self.verifier.verify_with_policy(&self.public_key, &digest, &self.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ECDSASigner;
+
+    const PRIVATE_KEY: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+
+    #[test]
+    fn test_chunked_verification() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let signature = signer.sign_bytes(&data).unwrap();
+
+        for encoded in [signature.der(), signature.compact()] {
+            let mut session = VerificationSession::start(&encoded, &signer.public_key(), None).unwrap();
+            for chunk in data.chunks(4096) {
+                session.feed(chunk);
+            }
+            assert!(session.finish());
+        }
+
+        let mut truncated = VerificationSession::start(&signature.der(), &signer.public_key(), None).unwrap();
+        truncated.feed(&data[..data.len() - 1]);
+        assert!(!truncated.finish());
+    }
+
+    #[test]
+    fn test_rejects_malformed_input_up_front() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let signature = signer.sign_bytes(b"data").unwrap();
+        assert!(matches!(VerificationSession::start("zz", &signer.public_key(), None), Err(ECDSAError::InvalidSignatureFormat)));
+        assert!(VerificationSession::start(&signature.der(), "02", None).is_err());
+    }
+
+    #[test]
+    fn test_verifier_policy() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let signature = signer.sign_bytes(b"data").unwrap();
+        let verifier = ECDSAVerifier::new(Some(HashAlgorithm::Keccak256));
+
+        // The session hashes with the verifier's algorithm, so a SHA-256 signature fails
+        let mut session = verifier.start_session(&signature.der(), &signer.public_key()).unwrap();
+        session.feed(b"data");
+        assert!(!session.finish());
+        let mut session = ECDSAVerifier::default().start_session(&signature.der(), &signer.public_key()).unwrap();
+        session.feed(b"da");
+        session.feed(b"ta");
+        assert!(session.finish());
+    }
+}
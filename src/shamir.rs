@@ -0,0 +1,211 @@
+//! Shamir secret sharing over GF(256), byte by byte, for key backups split across devices
+//! or people.
+//!
+//! Each share is `threshold || x || y || checksum`, where `checksum` is the first four bytes
+//! of the SHA-256 of the rest, so a corrupted share is caught before it spoils a recovery.
+//! The shared value is the secret followed by the first four bytes of its SHA-256, so that
+//! shares from different splits, or too few shares, are detected when combined.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::random::fill_random;
+use crate::ECDSAError;
+
+const CHECKSUM_LENGTH: usize = 4;
+/// Threshold, x-coordinate and checksum.
+const SHARE_OVERHEAD: usize = 2 + CHECKSUM_LENGTH;
+
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    Sha256::digest(data)[..CHECKSUM_LENGTH].try_into().unwrap()
+}
+
+/// Multiplies in GF(2^8) modulo the AES polynomial x^8 + x^4 + x^3 + x + 1, without
+/// branching on the secret operands.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    product
+}
+
+/// Inverts a nonzero element as `a^254`.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1;
+    let mut power = a;
+    for bit in 0..8 {
+        if (254 >> bit) & 1 == 1 {
+            result = gf_mul(result, power);
+        }
+        power = gf_mul(power, power);
+    }
+    result
+}
+
+/// Evaluates a polynomial, lowest coefficient first, with Horner's rule.
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients.iter().rev().fold(0, |acc, &coefficient| gf_mul(acc, x) ^ coefficient)
+}
+
+/// Splits `secret` into `count` shares, using `random` for the polynomial coefficients.
+fn split(secret: &[u8], threshold: u8, count: u8, random: &[u8]) -> Vec<Vec<u8>> {
+    let payload = [secret, &checksum(secret)].concat();
+    let degree = threshold as usize - 1;
+    let polynomials: Vec<Vec<u8>> = payload
+        .iter()
+        .zip(random.chunks(degree.max(1)))
+        .map(|(&byte, coefficients)| [&[byte], &coefficients[..degree]].concat())
+        .collect();
+
+    (1..=count)
+        .map(|x| {
+            let mut share = vec![threshold, x];
+            share.extend(polynomials.iter().map(|polynomial| evaluate(polynomial, x)));
+            let check = checksum(&share);
+            share.extend_from_slice(&check);
+            share
+        })
+        .collect()
+}
+
+/// Recovers the secret from decoded shares.
+fn combine(shares: &[Vec<u8>]) -> Result<Vec<u8>, ECDSAError> {
+    let first = shares.first().ok_or(ECDSAError::ShamirRecoveryFailed)?;
+    for share in shares {
+        let (body, check) = share.split_at(share.len().saturating_sub(CHECKSUM_LENGTH));
+        if share.len() <= SHARE_OVERHEAD + CHECKSUM_LENGTH || share[1] == 0 || checksum(body) != check {
+            return Err(ECDSAError::InvalidShamirShare);
+        }
+        if share.len() != first.len() || share[0] != first[0] {
+            return Err(ECDSAError::ShamirRecoveryFailed);
+        }
+    }
+    let xs: Vec<u8> = shares.iter().map(|share| share[1]).collect();
+    if xs.iter().enumerate().any(|(i, x)| xs[..i].contains(x)) || shares.len() < first[0] as usize {
+        return Err(ECDSAError::ShamirRecoveryFailed);
+    }
+
+    // Lagrange interpolation at zero; subtraction is XOR in GF(2^8)
+    let weights: Vec<u8> = xs
+        .iter()
+        .map(|&xi| {
+            xs.iter().filter(|&&xj| xj != xi).fold(1, |weight, &xj| gf_mul(weight, gf_mul(xj, gf_inv(xj ^ xi))))
+        })
+        .collect();
+    let payload: Vec<u8> = (2..first.len() - CHECKSUM_LENGTH)
+        .map(|position| shares.iter().zip(&weights).fold(0, |byte, (share, &weight)| byte ^ gf_mul(share[position], weight)))
+        .collect();
+
+    let (secret, digest) = payload.split_at(payload.len() - CHECKSUM_LENGTH);
+    if checksum(secret) != digest {
+        return Err(ECDSAError::ShamirRecoveryFailed);
+    }
+    Ok(secret.to_vec())
+}
+
+/// Splits a secret into shares, any `threshold` of which recover it.
+///
+/// # Arguments:
+/// * `secret` - The secret, e.g. a private key or mnemonic entropy (`Uint8Array` in JS).
+/// * `threshold` - The number of shares needed to recover the secret, at least 1.
+/// * `shares` - The number of shares to create, from `threshold` to 255.
+///
+/// # Returns:
+/// * The shares, as hex strings.
+///
+/// # Errors:
+/// * `InvalidShamirParameters` if the secret is empty or the counts are out of range.
+#[wasm_bindgen]
+pub fn split_secret(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<String>, ECDSAError> {
+    if secret.is_empty() || threshold == 0 || shares < threshold {
+        return Err(ECDSAError::InvalidShamirParameters);
+    }
+    let mut random = vec![0u8; (secret.len() + CHECKSUM_LENGTH) * (threshold as usize - 1).max(1)];
+    fill_random(&mut random).map_err(|_| ECDSAError::InvalidEntropy)?;
+    Ok(split(secret, threshold, shares, &random).iter().map(hex::encode).collect())
+}
+
+/// Recovers a secret from its shares.
+///
+/// # Arguments:
+/// * `shares` - At least the threshold number of shares from one split, provided as hex strings.
+///
+/// # Returns:
+/// * The secret (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `InvalidShamirShare` if a share is malformed or fails its checksum.
+/// * `ShamirRecoveryFailed` if there are too few shares, a share repeats, or the shares come
+///   from different splits.
+#[wasm_bindgen]
+pub fn combine_shares(shares: Vec<String>) -> Result<Vec<u8>, ECDSAError> {
+    let shares = shares
+        .iter()
+        .map(|share| hex::decode(share).map_err(|_| ECDSAError::InvalidShamirShare))
+        .collect::<Result<Vec<_>, _>>()?;
+    combine(&shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        // The multiplication example from FIPS 197, section 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1, "{a}");
+        }
+    }
+
+    #[test]
+    fn test_split_and_combine() {
+        let secret = b"correct horse battery staple";
+        let shares = split_secret(secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        // Any three or more shares, in any order
+        for subset in [&[0, 1, 2][..], &[4, 2, 0], &[1, 3, 4], &[0, 1, 2, 3, 4]] {
+            let chosen = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine_shares(chosen).unwrap(), secret, "{subset:?}");
+        }
+        assert!(matches!(combine_shares(shares[..2].to_vec()), Err(ECDSAError::ShamirRecoveryFailed)));
+        assert!(matches!(combine_shares(vec![shares[0].clone(), shares[0].clone(), shares[1].clone()]), Err(ECDSAError::ShamirRecoveryFailed)));
+
+        // A threshold of one makes every share a copy
+        let copies = split_secret(&[0x42], 1, 2).unwrap();
+        assert_eq!(combine_shares(vec![copies[1].clone()]).unwrap(), [0x42]);
+    }
+
+    #[test]
+    fn test_deterministic_split() {
+        // With zero coefficients every share holds the secret itself
+        let shares = split(b"\x01\x02", 2, 2, &[0; 6]);
+        assert_eq!(&shares[0][..4], [2, 1, 1, 2]);
+        assert_eq!(&shares[1][..4], [2, 2, 1, 2]);
+        assert_eq!(combine(&shares).unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn test_rejects_bad_shares() {
+        let shares = split_secret(b"secret", 2, 3).unwrap();
+        let mut corrupted = hex::decode(&shares[0]).unwrap();
+        corrupted[3] ^= 1;
+        assert!(matches!(combine_shares(vec![hex::encode(corrupted), shares[1].clone()]), Err(ECDSAError::InvalidShamirShare)));
+        assert!(matches!(combine_shares(vec!["zz".into()]), Err(ECDSAError::InvalidShamirShare)));
+
+        // Shares of another split pass their checksums but not the secret's digest
+        let other = split_secret(b"secret", 2, 3).unwrap();
+        assert!(matches!(combine_shares(vec![shares[0].clone(), other[1].clone()]), Err(ECDSAError::ShamirRecoveryFailed)));
+
+        for (threshold, count) in [(0, 3), (3, 2)] {
+            assert!(matches!(split_secret(b"secret", threshold, count), Err(ECDSAError::InvalidShamirParameters)));
+        }
+        assert!(matches!(split_secret(b"", 2, 3), Err(ECDSAError::InvalidShamirParameters)));
+    }
+}
@@ -0,0 +1,480 @@
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::Signature;
+
+use crate::ECDSAError;
+
+/// Length of a fixed-size (r || s) secp256k1 signature.
+pub(crate) const COMPACT_SIGNATURE_LENGTH: usize = 64;
+
+/// How strictly DER signatures are validated.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DerParsing {
+    /// BIP-66 strict DER: minimal lengths and integers, no negative values, no trailing data.
+    #[default]
+    Strict,
+    /// Tolerates legacy encodings, modelled on libsecp256k1's `ecdsa_signature_parse_der_lax`:
+    /// long-form or inconsistent lengths, zero padding, missing sign bytes and trailing data.
+    Lax,
+}
+
+/// Strips a single optional `0x` or `0X` prefix from a hex string.
+fn strip_hex_prefix(text: &str) -> &str {
+    text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text)
+}
+
+/// Builds a signature from big-endian `r` and `s` magnitudes of at most 32 significant bytes.
+fn signature_from_integers(r: &[u8], s: &[u8]) -> Result<Signature, ECDSAError> {
+    let mut compact = [0u8; COMPACT_SIGNATURE_LENGTH];
+    for (integer, out) in [r, s].into_iter().zip(compact.chunks_exact_mut(32)) {
+        let start = integer.iter().position(|&b| b != 0).unwrap_or(integer.len());
+        let integer = &integer[start..];
+        if integer.len() > 32 {
+            return Err(ECDSAError::InvalidSignatureFormat);
+        }
+        out[32 - integer.len()..].copy_from_slice(integer);
+    }
+    parse_compact(&compact)
+}
+
+/// Checks the BIP-66 strict DER rules on a signature (without a sighash byte).
+fn is_strict_der(sig: &[u8]) -> bool {
+    // 0x30 [total-length] 0x02 [R-length] [R] 0x02 [S-length] [S]
+    let len = sig.len();
+    if !(8..=72).contains(&len) || sig[0] != 0x30 || sig[1] as usize != len - 2 {
+        return false;
+    }
+
+    let len_r = sig[3] as usize;
+    if 5 + len_r >= len {
+        return false;
+    }
+    let len_s = sig[5 + len_r] as usize;
+    if len_r + len_s + 6 != len {
+        return false;
+    }
+
+    for (tag, start, int_len) in [(2, 4, len_r), (len_r + 4, len_r + 6, len_s)] {
+        if sig[tag] != 0x02 || int_len == 0 || sig[start] & 0x80 != 0 {
+            return false;
+        }
+        // A leading zero is only allowed when the next byte would otherwise be negative
+        if int_len > 1 && sig[start] == 0 && sig[start + 1] & 0x80 == 0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Reads a DER length that may use the long form with redundant leading zeros.
+fn read_lax_length(bytes: &[u8], pos: &mut usize) -> Option<usize> {
+    let first = *bytes.get(*pos)?;
+    *pos += 1;
+    if first & 0x80 == 0 {
+        return Some(first as usize);
+    }
+
+    let count = (first & 0x7f) as usize;
+    let digits = bytes.get(*pos..*pos + count)?;
+    *pos += count;
+    let digits = &digits[digits.iter().position(|&b| b != 0).unwrap_or(count)..];
+    if digits.len() > std::mem::size_of::<usize>() {
+        return None;
+    }
+    Some(digits.iter().fold(0, |acc, &b| (acc << 8) | b as usize))
+}
+
+/// Locates the `r` and `s` integers of a DER signature the way libsecp256k1's lax parser does.
+fn lax_der_integers(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    if *bytes.first()? != 0x30 {
+        return None;
+    }
+
+    // The sequence length is skipped without being checked against the input
+    let first = *bytes.get(1)?;
+    let mut pos = 2;
+    if first & 0x80 != 0 {
+        pos += (first & 0x7f) as usize;
+    }
+
+    let mut integers = [&[][..]; 2];
+    for integer in integers.iter_mut() {
+        if *bytes.get(pos)? != 0x02 {
+            return None;
+        }
+        pos += 1;
+        let len = read_lax_length(bytes, &mut pos)?;
+        *integer = bytes.get(pos..pos.checked_add(len)?)?;
+        pos += len;
+    }
+    Some((integers[0], integers[1]))
+}
+
+/// Parses an ASN.1 DER encoded signature, enforcing strict (BIP-66) encoding.
+pub(crate) fn parse_der(bytes: &[u8]) -> Result<Signature, ECDSAError> {
+    parse_der_with_mode(bytes, DerParsing::Strict)
+}
+
+/// Parses an ASN.1 DER encoded signature with the given strictness.
+pub(crate) fn parse_der_with_mode(bytes: &[u8], mode: DerParsing) -> Result<Signature, ECDSAError> {
+    match mode {
+        DerParsing::Strict => {
+            if !is_strict_der(bytes) {
+                return Err(ECDSAError::InvalidSignatureFormat);
+            }
+            let len_r = bytes[3] as usize;
+            signature_from_integers(&bytes[4..4 + len_r], &bytes[6 + len_r..])
+        }
+        DerParsing::Lax => {
+            let (r, s) = lax_der_integers(bytes).ok_or(ECDSAError::InvalidSignatureFormat)?;
+            signature_from_integers(r, s)
+        }
+    }
+}
+
+/// Parses a fixed-size 64-byte (r || s) signature, as emitted by most blockchain tooling.
+pub(crate) fn parse_compact(bytes: &[u8]) -> Result<Signature, ECDSAError> {
+    if bytes.len() != COMPACT_SIGNATURE_LENGTH {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    Signature::try_from(bytes).map_err(|_| ECDSAError::InvalidSignatureFormat)
+}
+
+/// Splits an EIP-2098 compact signature (r || yParity:s), whose top bit of `s` carries the
+/// recovery id. Only low-S signatures have that bit free, so high-S values are rejected.
+pub(crate) fn split_eip2098(signature: &[u8]) -> Result<(Signature, u8), ECDSAError> {
+    if signature.len() != COMPACT_SIGNATURE_LENGTH {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+    let mut compact = [0u8; COMPACT_SIGNATURE_LENGTH];
+    compact.copy_from_slice(signature);
+    let recovery_id = compact[32] >> 7;
+    compact[32] &= 0x7f;
+
+    let sig = parse_compact(&compact)?;
+    if sig.normalize_s().is_some() {
+        return Err(ECDSAError::InvalidSignatureFormat);
+    }
+    Ok((sig, recovery_id))
+}
+
+/// Encodes a low-S signature and its recovery id in the EIP-2098 compact form.
+pub(crate) fn to_eip2098(sig: &Signature, recovery_id: u8) -> [u8; COMPACT_SIGNATURE_LENGTH] {
+    let mut compact = [0u8; COMPACT_SIGNATURE_LENGTH];
+    compact.copy_from_slice(sig.as_ref());
+    compact[32] |= recovery_id << 7;
+    compact
+}
+
+/// Splits a 65-byte recoverable signature (r || s || v) into its signature and recovery id.
+/// Accepts both raw (0/1) and legacy (27/28) `v` values, and 64-byte EIP-2098 signatures.
+pub(crate) fn split_signature(signature: &[u8]) -> Result<(Signature, u8), ECDSAError> {
+    if signature.len() == COMPACT_SIGNATURE_LENGTH {
+        return split_eip2098(signature);
+    }
+    if signature.len() != 65 {
+        return Err(ECDSAError::InvalidSignatureLength);
+    }
+
+    let recovery_id = match signature[64] {
+        0 | 27 => 0,
+        1 | 28 => 1,
+        _ => return Err(ECDSAError::InvalidSignatureRecovery),
+    };
+    let sig = parse_compact(&signature[..64])?;
+    Ok((sig, recovery_id))
+}
+
+/// Encoding of a signature passed to the verifier.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureFormat {
+    /// Detect the encoding from the input: 64 bytes is compact, anything else must be DER.
+    Auto,
+    /// ASN.1 DER (typically 70-72 bytes).
+    Der,
+    /// Fixed-size 64-byte r || s.
+    Compact,
+}
+
+/// How verifiers treat signatures whose `s` lies in the upper half of the curve order.
+///
+/// For every valid `(r, s)` the pair `(r, n - s)` is also valid, so accepting both lets a
+/// third party alter a signature without the key. Bitcoin (BIP-62/146) and Ethereum
+/// (EIP-2) only accept the low-S form.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HighSPolicy {
+    /// Treat high-S signatures as invalid, matching Bitcoin and Ethereum consensus rules.
+    #[default]
+    Reject,
+    /// Normalize high-S signatures to their low-S form before verifying, for interop with
+    /// signers that do not canonicalize (e.g. OpenSSL, WebCrypto).
+    Normalize,
+}
+
+/// Applies `policy` to `sig`, returning the signature that should be verified.
+pub(crate) fn apply_high_s_policy(sig: &Signature, policy: HighSPolicy) -> Signature {
+    match policy {
+        HighSPolicy::Reject => *sig,
+        HighSPolicy::Normalize => sig.normalize_s().unwrap_or(*sig),
+    }
+}
+
+/// Parses a signature in the given format, with strict DER validation.
+pub(crate) fn parse_signature(bytes: &[u8], format: SignatureFormat) -> Result<Signature, ECDSAError> {
+    parse_signature_with_mode(bytes, format, DerParsing::Strict)
+}
+
+/// Parses a signature in the given format, validating DER input according to `der`.
+pub(crate) fn parse_signature_with_mode(
+    bytes: &[u8],
+    format: SignatureFormat,
+    der: DerParsing,
+) -> Result<Signature, ECDSAError> {
+    match format {
+        SignatureFormat::Der => parse_der_with_mode(bytes, der),
+        SignatureFormat::Compact => parse_compact(bytes),
+        SignatureFormat::Auto if bytes.len() == COMPACT_SIGNATURE_LENGTH => parse_compact(bytes),
+        SignatureFormat::Auto => parse_der_with_mode(bytes, der),
+    }
+}
+
+/// An ECDSA signature, either produced by `ECDSASigner` or parsed from an external encoding.
+/// Exposes the DER and compact (r || s) encodings and the individual `r`, `s` and `v` values.
+#[wasm_bindgen(js_name = Signature)]
+pub struct ECDSASignature {
+    inner: Signature,
+    recovery_id: Option<u8>,
+}
+
+impl ECDSASignature {
+    pub(crate) fn new(inner: Signature, recovery_id: Option<u8>) -> ECDSASignature {
+        ECDSASignature { inner, recovery_id }
+    }
+}
+
+#[wasm_bindgen(js_class = Signature)]
+impl ECDSASignature {
+    /// Parses a hex encoded ASN.1 DER signature.
+    pub fn from_der(signature: &str) -> Result<ECDSASignature, ECDSAError> {
+        let bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        parse_der(&bytes).map(|inner| ECDSASignature::new(inner, None))
+    }
+
+    /// Parses a hex encoded 64-byte compact (r || s) signature.
+    pub fn from_compact(signature: &str) -> Result<ECDSASignature, ECDSAError> {
+        let bytes = hex::decode(signature).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        parse_compact(&bytes).map(|inner| ECDSASignature::new(inner, None))
+    }
+
+    /// Parses a hex encoded 64-byte EIP-2098 compact signature, whose top bit of `s`
+    /// carries the recovery id.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signature is not 64 bytes or `s` is not in its low-S form.
+    pub fn from_eip2098(signature: &str) -> Result<ECDSASignature, ECDSAError> {
+        let bytes = hex::decode(strip_hex_prefix(signature)).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        let (inner, recovery_id) = split_eip2098(&bytes)?;
+        Ok(ECDSASignature::new(inner, Some(recovery_id)))
+    }
+
+    /// Parses a hex encoded signature of any supported encoding, with or without a `0x`
+    /// prefix: 65 bytes is recoverable (r || s || v), 64 bytes is compact, anything else must be DER.
+    ///
+    /// # Errors:
+    /// * Returns an error if the bytes match none of the encodings or `v` is not 0, 1, 27 or 28.
+    pub fn from_hex(signature: &str) -> Result<ECDSASignature, ECDSAError> {
+        let bytes = hex::decode(strip_hex_prefix(signature)).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+        if bytes.len() == 65 {
+            let (inner, recovery_id) = split_signature(&bytes)?;
+            return Ok(ECDSASignature::new(inner, Some(recovery_id)));
+        }
+        parse_signature(&bytes, SignatureFormat::Auto).map(|inner| ECDSASignature::new(inner, None))
+    }
+
+    /// The 32-byte `r` value, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn r(&self) -> String {
+        hex::encode(self.inner.r().to_bytes())
+    }
+
+    /// The 32-byte `s` value, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn s(&self) -> String {
+        hex::encode(self.inner.s().to_bytes())
+    }
+
+    /// The Ethereum-style `v` value (27 or 28), when a recovery id is known.
+    #[wasm_bindgen(getter)]
+    pub fn v(&self) -> Option<u8> {
+        self.recovery_id.map(|recovery_id| recovery_id + 27)
+    }
+
+    /// Whether `s` is in the lower half of the curve order (the canonical form).
+    #[wasm_bindgen(getter)]
+    pub fn is_low_s(&self) -> bool {
+        self.inner.normalize_s().is_none()
+    }
+
+    /// Returns the canonical low-S form of this signature.
+    /// Negating `s` flips the parity of the nonce point, so a known recovery id is flipped too.
+    pub fn normalize_s(&self) -> ECDSASignature {
+        match self.inner.normalize_s() {
+            Some(normalized) => ECDSASignature::new(normalized, self.recovery_id.map(|id| id ^ 1)),
+            None => ECDSASignature::new(self.inner, self.recovery_id),
+        }
+    }
+
+    /// The ASN.1 DER encoding of the signature, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn der(&self) -> String {
+        hex::encode(self.inner.to_der().as_bytes())
+    }
+
+    /// The fixed-size 64-byte encoding (r || s), as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn compact(&self) -> String {
+        hex::encode(self.inner.as_ref())
+    }
+
+    /// The recovery id (0 or 1), when the signature was produced by a recovering signer.
+    #[wasm_bindgen(getter)]
+    pub fn recovery_id(&self) -> Option<u8> {
+        self.recovery_id
+    }
+
+    /// The 65-byte recoverable encoding (r || s || recovery id), as a hex string.
+    /// Returns `None` when no recovery id is known.
+    #[wasm_bindgen(getter)]
+    pub fn recoverable(&self) -> Option<String> {
+        let recovery_id = self.recovery_id?;
+        let mut bytes = self.inner.as_ref().to_vec();
+        bytes.push(recovery_id);
+        Some(hex::encode(bytes))
+    }
+
+    /// The 64-byte EIP-2098 encoding (r || yParity:s), as a hex string. A high-S signature
+    /// is normalized first, which yields the equivalent signature for the same key.
+    /// Returns `None` when no recovery id is known.
+    #[wasm_bindgen(getter)]
+    pub fn eip2098(&self) -> Option<String> {
+        let normalized = self.normalize_s();
+        Some(hex::encode(to_eip2098(&normalized.inner, normalized.recovery_id?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DER: &str = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+    const R: &str = "bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb2696";
+    const S: &str = "7d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+
+    #[test]
+    fn test_components_and_round_trip() {
+        let sig = ECDSASignature::from_der(DER).unwrap();
+        assert_eq!(sig.r(), R);
+        assert_eq!(sig.s(), S);
+        assert_eq!(sig.v(), None);
+        assert_eq!(sig.compact(), format!("{}{}", R, S));
+
+        let compact = ECDSASignature::from_compact(&sig.compact()).unwrap();
+        assert_eq!(compact.der(), DER);
+    }
+
+    #[test]
+    fn test_from_hex_detects_encoding() {
+        assert_eq!(ECDSASignature::from_hex(DER).unwrap().r(), R);
+        assert_eq!(ECDSASignature::from_hex(&format!("{}{}", R, S)).unwrap().s(), S);
+
+        let recoverable = ECDSASignature::from_hex(&format!("{}{}1c", R, S)).unwrap();
+        assert_eq!(recoverable.recovery_id(), Some(1));
+        assert_eq!(recoverable.v(), Some(28));
+        assert_eq!(recoverable.recoverable().unwrap(), format!("{}{}01", R, S));
+    }
+
+    #[test]
+    fn test_from_hex_with_prefix() {
+        let compact = format!("{}{}", R, S);
+        assert_eq!(ECDSASignature::from_hex(&format!("0x{}", compact)).unwrap().r(), R);
+        assert_eq!(ECDSASignature::from_hex(&format!("0X{}1b", compact)).unwrap().recovery_id(), Some(0));
+        assert_eq!(ECDSASignature::from_hex(&format!("0x{}", DER)).unwrap().s(), S);
+        assert!(ECDSASignature::from_hex(&format!("0x0x{}", compact)).is_err());
+    }
+
+    #[test]
+    fn test_normalize_s() {
+        let high_s = "82cd616936451dda44a073dfa6c1bb000b5b442ee5e650a2857a8efd082c6d89";
+        let sig = ECDSASignature::from_hex(&format!("{}{}00", R, high_s)).unwrap();
+        assert!(!sig.is_low_s());
+
+        let normalized = sig.normalize_s();
+        assert!(normalized.is_low_s());
+        assert_eq!(normalized.s(), S);
+        assert_eq!(normalized.recovery_id(), Some(1));
+        assert_eq!(normalized.normalize_s().s(), S);
+    }
+
+    #[test]
+    fn test_eip2098() {
+        // Test cases from EIP-2098
+        let r = "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90";
+        let s = "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064";
+        let compact = "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b907e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064";
+        let sig = ECDSASignature::from_eip2098(compact).unwrap();
+        assert_eq!((sig.r(), sig.s(), sig.v()), (r.to_string(), s.to_string(), Some(27)));
+        assert_eq!(sig.eip2098().unwrap(), compact);
+
+        let r = "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76";
+        let s = "139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793";
+        let compact = "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793";
+        let recoverable = ECDSASignature::from_hex(&format!("{r}{s}1c")).unwrap();
+        assert_eq!(recoverable.eip2098().unwrap(), compact);
+        assert_eq!(ECDSASignature::from_eip2098(compact).unwrap().recoverable().unwrap(), format!("{r}{s}01"));
+
+        // A high-S signature is normalized, flipping the parity bit it encodes
+        let high_s = "82cd616936451dda44a073dfa6c1bb000b5b442ee5e650a2857a8efd082c6d89";
+        let normalized = ECDSASignature::from_hex(&format!("{}{}00", R, high_s)).unwrap().eip2098().unwrap();
+        assert_eq!(normalized, format!("{}{}", R, "fd329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8"));
+        assert!(ECDSASignature::from_der(DER).unwrap().eip2098().is_none());
+        assert!(ECDSASignature::from_eip2098(&format!("{}{}", R, S)[..126]).is_err());
+    }
+
+    #[test]
+    fn test_strict_and_lax_der() {
+        let der = hex::decode(DER).unwrap();
+        assert!(parse_der_with_mode(&der, DerParsing::Strict).is_ok());
+        assert!(parse_der_with_mode(&der, DerParsing::Lax).is_ok());
+
+        // Non-minimal r (extra zero padding), long-form lengths and trailing data
+        let padded = hex::decode(format!("304602220000{}0220{}", R, S)).unwrap();
+        let long_form = hex::decode(format!("30814602812100{}0220{}", R, S)).unwrap();
+        let trailing = [der.clone(), vec![0x01]].concat();
+
+        let expected = parse_der(&der).unwrap();
+        for legacy in [&padded, &long_form, &trailing] {
+            assert!(parse_der_with_mode(legacy, DerParsing::Strict).is_err());
+            assert_eq!(parse_der_with_mode(legacy, DerParsing::Lax).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_strict_der_rejects_negative_integers() {
+        // r with its high bit set but no 0x00 sign byte
+        let negative = hex::decode(format!("30440220{}0220{}", R, S)).unwrap();
+        assert!(parse_der(&negative).is_err());
+        assert!(parse_der_with_mode(&negative, DerParsing::Lax).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        assert!(ECDSASignature::from_der(&DER[..20]).is_err());
+        assert!(ECDSASignature::from_compact(DER).is_err());
+        assert!(matches!(
+            ECDSASignature::from_hex(&format!("{}{}05", R, S)),
+            Err(ECDSAError::InvalidSignatureRecovery)
+        ));
+    }
+}
@@ -0,0 +1,254 @@
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::{Signature, SigningKey};
+use k256::elliptic_curve::ff::PrimeField;
+use k256::Scalar;
+use ecdsa::hazmat::SignPrimitive;
+use sha2::{Digest, Sha256};
+
+use crate::random::fill_random;
+use crate::{digest_to_scalar, rfc6979, ECDSAError, ECDSASignature, SecretKey};
+
+/// Signs a message digest with an RFC 6979 nonce.
+/// Returns the low-S normalized signature together with its recovery id.
+pub(crate) fn sign_digest(
+    signing_key: &SigningKey,
+    digest: &[u8],
+    extra_entropy: &[u8],
+) -> Result<(Signature, u8), ECDSAError> {
+    let secret = Option::<Scalar>::from(Scalar::from_repr(signing_key.to_bytes()))
+        .ok_or(ECDSAError::InvalidPrivateKey)?;
+    let z = digest_to_scalar(digest);
+    let k = rfc6979::generate_k(&secret, &z, extra_entropy);
+
+    let (sig, recovery_id) = secret
+        .try_sign_prehashed(*k, z)
+        .map_err(|_| ECDSAError::SigningFailed)?;
+    let recovery_id = recovery_id.ok_or(ECDSAError::SigningFailed)?;
+    if recovery_id.is_x_reduced() {
+        return Err(ECDSAError::SigningFailed);
+    }
+
+    Ok((sig, recovery_id.is_y_odd() as u8))
+}
+
+/// Struct for ECDSA signing over secp256k1 using k256 crate.
+#[wasm_bindgen]
+pub struct ECDSASigner {
+    signing_key: SigningKey,
+}
+
+impl ECDSASigner {
+    pub(crate) fn from_signing_key(signing_key: SigningKey) -> ECDSASigner {
+        ECDSASigner { signing_key }
+    }
+}
+
+#[wasm_bindgen]
+impl ECDSASigner {
+    /// Creates a signer from a 32-byte secp256k1 private key.
+    ///
+    /// # Arguments:
+    /// * `private_key` - The private key scalar, provided as a hex string.
+    ///
+    /// # Errors:
+    /// * Returns an error if the key is not valid hex or is not a valid secp256k1 scalar.
+    #[wasm_bindgen(constructor)]
+    pub fn new(private_key: &str) -> Result<ECDSASigner, ECDSAError> {
+        SecretKey::from_hex(private_key)?.signer()
+    }
+
+    /// Returns the SEC1 compressed public key matching this signer, as a hex string.
+    pub fn public_key(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    /// Signs a message after hashing it with SHA-256.
+    ///
+    /// # Arguments:
+    /// * `message` - The message to sign.
+    ///
+    /// # Returns:
+    /// * The signature, available in both DER and compact hex encodings,
+    ///   along with the recovery id needed by `ECDSAVerifier::recover_public_key`.
+    ///
+    /// # Errors:
+    /// * Returns an error if the signing operation fails.
+    pub fn sign(&self, message: &str) -> Result<ECDSASignature, ECDSAError> {
+        self.sign_bytes(message.as_bytes())
+    }
+
+    /// Signs a message with a deterministic RFC 6979 nonce, after hashing it with SHA-256.
+    ///
+    /// The same key and message always produce the same signature, and no randomness is
+    /// drawn from the environment, which makes this safe in sandboxes without a reliable
+    /// entropy source.
+    ///
+    /// # Arguments:
+    /// * `message` - The message to sign.
+    ///
+    /// # Returns:
+    /// * The low-S normalized signature, with its recovery id.
+    pub fn sign_deterministic(&self, message: &str) -> Result<ECDSASignature, ECDSAError> {
+        let digest = Sha256::digest(message.as_bytes());
+        let (sig, recovery_id) = sign_digest(&self.signing_key, &digest, &[])?;
+
+        Ok(ECDSASignature::new(sig, Some(recovery_id)))
+    }
+
+    /// Signs a message with a hedged nonce: RFC 6979 with additional entropy mixed in
+    /// (section 3.6), after hashing the message with SHA-256.
+    ///
+    /// Fresh randomness in the nonce defends against fault and side-channel attacks on
+    /// purely deterministic signing, while the key and message still protect against a
+    /// weak random source.
+    ///
+    /// # Arguments:
+    /// * `message` - The message to sign.
+    /// * `extra_entropy` - Bytes mixed into the nonce derivation. When omitted, 32 bytes
+    ///   are drawn from the platform CSPRNG (`crypto.getRandomValues` in browsers).
+    ///   Passing fixed bytes makes the output reproducible, e.g. in tests.
+    ///
+    /// # Errors:
+    /// * `RandomnessUnavailable` if `extra_entropy` is omitted and no randomness is available.
+    /// * `SigningFailed` if the signing operation fails.
+    pub fn sign_hedged(&self, message: &str, extra_entropy: Option<Vec<u8>>) -> Result<ECDSASignature, ECDSAError> {
+        let extra_entropy = match extra_entropy {
+            Some(entropy) => entropy,
+            None => {
+                let mut entropy = vec![0u8; 32];
+                fill_random(&mut entropy)?;
+                entropy
+            }
+        };
+
+        let digest = Sha256::digest(message.as_bytes());
+        let (sig, recovery_id) = sign_digest(&self.signing_key, &digest, &extra_entropy)?;
+
+        Ok(ECDSASignature::new(sig, Some(recovery_id)))
+    }
+
+    /// Signs binary data (`Uint8Array` in JS) after hashing it with SHA-256.
+    pub fn sign_bytes(&self, message: &[u8]) -> Result<ECDSASignature, ECDSAError> {
+        let digest = Sha256::digest(message);
+        let (sig, recovery_id) = sign_digest(&self.signing_key, &digest, &[])?;
+
+        Ok(ECDSASignature::new(sig, Some(recovery_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{random, ECDSAVerifier};
+    use k256::ecdsa::signature::Signer;
+
+    const PRIVATE_KEY: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+
+    #[test]
+    fn test_public_key() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        assert_eq!(
+            signer.public_key(),
+            "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645"
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let signature = signer.sign("Test message").unwrap();
+        assert_eq!(signature.compact().len(), 128);
+
+        let verifier = ECDSAVerifier::default();
+        let result = verifier.verify_signature("Test message", &signature.der(), &signer.public_key());
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn test_matches_k256_signer() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let expected: Signature = signer.signing_key.sign(b"Test message");
+        let signature = signer.sign("Test message").unwrap();
+        assert_eq!(signature.compact(), hex::encode(expected.as_ref()));
+    }
+
+    #[test]
+    fn test_sign_deterministic_vectors() {
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "Satoshi Nakamoto",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+                 2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b\
+                 547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "Satoshi Nakamoto",
+                "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0\
+                 6b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5",
+            ),
+            (
+                "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+                "Alan Turing",
+                "7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c\
+                 58dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea",
+            ),
+        ];
+
+        for (private_key, message, expected) in vectors {
+            let signer = ECDSASigner::new(private_key).unwrap();
+            let signature = signer.sign_deterministic(message).unwrap();
+            assert_eq!(signature.compact(), expected, "{}", message);
+            assert_eq!(signer.sign_deterministic(message).unwrap().compact(), expected);
+        }
+    }
+
+    #[test]
+    fn test_sign_hedged() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let deterministic = signer.sign_deterministic("Test message").unwrap().compact();
+
+        let first = signer.sign_hedged("Test message", Some(vec![0x01; 32])).unwrap();
+        let again = signer.sign_hedged("Test message", Some(vec![0x01; 32])).unwrap();
+        let other = signer.sign_hedged("Test message", Some(vec![0x02; 32])).unwrap();
+        assert_eq!(first.compact(), again.compact());
+        assert_ne!(first.compact(), other.compact());
+        assert_ne!(first.compact(), deterministic);
+
+        let random = signer.sign_hedged("Test message", None).unwrap();
+        let verifier = ECDSAVerifier::default();
+        for signature in [first, random] {
+            assert!(verifier.verify_signature("Test message", &signature.der(), &signer.public_key()).unwrap());
+        }
+
+        random::install_entropy_source(Some(std::rc::Rc::new(|_: &mut [u8]| Err(ECDSAError::RandomnessUnavailable))));
+        assert!(matches!(signer.sign_hedged("Test message", None), Err(ECDSAError::RandomnessUnavailable)));
+        assert!(signer.sign_hedged("Test message", Some(vec![0x01; 32])).is_ok());
+        random::install_entropy_source(None);
+    }
+
+    #[test]
+    fn test_sign_and_recover() {
+        let signer = ECDSASigner::new(PRIVATE_KEY).unwrap();
+        let signature = signer.sign("Test message").unwrap();
+
+        let verifier = ECDSAVerifier::default();
+        let recovered = verifier
+            .recover_public_key("Test message", &signature.compact(), signature.recovery_id().unwrap())
+            .unwrap();
+        assert_eq!(recovered, signer.public_key());
+    }
+
+    #[test]
+    fn test_invalid_private_key() {
+        assert!(ECDSASigner::new("not hex").is_err());
+        assert!(ECDSASigner::new("00").is_err());
+        assert!(ECDSASigner::new(&"00".repeat(32)).is_err());
+    }
+}
@@ -0,0 +1,607 @@
+//! Sigstore bundle verification for `cosign sign-blob --bundle` signatures.
+//!
+//! A bundle carries a short-lived Fulcio certificate binding an OIDC identity to the signing
+//! key, an ECDSA signature over the artifact's SHA-256 digest, and optionally the Rekor
+//! transparency log entry recording that signature. The entry's Merkle inclusion proof is
+//! checked against the tree head (RFC 6962), and the signed checkpoint against the Rekor key
+//! when one is supplied. Only `messageSignature` bundles with `hashedrekord` entries are
+//! supported; DSSE envelopes and public-key bundles are rejected.
+
+use js_sys::Uint8Array;
+use sha2::{Digest, Sha256};
+use wasm_bindgen::prelude::*;
+
+use crate::encoding::from_base64;
+use crate::json::{self, Value};
+use crate::keys::asn1::{parse_spki, Der, KeyAlgorithm, TAG_OID};
+use crate::pem::decode_pem;
+use crate::time::{now_millis, parse_rfc3339};
+use crate::x509::{parse_certificate, validate_chain, Certificate};
+use crate::ECDSAError;
+
+const MEDIA_TYPE_PREFIX: &str = "application/vnd.dev.sigstore.bundle";
+
+/// `subjectAltName` (2.5.29.17)
+const OID_SUBJECT_ALT_NAME: &[u8] = &[0x55, 0x1d, 0x11];
+/// `extKeyUsage` (2.5.29.37)
+const OID_EXTENDED_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x25];
+/// `id-kp-codeSigning` (1.3.6.1.5.5.7.3.3)
+const OID_CODE_SIGNING: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03];
+/// Fulcio OIDC issuer (1.3.6.1.4.1.57264.1.1), the raw issuer URL
+const OID_FULCIO_ISSUER: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xbf, 0x30, 0x01, 0x01];
+/// Fulcio OIDC issuer V2 (1.3.6.1.4.1.57264.1.8), a DER `UTF8String`
+const OID_FULCIO_ISSUER_V2: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xbf, 0x30, 0x01, 0x08];
+
+const TAG_UTF8_STRING: u8 = 0x0c;
+/// `rfc822Name` and `uniformResourceIdentifier` in `GeneralName`, implicitly tagged
+const TAG_EMAIL: u8 = 0x81;
+const TAG_URI: u8 = 0x86;
+
+/// A Rekor inclusion proof: the audit path from the entry's leaf to a tree head.
+pub(crate) struct InclusionProof {
+    pub(crate) log_index: u64,
+    pub(crate) tree_size: u64,
+    pub(crate) root_hash: Vec<u8>,
+    pub(crate) hashes: Vec<Vec<u8>>,
+    /// The signed note committing to the tree head
+    pub(crate) checkpoint: Option<String>,
+}
+
+/// A Rekor transparency log entry.
+pub(crate) struct TlogEntry {
+    pub(crate) log_index: u64,
+    /// Seconds since the Unix epoch, as recorded by the log
+    pub(crate) integrated_time: u64,
+    /// The canonicalized entry body the log hashed
+    pub(crate) body: Vec<u8>,
+    pub(crate) inclusion_proof: Option<InclusionProof>,
+}
+
+/// The parts of a sigstore bundle that verification uses.
+pub(crate) struct Bundle {
+    /// DER certificates, leaf first
+    pub(crate) certificates: Vec<Vec<u8>>,
+    pub(crate) digest: Vec<u8>,
+    pub(crate) signature: Vec<u8>,
+    pub(crate) tlog_entry: Option<TlogEntry>,
+}
+
+fn field<'a>(value: &'a Value, path: &[&str]) -> Result<&'a Value, ECDSAError> {
+    path.iter().try_fold(value, |value, key| value.get(key)).ok_or(ECDSAError::InvalidSigstoreBundle)
+}
+
+fn base64_field(value: &Value, path: &[&str]) -> Result<Vec<u8>, ECDSAError> {
+    field(value, path)?.as_str().and_then(from_base64).ok_or(ECDSAError::InvalidSigstoreBundle)
+}
+
+fn text_field(value: &Value, path: &[&str]) -> Result<String, ECDSAError> {
+    field(value, path)?.as_str().map(str::to_string).ok_or(ECDSAError::InvalidSigstoreBundle)
+}
+
+/// Reads an integer, which the protobuf JSON mapping writes as a string for 64-bit fields.
+fn integer_field(value: &Value, path: &[&str]) -> Result<u64, ECDSAError> {
+    let value = field(value, path)?;
+    value.as_u64().or_else(|| value.as_str()?.parse().ok()).ok_or(ECDSAError::InvalidSigstoreBundle)
+}
+
+fn read_tlog_entry(entry: &Value) -> Result<TlogEntry, ECDSAError> {
+    let inclusion_proof = match entry.get("inclusionProof") {
+        Some(proof) => Some(InclusionProof {
+            log_index: integer_field(proof, &["logIndex"])?,
+            tree_size: integer_field(proof, &["treeSize"])?,
+            root_hash: base64_field(proof, &["rootHash"])?,
+            hashes: field(proof, &["hashes"])?
+                .as_array()
+                .ok_or(ECDSAError::InvalidSigstoreBundle)?
+                .iter()
+                .map(|hash| hash.as_str().and_then(from_base64).ok_or(ECDSAError::InvalidSigstoreBundle))
+                .collect::<Result<_, _>>()?,
+            checkpoint: proof.get("checkpoint").map(|checkpoint| text_field(checkpoint, &["envelope"])).transpose()?,
+        }),
+        None => None,
+    };
+    Ok(TlogEntry {
+        log_index: integer_field(entry, &["logIndex"])?,
+        integrated_time: integer_field(entry, &["integratedTime"])?,
+        body: base64_field(entry, &["canonicalizedBody"])?,
+        inclusion_proof,
+    })
+}
+
+/// Parses a JSON bundle of media type version 0.1 to 0.3.
+pub(crate) fn parse_bundle(text: &str) -> Result<Bundle, ECDSAError> {
+    let bundle = json::parse(text).map_err(|_| ECDSAError::InvalidSigstoreBundle)?;
+    let media_type = field(&bundle, &["mediaType"])?.as_str().ok_or(ECDSAError::InvalidSigstoreBundle)?;
+    if !media_type.starts_with(MEDIA_TYPE_PREFIX) {
+        return Err(ECDSAError::InvalidSigstoreBundle);
+    }
+
+    let material = field(&bundle, &["verificationMaterial"])?;
+    // Version 0.3 carries only the leaf; earlier versions may include intermediates
+    let certificates = match material.get("certificate") {
+        Some(certificate) => vec![base64_field(certificate, &["rawBytes"])?],
+        None => field(material, &["x509CertificateChain", "certificates"])?
+            .as_array()
+            .ok_or(ECDSAError::InvalidSigstoreBundle)?
+            .iter()
+            .map(|certificate| base64_field(certificate, &["rawBytes"]))
+            .collect::<Result<_, _>>()?,
+    };
+    if certificates.is_empty() {
+        return Err(ECDSAError::InvalidSigstoreBundle);
+    }
+
+    let signature = field(&bundle, &["messageSignature"])?;
+    if field(signature, &["messageDigest", "algorithm"])?.as_str() != Some("SHA2_256") {
+        return Err(ECDSAError::InvalidSigstoreBundle);
+    }
+    let tlog_entry = match material.get("tlogEntries").map(|entries| entries.as_array()) {
+        Some(Some(entries)) => entries.first().map(read_tlog_entry).transpose()?,
+        Some(None) => return Err(ECDSAError::InvalidSigstoreBundle),
+        None => None,
+    };
+    Ok(Bundle {
+        certificates,
+        digest: base64_field(signature, &["messageDigest", "digest"])?,
+        signature: base64_field(signature, &["signature"])?,
+        tlog_entry,
+    })
+}
+
+/// Verifies a DER ECDSA signature over `digest` with a P-256 SubjectPublicKeyInfo.
+fn verify_p256(spki: &[u8], digest: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+    let spki = parse_spki(spki)?;
+    if spki.algorithm != KeyAlgorithm::P256 {
+        return Err(ECDSAError::UnsupportedCurve);
+    }
+    match crate::p256::verify_bytes(digest, signature, spki.public_key) {
+        Err(ECDSAError::InvalidSignatureFormat) => Ok(false),
+        result => result,
+    }
+}
+
+/// Checks that the leaf was issued for code signing, as Fulcio certificates are.
+fn check_code_signing(certificate: &Certificate) -> Result<(), ECDSAError> {
+    let usages = certificate.extension(OID_EXTENDED_KEY_USAGE).ok_or(ECDSAError::InvalidCertificate)?;
+    let mut usages = Der::new(usages).sequence()?;
+    while !usages.is_empty() {
+        if usages.read(TAG_OID)? == OID_CODE_SIGNING {
+            return Ok(());
+        }
+    }
+    Err(ECDSAError::InvalidCertificate)
+}
+
+/// The first email address or URI in the subject alternative names: the OIDC identity
+/// Fulcio certified.
+fn signer_identity(certificate: &Certificate) -> Option<String> {
+    let mut names = Der::new(certificate.extension(OID_SUBJECT_ALT_NAME)?).sequence().ok()?;
+    while let Ok((tag, value, _)) = names.read_element() {
+        if tag == TAG_EMAIL || tag == TAG_URI {
+            return String::from_utf8(value.to_vec()).ok();
+        }
+    }
+    None
+}
+
+/// The OIDC issuer that authenticated the signer, from either Fulcio extension.
+fn oidc_issuer(certificate: &Certificate) -> Option<String> {
+    let issuer = match certificate.extension(OID_FULCIO_ISSUER_V2) {
+        Some(value) => Der::new(value).read(TAG_UTF8_STRING).ok()?,
+        None => certificate.extension(OID_FULCIO_ISSUER)?,
+    };
+    String::from_utf8(issuer.to_vec()).ok()
+}
+
+/// Checks that a `hashedrekord` entry body records this digest, signature and certificate.
+fn check_hashedrekord(body: &[u8], bundle: &Bundle) -> Result<(), ECDSAError> {
+    let body = std::str::from_utf8(body).map_err(|_| ECDSAError::RekorEntryMismatch)?;
+    let body = json::parse(body).map_err(|_| ECDSAError::RekorEntryMismatch)?;
+    let text = |path: &[&str]| field(&body, path).ok().and_then(Value::as_str);
+
+    let certificate = text(&["spec", "signature", "publicKey", "content"])
+        .and_then(from_base64)
+        .and_then(|pem| decode_pem(std::str::from_utf8(&pem).ok()?, &["CERTIFICATE"]).ok());
+    let matches = text(&["kind"]) == Some("hashedrekord")
+        && text(&["spec", "data", "hash", "algorithm"]) == Some("sha256")
+        && text(&["spec", "data", "hash", "value"]) == Some(&hex::encode(&bundle.digest))
+        && text(&["spec", "signature", "content"]).and_then(from_base64).as_ref() == Some(&bundle.signature)
+        && certificate.is_some_and(|(_, der)| der == bundle.certificates[0]);
+    if !matches {
+        return Err(ECDSAError::RekorEntryMismatch);
+    }
+    Ok(())
+}
+
+fn hash_children(left: &[u8], right: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update([1]).chain_update(left).chain_update(right).finalize().into()
+}
+
+/// Computes the tree head an inclusion proof leads to (RFC 9162 section 2.1.3.2), or `None`
+/// if the path has the wrong length for the index and tree size.
+pub(crate) fn root_from_inclusion_proof(index: u64, tree_size: u64, leaf: &[u8], hashes: &[Vec<u8>]) -> Option<[u8; 32]> {
+    if index >= tree_size {
+        return None;
+    }
+    let mut root: [u8; 32] = Sha256::new().chain_update([0]).chain_update(leaf).finalize().into();
+    let (mut node, mut last) = (index, tree_size - 1);
+    for hash in hashes {
+        if last == 0 {
+            return None;
+        }
+        if node & 1 == 1 || node == last {
+            root = hash_children(hash, &root);
+            while node & 1 == 0 && node != 0 {
+                node >>= 1;
+                last >>= 1;
+            }
+        } else {
+            root = hash_children(&root, hash);
+        }
+        node >>= 1;
+        last >>= 1;
+    }
+    (last == 0).then_some(root)
+}
+
+/// Checks that a checkpoint commits to the proof's tree head and, given the log's key, that
+/// the log signed it. Returns whether a signature was verified.
+fn check_checkpoint(envelope: &str, proof: &InclusionProof, log_key: Option<&[u8]>) -> Result<bool, ECDSAError> {
+    // A signed note: origin, tree size and root hash lines, a blank line, then signatures
+    let (note, signatures) = envelope.split_once("\n\n").ok_or(ECDSAError::InclusionProofMismatch)?;
+    let mut lines = note.lines().skip(1);
+    let tree_size = lines.next().and_then(|size| size.parse::<u64>().ok());
+    let root_hash = lines.next().and_then(from_base64);
+    if tree_size != Some(proof.tree_size) || root_hash.as_ref() != Some(&proof.root_hash) {
+        return Err(ECDSAError::InclusionProofMismatch);
+    }
+
+    let Some(log_key) = log_key else {
+        return Ok(false);
+    };
+    let key_hint = &Sha256::digest(log_key)[..4];
+    let digest = Sha256::new().chain_update(note).chain_update("\n").finalize();
+    for line in signatures.lines() {
+        let signature = line
+            .strip_prefix("\u{2014} ")
+            .and_then(|line| line.rsplit_once(' '))
+            .and_then(|(_, signature)| from_base64(signature))
+            .ok_or(ECDSAError::InclusionProofMismatch)?;
+        if signature.len() > 4 && &signature[..4] == key_hint && verify_p256(log_key, &digest, &signature[4..])? {
+            return Ok(true);
+        }
+    }
+    Err(ECDSAError::InclusionProofMismatch)
+}
+
+/// Checks the log entry against the bundle and its inclusion proof against the tree head.
+/// Returns whether the log's signature on the tree head was verified.
+fn check_tlog_entry(entry: &TlogEntry, bundle: &Bundle, log_key: Option<&[u8]>) -> Result<bool, ECDSAError> {
+    check_hashedrekord(&entry.body, bundle)?;
+    let Some(proof) = &entry.inclusion_proof else {
+        return Ok(false);
+    };
+    let root = root_from_inclusion_proof(proof.log_index, proof.tree_size, &entry.body, &proof.hashes);
+    if root.is_none_or(|root| root[..] != proof.root_hash[..]) {
+        return Err(ECDSAError::InclusionProofMismatch);
+    }
+    match &proof.checkpoint {
+        Some(checkpoint) => check_checkpoint(checkpoint, proof, log_key),
+        None => Ok(false),
+    }
+}
+
+/// A verified sigstore bundle.
+#[wasm_bindgen]
+pub struct SigstoreVerification {
+    identity: Option<String>,
+    issuer: Option<String>,
+    log_index: Option<u64>,
+    integrated_time: Option<u64>,
+    inclusion_verified: bool,
+}
+
+#[wasm_bindgen]
+impl SigstoreVerification {
+    /// The signer's email address or URI from the certificate, e.g. `alice@example.com` or a
+    /// CI workflow URL. Compare it, together with `issuer`, with the expected signer.
+    #[wasm_bindgen(getter)]
+    pub fn identity(&self) -> Option<String> {
+        self.identity.clone()
+    }
+
+    /// The OIDC issuer that authenticated the signer, e.g. `https://accounts.google.com`.
+    #[wasm_bindgen(getter)]
+    pub fn issuer(&self) -> Option<String> {
+        self.issuer.clone()
+    }
+
+    /// The Rekor log index of the entry, if the bundle has one.
+    #[wasm_bindgen(getter)]
+    pub fn log_index(&self) -> Option<u64> {
+        self.log_index
+    }
+
+    /// When Rekor recorded the entry, in seconds since the Unix epoch.
+    #[wasm_bindgen(getter)]
+    pub fn integrated_time(&self) -> Option<u64> {
+        self.integrated_time
+    }
+
+    /// Whether the entry was proven to be in a tree head signed by the Rekor key.
+    #[wasm_bindgen(getter)]
+    pub fn inclusion_verified(&self) -> bool {
+        self.inclusion_verified
+    }
+}
+
+/// Verifies a JSON bundle against `digest`, checking certificates at `time` in seconds since
+/// the Unix epoch when given.
+pub(crate) fn verify_bundle<A: AsRef<[u8]>>(
+    bundle: &str,
+    digest: &[u8],
+    trusted: &[A],
+    rekor_public_key: Option<&[u8]>,
+    time: Option<i64>,
+) -> Result<SigstoreVerification, ECDSAError> {
+    let bundle = parse_bundle(bundle)?;
+    if digest.len() != 32 || bundle.digest != digest {
+        return Err(ECDSAError::SigstoreSignatureMismatch);
+    }
+
+    let time = match (time, &bundle.tlog_entry) {
+        (Some(time), _) => time,
+        (None, Some(entry)) => i64::try_from(entry.integrated_time).map_err(|_| ECDSAError::InvalidSigstoreBundle)?,
+        (None, None) => now_millis().div_euclid(1000),
+    };
+    let (leaf, intermediates) = bundle.certificates.split_first().ok_or(ECDSAError::InvalidSigstoreBundle)?;
+    validate_chain(leaf, intermediates, trusted, time)?;
+
+    let certificate = parse_certificate(leaf)?;
+    check_code_signing(&certificate)?;
+    if !verify_p256(certificate.spki, digest, &bundle.signature)? {
+        return Err(ECDSAError::SigstoreSignatureMismatch);
+    }
+
+    let inclusion_verified = match &bundle.tlog_entry {
+        Some(entry) => check_tlog_entry(entry, &bundle, rekor_public_key)?,
+        None => false,
+    };
+    if rekor_public_key.is_some() && !inclusion_verified {
+        return Err(ECDSAError::InclusionProofMismatch);
+    }
+    Ok(SigstoreVerification {
+        identity: signer_identity(&certificate),
+        issuer: oidc_issuer(&certificate),
+        log_index: bundle.tlog_entry.as_ref().map(|entry| entry.log_index),
+        integrated_time: bundle.tlog_entry.as_ref().map(|entry| entry.integrated_time),
+        inclusion_verified,
+    })
+}
+
+/// Verifies a sigstore bundle, as made by `cosign sign-blob --bundle`, for an artifact.
+///
+/// The Fulcio certificate must chain to `trusted_certificates` and be valid when the
+/// signature was logged, and its key must have signed the artifact digest. A Rekor entry in
+/// the bundle must record the same digest, signature and certificate, and its inclusion proof
+/// must lead to the tree head it names. The entry's `signedEntryTimestamp` is not checked.
+///
+/// # Arguments:
+/// * `bundle` - The bundle JSON.
+/// * `digest` - The SHA-256 digest of the artifact (`Uint8Array` in JS).
+/// * `trusted_certificates` - The Fulcio root and intermediate certificates to trust, DER
+///   encoded (an array of `Uint8Array`). The public Fulcio CA uses P-384 keys, which need the
+///   `p384` feature.
+/// * `rekor_public_key` - The Rekor log's P-256 DER SubjectPublicKeyInfo. When given, the bundle
+///   must include an inclusion proof whose checkpoint the log signed.
+/// * `time` - An optional RFC 3339 timestamp at which to check the certificates. Defaults to
+///   the entry's integrated time, which is only as trustworthy as the log entry, or else to the
+///   current time.
+///
+/// # Returns:
+/// * The certified signer identity and issuer, and the Rekor entry's index and time.
+///
+/// # Errors:
+/// * `InvalidSigstoreBundle` if the bundle is malformed or of an unsupported kind.
+/// * `SigstoreSignatureMismatch` if the bundle is for another digest or the signature is invalid.
+/// * `InvalidCertificate` if the leaf certificate is not for code signing.
+/// * `UnsupportedCurve` if the leaf or Rekor key is not a P-256 key.
+/// * `RekorEntryMismatch` if the log entry records another artifact or signature.
+/// * `InclusionProofMismatch` if the inclusion proof or checkpoint does not verify, or is
+///   missing when `rekor_public_key` is given.
+/// * The errors of `verify_chain` if the certificate chain cannot be validated.
+#[wasm_bindgen]
+pub fn verify_sigstore_bundle(
+    bundle: &str,
+    digest: &[u8],
+    trusted_certificates: Vec<Uint8Array>,
+    rekor_public_key: Option<Vec<u8>>,
+    time: Option<String>,
+) -> Result<SigstoreVerification, ECDSAError> {
+    let time = match time {
+        Some(time) => Some(parse_rfc3339(&time).ok_or(ECDSAError::InvalidCertificate)?.div_euclid(1000)),
+        None => None,
+    };
+    let trusted = trusted_certificates.iter().map(Uint8Array::to_vec).collect::<Vec<_>>();
+    verify_bundle(bundle, digest, &trusted, rekor_public_key.as_deref(), time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_base64;
+
+    // A bundle for "hello sigstore\n", signed by a leaf issued for alice@example.com at
+    // 2026-10-01T12:00:00Z, whose entry is leaf 4 of a 6-leaf log
+    const BUNDLE: &str = r#"{
+  "mediaType": "application/vnd.dev.sigstore.bundle.v0.3+json",
+  "verificationMaterial": {
+    "certificate": {
+      "rawBytes": "MIIByzCCAXCgAwIBAgIBAzAKBggqhkjOPQQDAjA3MRUwEwYDVQQKDAxzaWdzdG9yZS5kZXYxHjAcBgNVBAMMFXNpZ3N0b3JlLWludGVybWVkaWF0ZTAeFw0yNjEwMDExMjAwMDBaFw0yNjEwMDExMjEwMDBaMAAwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAR87taSwogcbq4mprfpKjQdirq8JrW79+H+6lAL/ovMXf13KAJpmCDkRDwstlXZW+d0ML4ESZeoirOVw8YeMgheo4GjMIGgMA4GA1UdDwEB/wQEAwIHgDATBgNVHSUEDDAKBggrBgEFBQcDAzAfBgNVHREBAf8EFTATgRFhbGljZUBleGFtcGxlLmNvbTAqBgorBgEEAYO/MAEBBBxodHRwczovL2FjY291bnRzLmV4YW1wbGUuY29tMCwGCisGAQQBg78wAQgEHgwcaHR0cHM6Ly9hY2NvdW50cy5leGFtcGxlLmNvbTAKBggqhkjOPQQDAgNJADBGAiEAijFliRclIlxy3x4R0rHlF6QB93KyqEXH7WjZT08Xq+cCIQCuMkHYDgr6nNt9O26ARuA+RRlCVPl74P5IEBo/5NhiUw=="
+    },
+    "tlogEntries": [
+      {
+        "logIndex": "104",
+        "logId": {
+          "keyId": "0Auj87F1YC+kMP3UrWOtSapmK8NaQ8YApiGxKBlIPHI="
+        },
+        "kindVersion": {
+          "kind": "hashedrekord",
+          "version": "0.0.1"
+        },
+        "integratedTime": "1790856180",
+        "inclusionProof": {
+          "logIndex": "4",
+          "rootHash": "CPRKZRzmKdlEPm0TKVOfnF1vN2Adoal3+rMe0LnP97o=",
+          "treeSize": "6",
+          "hashes": [
+            "n/U3thiiV++CkIxFkVrLrrbZ3qA11tk5ZPyIwZ6YyLM=",
+            "l5nzB1F+9RfCIF35tndivzR1ayAJn7ffzOdrzr0nOy4="
+          ],
+          "checkpoint": {
+            "envelope": "rekor.example.com - 1234567890\n6\nCPRKZRzmKdlEPm0TKVOfnF1vN2Adoal3+rMe0LnP97o=\n\n— rekor.example.com 0Auj8zBGAiEAifvd2aF+6QwTujnlsvGaVEMJYzd2aKs/D8ViUS14p9sCIQDbY+CocddKR05dtkv/11b16uY/w8K9uBD5K0OZAe3gMw==\n"
+          }
+        },
+        "canonicalizedBody": "eyJhcGlWZXJzaW9uIjoiMC4wLjEiLCJraW5kIjoiaGFzaGVkcmVrb3JkIiwic3BlYyI6eyJkYXRhIjp7Imhhc2giOnsiYWxnb3JpdGhtIjoic2hhMjU2IiwidmFsdWUiOiI5ZjEzNjUzZWU2NjM0N2IzYmJiMzY5YWU0ZjlmMjYxZmQwODJlYjM2ZGMyOWJiNzE3NjAxNjIwN2I5MjA1NGViIn19LCJzaWduYXR1cmUiOnsiY29udGVudCI6Ik1FVUNJQU9wMThrQU8vTm5WUVR0QzV0T3BIU09WdjJIc3dOM2tHamN3dlFrNUtrQ0FpRUE3ZHhERjd2TXhCeUo2OHpaSzE4ZWE0a2xCd1ZuOEQrZWFGaVNINU5UWmdVPSIsInB1YmxpY0tleSI6eyJjb250ZW50IjoiTFMwdExTMUNSVWRKVGlCRFJWSlVTVVpKUTBGVVJTMHRMUzB0Q2sxSlNVSjVla05EUVZoRFowRjNTVUpCWjBsQ1FYcEJTMEpuWjNGb2EycFBVRkZSUkVGcVFUTk5VbFYzUlhkWlJGWlJVVXRFUVhoNllWZGtlbVJIT1hrS1dsTTFhMXBZV1hoSWFrRmpRbWRPVmtKQlRVMUdXRTV3V2pOT01HSXpTbXhNVjJ4MVpFZFdlV0pYVm10aFYwWXdXbFJCWlVaM01IbE9ha1YzVFVSRmVBcE5ha0YzVFVSQ1lVWjNNSGxPYWtWM1RVUkZlRTFxUlhkTlJFSmhUVUZCZDFkVVFWUkNaMk54YUd0cVQxQlJTVUpDWjJkeGFHdHFUMUJSVFVKQ2QwNURDa0ZCVWpnM2RHRlRkMjluWTJKeE5HMXdjbVp3UzJwUlpHbHljVGhLY2xjM09TdElLelpzUVV3dmIzWk5XR1l4TTB0QlNuQnRRMFJyVWtSM2MzUnNXRm9LVnl0a01FMU1ORVZUV21WdmFYSlBWbmM0V1dWTloyaGxielJIYWsxSlIyZE5RVFJIUVRGVlpFUjNSVUl2ZDFGRlFYZEpTR2RFUVZSQ1owNVdTRk5WUlFwRVJFRkxRbWRuY2tKblJVWkNVV05FUVhwQlprSm5UbFpJVWtWQ1FXWTRSVVpVUVZSblVrWm9Za2RzYWxwVlFteGxSMFowWTBkNGJFeHRUblppVkVGeENrSm5iM0pDWjBWRlFWbFBMMDFCUlVKQ1FuaHZaRWhTZDJONmIzWk1Na1pxV1RJNU1XSnVVbnBNYlZZMFdWY3hkMkpIVlhWWk1qbDBUVU4zUjBOcGMwY0tRVkZSUW1jM09IZEJVV2RGU0dkM1kyRklVakJqU0UwMlRIazVhRmt5VG5aa1Z6VXdZM2sxYkdWSFJuUmpSM2hzVEcxT2RtSlVRVXRDWjJkeGFHdHFUd3BRVVZGRVFXZE9Ta0ZFUWtkQmFVVkJhV3BHYkdsU1kyeEpiSGg1TTNnMFVqQnlTR3hHTmxGQ09UTkxlWEZGV0VnM1YycGFWREE0V0hFclkwTkpVVU4xQ2sxclNGbEVaM0kyYms1ME9VOHlOa0ZTZFVFclVsSnNRMVpRYkRjMFVEVkpSVUp2THpWT2FHbFZkejA5Q2kwdExTMHRSVTVFSUVORlVsUkpSa2xEUVZSRkxTMHRMUzBLIn19fX0="
+      }
+    ]
+  },
+  "messageSignature": {
+    "messageDigest": {
+      "algorithm": "SHA2_256",
+      "digest": "nxNlPuZjR7O7s2muT58mH9CC6zbcKbtxdgFiB7kgVOs="
+    },
+    "signature": "MEUCIAOp18kAO/NnVQTtC5tOpHSOVv2HswN3kGjcwvQk5KkCAiEA7dxDF7vMxByJ68zZK18ea4klBwVn8D+eaFiSH5NTZgU="
+  }
+}"#;
+    const ROOT: &str = "-----BEGIN CERTIFICATE-----
+MIIBajCCAQ+gAwIBAgIBATAKBggqhkjOPQQDAjAqMRUwEwYDVQQKDAxzaWdzdG9y
+ZS5kZXYxETAPBgNVBAMMCHNpZ3N0b3JlMB4XDTI2MDEwMTAwMDAwMFoXDTM2MDEw
+MTAwMDAwMFowKjEVMBMGA1UECgwMc2lnc3RvcmUuZGV2MREwDwYDVQQDDAhzaWdz
+dG9yZTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABP/3rk1SaZI+7Dx67TSZWvd7
+Y0OlK7xKULiiCtQl6uObHVpzlzgS8Wd6cE+9Yi86xnmOPTPMezqVwVNBH59qEOOj
+JjAkMBIGA1UdEwEB/wQIMAYBAf8CAQEwDgYDVR0PAQH/BAQDAgEGMAoGCCqGSM49
+BAMCA0kAMEYCIQDb9+R5tewEqfYOPwj06lPSIKoD9YETgEtN3tiUYE8AogIhANEF
+vsxA60CoSWtjo2aYKaCFUzJ1LrmVS9wKJZKRzgiS
+-----END CERTIFICATE-----";
+    const INTERMEDIATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBdjCCARygAwIBAgIBAjAKBggqhkjOPQQDAjAqMRUwEwYDVQQKDAxzaWdzdG9y
+ZS5kZXYxETAPBgNVBAMMCHNpZ3N0b3JlMB4XDTI2MDEwMTAwMDAwMFoXDTM2MDEw
+MTAwMDAwMFowNzEVMBMGA1UECgwMc2lnc3RvcmUuZGV2MR4wHAYDVQQDDBVzaWdz
+dG9yZS1pbnRlcm1lZGlhdGUwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASVqrKb
+WOSzH2mnawqVaY8OZDMPNUZO5aslzMQddii4Su2KTqmEhHqEeH8NDiSrhYLJvyHA
+RNtgCHM9qhXdP0bYoyYwJDASBgNVHRMBAf8ECDAGAQH/AgEAMA4GA1UdDwEB/wQE
+AwIBBjAKBggqhkjOPQQDAgNIADBFAiEAuo62gRO7qMvOahEW/Ato9vXOiB2H6wNb
+F9kzDkUfjdkCIA46biMiYomnCrh+NgLy9OV1m7osStiPjGKV/evvbkYB
+-----END CERTIFICATE-----";
+    const REKOR_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEzuZDCO1TrgP7+9WDaNL+abCoPDwj
+MWLhLrEqpV69VbzNwQ19EWrhhqYRKf1Y8oqeX2T+QMqVpflX85xz8fbvJw==
+-----END PUBLIC KEY-----";
+    const DIGEST: &str = "9f13653ee66347b3bbb369ae4f9f261fd082eb36dc29bb7176016207b92054eb";
+
+    fn der(pem: &str) -> Vec<u8> {
+        decode_pem(pem, &["CERTIFICATE", "PUBLIC KEY"]).unwrap().1
+    }
+
+    fn verify(bundle: &str, trusted: &[&str], rekor_key: Option<&[u8]>) -> Result<SigstoreVerification, ECDSAError> {
+        let trusted = trusted.iter().map(|pem| der(pem)).collect::<Vec<_>>();
+        verify_bundle(bundle, &hex::decode(DIGEST).unwrap(), &trusted, rekor_key, None)
+    }
+
+    #[test]
+    fn test_verify_bundle() {
+        let verification = verify(BUNDLE, &[ROOT, INTERMEDIATE], Some(&der(REKOR_KEY))).unwrap();
+        assert_eq!(verification.identity().as_deref(), Some("alice@example.com"));
+        assert_eq!(verification.issuer().as_deref(), Some("https://accounts.example.com"));
+        assert_eq!(verification.log_index(), Some(104));
+        assert_eq!(verification.integrated_time(), Some(1790856180));
+        assert!(verification.inclusion_verified());
+
+        // Without the log key the proof is still checked against the tree head it names
+        assert!(!verify(BUNDLE, &[ROOT, INTERMEDIATE], None).unwrap().inclusion_verified());
+
+        // A version 0.1 bundle carrying the intermediate, and one without a log entry
+        let start = BUNDLE.find(r#""certificate""#).unwrap();
+        let end = BUNDLE.find(r#""tlogEntries""#).unwrap();
+        let leaf = parse_bundle(BUNDLE).unwrap().certificates.remove(0);
+        let chain = format!(
+            r#""x509CertificateChain": {{"certificates": [{{"rawBytes": "{}"}}, {{"rawBytes": "{}"}}]}},"#,
+            to_base64(&leaf),
+            to_base64(&der(INTERMEDIATE))
+        );
+        let bundle = format!("{}{}{}", &BUNDLE[..start], chain, &BUNDLE[end..])
+            .replace(".v0.3+json", "+json;version=0.1");
+        assert!(verify(&bundle, &[ROOT], None).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_mismatches() {
+        let trusted = [ROOT, INTERMEDIATE];
+        let digest = hex::decode(DIGEST).unwrap();
+        let anchors = [der(ROOT), der(INTERMEDIATE)];
+        assert!(matches!(
+            verify_bundle(BUNDLE, &[0; 32], &anchors, None, None),
+            Err(ECDSAError::SigstoreSignatureMismatch)
+        ));
+        // The certificate is only valid for ten minutes
+        assert!(matches!(
+            verify_bundle(BUNDLE, &digest, &anchors, None, Some(1790856601)),
+            Err(ECDSAError::CertificateExpired)
+        ));
+        assert!(matches!(verify(BUNDLE, &[ROOT], None), Err(ECDSAError::UntrustedCertificate)));
+
+        let signature = parse_bundle(BUNDLE).unwrap().signature;
+        let mut tampered = signature.clone();
+        tampered[10] ^= 1;
+        let bundle = BUNDLE.replace(
+            &format!(r#""signature": "{}""#, to_base64(&signature)),
+            &format!(r#""signature": "{}""#, to_base64(&tampered)),
+        );
+        assert!(matches!(verify(&bundle, &trusted, None), Err(ECDSAError::SigstoreSignatureMismatch)));
+
+        let body = parse_bundle(BUNDLE).unwrap().tlog_entry.unwrap().body;
+        let other = String::from_utf8(body.clone()).unwrap().replace("hashedrekord", "rekord");
+        let bundle = BUNDLE.replace(&to_base64(&body), &to_base64(other.as_bytes()));
+        assert!(matches!(verify(&bundle, &trusted, None), Err(ECDSAError::RekorEntryMismatch)));
+
+        for bundle in ["", "{}", &BUNDLE.replace("SHA2_256", "SHA2_384"), &BUNDLE.replace("sigstore.bundle", "in-toto")] {
+            assert!(matches!(verify(bundle, &trusted, None), Err(ECDSAError::InvalidSigstoreBundle)));
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof() {
+        let trusted = [ROOT, INTERMEDIATE];
+        // Another leaf index, another tree size and another log key
+        for (from, to) in [(r#""logIndex": "4""#, r#""logIndex": "5""#), (r#""treeSize": "6""#, r#""treeSize": "7""#)] {
+            let bundle = BUNDLE.replace(from, to);
+            assert!(matches!(verify(&bundle, &trusted, None), Err(ECDSAError::InclusionProofMismatch)), "{to}");
+        }
+        let root = der(ROOT);
+        let other_key = parse_certificate(&root).unwrap().spki;
+        assert!(matches!(verify(BUNDLE, &trusted, Some(other_key)), Err(ECDSAError::InclusionProofMismatch)));
+
+        // The log key demands a proof
+        let start = BUNDLE.find(r#""inclusionProof""#).unwrap();
+        let end = BUNDLE.find(r#""canonicalizedBody""#).unwrap();
+        let bundle = format!("{}{}", &BUNDLE[..start], &BUNDLE[end..]);
+        assert!(!verify(&bundle, &trusted, None).unwrap().inclusion_verified());
+        assert!(matches!(verify(&bundle, &trusted, Some(&der(REKOR_KEY))), Err(ECDSAError::InclusionProofMismatch)));
+    }
+
+    #[test]
+    fn test_root_from_inclusion_proof() {
+        // A single leaf is its own tree head
+        let leaf: [u8; 32] = Sha256::new().chain_update([0]).chain_update(b"leaf").finalize().into();
+        assert_eq!(root_from_inclusion_proof(0, 1, b"leaf", &[]), Some(leaf));
+        assert_eq!(root_from_inclusion_proof(0, 1, b"leaf", &[leaf.to_vec()]), None);
+        assert_eq!(root_from_inclusion_proof(1, 1, b"leaf", &[]), None);
+
+        // Two leaves hash together, in order
+        let other: [u8; 32] = Sha256::new().chain_update([0]).chain_update(b"other").finalize().into();
+        assert_eq!(root_from_inclusion_proof(0, 2, b"leaf", &[other.to_vec()]), Some(hash_children(&leaf, &other)));
+        assert_eq!(root_from_inclusion_proof(1, 2, b"other", &[leaf.to_vec()]), Some(hash_children(&leaf, &other)));
+        assert_eq!(root_from_inclusion_proof(0, 2, b"leaf", &[]), None);
+    }
+}
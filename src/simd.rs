@@ -0,0 +1,53 @@
+//! WebAssembly SIMD (`simd128`) support and detection.
+//!
+//! SIMD in wasm is fixed when the module is compiled: a module that uses it fails to load in
+//! an engine without it, so it cannot switch code paths at runtime. Instead, applications
+//! ship a baseline build and a build with `RUSTFLAGS="-C target-feature=+simd128"`, and pick
+//! one using `simd_supported`, which any build can answer. `simd_enabled` reports which kind
+//! of build is running.
+
+use wasm_bindgen::prelude::*;
+
+/// The smallest module using a SIMD instruction (`i8x16.splat` and `i8x16.popcnt`), as
+/// probed by wasm-feature-detect; it only validates in engines with SIMD.
+#[cfg(target_arch = "wasm32")]
+const SIMD_PROBE: [u8; 31] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7b, 0x03,
+    0x02, 0x01, 0x00, 0x0a, 0x0a, 0x01, 0x08, 0x00, 0x41, 0x00, 0xfd, 0x0f, 0xfd, 0x62, 0x0b,
+];
+
+/// Whether the JavaScript engine can run WebAssembly SIMD, whether or not this build uses it.
+///
+/// # Returns:
+/// * `true` if a module using `simd128` instructions would load; always `false` outside wasm.
+#[wasm_bindgen]
+pub fn simd_supported() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::WebAssembly::validate(&js_sys::Uint8Array::from(&SIMD_PROBE[..]).into()).unwrap_or(false)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        false
+    }
+}
+
+/// Whether this build was compiled for WebAssembly SIMD.
+///
+/// # Returns:
+/// * `true` for builds with the `simd128` target feature.
+#[wasm_bindgen]
+pub fn simd_enabled() -> bool {
+    cfg!(all(target_arch = "wasm32", target_feature = "simd128"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detection_outside_wasm() {
+        assert!(!simd_supported());
+        assert!(!simd_enabled());
+    }
+}
@@ -0,0 +1,376 @@
+//! Sign-In with Ethereum (EIP-4361): parsing the structured login message, checking its
+//! domain, nonce and validity window, and verifying the `personal_sign` signature.
+//!
+//! Only externally owned accounts are supported; contract wallets that sign through
+//! EIP-1271 need an on-chain call and cannot be checked here.
+
+use wasm_bindgen::prelude::*;
+use js_sys::Array;
+
+use crate::ethereum::{address_bytes, parse_address, personal_message_hash, validate_eip55};
+use crate::signature::split_signature;
+use crate::time::{now_millis, parse_rfc3339};
+use crate::{recover_from_digest, ECDSAError};
+
+const PREAMBLE_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+/// A parsed EIP-4361 message.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct SiweMessage {
+    scheme: Option<String>,
+    domain: String,
+    address: String,
+    statement: Option<String>,
+    uri: String,
+    version: String,
+    chain_id: u64,
+    nonce: String,
+    issued_at: String,
+    expiration_time: Option<String>,
+    not_before: Option<String>,
+    request_id: Option<String>,
+    resources: Vec<String>,
+}
+
+/// Walks the message lines, consuming `Tag: value` fields in their fixed order.
+struct Lines<'a> {
+    lines: std::iter::Peekable<std::str::Split<'a, char>>,
+}
+
+impl<'a> Lines<'a> {
+    fn next(&mut self) -> Result<&'a str, ECDSAError> {
+        self.lines.next().ok_or(ECDSAError::InvalidSiweMessage)
+    }
+
+    fn field(&mut self, tag: &str) -> Result<&'a str, ECDSAError> {
+        self.optional_field(tag)?.ok_or(ECDSAError::InvalidSiweMessage)
+    }
+
+    fn optional_field(&mut self, tag: &str) -> Result<Option<&'a str>, ECDSAError> {
+        match self.lines.peek().and_then(|line| line.strip_prefix(tag)?.strip_prefix(": ")) {
+            Some("") => Err(ECDSAError::InvalidSiweMessage),
+            Some(value) => {
+                self.lines.next();
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn timestamp(value: &str) -> Result<String, ECDSAError> {
+    parse_rfc3339(value).ok_or(ECDSAError::InvalidSiweMessage)?;
+    Ok(value.to_string())
+}
+
+#[wasm_bindgen]
+impl SiweMessage {
+    /// Parses an EIP-4361 message.
+    ///
+    /// # Errors:
+    /// * Returns an error if a required field is missing, fields are out of order, the
+    ///   address lacks an EIP-55 checksum, the version is not 1, the nonce is shorter than
+    ///   8 alphanumeric characters or a timestamp is not RFC 3339.
+    pub fn parse(message: &str) -> Result<SiweMessage, ECDSAError> {
+        let mut lines = Lines { lines: message.split('\n').peekable() };
+
+        let origin = lines.next()?.strip_suffix(PREAMBLE_SUFFIX).ok_or(ECDSAError::InvalidSiweMessage)?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain),
+            None => (None, origin),
+        };
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err(ECDSAError::InvalidSiweMessage);
+        }
+
+        let address = lines.next()?;
+        if !validate_eip55(address) || !lines.next()?.is_empty() {
+            return Err(ECDSAError::InvalidSiweMessage);
+        }
+        let statement = match lines.next()? {
+            "" => None,
+            statement => {
+                if !lines.next()?.is_empty() {
+                    return Err(ECDSAError::InvalidSiweMessage);
+                }
+                Some(statement.to_string())
+            }
+        };
+
+        let uri = lines.field("URI")?.to_string();
+        let version = lines.field("Version")?.to_string();
+        let chain_id = lines.field("Chain ID")?.parse().map_err(|_| ECDSAError::InvalidSiweMessage)?;
+        let nonce = lines.field("Nonce")?.to_string();
+        let issued_at = timestamp(lines.field("Issued At")?)?;
+        let expiration_time = lines.optional_field("Expiration Time")?.map(timestamp).transpose()?;
+        let not_before = lines.optional_field("Not Before")?.map(timestamp).transpose()?;
+        let request_id = lines.optional_field("Request ID")?.map(str::to_string);
+
+        let mut resources = Vec::new();
+        if lines.lines.peek() == Some(&"Resources:") {
+            lines.next()?;
+            while let Some(resource) = lines.lines.peek().and_then(|line| line.strip_prefix("- ")) {
+                resources.push(resource.to_string());
+                lines.next()?;
+            }
+        }
+        if lines.lines.next().is_some() {
+            return Err(ECDSAError::InvalidSiweMessage);
+        }
+        if version != "1" || nonce.len() < 8 || !nonce.bytes().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(ECDSAError::InvalidSiweMessage);
+        }
+
+        Ok(SiweMessage {
+            scheme,
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+
+    /// The URI scheme preceding the domain, if the message includes one.
+    #[wasm_bindgen(getter)]
+    pub fn scheme(&self) -> Option<String> {
+        self.scheme.clone()
+    }
+
+    /// The domain requesting the sign-in.
+    #[wasm_bindgen(getter)]
+    pub fn domain(&self) -> String {
+        self.domain.clone()
+    }
+
+    /// The EIP-55 checksummed address of the account signing in.
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    /// The human-readable statement, if any.
+    #[wasm_bindgen(getter)]
+    pub fn statement(&self) -> Option<String> {
+        self.statement.clone()
+    }
+
+    /// The URI of the resource the sign-in is for.
+    #[wasm_bindgen(getter)]
+    pub fn uri(&self) -> String {
+        self.uri.clone()
+    }
+
+    /// The message version; always `1`.
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> String {
+        self.version.clone()
+    }
+
+    /// The EIP-155 chain id the session is bound to.
+    #[wasm_bindgen(getter)]
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// The server-issued nonce preventing replay.
+    #[wasm_bindgen(getter)]
+    pub fn nonce(&self) -> String {
+        self.nonce.clone()
+    }
+
+    /// When the message was created, as an RFC 3339 timestamp.
+    #[wasm_bindgen(getter)]
+    pub fn issued_at(&self) -> String {
+        self.issued_at.clone()
+    }
+
+    /// When the message stops being valid, if set.
+    #[wasm_bindgen(getter)]
+    pub fn expiration_time(&self) -> Option<String> {
+        self.expiration_time.clone()
+    }
+
+    /// When the message becomes valid, if set.
+    #[wasm_bindgen(getter)]
+    pub fn not_before(&self) -> Option<String> {
+        self.not_before.clone()
+    }
+
+    /// The application's request identifier, if set.
+    #[wasm_bindgen(getter)]
+    pub fn request_id(&self) -> Option<String> {
+        self.request_id.clone()
+    }
+
+    /// The resource URIs listed in the message.
+    #[wasm_bindgen(getter)]
+    pub fn resources(&self) -> Array {
+        self.resources.iter().map(|resource| JsValue::from_str(resource)).collect()
+    }
+}
+
+impl SiweMessage {
+    /// Checks the validity window against `now` (milliseconds since the epoch).
+    fn check_time(&self, now: i64) -> Result<(), ECDSAError> {
+        let parse = |timestamp: &Option<String>| timestamp.as_deref().and_then(parse_rfc3339);
+        if parse(&self.expiration_time).is_some_and(|expiration| now >= expiration) {
+            return Err(ECDSAError::SiweExpired);
+        }
+        if parse(&self.not_before).is_some_and(|not_before| now < not_before) {
+            return Err(ECDSAError::SiweNotYetValid);
+        }
+        Ok(())
+    }
+}
+
+/// Verifies a Sign-In with Ethereum message and its `personal_sign` signature.
+///
+/// # Arguments:
+/// * `message` - The full EIP-4361 message text that was signed.
+/// * `signature` - The 65-byte (r || s || v) or 64-byte EIP-2098 signature, provided as a hex string.
+/// * `domain` - If given, the message's domain must equal it (the server's own host).
+/// * `nonce` - If given, the message's nonce must equal it (the nonce the server issued).
+/// * `time` - The RFC 3339 time to check the validity window against. Defaults to now.
+///
+/// # Returns:
+/// * The parsed message, once every check has passed.
+///
+/// # Errors:
+/// * Returns a descriptive error if the message is malformed, the domain or nonce does not
+///   match, the message is expired or not yet valid, or the signature is not from its address.
+#[wasm_bindgen]
+pub fn verify_siwe(
+    message: &str,
+    signature: &str,
+    domain: Option<String>,
+    nonce: Option<String>,
+    time: Option<String>,
+) -> Result<SiweMessage, ECDSAError> {
+    let parsed = SiweMessage::parse(message)?;
+    if domain.is_some_and(|domain| domain != parsed.domain) {
+        return Err(ECDSAError::SiweDomainMismatch);
+    }
+    if nonce.is_some_and(|nonce| nonce != parsed.nonce) {
+        return Err(ECDSAError::SiweNonceMismatch);
+    }
+    let now = match time {
+        Some(time) => parse_rfc3339(&time).ok_or(ECDSAError::InvalidSiweMessage)?,
+        None => now_millis(),
+    };
+    parsed.check_time(now)?;
+
+    let signature_bytes = hex::decode(signature.trim_start_matches("0x")).map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    let (sig, recovery_id) = split_signature(&signature_bytes)?;
+    let digest = personal_message_hash(message.as_bytes());
+    let signer = recover_from_digest(&digest, &sig, recovery_id).map_err(|_| ECDSAError::SiweSignatureMismatch)?;
+    if address_bytes(&signer) != parse_address(&parsed.address)? {
+        return Err(ECDSAError::SiweSignatureMismatch);
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+
+    const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0x2c7536E3605D9C16a7a3D7b1898e529396a65c23
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24.000+02:00
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    fn sign(message: &str) -> String {
+        let key = SigningKey::from_bytes(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+        let (sig, recovery_id) = sign_digest(&key, &personal_message_hash(message.as_bytes()), &[]).unwrap();
+        let mut bytes = sig.as_ref().to_vec();
+        bytes.push(recovery_id + 27);
+        hex::encode(bytes)
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let message = SiweMessage::parse(MESSAGE).unwrap();
+        assert_eq!(message.scheme(), None);
+        assert_eq!(message.domain(), "service.org");
+        assert_eq!(message.statement().unwrap(), "I accept the ServiceOrg Terms of Service: https://service.org/tos");
+        assert_eq!(message.chain_id(), 1);
+        assert_eq!(message.nonce(), "32891756");
+        assert_eq!(message.not_before(), None);
+        assert_eq!(message.resources.len(), 2);
+
+        let minimal = "https://example.com wants you to sign in with your Ethereum account:
+0x2c7536E3605D9C16a7a3D7b1898e529396a65c23
+
+
+URI: https://example.com
+Version: 1
+Chain ID: 10
+Nonce: abcdefgh
+Issued At: 2024-02-29T00:00:00Z";
+        let message = SiweMessage::parse(minimal).unwrap();
+        assert_eq!(message.scheme().unwrap(), "https");
+        assert_eq!(message.domain(), "example.com");
+        assert_eq!(message.statement(), None);
+    }
+
+    #[test]
+    fn test_rejects_malformed_message() {
+        for (from, to) in [
+            ("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23", "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"),
+            ("Version: 1", "Version: 2"),
+            ("Nonce: 32891756", "Nonce: 1234"),
+            ("Issued At: 2021-09-30T16:25:24Z", "Issued At: 2021-09-31T16:25:24Z"),
+            ("Chain ID: 1\nNonce: 32891756", "Nonce: 32891756\nChain ID: 1"),
+            (" wants you", " would like you"),
+        ] {
+            assert!(SiweMessage::parse(&MESSAGE.replace(from, to)).is_err(), "{to}");
+        }
+        assert!(SiweMessage::parse(&format!("{MESSAGE}\n")).is_err());
+    }
+
+    #[test]
+    fn test_verify_siwe() {
+        let signature = sign(MESSAGE);
+        let at = |time: &str| Some(time.to_string());
+        let verify = |domain: &str, nonce: &str, time: &str| verify_siwe(MESSAGE, &signature, at(domain), at(nonce), at(time));
+
+        let message = verify("service.org", "32891756", "2021-10-01T00:00:00Z").unwrap();
+        assert_eq!(message.address(), "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+
+        assert!(matches!(verify("evil.org", "32891756", "2021-10-01T00:00:00Z"), Err(ECDSAError::SiweDomainMismatch)));
+        assert!(matches!(verify("service.org", "00000000", "2021-10-01T00:00:00Z"), Err(ECDSAError::SiweNonceMismatch)));
+        // Expiration is 14:25:24Z once the +02:00 offset is applied
+        assert!(matches!(verify("service.org", "32891756", "2021-10-01T14:25:24Z"), Err(ECDSAError::SiweExpired)));
+
+        let not_before = MESSAGE.replace("Resources:", "Not Before: 2021-09-30T20:00:00Z\nResources:");
+        let result = verify_siwe(&not_before, &sign(&not_before), None, None, at("2021-09-30T19:00:00Z"));
+        assert!(matches!(result, Err(ECDSAError::SiweNotYetValid)));
+
+        let other = MESSAGE.replace("32891756", "32891757");
+        assert!(matches!(
+            verify_siwe(&other, &signature, None, None, at("2021-10-01T00:00:00Z")),
+            Err(ECDSAError::SiweSignatureMismatch)
+        ));
+    }
+}
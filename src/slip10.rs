@@ -0,0 +1,141 @@
+//! SLIP-0010 hierarchical deterministic keys for Ed25519.
+//!
+//! Ed25519 only supports hardened derivation: every child is
+//! `HMAC-SHA512(chain_code, 0x00 || private_key || index)`, so there are no public parents.
+
+use wasm_bindgen::prelude::*;
+
+use crate::bip32::{parse_path, HARDENED_OFFSET};
+use crate::ed25519::public_key_from_seed;
+use crate::kdf::hmac_sha512;
+use crate::ECDSAError;
+
+/// A SLIP-0010 Ed25519 private key with its chain code.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Ed25519ExtendedKey {
+    private_key: [u8; 32],
+    chain_code: [u8; 32],
+    depth: u8,
+    child_number: u32,
+}
+
+impl Ed25519ExtendedKey {
+    fn from_hmac(i: [u8; 64], depth: u8, child_number: u32) -> Ed25519ExtendedKey {
+        Ed25519ExtendedKey {
+            private_key: i[..32].try_into().unwrap(),
+            chain_code: i[32..].try_into().unwrap(),
+            depth,
+            child_number,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Ed25519ExtendedKey {
+    /// Derives the master key from a seed, e.g. `Mnemonic.to_seed()`.
+    ///
+    /// # Arguments:
+    /// * `seed` - The 16 to 64 byte seed.
+    ///
+    /// # Errors:
+    /// * Returns an error if the seed length is out of range.
+    pub fn from_seed(seed: &[u8]) -> Result<Ed25519ExtendedKey, ECDSAError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(ECDSAError::InvalidExtendedKey);
+        }
+        Ok(Ed25519ExtendedKey::from_hmac(hmac_sha512(b"ed25519 seed", &[seed]), 0, 0))
+    }
+
+    /// Derives a direct hardened child.
+    ///
+    /// # Arguments:
+    /// * `index` - The child index including the 2^31 hardened offset.
+    ///
+    /// # Errors:
+    /// * Returns an error if `index` is not hardened.
+    pub fn derive_child(&self, index: u32) -> Result<Ed25519ExtendedKey, ECDSAError> {
+        if index < HARDENED_OFFSET {
+            return Err(ECDSAError::InvalidDerivationPath);
+        }
+        let depth = self.depth.checked_add(1).ok_or(ECDSAError::InvalidDerivationPath)?;
+        let i = hmac_sha512(&self.chain_code, &[&[0], &self.private_key, &index.to_be_bytes()]);
+        Ok(Ed25519ExtendedKey::from_hmac(i, depth, index))
+    }
+
+    /// Derives a descendant along a path such as `m/44'/501'/0'/0'` (Solana).
+    ///
+    /// # Arguments:
+    /// * `path` - Slash-separated indices, all hardened with `'` or `h`. Paths starting
+    ///   with `m` are only accepted on a master key; others are relative to this key.
+    ///
+    /// # Errors:
+    /// * Returns an error if the path is malformed or has a non-hardened component.
+    pub fn derive_path(&self, path: &str) -> Result<Ed25519ExtendedKey, ECDSAError> {
+        let (absolute, indices) = parse_path(path)?;
+        if absolute && self.depth != 0 {
+            return Err(ECDSAError::InvalidDerivationPath);
+        }
+        indices.into_iter().try_fold(self.clone(), |key, index| key.derive_child(index))
+    }
+
+    /// The number of derivation steps from the master key.
+    #[wasm_bindgen(getter)]
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The index this key was derived with, including the 2^31 hardened offset.
+    #[wasm_bindgen(getter)]
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// The 32-byte chain code, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn chain_code(&self) -> String {
+        hex::encode(self.chain_code)
+    }
+
+    /// Returns the 32-byte Ed25519 private key seed, as a hex string.
+    pub fn private_key(&self) -> String {
+        hex::encode(self.private_key)
+    }
+
+    /// Returns the 32-byte Ed25519 public key, as a hex string, for use with `Ed25519Verifier`.
+    pub fn public_key(&self) -> String {
+        hex::encode(public_key_from_seed(&self.private_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slip10_vector_1() {
+        let master = Ed25519ExtendedKey::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+        assert_eq!(master.chain_code(), "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb");
+        assert_eq!(master.private_key(), "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7");
+        assert_eq!(master.public_key(), "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed");
+
+        let child = master.derive_path("m/0'").unwrap();
+        assert_eq!(child.chain_code(), "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69");
+        assert_eq!(child.private_key(), "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+        assert_eq!(child.public_key(), "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c");
+
+        let leaf = master.derive_path("m/0H/1H/2H/2H/1000000000H").unwrap();
+        assert_eq!(leaf.depth(), 5);
+        assert_eq!(leaf.chain_code(), "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230");
+        assert_eq!(leaf.private_key(), "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793");
+        assert_eq!(leaf.public_key(), "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a");
+    }
+
+    #[test]
+    fn test_rejects_non_hardened() {
+        let master = Ed25519ExtendedKey::from_seed(&[1; 32]).unwrap();
+        assert!(matches!(master.derive_child(0), Err(ECDSAError::InvalidDerivationPath)));
+        assert!(matches!(master.derive_path("m/44'/501'/0"), Err(ECDSAError::InvalidDerivationPath)));
+        assert!(Ed25519ExtendedKey::from_seed(&[1; 15]).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Solana message signature verification.
+//!
+//! Wallets sign `signMessage` payloads as raw bytes with Ed25519. The CLI's
+//! `sign-offchain-message` instead signs a version 0 off-chain message: the signing domain
+//! `\xffsolana offchain`, a version byte, a format byte, a little-endian `u16` length and
+//! the message, which cannot be mistaken for a transaction.
+
+use wasm_bindgen::prelude::*;
+
+use crate::ed25519::{verify_ed25519, PUBLIC_KEY_LENGTH};
+use crate::encoding::from_base58;
+use crate::ECDSAError;
+
+const SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+/// Longest message a Ledger device will display, keeping the envelope within a packet.
+const MAX_LEDGER_LENGTH: usize = 1212;
+const MAX_LENGTH: usize = u16::MAX as usize - 20;
+
+/// Wraps `message` in a version 0 off-chain message envelope, choosing the format byte
+/// (restricted ASCII, limited UTF-8 or extended UTF-8) the way the Solana SDK does.
+fn offchain_message(message: &[u8]) -> Option<Vec<u8>> {
+    let format = if message.len() > MAX_LENGTH || std::str::from_utf8(message).is_err() {
+        return None;
+    } else if message.len() > MAX_LEDGER_LENGTH {
+        2
+    } else if message.iter().all(|b| (0x20..=0x7e).contains(b)) {
+        0
+    } else {
+        1
+    };
+
+    let mut envelope = SIGNING_DOMAIN.to_vec();
+    envelope.extend_from_slice(&[0, format]);
+    envelope.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    envelope.extend_from_slice(message);
+    Some(envelope)
+}
+
+/// Verifies a Solana message signature.
+///
+/// The signature is accepted over either the raw message, as produced by wallet
+/// `signMessage`, or its version 0 off-chain message envelope, as produced by
+/// `solana sign-offchain-message`.
+///
+/// # Arguments:
+/// * `message_bytes` - The message that was signed (`Uint8Array` in JS).
+/// * `signature` - The 64-byte Ed25519 signature (`Uint8Array` in JS).
+/// * `pubkey_base58` - The signer's account address, a base58-encoded 32-byte public key.
+///
+/// # Returns:
+/// * A boolean indicating whether the signature is valid or not.
+///
+/// # Errors:
+/// * Returns an error if the address is not base58 for a valid public key, or the signature
+///   is not 64 bytes.
+#[wasm_bindgen]
+pub fn verify_solana_message(message_bytes: &[u8], signature: &[u8], pubkey_base58: &str) -> Result<bool, ECDSAError> {
+    let public_key = from_base58(pubkey_base58).ok_or(ECDSAError::InvalidPublicKey)?;
+    if public_key.len() != PUBLIC_KEY_LENGTH {
+        return Err(ECDSAError::InvalidPublicKey);
+    }
+    if verify_ed25519(&public_key, message_bytes, signature)? {
+        return Ok(true);
+    }
+    match offchain_message(message_bytes) {
+        Some(envelope) => verify_ed25519(&public_key, &envelope, signature),
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519::{public_key_from_seed, sign_ed25519};
+    use crate::encoding::to_base58;
+
+    const SEED: [u8; 32] = [0x42; 32];
+
+    #[test]
+    fn test_offchain_envelope() {
+        let envelope = offchain_message(b"hello").unwrap();
+        assert_eq!(hex::encode(&envelope[..16]), "ff736f6c616e61206f6666636861696e");
+        assert_eq!(envelope[16..20], [0, 0, 5, 0]);
+        assert_eq!(offchain_message("héllo".as_bytes()).unwrap()[17], 1);
+        assert_eq!(offchain_message(&[b'a'; 2000]).unwrap()[17], 2);
+        assert!(offchain_message(&[0xff]).is_none());
+    }
+
+    #[test]
+    fn test_verify_solana_message() {
+        let address = to_base58(&public_key_from_seed(&SEED));
+        let message = b"Sign in to example.com";
+
+        let raw = sign_ed25519(&SEED, message);
+        assert!(verify_solana_message(message, &raw, &address).unwrap());
+        let offchain = sign_ed25519(&SEED, &offchain_message(message).unwrap());
+        assert!(verify_solana_message(message, &offchain, &address).unwrap());
+        assert!(!verify_solana_message(b"Sign in to evil.com", &raw, &address).unwrap());
+
+        let other = to_base58(&public_key_from_seed(&[0x43; 32]));
+        assert!(!verify_solana_message(message, &raw, &other).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let address = to_base58(&public_key_from_seed(&SEED));
+        let signature = sign_ed25519(&SEED, b"gm");
+        assert!(matches!(verify_solana_message(b"gm", &signature, "0OIl"), Err(ECDSAError::InvalidPublicKey)));
+        assert!(matches!(verify_solana_message(b"gm", &signature, &to_base58(&[1; 31])), Err(ECDSAError::InvalidPublicKey)));
+        assert!(verify_solana_message(b"gm", &signature[..63], &address).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! SS58, the Base58 address format of Substrate chains such as Polkadot and Kusama.
+//!
+//! An address is `prefix || payload || checksum`, where the network prefix takes one byte
+//! below 64 and two bytes up to 16383, and the checksum is the start of
+//! `BLAKE2b-512("SS58PRE" || prefix || payload)`.
+
+use wasm_bindgen::prelude::*;
+use blake2::{Blake2b512, Digest};
+
+use crate::encoding::{from_base58, to_base58};
+use crate::hash::Blake2b256;
+use crate::keys::parse_public_key;
+use crate::ECDSAError;
+
+const CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+const MAX_PREFIX: u16 = 16383;
+/// Prefixes 46 and 47 are reserved and never identify a network.
+const RESERVED_PREFIXES: [u16; 2] = [46, 47];
+
+/// Checksum length for a payload length; `None` for lengths SS58 does not define.
+fn checksum_length(payload_length: usize) -> Option<usize> {
+    match payload_length {
+        1 | 2 | 4 | 8 => Some(1),
+        32 | 33 => Some(2),
+        _ => None,
+    }
+}
+
+fn encode_prefix(prefix: u16) -> Vec<u8> {
+    if prefix < 64 {
+        vec![prefix as u8]
+    } else {
+        vec![((prefix & 0xfc) >> 2) as u8 | 0x40, (prefix >> 8) as u8 | ((prefix & 0x03) << 6) as u8]
+    }
+}
+
+fn checksum(data: &[u8]) -> Vec<u8> {
+    Blake2b512::new().chain_update(CHECKSUM_PREFIX).chain_update(data).finalize().to_vec()
+}
+
+/// A decoded SS58 address: the network prefix and the account id or public key.
+#[wasm_bindgen]
+pub struct Ss58Address {
+    prefix: u16,
+    payload: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Ss58Address {
+    /// The network prefix, e.g. 0 for Polkadot, 2 for Kusama or 42 for generic Substrate.
+    #[wasm_bindgen(getter)]
+    pub fn prefix(&self) -> u16 {
+        self.prefix
+    }
+
+    /// Returns the account id or public key (`Uint8Array` in JS).
+    pub fn payload(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
+
+    /// Checks whether the address belongs to a public key.
+    ///
+    /// A 32-byte Ed25519 or Sr25519 key is its own account id. A secp256k1 key (33 or 65
+    /// bytes) matches either its compressed form or, as Substrate derives ECDSA account
+    /// ids, the BLAKE2b-256 hash of its compressed form.
+    ///
+    /// # Arguments:
+    /// * `public_key` - The public key (`Uint8Array` in JS).
+    pub fn matches_public_key(&self, public_key: &[u8]) -> bool {
+        if public_key.len() == 32 {
+            return self.payload == public_key;
+        }
+        let compressed = match parse_public_key(public_key) {
+            Ok(key) => key.to_bytes().to_vec(),
+            Err(_) => return false,
+        };
+        self.payload == compressed || self.payload[..] == Blake2b256::digest(&compressed)[..]
+    }
+}
+
+/// Encodes a public key or account id as an SS58 address.
+///
+/// # Arguments:
+/// * `payload` - The 32-byte account id or public key, or a 33-byte compressed secp256k1
+///   key (`Uint8Array` in JS).
+/// * `prefix` - The network prefix, below 16384.
+///
+/// # Returns:
+/// * The Base58 address, e.g. starting with `1` on Polkadot or `5` for generic Substrate.
+///
+/// # Errors:
+/// * Returns an error if the payload length is not one SS58 defines, or the prefix is
+///   reserved or out of range.
+#[wasm_bindgen]
+pub fn ss58_encode(payload: &[u8], prefix: u16) -> Result<String, ECDSAError> {
+    let checksum_length = checksum_length(payload.len()).ok_or(ECDSAError::InvalidPublicKey)?;
+    if prefix > MAX_PREFIX || RESERVED_PREFIXES.contains(&prefix) {
+        return Err(ECDSAError::InvalidAddress);
+    }
+
+    let mut data = encode_prefix(prefix);
+    data.extend_from_slice(payload);
+    let checksum = checksum(&data);
+    data.extend_from_slice(&checksum[..checksum_length]);
+    Ok(to_base58(&data))
+}
+
+/// Decodes an SS58 address.
+///
+/// # Errors:
+/// * Returns an error if the text is not Base58, the prefix is reserved or malformed, the
+///   payload length is not one SS58 defines, or the checksum does not match.
+#[wasm_bindgen]
+pub fn ss58_decode(address: &str) -> Result<Ss58Address, ECDSAError> {
+    let data = from_base58(address).ok_or(ECDSAError::InvalidAddress)?;
+    let (prefix, prefix_length) = match data.first() {
+        Some(&first) if first < 64 => (first as u16, 1),
+        Some(&first) if first < 128 => {
+            let second = *data.get(1).ok_or(ECDSAError::InvalidAddress)? as u16;
+            let prefix = ((first as u16 & 0x3f) << 2) | (second >> 6) | ((second & 0x3f) << 8);
+            (prefix, 2)
+        }
+        _ => return Err(ECDSAError::InvalidAddress),
+    };
+    if RESERVED_PREFIXES.contains(&prefix) || (prefix_length == 2 && prefix < 64) {
+        return Err(ECDSAError::InvalidAddress);
+    }
+
+    // The checksum length follows from the payload length, which is only known once it is removed
+    let rest = data.len() - prefix_length;
+    let (payload_length, checksum_length) = [1, 2]
+        .iter()
+        .find_map(|&length| (checksum_length(rest.checked_sub(length)?) == Some(length)).then_some((rest - length, length)))
+        .ok_or(ECDSAError::InvalidAddress)?;
+
+    let (body, expected) = data.split_at(prefix_length + payload_length);
+    if checksum(body)[..checksum_length] != *expected {
+        return Err(ECDSAError::InvalidAddress);
+    }
+    Ok(Ss58Address { prefix, payload: body[prefix_length..].to_vec() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+
+    // The Sr25519 public key of the `//Alice` development account
+    const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    #[test]
+    fn test_alice_addresses() {
+        let alice = hex::decode(ALICE).unwrap();
+        for (prefix, address) in [
+            (42, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            (0, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+        ] {
+            assert_eq!(ss58_encode(&alice, prefix).unwrap(), address);
+            let decoded = ss58_decode(address).unwrap();
+            assert_eq!(decoded.prefix(), prefix);
+            assert_eq!(hex::encode(decoded.payload()), ALICE);
+            assert!(decoded.matches_public_key(&alice));
+        }
+    }
+
+    #[test]
+    fn test_two_byte_prefixes() {
+        let payload = [7u8; 32];
+        for prefix in [64, 255, 1284, MAX_PREFIX] {
+            let decoded = ss58_decode(&ss58_encode(&payload, prefix).unwrap()).unwrap();
+            assert_eq!(decoded.prefix(), prefix);
+            assert_eq!(decoded.payload(), payload);
+        }
+        assert!(ss58_encode(&payload, MAX_PREFIX + 1).is_err());
+        assert!(ss58_encode(&payload, 46).is_err());
+    }
+
+    #[test]
+    fn test_ecdsa_account() {
+        let key = SigningKey::from_bytes(&[0x31; 32]).unwrap();
+        let compressed = key.verifying_key().to_bytes();
+        let account = ss58_decode(&ss58_encode(&Blake2b256::digest(compressed), 0).unwrap()).unwrap();
+        assert!(account.matches_public_key(&compressed));
+        assert!(!account.matches_public_key(&hex::decode(ALICE).unwrap()));
+
+        let raw = ss58_decode(&ss58_encode(&compressed, 42).unwrap()).unwrap();
+        assert_eq!(raw.payload(), compressed.to_vec());
+        assert!(raw.matches_public_key(&compressed));
+    }
+
+    #[test]
+    fn test_rejects_bad_addresses() {
+        let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        assert!(ss58_decode(&address.replace('Y', "Z")).is_err());
+        assert!(ss58_decode(&address[..40]).is_err());
+        assert!(ss58_decode("0OIl").is_err());
+        assert!(ss58_decode("").is_err());
+        assert!(ss58_encode(&[0; 20], 0).is_err());
+    }
+}
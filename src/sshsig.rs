@@ -0,0 +1,479 @@
+//! OpenSSH signatures (`ssh-keygen -Y sign`), following OpenSSH's PROTOCOL.sshsig.
+//!
+//! An `SSH SIGNATURE` PEM block wraps `"SSHSIG" || version || publickey || namespace ||
+//! reserved || hash_algorithm || signature`. The key signs `"SSHSIG" || namespace ||
+//! reserved || hash_algorithm || H(message)`, so a signature made for one namespace (such as
+//! `git` or `file`) cannot be replayed in another.
+
+use wasm_bindgen::prelude::*;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::encoding::{from_base64, to_base64};
+use crate::keys::asn1::KeyAlgorithm;
+use crate::pem::decode_pem;
+use crate::time::{days_from_civil, now_millis};
+use crate::ECDSAError;
+
+const MAGIC: &[u8] = b"SSHSIG";
+const VERSION: u32 = 1;
+
+const KEY_TYPE_ED25519: &str = "ssh-ed25519";
+const KEY_TYPE_ECDSA_P256: &str = "ecdsa-sha2-nistp256";
+
+/// A reader over the SSH wire encoding (RFC 4251 section 5).
+pub(crate) struct SshReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SshReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> SshReader<'a> {
+        SshReader { bytes }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], ECDSAError> {
+        if self.bytes.len() < count {
+            return Err(ECDSAError::InvalidSshSignature);
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, ECDSAError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Reads a length-prefixed `string`.
+    pub(crate) fn string(&mut self) -> Result<&'a [u8], ECDSAError> {
+        let length = self.u32()? as usize;
+        self.take(length)
+    }
+
+    pub(crate) fn text(&mut self) -> Result<&'a str, ECDSAError> {
+        std::str::from_utf8(self.string()?).map_err(|_| ECDSAError::InvalidSshSignature)
+    }
+
+    pub(crate) fn finish(&self) -> Result<(), ECDSAError> {
+        match self.bytes.is_empty() {
+            true => Ok(()),
+            false => Err(ECDSAError::InvalidSshSignature),
+        }
+    }
+}
+
+/// Appends a length-prefixed `string`.
+fn write_string(output: &mut Vec<u8>, bytes: &[u8]) {
+    output.extend((bytes.len() as u32).to_be_bytes());
+    output.extend_from_slice(bytes);
+}
+
+/// An SSH public key, kept with its wire encoding for comparisons and fingerprints.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SshPublicKey {
+    pub(crate) algorithm: KeyAlgorithm,
+    /// A SEC1 point, or the 32 raw bytes of an Ed25519 key
+    pub(crate) public_key: Vec<u8>,
+    pub(crate) blob: Vec<u8>,
+}
+
+impl SshPublicKey {
+    /// Parses a public key blob, such as the base64 part of an `authorized_keys` line.
+    pub(crate) fn parse(blob: &[u8]) -> Result<SshPublicKey, ECDSAError> {
+        let mut reader = SshReader::new(blob);
+        let (algorithm, public_key) = match reader.text()? {
+            KEY_TYPE_ED25519 => (KeyAlgorithm::Ed25519, reader.string()?),
+            KEY_TYPE_ECDSA_P256 => {
+                if reader.string()? != b"nistp256" {
+                    return Err(ECDSAError::InvalidSshSignature);
+                }
+                (KeyAlgorithm::P256, reader.string()?)
+            }
+            _ => return Err(ECDSAError::UnsupportedSshKeyType),
+        };
+        reader.finish()?;
+        algorithm.check_public_key(public_key)?;
+        Ok(SshPublicKey { algorithm, public_key: public_key.to_vec(), blob: blob.to_vec() })
+    }
+
+    pub(crate) fn key_type(&self) -> &'static str {
+        match self.algorithm {
+            KeyAlgorithm::Ed25519 => KEY_TYPE_ED25519,
+            _ => KEY_TYPE_ECDSA_P256,
+        }
+    }
+
+    /// The OpenSSH fingerprint, e.g. `SHA256:zLr19mbo2V5SZHPMU7LP4bOtUROS00o1tn03ViPtH0w`.
+    pub(crate) fn fingerprint(&self) -> String {
+        format!("SHA256:{}", to_base64(&Sha256::digest(&self.blob)).trim_end_matches('='))
+    }
+
+    /// Verifies an SSH signature blob (`string format || string signature`) over `message`.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool, ECDSAError> {
+        let mut reader = SshReader::new(signature);
+        if reader.text()? != self.key_type() {
+            return Err(ECDSAError::InvalidSshSignature);
+        }
+        let signature = reader.string()?;
+        reader.finish()?;
+        match self.algorithm {
+            #[cfg(feature = "ed25519")]
+            KeyAlgorithm::Ed25519 => {
+                crate::ed25519::verify_ed25519(&self.public_key, message, signature).or(Ok(false))
+            }
+            #[cfg(feature = "p256")]
+            KeyAlgorithm::P256 => {
+                // The signature is `mpint r || mpint s` (RFC 5656 section 3.1.2)
+                let mut integers = SshReader::new(signature);
+                let mut fixed = [0u8; 64];
+                for half in fixed.chunks_exact_mut(32) {
+                    let integer = integers.string()?;
+                    let integer = &integer[integer.iter().position(|&b| b != 0).unwrap_or(integer.len())..];
+                    if integer.len() > 32 {
+                        return Ok(false);
+                    }
+                    half[32 - integer.len()..].copy_from_slice(integer);
+                }
+                integers.finish()?;
+                crate::p256::verify_bytes(&Sha256::digest(message), &fixed, &self.public_key).or(Ok(false))
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = (message, signature);
+                Err(ECDSAError::UnsupportedSshKeyType)
+            }
+        }
+    }
+}
+
+/// One entry of an `allowed_signers` file (see `ssh-keygen(1)`), or a bare public key line.
+pub(crate) struct AllowedSigner {
+    /// The comma-separated principals, absent for a bare public key
+    pub(crate) principals: Option<String>,
+    namespaces: Option<Vec<String>>,
+    /// The validity window, in seconds since the Unix epoch
+    valid_after: Option<i64>,
+    valid_before: Option<i64>,
+    pub(crate) key: SshPublicKey,
+}
+
+impl AllowedSigner {
+    fn allows(&self, namespace: &str, time: i64) -> bool {
+        self.namespaces.as_ref().is_none_or(|namespaces| namespaces.iter().any(|allowed| allowed == namespace))
+            && self.valid_after.is_none_or(|after| time >= after)
+            && self.valid_before.is_none_or(|before| time < before)
+    }
+}
+
+/// Splits a line on whitespace, keeping double-quoted sections together.
+fn tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let (mut start, mut quoted) = (None, false);
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if let Some(from) = start.take() {
+                    tokens.push(&line[from..index]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+    tokens.extend(start.map(|from| &line[from..]));
+    tokens
+}
+
+/// Parses an `allowed_signers` time, `YYYYMMDD[HHMM[SS]]` with an optional `Z`. Times are
+/// taken as UTC, as there is no local time zone in wasm.
+fn parse_time(text: &str) -> Option<i64> {
+    let digits = text.strip_suffix('Z').unwrap_or(text);
+    if !matches!(digits.len(), 8 | 12 | 14) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| digits.get(range).map_or(0, |field| field.parse::<i64>().unwrap());
+    let (month, day, hour, minute, second) = (field(4..6), field(6..8), field(8..10), field(10..12), field(12..14));
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    Some(days_from_civil(field(0..4), month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Parses every non-empty, non-comment line of an `allowed_signers` file. Each line is
+/// `principals [options] keytype base64 [comment]`, or just `keytype base64 [comment]`.
+pub(crate) fn parse_allowed_signers(text: &str) -> Result<Vec<AllowedSigner>, ECDSAError> {
+    let mut signers = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let fields = tokens(line);
+        // The key type is the first token followed by a key blob of that type
+        let key_index = (0..fields.len().saturating_sub(1))
+            .find(|&index| {
+                from_base64(fields[index + 1]).is_some_and(|blob| SshReader::new(&blob).text().ok() == Some(fields[index]))
+            })
+            .ok_or(ECDSAError::InvalidAllowedSigners)?;
+        let key = SshPublicKey::parse(&from_base64(fields[key_index + 1]).unwrap())?;
+
+        let mut signer =
+            AllowedSigner { principals: None, namespaces: None, valid_after: None, valid_before: None, key };
+        match key_index {
+            0 => {}
+            1 | 2 => signer.principals = Some(fields[0].to_string()),
+            _ => return Err(ECDSAError::InvalidAllowedSigners),
+        }
+        let options = if key_index == 2 { fields[1] } else { "" };
+        for option in tokens(&options.replace(',', " ")) {
+            // Quoted values may themselves hold commas, which were turned into spaces above
+            let (name, value) = option.split_once('=').unwrap_or((option, ""));
+            let value = value.trim_matches('"').replace(' ', ",");
+            match name.to_ascii_lowercase().as_str() {
+                "namespaces" => signer.namespaces = Some(value.split(',').map(str::to_string).collect()),
+                "valid-after" => signer.valid_after = Some(parse_time(&value).ok_or(ECDSAError::InvalidAllowedSigners)?),
+                "valid-before" => signer.valid_before = Some(parse_time(&value).ok_or(ECDSAError::InvalidAllowedSigners)?),
+                // Certificate authorities sign certificates, which are not supported
+                _ => return Err(ECDSAError::InvalidAllowedSigners),
+            }
+        }
+        signers.push(signer);
+    }
+    Ok(signers)
+}
+
+/// A decoded `SSHSIG` signature.
+pub(crate) struct SshSig {
+    pub(crate) key: SshPublicKey,
+    pub(crate) namespace: String,
+    pub(crate) hash_algorithm: String,
+    reserved: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+/// Decodes the binary form of an `SSH SIGNATURE` block.
+pub(crate) fn parse_sshsig(bytes: &[u8]) -> Result<SshSig, ECDSAError> {
+    let mut reader = SshReader::new(bytes);
+    if reader.take(MAGIC.len())? != MAGIC || reader.u32()? != VERSION {
+        return Err(ECDSAError::InvalidSshSignature);
+    }
+    let key = SshPublicKey::parse(reader.string()?)?;
+    let namespace = reader.text()?.to_string();
+    let reserved = reader.string()?.to_vec();
+    let hash_algorithm = reader.text()?.to_string();
+    let signature = reader.string()?.to_vec();
+    reader.finish()?;
+    if namespace.is_empty() || !matches!(hash_algorithm.as_str(), "sha256" | "sha512") {
+        return Err(ECDSAError::InvalidSshSignature);
+    }
+    Ok(SshSig { key, namespace, hash_algorithm, reserved, signature })
+}
+
+impl SshSig {
+    /// Checks the signature over `message`, returning the allowed signer entry that holds the key.
+    pub(crate) fn verify<'s>(
+        &self,
+        message: &[u8],
+        allowed_signers: &'s [AllowedSigner],
+        time: i64,
+    ) -> Result<&'s AllowedSigner, ECDSAError> {
+        let digest = match self.hash_algorithm.as_str() {
+            "sha256" => Sha256::digest(message).to_vec(),
+            _ => Sha512::digest(message).to_vec(),
+        };
+        let mut signed = MAGIC.to_vec();
+        write_string(&mut signed, self.namespace.as_bytes());
+        write_string(&mut signed, &self.reserved);
+        write_string(&mut signed, self.hash_algorithm.as_bytes());
+        write_string(&mut signed, &digest);
+        if !self.key.verify(&signed, &self.signature)? {
+            return Err(ECDSAError::SshSignatureMismatch);
+        }
+        allowed_signers
+            .iter()
+            .find(|signer| signer.key == self.key && signer.allows(&self.namespace, time))
+            .ok_or(ECDSAError::SshSignerNotAllowed)
+    }
+}
+
+/// A verified SSH signature.
+///
+/// The caller must still check that the namespace is the one it expects, as
+/// `ssh-keygen -Y verify -n` does, unless the allowed signer entry restricts it.
+#[wasm_bindgen]
+pub struct SshSignature {
+    namespace: String,
+    hash_algorithm: String,
+    key: SshPublicKey,
+    principals: Option<String>,
+}
+
+#[wasm_bindgen]
+impl SshSignature {
+    /// The namespace the signature was made for, e.g. `file` or `git`.
+    #[wasm_bindgen(getter)]
+    pub fn namespace(&self) -> String {
+        self.namespace.clone()
+    }
+
+    /// The message hash: `sha256` or `sha512`.
+    #[wasm_bindgen(getter)]
+    pub fn hash_algorithm(&self) -> String {
+        self.hash_algorithm.clone()
+    }
+
+    /// The signing key type: `ssh-ed25519` or `ecdsa-sha2-nistp256`.
+    #[wasm_bindgen(getter)]
+    pub fn key_type(&self) -> String {
+        self.key.key_type().to_string()
+    }
+
+    /// The signing key as an `authorized_keys` style line without a comment.
+    #[wasm_bindgen(getter)]
+    pub fn public_key(&self) -> String {
+        format!("{} {}", self.key.key_type(), to_base64(&self.key.blob))
+    }
+
+    /// The signing key's `SHA256:` fingerprint, as `ssh-keygen -l` prints it.
+    #[wasm_bindgen(getter)]
+    pub fn fingerprint(&self) -> String {
+        self.key.fingerprint()
+    }
+
+    /// The principals of the matching `allowed_signers` entry, if it named any.
+    #[wasm_bindgen(getter)]
+    pub fn principals(&self) -> Option<String> {
+        self.principals.clone()
+    }
+}
+
+/// Verifies an OpenSSH signature, like `ssh-keygen -Y verify`.
+///
+/// # Arguments:
+/// * `message` - The signed data (`Uint8Array` in JS).
+/// * `sshsig_pem` - The `-----BEGIN SSH SIGNATURE-----` block from `ssh-keygen -Y sign`.
+/// * `allowed_signer` - One or more `allowed_signers` lines
+///   (`principals [namespaces="..."] keytype base64`), or a bare public key line as found in
+///   `id_ed25519.pub`. The `namespaces`, `valid-after` and `valid-before` options are honoured,
+///   with times read as UTC.
+///
+/// # Returns:
+/// * The signature's namespace, hash algorithm and signing key, and the matching principals.
+///
+/// # Errors:
+/// * `InvalidSshSignature` if the signature is malformed or uses another hash algorithm.
+/// * `UnsupportedSshKeyType` for keys other than `ssh-ed25519` and `ecdsa-sha2-nistp256`
+///   (the latter with the `p256` feature).
+/// * `InvalidAllowedSigners` for malformed lines or unsupported options such as `cert-authority`.
+/// * `SshSignatureMismatch` if the signature does not verify.
+/// * `SshSignerNotAllowed` if no allowed signer holds the key for the namespace at the current time.
+#[wasm_bindgen]
+pub fn verify_sshsig(message: &[u8], sshsig_pem: &str, allowed_signer: &str) -> Result<SshSignature, ECDSAError> {
+    let (_, bytes) = decode_pem(sshsig_pem, &["SSH SIGNATURE"]).map_err(|_| ECDSAError::InvalidSshSignature)?;
+    let signature = parse_sshsig(&bytes)?;
+    let allowed_signers = parse_allowed_signers(allowed_signer)?;
+    let signer = signature.verify(message, &allowed_signers, now_millis().div_euclid(1000))?;
+    Ok(SshSignature {
+        principals: signer.principals.clone(),
+        namespace: signature.namespace,
+        hash_algorithm: signature.hash_algorithm,
+        key: signature.key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &[u8] = b"hello sshsig\n";
+    const ED25519_KEY: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIAtr4ZAiTESglZMVMF4mvQIWtzoTIiY1ongDFCK7dObu alice@example.com";
+    #[cfg(any(feature = "ed25519", feature = "p256"))]
+    const ECDSA_KEY: &str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBFwEmxZ2zqNcRFnEbPTUk6rw4ISORyt5gDXTA8hnYMYBBmAhI5V+Eboe528Msn5F2HQPHqqAK6WPEVjqANCkbp8= bob@example.com";
+
+    // `ssh-keygen -Y sign -n file` over MESSAGE with each key, and `-n git -O hashalg=sha256`
+    const ED25519_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgC2vhkCJMRKCVkxUwXia9Aha3Oh
+MiJjWieAMUIrt05u4AAAAEZmlsZQAAAAAAAAAGc2hhNTEyAAAAUwAAAAtzc2gtZWQyNTUx
+OQAAAEDD8N582fAOHNS6QYNhSCGciiCud5BNod1l3H8X0PCzcw2u5oCPM2dQHtShELY/yi
+1+X6Nqlh2paDx4EStBzWkJ
+-----END SSH SIGNATURE-----";
+    #[cfg(feature = "p256")]
+    const ECDSA_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAGgAAAATZWNkc2Etc2hhMi1uaXN0cDI1NgAAAAhuaXN0cDI1NgAAAE
+EEXASbFnbOo1xEWcRs9NSTqvDghI5HK3mANdMDyGdgxgEGYCEjlX4Ruh7nbwyyfkXYdA8e
+qoArpY8RWOoA0KRunwAAAARmaWxlAAAAAAAAAAZzaGE1MTIAAABjAAAAE2VjZHNhLXNoYT
+ItbmlzdHAyNTYAAABIAAAAIC9VATJfY9UaaKgelxj7kn/L/tdvkH0NU+Bx6KKXrX71AAAA
+ICsNoypMzUV1BKaWd5Hwww+fuvnf0Jpln4TnuEFNCpQy
+-----END SSH SIGNATURE-----";
+    #[cfg(feature = "ed25519")]
+    const GIT_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgC2vhkCJMRKCVkxUwXia9Aha3Oh
+MiJjWieAMUIrt05u4AAAADZ2l0AAAAAAAAAAZzaGEyNTYAAABTAAAAC3NzaC1lZDI1NTE5
+AAAAQCaravn3CKnYM+OQ0lZ719pRkHvoR7QGKGF/dhusU7TnUrOrqLQCwYYHpKhW2Ko4df
+4U464pxAjm7UBXO1rcBAQ=
+-----END SSH SIGNATURE-----";
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    fn test_verify_ed25519() {
+        let signature = verify_sshsig(MESSAGE, ED25519_SIGNATURE, ED25519_KEY).unwrap();
+        assert_eq!((signature.namespace().as_str(), signature.hash_algorithm().as_str()), ("file", "sha512"));
+        assert_eq!(signature.key_type(), "ssh-ed25519");
+        assert!(ED25519_KEY.starts_with(&signature.public_key()));
+        assert_eq!(signature.fingerprint(), "SHA256:zLr19mbo2V5SZHPMU7LP4bOtUROS00o1tn03ViPtH0w");
+        assert_eq!(signature.principals(), None);
+
+        let signature = verify_sshsig(MESSAGE, GIT_SIGNATURE, &format!("alice@example.com {ED25519_KEY}")).unwrap();
+        assert_eq!((signature.namespace().as_str(), signature.hash_algorithm().as_str()), ("git", "sha256"));
+        assert_eq!(signature.principals().as_deref(), Some("alice@example.com"));
+
+        assert!(matches!(verify_sshsig(b"hello sshsig", ED25519_SIGNATURE, ED25519_KEY), Err(ECDSAError::SshSignatureMismatch)));
+        assert!(matches!(verify_sshsig(MESSAGE, ED25519_SIGNATURE, ECDSA_KEY), Err(ECDSAError::SshSignerNotAllowed)));
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_verify_ecdsa_p256() {
+        let allowed = format!("{ED25519_KEY}\n# Bob\nbob@example.com,robert@example.com {ECDSA_KEY}");
+        let signature = verify_sshsig(MESSAGE, ECDSA_SIGNATURE, &allowed).unwrap();
+        assert_eq!(signature.key_type(), "ecdsa-sha2-nistp256");
+        assert_eq!(signature.principals().as_deref(), Some("bob@example.com,robert@example.com"));
+        assert!(matches!(verify_sshsig(b"other", ECDSA_SIGNATURE, &allowed), Err(ECDSAError::SshSignatureMismatch)));
+    }
+
+    #[test]
+    #[cfg(feature = "ed25519")]
+    fn test_allowed_signer_options() {
+        let allowed = |options: &str| format!("alice@example.com {options} {ED25519_KEY}");
+        for options in [r#"namespaces="file,git""#, r#"valid-after="20200101",namespaces="file""#, "valid-before=29991231235959Z"] {
+            assert!(verify_sshsig(MESSAGE, ED25519_SIGNATURE, &allowed(options)).is_ok(), "{options}");
+        }
+        for options in [r#"namespaces="git""#, "valid-after=29990101", "valid-before=202001010000"] {
+            assert!(matches!(verify_sshsig(MESSAGE, ED25519_SIGNATURE, &allowed(options)), Err(ECDSAError::SshSignerNotAllowed)));
+        }
+        for options in ["cert-authority", "valid-after=2020", "namespaces=\"file\" extra"] {
+            assert!(matches!(verify_sshsig(MESSAGE, ED25519_SIGNATURE, &allowed(options)), Err(ECDSAError::InvalidAllowedSigners)));
+        }
+        assert_eq!(tokens(r#" a  b="c d",e  f "#), ["a", r#"b="c d",e"#, "f"]);
+    }
+
+    #[test]
+    fn test_rejects_malformed_signatures() {
+        let (_, bytes) = decode_pem(ED25519_SIGNATURE, &["SSH SIGNATURE"]).unwrap();
+        assert!(parse_sshsig(&bytes).is_ok());
+        // Bad magic, a truncated blob, trailing bytes and an unknown hash algorithm
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b's';
+        let mut unknown_hash = bytes.clone();
+        let hash = bytes.windows(6).position(|window| window == b"sha512").unwrap();
+        unknown_hash[hash + 3..hash + 6].copy_from_slice(b"384");
+        for bytes in [bad_magic, bytes[..bytes.len() - 1].to_vec(), [bytes.as_slice(), &[0]].concat(), unknown_hash] {
+            assert!(matches!(parse_sshsig(&bytes), Err(ECDSAError::InvalidSshSignature)));
+        }
+        assert!(matches!(
+            verify_sshsig(MESSAGE, "-----BEGIN SSH SIGNATURE-----\n-----END SSH SIGNATURE-----", ED25519_KEY),
+            Err(ECDSAError::InvalidSshSignature)
+        ));
+        assert!(matches!(parse_allowed_signers("alice@example.com"), Err(ECDSAError::InvalidAllowedSigners)));
+        let rsa = "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAAQE=";
+        assert!(matches!(parse_allowed_signers(rsa), Err(ECDSAError::UnsupportedSshKeyType)));
+    }
+}
@@ -0,0 +1,131 @@
+//! Hashing and verifying `Blob`s and `ReadableStream`s chunk by chunk.
+//!
+//! A signed download can be checked straight from `fetch` (`response.body`) or a file input
+//! without holding the whole payload in JS or wasm memory: each chunk is copied into one
+//! reusable wasm buffer, absorbed by a `Hasher` and dropped.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use js_sys::{Reflect, Uint8Array};
+use web_sys::{Blob, ReadableStream, ReadableStreamDefaultReader};
+
+use crate::hash::{HashAlgorithm, Hasher};
+use crate::session::VerificationSession;
+use crate::ECDSAError;
+
+/// Passes every chunk the reader yields to `sink`.
+async fn read_into(reader: &ReadableStreamDefaultReader, mut sink: impl FnMut(&[u8])) -> Result<(), ECDSAError> {
+    let mut buffer = Vec::new();
+    loop {
+        let result = JsFuture::from(reader.read()).await.map_err(|_| ECDSAError::StreamReadFailed)?;
+        let done = Reflect::get(&result, &"done".into()).map_err(|_| ECDSAError::StreamReadFailed)?;
+        if done.is_truthy() {
+            return Ok(());
+        }
+        let value = Reflect::get(&result, &"value".into()).map_err(|_| ECDSAError::StreamReadFailed)?;
+        let chunk: Uint8Array = value.dyn_into().map_err(|_| ECDSAError::StreamReadFailed)?;
+        buffer.resize(chunk.length() as usize, 0);
+        chunk.copy_to(&mut buffer);
+        sink(&buffer);
+    }
+}
+
+/// Reads a stream of `Uint8Array` chunks to its end, releasing the reader's lock afterwards.
+async fn consume_stream(stream: &ReadableStream, sink: impl FnMut(&[u8])) -> Result<(), ECDSAError> {
+    let reader: ReadableStreamDefaultReader = stream.get_reader().unchecked_into();
+    let result = read_into(&reader, sink).await;
+    reader.release_lock();
+    result
+}
+
+async fn hash_stream(stream: &ReadableStream, algorithm: HashAlgorithm) -> Result<Vec<u8>, ECDSAError> {
+    let mut hasher = Hasher::new(Some(algorithm));
+    consume_stream(stream, |chunk| hasher.update(chunk)).await?;
+    Ok(hasher.finalize())
+}
+
+/// Hashes a `ReadableStream` of bytes, such as `response.body` from `fetch`.
+///
+/// # Arguments:
+/// * `stream` - A stream of `Uint8Array` chunks. It is locked while being read and fully
+///   consumed.
+/// * `algorithm` - Optional hash function; defaults to SHA-256.
+///
+/// # Returns:
+/// * A promise of the digest (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `StreamReadFailed` if the stream errors or yields something other than a `Uint8Array`.
+#[wasm_bindgen]
+pub async fn hash_readable_stream(stream: ReadableStream, algorithm: Option<HashAlgorithm>) -> Result<Vec<u8>, ECDSAError> {
+    hash_stream(&stream, algorithm.unwrap_or_default()).await
+}
+
+/// Hashes a `Blob` or `File` without reading it into memory at once.
+///
+/// # Arguments:
+/// * `blob` - The blob to hash.
+/// * `algorithm` - Optional hash function; defaults to SHA-256.
+///
+/// # Returns:
+/// * A promise of the digest (`Uint8Array` in JS).
+///
+/// # Errors:
+/// * `StreamReadFailed` if the blob cannot be read.
+#[wasm_bindgen]
+pub async fn hash_blob(blob: Blob, algorithm: Option<HashAlgorithm>) -> Result<Vec<u8>, ECDSAError> {
+    hash_stream(&blob.stream(), algorithm.unwrap_or_default()).await
+}
+
+/// Verifies a secp256k1 ECDSA signature over the contents of a `ReadableStream`, hashing it
+/// as it arrives.
+///
+/// # Arguments:
+/// * `stream` - A stream of `Uint8Array` chunks; it is fully consumed.
+/// * `signature` - The DER or compact signature, provided as a hex string.
+/// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+/// * `algorithm` - Optional hash function; defaults to SHA-256.
+///
+/// # Returns:
+/// * A promise of whether the signature is valid.
+///
+/// # Errors:
+/// * `StreamReadFailed` if the stream cannot be read, or an error if the signature or key
+///   is malformed.
+#[wasm_bindgen]
+pub async fn verify_stream_signature(
+    stream: ReadableStream,
+    signature: String,
+    public_key: String,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<bool, ECDSAError> {
+    let mut session = VerificationSession::start(&signature, &public_key, algorithm)?;
+    consume_stream(&stream, |chunk| session.feed(chunk)).await?;
+    Ok(session.finish())
+}
+
+/// Verifies a secp256k1 ECDSA signature over the contents of a `Blob` or `File`.
+///
+/// # Arguments:
+/// * `blob` - The signed blob.
+/// * `signature` - The DER or compact signature, provided as a hex string.
+/// * `public_key` - The signer's SEC1 public key, provided as a hex string.
+/// * `algorithm` - Optional hash function; defaults to SHA-256.
+///
+/// # Returns:
+/// * A promise of whether the signature is valid.
+///
+/// # Errors:
+/// * `StreamReadFailed` if the blob cannot be read, or an error if the signature or key
+///   is malformed.
+#[wasm_bindgen]
+pub async fn verify_blob_signature(
+    blob: Blob,
+    signature: String,
+    public_key: String,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<bool, ECDSAError> {
+    verify_stream_signature(blob.stream(), signature, public_key, algorithm).await
+}
+
@@ -0,0 +1,124 @@
+//! BIP-341 Taproot output key tweaking.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::elliptic_curve::AffineXCoordinate;
+use k256::{FieldBytes, ProjectivePoint, Scalar};
+
+use crate::schnorr::{has_even_y, lift_x, parse_x_only, tagged_hash};
+use crate::{ECDSAError, SchnorrVerifier};
+
+/// Computes the Taproot output key `Q = P + H_TapTweak(P || merkle_root) * G`.
+///
+/// `merkle_root` is empty for a key-path-only output, or the 32-byte root of the script tree.
+/// Returns the x-only output key and whether its y-coordinate is odd (the control block parity).
+pub(crate) fn tweak_public_key(internal_key: &[u8; 32], merkle_root: &[u8]) -> Result<([u8; 32], bool), ECDSAError> {
+    if !merkle_root.is_empty() && merkle_root.len() != 32 {
+        return Err(ECDSAError::InvalidTaprootTweak);
+    }
+    let point = lift_x(internal_key)?;
+
+    let tweak = tagged_hash("TapTweak", &[internal_key, merkle_root]);
+    let tweak = Option::<Scalar>::from(Scalar::from_repr(FieldBytes::from(tweak)))
+        .ok_or(ECDSAError::InvalidTaprootTweak)?;
+
+    let output = (ProjectivePoint::from(point) + ProjectivePoint::GENERATOR * tweak).to_affine();
+    if output == k256::AffinePoint::IDENTITY {
+        return Err(ECDSAError::InvalidTaprootTweak);
+    }
+
+    let mut x_only = [0u8; 32];
+    x_only.copy_from_slice(&output.x());
+    Ok((x_only, !has_even_y(&output)))
+}
+
+/// Decodes an optional hex merkle root; `None` and the empty string both mean no script tree.
+fn decode_merkle_root(merkle_root: Option<String>) -> Result<Vec<u8>, ECDSAError> {
+    hex::decode(merkle_root.unwrap_or_default()).map_err(|_| ECDSAError::InvalidTaprootTweak)
+}
+
+#[wasm_bindgen]
+impl SchnorrVerifier {
+    /// Derives the BIP-341 Taproot output key committing to an internal key and script tree.
+    ///
+    /// # Arguments:
+    /// * `internal_key` - The 32-byte x-only internal public key, provided as a hex string.
+    /// * `merkle_root` - The 32-byte script tree root as a hex string, or omitted for a
+    ///   key-path-only output.
+    ///
+    /// # Returns:
+    /// * The 32-byte x-only output key, as a hex string.
+    pub fn taproot_output_key(&self, internal_key: &str, merkle_root: Option<String>) -> Result<String, ECDSAError> {
+        let internal_key = hex::decode(internal_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+        let (output_key, _) = tweak_public_key(&parse_x_only(&internal_key)?, &decode_merkle_root(merkle_root)?)?;
+        Ok(hex::encode(output_key))
+    }
+
+    /// Checks that a Taproot output key commits to the expected internal key and script tree.
+    ///
+    /// # Arguments:
+    /// * `internal_key` - The 32-byte x-only internal public key, provided as a hex string.
+    /// * `merkle_root` - The 32-byte script tree root as a hex string, or omitted for a
+    ///   key-path-only output.
+    /// * `output_key` - The 32-byte x-only output key from the scriptPubKey, provided as a hex string.
+    ///
+    /// # Returns:
+    /// * A boolean indicating whether the output key matches the tweaked internal key.
+    ///
+    /// # Errors:
+    /// * Returns an error if a key is malformed or the merkle root is not 32 bytes.
+    pub fn verify_taproot_tweak(
+        &self,
+        internal_key: &str,
+        merkle_root: Option<String>,
+        output_key: &str,
+    ) -> Result<bool, ECDSAError> {
+        let output_key = hex::decode(output_key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+        let output_key = parse_x_only(&output_key)?;
+        let expected = self.taproot_output_key(internal_key, merkle_root)?;
+        Ok(expected == hex::encode(output_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // scriptPubKey cases from the BIP-341 wallet test vectors
+    const KEY_PATH_INTERNAL: &str = "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d";
+    const KEY_PATH_OUTPUT: &str = "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343";
+    const SCRIPT_INTERNAL: &str = "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27";
+    const SCRIPT_ROOT: &str = "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21";
+    const SCRIPT_OUTPUT: &str = "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3";
+
+    #[test]
+    fn test_key_path_tweak() {
+        let verifier = SchnorrVerifier::new();
+        assert_eq!(verifier.taproot_output_key(KEY_PATH_INTERNAL, None).unwrap(), KEY_PATH_OUTPUT);
+        assert!(verifier.verify_taproot_tweak(KEY_PATH_INTERNAL, None, KEY_PATH_OUTPUT).unwrap());
+        assert!(verifier.verify_taproot_tweak(KEY_PATH_INTERNAL, Some(String::new()), KEY_PATH_OUTPUT).unwrap());
+    }
+
+    #[test]
+    fn test_script_tree_tweak() {
+        let verifier = SchnorrVerifier::new();
+        let root = Some(SCRIPT_ROOT.to_string());
+        assert!(verifier.verify_taproot_tweak(SCRIPT_INTERNAL, root.clone(), SCRIPT_OUTPUT).unwrap());
+        assert!(!verifier.verify_taproot_tweak(SCRIPT_INTERNAL, None, SCRIPT_OUTPUT).unwrap());
+        assert!(!verifier.verify_taproot_tweak(KEY_PATH_INTERNAL, root, SCRIPT_OUTPUT).unwrap());
+
+        let internal = parse_x_only(&hex::decode(SCRIPT_INTERNAL).unwrap()).unwrap();
+        let (_, odd) = tweak_public_key(&internal, &hex::decode(SCRIPT_ROOT).unwrap()).unwrap();
+        assert!(odd);
+    }
+
+    #[test]
+    fn test_rejects_malformed_merkle_root() {
+        let verifier = SchnorrVerifier::new();
+        let short_root = Some(SCRIPT_ROOT[..62].to_string());
+        assert!(matches!(
+            verifier.verify_taproot_tweak(SCRIPT_INTERNAL, short_root, SCRIPT_OUTPUT),
+            Err(ECDSAError::InvalidTaprootTweak)
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+//! Tron signed messages and addresses.
+//!
+//! Tron reuses Ethereum's key handling: an address is the last 20 bytes of the Keccak-256
+//! of the uncompressed public key, behind a 0x41 version byte and Base58Check encoded, so
+//! mainnet addresses start with `T`. TronWeb's `signMessageV2` mirrors `personal_sign`
+//! with its own prefix.
+
+use wasm_bindgen::prelude::*;
+
+use crate::constant_time::constant_time_eq;
+use crate::encoding::from_base58check;
+use crate::ethereum::{address_bytes, prefixed_message_hash};
+use crate::signature::split_signature;
+use crate::{recover_from_digest, ECDSAError};
+
+const ADDRESS_VERSION: u8 = 0x41;
+const MESSAGE_PREFIX: &[u8] = b"\x19TRON Signed Message:\n";
+
+/// Parses a Base58Check `T...` address into its 20-byte account.
+fn parse_tron_address(address: &str) -> Result<[u8; 20], ECDSAError> {
+    let data = from_base58check(address).ok_or(ECDSAError::InvalidAddress)?;
+    match data.split_first() {
+        Some((&ADDRESS_VERSION, account)) => account.try_into().map_err(|_| ECDSAError::InvalidAddress),
+        _ => Err(ECDSAError::InvalidAddress),
+    }
+}
+
+/// Verifies a Tron signed message, as produced by TronWeb's `signMessageV2` and TronLink.
+///
+/// # Arguments:
+/// * `address` - The claimed signer's Base58Check address, e.g. `T...`.
+/// * `message` - The signed message, before the Tron prefix is applied.
+/// * `signature` - The 65-byte signature (r || s || v), provided as a hex string with or
+///   without `0x`. `v` may be 0/1 or 27/28. A 64-byte EIP-2098 compact signature is also
+///   accepted.
+///
+/// # Returns:
+/// * A boolean indicating whether the signature was produced by `address`.
+///
+/// # Errors:
+/// * Returns an error if the address or the signature is malformed.
+#[wasm_bindgen]
+pub fn verify_tron_message(address: &str, message: &str, signature: &str) -> Result<bool, ECDSAError> {
+    let expected = parse_tron_address(address)?;
+    let signature_bytes = hex::decode(signature.trim_start_matches("0x"))
+        .map_err(|_| ECDSAError::InvalidSignatureFormat)?;
+    let (sig, recovery_id) = split_signature(&signature_bytes)?;
+
+    let digest = prefixed_message_hash(MESSAGE_PREFIX, message.as_bytes());
+    match recover_from_digest(&digest, &sig, recovery_id) {
+        Ok(pubkey) => Ok(constant_time_eq(&address_bytes(&pubkey), &expected)),
+        Err(_) => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::to_base58check;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+
+    fn tron_address(key: &SigningKey) -> String {
+        let mut data = vec![ADDRESS_VERSION];
+        data.extend_from_slice(&address_bytes(&key.verifying_key()));
+        to_base58check(&data)
+    }
+
+    fn sign(key: &SigningKey, message: &str) -> String {
+        let digest = prefixed_message_hash(MESSAGE_PREFIX, message.as_bytes());
+        let (sig, recovery_id) = sign_digest(key, &digest, &[]).unwrap();
+        let mut bytes = sig.as_ref().to_vec();
+        bytes.push(recovery_id + 27);
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    #[test]
+    fn test_parse_tron_address() {
+        // Example from the Tron developer documentation
+        let account = parse_tron_address("TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL").unwrap();
+        assert_eq!(hex::encode_upper(account), "8840E6C55B9ADA326D211D818C34A994AECED808");
+        assert!(parse_tron_address("TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeM").is_err());
+        // A Bitcoin P2PKH address has the wrong version byte
+        assert!(parse_tron_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH").is_err());
+    }
+
+    #[test]
+    fn test_verify_tron_message() {
+        let key = SigningKey::from_bytes(&[0x5a; 32]).unwrap();
+        let address = tron_address(&key);
+        assert!(address.starts_with('T'));
+        let signature = sign(&key, "Hello TRON");
+
+        assert!(verify_tron_message(&address, "Hello TRON", &signature).unwrap());
+        assert!(!verify_tron_message(&address, "Hello TRX", &signature).unwrap());
+        let other = tron_address(&SigningKey::from_bytes(&[0x5b; 32]).unwrap());
+        assert!(!verify_tron_message(&other, "Hello TRON", &signature).unwrap());
+
+        // An Ethereum personal_sign signature does not verify as a Tron message
+        let digest = crate::ethereum::personal_message_hash(b"Hello TRON");
+        let (sig, recovery_id) = sign_digest(&key, &digest, &[]).unwrap();
+        let ethereum = hex::encode([sig.as_ref(), &[recovery_id]].concat());
+        assert!(!verify_tron_message(&address, "Hello TRON", &ethereum).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let key = SigningKey::from_bytes(&[0x5a; 32]).unwrap();
+        let address = tron_address(&key);
+        let signature = sign(&key, "hi");
+        assert!(matches!(verify_tron_message("T123", "hi", &signature), Err(ECDSAError::InvalidAddress)));
+        assert!(verify_tron_message(&address, "hi", &signature[..128]).is_err());
+        assert!(verify_tron_message(&address, "hi", "0xzz").is_err());
+    }
+}
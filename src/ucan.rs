@@ -0,0 +1,352 @@
+//! UCAN (User Controlled Authorization Network) capability tokens, versions 0.8 to 0.10.
+//!
+//! A UCAN is a JWT whose issuer `iss` and audience `aud` are DIDs; the issuer's `did:key`
+//! supplies the verification key. Delegated capabilities cite the tokens granting them in
+//! `prf`, either inline (0.8) or by CID (0.9 and later): a CIDv1 of the token's bytes with the
+//! `raw` codec and a SHA-256 multihash, in base32 multibase. Each proof must be addressed to
+//! the issuer of the token citing it, and must be valid for at least as long.
+
+use wasm_bindgen::prelude::*;
+
+use crate::did::parse_did_key;
+use crate::encoding::to_base32;
+use crate::hash::HashAlgorithm;
+use crate::jose::split_jws;
+use crate::json::{self, canonicalize, Value};
+use crate::keys::asn1::KeyAlgorithm;
+use crate::multihash::compute_multihash;
+use crate::time::{now_millis, parse_rfc3339};
+use crate::ECDSAError;
+
+/// JWS algorithms and the key types that sign with them.
+const ALGORITHMS: [(&str, KeyAlgorithm); 4] = [
+    ("EdDSA", KeyAlgorithm::Ed25519),
+    ("ES256", KeyAlgorithm::P256),
+    ("ES256K", KeyAlgorithm::Secp256k1),
+    ("ES384", KeyAlgorithm::P384),
+];
+
+/// Delegation depth limit, which also bounds the recursion on hostile input.
+const MAX_CHAIN_LENGTH: usize = 16;
+
+/// The CID prefix: version 1 and the `raw` codec.
+const CID_PREFIX: [u8; 2] = [0x01, 0x55];
+
+/// A UCAN whose signature has been checked.
+pub(crate) struct Ucan {
+    pub(crate) issuer: String,
+    pub(crate) audience: String,
+    pub(crate) version: Option<String>,
+    pub(crate) not_before: Option<u64>,
+    /// `None` for tokens that never expire (`"exp": null`)
+    pub(crate) expires: Option<u64>,
+    pub(crate) capabilities: Value,
+    pub(crate) proofs: Vec<String>,
+}
+
+/// The CID UCAN 0.9 and later use to cite a token.
+pub(crate) fn token_cid(token: &str) -> String {
+    let cid = [&CID_PREFIX[..], &compute_multihash(HashAlgorithm::Sha256, token.as_bytes())].concat();
+    format!("b{}", to_base32(&cid))
+}
+
+/// Reads an optional time claim in whole seconds. `null` means unbounded.
+fn time_claim(payload: &Value, name: &str) -> Result<Option<u64>, ECDSAError> {
+    match payload.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value.as_u64().map(Some).ok_or(ECDSAError::InvalidUcan),
+    }
+}
+
+/// Parses a UCAN and checks its signature with the issuer's `did:key`.
+pub(crate) fn verify_token(token: &str) -> Result<Ucan, ECDSAError> {
+    let jws = split_jws(token).map_err(|_| ECDSAError::InvalidUcan)?;
+    let payload = std::str::from_utf8(&jws.payload).map_err(|_| ECDSAError::InvalidUcan)?;
+    let payload = json::parse(payload).map_err(|_| ECDSAError::InvalidUcan)?;
+    let claim = |name: &str| payload.get(name).and_then(Value::as_str).ok_or(ECDSAError::InvalidUcan);
+    if jws.header.get("typ").and_then(Value::as_str).is_some_and(|typ| typ != "JWT") {
+        return Err(ECDSAError::InvalidUcan);
+    }
+
+    let issuer = claim("iss")?.to_string();
+    let (algorithm, public_key) = parse_did_key(&issuer)?;
+    let alg = jws.header.get("alg").and_then(Value::as_str);
+    if !ALGORITHMS.iter().any(|&(name, key_algorithm)| Some(name) == alg && key_algorithm == algorithm) {
+        return Err(ECDSAError::UnsupportedJwtAlgorithm);
+    }
+    if !algorithm.verify_raw(&public_key, jws.signing_input.as_bytes(), &jws.signature)? {
+        return Err(ECDSAError::JwtSignatureMismatch);
+    }
+
+    let proofs = match payload.get("prf") {
+        None => Vec::new(),
+        Some(proofs) => proofs
+            .as_array()
+            .ok_or(ECDSAError::InvalidUcan)?
+            .iter()
+            .map(|proof| proof.as_str().map(str::to_string).ok_or(ECDSAError::InvalidUcan))
+            .collect::<Result<_, _>>()?,
+    };
+    // 0.10 moved the version from the header into the payload
+    let version = jws.header.get("ucv").or_else(|| payload.get("ucv")).and_then(Value::as_str).map(str::to_string);
+    Ok(Ucan {
+        audience: claim("aud")?.to_string(),
+        issuer,
+        version,
+        not_before: time_claim(&payload, "nbf")?,
+        expires: time_claim(&payload, "exp")?,
+        capabilities: payload.get("att").cloned().unwrap_or(Value::Null),
+        proofs,
+    })
+}
+
+/// Verifies a token and, recursively, the proofs it cites at `time`, in seconds since the
+/// Unix epoch. Returns the token with the issuers at the roots of its delegation chains.
+pub(crate) fn verify_chain(
+    token: &str,
+    proofs: &[(String, &str)],
+    time: u64,
+    depth: usize,
+) -> Result<(Ucan, Vec<String>), ECDSAError> {
+    if depth >= MAX_CHAIN_LENGTH {
+        return Err(ECDSAError::InvalidUcanChain);
+    }
+    let ucan = verify_token(token)?;
+    if ucan.expires.is_some_and(|expires| time >= expires) {
+        return Err(ECDSAError::JwtExpired);
+    }
+    if ucan.not_before.is_some_and(|not_before| time < not_before) {
+        return Err(ECDSAError::JwtNotYetValid);
+    }
+
+    let mut roots = Vec::new();
+    for proof in &ucan.proofs {
+        let proof = if proof.contains('.') {
+            proof.as_str()
+        } else {
+            proofs.iter().find(|(cid, _)| cid == proof).map(|&(_, token)| token).ok_or(ECDSAError::UcanProofMissing)?
+        };
+        let (parent, parent_roots) = verify_chain(proof, proofs, time, depth + 1)?;
+        let outlives = parent.expires.is_some_and(|limit| ucan.expires.is_none_or(|expires| expires > limit));
+        let starts_early = matches!((parent.not_before, ucan.not_before), (Some(limit), Some(start)) if start < limit);
+        if parent.audience != ucan.issuer || outlives || starts_early {
+            return Err(ECDSAError::InvalidUcanChain);
+        }
+        roots.extend(parent_roots);
+    }
+    if ucan.proofs.is_empty() {
+        roots.push(ucan.issuer.clone());
+    }
+    Ok((ucan, roots))
+}
+
+/// A verified UCAN and its delegation chain.
+#[wasm_bindgen]
+pub struct UcanToken {
+    ucan: Ucan,
+    root_issuers: Vec<String>,
+}
+
+#[wasm_bindgen]
+impl UcanToken {
+    /// The issuer's DID.
+    #[wasm_bindgen(getter)]
+    pub fn issuer(&self) -> String {
+        self.ucan.issuer.clone()
+    }
+
+    /// The audience's DID: the principal the capabilities are delegated to.
+    #[wasm_bindgen(getter)]
+    pub fn audience(&self) -> String {
+        self.ucan.audience.clone()
+    }
+
+    /// The `ucv` version, e.g. `0.9.1`, if the token declares one.
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> Option<String> {
+        self.ucan.version.clone()
+    }
+
+    /// The `nbf` time, in seconds since the Unix epoch.
+    #[wasm_bindgen(getter)]
+    pub fn not_before(&self) -> Option<u64> {
+        self.ucan.not_before
+    }
+
+    /// The `exp` time, in seconds since the Unix epoch, or `undefined` if it never expires.
+    #[wasm_bindgen(getter)]
+    pub fn expires(&self) -> Option<u64> {
+        self.ucan.expires
+    }
+
+    /// The `att` capabilities as canonical JSON. They are not checked against the proofs:
+    /// whether each is a valid attenuation depends on the resource's semantics.
+    #[wasm_bindgen(getter)]
+    pub fn capabilities(&self) -> String {
+        canonicalize(&self.ucan.capabilities).unwrap_or_default()
+    }
+
+    /// The issuers of the tokens at the roots of the delegation chains: the resource owners
+    /// the capabilities derive from. A token without proofs is its own root.
+    #[wasm_bindgen(getter)]
+    pub fn root_issuers(&self) -> Vec<String> {
+        self.root_issuers.clone()
+    }
+}
+
+/// Verifies a UCAN and its chain of proofs.
+///
+/// Every token's signature is checked with its issuer's `did:key`, and every token must be
+/// within its `nbf` and `exp` times. Each proof must be addressed to the issuer of the token
+/// citing it and may not expire before it.
+///
+/// # Arguments:
+/// * `token` - The encoded UCAN.
+/// * `proofs` - The encoded tokens that `prf` fields cite by CID, in any order. Proofs
+///   included inline, as in UCAN 0.8, need not be repeated.
+/// * `audience` - If given, the DID the token must be addressed to.
+/// * `time` - An optional RFC 3339 timestamp to validate at, defaulting to the current time.
+///
+/// # Returns:
+/// * The token's issuer, audience, times and capabilities, and the root issuers of its chain.
+///
+/// # Errors:
+/// * `InvalidUcan` if a token is malformed, or `InvalidDidKey` if an issuer is not a `did:key`.
+/// * `UnsupportedJwtAlgorithm` if a token's `alg` does not match its issuer's key type.
+/// * `JwtSignatureMismatch` if a signature is invalid.
+/// * `JwtExpired` or `JwtNotYetValid` if a token is not valid at `time`.
+/// * `UcanProofMissing` if a cited proof is not in `proofs`.
+/// * `InvalidUcanChain` if a proof is for another audience, expires too early, or the chain
+///   is too long.
+/// * `UcanAudienceMismatch` if the token is not addressed to `audience`.
+#[wasm_bindgen]
+pub fn verify_ucan(
+    token: &str,
+    proofs: Vec<String>,
+    audience: Option<String>,
+    time: Option<String>,
+) -> Result<UcanToken, ECDSAError> {
+    let time = match time {
+        Some(time) => parse_rfc3339(&time).ok_or(ECDSAError::InvalidUcan)?,
+        None => now_millis(),
+    };
+    let time = u64::try_from(time.div_euclid(1000)).map_err(|_| ECDSAError::InvalidUcan)?;
+    let proofs = proofs.iter().map(|proof| (token_cid(proof.trim()), proof.trim())).collect::<Vec<_>>();
+    let (ucan, root_issuers) = verify_chain(token.trim(), &proofs, time, 0)?;
+    if audience.is_some_and(|audience| audience != ucan.audience) {
+        return Err(ECDSAError::UcanAudienceMismatch);
+    }
+    Ok(UcanToken { ucan, root_issuers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::encode_did_key;
+    use crate::encoding::to_base64url;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use sha2::{Digest, Sha256};
+
+    const TIME: &str = "2026-06-01T00:00:00Z";
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_bytes(&[byte; 32]).unwrap()
+    }
+
+    fn did(key: &SigningKey) -> String {
+        encode_did_key(KeyAlgorithm::Secp256k1, &key.verifying_key().to_bytes()).unwrap()
+    }
+
+    fn issue(issuer: &SigningKey, audience: &str, times: &str, proofs: &[&str]) -> String {
+        let header = r#"{"alg":"ES256K","typ":"JWT","ucv":"0.9.1"}"#;
+        let proofs = proofs.iter().map(|proof| format!("\"{proof}\"")).collect::<Vec<_>>().join(",");
+        let payload = format!(
+            r#"{{"iss":"{}","aud":"{audience}",{times},"att":[{{"with":"storage://alice","can":"store/put"}}],"prf":[{proofs}]}}"#,
+            did(issuer)
+        );
+        let signing_input = format!("{}.{}", to_base64url(header.as_bytes()), to_base64url(payload.as_bytes()));
+        let (sig, _) = sign_digest(issuer, &Sha256::digest(signing_input.as_bytes()), &[]).unwrap();
+        format!("{signing_input}.{}", to_base64url(sig.as_ref()))
+    }
+
+    fn verify(token: &str, proofs: &[&str]) -> Result<UcanToken, ECDSAError> {
+        let proofs = proofs.iter().map(|proof| proof.to_string()).collect();
+        verify_ucan(token, proofs, None, Some(TIME.to_string()))
+    }
+
+    // Alice delegates to Bob until 2027, and Bob to Carol until the end of 2026
+    fn chain() -> (String, String) {
+        let root = issue(&key(1), &did(&key(2)), r#""nbf":1767225600,"exp":1798761600"#, &[]);
+        let delegated = issue(&key(2), &did(&key(3)), r#""exp":1798675200"#, &[&token_cid(&root)]);
+        (root, delegated)
+    }
+
+    #[test]
+    fn test_verify_delegation_chain() {
+        let (root, delegated) = chain();
+        let token = verify(&delegated, &[&root]).unwrap();
+        assert_eq!(token.issuer(), did(&key(2)));
+        assert_eq!(token.audience(), did(&key(3)));
+        assert_eq!(token.version().as_deref(), Some("0.9.1"));
+        assert_eq!((token.not_before(), token.expires()), (None, Some(1798675200)));
+        assert_eq!(token.capabilities(), r#"[{"can":"store/put","with":"storage://alice"}]"#);
+        assert_eq!(token.root_issuers(), vec![did(&key(1))]);
+
+        // Inline proofs, as in UCAN 0.8
+        let inline = issue(&key(2), &did(&key(3)), r#""exp":1798675200"#, &[&root]);
+        assert_eq!(verify(&inline, &[]).unwrap().root_issuers(), vec![did(&key(1))]);
+
+        let audience = Some(did(&key(3)));
+        assert!(verify_ucan(&delegated, vec![root.clone()], audience, Some(TIME.to_string())).is_ok());
+        let audience = Some(did(&key(2)));
+        assert!(matches!(
+            verify_ucan(&delegated, vec![root], audience, Some(TIME.to_string())),
+            Err(ECDSAError::UcanAudienceMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_token_cid() {
+        // The CID of the empty token, as `ipfs add --raw-leaves --cid-version 1` prints it
+        assert_eq!(token_cid(""), "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+    }
+
+    #[test]
+    fn test_rejects_invalid_chains() {
+        let (root, delegated) = chain();
+        assert!(matches!(verify(&delegated, &[]), Err(ECDSAError::UcanProofMissing)));
+
+        // A proof for another audience, or one expiring before the delegation
+        let misdirected = issue(&key(1), &did(&key(4)), r#""exp":1798761600"#, &[]);
+        let token = issue(&key(2), &did(&key(3)), r#""exp":1798675200"#, &[&token_cid(&misdirected)]);
+        assert!(matches!(verify(&token, &[&misdirected]), Err(ECDSAError::InvalidUcanChain)));
+        let token = issue(&key(2), &did(&key(3)), r#""exp":null"#, &[&token_cid(&root)]);
+        assert!(matches!(verify(&token, &[&root]), Err(ECDSAError::InvalidUcanChain)));
+        let token = issue(&key(2), &did(&key(3)), r#""nbf":1767225599,"exp":1798675200"#, &[&token_cid(&root)]);
+        assert!(matches!(verify(&token, &[&root]), Err(ECDSAError::InvalidUcanChain)));
+
+        // Time bounds apply to every token in the chain
+        let expired = issue(&key(1), &did(&key(2)), r#""exp":1767225600"#, &[]);
+        let token = issue(&key(2), &did(&key(3)), r#""exp":1767225600"#, &[&token_cid(&expired)]);
+        assert!(matches!(verify(&token, &[&expired]), Err(ECDSAError::JwtExpired)));
+        let early = issue(&key(1), &did(&key(2)), r#""nbf":1798675200,"exp":null"#, &[]);
+        assert!(matches!(verify(&early, &[]), Err(ECDSAError::JwtNotYetValid)));
+    }
+
+    #[test]
+    fn test_rejects_bad_signatures() {
+        let (root, _) = chain();
+        let (signing_input, signature) = root.rsplit_once('.').unwrap();
+        let (header, _) = signing_input.split_once('.').unwrap();
+        let forged = issue(&key(5), &did(&key(2)), r#""exp":null"#, &[]).replace(&did(&key(5)), &did(&key(1)));
+        let forged = format!("{header}.{}.{signature}", forged.split('.').nth(1).unwrap());
+        assert!(matches!(verify(&forged, &[]), Err(ECDSAError::JwtSignatureMismatch)));
+
+        // The algorithm must match the issuer's key type
+        let header = to_base64url(br#"{"alg":"ES256","typ":"JWT"}"#);
+        let relabeled = format!("{header}.{}", root.split_once('.').unwrap().1);
+        assert!(matches!(verify(&relabeled, &[]), Err(ECDSAError::UnsupportedJwtAlgorithm)));
+        assert!(matches!(verify("a.b.c", &[]), Err(ECDSAError::InvalidUcan)));
+    }
+}
@@ -0,0 +1,147 @@
+//! ECVRF-SECP256K1-SHA256-TAI verifiable random function proofs: the RFC 9381
+//! try-and-increment procedures instantiated on secp256k1 with suite string `0xFE`.
+//!
+//! A proof is `Gamma || c || s`: the 33-byte compressed point `Gamma = x*H`, a 16-byte
+//! challenge and a 32-byte scalar. The VRF output is derived from `Gamma` alone, so every
+//! valid proof for a key and input yields the same output.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::{FieldBytes, ProjectivePoint, Scalar, U256};
+use sha2::{Digest, Sha256};
+
+use crate::schnorr::decode_point;
+use crate::ECDSAError;
+
+const SUITE: u8 = 0xfe;
+const POINT_LENGTH: usize = 33;
+const CHALLENGE_LENGTH: usize = 16;
+const PROOF_LENGTH: usize = POINT_LENGTH + CHALLENGE_LENGTH + 32;
+
+fn encode(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_bytes().to_vec()
+}
+
+/// Hashes the public key and input to a curve point by trying successive counters until
+/// `0x02 || hash` decodes.
+fn encode_to_curve(public_key: &[u8], alpha: &[u8]) -> Result<ProjectivePoint, ECDSAError> {
+    (0..=u8::MAX)
+        .find_map(|counter| {
+            let hash = Sha256::new().chain_update([SUITE, 0x01]).chain_update(public_key).chain_update(alpha).chain_update([counter, 0x00]).finalize();
+            decode_point(&[&[0x02], hash.as_slice()].concat())
+        })
+        .ok_or(ECDSAError::VrfVerificationFailed)
+}
+
+/// The 16-byte challenge over the key, the hashed input and the proof's points.
+fn challenge(points: [&ProjectivePoint; 5]) -> [u8; CHALLENGE_LENGTH] {
+    let hasher = points.iter().fold(Sha256::new().chain_update([SUITE, 0x02]), |hasher, point| hasher.chain_update(encode(point)));
+    hasher.chain_update([0x00]).finalize()[..CHALLENGE_LENGTH].try_into().unwrap()
+}
+
+fn challenge_scalar(challenge: &[u8; CHALLENGE_LENGTH]) -> Scalar {
+    let mut bytes = FieldBytes::default();
+    bytes[32 - CHALLENGE_LENGTH..].copy_from_slice(challenge);
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(bytes)
+}
+
+/// The VRF output `beta` of a proof's `Gamma`.
+fn proof_to_hash(gamma: &ProjectivePoint) -> [u8; 32] {
+    Sha256::new().chain_update([SUITE, 0x03]).chain_update(encode(gamma)).chain_update([0x00]).finalize().into()
+}
+
+/// Verifies an ECVRF proof and returns the VRF output.
+///
+/// # Arguments:
+/// * `public_key` - The prover's SEC1 public key, provided as a hex string.
+/// * `alpha` - The VRF input, provided as a hex string.
+/// * `proof` - The 81-byte proof, provided as a hex string.
+///
+/// # Returns:
+/// * The 32-byte VRF output `beta`, as a hex string.
+///
+/// # Errors:
+/// * `InvalidVrfProof` if the proof is malformed.
+/// * `VrfVerificationFailed` if the proof does not verify for this key and input.
+#[wasm_bindgen]
+pub fn verify_ecvrf(public_key: &str, alpha: &str, proof: &str) -> Result<String, ECDSAError> {
+    let public_key = k256::PublicKey::from(&crate::keys::parse_public_key_hex(public_key)?).to_projective();
+    let alpha = hex::decode(alpha).map_err(|_| ECDSAError::InvalidDigestLength)?;
+    let proof = hex::decode(proof).ok().filter(|proof| proof.len() == PROOF_LENGTH).ok_or(ECDSAError::InvalidVrfProof)?;
+
+    let (gamma, rest) = proof.split_at(POINT_LENGTH);
+    let (c, s) = rest.split_at(CHALLENGE_LENGTH);
+    let gamma = decode_point(gamma).ok_or(ECDSAError::InvalidVrfProof)?;
+    let c: [u8; CHALLENGE_LENGTH] = c.try_into().unwrap();
+    let s = Option::<Scalar>::from(Scalar::from_repr(FieldBytes::clone_from_slice(s))).ok_or(ECDSAError::InvalidVrfProof)?;
+
+    let h = encode_to_curve(&encode(&public_key), &alpha)?;
+    let c_scalar = challenge_scalar(&c);
+    let u = ProjectivePoint::GENERATOR * s - public_key * c_scalar;
+    let v = h * s - gamma * c_scalar;
+    if challenge([&public_key, &h, &gamma, &u, &v]) != c {
+        return Err(ECDSAError::VrfVerificationFailed);
+    }
+    Ok(hex::encode(proof_to_hash(&gamma)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(seed: &str) -> Scalar {
+        <Scalar as Reduce<U256>>::from_be_bytes_reduced(Sha256::digest(seed.as_bytes()))
+    }
+
+    /// Proves with an arbitrary nonce `k`: `Gamma = x*H`, `c = challenge(Y, H, Gamma, k*G, k*H)`
+    /// and `s = k + c*x`.
+    fn prove(secret_key: Scalar, alpha: &[u8], nonce: &str) -> (String, String) {
+        let public_key = ProjectivePoint::GENERATOR * secret_key;
+        let h = encode_to_curve(&encode(&public_key), alpha).unwrap();
+        let gamma = h * secret_key;
+        let k = scalar(nonce);
+        let c = challenge([&public_key, &h, &gamma, &(ProjectivePoint::GENERATOR * k), &(h * k)]);
+        let s = k + challenge_scalar(&c) * secret_key;
+        (hex::encode(encode(&public_key)), hex::encode([encode(&gamma).as_slice(), &c, &s.to_bytes()].concat()))
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let alpha = b"sample";
+        let (public_key, proof) = prove(scalar("key"), alpha, "nonce");
+        let beta = verify_ecvrf(&public_key, &hex::encode(alpha), &proof).unwrap();
+        assert_eq!(beta.len(), 64);
+
+        // The output depends only on the key and input, not on the prover's nonce
+        let (_, other_proof) = prove(scalar("key"), alpha, "another nonce");
+        assert_ne!(proof, other_proof);
+        assert_eq!(verify_ecvrf(&public_key, &hex::encode(alpha), &other_proof).unwrap(), beta);
+        let (_, other_input) = prove(scalar("key"), b"other", "nonce");
+        assert_ne!(verify_ecvrf(&public_key, &hex::encode(b"other"), &other_input).unwrap(), beta);
+    }
+
+    #[test]
+    fn test_rejects_invalid_proofs() {
+        let (public_key, proof) = prove(scalar("key"), b"sample", "nonce");
+        let (other_key, _) = prove(scalar("other key"), b"sample", "nonce");
+        assert!(matches!(verify_ecvrf(&public_key, "00", &proof), Err(ECDSAError::VrfVerificationFailed)));
+        assert!(matches!(verify_ecvrf(&other_key, &hex::encode(b"sample"), &proof), Err(ECDSAError::VrfVerificationFailed)));
+
+        // An altered response
+        let mut tampered = hex::decode(&proof).unwrap();
+        tampered[80] ^= 1;
+        assert!(matches!(verify_ecvrf(&public_key, &hex::encode(b"sample"), &hex::encode(tampered)), Err(ECDSAError::VrfVerificationFailed)));
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let (public_key, proof) = prove(scalar("key"), b"", "nonce");
+        assert!(matches!(verify_ecvrf(&public_key, "", &proof[2..]), Err(ECDSAError::InvalidVrfProof)));
+        let no_gamma = "00".repeat(POINT_LENGTH) + &proof[2 * POINT_LENGTH..];
+        assert!(matches!(verify_ecvrf(&public_key, "", &no_gamma), Err(ECDSAError::InvalidVrfProof)));
+        let high_s = proof[..2 * (POINT_LENGTH + CHALLENGE_LENGTH)].to_string() + &"ff".repeat(32);
+        assert!(matches!(verify_ecvrf(&public_key, "", &high_s), Err(ECDSAError::InvalidVrfProof)));
+    }
+}
@@ -0,0 +1,148 @@
+//! Feldman verifiable secret sharing on secp256k1, as used by distributed key generation:
+//! a dealer publishes commitments `C_j = a_j * G` to the coefficients of its polynomial
+//! `f(x) = a_0 + a_1*x + ... + a_(t-1)*x^(t-1)`, and each participant checks that its share
+//! `f(i)` satisfies `f(i)*G = sum(C_j * i^j)`.
+//!
+//! Identifiers are 32-byte nonzero scalars, as in FROST.
+
+use wasm_bindgen::prelude::*;
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::ff::PrimeField;
+use k256::{FieldBytes, ProjectivePoint, Scalar};
+
+use crate::schnorr::decode_point;
+use crate::ECDSAError;
+
+fn parse_scalar(scalar: &str) -> Option<Scalar> {
+    let bytes: [u8; 32] = hex::decode(scalar).ok()?.try_into().ok()?;
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes)))
+}
+
+fn parse_identifier(identifier: &str) -> Result<Scalar, ECDSAError> {
+    parse_scalar(identifier).filter(|identifier| !bool::from(identifier.is_zero())).ok_or(ECDSAError::InvalidVssShare)
+}
+
+/// Parses the dealer's commitments, the constant term's first.
+fn parse_commitments(commitments: &[String]) -> Result<Vec<ProjectivePoint>, ECDSAError> {
+    if commitments.is_empty() {
+        return Err(ECDSAError::InvalidVssCommitment);
+    }
+    commitments
+        .iter()
+        .map(|commitment| hex::decode(commitment).ok().and_then(|bytes| decode_point(&bytes)).ok_or(ECDSAError::InvalidVssCommitment))
+        .collect()
+}
+
+/// Evaluates the committed polynomial "in the exponent": `sum(C_j * x^j)`, which is
+/// `f(x) * G`.
+fn evaluate_commitments(commitments: &[ProjectivePoint], x: &Scalar) -> ProjectivePoint {
+    commitments.iter().rev().fold(ProjectivePoint::IDENTITY, |acc, commitment| acc * x + commitment)
+}
+
+/// Verifies a share from a dealer against the dealer's published commitments.
+///
+/// # Arguments:
+/// * `identifier` - The receiving participant's 32-byte identifier, provided as a hex string.
+/// * `share` - The 32-byte share `f(identifier)`, provided as a hex string.
+/// * `commitments` - The dealer's 33-byte compressed coefficient commitments, constant term
+///   first, provided as hex strings. Their number is the threshold.
+///
+/// # Returns:
+/// * A boolean indicating whether the share is consistent with the commitments; a `false`
+///   identifies a bad dealer.
+///
+/// # Errors:
+/// * `InvalidVssShare` if the identifier or share is not a valid scalar.
+/// * `InvalidVssCommitment` if there are no commitments or one is malformed.
+#[wasm_bindgen]
+pub fn verify_feldman_share(identifier: &str, share: &str, commitments: Vec<String>) -> Result<bool, ECDSAError> {
+    let identifier = parse_identifier(identifier)?;
+    let share = parse_scalar(share).ok_or(ECDSAError::InvalidVssShare)?;
+    let commitments = parse_commitments(&commitments)?;
+    Ok(ProjectivePoint::GENERATOR * share == evaluate_commitments(&commitments, &identifier))
+}
+
+/// Computes a participant's public verifying share `f(identifier) * G` from the summed
+/// commitments, which is what FROST checks that participant's signature shares against.
+///
+/// # Arguments:
+/// * `identifier` - The participant's 32-byte identifier, provided as a hex string.
+/// * `commitments` - The 33-byte compressed coefficient commitments, constant term first,
+///   provided as hex strings.
+///
+/// # Returns:
+/// * The 33-byte compressed verifying share, as a hex string.
+///
+/// # Errors:
+/// * `InvalidVssShare` if the identifier is not a valid scalar.
+/// * `InvalidVssCommitment` if a commitment is malformed or the share is the point at infinity.
+#[wasm_bindgen]
+pub fn feldman_verifying_share(identifier: &str, commitments: Vec<String>) -> Result<String, ECDSAError> {
+    let identifier = parse_identifier(identifier)?;
+    let point = evaluate_commitments(&parse_commitments(&commitments)?, &identifier);
+    if point == ProjectivePoint::IDENTITY {
+        return Err(ECDSAError::InvalidVssCommitment);
+    }
+    Ok(hex::encode(point.to_affine().to_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::elliptic_curve::ops::Reduce;
+    use k256::U256;
+    use sha2::{Digest, Sha256};
+
+    fn scalar(seed: &str) -> Scalar {
+        <Scalar as Reduce<U256>>::from_be_bytes_reduced(Sha256::digest(seed.as_bytes()))
+    }
+
+    fn hex_scalar(scalar: Scalar) -> String {
+        hex::encode(scalar.to_bytes())
+    }
+
+    /// A 3-of-n dealing: the polynomial's coefficients and their commitments.
+    fn dealer() -> (Vec<Scalar>, Vec<String>) {
+        let coefficients: Vec<Scalar> = ["a0", "a1", "a2"].iter().map(|seed| scalar(seed)).collect();
+        let commitments = coefficients.iter().map(|a| hex::encode((ProjectivePoint::GENERATOR * a).to_affine().to_bytes())).collect();
+        (coefficients, commitments)
+    }
+
+    fn share(coefficients: &[Scalar], x: u64) -> Scalar {
+        let x = Scalar::from(x);
+        coefficients.iter().rev().fold(Scalar::ZERO, |acc, a| acc * x + a)
+    }
+
+    #[test]
+    fn test_verify_shares() {
+        let (coefficients, commitments) = dealer();
+        for i in 1..=5 {
+            let identifier = hex_scalar(Scalar::from(i));
+            let value = share(&coefficients, i);
+            assert!(verify_feldman_share(&identifier, &hex_scalar(value), commitments.clone()).unwrap(), "{i}");
+            // A share for another participant, or an altered share, is detected
+            assert!(!verify_feldman_share(&identifier, &hex_scalar(share(&coefficients, i + 1)), commitments.clone()).unwrap());
+            assert!(!verify_feldman_share(&identifier, &hex_scalar(value + Scalar::ONE), commitments.clone()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_verifying_share() {
+        let (coefficients, commitments) = dealer();
+        let identifier = hex_scalar(Scalar::from(7u64));
+        let expected = (ProjectivePoint::GENERATOR * share(&coefficients, 7)).to_affine().to_bytes();
+        assert_eq!(feldman_verifying_share(&identifier, commitments.clone()).unwrap(), hex::encode(expected));
+        // A single commitment is a constant polynomial: every share is the secret
+        assert_eq!(feldman_verifying_share(&identifier, commitments[..1].to_vec()).unwrap(), commitments[0]);
+    }
+
+    #[test]
+    fn test_rejects_malformed() {
+        let (_, commitments) = dealer();
+        let one = hex_scalar(Scalar::ONE);
+        assert!(matches!(verify_feldman_share(&hex_scalar(Scalar::ZERO), &one, commitments.clone()), Err(ECDSAError::InvalidVssShare)));
+        assert!(matches!(verify_feldman_share(&one, "ff", commitments), Err(ECDSAError::InvalidVssShare)));
+        assert!(matches!(verify_feldman_share(&one, &one, vec![]), Err(ECDSAError::InvalidVssCommitment)));
+        assert!(matches!(verify_feldman_share(&one, &one, vec!["00".repeat(33)]), Err(ECDSAError::InvalidVssCommitment)));
+    }
+}
@@ -0,0 +1,784 @@
+//! WebAuthn (Level 2) assertion verification for passkey logins, and attestation
+//! verification for registrations.
+//!
+//! The authenticator signs `authenticatorData || SHA-256(clientDataJSON)` with the
+//! credential's private key, encoding ECDSA signatures as DER. The authenticator data
+//! starts with the SHA-256 of the relying party ID, a flags byte and a big-endian
+//! 32-bit signature counter. At registration it also carries the new credential, and the
+//! attestation object wraps it with a statement from the authenticator vouching for it.
+
+use wasm_bindgen::prelude::*;
+use js_sys::Uint8Array;
+use sha2::{Digest, Sha256};
+
+use crate::cbor::{self, Value};
+use crate::cose::{parse_cose_key, CoseAlgorithm};
+use crate::json;
+use crate::keys::asn1::{parse_spki, Der, TAG_OCTET_STRING};
+use crate::time::{now_millis, parse_rfc3339};
+use crate::x509::{parse_certificate, validate_chain};
+use crate::ECDSAError;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_BACKUP_ELIGIBLE: u8 = 0x08;
+const FLAG_BACKED_UP: u8 = 0x10;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+const FLAG_EXTENSIONS: u8 = 0x80;
+
+/// id-fido-gen-ce-aaguid (1.3.6.1.4.1.45724.1.1.4)
+const OID_FIDO_AAGUID: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0xe5, 0x1c, 0x01, 0x01, 0x04];
+/// Apple's anonymous attestation nonce extension (1.2.840.113635.100.8.2)
+const OID_APPLE_NONCE: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x63, 0x64, 0x08, 0x02];
+/// id-at-organizationalUnitName (2.5.4.11)
+const OID_ORGANIZATIONAL_UNIT: &[u8] = &[0x55, 0x04, 0x0b];
+
+/// The fixed-size head of the authenticator data: RP ID hash, flags and sign count.
+const AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// A credential created at registration, present when the AT flag is set.
+pub(crate) struct AttestedCredential {
+    pub(crate) aaguid: [u8; 16],
+    pub(crate) credential_id: Vec<u8>,
+    /// The credential public key as a COSE_Key, exactly as encoded by the authenticator
+    pub(crate) public_key: Vec<u8>,
+}
+
+/// The parts of an authenticator data structure used when verifying assertions and attestations.
+pub(crate) struct AuthenticatorData {
+    pub(crate) rp_id_hash: [u8; 32],
+    pub(crate) flags: u8,
+    pub(crate) sign_count: u32,
+    pub(crate) attested_credential: Option<AttestedCredential>,
+}
+
+/// Parses authenticator data. The attested credential and the extensions map must be present
+/// exactly when their flags (AT and ED) are set, and nothing may follow them.
+pub(crate) fn parse_authenticator_data(bytes: &[u8]) -> Result<AuthenticatorData, ECDSAError> {
+    if bytes.len() < AUTHENTICATOR_DATA_LENGTH {
+        return Err(ECDSAError::InvalidWebAuthnData);
+    }
+    let flags = bytes[32];
+    let mut rest = &bytes[AUTHENTICATOR_DATA_LENGTH..];
+    let mut attested_credential = None;
+    if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        if rest.len() < 18 {
+            return Err(ECDSAError::InvalidWebAuthnData);
+        }
+        let id_length = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+        let credential_id = rest.get(18..18 + id_length).ok_or(ECDSAError::InvalidWebAuthnData)?;
+        let key_start = 18 + id_length;
+        let (_, key_length) = cbor::decode_prefix(&rest[key_start..]).map_err(|_| ECDSAError::InvalidWebAuthnData)?;
+        attested_credential = Some(AttestedCredential {
+            aaguid: rest[..16].try_into().unwrap(),
+            credential_id: credential_id.to_vec(),
+            public_key: rest[key_start..key_start + key_length].to_vec(),
+        });
+        rest = &rest[key_start + key_length..];
+    }
+    if flags & FLAG_EXTENSIONS != 0 {
+        match cbor::decode(rest) {
+            Ok(Value::Map(_)) => rest = &[],
+            _ => return Err(ECDSAError::InvalidWebAuthnData),
+        }
+    }
+    if !rest.is_empty() {
+        return Err(ECDSAError::InvalidWebAuthnData);
+    }
+    Ok(AuthenticatorData {
+        rp_id_hash: bytes[..32].try_into().unwrap(),
+        flags,
+        sign_count: u32::from_be_bytes(bytes[33..37].try_into().unwrap()),
+        attested_credential,
+    })
+}
+
+/// The members of `clientDataJSON` the relying party checks.
+pub(crate) struct ClientData {
+    pub(crate) ceremony: String,
+    pub(crate) challenge: String,
+    pub(crate) origin: String,
+}
+
+pub(crate) fn parse_client_data(bytes: &[u8]) -> Result<ClientData, ECDSAError> {
+    let text = std::str::from_utf8(bytes).map_err(|_| ECDSAError::InvalidWebAuthnData)?;
+    let client_data = json::parse(text).map_err(|_| ECDSAError::InvalidWebAuthnData)?;
+    let member = |name: &str| {
+        client_data.get(name).and_then(json::Value::as_str).map(str::to_string).ok_or(ECDSAError::InvalidWebAuthnData)
+    };
+    Ok(ClientData { ceremony: member("type")?, challenge: member("challenge")?, origin: member("origin")? })
+}
+
+/// A verified WebAuthn assertion.
+///
+/// The signature has been checked, but the relying party must still compare the challenge,
+/// origin and RP ID hash with its own values and check the sign count against the one stored.
+#[wasm_bindgen]
+pub struct WebAuthnAssertion {
+    authenticator_data: AuthenticatorData,
+    client_data: ClientData,
+}
+
+#[wasm_bindgen]
+impl WebAuthnAssertion {
+    /// The SHA-256 of the relying party ID, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn rp_id_hash(&self) -> String {
+        hex::encode(self.authenticator_data.rp_id_hash)
+    }
+
+    /// The signature counter, or 0 if the authenticator does not keep one.
+    #[wasm_bindgen(getter)]
+    pub fn sign_count(&self) -> u32 {
+        self.authenticator_data.sign_count
+    }
+
+    /// The raw flags byte.
+    #[wasm_bindgen(getter)]
+    pub fn flags(&self) -> u8 {
+        self.authenticator_data.flags
+    }
+
+    /// Whether the user was present (UP).
+    #[wasm_bindgen(getter)]
+    pub fn user_present(&self) -> bool {
+        self.authenticator_data.flags & FLAG_USER_PRESENT != 0
+    }
+
+    /// Whether the user was verified, e.g. by biometrics or a PIN (UV).
+    #[wasm_bindgen(getter)]
+    pub fn user_verified(&self) -> bool {
+        self.authenticator_data.flags & FLAG_USER_VERIFIED != 0
+    }
+
+    /// Whether the credential may be synced, as passkeys are (BE).
+    #[wasm_bindgen(getter)]
+    pub fn backup_eligible(&self) -> bool {
+        self.authenticator_data.flags & FLAG_BACKUP_ELIGIBLE != 0
+    }
+
+    /// Whether the credential is currently backed up (BS).
+    #[wasm_bindgen(getter)]
+    pub fn backed_up(&self) -> bool {
+        self.authenticator_data.flags & FLAG_BACKED_UP != 0
+    }
+
+    /// The base64url challenge from the client data.
+    #[wasm_bindgen(getter)]
+    pub fn challenge(&self) -> String {
+        self.client_data.challenge.clone()
+    }
+
+    /// The origin the browser reported, e.g. `https://example.com`.
+    #[wasm_bindgen(getter)]
+    pub fn origin(&self) -> String {
+        self.client_data.origin.clone()
+    }
+}
+
+/// Verifies a WebAuthn assertion (`navigator.credentials.get`) against a stored credential.
+///
+/// # Arguments:
+/// * `authenticator_data` - `response.authenticatorData` (`Uint8Array` in JS).
+/// * `client_data_json` - `response.clientDataJSON`, exactly as received.
+/// * `signature` - `response.signature`, a DER encoded ECDSA signature.
+/// * `credential_pubkey_cose` - The credential public key saved at registration, as a COSE_Key.
+///   ES256 (P-256, with the `p256` feature) and ES256K keys are supported.
+///
+/// # Returns:
+/// * The assertion's flags, sign count, challenge and origin. The user must have been present.
+///
+/// # Errors:
+/// * `InvalidWebAuthnData` if the data is malformed, the client data is not for `webauthn.get`,
+///   or the user presence flag is not set.
+/// * `WebAuthnSignatureMismatch` if the signature does not verify.
+/// * `InvalidCoseKey`, `UnsupportedCoseAlgorithm` or `UnsupportedCurve` if the key cannot be used.
+#[wasm_bindgen]
+pub fn verify_webauthn_assertion(
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+    credential_pubkey_cose: &[u8],
+) -> Result<WebAuthnAssertion, ECDSAError> {
+    let (algorithm, public_key) = parse_cose_key(credential_pubkey_cose)?;
+    let parsed_authenticator_data = parse_authenticator_data(authenticator_data)?;
+    let client_data = parse_client_data(client_data_json)?;
+    if client_data.ceremony != "webauthn.get" || parsed_authenticator_data.flags & FLAG_USER_PRESENT == 0 {
+        return Err(ECDSAError::InvalidWebAuthnData);
+    }
+
+    let signed = [authenticator_data, &Sha256::digest(client_data_json)].concat();
+    if !algorithm.verify_der(&public_key, &signed, signature)? {
+        return Err(ECDSAError::WebAuthnSignatureMismatch);
+    }
+    Ok(WebAuthnAssertion { authenticator_data: parsed_authenticator_data, client_data })
+}
+
+/// A verified WebAuthn registration.
+///
+/// The relying party must still compare the challenge, origin and RP ID hash with its own
+/// values, and decide whether the attestation type meets its policy before storing the
+/// credential ID and public key.
+#[wasm_bindgen]
+pub struct WebAuthnRegistration {
+    format: String,
+    attestation_type: &'static str,
+    authenticator_data: AuthenticatorData,
+    credential: AttestedCredential,
+    client_data: ClientData,
+}
+
+#[wasm_bindgen]
+impl WebAuthnRegistration {
+    /// The attestation statement format: `none`, `packed` or `apple`.
+    #[wasm_bindgen(getter)]
+    pub fn format(&self) -> String {
+        self.format.clone()
+    }
+
+    /// How the credential was attested: `none`, `self` (signed by the credential key),
+    /// `basic` (an attestation certificate chaining to a trust anchor) or `anonca` (Apple's
+    /// per-credential certificate).
+    #[wasm_bindgen(getter)]
+    pub fn attestation_type(&self) -> String {
+        self.attestation_type.to_string()
+    }
+
+    /// The authenticator model's AAGUID, as a hex string. All zeros when not attested.
+    #[wasm_bindgen(getter)]
+    pub fn aaguid(&self) -> String {
+        hex::encode(self.credential.aaguid)
+    }
+
+    /// The credential ID to store and later pass in `allowCredentials`.
+    #[wasm_bindgen(getter)]
+    pub fn credential_id(&self) -> Vec<u8> {
+        self.credential.credential_id.clone()
+    }
+
+    /// The credential public key as a COSE_Key, for `verify_webauthn_assertion`.
+    #[wasm_bindgen(getter)]
+    pub fn credential_public_key(&self) -> Vec<u8> {
+        self.credential.public_key.clone()
+    }
+
+    /// The SHA-256 of the relying party ID, as a hex string.
+    #[wasm_bindgen(getter)]
+    pub fn rp_id_hash(&self) -> String {
+        hex::encode(self.authenticator_data.rp_id_hash)
+    }
+
+    /// The initial signature counter.
+    #[wasm_bindgen(getter)]
+    pub fn sign_count(&self) -> u32 {
+        self.authenticator_data.sign_count
+    }
+
+    /// The raw flags byte.
+    #[wasm_bindgen(getter)]
+    pub fn flags(&self) -> u8 {
+        self.authenticator_data.flags
+    }
+
+    /// Whether the user was verified (UV).
+    #[wasm_bindgen(getter)]
+    pub fn user_verified(&self) -> bool {
+        self.authenticator_data.flags & FLAG_USER_VERIFIED != 0
+    }
+
+    /// Whether the credential may be synced (BE).
+    #[wasm_bindgen(getter)]
+    pub fn backup_eligible(&self) -> bool {
+        self.authenticator_data.flags & FLAG_BACKUP_ELIGIBLE != 0
+    }
+
+    /// Whether the credential is currently backed up (BS).
+    #[wasm_bindgen(getter)]
+    pub fn backed_up(&self) -> bool {
+        self.authenticator_data.flags & FLAG_BACKED_UP != 0
+    }
+
+    /// The base64url challenge from the client data.
+    #[wasm_bindgen(getter)]
+    pub fn challenge(&self) -> String {
+        self.client_data.challenge.clone()
+    }
+
+    /// The origin the browser reported.
+    #[wasm_bindgen(getter)]
+    pub fn origin(&self) -> String {
+        self.client_data.origin.clone()
+    }
+}
+
+/// Reads the `x5c` certificate chain of an attestation statement, if present.
+fn certificate_chain(statement: &Value) -> Result<Option<Vec<&[u8]>>, ECDSAError> {
+    let Some(chain) = statement.get_text("x5c") else {
+        return Ok(None);
+    };
+    let chain = chain.as_array().filter(|chain| !chain.is_empty()).ok_or(ECDSAError::InvalidAttestation)?;
+    chain.iter().map(|certificate| certificate.as_bytes().ok_or(ECDSAError::InvalidAttestation)).collect::<Result<_, _>>().map(Some)
+}
+
+/// Verifies a `packed` statement (WebAuthn section 8.2), returning the attestation type.
+fn verify_packed<A: AsRef<[u8]>>(
+    statement: &Value,
+    signed: &[u8],
+    credential: &AttestedCredential,
+    (algorithm, public_key): (CoseAlgorithm, &[u8]),
+    trust_anchors: &[A],
+    time: i64,
+) -> Result<&'static str, ECDSAError> {
+    let statement_algorithm = statement.get_text("alg").and_then(Value::as_i64).ok_or(ECDSAError::InvalidAttestation)?;
+    let statement_algorithm = CoseAlgorithm::from_id(statement_algorithm)?;
+    let signature = statement.get_text("sig").and_then(Value::as_bytes).ok_or(ECDSAError::InvalidAttestation)?;
+
+    let Some(chain) = certificate_chain(statement)? else {
+        // Self attestation: the credential key signs its own registration
+        if statement_algorithm != algorithm {
+            return Err(ECDSAError::InvalidAttestation);
+        }
+        if !algorithm.verify_der(public_key, signed, signature)? {
+            return Err(ECDSAError::WebAuthnSignatureMismatch);
+        }
+        return Ok("self");
+    };
+
+    let certificate = parse_certificate(chain[0])?;
+    let spki = parse_spki(certificate.spki)?;
+    let is_ca = certificate.basic_constraints.as_ref().is_some_and(|constraints| constraints.ca);
+    if spki.algorithm != statement_algorithm.key_algorithm()
+        || certificate.version != 2
+        || certificate.subject_attribute(OID_ORGANIZATIONAL_UNIT) != Some(b"Authenticator Attestation")
+        || is_ca
+    {
+        return Err(ECDSAError::InvalidAttestation);
+    }
+    if let Some(extension) = certificate.extension(OID_FIDO_AAGUID) {
+        let mut value = Der::new(extension);
+        let aaguid = value.read(TAG_OCTET_STRING).map_err(|_| ECDSAError::InvalidAttestation)?;
+        if !value.is_empty() || aaguid != credential.aaguid {
+            return Err(ECDSAError::InvalidAttestation);
+        }
+    }
+    if !statement_algorithm.verify_der(spki.public_key, signed, signature)? {
+        return Err(ECDSAError::WebAuthnSignatureMismatch);
+    }
+    validate_chain(chain[0], &chain[1..], trust_anchors, time)?;
+    Ok("basic")
+}
+
+/// Verifies an `apple` anonymous statement (WebAuthn section 8.8), returning the attestation type.
+/// The credential certificate carries a nonce over the signed data instead of a signature.
+fn verify_apple<A: AsRef<[u8]>>(
+    statement: &Value,
+    signed: &[u8],
+    (algorithm, public_key): (CoseAlgorithm, &[u8]),
+    trust_anchors: &[A],
+    time: i64,
+) -> Result<&'static str, ECDSAError> {
+    let chain = certificate_chain(statement)?.ok_or(ECDSAError::InvalidAttestation)?;
+    let certificate = parse_certificate(chain[0])?;
+    let extension = certificate.extension(OID_APPLE_NONCE).ok_or(ECDSAError::InvalidAttestation)?;
+    let mut outer = Der::new(extension);
+    let nonce = outer
+        .sequence()
+        .and_then(|mut sequence| {
+            let mut wrapper = sequence.optional_explicit(1)?.ok_or(ECDSAError::InvalidKeyEncoding)?;
+            let nonce = wrapper.read(TAG_OCTET_STRING)?;
+            wrapper.finish()?;
+            sequence.finish()?;
+            outer.finish()?;
+            Ok(nonce)
+        })
+        .map_err(|_| ECDSAError::InvalidAttestation)?;
+    let spki = parse_spki(certificate.spki)?;
+    if nonce != Sha256::digest(signed).as_slice() || spki.algorithm != algorithm.key_algorithm() || spki.public_key != public_key {
+        return Err(ECDSAError::InvalidAttestation);
+    }
+    validate_chain(chain[0], &chain[1..], trust_anchors, time)?;
+    Ok("anonca")
+}
+
+/// Verifies an attestation object at `time`, in seconds since the Unix epoch.
+pub(crate) fn verify_attestation<A: AsRef<[u8]>>(
+    attestation_object: &[u8],
+    client_data_json: &[u8],
+    trust_anchors: &[A],
+    time: i64,
+) -> Result<WebAuthnRegistration, ECDSAError> {
+    let object = cbor::decode(attestation_object).map_err(|_| ECDSAError::InvalidAttestation)?;
+    let format = object.get_text("fmt").and_then(Value::as_text).ok_or(ECDSAError::InvalidAttestation)?;
+    let statement = object.get_text("attStmt").filter(|statement| statement.as_map().is_some());
+    let statement = statement.ok_or(ECDSAError::InvalidAttestation)?;
+    let raw_authenticator_data = object.get_text("authData").and_then(Value::as_bytes).ok_or(ECDSAError::InvalidAttestation)?;
+
+    let mut authenticator_data = parse_authenticator_data(raw_authenticator_data)?;
+    let client_data = parse_client_data(client_data_json)?;
+    if client_data.ceremony != "webauthn.create" || authenticator_data.flags & FLAG_USER_PRESENT == 0 {
+        return Err(ECDSAError::InvalidWebAuthnData);
+    }
+    let credential = authenticator_data.attested_credential.take().ok_or(ECDSAError::InvalidWebAuthnData)?;
+    let (algorithm, public_key) = parse_cose_key(&credential.public_key)?;
+
+    let signed = [raw_authenticator_data, &Sha256::digest(client_data_json)].concat();
+    let attestation_type = match format {
+        "none" if statement.as_map() == Some(&[]) => "none",
+        "none" => return Err(ECDSAError::InvalidAttestation),
+        "packed" => verify_packed(statement, &signed, &credential, (algorithm, &public_key), trust_anchors, time)?,
+        "apple" => verify_apple(statement, &signed, (algorithm, &public_key), trust_anchors, time)?,
+        _ => return Err(ECDSAError::UnsupportedAttestationFormat),
+    };
+    Ok(WebAuthnRegistration {
+        format: format.to_string(),
+        attestation_type,
+        authenticator_data,
+        credential,
+        client_data,
+    })
+}
+
+/// Verifies a WebAuthn registration (`navigator.credentials.create`) and its attestation.
+///
+/// Supports the `none`, `packed` (self and certificate attestation) and `apple` formats.
+/// Attestation certificate chains are validated like `verify_chain`.
+///
+/// # Arguments:
+/// * `attestation_object` - `response.attestationObject` (`Uint8Array` in JS).
+/// * `client_data_json` - `response.clientDataJSON`, exactly as received.
+/// * `trust_anchors` - DER root certificates for the authenticators to accept, such as those
+///   from the FIDO Metadata Service or Apple's WebAuthn root (an array of `Uint8Array`).
+/// * `time` - An optional RFC 3339 timestamp to validate certificates at, defaulting to now.
+///
+/// # Returns:
+/// * The registration's attestation type, credential ID and public key, flags and client data.
+///
+/// # Errors:
+/// * `InvalidWebAuthnData` if the data is malformed, the client data is not for
+///   `webauthn.create`, or the user presence or attested credential flag is not set.
+/// * `InvalidAttestation` if the attestation statement or certificate does not meet the format's
+///   requirements, or `time` is malformed.
+/// * `UnsupportedAttestationFormat` for formats other than those above.
+/// * `WebAuthnSignatureMismatch` if the attestation signature does not verify.
+/// * The errors of `verify_chain` if the attestation certificate is not trusted at `time`.
+/// * `InvalidCoseKey`, `UnsupportedCoseAlgorithm` or `UnsupportedCurve` if the credential key cannot be used.
+#[wasm_bindgen]
+pub fn verify_webauthn_attestation(
+    attestation_object: &[u8],
+    client_data_json: &[u8],
+    trust_anchors: Vec<Uint8Array>,
+    time: Option<String>,
+) -> Result<WebAuthnRegistration, ECDSAError> {
+    let time = match time {
+        Some(time) => parse_rfc3339(&time).ok_or(ECDSAError::InvalidAttestation)?,
+        None => now_millis(),
+    };
+    let trust_anchors = trust_anchors.iter().map(Uint8Array::to_vec).collect::<Vec<_>>();
+    verify_attestation(attestation_object, client_data_json, &trust_anchors, time.div_euclid(1000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "p256")]
+    use crate::pem::decode_pem;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+    const CLIENT_DATA: &[u8] =
+        br#"{"type":"webauthn.get","challenge":"dGVzdC1jaGFsbGVuZ2U","origin":"https://example.com","crossOrigin":false}"#;
+
+    const CREATE_CLIENT_DATA: &[u8] = br#"{"type":"webauthn.create","challenge":"cmVnaXN0ZXI","origin":"https://example.com"}"#;
+
+    // A P-256 root, a packed attestation certificate for AAGUID 0102..10 and an Apple-style
+    // certificate for the credential key, all valid from 2025 to 2035
+    #[cfg(feature = "p256")]
+    const ATTESTATION_ROOT: &str = "-----BEGIN CERTIFICATE-----
+MIIBNzCB3qADAgECAgEBMAoGCCqGSM49BAMCMBsxGTAXBgNVBAMMEEF0dGVzdGF0
+aW9uIFJvb3QwHhcNMjUwMTAxMDAwMDAwWhcNMzUwMTAxMDAwMDAwWjAbMRkwFwYD
+VQQDDBBBdHRlc3RhdGlvbiBSb290MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE
+D3Xp6Wpb/39Ndc94y9KKyaOjwa1hFmxFwMmpQkLfLE5fmVmo8jp3ziVcpq/GKF2z
+dKCPJwSrEGhD4tswJ1ClDaMTMBEwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQD
+AgNIADBFAiEAr+ZUfHbxnn9Hn6PgzG0BcE+MHTi3VKH7IEmFpPiVwo4CIA03fsoB
+1dqknjA/Uk6YznLScYvG4K/wbNCk9A/Ysyeh
+-----END CERTIFICATE-----";
+    #[cfg(feature = "p256")]
+    const PACKED_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBoTCCAUegAwIBAgIBAjAKBggqhkjOPQQDAjAbMRkwFwYDVQQDDBBBdHRlc3Rh
+dGlvbiBSb290MB4XDTI1MDEwMTAwMDAwMFoXDTM1MDEwMTAwMDAwMFowZDELMAkG
+A1UEBhMCVVMxFDASBgNVBAoMC1Rlc3QgVmVuZG9yMSIwIAYDVQQLDBlBdXRoZW50
+aWNhdG9yIEF0dGVzdGF0aW9uMRswGQYDVQQDDBJUZXN0IEF1dGhlbnRpY2F0b3Iw
+WTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASDPv+NVJoJ2a2dhgaltFLnchic4WuN
+UI+c2//51beFZughZqAEguIysXJA9yhSLTBNSyB9W54HKN1XJstAomW9ozMwMTAM
+BgNVHRMBAf8EAjAAMCEGCysGAQQBguUcAQEEBBIEEAECAwQFBgcICQoLDA0ODxAw
+CgYIKoZIzj0EAwIDSAAwRQIhAOZQRUkVPZ0EaMdpQ2YyicfCyfmsY8B1Km0Tfqpk
+xCKQAiBgkiSXjQUwgOp3uMqLiYMerCPjoQ6OpnOGA7GDybb93A==
+-----END CERTIFICATE-----";
+    #[cfg(feature = "p256")]
+    const APPLE_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBWzCCAQKgAwIBAgIBAzAKBggqhkjOPQQDAjAbMRkwFwYDVQQDDBBBdHRlc3Rh
+dGlvbiBSb290MB4XDTI1MDEwMTAwMDAwMFoXDTM1MDEwMTAwMDAwMFowGzEZMBcG
+A1UEAwwQQXBwbGUgQ3JlZGVudGlhbDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IA
+BN6k8YjkmqHui3RxrVwYA1BQamOdC5DhbnOGsAf0qGNrVFiX56YVEovbczvAlOiq
+dur+Re7LKIEDXdZ8ylrYbXejNzA1MDMGCSqGSIb3Y2QIAgQmMCShIgQgx79pTbDN
+CwRQpm9c8q7l3M2neHPzHBj/ZIEPJGu/Sv8wCgYIKoZIzj0EAwIDRwAwRAIgNJph
+KTu7LWUry5pGHnvVOqCP0LGhP2w3QvTpJIxksRICIA4OGNlk6iIwLebovjqHcQu5
+H6hWnNmqKEW7KDxBObEk
+-----END CERTIFICATE-----";
+    #[cfg(feature = "p256")]
+    const CREDENTIAL_KEY: &str = "a5010203262001215820dea4f188e49aa1ee8b7471ad5c180350506a639d0b90e16e7386b007f4a8636b225820545897e7a615128bdb733bc094e8aa76eafe45eecb2881035dd67cca5ad86d77";
+    #[cfg(feature = "p256")]
+    const PACKED_SIGNATURE: &str = "3045022100d93b4c2b3653afe94b365a6970a7adf193fd12060b05f10411cc9904c67c37f2022009fa5b4d7c84fa62a5a2e7432bb9d4ed05582bc65e4f18cb9508a177df5d48b3";
+
+    fn authenticator_data(flags: u8, sign_count: u32) -> Vec<u8> {
+        [&Sha256::digest(b"example.com")[..], &[flags], &sign_count.to_be_bytes()].concat()
+    }
+
+    /// Registration authenticator data (UP, UV and AT) for a 16-byte credential ID.
+    fn registration_data(aaguid: &[u8], cose_key: &[u8]) -> Vec<u8> {
+        [authenticator_data(0x45, 0), aaguid.to_vec(), vec![0, 16], vec![0xc0; 16], cose_key.to_vec()].concat()
+    }
+
+    fn es256k_cose_key() -> Vec<u8> {
+        let point = SigningKey::from_bytes(&[0x2a; 32]).unwrap().verifying_key().to_encoded_point(false);
+        cbor::encode(&Value::Map(vec![
+            (Value::Integer(1), Value::Integer(2)),
+            (Value::Integer(3), Value::Integer(-47)),
+            (Value::Integer(-1), Value::Integer(8)),
+            (Value::Integer(-2), Value::Bytes(point.x().unwrap().to_vec())),
+            (Value::Integer(-3), Value::Bytes(point.y().unwrap().to_vec())),
+        ]))
+    }
+
+    /// A DER ES256K signature by the `es256k_cose_key` credential over the given data.
+    fn es256k_sign(authenticator_data: &[u8], client_data: &[u8]) -> Vec<u8> {
+        let key = SigningKey::from_bytes(&[0x2a; 32]).unwrap();
+        let signed = [authenticator_data, &Sha256::digest(client_data)].concat();
+        let (sig, _) = sign_digest(&key, &Sha256::digest(&signed), &[]).unwrap();
+        sig.to_der().as_bytes().to_vec()
+    }
+
+    /// An ES256K credential and a signature over the given data.
+    fn es256k_assertion(authenticator_data: &[u8], client_data: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        (es256k_cose_key(), es256k_sign(authenticator_data, client_data))
+    }
+
+    fn attestation_object(format: &str, statement: Vec<(&str, Value)>, authenticator_data: &[u8]) -> Vec<u8> {
+        let statement = statement.into_iter().map(|(key, value)| (Value::Text(key.to_string()), value)).collect();
+        cbor::encode(&Value::Map(vec![
+            (Value::Text("fmt".to_string()), Value::Text(format.to_string())),
+            (Value::Text("attStmt".to_string()), Value::Map(statement)),
+            (Value::Text("authData".to_string()), Value::Bytes(authenticator_data.to_vec())),
+        ]))
+    }
+
+    #[cfg(feature = "p256")]
+    fn der(pem: &str) -> Vec<u8> {
+        decode_pem(pem, &["CERTIFICATE"]).unwrap().1
+    }
+
+    #[cfg(feature = "p256")]
+    fn at(time: &str) -> i64 {
+        parse_rfc3339(time).unwrap() / 1000
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_verify_es256_assertion() {
+        let cose_key = hex::decode(
+            "a501020326200121582060fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+             2258207903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299",
+        )
+        .unwrap();
+        let authenticator_data = authenticator_data(0x05, 7);
+        let signature = hex::decode(
+            "3044022049eb92b5435e3c183d642c00e49dc57e4a2b0d726c356a815a4f55fda639da38\
+             02206a618dee7e9280a389892a10039fdecdc7ef815efc51865240efe710a711b6d2",
+        )
+        .unwrap();
+
+        let assertion = verify_webauthn_assertion(&authenticator_data, CLIENT_DATA, &signature, &cose_key).unwrap();
+        assert_eq!(assertion.rp_id_hash(), hex::encode(Sha256::digest(b"example.com")));
+        assert_eq!(assertion.sign_count(), 7);
+        assert!(assertion.user_present() && assertion.user_verified());
+        assert!(!assertion.backup_eligible() && !assertion.backed_up());
+        assert_eq!(assertion.challenge(), "dGVzdC1jaGFsbGVuZ2U");
+        assert_eq!(assertion.origin(), "https://example.com");
+
+        let mut tampered = authenticator_data.clone();
+        tampered[36] ^= 1;
+        assert!(matches!(
+            verify_webauthn_assertion(&tampered, CLIENT_DATA, &signature, &cose_key),
+            Err(ECDSAError::WebAuthnSignatureMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_verify_es256k_assertion() {
+        let authenticator_data = authenticator_data(0x1d, 0x0102_0304);
+        let (cose_key, signature) = es256k_assertion(&authenticator_data, CLIENT_DATA);
+        let assertion = verify_webauthn_assertion(&authenticator_data, CLIENT_DATA, &signature, &cose_key).unwrap();
+        assert_eq!(assertion.sign_count(), 0x0102_0304);
+        assert_eq!(assertion.flags(), 0x1d);
+        assert!(assertion.backup_eligible() && assertion.backed_up());
+
+        let other_client_data = String::from_utf8(CLIENT_DATA.to_vec()).unwrap().replace("example.com", "evil.com");
+        assert!(matches!(
+            verify_webauthn_assertion(&authenticator_data, other_client_data.as_bytes(), &signature, &cose_key),
+            Err(ECDSAError::WebAuthnSignatureMismatch)
+        ));
+        assert!(matches!(
+            verify_webauthn_assertion(&authenticator_data, CLIENT_DATA, &signature[..signature.len() - 1], &cose_key),
+            Err(ECDSAError::WebAuthnSignatureMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_assertions() {
+        // Registration client data and assertions without user presence
+        let create = String::from_utf8(CLIENT_DATA.to_vec()).unwrap().replace("webauthn.get", "webauthn.create");
+        let data = authenticator_data(0x05, 1);
+        let (cose_key, signature) = es256k_assertion(&data, create.as_bytes());
+        assert!(matches!(
+            verify_webauthn_assertion(&data, create.as_bytes(), &signature, &cose_key),
+            Err(ECDSAError::InvalidWebAuthnData)
+        ));
+        let data = authenticator_data(0x04, 1);
+        let (cose_key, signature) = es256k_assertion(&data, CLIENT_DATA);
+        assert!(matches!(
+            verify_webauthn_assertion(&data, CLIENT_DATA, &signature, &cose_key),
+            Err(ECDSAError::InvalidWebAuthnData)
+        ));
+
+        assert!(matches!(
+            verify_webauthn_assertion(&data[..36], CLIENT_DATA, &signature, &cose_key),
+            Err(ECDSAError::InvalidWebAuthnData)
+        ));
+        assert!(matches!(verify_webauthn_assertion(&data, b"{}", &signature, &cose_key), Err(ECDSAError::InvalidWebAuthnData)));
+        // An OKP (Ed25519) key and a key whose curve does not match its algorithm
+        for key in ["a4010103272006215820d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a", "a301020326200a"] {
+            let key = hex::decode(key).unwrap();
+            assert!(verify_webauthn_assertion(&data, CLIENT_DATA, &signature, &key).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_authenticator_data() {
+        let cose_key = es256k_cose_key();
+        let data = registration_data(&[7; 16], &cose_key);
+        let parsed = parse_authenticator_data(&data).unwrap();
+        let credential = parsed.attested_credential.unwrap();
+        assert_eq!(credential.aaguid, [7; 16]);
+        assert_eq!(credential.credential_id, [0xc0; 16]);
+        assert_eq!(credential.public_key, cose_key);
+
+        // Extensions follow the credential when ED is set
+        let mut with_extensions = [data.as_slice(), &hex::decode("a16b6372656450726f7465637401").unwrap()].concat();
+        assert!(parse_authenticator_data(&with_extensions).is_err());
+        with_extensions[32] |= FLAG_EXTENSIONS;
+        assert!(parse_authenticator_data(&with_extensions).unwrap().attested_credential.is_some());
+
+        // Trailing bytes, a truncated credential and a missing extensions map
+        let mut flagged = authenticator_data(0x81, 0);
+        for data in [[data.as_slice(), &[0]].concat(), data[..data.len() - 1].to_vec(), data[..60].to_vec(), flagged.clone()] {
+            assert!(matches!(parse_authenticator_data(&data), Err(ECDSAError::InvalidWebAuthnData)));
+        }
+        flagged.push(0xa0);
+        assert!(parse_authenticator_data(&flagged).unwrap().attested_credential.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_verify_packed_attestation() {
+        let data = registration_data(&(1..=16).collect::<Vec<u8>>(), &hex::decode(CREDENTIAL_KEY).unwrap());
+        let statement = |certificate: &str| {
+            vec![
+                ("alg", Value::Integer(-7)),
+                ("sig", Value::Bytes(hex::decode(PACKED_SIGNATURE).unwrap())),
+                ("x5c", Value::Array(vec![Value::Bytes(der(certificate))])),
+            ]
+        };
+        let object = attestation_object("packed", statement(PACKED_CERTIFICATE), &data);
+        let anchors = [der(ATTESTATION_ROOT)];
+
+        let registration = verify_attestation(&object, CREATE_CLIENT_DATA, &anchors, at("2030-01-01T00:00:00Z")).unwrap();
+        assert_eq!((registration.format().as_str(), registration.attestation_type().as_str()), ("packed", "basic"));
+        assert_eq!(registration.aaguid(), "0102030405060708090a0b0c0d0e0f10");
+        assert_eq!(registration.credential_id(), [0xc0; 16]);
+        assert_eq!(registration.credential_public_key(), hex::decode(CREDENTIAL_KEY).unwrap());
+        assert_eq!((registration.sign_count(), registration.flags()), (0, 0x45));
+        assert!(registration.user_verified() && !registration.backup_eligible());
+        assert_eq!(registration.challenge(), "cmVnaXN0ZXI");
+
+        assert!(matches!(
+            verify_attestation(&object, CREATE_CLIENT_DATA, &[] as &[Vec<u8>], at("2030-01-01T00:00:00Z")),
+            Err(ECDSAError::UntrustedCertificate)
+        ));
+        assert!(matches!(
+            verify_attestation(&object, CREATE_CLIENT_DATA, &anchors, at("2036-01-01T00:00:00Z")),
+            Err(ECDSAError::CertificateExpired)
+        ));
+        let other_client_data = String::from_utf8(CREATE_CLIENT_DATA.to_vec()).unwrap().replace("cmVnaXN0ZXI", "b3RoZXI");
+        assert!(matches!(
+            verify_attestation(&object, other_client_data.as_bytes(), &anchors, at("2030-01-01T00:00:00Z")),
+            Err(ECDSAError::WebAuthnSignatureMismatch)
+        ));
+
+        // The root is a CA without the attestation OU, and the AAGUID must match the certificate's
+        let object = attestation_object("packed", statement(ATTESTATION_ROOT), &data);
+        assert!(matches!(verify_attestation(&object, CREATE_CLIENT_DATA, &anchors, 0), Err(ECDSAError::InvalidAttestation)));
+        let data = registration_data(&[0; 16], &hex::decode(CREDENTIAL_KEY).unwrap());
+        let object = attestation_object("packed", statement(PACKED_CERTIFICATE), &data);
+        assert!(matches!(verify_attestation(&object, CREATE_CLIENT_DATA, &anchors, 0), Err(ECDSAError::InvalidAttestation)));
+    }
+
+    #[test]
+    #[cfg(feature = "p256")]
+    fn test_verify_apple_attestation() {
+        let data = registration_data(&(1..=16).collect::<Vec<u8>>(), &hex::decode(CREDENTIAL_KEY).unwrap());
+        let object = attestation_object("apple", vec![("x5c", Value::Array(vec![Value::Bytes(der(APPLE_CERTIFICATE))]))], &data);
+        let anchors = [der(ATTESTATION_ROOT)];
+        let registration = verify_attestation(&object, CREATE_CLIENT_DATA, &anchors, at("2030-01-01T00:00:00Z")).unwrap();
+        assert_eq!(registration.attestation_type(), "anonca");
+
+        // The nonce covers the client data, and the certificate must hold the credential key
+        let other_client_data = String::from_utf8(CREATE_CLIENT_DATA.to_vec()).unwrap().replace("cmVnaXN0ZXI", "b3RoZXI");
+        assert!(matches!(
+            verify_attestation(&object, other_client_data.as_bytes(), &anchors, at("2030-01-01T00:00:00Z")),
+            Err(ECDSAError::InvalidAttestation)
+        ));
+        let object = attestation_object("apple", vec![("x5c", Value::Array(vec![Value::Bytes(der(PACKED_CERTIFICATE))]))], &data);
+        assert!(matches!(verify_attestation(&object, CREATE_CLIENT_DATA, &anchors, 0), Err(ECDSAError::InvalidAttestation)));
+    }
+
+    #[test]
+    fn test_verify_self_and_none_attestation() {
+        let data = registration_data(&[0; 16], &es256k_cose_key());
+        let signature = es256k_sign(&data, CREATE_CLIENT_DATA);
+        let statement = |algorithm: i64| vec![("alg", Value::Integer(algorithm as i128)), ("sig", Value::Bytes(signature.clone()))];
+        let no_anchors: &[Vec<u8>] = &[];
+
+        let object = attestation_object("packed", statement(-47), &data);
+        let registration = verify_attestation(&object, CREATE_CLIENT_DATA, no_anchors, 0).unwrap();
+        assert_eq!(registration.attestation_type(), "self");
+        assert_eq!(registration.aaguid(), "0".repeat(32));
+        let object = attestation_object("none", vec![], &data);
+        assert_eq!(verify_attestation(&object, CREATE_CLIENT_DATA, no_anchors, 0).unwrap().attestation_type(), "none");
+
+        // The statement algorithm must be the credential's, and assertions are not registrations
+        let object = attestation_object("packed", statement(-7), &data);
+        assert!(verify_attestation(&object, CREATE_CLIENT_DATA, no_anchors, 0).is_err());
+        let object = attestation_object("none", statement(-47), &data);
+        assert!(matches!(verify_attestation(&object, CREATE_CLIENT_DATA, no_anchors, 0), Err(ECDSAError::InvalidAttestation)));
+        let object = attestation_object("tpm", vec![], &data);
+        assert!(matches!(
+            verify_attestation(&object, CREATE_CLIENT_DATA, no_anchors, 0),
+            Err(ECDSAError::UnsupportedAttestationFormat)
+        ));
+        let object = attestation_object("none", vec![], &data);
+        assert!(matches!(verify_attestation(&object, CLIENT_DATA, no_anchors, 0), Err(ECDSAError::InvalidWebAuthnData)));
+        let object = attestation_object("none", vec![], &authenticator_data(0x05, 0));
+        assert!(matches!(verify_attestation(&object, CREATE_CLIENT_DATA, no_anchors, 0), Err(ECDSAError::InvalidWebAuthnData)));
+    }
+}
@@ -0,0 +1,98 @@
+//! WebCrypto interop: moving public keys between `SubtleCrypto` `CryptoKey` objects and the
+//! crate's key types.
+//!
+//! Keys cross as DER SubjectPublicKeyInfo, which WebCrypto exports and imports for ECDSA and
+//! Ed25519 keys alike. WebCrypto has no secp256k1, so those keys stay on the wasm side.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use web_sys::{CryptoKey, SubtleCrypto};
+
+use crate::keys::asn1::{parse_spki, KeyAlgorithm};
+use crate::pem::ImportedPublicKey;
+use crate::ECDSAError;
+
+/// The `SubtleCrypto` of the current global object, in windows and workers alike.
+fn subtle() -> Result<SubtleCrypto, ECDSAError> {
+    let crypto = Reflect::get(&js_sys::global(), &"crypto".into()).map_err(|_| ECDSAError::WebCryptoUnavailable)?;
+    let subtle = Reflect::get(&crypto, &"subtle".into()).map_err(|_| ECDSAError::WebCryptoUnavailable)?;
+    subtle.dyn_into().map_err(|_| ECDSAError::WebCryptoUnavailable)
+}
+
+/// The WebCrypto algorithm name and, for ECDSA, the named curve of a key type.
+fn algorithm_parameters(algorithm: KeyAlgorithm) -> Result<(&'static str, Option<&'static str>), ECDSAError> {
+    match algorithm {
+        KeyAlgorithm::P256 | KeyAlgorithm::P384 | KeyAlgorithm::P521 => Ok(("ECDSA", Some(algorithm.name()))),
+        KeyAlgorithm::Ed25519 => Ok(("Ed25519", None)),
+        KeyAlgorithm::Secp256k1 => Err(ECDSAError::UnsupportedCurve),
+    }
+}
+
+/// Converts a WebCrypto public key into an `ImportedPublicKey`, exporting it as SPKI.
+///
+/// # Arguments:
+/// * `key` - A public `CryptoKey`, e.g. `keyPair.publicKey` from `generateKey` with ECDSA
+///   (P-256, P-384 or P-521, with the matching feature) or Ed25519.
+///
+/// # Returns:
+/// * A promise of the key's curve and encoded public key, ready for the verifier of its curve.
+///
+/// # Errors:
+/// * `InvalidCryptoKey` for private and secret keys, or if WebCrypto refuses the export.
+/// * `WebCryptoUnavailable` if the global object has no `crypto.subtle`.
+/// * `UnsupportedCurve` for curves whose feature is disabled.
+#[wasm_bindgen]
+pub async fn crypto_key_to_public_key(key: CryptoKey) -> Result<ImportedPublicKey, ECDSAError> {
+    if key.type_() != "public" {
+        return Err(ECDSAError::InvalidCryptoKey);
+    }
+    let exported = subtle()?.export_key("spki", &key).map_err(|_| ECDSAError::InvalidCryptoKey)?;
+    let spki = JsFuture::from(exported).await.map_err(|_| ECDSAError::InvalidCryptoKey)?;
+    ImportedPublicKey::from_spki(&Uint8Array::new(&spki).to_vec())
+}
+
+/// Imports a public key into WebCrypto as an extractable verification key.
+///
+/// # Arguments:
+/// * `spki` - The key as a DER SubjectPublicKeyInfo, such as `ImportedPublicKey.to_spki()`
+///   returns (`Uint8Array` in JS).
+///
+/// # Returns:
+/// * A promise of a `CryptoKey` with the `verify` usage, for `crypto.subtle.verify`.
+///
+/// # Errors:
+/// * `UnsupportedCurve` for secp256k1 keys, which WebCrypto does not support.
+/// * `InvalidCryptoKey` if WebCrypto rejects the key, e.g. Ed25519 in an older browser.
+/// * `WebCryptoUnavailable` if the global object has no `crypto.subtle`, besides the
+///   errors of `decode_spki`.
+#[wasm_bindgen]
+pub async fn public_key_to_crypto_key(spki: Vec<u8>) -> Result<CryptoKey, ECDSAError> {
+    let (name, named_curve) = algorithm_parameters(parse_spki(&spki)?.algorithm)?;
+    let parameters = Object::new();
+    let set = |key: &str, value: &str| Reflect::set(&parameters, &key.into(), &value.into()).map(|_| ());
+    set("name", name).map_err(|_| ECDSAError::InvalidCryptoKey)?;
+    if let Some(named_curve) = named_curve {
+        set("namedCurve", named_curve).map_err(|_| ECDSAError::InvalidCryptoKey)?;
+    }
+    let usages = Array::of1(&"verify".into());
+    let imported = subtle()?
+        .import_key_with_object("spki", &Uint8Array::from(spki.as_slice()), &parameters, true, &usages)
+        .map_err(|_| ECDSAError::InvalidCryptoKey)?;
+    let key = JsFuture::from(imported).await.map_err(|_| ECDSAError::InvalidCryptoKey)?;
+    Ok(key.unchecked_into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_algorithm_parameters() {
+        assert_eq!(algorithm_parameters(KeyAlgorithm::P256).unwrap(), ("ECDSA", Some("P-256")));
+        assert_eq!(algorithm_parameters(KeyAlgorithm::P521).unwrap(), ("ECDSA", Some("P-521")));
+        assert_eq!(algorithm_parameters(KeyAlgorithm::Ed25519).unwrap(), ("Ed25519", None));
+        assert!(matches!(algorithm_parameters(KeyAlgorithm::Secp256k1), Err(ECDSAError::UnsupportedCurve)));
+    }
+}
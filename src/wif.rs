@@ -0,0 +1,149 @@
+//! Wallet Import Format: Base58Check private keys as used by Bitcoin Core and legacy wallets.
+//!
+//! The payload is a version byte (0x80 mainnet, 0xef testnet), the 32-byte key and, for
+//! keys whose public key is used in compressed form, a trailing 0x01.
+
+use wasm_bindgen::prelude::*;
+use k256::ecdsa::SigningKey;
+
+use crate::encoding::{from_base58check, to_base58check};
+use crate::{ECDSAError, ECDSASigner};
+
+const MAINNET_VERSION: u8 = 0x80;
+const TESTNET_VERSION: u8 = 0xef;
+const COMPRESSED_FLAG: u8 = 0x01;
+
+/// A private key decoded from WIF, along with its encoding flags.
+#[wasm_bindgen]
+pub struct WifKey {
+    signing_key: SigningKey,
+    compressed: bool,
+    testnet: bool,
+}
+
+#[wasm_bindgen]
+impl WifKey {
+    /// Returns the 32-byte private key, as a hex string.
+    pub fn private_key(&self) -> String {
+        hex::encode(self.signing_key.to_bytes())
+    }
+
+    /// Whether the key's public key is meant to be used in compressed form.
+    #[wasm_bindgen(getter)]
+    pub fn compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// Whether the key carries the testnet version byte.
+    #[wasm_bindgen(getter)]
+    pub fn testnet(&self) -> bool {
+        self.testnet
+    }
+
+    /// Creates an `ECDSASigner` for this key.
+    pub fn signer(&self) -> ECDSASigner {
+        ECDSASigner::from_signing_key(self.signing_key.clone())
+    }
+}
+
+/// Encodes a private key in Wallet Import Format.
+///
+/// # Arguments:
+/// * `private_key` - The 32-byte private key, provided as a hex string.
+/// * `compressed` - Mark the key for compressed public keys. Defaults to true.
+/// * `testnet` - Use the testnet version byte. Defaults to false.
+///
+/// # Returns:
+/// * The WIF string (starting with `K`/`L`, or `5` when uncompressed; `c`/`9` on testnet).
+///
+/// # Errors:
+/// * Returns an error if the private key is not a valid secp256k1 scalar.
+#[wasm_bindgen]
+pub fn wif_encode(private_key: &str, compressed: Option<bool>, testnet: Option<bool>) -> Result<String, ECDSAError> {
+    let key_bytes = hex::decode(private_key).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+    if key_bytes.len() != 32 {
+        return Err(ECDSAError::InvalidPrivateKey);
+    }
+    SigningKey::from_bytes(&key_bytes).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+
+    let mut payload = vec![if testnet.unwrap_or(false) { TESTNET_VERSION } else { MAINNET_VERSION }];
+    payload.extend_from_slice(&key_bytes);
+    if compressed.unwrap_or(true) {
+        payload.push(COMPRESSED_FLAG);
+    }
+    Ok(to_base58check(&payload))
+}
+
+/// Decodes a Wallet Import Format private key.
+///
+/// # Arguments:
+/// * `wif` - The WIF string.
+///
+/// # Returns:
+/// * The decoded key; call `signer()` on it to sign.
+///
+/// # Errors:
+/// * Returns an error if the checksum, version byte, length or key is invalid.
+#[wasm_bindgen]
+pub fn wif_decode(wif: &str) -> Result<WifKey, ECDSAError> {
+    let payload = from_base58check(wif.trim()).ok_or(ECDSAError::InvalidPrivateKey)?;
+    let compressed = match payload.len() {
+        33 => false,
+        34 if payload[33] == COMPRESSED_FLAG => true,
+        _ => return Err(ECDSAError::InvalidPrivateKey),
+    };
+    let testnet = match payload[0] {
+        MAINNET_VERSION => false,
+        TESTNET_VERSION => true,
+        _ => return Err(ECDSAError::InvalidPrivateKey),
+    };
+
+    let signing_key = SigningKey::from_bytes(&payload[1..33]).map_err(|_| ECDSAError::InvalidPrivateKey)?;
+    Ok(WifKey { signing_key, compressed, testnet })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+    #[test]
+    fn test_wif_vectors() {
+        let key = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+        let uncompressed = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ";
+        assert_eq!(wif_encode(key, Some(false), None).unwrap(), uncompressed);
+
+        let decoded = wif_decode(uncompressed).unwrap();
+        assert_eq!(decoded.private_key(), key);
+        assert!(!decoded.compressed());
+        assert!(!decoded.testnet());
+
+        let compressed = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+        assert_eq!(wif_encode(ONE, None, None).unwrap(), compressed);
+        assert!(wif_decode(compressed).unwrap().compressed());
+    }
+
+    #[test]
+    fn test_testnet_round_trip() {
+        let wif = wif_encode(ONE, None, Some(true)).unwrap();
+        assert!(wif.starts_with('c'));
+        let decoded = wif_decode(&wif).unwrap();
+        assert!(decoded.testnet() && decoded.compressed());
+        assert_eq!(decoded.private_key(), ONE);
+        assert_eq!(
+            decoded.signer().public_key(),
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        assert!(wif_encode(&"00".repeat(32), None, None).is_err());
+        assert!(wif_encode("abcd", None, None).is_err());
+        // Corrupted checksum
+        assert!(wif_decode("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWo").is_err());
+        // Valid Base58Check, but a P2PKH address rather than a key
+        assert!(wif_decode("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH").is_err());
+    }
+}
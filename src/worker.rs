@@ -0,0 +1,193 @@
+//! A message protocol for running the verifier inside a Web Worker.
+//!
+//! The worker script (`js/worker.js`) passes each `MessageEvent.data` to
+//! `handle_worker_message` and posts the response back; `js/worker-client.js` wraps the
+//! exchange in promises on the main thread. Requests look like
+//!
+//! `{ id, op: "verify", message, signature, publicKey, hashAlgorithm? }`
+//! `{ id, op: "verifyBatch", items: [{ message, signature, publicKey }], hashAlgorithm? }`
+//! `{ id, op: "hash", data, hashAlgorithm? }`
+//!
+//! and responses `{ id, ok: true, result }` or `{ id, ok: false, error }`. Binary fields may be
+//! `Uint8Array`s or `ArrayBuffer`s, so callers can transfer rather than copy large payloads;
+//! strings are taken as UTF-8 for messages and as hex for signatures and keys.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use js_sys::{Array, ArrayBuffer, Object, Reflect, Uint8Array};
+
+use crate::batch::{items_from_js, result_to_js, BatchItem};
+use crate::hash::{digest, HashAlgorithm};
+use crate::{ECDSAError, ECDSAVerifier};
+
+/// A decoded worker request.
+enum WorkerRequest {
+    Verify { message: Vec<u8>, signature: Vec<u8>, public_key: Vec<u8>, hash_algorithm: HashAlgorithm },
+    VerifyBatch { items: Vec<BatchItem>, hash_algorithm: HashAlgorithm },
+    Hash { data: Vec<u8>, hash_algorithm: HashAlgorithm },
+}
+
+/// The outcome of a request, before conversion to JS.
+#[derive(Debug)]
+enum WorkerResult {
+    Valid(bool),
+    Batch(Vec<Result<bool, ECDSAError>>),
+    Digest(Vec<u8>),
+}
+
+/// Maps the numeric value of a JS `HashAlgorithm` back to the enum.
+fn hash_algorithm_from_index(index: f64) -> Result<HashAlgorithm, ECDSAError> {
+    const ALGORITHMS: [HashAlgorithm; 5] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Blake2b256,
+    ];
+    ALGORITHMS
+        .into_iter()
+        .find(|algorithm| *algorithm as u32 as f64 == index)
+        .ok_or(ECDSAError::InvalidWorkerRequest)
+}
+
+fn field(request: &JsValue, name: &str) -> Result<JsValue, ECDSAError> {
+    Reflect::get(request, &JsValue::from_str(name)).map_err(|_| ECDSAError::InvalidWorkerRequest)
+}
+
+/// Reads a binary field; strings are decoded with `decode_string`.
+fn bytes_field(
+    request: &JsValue,
+    name: &str,
+    decode_string: fn(&str) -> Result<Vec<u8>, ECDSAError>,
+) -> Result<Vec<u8>, ECDSAError> {
+    let value = field(request, name)?;
+    if let Some(text) = value.as_string() {
+        return decode_string(&text);
+    }
+    if let Some(buffer) = value.dyn_ref::<ArrayBuffer>() {
+        return Ok(Uint8Array::new(buffer).to_vec());
+    }
+    value.dyn_into::<Uint8Array>().map(|array| array.to_vec()).map_err(|_| ECDSAError::InvalidWorkerRequest)
+}
+
+fn utf8(text: &str) -> Result<Vec<u8>, ECDSAError> {
+    Ok(text.as_bytes().to_vec())
+}
+
+fn hex_bytes(text: &str) -> Result<Vec<u8>, ECDSAError> {
+    hex::decode(text).map_err(|_| ECDSAError::InvalidWorkerRequest)
+}
+
+impl WorkerRequest {
+    fn from_js(request: &JsValue) -> Result<WorkerRequest, ECDSAError> {
+        let hash_algorithm = match field(request, "hashAlgorithm")?.as_f64() {
+            Some(index) => hash_algorithm_from_index(index)?,
+            None => HashAlgorithm::default(),
+        };
+        let op = field(request, "op")?.as_string().ok_or(ECDSAError::InvalidWorkerRequest)?;
+        match op.as_str() {
+            "verify" => Ok(WorkerRequest::Verify {
+                message: bytes_field(request, "message", utf8)?,
+                signature: bytes_field(request, "signature", hex_bytes)?,
+                public_key: bytes_field(request, "publicKey", hex_bytes)?,
+                hash_algorithm,
+            }),
+            "verifyBatch" => Ok(WorkerRequest::VerifyBatch { items: items_from_js(&field(request, "items")?)?, hash_algorithm }),
+            "hash" => Ok(WorkerRequest::Hash { data: bytes_field(request, "data", utf8)?, hash_algorithm }),
+            _ => Err(ECDSAError::InvalidWorkerRequest),
+        }
+    }
+
+    fn execute(&self) -> Result<WorkerResult, ECDSAError> {
+        match self {
+            WorkerRequest::Verify { message, signature, public_key, hash_algorithm } => {
+                let verifier = ECDSAVerifier::new(Some(*hash_algorithm));
+                verifier.verify_signature_bytes(message, signature, public_key).map(WorkerResult::Valid)
+            }
+            WorkerRequest::VerifyBatch { items, hash_algorithm } => {
+                Ok(WorkerResult::Batch(ECDSAVerifier::new(Some(*hash_algorithm)).verify_items(items)))
+            }
+            WorkerRequest::Hash { data, hash_algorithm } => Ok(WorkerResult::Digest(digest(*hash_algorithm, data))),
+        }
+    }
+}
+
+impl WorkerResult {
+    fn to_js(&self) -> JsValue {
+        match self {
+            WorkerResult::Valid(valid) => JsValue::from_bool(*valid),
+            WorkerResult::Batch(results) => results.iter().map(result_to_js).collect::<Array>().into(),
+            WorkerResult::Digest(digest) => Uint8Array::from(digest.as_slice()).into(),
+        }
+    }
+}
+
+/// Handles one request from the main thread; call it from the worker's `onmessage`.
+///
+/// # Arguments:
+/// * `request` - The message data, `{ id, op, ... }` as described in the module docs.
+///
+/// # Returns:
+/// * `{ id, ok: true, result }`, or `{ id, ok: false, error }` if the request is malformed
+///   or fails. It never throws, so every request gets an answer. A `Uint8Array` result owns
+///   its buffer, which the worker can list as transferable when posting it back.
+#[wasm_bindgen]
+pub fn handle_worker_message(request: JsValue) -> JsValue {
+    let outcome = WorkerRequest::from_js(&request).and_then(|request| request.execute());
+    let response = Object::new();
+    let id = Reflect::get(&request, &JsValue::from_str("id")).unwrap_or(JsValue::UNDEFINED);
+    // Setting properties on a fresh plain object cannot fail
+    let _ = Reflect::set(&response, &JsValue::from_str("id"), &id);
+    let _ = Reflect::set(&response, &JsValue::from_str("ok"), &JsValue::from_bool(outcome.is_ok()));
+    let (key, value) = match outcome {
+        Ok(result) => ("result", result.to_js()),
+        Err(err) => ("error", JsValue::from_str(&err.to_string())),
+    };
+    let _ = Reflect::set(&response, &JsValue::from_str(key), &value);
+    response.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str = "3045022100bc1b3499f479213152e592781fefcc2c561ef288fc83295d413f72294abb269602207d329e96c9bae225bb5f8c20593e44feaf5398b7c9624f993a57cf8fc809d3b8";
+    const PUBLIC_KEY: &str = "032c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645";
+
+    #[test]
+    fn test_hash_algorithm_indices() {
+        assert_eq!(hash_algorithm_from_index(0.0).unwrap(), HashAlgorithm::Sha256);
+        assert_eq!(hash_algorithm_from_index(3.0).unwrap(), HashAlgorithm::Keccak256);
+        assert_eq!(hash_algorithm_from_index(HashAlgorithm::Blake2b256 as u32 as f64).unwrap(), HashAlgorithm::Blake2b256);
+        assert!(hash_algorithm_from_index(5.0).is_err());
+        assert!(hash_algorithm_from_index(0.5).is_err());
+    }
+
+    #[test]
+    fn test_execute_verify() {
+        let request = |message: &[u8]| WorkerRequest::Verify {
+            message: message.to_vec(),
+            signature: hex::decode(SIGNATURE).unwrap(),
+            public_key: hex::decode(PUBLIC_KEY).unwrap(),
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+        assert!(matches!(request(b"Test message").execute(), Ok(WorkerResult::Valid(true))));
+        assert!(matches!(request(b"Other message").execute(), Ok(WorkerResult::Valid(false))));
+    }
+
+    #[test]
+    fn test_execute_batch_and_hash() {
+        let item = |message: &str| BatchItem {
+            message: message.to_string(),
+            signature: SIGNATURE.to_string(),
+            public_key: PUBLIC_KEY.to_string(),
+        };
+        let batch = WorkerRequest::VerifyBatch { items: vec![item("Test message"), item("Other")], hash_algorithm: HashAlgorithm::Sha256 };
+        let Ok(WorkerResult::Batch(results)) = batch.execute() else { panic!("expected batch results") };
+        assert!(matches!(results[..], [Ok(true), Ok(false)]));
+
+        let hash = WorkerRequest::Hash { data: b"abc".to_vec(), hash_algorithm: HashAlgorithm::Sha256 };
+        let Ok(WorkerResult::Digest(digest)) = hash.execute() else { panic!("expected a digest") };
+        assert_eq!(hex::encode(digest), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+}
@@ -0,0 +1,92 @@
+//! X25519 Diffie-Hellman key agreement (RFC 7748), using x25519-dalek.
+
+use wasm_bindgen::prelude::*;
+use x25519_dalek::X25519_BASEPOINT_BYTES;
+
+use crate::ECDSAError;
+
+/// Computes the X25519 function: the u-coordinate of `scalar * u`.
+pub(crate) fn x25519_raw(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    x25519_dalek::x25519(*scalar, *u)
+}
+
+/// Decodes a 32-byte hex string.
+fn decode_key(key: &str, error: ECDSAError) -> Result<[u8; 32], ECDSAError> {
+    let bytes = hex::decode(key).map_err(|_| ECDSAError::InvalidPublicKey)?;
+    bytes.try_into().map_err(|_| error)
+}
+
+/// Derives the X25519 public key for a secret key.
+///
+/// # Arguments:
+/// * `secret_key` - The 32-byte secret key, provided as a hex string.
+///
+/// # Returns:
+/// * The 32-byte public key (a Curve25519 u-coordinate), as a hex string.
+#[wasm_bindgen]
+pub fn x25519_public_key(secret_key: &str) -> Result<String, ECDSAError> {
+    let secret_key = decode_key(secret_key, ECDSAError::InvalidPrivateKey)?;
+    Ok(hex::encode(x25519_raw(&secret_key, &X25519_BASEPOINT_BYTES)))
+}
+
+/// Computes an X25519 shared secret.
+///
+/// # Arguments:
+/// * `secret_key` - Our 32-byte secret key, provided as a hex string.
+/// * `peer_public_key` - The peer's 32-byte public key, provided as a hex string.
+///
+/// # Returns:
+/// * The 32-byte shared secret, as a hex string. Hash it (e.g. with HKDF) before use as a key.
+///
+/// # Errors:
+/// * Returns an error if a key is not 32 bytes, or the peer key is a low-order point
+///   that would force an all-zero shared secret.
+#[wasm_bindgen]
+pub fn x25519(secret_key: &str, peer_public_key: &str) -> Result<String, ECDSAError> {
+    let secret_key = decode_key(secret_key, ECDSAError::InvalidPrivateKey)?;
+    let peer_public_key = decode_key(peer_public_key, ECDSAError::InvalidPublicKey)?;
+
+    let shared = x25519_raw(&secret_key, &peer_public_key);
+    if shared == [0u8; 32] {
+        return Err(ECDSAError::InvalidPublicKey);
+    }
+    Ok(hex::encode(shared))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7748 section 6.1
+    const ALICE_SECRET: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+    const ALICE_PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+    const BOB_SECRET: &str = "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb";
+    const BOB_PUBLIC: &str = "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f";
+    const SHARED: &str = "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742";
+
+    #[test]
+    fn test_rfc7748_key_agreement() {
+        assert_eq!(x25519_public_key(ALICE_SECRET).unwrap(), ALICE_PUBLIC);
+        assert_eq!(x25519_public_key(BOB_SECRET).unwrap(), BOB_PUBLIC);
+        assert_eq!(x25519(ALICE_SECRET, BOB_PUBLIC).unwrap(), SHARED);
+        assert_eq!(x25519(BOB_SECRET, ALICE_PUBLIC).unwrap(), SHARED);
+    }
+
+    #[test]
+    fn test_rfc7748_function_vector() {
+        // Section 5.2; the u-coordinate has its top bit set, which must be ignored
+        let scalar = hex::decode("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4").unwrap();
+        let u = hex::decode("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c").unwrap();
+        assert_eq!(
+            hex::encode(x25519_raw(&scalar.try_into().unwrap(), &u.try_into().unwrap())),
+            "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"
+        );
+    }
+
+    #[test]
+    fn test_rejects_low_order_and_bad_lengths() {
+        assert!(x25519(ALICE_SECRET, &"00".repeat(32)).is_err());
+        assert!(x25519(ALICE_SECRET, &BOB_PUBLIC[..62]).is_err());
+        assert!(x25519_public_key("abcd").is_err());
+    }
+}
@@ -0,0 +1,747 @@
+//! X.509 certificates (RFC 5280): parsing the to-be-signed structure, checking the issuer's
+//! ECDSA signature over it, and validating a chain up to a trust anchor.
+//!
+//! Names are kept as their DER encodings and compared byte for byte, which matches how CAs
+//! copy the issuer's subject into the certificates they issue.
+
+use wasm_bindgen::prelude::*;
+use js_sys::Uint8Array;
+use sha2::{Digest, Sha256, Sha384};
+
+use crate::keys::asn1::{
+    parse_spki, Der, KeyAlgorithm, SubjectPublicKeyInfo, TAG_BIT_STRING, TAG_INTEGER, TAG_OCTET_STRING, TAG_OID, TAG_SEQUENCE,
+};
+use crate::keys::parse_public_key;
+use crate::signature::parse_der;
+use crate::time::{days_from_civil, now_millis, parse_rfc3339};
+use crate::{verify_digest, ECDSAError};
+
+/// `ecdsa-with-SHA256` (1.2.840.10045.4.3.2)
+const OID_ECDSA_SHA256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02];
+/// `ecdsa-with-SHA384` (1.2.840.10045.4.3.3)
+const OID_ECDSA_SHA384: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03];
+
+/// `keyUsage` (2.5.29.15)
+const OID_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x0f];
+/// `subjectAltName` (2.5.29.17)
+const OID_SUBJECT_ALT_NAME: &[u8] = &[0x55, 0x1d, 0x11];
+/// `basicConstraints` (2.5.29.19)
+const OID_BASIC_CONSTRAINTS: &[u8] = &[0x55, 0x1d, 0x13];
+/// `extKeyUsage` (2.5.29.37)
+const OID_EXTENDED_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x25];
+
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+
+/// The certificate signature algorithms that can be verified.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SignatureAlgorithm {
+    EcdsaSha256,
+    EcdsaSha384,
+}
+
+impl SignatureAlgorithm {
+    /// Reads an `AlgorithmIdentifier`; the ECDSA identifiers have no parameters (RFC 5758).
+    fn read(der: &mut Der) -> Result<SignatureAlgorithm, ECDSAError> {
+        let mut identifier = der.sequence()?;
+        let algorithm = match identifier.read(TAG_OID)? {
+            OID_ECDSA_SHA256 => SignatureAlgorithm::EcdsaSha256,
+            OID_ECDSA_SHA384 => SignatureAlgorithm::EcdsaSha384,
+            _ => return Err(ECDSAError::UnsupportedSignatureAlgorithm),
+        };
+        identifier.finish()?;
+        Ok(algorithm)
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            SignatureAlgorithm::EcdsaSha256 => Sha256::digest(data).to_vec(),
+            SignatureAlgorithm::EcdsaSha384 => Sha384::digest(data).to_vec(),
+        }
+    }
+}
+
+/// The `basicConstraints` extension.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BasicConstraints {
+    pub(crate) ca: bool,
+    /// The most non-self-issued intermediates that may follow this CA in a path
+    pub(crate) path_length: Option<u64>,
+}
+
+/// A parsed certificate, borrowing from its DER encoding.
+pub(crate) struct Certificate<'a> {
+    /// The complete `tbsCertificate` element, which is what the issuer signs
+    tbs: &'a [u8],
+    /// 0 for v1 up to 2 for v3
+    pub(crate) version: u64,
+    /// The DER encoding of the issuer name
+    pub(crate) issuer: &'a [u8],
+    /// Start of the validity period, in seconds since the Unix epoch
+    pub(crate) not_before: i64,
+    /// End of the validity period (inclusive), in seconds since the Unix epoch
+    pub(crate) not_after: i64,
+    /// The DER encoding of the subject name
+    pub(crate) subject: &'a [u8],
+    /// The complete `subjectPublicKeyInfo` element
+    pub(crate) spki: &'a [u8],
+    pub(crate) basic_constraints: Option<BasicConstraints>,
+    /// The `keyCertSign` bit of the key usage extension, if present
+    pub(crate) key_cert_sign: Option<bool>,
+    /// Whether a critical extension other than those named above was found
+    pub(crate) unknown_critical_extension: bool,
+    /// Every extension's identifier and value
+    extensions: Vec<(&'a [u8], &'a [u8])>,
+    signature_algorithm: SignatureAlgorithm,
+    signature: &'a [u8],
+}
+
+/// Parses a UTCTime or GeneralizedTime in the forms RFC 5280 allows: UTC, whole seconds.
+fn parse_time(tag: u8, content: &[u8]) -> Result<i64, ECDSAError> {
+    let (year, rest) = match (tag, content.len()) {
+        (TAG_UTC_TIME, 13) => {
+            // Two-digit years 50-99 are 19xx and 00-49 are 20xx
+            let year = digits(&content[..2])?;
+            (if year >= 50 { 1900 + year } else { 2000 + year }, &content[2..])
+        }
+        (TAG_GENERALIZED_TIME, 15) => (digits(&content[..4])?, &content[4..]),
+        _ => return Err(ECDSAError::InvalidCertificate),
+    };
+    if rest[10] != b'Z' {
+        return Err(ECDSAError::InvalidCertificate);
+    }
+    let (month, day) = (digits(&rest[0..2])?, digits(&rest[2..4])?);
+    let (hour, minute, second) = (digits(&rest[4..6])?, digits(&rest[6..8])?, digits(&rest[8..10])?);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = [31, if leap { 29 } else { 28 }, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    if !(1..=12).contains(&month) || !(1..=month_days[month as usize - 1]).contains(&day) {
+        return Err(ECDSAError::InvalidCertificate);
+    }
+    if hour > 23 || minute > 59 || second > 59 {
+        return Err(ECDSAError::InvalidCertificate);
+    }
+    Ok(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+fn digits(bytes: &[u8]) -> Result<i64, ECDSAError> {
+    bytes.iter().try_fold(0, |value, &c| {
+        if c.is_ascii_digit() {
+            Ok(value * 10 + (c - b'0') as i64)
+        } else {
+            Err(ECDSAError::InvalidCertificate)
+        }
+    })
+}
+
+fn read_time(der: &mut Der) -> Result<i64, ECDSAError> {
+    let (tag, content, _) = der.read_element()?;
+    parse_time(tag, content)
+}
+
+/// Reads a SEQUENCE and returns its complete encoding.
+fn read_sequence_element<'a>(der: &mut Der<'a>) -> Result<&'a [u8], ECDSAError> {
+    match der.read_element()? {
+        (TAG_SEQUENCE, _, element) => Ok(element),
+        _ => Err(ECDSAError::InvalidCertificate),
+    }
+}
+
+fn read_basic_constraints(value: &[u8]) -> Result<BasicConstraints, ECDSAError> {
+    let mut outer = Der::new(value);
+    let mut fields = outer.sequence()?;
+    outer.finish()?;
+    let ca = match fields.peek_tag() {
+        Some(TAG_BOOLEAN) => fields.read(TAG_BOOLEAN)? == [0xff],
+        _ => false,
+    };
+    let path_length = match fields.is_empty() {
+        true => None,
+        false => Some(fields.small_integer()?),
+    };
+    fields.finish()?;
+    Ok(BasicConstraints { ca, path_length })
+}
+
+fn read_key_cert_sign(value: &[u8]) -> Result<bool, ECDSAError> {
+    let mut outer = Der::new(value);
+    // Named bit lists drop trailing zero bits, so the unused-bits count is usually non-zero
+    let bits = outer.read(TAG_BIT_STRING)?;
+    outer.finish()?;
+    match bits {
+        [unused, ..] if *unused < 8 => Ok(bits.get(1).is_some_and(|byte| byte & 0x04 != 0)),
+        _ => Err(ECDSAError::InvalidCertificate),
+    }
+}
+
+fn read_certificate(der: &[u8]) -> Result<Certificate<'_>, ECDSAError> {
+    let mut outer = Der::new(der);
+    let mut certificate = outer.sequence()?;
+    outer.finish()?;
+
+    let tbs = read_sequence_element(&mut certificate)?;
+    let signature_algorithm = SignatureAlgorithm::read(&mut certificate)?;
+    let signature = certificate.bit_string()?;
+    certificate.finish()?;
+
+    let mut fields = Der::new(tbs).sequence()?;
+    let version = match fields.optional_explicit(0)? {
+        Some(mut wrapper) => {
+            // DER omits the default v1, so only v2 (1) and v3 (2) may appear
+            let version = wrapper.small_integer()?;
+            if !(1..=2).contains(&version) {
+                return Err(ECDSAError::InvalidCertificate);
+            }
+            wrapper.finish()?;
+            version
+        }
+        None => 0,
+    };
+    fields.read(TAG_INTEGER)?;
+    // The inner signature field must repeat the outer algorithm (RFC 5280 section 4.1.2.3)
+    if SignatureAlgorithm::read(&mut fields)? != signature_algorithm {
+        return Err(ECDSAError::InvalidCertificate);
+    }
+    let issuer = read_sequence_element(&mut fields)?;
+    let mut validity = fields.sequence()?;
+    let not_before = read_time(&mut validity)?;
+    let not_after = read_time(&mut validity)?;
+    validity.finish()?;
+    if not_after < not_before {
+        return Err(ECDSAError::InvalidCertificate);
+    }
+    let subject = read_sequence_element(&mut fields)?;
+    let spki = read_sequence_element(&mut fields)?;
+    // issuerUniqueID [1] and subjectUniqueID [2] are obsolete and skipped
+    for tag in [0x81, 0x82] {
+        if fields.peek_tag() == Some(tag) {
+            fields.read_element()?;
+        }
+    }
+    let mut basic_constraints = None;
+    let mut key_cert_sign = None;
+    let mut unknown_critical_extension = false;
+    let mut extensions = Vec::new();
+    if let Some(mut wrapper) = fields.optional_explicit(3)? {
+        let mut sequence = wrapper.sequence()?;
+        wrapper.finish()?;
+        while !sequence.is_empty() {
+            let mut extension = sequence.sequence()?;
+            let id = extension.read(TAG_OID)?;
+            let critical = match extension.peek_tag() {
+                Some(TAG_BOOLEAN) => extension.read(TAG_BOOLEAN)? == [0xff],
+                _ => false,
+            };
+            let value = extension.read(TAG_OCTET_STRING)?;
+            extension.finish()?;
+            // Each extension may appear only once (RFC 5280 section 4.2)
+            if extensions.iter().any(|&(seen, _)| seen == id) {
+                return Err(ECDSAError::InvalidCertificate);
+            }
+            extensions.push((id, value));
+            match id {
+                OID_BASIC_CONSTRAINTS => basic_constraints = Some(read_basic_constraints(value)?),
+                OID_KEY_USAGE => key_cert_sign = Some(read_key_cert_sign(value)?),
+                // Names and extended key usages are left to the application
+                OID_SUBJECT_ALT_NAME | OID_EXTENDED_KEY_USAGE => {}
+                _ => unknown_critical_extension |= critical,
+            }
+        }
+    }
+    fields.finish()?;
+
+    Ok(Certificate {
+        tbs,
+        version,
+        issuer,
+        not_before,
+        not_after,
+        subject,
+        spki,
+        basic_constraints,
+        key_cert_sign,
+        unknown_critical_extension,
+        extensions,
+        signature_algorithm,
+        signature,
+    })
+}
+
+/// Parses a DER certificate signed with ECDSA-with-SHA256 or ECDSA-with-SHA384.
+pub(crate) fn parse_certificate(der: &[u8]) -> Result<Certificate<'_>, ECDSAError> {
+    read_certificate(der).map_err(|error| match error {
+        ECDSAError::InvalidKeyEncoding => ECDSAError::InvalidCertificate,
+        error => error,
+    })
+}
+
+impl<'a> Certificate<'a> {
+    /// The value of the extension with the given identifier, if present.
+    pub(crate) fn extension(&self, id: &[u8]) -> Option<&'a [u8]> {
+        self.extensions.iter().find(|&&(extension, _)| extension == id).map(|&(_, value)| value)
+    }
+
+    /// The value of the first subject name attribute of the given type, such as `commonName`
+    /// (2.5.4.3). Attributes whose encoding cannot be read are skipped.
+    pub(crate) fn subject_attribute(&self, attribute_type: &[u8]) -> Option<&'a [u8]> {
+        let mut name = Der::new(self.subject).sequence().ok()?;
+        while let Ok(mut set) = name.read(0x31).map(Der::new) {
+            while let Ok(mut attribute) = set.sequence() {
+                if attribute.read(TAG_OID).ok()? == attribute_type {
+                    return attribute.read_element().ok().map(|(_, value, _)| value);
+                }
+            }
+        }
+        None
+    }
+
+    fn check_validity(&self, time: i64) -> Result<(), ECDSAError> {
+        if time < self.not_before {
+            return Err(ECDSAError::CertificateNotYetValid);
+        }
+        if time > self.not_after {
+            return Err(ECDSAError::CertificateExpired);
+        }
+        Ok(())
+    }
+
+    /// Checks that the certificate may issue certificates, with `below` non-self-issued
+    /// intermediates already beneath it in the path.
+    fn check_issuer(&self, below: u64) -> Result<(), ECDSAError> {
+        match self.basic_constraints {
+            Some(BasicConstraints { ca: true, path_length }) => {
+                if self.key_cert_sign == Some(false) {
+                    return Err(ECDSAError::InvalidCaCertificate);
+                }
+                if path_length.is_some_and(|length| below > length) {
+                    return Err(ECDSAError::PathLengthExceeded);
+                }
+                Ok(())
+            }
+            _ => Err(ECDSAError::InvalidCaCertificate),
+        }
+    }
+
+    /// Checks the certificate's signature against the issuer's public key.
+    ///
+    /// # Errors:
+    /// * `UnsupportedCurve` if the issuer key is not an ECDSA key this build can verify with.
+    pub(crate) fn verify_signature(&self, issuer: &SubjectPublicKeyInfo) -> Result<bool, ECDSAError> {
+        let digest = self.signature_algorithm.digest(self.tbs);
+        match issuer.algorithm {
+            KeyAlgorithm::Secp256k1 => {
+                let key = parse_public_key(issuer.public_key)?;
+                let Ok(sig) = parse_der(self.signature) else {
+                    return Ok(false);
+                };
+                // X.509 signers are not required to produce low-S signatures
+                Ok(verify_digest(&key, &digest, &sig.normalize_s().unwrap_or(sig)))
+            }
+            #[cfg(feature = "p256")]
+            KeyAlgorithm::P256 => verify_or_false(crate::p256::verify_bytes(&digest, self.signature, issuer.public_key)),
+            #[cfg(feature = "p384")]
+            KeyAlgorithm::P384 => verify_or_false(crate::p384::verify_bytes(&digest, self.signature, issuer.public_key)),
+            #[cfg(feature = "p521")]
+            KeyAlgorithm::P521 => verify_or_false(crate::p521::verify_bytes(&digest, self.signature, issuer.public_key)),
+            _ => Err(ECDSAError::UnsupportedCurve),
+        }
+    }
+}
+
+/// A malformed signature is a verification failure rather than an error.
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521"))]
+fn verify_or_false(result: Result<bool, ECDSAError>) -> Result<bool, ECDSAError> {
+    match result {
+        Err(ECDSAError::InvalidSignatureFormat) => Ok(false),
+        result => result,
+    }
+}
+
+/// Verifies that an X.509 certificate was signed by the holder of `issuer_spki`.
+///
+/// Only the signature is checked; validity dates, names and extensions are not.
+///
+/// # Arguments:
+/// * `cert_der` - The DER encoded certificate (`Uint8Array` in JS).
+/// * `issuer_spki` - The issuer's DER SubjectPublicKeyInfo, e.g. from WebCrypto's
+///   `exportKey("spki")`. The key may be on secp256k1 or, if enabled, P-256, P-384 or P-521.
+///
+/// # Returns:
+/// * A boolean indicating whether the ECDSA-with-SHA256 or ECDSA-with-SHA384 signature is valid.
+///
+/// # Errors:
+/// * `InvalidCertificate` if the certificate is malformed.
+/// * `UnsupportedSignatureAlgorithm` for signature algorithms other than ECDSA-with-SHA256/384.
+/// * `UnsupportedCurve` or `InvalidKeyEncoding` if the issuer key cannot be used.
+#[wasm_bindgen]
+pub fn verify_certificate(cert_der: &[u8], issuer_spki: &[u8]) -> Result<bool, ECDSAError> {
+    let certificate = parse_certificate(cert_der)?;
+    certificate.verify_signature(&parse_spki(issuer_spki)?)
+}
+
+fn parse_all<C: AsRef<[u8]>>(certificates: &[C]) -> Result<Vec<Certificate<'_>>, ECDSAError> {
+    certificates.iter().map(|der| parse_certificate(der.as_ref())).collect()
+}
+
+/// Builds a path from `leaf` through `intermediates` to one of `trust_anchors` and checks it
+/// at `time`, in seconds since the Unix epoch.
+///
+/// Each step takes the first certificate whose subject matches the issuer name and whose key
+/// verifies the signature. Trust anchors only supply a name and a key: their own signature,
+/// validity and constraints are not checked.
+pub(crate) fn validate_chain<I: AsRef<[u8]>, A: AsRef<[u8]>>(
+    leaf: &[u8],
+    intermediates: &[I],
+    trust_anchors: &[A],
+    time: i64,
+) -> Result<(), ECDSAError> {
+    let intermediates = parse_all(intermediates)?;
+    let trust_anchors = parse_all(trust_anchors)?;
+
+    let leaf = parse_certificate(leaf)?;
+    let mut current = &leaf;
+    let mut below = 0;
+    let mut used = vec![false; intermediates.len()];
+    loop {
+        if current.unknown_critical_extension {
+            return Err(ECDSAError::UnsupportedCriticalExtension);
+        }
+        current.check_validity(time)?;
+
+        for anchor in trust_anchors.iter().filter(|anchor| anchor.subject == current.issuer) {
+            if current.verify_signature(&parse_spki(anchor.spki)?)? {
+                return Ok(());
+            }
+        }
+
+        let mut issuer = None;
+        for (index, candidate) in intermediates.iter().enumerate() {
+            if !used[index] && candidate.subject == current.issuer && current.verify_signature(&parse_spki(candidate.spki)?)? {
+                issuer = Some(index);
+                break;
+            }
+        }
+        // Each intermediate is used at most once, which also bounds the loop
+        let Some(index) = issuer else {
+            return Err(ECDSAError::UntrustedCertificate);
+        };
+        used[index] = true;
+        let next = &intermediates[index];
+        next.check_issuer(below)?;
+        if next.subject != next.issuer {
+            below += 1;
+        }
+        current = next;
+    }
+}
+
+/// Validates an X.509 certificate chain: signatures from the leaf up to a trust anchor,
+/// CA basic constraints and path lengths, and every certificate's validity period.
+///
+/// # Arguments:
+/// * `leaf` - The DER encoded end-entity certificate (`Uint8Array` in JS).
+/// * `intermediates` - DER intermediate certificates, in any order (an array of `Uint8Array`).
+/// * `trust_anchors` - DER root certificates to trust (an array of `Uint8Array`). Only their
+///   names and keys are used.
+/// * `time` - An optional RFC 3339 timestamp to validate at, defaulting to the current time.
+///
+/// # Errors:
+/// * `UntrustedCertificate` if no path to a trust anchor can be built.
+/// * `CertificateExpired` or `CertificateNotYetValid` if a certificate on the path is not valid at `time`.
+/// * `InvalidCaCertificate` or `PathLengthExceeded` if an intermediate may not issue the certificate below it.
+/// * `UnsupportedCriticalExtension` if a certificate has a critical extension that is not understood.
+/// * `InvalidCertificate` and the errors of `verify_certificate` for malformed certificates or `time`.
+#[wasm_bindgen]
+pub fn verify_chain(
+    leaf: &[u8],
+    intermediates: Vec<Uint8Array>,
+    trust_anchors: Vec<Uint8Array>,
+    time: Option<String>,
+) -> Result<(), ECDSAError> {
+    let time = match time {
+        Some(time) => parse_rfc3339(&time).ok_or(ECDSAError::InvalidCertificate)?,
+        None => now_millis(),
+    };
+    let to_vec = |certificates: Vec<Uint8Array>| certificates.iter().map(Uint8Array::to_vec).collect::<Vec<_>>();
+    validate_chain(leaf, &to_vec(intermediates), &to_vec(trust_anchors), time.div_euclid(1000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pem::decode_pem;
+    use crate::signer::sign_digest;
+    use k256::ecdsa::SigningKey;
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+    // A P-256 root, signing a secp256k1 intermediate with SHA-384, signing a P-256 leaf with SHA-256
+    const ROOT: &str = "-----BEGIN CERTIFICATE-----
+MIIBgzCCASmgAwIBAgIUTA8okesCL6c7rOs7Zv30v+lKldAwCgYIKoZIzj0EAwIw
+DzENMAsGA1UEAwwEUm9vdDAeFw0yNjEwMTUwMzAwMTJaFw0zNjEwMTIwMzAwMTJa
+MA8xDTALBgNVBAMMBFJvb3QwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASF2VBu
+zPJK8ZSDF6KbhpW57FXprmiKIX3k+nxei/siAevQaSytLB+YpcgquJT4J+eM2Lbz
+rjbT/TJld3Gix1hEo2MwYTAdBgNVHQ4EFgQU2wOIzapeXSQrszg+FC05bBNS8ugw
+HwYDVR0jBBgwFoAU2wOIzapeXSQrszg+FC05bBNS8ugwDwYDVR0TAQH/BAUwAwEB
+/zAOBgNVHQ8BAf8EBAMCAgQwCgYIKoZIzj0EAwIDSAAwRQIhAJ5PvKEweSd2MyWh
+bNnQvfxPXijOtYcNxKQWRZ1TbUg8AiBUG0BaHoooVz+GaVHaSRKsGsxlusB8tyNx
+N/bV0twa9g==
+-----END CERTIFICATE-----";
+    const INTERMEDIATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBeDCCAR6gAwIBAgIBAjAKBggqhkjOPQQDAzAPMQ0wCwYDVQQDDARSb290MB4X
+DTI2MTAxNTAzMDAxMloXDTM2MTAxMjAzMDAxMlowFzEVMBMGA1UEAwwMSW50ZXJt
+ZWRpYXRlMFYwEAYHKoZIzj0CAQYFK4EEAAoDQgAEYK+C62CpA3qv5pmhzhOxbPZu
+wP7v5hjIhkh+sR6nwrlAC19dBluGXHiuwOfadrKq3kLV++liBTY6Ya7ntK97P6Nm
+MGQwEgYDVR0TAQH/BAgwBgEB/wIBADAOBgNVHQ8BAf8EBAMCAgQwHQYDVR0OBBYE
+FFBufSHND78vBPUDgOyJZn0zzcuYMB8GA1UdIwQYMBaAFNsDiM2qXl0kK7M4PhQt
+OWwTUvLoMAoGCCqGSM49BAMDA0gAMEUCIQDzRG/jYwiakyIY6BsP2HKl8PptfB24
+fNHK/LBoWpfnlwIgVb727aT3cL9UHslCTAa3h3wVV2WeUM9QGXe1rLBVGKo=
+-----END CERTIFICATE-----";
+    const LEAF: &str = "-----BEGIN CERTIFICATE-----
+MIIBZTCCAQugAwIBAgIBAzAKBggqhkjOPQQDAjAXMRUwEwYDVQQDDAxJbnRlcm1l
+ZGlhdGUwHhcNMjYxMDE1MDMwMDEzWhcNMjcxMDE1MDMwMDEzWjAPMQ0wCwYDVQQD
+DARMZWFmMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEpW64hbuP2qxQhP4Gpb7W
+Iqqrdke46j6/9VwFKa//3MFOk5lE6V//wtpGjJ4Zab4cRrapPzmJMghxrDQ57Cq/
+/6NQME4wDAYDVR0TAQH/BAIwADAdBgNVHQ4EFgQU8H52ElHzbrRGzDHh0bPrh33w
+hJIwHwYDVR0jBBgwFoAUUG59Ic0Pvy8E9QOA7IlmfTPNy5gwCgYIKoZIzj0EAwID
+SAAwRQIgNLoZJk1J8drlCCmwCKetIzWVauIuD5C6M14qv6pyNkgCIQDe4Bz0rEyJ
+0H4ozqAi8W2QAOhf0TDbIPZCQgzlbxsg8w==
+-----END CERTIFICATE-----";
+
+    const ROOT_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEhdlQbszySvGUgxeim4aVuexV6a5o
+iiF95Pp8Xov7IgHr0GksrSwfmKXIKriU+CfnjNi286420/0yZXdxosdYRA==
+-----END PUBLIC KEY-----";
+    const INTERMEDIATE_KEY: &str = "-----BEGIN PUBLIC KEY-----
+MFYwEAYHKoZIzj0CAQYFK4EEAAoDQgAEYK+C62CpA3qv5pmhzhOxbPZuwP7v5hjI
+hkh+sR6nwrlAC19dBluGXHiuwOfadrKq3kLV++liBTY6Ya7ntK97Pw==
+-----END PUBLIC KEY-----";
+
+    fn der(pem: &str) -> Vec<u8> {
+        decode_pem(pem, &["CERTIFICATE", "PUBLIC KEY"]).unwrap().1
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        for (pem, algorithm) in [
+            (ROOT, SignatureAlgorithm::EcdsaSha256),
+            (INTERMEDIATE, SignatureAlgorithm::EcdsaSha384),
+            (LEAF, SignatureAlgorithm::EcdsaSha256),
+        ] {
+            let der = der(pem);
+            let certificate = parse_certificate(&der).unwrap();
+            assert_eq!(certificate.signature_algorithm, algorithm);
+            assert_eq!(certificate.tbs, &der[4..der.len() - 12 - 3 - certificate.signature.len()]);
+        }
+
+        let (intermediate, leaf) = (der(INTERMEDIATE), der(LEAF));
+        let intermediate = parse_certificate(&intermediate).unwrap();
+        // 2026-10-15T03:00:12Z to 2036-10-12T03:00:12Z
+        assert_eq!((intermediate.not_before, intermediate.not_after), (1_792_033_212, 2_107_393_212));
+        assert_eq!(intermediate.basic_constraints, Some(BasicConstraints { ca: true, path_length: Some(0) }));
+        assert_eq!(intermediate.key_cert_sign, Some(true));
+        let leaf = parse_certificate(&leaf).unwrap();
+        assert_eq!(leaf.issuer, intermediate.subject);
+        assert_eq!(leaf.basic_constraints, Some(BasicConstraints { ca: false, path_length: None }));
+        assert_eq!(leaf.key_cert_sign, None);
+    }
+
+    #[test]
+    fn test_verify_certificate() {
+        let (intermediate, leaf) = (der(INTERMEDIATE), der(LEAF));
+        let intermediate_spki = der(INTERMEDIATE_KEY);
+
+        // A secp256k1 issuer over SHA-256
+        assert!(verify_certificate(&leaf, &intermediate_spki).unwrap());
+        assert!(!verify_certificate(&intermediate, &intermediate_spki).unwrap());
+
+        // Changing a byte of the subject breaks the signature
+        let mut tampered = leaf.clone();
+        let position = tampered.windows(4).position(|w| w == b"Leaf").unwrap();
+        tampered[position] = b'l';
+        assert!(!verify_certificate(&tampered, &intermediate_spki).unwrap());
+
+        // P-256 issuers: self-signed over SHA-256 and the intermediate over SHA-384
+        let root_spki = der(ROOT_KEY);
+        #[cfg(feature = "p256")]
+        {
+            assert!(verify_certificate(&der(ROOT), &root_spki).unwrap());
+            assert!(verify_certificate(&intermediate, &root_spki).unwrap());
+            assert!(!verify_certificate(&leaf, &root_spki).unwrap());
+        }
+        #[cfg(not(feature = "p256"))]
+        assert!(matches!(verify_certificate(&intermediate, &root_spki), Err(ECDSAError::UnsupportedCurve)));
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time(TAG_UTC_TIME, b"700101000000Z").unwrap(), 0);
+        assert_eq!(parse_time(TAG_UTC_TIME, b"491231235959Z").unwrap(), 2_524_607_999);
+        assert_eq!(parse_time(TAG_GENERALIZED_TIME, b"20500101000000Z").unwrap(), 2_524_608_000);
+        for (tag, time) in [
+            (TAG_UTC_TIME, &b"700101000000"[..]),
+            (TAG_UTC_TIME, b"700101000000+0100"),
+            (TAG_UTC_TIME, b"700230000000Z"),
+            (TAG_GENERALIZED_TIME, b"700101000000Z"),
+            (TAG_GENERALIZED_TIME, b"19700101000000.5Z"),
+        ] {
+            assert!(matches!(parse_time(tag, time), Err(ECDSAError::InvalidCertificate)));
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_certificates() {
+        let leaf = der(LEAF);
+        assert!(matches!(verify_certificate(&leaf[..leaf.len() - 1], &[]), Err(ECDSAError::InvalidCertificate)));
+
+        // ecdsa-with-SHA512 in both algorithm identifiers
+        let mut sha512 = leaf.clone();
+        for _ in 0..2 {
+            let position = sha512.windows(OID_ECDSA_SHA256.len()).position(|w| w == OID_ECDSA_SHA256).unwrap();
+            sha512[position + 7] = 0x04;
+        }
+        assert!(matches!(parse_certificate(&sha512), Err(ECDSAError::UnsupportedSignatureAlgorithm)));
+
+        // The outer algorithm must match the one inside the signed part
+        let mut mismatched = leaf.clone();
+        let position = mismatched.windows(OID_ECDSA_SHA256.len()).rposition(|w| w == OID_ECDSA_SHA256).unwrap();
+        mismatched[position + 7] = 0x03;
+        assert!(matches!(parse_certificate(&mismatched), Err(ECDSAError::InvalidCertificate)));
+
+        // Ed25519 issuer keys cannot check ECDSA signatures
+        let ed25519 = hex::decode("302a300506032b6570032100d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a").unwrap();
+        assert!(matches!(verify_certificate(&leaf, &ed25519), Err(ECDSAError::UnsupportedCurve)));
+    }
+
+    fn at(time: &str) -> i64 {
+        parse_rfc3339(time).unwrap() / 1000
+    }
+
+    fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut encoding = vec![tag];
+        match content.len() {
+            length @ 0..=0x7f => encoding.push(length as u8),
+            length @ 0x80..=0xff => encoding.extend([0x81, length as u8]),
+            length => encoding.extend([0x82, (length >> 8) as u8, length as u8]),
+        }
+        encoding.extend_from_slice(content);
+        encoding
+    }
+
+    fn extension(id: &[u8], critical: bool, value: &[u8]) -> Vec<u8> {
+        let critical = if critical { tlv(TAG_BOOLEAN, &[0xff]) } else { Vec::new() };
+        tlv(TAG_SEQUENCE, &[tlv(TAG_OID, id), critical, tlv(TAG_OCTET_STRING, value)].concat())
+    }
+
+    fn ca(path_length: Option<u8>) -> Vec<u8> {
+        let path_length = path_length.map(|length| tlv(TAG_INTEGER, &[length])).unwrap_or_default();
+        extension(OID_BASIC_CONSTRAINTS, true, &tlv(TAG_SEQUENCE, &[tlv(TAG_BOOLEAN, &[0xff]), path_length].concat()))
+    }
+
+    /// Issues a secp256k1 certificate valid from 2025 to 2034, signed with ECDSA-with-SHA256.
+    fn issue(subject: &str, subject_key: u8, issuer: &str, issuer_key: u8, extensions: &[Vec<u8>]) -> Vec<u8> {
+        let name = |common_name: &str| {
+            let attribute = [tlv(TAG_OID, &[0x55, 0x04, 0x03]), tlv(0x0c, common_name.as_bytes())].concat();
+            tlv(TAG_SEQUENCE, &tlv(0x31, &tlv(TAG_SEQUENCE, &attribute)))
+        };
+        let key = |byte: u8| SigningKey::from_bytes(&[byte; 32]).unwrap();
+        let point = key(subject_key).verifying_key().to_encoded_point(false);
+        let key_algorithm = [tlv(TAG_OID, &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01]), tlv(TAG_OID, &[0x2b, 0x81, 0x04, 0x00, 0x0a])];
+        let spki = [tlv(TAG_SEQUENCE, &key_algorithm.concat()), tlv(TAG_BIT_STRING, &[&[0], point.as_bytes()].concat())];
+        let validity = [tlv(TAG_UTC_TIME, b"250101000000Z"), tlv(TAG_UTC_TIME, b"341231235959Z")];
+        let algorithm = tlv(TAG_SEQUENCE, &tlv(TAG_OID, OID_ECDSA_SHA256));
+
+        let mut tbs = [
+            tlv(0xa0, &tlv(TAG_INTEGER, &[2])),
+            tlv(TAG_INTEGER, &[subject_key]),
+            algorithm.clone(),
+            name(issuer),
+            tlv(TAG_SEQUENCE, &validity.concat()),
+            name(subject),
+            tlv(TAG_SEQUENCE, &spki.concat()),
+        ]
+        .concat();
+        if !extensions.is_empty() {
+            tbs.extend(tlv(0xa3, &tlv(TAG_SEQUENCE, &extensions.concat())));
+        }
+        let tbs = tlv(TAG_SEQUENCE, &tbs);
+        let (sig, _) = sign_digest(&key(issuer_key), &Sha256::digest(&tbs), &[]).unwrap();
+        let signature = tlv(TAG_BIT_STRING, &[&[0], sig.to_der().as_bytes()].concat());
+        tlv(TAG_SEQUENCE, &[tbs, algorithm, signature].concat())
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let (intermediate, leaf) = (der(INTERMEDIATE), der(LEAF));
+        let (intermediate, leaf) = (intermediate.as_slice(), leaf.as_slice());
+        let no_certificates: [&[u8]; 0] = [];
+
+        // Any certificate can be a trust anchor; only its name and key are used
+        assert!(validate_chain(leaf, &no_certificates, &[intermediate], at("2027-01-01T00:00:00Z")).is_ok());
+        assert!(matches!(
+            validate_chain(leaf, &no_certificates, &[intermediate], at("2027-10-16T00:00:00Z")),
+            Err(ECDSAError::CertificateExpired)
+        ));
+        assert!(matches!(
+            validate_chain(leaf, &no_certificates, &[intermediate], at("2026-10-15T03:00:12Z")),
+            Err(ECDSAError::CertificateNotYetValid)
+        ));
+        assert!(matches!(
+            validate_chain(intermediate, &no_certificates, &[leaf], at("2027-01-01T00:00:00Z")),
+            Err(ECDSAError::UntrustedCertificate)
+        ));
+
+        #[cfg(feature = "p256")]
+        {
+            let root = der(ROOT);
+            let root = root.as_slice();
+            let time = at("2027-01-01T00:00:00Z");
+            assert!(validate_chain(leaf, &[intermediate], &[root], time).is_ok());
+            assert!(validate_chain(leaf, &[root, intermediate], &[root], time).is_ok());
+            assert!(matches!(
+                validate_chain(leaf, &no_certificates, &[root], time),
+                Err(ECDSAError::UntrustedCertificate)
+            ));
+            // The intermediate's validity period is checked too
+            assert!(matches!(
+                validate_chain(leaf, &[intermediate], &[root], at("2036-10-13T00:00:00Z")),
+                Err(ECDSAError::CertificateExpired)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_chain_constraints() {
+        let time = at("2030-01-01T00:00:00Z");
+        let trust_anchors = [issue("Root", 1, "Root", 1, &[ca(None)])];
+        let check = |leaf: &[u8], intermediates: &[Vec<u8>]| validate_chain(leaf, intermediates, &trust_anchors, time);
+
+        // Intermediates need basicConstraints with cA set, and keyCertSign if key usage is present
+        let leaf = issue("Leaf", 3, "CA", 2, &[]);
+        assert!(check(&leaf, &[issue("CA", 2, "Root", 1, &[ca(None)])]).is_ok());
+        for extensions in [
+            vec![],
+            vec![extension(OID_BASIC_CONSTRAINTS, true, &tlv(TAG_SEQUENCE, &[]))],
+            vec![ca(None), extension(OID_KEY_USAGE, true, &tlv(TAG_BIT_STRING, &[7, 0x80]))],
+        ] {
+            let intermediate = issue("CA", 2, "Root", 1, &extensions);
+            assert!(matches!(check(&leaf, &[intermediate]), Err(ECDSAError::InvalidCaCertificate)));
+        }
+
+        // A path length of 0 allows no further intermediates below
+        let leaf = issue("Leaf", 4, "Sub CA", 3, &[]);
+        let sub_ca = issue("Sub CA", 3, "CA", 2, &[ca(None)]);
+        let intermediates = [sub_ca.clone(), issue("CA", 2, "Root", 1, &[ca(Some(1))])];
+        assert!(check(&leaf, &intermediates).is_ok());
+        let intermediates = [sub_ca, issue("CA", 2, "Root", 1, &[ca(Some(0))])];
+        assert!(matches!(check(&leaf, &intermediates), Err(ECDSAError::PathLengthExceeded)));
+
+        // Unknown extensions are only fatal when critical
+        let intermediates = [issue("CA", 2, "Root", 1, &[ca(None)])];
+        let unknown = [0x2b, 0x06, 0x01, 0x04, 0x01];
+        assert!(check(&issue("Leaf", 3, "CA", 2, &[extension(&unknown, false, &[0x05, 0x00])]), &intermediates).is_ok());
+        assert!(matches!(
+            check(&issue("Leaf", 3, "CA", 2, &[extension(&unknown, true, &[0x05, 0x00])]), &intermediates),
+            Err(ECDSAError::UnsupportedCriticalExtension)
+        ));
+    }
+}